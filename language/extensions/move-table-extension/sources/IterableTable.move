@@ -0,0 +1,247 @@
+/// A table whose entries can be enumerated, e.g. to export or migrate the data of a table.
+///
+/// The entries form a doubly linked list in insertion order: each entry stores the keys of its
+/// neighbors next to its value, and the table stores the keys of the first and the last entry.
+/// Iteration therefore needs no storage besides the entries themselves, and adding or removing
+/// an entry touches at most its two neighbors. Iteration is cursor based: the key of an entry
+/// is the cursor from which to continue, which allows to read a large table page by page, across
+/// multiple transactions:
+///
+/// ```
+/// let (keys, cursor) = iterable_table::keys(&t, option::none(), 100);
+/// while (option::is_some(&cursor)) {
+///     ...
+///     (keys, cursor) = iterable_table::keys(&t, cursor, 100);
+/// }
+/// ```
+module extensions::iterable_table {
+    use std::errors;
+    use std::option::{Self, Option};
+    use std::vector;
+    use extensions::table::{Self, Table};
+
+    const ENOT_EMPTY: u64 = 102;
+    const ELENGTH_MISMATCH: u64 = 103;
+
+    /// The value of an entry, together with the keys of the previous and the next entry.
+    struct IterableValue<K: copy + store + drop, V: store> has store {
+        val: V,
+        prev: Option<K>,
+        next: Option<K>,
+    }
+
+    /// Type of iterable tables
+    struct IterableTable<K: copy + store + drop, V: store> has store {
+        inner: Table<K, IterableValue<K, V>>,
+        head: Option<K>,
+        tail: Option<K>,
+    }
+
+    /// Create a new IterableTable.
+    public fun new<K: copy + store + drop, V: store>(): IterableTable<K, V> {
+        IterableTable{
+            inner: table::new(),
+            head: option::none(),
+            tail: option::none(),
+        }
+    }
+
+    /// Destroy a table. The table must be empty to succeed.
+    public fun destroy_empty<K: copy + store + drop, V: store>(table: IterableTable<K, V>) {
+        assert!(empty(&table), errors::invalid_state(ENOT_EMPTY));
+        let IterableTable{ inner, head: _, tail: _ } = table;
+        table::destroy_empty(inner)
+    }
+
+    /// Add a new entry after the last entry of the table. Aborts if an
+    /// entry for this key already exists.
+    public fun add<K: copy + store + drop, V: store>(table: &mut IterableTable<K, V>, key: K, val: V) {
+        let wrapped_value = IterableValue{
+            val,
+            prev: table.tail,
+            next: option::none(),
+        };
+        table::add(&mut table.inner, key, wrapped_value);
+        if (option::is_some(&table.tail)) {
+            let k = *option::borrow(&table.tail);
+            table::borrow_mut(&mut table.inner, k).next = option::some(key);
+        } else {
+            table.head = option::some(key);
+        };
+        table.tail = option::some(key);
+    }
+
+    /// Remove from `table` and return the value which `key` maps to.
+    /// Aborts if there is no entry for `key`.
+    public fun remove<K: copy + store + drop, V: store>(table: &mut IterableTable<K, V>, key: K): V {
+        let (val, _, _) = remove_iter(table, key);
+        val
+    }
+
+    /// Acquire an immutable reference to the value which `key` maps to.
+    /// Aborts if there is no entry for `key`.
+    public fun borrow<K: copy + store + drop, V: store>(table: &IterableTable<K, V>, key: K): &V {
+        &table::borrow(&table.inner, key).val
+    }
+
+    /// Acquire a mutable reference to the value which `key` maps to.
+    /// Aborts if there is no entry for `key`.
+    public fun borrow_mut<K: copy + store + drop, V: store>(table: &mut IterableTable<K, V>, key: K): &mut V {
+        &mut table::borrow_mut(&mut table.inner, key).val
+    }
+
+    /// Acquire a mutable reference to the value which `key` maps to.
+    /// Insert the pair (`key`, `default`) first if there is no entry for `key`.
+    public fun borrow_mut_with_default<K: copy + store + drop, V: store + drop>(
+        table: &mut IterableTable<K, V>,
+        key: K,
+        default: V
+    ): &mut V {
+        if (!contains(table, key)) {
+            add(table, key, default)
+        };
+        borrow_mut(table, key)
+    }
+
+    /// Returns the length of the table, i.e. the number of entries.
+    public fun length<K: copy + store + drop, V: store>(table: &IterableTable<K, V>): u64 {
+        table::length(&table.inner)
+    }
+
+    /// Returns true if this table is empty.
+    public fun empty<K: copy + store + drop, V: store>(table: &IterableTable<K, V>): bool {
+        table::empty(&table.inner)
+    }
+
+    /// Returns true iff `table` contains an entry for `key`.
+    public fun contains<K: copy + store + drop, V: store>(table: &IterableTable<K, V>, key: K): bool {
+        table::contains(&table.inner, key)
+    }
+
+    // ======================================================================================================
+    // Iteration
+
+    /// Returns the key of the first entry, or `none` if the table is empty.
+    public fun head_key<K: copy + store + drop, V: store>(table: &IterableTable<K, V>): Option<K> {
+        table.head
+    }
+
+    /// Returns the key of the last entry, or `none` if the table is empty.
+    public fun tail_key<K: copy + store + drop, V: store>(table: &IterableTable<K, V>): Option<K> {
+        table.tail
+    }
+
+    /// Acquire an immutable reference to the value which `key` maps to, together with the keys
+    /// of the previous and the next entry. Aborts if there is no entry for `key`.
+    public fun borrow_iter<K: copy + store + drop, V: store>(
+        table: &IterableTable<K, V>,
+        key: K
+    ): (&V, Option<K>, Option<K>) {
+        let v = table::borrow(&table.inner, key);
+        (&v.val, v.prev, v.next)
+    }
+
+    /// Acquire a mutable reference to the value which `key` maps to, together with the keys
+    /// of the previous and the next entry. Aborts if there is no entry for `key`.
+    public fun borrow_iter_mut<K: copy + store + drop, V: store>(
+        table: &mut IterableTable<K, V>,
+        key: K
+    ): (&mut V, Option<K>, Option<K>) {
+        let v = table::borrow_mut(&mut table.inner, key);
+        (&mut v.val, v.prev, v.next)
+    }
+
+    /// Remove from `table` and return the value which `key` maps to, together with the keys
+    /// of the previous and the next entry. Aborts if there is no entry for `key`.
+    public fun remove_iter<K: copy + store + drop, V: store>(
+        table: &mut IterableTable<K, V>,
+        key: K
+    ): (V, Option<K>, Option<K>) {
+        let IterableValue{ val, prev, next } = table::remove(&mut table.inner, key);
+        if (option::is_some(&prev)) {
+            let k = *option::borrow(&prev);
+            table::borrow_mut(&mut table.inner, k).next = next;
+        } else {
+            table.head = next;
+        };
+        if (option::is_some(&next)) {
+            let k = *option::borrow(&next);
+            table::borrow_mut(&mut table.inner, k).prev = prev;
+        } else {
+            table.tail = prev;
+        };
+        (val, prev, next)
+    }
+
+    /// Returns the keys of at most `limit` entries in insertion order, starting with the entry
+    /// for `cursor`, or with the first entry if `cursor` is `none`. Also returns the cursor from
+    /// which to continue, which is `none` if there are no more entries. Aborts if there is no
+    /// entry for `cursor`.
+    public fun keys<K: copy + store + drop, V: store>(
+        table: &IterableTable<K, V>,
+        cursor: Option<K>,
+        limit: u64
+    ): (vector<K>, Option<K>) {
+        let keys = vector::empty();
+        let key = if (option::is_some(&cursor)) cursor else table.head;
+        while (option::is_some(&key) && vector::length(&keys) < limit) {
+            let k = *option::borrow(&key);
+            vector::push_back(&mut keys, k);
+            key = table::borrow(&table.inner, k).next;
+        };
+        (keys, key)
+    }
+
+    // ======================================================================================================
+    // Bulk operations
+
+    /// Add an entry for each pair of `keys` and `vals`, in order. Aborts if the vectors
+    /// have different lengths, or if an entry for one of the keys already exists.
+    public fun add_all<K: copy + store + drop, V: store>(
+        table: &mut IterableTable<K, V>,
+        keys: vector<K>,
+        vals: vector<V>
+    ) {
+        assert!(vector::length(&keys) == vector::length(&vals), errors::invalid_argument(ELENGTH_MISMATCH));
+        vector::reverse(&mut keys);
+        vector::reverse(&mut vals);
+        while (!vector::is_empty(&keys)) {
+            add(table, vector::pop_back(&mut keys), vector::pop_back(&mut vals));
+        };
+        vector::destroy_empty(vals);
+    }
+
+    /// Remove the entries for `keys` and return their values, in the order of `keys`.
+    /// Aborts if there is no entry for one of the keys.
+    public fun remove_all<K: copy + store + drop, V: store>(
+        table: &mut IterableTable<K, V>,
+        keys: vector<K>
+    ): vector<V> {
+        let vals = vector::empty();
+        vector::reverse(&mut keys);
+        while (!vector::is_empty(&keys)) {
+            vector::push_back(&mut vals, remove(table, vector::pop_back(&mut keys)));
+        };
+        vals
+    }
+
+    /// Move all entries of `other` to the end of `table`, in order. Aborts if `table` already
+    /// contains an entry for one of the keys.
+    public fun append<K: copy + store + drop, V: store>(
+        table: &mut IterableTable<K, V>,
+        other: &mut IterableTable<K, V>
+    ) {
+        while (option::is_some(&other.head)) {
+            let key = *option::borrow(&other.head);
+            let val = remove(other, key);
+            add(table, key, val);
+        };
+    }
+
+    #[test_only]
+    /// Testing only: allows to drop a table even if it is not empty.
+    public fun drop_unchecked<K: copy + store + drop, V: store>(table: IterableTable<K, V>) {
+        let IterableTable{ inner, head: _, tail: _ } = table;
+        table::drop_unchecked(inner)
+    }
+}
@@ -0,0 +1,112 @@
+/// Specifications of the `IterableTable` module.
+spec extensions::iterable_table {
+    // The links between entries are not specified as an invariant, so the functions which
+    // maintain them are specified by their effect on the set of entries only.
+
+    spec new {
+        aborts_if false;
+        ensures table::spec_len(result.inner) == 0;
+        ensures option::is_none(result.head) && option::is_none(result.tail);
+    }
+
+    spec destroy_empty {
+        aborts_if table::spec_len(table.inner) != 0;
+    }
+
+    spec add {
+        pragma aborts_if_is_partial;
+        aborts_if table::spec_contains(table.inner, key);
+        ensures table::spec_contains(table.inner, key);
+        ensures table::spec_get(table.inner, key).val == val;
+        ensures table::spec_len(table.inner) == table::spec_len(old(table).inner) + 1;
+        ensures table.tail == option::spec_some(key);
+    }
+
+    spec remove {
+        pragma aborts_if_is_partial;
+        aborts_if !table::spec_contains(table.inner, key);
+        ensures !table::spec_contains(table.inner, key);
+        ensures result == table::spec_get(old(table).inner, key).val;
+        ensures table::spec_len(table.inner) == table::spec_len(old(table).inner) - 1;
+    }
+
+    spec remove_iter {
+        pragma aborts_if_is_partial;
+        aborts_if !table::spec_contains(table.inner, key);
+        ensures !table::spec_contains(table.inner, key);
+        ensures result_1 == table::spec_get(old(table).inner, key).val;
+        ensures result_2 == table::spec_get(old(table).inner, key).prev;
+        ensures result_3 == table::spec_get(old(table).inner, key).next;
+        ensures table::spec_len(table.inner) == table::spec_len(old(table).inner) - 1;
+    }
+
+    spec borrow {
+        aborts_if !table::spec_contains(table.inner, key);
+        ensures result == table::spec_get(table.inner, key).val;
+    }
+
+    spec borrow_mut {
+        aborts_if !table::spec_contains(table.inner, key);
+    }
+
+    spec borrow_iter {
+        aborts_if !table::spec_contains(table.inner, key);
+        ensures result_1 == table::spec_get(table.inner, key).val;
+        ensures result_2 == table::spec_get(table.inner, key).prev;
+        ensures result_3 == table::spec_get(table.inner, key).next;
+    }
+
+    spec borrow_iter_mut {
+        aborts_if !table::spec_contains(table.inner, key);
+    }
+
+    spec length {
+        aborts_if false;
+        ensures result == table::spec_len(table.inner);
+    }
+
+    spec empty {
+        aborts_if false;
+        ensures result == (table::spec_len(table.inner) == 0);
+    }
+
+    spec contains {
+        aborts_if false;
+        ensures result == table::spec_contains(table.inner, key);
+    }
+
+    spec head_key {
+        aborts_if false;
+        ensures result == table.head;
+    }
+
+    spec tail_key {
+        aborts_if false;
+        ensures result == table.tail;
+    }
+
+    spec keys {
+        pragma verify = false;
+        ensures len(result_1) <= limit;
+    }
+
+    spec add_all {
+        pragma verify = false;
+        ensures table::spec_len(table.inner) == table::spec_len(old(table).inner) + len(keys);
+        ensures forall k in keys: table::spec_contains(table.inner, k);
+    }
+
+    spec remove_all {
+        pragma verify = false;
+        ensures len(result) == len(keys);
+        ensures table::spec_len(table.inner) == table::spec_len(old(table).inner) - len(keys);
+        ensures forall k in keys: !table::spec_contains(table.inner, k);
+    }
+
+    spec append {
+        pragma verify = false;
+        ensures table::spec_len(other.inner) == 0;
+        ensures table::spec_len(table.inner)
+            == table::spec_len(old(table).inner) + table::spec_len(old(other).inner);
+    }
+}
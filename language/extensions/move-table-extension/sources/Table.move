@@ -1,6 +1,7 @@
 /// Type of large-scale storage tables.
 module extensions::table {
     use std::errors;
+    use std::vector;
 
     // TODO: native code should not use reasons to signal logical type of error. Instead,
     // use Errors::ALREADY_PUBLISHED and Errors::NOT_PUBLISHED.
@@ -8,6 +9,7 @@ module extensions::table {
     // native code raises this with Errors::invalid_arguments()
     const ENOT_FOUND: u64 = 101;
     const ENOT_EMPTY: u64 = 102;
+    const ELENGTH_MISMATCH: u64 = 103;
 
     /// Type of tables
     struct Table<phantom K: copy + drop, phantom V> has store {
@@ -69,6 +71,40 @@ module extensions::table {
         borrow_mut(table, key)
     }
 
+    /// Insert the pair (`key`, `value`) if there is no entry for `key`,
+    /// update the value of the entry for `key` to `value` otherwise.
+    public fun upsert<K: copy + drop, V: drop>(table: &mut Table<K, V>, key: K, value: V) {
+        if (!contains(table, copy key)) {
+            add(table, copy key, value)
+        } else {
+            let ref = borrow_mut(table, key);
+            *ref = value;
+        };
+    }
+
+    /// Add an entry for each pair of `keys` and `vals`. Aborts if the vectors
+    /// have different lengths, or if an entry for one of the keys already exists.
+    public fun add_all<K: copy + drop, V>(table: &mut Table<K, V>, keys: vector<K>, vals: vector<V>) {
+        assert!(vector::length(&keys) == vector::length(&vals), errors::invalid_argument(ELENGTH_MISMATCH));
+        vector::reverse(&mut keys);
+        vector::reverse(&mut vals);
+        while (!vector::is_empty(&keys)) {
+            add(table, vector::pop_back(&mut keys), vector::pop_back(&mut vals));
+        };
+        vector::destroy_empty(vals);
+    }
+
+    /// Remove the entries for `keys` and return their values, in the order of `keys`.
+    /// Aborts if there is no entry for one of the keys.
+    public fun remove_all<K: copy + drop, V>(table: &mut Table<K, V>, keys: vector<K>): vector<V> {
+        let vals = vector::empty();
+        vector::reverse(&mut keys);
+        while (!vector::is_empty(&keys)) {
+            vector::push_back(&mut vals, remove(table, vector::pop_back(&mut keys)));
+        };
+        vals
+    }
+
     /// Remove from `table` and return the value which `key` maps to.
     /// Aborts if there is no entry for `key`.
     public fun remove<K: copy + drop, V>(table: &mut Table<K, V>, key: K): V {
@@ -11,6 +11,7 @@ spec extensions::table {
             map_is_empty = empty,
             map_has_key = contains,
             map_add_no_override = add,
+            map_add_override_if_exists = upsert,
             map_del_must_exist = remove,
             map_borrow = borrow,
             map_borrow_mut = borrow_mut,
@@ -53,6 +54,26 @@ spec extensions::table {
         pragma intrinsic;
     }
 
+    spec upsert {
+        pragma intrinsic;
+    }
+
+    spec add_all {
+        pragma verify = false;
+        pragma aborts_if_is_partial;
+        aborts_if len(keys) != len(vals);
+        ensures spec_len(table) == spec_len(old(table)) + len(keys);
+        ensures forall k in keys: spec_contains(table, k);
+    }
+
+    spec remove_all {
+        pragma verify = false;
+        pragma aborts_if_is_partial;
+        ensures len(result) == len(keys);
+        ensures spec_len(table) == spec_len(old(table)) - len(keys);
+        ensures forall k in keys: !spec_contains(table, k);
+    }
+
     spec contains {
         pragma intrinsic;
     }
@@ -0,0 +1,83 @@
+/// A table which exposes its length, with the interface of the `table_with_length` module
+/// found in other Move frameworks. Since `table::Table` already tracks its length, this is a
+/// thin wrapper around it, which allows such code to be used with this extension unchanged.
+module extensions::table_with_length {
+    use extensions::table::{Self, Table};
+
+    /// Type of tables
+    struct TableWithLength<phantom K: copy + drop, phantom V> has store {
+        inner: Table<K, V>,
+    }
+
+    /// Create a new Table.
+    public fun new<K: copy + drop, V: store>(): TableWithLength<K, V> {
+        TableWithLength{
+            inner: table::new<K, V>(),
+        }
+    }
+
+    /// Destroy a table. The table must be empty to succeed.
+    public fun destroy_empty<K: copy + drop, V>(table: TableWithLength<K, V>) {
+        let TableWithLength{ inner } = table;
+        table::destroy_empty(inner)
+    }
+
+    /// Add a new entry to the table. Aborts if an entry for this
+    /// key already exists. The entry itself is not stored in the
+    /// table, and cannot be discovered from it.
+    public fun add<K: copy + drop, V>(table: &mut TableWithLength<K, V>, key: K, val: V) {
+        table::add(&mut table.inner, key, val)
+    }
+
+    /// Acquire an immutable reference to the value which `key` maps to.
+    /// Aborts if there is no entry for `key`.
+    public fun borrow<K: copy + drop, V>(table: &TableWithLength<K, V>, key: K): &V {
+        table::borrow(&table.inner, key)
+    }
+
+    /// Acquire a mutable reference to the value which `key` maps to.
+    /// Aborts if there is no entry for `key`.
+    public fun borrow_mut<K: copy + drop, V>(table: &mut TableWithLength<K, V>, key: K): &mut V {
+        table::borrow_mut(&mut table.inner, key)
+    }
+
+    /// Returns the length of the table, i.e. the number of entries.
+    public fun length<K: copy + drop, V>(table: &TableWithLength<K, V>): u64 {
+        table::length(&table.inner)
+    }
+
+    /// Returns true if this table is empty.
+    public fun empty<K: copy + drop, V>(table: &TableWithLength<K, V>): bool {
+        table::empty(&table.inner)
+    }
+
+    /// Acquire a mutable reference to the value which `key` maps to.
+    /// Insert the pair (`key`, `default`) first if there is no entry for `key`.
+    public fun borrow_mut_with_default<K: copy + drop, V: drop>(table: &mut TableWithLength<K, V>, key: K, default: V): &mut V {
+        table::borrow_mut_with_default(&mut table.inner, key, default)
+    }
+
+    /// Insert the pair (`key`, `value`) if there is no entry for `key`,
+    /// update the value of the entry for `key` to `value` otherwise.
+    public fun upsert<K: copy + drop, V: drop>(table: &mut TableWithLength<K, V>, key: K, value: V) {
+        table::upsert(&mut table.inner, key, value)
+    }
+
+    /// Remove from `table` and return the value which `key` maps to.
+    /// Aborts if there is no entry for `key`.
+    public fun remove<K: copy + drop, V>(table: &mut TableWithLength<K, V>, key: K): V {
+        table::remove(&mut table.inner, key)
+    }
+
+    /// Returns true iff `table` contains an entry for `key`.
+    public fun contains<K: copy + drop, V>(table: &TableWithLength<K, V>, key: K): bool {
+        table::contains(&table.inner, key)
+    }
+
+    #[test_only]
+    /// Testing only: allows to drop a table even if it is not empty.
+    public fun drop_unchecked<K: copy + drop, V>(table: TableWithLength<K, V>) {
+        let TableWithLength{ inner } = table;
+        table::drop_unchecked(inner)
+    }
+}
@@ -11,7 +11,7 @@ use better_any::{Tid, TidAble};
 use move_binary_format::errors::{PartialVMError, PartialVMResult};
 use move_core_types::{
     account_address::AccountAddress,
-    effects::Op,
+    effects::{self, Op},
     gas_algebra::{InternalGas, InternalGasPerByte, NumBytes},
     language_storage::TypeTag,
     value::MoveTypeLayout,
@@ -85,6 +85,64 @@ pub struct TableChange {
     pub entries: BTreeMap<Vec<u8>, Op<Vec<u8>>>,
 }
 
+impl TableChangeSet {
+    pub fn is_empty(&self) -> bool {
+        self.new_tables.is_empty() && self.removed_tables.is_empty() && self.changes.is_empty()
+    }
+
+    /// Squashes a table change set which was produced after this one into it, so that
+    /// the changes of multiple sessions can be applied at once. Fails if the change sets
+    /// are inconsistent, e.g. if `other` adds an entry which this change set already added.
+    pub fn squash(&mut self, other: Self) -> anyhow::Result<()> {
+        let TableChangeSet {
+            new_tables,
+            removed_tables,
+            changes,
+        } = other;
+        for (handle, info) in new_tables {
+            if self.new_tables.insert(handle, info).is_some() {
+                anyhow::bail!("table {} is created by both change sets", handle.0)
+            }
+        }
+        for handle in removed_tables {
+            if !self.removed_tables.insert(handle) {
+                anyhow::bail!("table {} is removed by both change sets", handle.0)
+            }
+        }
+        for (handle, change) in changes {
+            match self.changes.entry(handle) {
+                Entry::Occupied(mut entry) => {
+                    effects::squash(&mut entry.get_mut().entries, change.entries)?;
+                    if entry.get().entries.is_empty() {
+                        entry.remove();
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(change);
+                }
+            }
+        }
+        self.remove_transient_tables();
+        Ok(())
+    }
+
+    /// Removes the tables which are both created and removed by this change set, together
+    /// with their changes, since they are not visible outside of it.
+    fn remove_transient_tables(&mut self) {
+        let transient = self
+            .new_tables
+            .keys()
+            .filter(|handle| self.removed_tables.contains(handle))
+            .copied()
+            .collect::<Vec<_>>();
+        for handle in transient {
+            self.new_tables.remove(&handle);
+            self.removed_tables.remove(&handle);
+            self.changes.remove(&handle);
+        }
+    }
+}
+
 /// A table resolver which needs to be provided by the environment. This allows to lookup
 /// data in remote storage, as well as retrieve cost of table operations.
 pub trait TableResolver {
@@ -202,11 +260,13 @@ impl<'a> NativeTableContext<'a> {
                 changes.insert(handle, TableChange { entries });
             }
         }
-        Ok(TableChangeSet {
+        let mut change_set = TableChangeSet {
             new_tables,
             removed_tables,
             changes,
-        })
+        };
+        change_set.remove_transient_tables();
+        Ok(change_set)
     }
 }
 
@@ -713,3 +773,216 @@ fn get_type_layout(context: &NativeContext, ty: &Type) -> PartialVMResult<MoveTy
         .type_to_type_layout(ty)?
         .ok_or_else(|| partial_extension_error("cannot determine type layout"))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_core_types::value::MoveStructLayout;
+    use move_vm_types::values::Struct;
+
+    struct NoResolver;
+
+    impl TableResolver for NoResolver {
+        fn resolve_table_entry(
+            &self,
+            _handle: &TableHandle,
+            _key: &[u8],
+        ) -> Result<Option<Vec<u8>>, anyhow::Error> {
+            Ok(None)
+        }
+    }
+
+    fn handle(n: u8) -> TableHandle {
+        TableHandle(AccountAddress::new([n; AccountAddress::LENGTH]))
+    }
+
+    fn info() -> TableInfo {
+        TableInfo::new(TypeTag::U64, TypeTag::U64)
+    }
+
+    fn change(entries: Vec<(u8, Op<Vec<u8>>)>) -> TableChange {
+        TableChange {
+            entries: entries.into_iter().map(|(k, op)| (vec![k], op)).collect(),
+        }
+    }
+
+    fn change_set(
+        new_tables: Vec<TableHandle>,
+        removed_tables: Vec<TableHandle>,
+        changes: Vec<(TableHandle, TableChange)>,
+    ) -> TableChangeSet {
+        TableChangeSet {
+            new_tables: new_tables.into_iter().map(|h| (h, info())).collect(),
+            removed_tables: removed_tables.into_iter().collect(),
+            changes: changes.into_iter().collect(),
+        }
+    }
+
+    /// A table with a single entry, which the session inserted. Like the natives, this stores
+    /// the value in a box, since only structs can be moved into a global value.
+    fn table_with_new_entry(handle: TableHandle) -> Table {
+        let mut gv = GlobalValue::none();
+        gv.move_to(Value::struct_(Struct::pack(vec![Value::u64(1)])))
+            .map_err(|(err, _)| err)
+            .unwrap();
+        Table {
+            handle,
+            key_layout: MoveTypeLayout::U64,
+            value_layout: MoveTypeLayout::Struct(MoveStructLayout::new(vec![MoveTypeLayout::U64])),
+            content: [(vec![0], gv)].into_iter().collect(),
+        }
+    }
+
+    #[test]
+    fn squash_merges_changes() {
+        let mut first = change_set(
+            vec![handle(1)],
+            vec![],
+            vec![(
+                handle(1),
+                change(vec![(0, Op::New(vec![1])), (1, Op::New(vec![1]))]),
+            )],
+        );
+        let second = change_set(
+            vec![handle(2)],
+            vec![handle(3)],
+            vec![
+                (
+                    handle(1),
+                    change(vec![(0, Op::Modify(vec![2])), (2, Op::New(vec![3]))]),
+                ),
+                (handle(2), change(vec![(0, Op::New(vec![4]))])),
+            ],
+        );
+        first.squash(second).unwrap();
+
+        assert_eq!(
+            first.new_tables.keys().copied().collect::<Vec<_>>(),
+            vec![handle(1), handle(2)]
+        );
+        assert_eq!(
+            first.removed_tables.iter().copied().collect::<Vec<_>>(),
+            vec![handle(3)]
+        );
+        let entries = &first.changes[&handle(1)].entries;
+        assert_eq!(entries[&vec![0]], Op::New(vec![2]));
+        assert_eq!(entries[&vec![1]], Op::New(vec![1]));
+        assert_eq!(entries[&vec![2]], Op::New(vec![3]));
+        assert_eq!(
+            first.changes[&handle(2)].entries[&vec![0]],
+            Op::New(vec![4])
+        );
+    }
+
+    #[test]
+    fn squash_drops_entries_created_and_deleted() {
+        let mut first = change_set(
+            vec![],
+            vec![],
+            vec![(handle(1), change(vec![(0, Op::New(vec![1]))]))],
+        );
+        let second = change_set(
+            vec![],
+            vec![],
+            vec![(handle(1), change(vec![(0, Op::Delete)]))],
+        );
+        first.squash(second).unwrap();
+        assert!(first.is_empty());
+    }
+
+    #[test]
+    fn squash_drops_tables_created_and_removed() {
+        let mut first = change_set(
+            vec![handle(1), handle(2)],
+            vec![],
+            vec![
+                (handle(1), change(vec![(0, Op::New(vec![1]))])),
+                (handle(2), change(vec![(0, Op::New(vec![2]))])),
+            ],
+        );
+        let second = change_set(vec![], vec![handle(1)], vec![]);
+        first.squash(second).unwrap();
+
+        assert_eq!(
+            first.new_tables.keys().copied().collect::<Vec<_>>(),
+            vec![handle(2)]
+        );
+        assert!(first.removed_tables.is_empty());
+        assert_eq!(
+            first.changes.keys().copied().collect::<Vec<_>>(),
+            vec![handle(2)]
+        );
+    }
+
+    #[test]
+    fn squash_rejects_conflicts() {
+        let mut first = change_set(vec![handle(1)], vec![], vec![]);
+        let second = change_set(vec![handle(1)], vec![], vec![]);
+        assert!(first.squash(second).is_err());
+
+        let mut first = change_set(vec![], vec![handle(1)], vec![]);
+        let second = change_set(vec![], vec![handle(1)], vec![]);
+        assert!(first.squash(second).is_err());
+
+        let mut first = change_set(
+            vec![],
+            vec![],
+            vec![(handle(1), change(vec![(0, Op::New(vec![1]))]))],
+        );
+        let second = change_set(
+            vec![],
+            vec![],
+            vec![(handle(1), change(vec![(0, Op::New(vec![2]))]))],
+        );
+        assert!(first.squash(second).is_err());
+
+        let mut first = change_set(
+            vec![],
+            vec![],
+            vec![(handle(1), change(vec![(0, Op::Delete)]))],
+        );
+        let second = change_set(
+            vec![],
+            vec![],
+            vec![(handle(1), change(vec![(0, Op::Modify(vec![2]))]))],
+        );
+        assert!(first.squash(second).is_err());
+    }
+
+    #[test]
+    fn into_change_set_drops_transient_tables() {
+        let context = NativeTableContext::new([0; 32], &NoResolver);
+        {
+            let mut table_data = context.table_data.borrow_mut();
+            table_data.new_tables.insert(handle(1), info());
+            table_data.new_tables.insert(handle(2), info());
+            table_data.removed_tables.insert(handle(1));
+            table_data.removed_tables.insert(handle(3));
+            for h in [handle(1), handle(2), handle(3)] {
+                table_data.tables.insert(h, table_with_new_entry(h));
+            }
+        }
+        let change_set = context.into_change_set().unwrap();
+
+        assert_eq!(
+            change_set.new_tables.keys().copied().collect::<Vec<_>>(),
+            vec![handle(2)]
+        );
+        assert_eq!(
+            change_set
+                .removed_tables
+                .iter()
+                .copied()
+                .collect::<Vec<_>>(),
+            vec![handle(3)]
+        );
+        assert_eq!(
+            change_set.changes.keys().copied().collect::<Vec<_>>(),
+            vec![handle(2), handle(3)]
+        );
+        assert_eq!(
+            change_set.changes[&handle(2)].entries[&vec![0]],
+            Op::New(1u64.to_le_bytes().to_vec())
+        );
+    }
+}
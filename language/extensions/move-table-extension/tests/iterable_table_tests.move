@@ -0,0 +1,131 @@
+#[test_only]
+module extensions::iterable_table_tests {
+    use std::option;
+    use std::vector;
+    use extensions::iterable_table as T;
+
+    fun collect_keys(t: &T::IterableTable<u64, u64>): vector<u64> {
+        let keys = vector::empty();
+        let key = T::head_key(t);
+        while (option::is_some(&key)) {
+            let k = *option::borrow(&key);
+            let (_, _, next) = T::borrow_iter(t, k);
+            vector::push_back(&mut keys, k);
+            key = next;
+        };
+        keys
+    }
+
+    fun collect_keys_reversed(t: &T::IterableTable<u64, u64>): vector<u64> {
+        let keys = vector::empty();
+        let key = T::tail_key(t);
+        while (option::is_some(&key)) {
+            let k = *option::borrow(&key);
+            let (_, prev, _) = T::borrow_iter(t, k);
+            vector::push_back(&mut keys, k);
+            key = prev;
+        };
+        keys
+    }
+
+    #[test]
+    fun iterate_in_insertion_order() {
+        let t = T::new<u64, u64>();
+        T::add(&mut t, 3, 30);
+        T::add(&mut t, 1, 10);
+        T::add(&mut t, 2, 20);
+        assert!(T::length(&t) == 3, 1);
+        assert!(collect_keys(&t) == vector[3, 1, 2], 2);
+        assert!(collect_keys_reversed(&t) == vector[2, 1, 3], 3);
+        let (val, prev, next) = T::borrow_iter(&t, 1);
+        assert!(*val == 10, 4);
+        assert!(prev == option::some(3) && next == option::some(2), 5);
+        T::drop_unchecked(t)
+    }
+
+    #[test]
+    fun remove_relinks_neighbors() {
+        let t = T::new<u64, u64>();
+        T::add_all(&mut t, vector[1, 2, 3, 4], vector[10, 20, 30, 40]);
+        let (val, prev, next) = T::remove_iter(&mut t, 2);
+        assert!(val == 20, 1);
+        assert!(prev == option::some(1) && next == option::some(3), 2);
+        assert!(T::remove(&mut t, 1) == 10, 3);
+        assert!(T::remove(&mut t, 4) == 40, 4);
+        assert!(T::head_key(&t) == option::some(3), 5);
+        assert!(T::tail_key(&t) == option::some(3), 6);
+        assert!(collect_keys(&t) == vector[3], 7);
+        T::remove(&mut t, 3);
+        assert!(T::head_key(&t) == option::none(), 8);
+        assert!(T::tail_key(&t) == option::none(), 9);
+        T::add(&mut t, 5, 50);
+        assert!(collect_keys(&t) == vector[5], 10);
+        T::remove(&mut t, 5);
+        T::destroy_empty(t)
+    }
+
+    #[test]
+    fun paginate_keys() {
+        let t = T::new<u64, u64>();
+        T::add_all(&mut t, vector[5, 4, 3, 2, 1], vector[0, 0, 0, 0, 0]);
+        let (keys, cursor) = T::keys(&t, option::none(), 2);
+        assert!(keys == vector[5, 4] && cursor == option::some(3), 1);
+        let (keys, cursor) = T::keys(&t, cursor, 2);
+        assert!(keys == vector[3, 2] && cursor == option::some(1), 2);
+        let (keys, cursor) = T::keys(&t, cursor, 2);
+        assert!(keys == vector[1] && cursor == option::none(), 3);
+        let (keys, cursor) = T::keys(&t, option::some(4), 10);
+        assert!(keys == vector[4, 3, 2, 1] && cursor == option::none(), 4);
+        let (keys, cursor) = T::keys(&t, option::none(), 0);
+        assert!(vector::is_empty(&keys) && cursor == option::some(5), 5);
+        T::drop_unchecked(t)
+    }
+
+    #[test]
+    fun bulk_operations() {
+        let t = T::new<u64, u64>();
+        T::add_all(&mut t, vector[1, 2], vector[10, 20]);
+        let other = T::new<u64, u64>();
+        T::add_all(&mut other, vector[4, 3], vector[40, 30]);
+        T::append(&mut t, &mut other);
+        assert!(T::empty(&other), 1);
+        T::destroy_empty(other);
+        assert!(collect_keys(&t) == vector[1, 2, 4, 3], 2);
+        assert!(*T::borrow(&t, 4) == 40, 3);
+        assert!(T::remove_all(&mut t, vector[4, 1]) == vector[40, 10], 4);
+        assert!(collect_keys(&t) == vector[2, 3], 5);
+        *T::borrow_mut(&mut t, 2) = 21;
+        *T::borrow_mut_with_default(&mut t, 6, 0) = 60;
+        let (val, _, _) = T::borrow_iter_mut(&mut t, 3);
+        *val = 31;
+        assert!(collect_keys(&t) == vector[2, 3, 6], 6);
+        assert!(T::remove_all(&mut t, vector[2, 3, 6]) == vector[21, 31, 60], 7);
+        T::destroy_empty(t)
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 25607)]
+    fun add_existing() {
+        let t = T::new<u64, u64>();
+        T::add(&mut t, 1, 10);
+        T::add(&mut t, 1, 20);
+        T::drop_unchecked(t)
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 25863)]
+    fun keys_from_missing_cursor() {
+        let t = T::new<u64, u64>();
+        T::add(&mut t, 1, 10);
+        T::keys(&t, option::some(2), 1);
+        T::drop_unchecked(t)
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 26113)]
+    fun destroy_non_empty() {
+        let t = T::new<u64, u64>();
+        T::add(&mut t, 1, 10);
+        T::destroy_empty(t)
+    }
+}
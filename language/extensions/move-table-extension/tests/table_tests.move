@@ -254,4 +254,44 @@ module extensions::table_tests {
 
         T::drop_unchecked(t)
     }
+
+    #[test]
+    fun test_upsert() {
+        let t = T::new<u64, u64>();
+        T::upsert(&mut t, 1, 2);
+        assert!(*T::borrow(&t, 1) == 2, 1);
+        T::upsert(&mut t, 1, 3);
+        assert!(*T::borrow(&t, 1) == 3, 2);
+        assert!(T::length(&t) == 1, 3);
+        T::drop_unchecked(t)
+    }
+
+    #[test]
+    fun test_add_remove_all() {
+        let t = T::new<u64, u64>();
+        T::add_all(&mut t, vector[1, 2, 3], vector[10, 20, 30]);
+        assert!(T::length(&t) == 3, 1);
+        assert!(*T::borrow(&t, 2) == 20, 2);
+        let vals = T::remove_all(&mut t, vector[3, 1]);
+        assert!(vals == vector[30, 10], 3);
+        assert!(T::length(&t) == 1, 4);
+        assert!(T::contains(&t, 2), 5);
+        T::drop_unchecked(t)
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 26375)]
+    fun test_add_all_length_mismatch() {
+        let t = T::new<u64, u64>();
+        T::add_all(&mut t, vector[1, 2], vector[10]);
+        T::drop_unchecked(t)
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 25607)]
+    fun test_add_all_duplicate() {
+        let t = T::new<u64, u64>();
+        T::add_all(&mut t, vector[1, 1], vector[10, 20]);
+        T::drop_unchecked(t)
+    }
 }
@@ -0,0 +1,49 @@
+#[test_only]
+module extensions::table_with_length_tests {
+    use extensions::table_with_length as T;
+
+    #[test]
+    fun simple_read_write() {
+        let t = T::new<u64, u64>();
+        T::add(&mut t, 1, 2);
+        T::add(&mut t, 10, 33);
+        assert!(*T::borrow(&t, 1) == 2, 1);
+        assert!(*T::borrow(&t, 10) == 33, 2);
+        *T::borrow_mut(&mut t, 1) = 3;
+        assert!(*T::borrow(&t, 1) == 3, 3);
+        assert!(T::length(&t) == 2, 4);
+        T::drop_unchecked(t)
+    }
+
+    #[test]
+    fun test_upsert_and_default() {
+        let t = T::new<u64, u64>();
+        T::upsert(&mut t, 1, 2);
+        T::upsert(&mut t, 1, 3);
+        assert!(*T::borrow(&t, 1) == 3, 1);
+        *T::borrow_mut_with_default(&mut t, 2, 0) = 5;
+        assert!(*T::borrow(&t, 2) == 5, 2);
+        assert!(T::length(&t) == 2, 3);
+        T::drop_unchecked(t)
+    }
+
+    #[test]
+    fun test_destroy() {
+        let t = T::new<u64, u64>();
+        assert!(T::empty(&t), 1);
+        T::add(&mut t, 1, 2);
+        assert!(!T::empty(&t), 2);
+        assert!(T::contains(&t, 1), 3);
+        assert!(T::remove(&mut t, 1) == 2, 4);
+        assert!(!T::contains(&t, 1), 5);
+        T::destroy_empty(t)
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 26113)]
+    fun test_destroy_fails() {
+        let t = T::new<u64, u64>();
+        T::add(&mut t, 1, 2);
+        T::destroy_empty(t)
+    }
+}
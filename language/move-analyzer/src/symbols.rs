@@ -619,6 +619,7 @@ impl Symbolicator {
         let build_config = move_package::BuildConfig {
             test_mode: true,
             install_dir: Some(tempdir().unwrap().path().to_path_buf()),
+            lint: true,
             ..Default::default()
         };
 
@@ -645,7 +646,9 @@ impl Symbolicator {
         let mut typed_ast = None;
         let mut diagnostics = None;
         build_plan.compile_with_driver(&mut std::io::sink(), |compiler| {
-            let (files, compilation_result) = compiler.run::<PASS_TYPING>()?;
+            // keep going past errors so that symbols are still computed for code that does not
+            // compile
+            let (files, compilation_result) = compiler.set_ide_mode(true).run::<PASS_TYPING>()?;
             let (_, compiler) = match compilation_result {
                 Ok(v) => v,
                 Err(diags) => {
@@ -3674,3 +3677,73 @@ fn symbols_test() {
         None,
     );
 }
+
+#[test]
+/// Tests if symbolication information is still computed for code that does not type check.
+fn symbols_with_errors_test() {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+
+    path.push("tests/symbols-errors");
+
+    let (symbols_opt, _) = Symbolicator::get_symbols(path.as_path()).unwrap();
+    let symbols = symbols_opt.unwrap();
+
+    let mut fpath = path.clone();
+    fpath.push("sources/M1.move");
+    let cpath = dunce::canonicalize(&fpath).unwrap();
+
+    let mod_symbols = symbols.file_use_defs.get(&cpath).unwrap();
+
+    // local var in a function with a type error (type_error function)
+    assert_use_def(
+        mod_symbols,
+        &symbols.file_name_mapping,
+        0,
+        7,
+        8,
+        6,
+        12,
+        "M1.move",
+        "u64",
+        None,
+    );
+    // param var passed to an unbound function (unbound_function function)
+    assert_use_def(
+        mod_symbols,
+        &symbols.file_name_mapping,
+        0,
+        11,
+        16,
+        10,
+        25,
+        "M1.move",
+        "u64",
+        None,
+    );
+    // param var passed to a function of an unbound module (unbound_module_call function)
+    assert_use_def(
+        mod_symbols,
+        &symbols.file_name_mapping,
+        0,
+        15,
+        29,
+        14,
+        28,
+        "M1.move",
+        "u64",
+        None,
+    );
+    // param var packed into a struct of an unbound module (unbound_module_pack function)
+    assert_use_def(
+        mod_symbols,
+        &symbols.file_name_mapping,
+        0,
+        19,
+        31,
+        18,
+        28,
+        "M1.move",
+        "u64",
+        None,
+    );
+}
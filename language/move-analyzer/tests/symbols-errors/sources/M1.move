@@ -0,0 +1,22 @@
+module SymbolsErrors::M1 {
+    struct SomeStruct has drop {
+        some_field: u64,
+    }
+
+    fun type_error(s: SomeStruct): bool {
+        let value = s.some_field;
+        value
+    }
+
+    fun unbound_function(value: u64) {
+        unknown(value);
+    }
+
+    fun unbound_module_call(value: u64) {
+        unknown_module::call(value);
+    }
+
+    fun unbound_module_pack(value: u64) {
+        unknown_module::S { f: value };
+    }
+}
@@ -2,15 +2,15 @@
 // Copyright (c) The Move Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::BTreeSet;
+use std::{collections::BTreeSet, fmt};
 
 use crate::{
     errors::{PartialVMError, PartialVMResult},
-    file_format::{AbilitySet, StructTypeParameter, Visibility},
+    file_format::{Ability, AbilitySet, StructTypeParameter, Visibility},
     file_format_common::VERSION_5,
-    normalized::Module,
+    normalized::{Module, Type},
 };
-use move_core_types::vm_status::StatusCode;
+use move_core_types::{identifier::Identifier, language_storage::ModuleId, vm_status::StatusCode};
 
 /// The result of a linking and layout compatibility check. Here is what the different combinations. NOTE that if `check_struct_layout` is false, type safety over a series of upgrades cannot be guaranteed.
 /// mean:
@@ -72,13 +72,43 @@ impl Compatibility {
 
     /// Check compatibility for `new_module` relative to old module `old_module`.
     pub fn check(&self, old_module: &Module, new_module: &Module) -> PartialVMResult<()> {
-        let mut struct_and_pub_function_linking = true;
-        let mut struct_layout = true;
-        let mut friend_linking = true;
+        let is_checked = |incompatibility: &Incompatibility| {
+            (self.check_struct_and_pub_function_linking && incompatibility.breaks_linking)
+                || (self.check_struct_layout && incompatibility.breaks_layout)
+                || (self.check_friend_linking && incompatibility.breaks_friend_linking)
+        };
+        if Self::report(old_module, new_module).iter().any(is_checked) {
+            return Err(PartialVMError::new(
+                StatusCode::BACKWARD_INCOMPATIBLE_MODULE_UPDATE,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Lists every change from `old_module` to `new_module` which breaks any of the guarantees
+    /// a compatibility check can ask for, in the order structs, functions, friends.
+    pub fn report(old_module: &Module, new_module: &Module) -> Vec<Incompatibility> {
+        let mut incompatibilities = vec![];
+        let mut report = |change: Change, breaks_linking, breaks_layout, breaks_friend_linking| {
+            incompatibilities.push(Incompatibility {
+                change,
+                breaks_linking,
+                breaks_layout,
+                breaks_friend_linking,
+            })
+        };
 
         // module's name and address are unchanged
         if old_module.address != new_module.address || old_module.name != new_module.name {
-            struct_and_pub_function_linking = false;
+            report(
+                Change::ModuleRenamed {
+                    old: ModuleId::new(old_module.address, old_module.name.clone()),
+                    new: ModuleId::new(new_module.address, new_module.name.clone()),
+                },
+                true,
+                false,
+                false,
+            );
         }
 
         // old module's structs are a subset of the new module's structs
@@ -89,19 +119,36 @@ impl Compatibility {
                     // Struct not present in new . Existing modules that depend on this struct will fail to link with the new version of the module.
                     // Also, struct layout cannot be guaranteed transitively, because after
                     // removing the struct, it could be re-added later with a different layout.
-                    struct_and_pub_function_linking = false;
-                    struct_layout = false;
-                    break;
+                    report(Change::StructRemoved(name.clone()), true, true, false);
+                    continue;
                 }
             };
 
-            if !struct_abilities_compatibile(old_struct.abilities, new_struct.abilities)
-                || !struct_type_parameters_compatibile(
-                    &old_struct.type_parameters,
-                    &new_struct.type_parameters,
-                )
-            {
-                struct_and_pub_function_linking = false;
+            if !struct_abilities_compatibile(old_struct.abilities, new_struct.abilities) {
+                report(
+                    Change::StructAbilitiesRemoved {
+                        name: name.clone(),
+                        removed: old_struct
+                            .abilities
+                            .into_iter()
+                            .filter(|ability| !new_struct.abilities.has_ability(*ability))
+                            .collect(),
+                    },
+                    true,
+                    false,
+                    false,
+                );
+            }
+            if !struct_type_parameters_compatibile(
+                &old_struct.type_parameters,
+                &new_struct.type_parameters,
+            ) {
+                report(
+                    Change::StructTypeParametersChanged(name.clone()),
+                    true,
+                    false,
+                    false,
+                );
             }
             if new_struct.fields != old_struct.fields {
                 // Fields changed. Code in this module will fail at runtime if it tries to
@@ -110,7 +157,12 @@ impl Compatibility {
                 // choose that changing the name (but not position or type) of a field is
                 // compatible. The VM does not care about the name of a field
                 // (it's purely informational), but clients presumably do.
-                struct_layout = false
+                report(
+                    Change::StructFieldsChanged(name.clone()),
+                    false,
+                    true,
+                    false,
+                );
             }
         }
 
@@ -129,14 +181,17 @@ impl Compatibility {
         // friend list. But for simplicity, we decided to go to the more restrictive form now and
         // we may revisit this in the future.
         for (name, old_func) in &old_module.exposed_functions {
+            // Changes of friend functions only break the linking of friend modules.
+            let is_friend = matches!(old_func.visibility, Visibility::Friend);
+            let mut report_function = |change| report(change, !is_friend, false, is_friend);
             let new_func = match new_module.exposed_functions.get(name) {
                 Some(new_func) => new_func,
                 None => {
-                    if matches!(old_func.visibility, Visibility::Friend) {
-                        friend_linking = false;
-                    } else {
-                        struct_and_pub_function_linking = false;
-                    }
+                    report_function(Change::FunctionRemoved {
+                        name: name.clone(),
+                        visibility: old_func.visibility,
+                        is_entry: old_func.is_entry,
+                    });
                     continue;
                 }
             };
@@ -162,20 +217,38 @@ impl Compatibility {
                 // If it was not an entry function, it is allowed to become one.
                 !old_func.is_entry || new_func.is_entry
             };
-            if !is_vis_compatible
-                || !is_entry_compatible
-                || old_func.parameters != new_func.parameters
-                || old_func.return_ != new_func.return_
-                || !fun_type_parameters_compatibile(
-                    &old_func.type_parameters,
-                    &new_func.type_parameters,
-                )
-            {
-                if matches!(old_func.visibility, Visibility::Friend) {
-                    friend_linking = false;
-                } else {
-                    struct_and_pub_function_linking = false;
-                }
+            if !is_vis_compatible {
+                report_function(Change::FunctionVisibilityChanged {
+                    name: name.clone(),
+                    old: old_func.visibility,
+                    new: new_func.visibility,
+                });
+            }
+            if !is_entry_compatible {
+                report_function(Change::FunctionEntryChanged {
+                    name: name.clone(),
+                    is_entry: new_func.is_entry,
+                });
+            }
+            if old_func.parameters != new_func.parameters {
+                report_function(Change::FunctionParametersChanged {
+                    name: name.clone(),
+                    old: old_func.parameters.clone(),
+                    new: new_func.parameters.clone(),
+                });
+            }
+            if old_func.return_ != new_func.return_ {
+                report_function(Change::FunctionReturnChanged {
+                    name: name.clone(),
+                    old: old_func.return_.clone(),
+                    new: new_func.return_.clone(),
+                });
+            }
+            if !fun_type_parameters_compatibile(
+                &old_func.type_parameters,
+                &new_func.type_parameters,
+            ) {
+                report_function(Change::FunctionTypeParametersChanged(name.clone()));
             }
         }
 
@@ -186,27 +259,192 @@ impl Compatibility {
         //
         let old_friend_module_ids: BTreeSet<_> = old_module.friends.iter().cloned().collect();
         let new_friend_module_ids: BTreeSet<_> = new_module.friends.iter().cloned().collect();
-        if !old_friend_module_ids.is_subset(&new_friend_module_ids) {
-            friend_linking = false;
+        for removed in old_friend_module_ids.difference(&new_friend_module_ids) {
+            report(Change::FriendRemoved(removed.clone()), false, false, true);
         }
 
-        if self.check_struct_and_pub_function_linking && !struct_and_pub_function_linking {
-            return Err(PartialVMError::new(
-                StatusCode::BACKWARD_INCOMPATIBLE_MODULE_UPDATE,
-            ));
-        }
-        if self.check_struct_layout && !struct_layout {
-            return Err(PartialVMError::new(
-                StatusCode::BACKWARD_INCOMPATIBLE_MODULE_UPDATE,
-            ));
+        incompatibilities
+    }
+}
+
+/// A change in an upgrade of a module, and the guarantees of `Compatibility` it breaks.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Incompatibility {
+    pub change: Change,
+    /// Modules which use the changed struct or function may no longer link.
+    pub breaks_linking: bool,
+    /// Values of the changed struct published under the old layout may no longer be readable.
+    pub breaks_layout: bool,
+    /// Friend modules which use the changed function may no longer link.
+    pub breaks_friend_linking: bool,
+}
+
+impl Incompatibility {
+    /// Whether the change only affects the layout of published values, so that dependent
+    /// modules continue to link but data needs to be migrated.
+    pub fn is_layout_only(&self) -> bool {
+        self.breaks_layout && !self.breaks_linking && !self.breaks_friend_linking
+    }
+
+    /// Whether the change only affects linking, so that recompiling and redeploying dependent
+    /// modules fixes it without a data migration.
+    pub fn is_linking_only(&self) -> bool {
+        !self.breaks_layout && (self.breaks_linking || self.breaks_friend_linking)
+    }
+}
+
+/// A change to the interface of a module, as seen by a compatibility check.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Change {
+    ModuleRenamed {
+        old: ModuleId,
+        new: ModuleId,
+    },
+    StructRemoved(Identifier),
+    StructAbilitiesRemoved {
+        name: Identifier,
+        removed: Vec<Ability>,
+    },
+    StructTypeParametersChanged(Identifier),
+    StructFieldsChanged(Identifier),
+    FunctionRemoved {
+        name: Identifier,
+        visibility: Visibility,
+        is_entry: bool,
+    },
+    FunctionVisibilityChanged {
+        name: Identifier,
+        old: Visibility,
+        new: Visibility,
+    },
+    FunctionEntryChanged {
+        name: Identifier,
+        is_entry: bool,
+    },
+    FunctionParametersChanged {
+        name: Identifier,
+        old: Vec<Type>,
+        new: Vec<Type>,
+    },
+    FunctionReturnChanged {
+        name: Identifier,
+        old: Vec<Type>,
+        new: Vec<Type>,
+    },
+    FunctionTypeParametersChanged(Identifier),
+    FriendRemoved(ModuleId),
+}
+
+impl Change {
+    /// The name of the struct the change is about, if any.
+    pub fn struct_name(&self) -> Option<&Identifier> {
+        use Change::*;
+        match self {
+            StructRemoved(name)
+            | StructAbilitiesRemoved { name, .. }
+            | StructTypeParametersChanged(name)
+            | StructFieldsChanged(name) => Some(name),
+            _ => None,
         }
-        if self.check_friend_linking && !friend_linking {
-            return Err(PartialVMError::new(
-                StatusCode::BACKWARD_INCOMPATIBLE_MODULE_UPDATE,
-            ));
+    }
+
+    /// The name of the function the change is about, if any.
+    pub fn function_name(&self) -> Option<&Identifier> {
+        use Change::*;
+        match self {
+            FunctionRemoved { name, .. }
+            | FunctionVisibilityChanged { name, .. }
+            | FunctionEntryChanged { name, .. }
+            | FunctionParametersChanged { name, .. }
+            | FunctionReturnChanged { name, .. }
+            | FunctionTypeParametersChanged(name) => Some(name),
+            _ => None,
         }
+    }
+}
 
-        Ok(())
+fn visibility_str(visibility: Visibility) -> &'static str {
+    match visibility {
+        Visibility::Public => "public",
+        Visibility::Friend => "public(friend)",
+        Visibility::Private => "private",
+    }
+}
+
+fn types_str(tys: &[Type]) -> String {
+    let tys = tys.iter().map(|ty| ty.to_string()).collect::<Vec<_>>();
+    format!("({})", tys.join(", "))
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use Change::*;
+        match self {
+            ModuleRenamed { old, new } => write!(
+                f,
+                "module {} was renamed to {}",
+                old.short_str_lossless(),
+                new.short_str_lossless()
+            ),
+            StructRemoved(name) => write!(f, "struct `{}` was removed", name),
+            StructAbilitiesRemoved { name, removed } => {
+                let removed = removed
+                    .iter()
+                    .map(|ability| format!("{:?}", ability).to_lowercase())
+                    .collect::<Vec<_>>();
+                write!(f, "struct `{}` no longer has {}", name, removed.join(", "))
+            }
+            StructTypeParametersChanged(name) => {
+                write!(f, "type parameters of struct `{}` changed", name)
+            }
+            StructFieldsChanged(name) => write!(f, "fields of struct `{}` changed", name),
+            FunctionRemoved {
+                name,
+                visibility,
+                is_entry,
+            } => write!(
+                f,
+                "{}{} function `{}` was removed",
+                visibility_str(*visibility),
+                if *is_entry { " entry" } else { "" },
+                name
+            ),
+            FunctionVisibilityChanged { name, old, new } => write!(
+                f,
+                "function `{}` changed from {} to {}",
+                name,
+                visibility_str(*old),
+                visibility_str(*new)
+            ),
+            FunctionEntryChanged { name, is_entry } => write!(
+                f,
+                "function `{}` {}",
+                name,
+                if *is_entry {
+                    "became an entry function"
+                } else {
+                    "is no longer an entry function"
+                }
+            ),
+            FunctionParametersChanged { name, old, new } => write!(
+                f,
+                "parameters of function `{}` changed from {} to {}",
+                name,
+                types_str(old),
+                types_str(new)
+            ),
+            FunctionReturnChanged { name, old, new } => write!(
+                f,
+                "return type of function `{}` changed from {} to {}",
+                name,
+                types_str(old),
+                types_str(new)
+            ),
+            FunctionTypeParametersChanged(name) => {
+                write!(f, "type parameters of function `{}` changed", name)
+            }
+            FriendRemoved(id) => write!(f, "friend {} was removed", id.short_str_lossless()),
+        }
     }
 }
 
@@ -3,7 +3,11 @@
 
 use std::convert::TryFrom;
 
-use crate::{compatibility::Compatibility, file_format::*, normalized};
+use crate::{
+    compatibility::{Change, Compatibility},
+    file_format::*,
+    normalized,
+};
 use move_core_types::{account_address::AccountAddress, identifier::Identifier};
 
 fn mk_module(vis: u8) -> normalized::Module {
@@ -115,3 +119,47 @@ fn deprecated_add_script_visibility() {
         .check(&friend_module, &script_module)
         .is_err());
 }
+
+#[test]
+fn report_lists_incompatibilities() {
+    let script_module = mk_module(Visibility::DEPRECATED_SCRIPT);
+    let public_module = mk_module(Visibility::Public as u8);
+    let friend_module = mk_module(Visibility::Friend as u8);
+    let private_module = mk_module(Visibility::Private as u8);
+    let name = Identifier::new("fn").unwrap();
+
+    assert!(Compatibility::report(&public_module, &public_module).is_empty());
+
+    // script -> public only breaks linking
+    let report = Compatibility::report(&script_module, &public_module);
+    assert_eq!(report.len(), 1);
+    assert_eq!(
+        report[0].change,
+        Change::FunctionEntryChanged {
+            name: name.clone(),
+            is_entry: false,
+        }
+    );
+    assert!(report[0].is_linking_only());
+    assert!(report[0].breaks_linking && !report[0].breaks_friend_linking);
+
+    // friend -> private removes the function for friends only
+    let report = Compatibility::report(&friend_module, &private_module);
+    assert_eq!(report.len(), 1);
+    assert_eq!(
+        report[0].change,
+        Change::FunctionRemoved {
+            name,
+            visibility: Visibility::Friend,
+            is_entry: false,
+        }
+    );
+    assert!(!report[0].breaks_linking && report[0].breaks_friend_linking);
+    assert_eq!(
+        report[0].change.to_string(),
+        "public(friend) function `fn` was removed"
+    );
+    assert!(Compatibility::new(true, true, false)
+        .check(&friend_module, &private_module)
+        .is_ok());
+}
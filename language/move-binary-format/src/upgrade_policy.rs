@@ -0,0 +1,329 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Named policies restricting how a published module may be upgraded.
+//!
+//! A policy is recorded in the metadata of a module and constrains all later versions of it:
+//! - `compatible`: the new version must pass the full compatibility check of
+//!   `crate::compatibility`.
+//! - `additive-only`: in addition, no existing function may change, including its body; only new
+//!   structs and functions may be added.
+//! - `dependency-only`: in addition, nothing may be added; only the modules the module depends on
+//!   may be upgraded under it.
+//! - `immutable`: the module cannot be changed at all.
+//!
+//! Policies can only be tightened: the new version of a module must record a policy at least as
+//! strict as the old one. Republishing a module unchanged is always allowed.
+
+use crate::{
+    access::ModuleAccess,
+    compatibility::Compatibility,
+    errors::{PartialVMError, PartialVMResult},
+    file_format::{
+        Bytecode, CompiledModule, FieldHandleIndex, FunctionHandleIndex, SignatureIndex,
+        StructDefinitionIndex,
+    },
+    normalized,
+};
+use move_core_types::{identifier::Identifier, metadata::Metadata, vm_status::StatusCode};
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+/// The metadata key under which the upgrade policy of a module is recorded.
+pub const UPGRADE_POLICY_KEY: &[u8] = b"upgrade_policy";
+
+/// A policy for upgrading a module, from the least to the most restrictive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum UpgradePolicy {
+    Compatible = 0,
+    AdditiveOnly = 1,
+    DependencyOnly = 2,
+    Immutable = 3,
+}
+
+impl UpgradePolicy {
+    pub const ALL: [UpgradePolicy; 4] = [
+        UpgradePolicy::Compatible,
+        UpgradePolicy::AdditiveOnly,
+        UpgradePolicy::DependencyOnly,
+        UpgradePolicy::Immutable,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            UpgradePolicy::Compatible => "compatible",
+            UpgradePolicy::AdditiveOnly => "additive-only",
+            UpgradePolicy::DependencyOnly => "dependency-only",
+            UpgradePolicy::Immutable => "immutable",
+        }
+    }
+
+    /// Returns the policy recorded in the metadata of `module`, if any.
+    pub fn from_module(module: &CompiledModule) -> PartialVMResult<Option<Self>> {
+        let metadata = match module
+            .metadata
+            .iter()
+            .find(|metadata| metadata.key == UPGRADE_POLICY_KEY)
+        {
+            Some(metadata) => metadata,
+            None => return Ok(None),
+        };
+        match metadata.value.as_slice() {
+            [byte] if (*byte as usize) < Self::ALL.len() => Ok(Some(Self::ALL[*byte as usize])),
+            _ => Err(PartialVMError::new(StatusCode::MALFORMED)
+                .with_message("malformed upgrade policy metadata".to_string())),
+        }
+    }
+
+    /// Records the policy in the metadata of `module`, replacing any policy recorded before.
+    pub fn record(self, module: &mut CompiledModule) {
+        module
+            .metadata
+            .retain(|metadata| metadata.key != UPGRADE_POLICY_KEY);
+        module.metadata.push(Metadata {
+            key: UPGRADE_POLICY_KEY.to_vec(),
+            value: vec![self as u8],
+        });
+    }
+
+    /// Checks that `new_module` may replace `old_module` under the policy recorded in
+    /// `old_module`. Modules without a recorded policy can be replaced by any module.
+    pub fn check_upgrade(
+        old_module: &CompiledModule,
+        new_module: &CompiledModule,
+    ) -> PartialVMResult<()> {
+        let violations = Self::upgrade_violations(old_module, new_module)?;
+        if violations.is_empty() {
+            return Ok(());
+        }
+        Err(
+            PartialVMError::new(StatusCode::BACKWARD_INCOMPATIBLE_MODULE_UPDATE).with_message(
+                format!(
+                    "upgrade of module {} violates its upgrade policy: {}",
+                    old_module.self_id(),
+                    violations.join("; ")
+                ),
+            ),
+        )
+    }
+
+    /// Lists the ways in which replacing `old_module` by `new_module` violates the policy
+    /// recorded in `old_module`.
+    pub fn upgrade_violations(
+        old_module: &CompiledModule,
+        new_module: &CompiledModule,
+    ) -> PartialVMResult<Vec<String>> {
+        let old_policy = match Self::from_module(old_module)? {
+            Some(policy) => policy,
+            None => return Ok(vec![]),
+        };
+        let new_policy = Self::from_module(new_module)?;
+        if old_module == new_module {
+            return Ok(vec![]);
+        }
+
+        let mut violations = vec![];
+        if new_policy < Some(old_policy) {
+            violations.push(format!(
+                "the upgrade policy cannot be relaxed from `{}` to `{}`",
+                old_policy,
+                new_policy.map_or("none", Self::name)
+            ));
+        }
+        violations.extend(old_policy.violations(old_module, new_module));
+        Ok(violations)
+    }
+
+    /// Lists the ways in which replacing `old_module` by `new_module` violates this policy,
+    /// regardless of the policies the modules record.
+    pub fn violations(
+        self,
+        old_module: &CompiledModule,
+        new_module: &CompiledModule,
+    ) -> Vec<String> {
+        if old_module == new_module {
+            return vec![];
+        }
+        if self == UpgradePolicy::Immutable {
+            return vec!["the module is immutable".to_string()];
+        }
+
+        let mut violations = Compatibility::report(
+            &normalized::Module::new(old_module),
+            &normalized::Module::new(new_module),
+        )
+        .into_iter()
+        .map(|incompatibility| incompatibility.change.to_string())
+        .collect::<Vec<_>>();
+        if self == UpgradePolicy::Compatible {
+            return violations;
+        }
+
+        let old_functions = functions(old_module);
+        let new_functions = functions(new_module);
+        for (name, old_function) in &old_functions {
+            match new_functions.get(name) {
+                None => violations.push(format!("function `{}` was removed", name)),
+                Some(new_function) if new_function != old_function => {
+                    violations.push(format!("function `{}` changed", name))
+                }
+                Some(_) => {}
+            }
+        }
+        if self == UpgradePolicy::AdditiveOnly {
+            return violations;
+        }
+
+        for name in new_functions.keys() {
+            if !old_functions.contains_key(name) {
+                violations.push(format!("function `{}` was added", name));
+            }
+        }
+        let old_structs = normalized::Module::new(old_module).structs;
+        for name in normalized::Module::new(new_module).structs.keys() {
+            if !old_structs.contains_key(name) {
+                violations.push(format!("struct `{}` was added", name));
+            }
+        }
+        if old_module.immediate_friends() != new_module.immediate_friends() {
+            violations.push("friends changed".to_string());
+        }
+        violations
+    }
+}
+
+impl fmt::Display for UpgradePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for UpgradePolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|policy| policy.name() == s)
+            .ok_or_else(|| {
+                let names = Self::ALL.map(Self::name);
+                format!(
+                    "unknown upgrade policy `{}`, expected one of {}",
+                    s,
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+/// A function definition with all indices into the module's tables resolved, so that it can be
+/// compared across versions of a module.
+#[derive(PartialEq, Eq)]
+struct ResolvedFunction {
+    signature: normalized::Function,
+    acquires: Vec<String>,
+    locals: Vec<String>,
+    code: Option<Vec<String>>,
+}
+
+fn functions(module: &CompiledModule) -> BTreeMap<Identifier, ResolvedFunction> {
+    module
+        .function_defs()
+        .iter()
+        .map(|def| {
+            let (name, signature) = normalized::Function::new(module, def);
+            let function = ResolvedFunction {
+                signature,
+                acquires: def
+                    .acquires_global_resources
+                    .iter()
+                    .map(|idx| struct_name(module, *idx))
+                    .collect(),
+                locals: def
+                    .code
+                    .as_ref()
+                    .map(|code| types(module, code.locals))
+                    .unwrap_or_default(),
+                code: def.code.as_ref().map(|code| {
+                    code.code
+                        .iter()
+                        .map(|instr| resolve(module, instr))
+                        .collect()
+                }),
+            };
+            (name, function)
+        })
+        .collect()
+}
+
+fn types(module: &CompiledModule, idx: SignatureIndex) -> Vec<String> {
+    module
+        .signature_at(idx)
+        .0
+        .iter()
+        .map(|ty| normalized::Type::new(module, ty).to_string())
+        .collect()
+}
+
+fn struct_name(module: &CompiledModule, idx: StructDefinitionIndex) -> String {
+    let handle = module.struct_handle_at(module.struct_def_at(idx).struct_handle);
+    module.identifier_at(handle.name).to_string()
+}
+
+fn field(module: &CompiledModule, idx: FieldHandleIndex) -> String {
+    let handle = module.field_handle_at(idx);
+    format!("{}.{}", struct_name(module, handle.owner), handle.field)
+}
+
+fn function(module: &CompiledModule, idx: FunctionHandleIndex) -> String {
+    let handle = module.function_handle_at(idx);
+    let module_id = module.module_id_for_handle(module.module_handle_at(handle.module));
+    format!(
+        "{}::{}",
+        module_id.short_str_lossless(),
+        module.identifier_at(handle.name)
+    )
+}
+
+/// Renders an instruction with the entries of the module's tables it refers to in place of
+/// their indices.
+fn resolve(module: &CompiledModule, instr: &Bytecode) -> String {
+    use Bytecode::*;
+    let generic = |name: String, type_args: SignatureIndex| {
+        format!("{}<{}>", name, types(module, type_args).join(", "))
+    };
+    let operand = match instr {
+        LdConst(idx) => {
+            let constant = module.constant_at(*idx);
+            format!("{:?}, {:?}", constant.type_, constant.data)
+        }
+        MutBorrowField(idx) | ImmBorrowField(idx) => field(module, *idx),
+        MutBorrowFieldGeneric(idx) | ImmBorrowFieldGeneric(idx) => {
+            let inst = module.field_instantiation_at(*idx);
+            generic(field(module, inst.handle), inst.type_parameters)
+        }
+        Call(idx) => function(module, *idx),
+        CallGeneric(idx) => {
+            let inst = module.function_instantiation_at(*idx);
+            generic(function(module, inst.handle), inst.type_parameters)
+        }
+        Pack(idx) | Unpack(idx) | MutBorrowGlobal(idx) | ImmBorrowGlobal(idx) | Exists(idx)
+        | MoveFrom(idx) | MoveTo(idx) => struct_name(module, *idx),
+        PackGeneric(idx)
+        | UnpackGeneric(idx)
+        | MutBorrowGlobalGeneric(idx)
+        | ImmBorrowGlobalGeneric(idx)
+        | ExistsGeneric(idx)
+        | MoveFromGeneric(idx)
+        | MoveToGeneric(idx) => {
+            let inst = module.struct_instantiation_at(*idx);
+            generic(struct_name(module, inst.def), inst.type_parameters)
+        }
+        VecPack(idx, n) | VecUnpack(idx, n) => format!("{}, {}", types(module, *idx).join(", "), n),
+        VecLen(idx) | VecImmBorrow(idx) | VecMutBorrow(idx) | VecPushBack(idx)
+        | VecPopBack(idx) | VecSwap(idx) => types(module, *idx).join(", "),
+        _ => return format!("{:?}", instr),
+    };
+    let debug = format!("{:?}", instr);
+    let name = debug.split('(').next().unwrap_or(&debug);
+    format!("{}({})", name, operand)
+}
@@ -98,6 +98,7 @@ fn nested_loops_max_depth() {
     let result = verify_module(
         &VerifierConfig {
             max_loop_depth: Some(2),
+            ..Default::default()
         },
         &module,
     );
@@ -118,6 +119,7 @@ fn nested_loops_exceed_max_depth() {
     let result = verify_module(
         &VerifierConfig {
             max_loop_depth: Some(2),
+            ..Default::default()
         },
         &module,
     );
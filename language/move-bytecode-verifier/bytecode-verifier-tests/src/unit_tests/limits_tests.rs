@@ -0,0 +1,171 @@
+// Copyright (c) The Diem Core Contributors
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::support::dummy_procedure_module;
+use move_binary_format::file_format::{
+    basic_test_module, empty_module, Bytecode, CompiledModule, IdentifierIndex, ModuleHandle,
+    Signature, SignatureIndex, SignatureToken,
+};
+use move_bytecode_verifier::{CodeUnitVerifier, LimitsVerifier, VerifierConfig};
+use move_core_types::{identifier::Identifier, vm_status::StatusCode};
+
+fn branching_module() -> CompiledModule {
+    // three basic blocks
+    dummy_procedure_module(vec![
+        Bytecode::LdTrue,
+        Bytecode::BrTrue(3),
+        Bytecode::Branch(3),
+        Bytecode::Ret,
+    ])
+}
+
+#[test]
+fn max_function_size() {
+    let module = dummy_procedure_module(vec![Bytecode::LdU64(0), Bytecode::Pop, Bytecode::Ret]);
+    let config = |n| VerifierConfig {
+        max_function_size: Some(n),
+        ..Default::default()
+    };
+    assert!(LimitsVerifier::verify_module(&config(3), &module).is_ok());
+    assert_eq!(
+        LimitsVerifier::verify_module(&config(2), &module)
+            .unwrap_err()
+            .major_status(),
+        StatusCode::TOO_MANY_INSTRUCTIONS
+    );
+}
+
+#[test]
+fn max_locals() {
+    let mut module = dummy_procedure_module(vec![Bytecode::Ret]);
+    module
+        .signatures
+        .push(Signature(vec![SignatureToken::U64, SignatureToken::Bool]));
+    module.function_defs[0].code.as_mut().unwrap().locals = SignatureIndex(1);
+    let config = |n| VerifierConfig {
+        max_locals: Some(n),
+        ..Default::default()
+    };
+    assert!(LimitsVerifier::verify_module(&config(2), &module).is_ok());
+    assert_eq!(
+        LimitsVerifier::verify_module(&config(1), &module)
+            .unwrap_err()
+            .major_status(),
+        StatusCode::TOO_MANY_LOCALS
+    );
+}
+
+#[test]
+fn max_type_nodes_and_depth() {
+    let mut module = empty_module();
+    // vector<vector<u8>> has 3 nodes and a depth of 3, (u64, bool) have a single node each
+    module.signatures.push(Signature(vec![
+        SignatureToken::Vector(Box::new(SignatureToken::Vector(Box::new(
+            SignatureToken::U8,
+        )))),
+        SignatureToken::U64,
+        SignatureToken::Bool,
+    ]));
+
+    let config = VerifierConfig {
+        max_type_nodes: Some(3),
+        max_generic_instantiation_depth: Some(3),
+        ..Default::default()
+    };
+    assert!(LimitsVerifier::verify_module(&config, &module).is_ok());
+
+    let config = VerifierConfig {
+        max_type_nodes: Some(2),
+        ..Default::default()
+    };
+    assert_eq!(
+        LimitsVerifier::verify_module(&config, &module)
+            .unwrap_err()
+            .major_status(),
+        StatusCode::TOO_MANY_TYPE_NODES
+    );
+
+    let config = VerifierConfig {
+        max_generic_instantiation_depth: Some(2),
+        ..Default::default()
+    };
+    assert_eq!(
+        LimitsVerifier::verify_module(&config, &module)
+            .unwrap_err()
+            .major_status(),
+        StatusCode::MAX_GENERIC_INSTANTIATION_DEPTH_REACHED
+    );
+}
+
+#[test]
+fn max_fields_in_struct() {
+    // struct Bar { x: u64 }
+    let module = basic_test_module();
+    let config = |n| VerifierConfig {
+        max_fields_in_struct: Some(n),
+        ..Default::default()
+    };
+    assert!(LimitsVerifier::verify_module(&config(1), &module).is_ok());
+    assert_eq!(
+        LimitsVerifier::verify_module(&config(0), &module)
+            .unwrap_err()
+            .major_status(),
+        StatusCode::TOO_MANY_FIELDS
+    );
+}
+
+#[test]
+fn max_dependencies() {
+    let mut module = empty_module();
+    module.module_handles.push(ModuleHandle {
+        address: module.module_handles[0].address,
+        name: IdentifierIndex(module.identifiers.len() as u16),
+    });
+    module
+        .identifiers
+        .push(Identifier::new("Dependency").unwrap());
+    let config = |n| VerifierConfig {
+        max_dependencies: Some(n),
+        ..Default::default()
+    };
+    assert!(LimitsVerifier::verify_module(&config(1), &module).is_ok());
+    assert_eq!(
+        LimitsVerifier::verify_module(&config(0), &module)
+            .unwrap_err()
+            .major_status(),
+        StatusCode::TOO_MANY_DEPENDENCIES
+    );
+}
+
+#[test]
+fn max_basic_blocks() {
+    let module = branching_module();
+    let config = |n| VerifierConfig {
+        max_basic_blocks: Some(n),
+        ..Default::default()
+    };
+    assert!(CodeUnitVerifier::verify_module(&config(3), &module).is_ok());
+    assert_eq!(
+        CodeUnitVerifier::verify_module(&config(2), &module)
+            .unwrap_err()
+            .major_status(),
+        StatusCode::TOO_MANY_BASIC_BLOCKS
+    );
+}
+
+#[test]
+fn max_per_fun_meter_units() {
+    let module = branching_module();
+    let config = |n| VerifierConfig {
+        max_per_fun_meter_units: Some(n),
+        ..Default::default()
+    };
+    assert!(CodeUnitVerifier::verify_module(&config(10_000), &module).is_ok());
+    assert_eq!(
+        CodeUnitVerifier::verify_module(&config(20), &module)
+            .unwrap_err()
+            .major_status(),
+        StatusCode::PROGRAM_TOO_COMPLEX
+    );
+}
@@ -6,19 +6,22 @@
 //! The overall verification is split between stack_usage_verifier.rs and
 //! abstract_interpreter.rs. CodeUnitVerifier simply orchestrates calls into these two files.
 use crate::{
-    acquires_list_verifier::AcquiresVerifier, control_flow, locals_safety, reference_safety,
-    stack_usage_verifier::StackUsageVerifier, type_safety, verifier::VerifierConfig,
+    acquires_list_verifier::AcquiresVerifier, control_flow, locals_safety, meter::Meter,
+    reference_safety, stack_usage_verifier::StackUsageVerifier, type_safety,
+    verifier::VerifierConfig,
 };
 use move_binary_format::{
     access::ModuleAccess,
     binary_views::{BinaryIndexedView, FunctionView},
-    errors::{Location, PartialVMResult, VMResult},
+    control_flow_graph::ControlFlowGraph,
+    errors::{Location, PartialVMError, PartialVMResult, VMResult},
     file_format::{
         CompiledModule, CompiledScript, FunctionDefinition, FunctionDefinitionIndex,
         IdentifierIndex, TableIndex,
     },
     IndexKind,
 };
+use move_core_types::vm_status::StatusCode;
 use std::collections::HashMap;
 
 pub struct CodeUnitVerifier<'a> {
@@ -69,7 +72,7 @@ impl<'a> CodeUnitVerifier<'a> {
             function_view,
             name_def_map: HashMap::new(),
         };
-        code_unit_verifier.verify_common()
+        code_unit_verifier.verify_common(verifier_config)
     }
 
     fn verify_function(
@@ -99,14 +102,32 @@ impl<'a> CodeUnitVerifier<'a> {
             function_view,
             name_def_map,
         };
-        code_unit_verifier.verify_common()?;
+        code_unit_verifier.verify_common(verifier_config)?;
         AcquiresVerifier::verify(module, index, function_definition)
     }
 
-    fn verify_common(&self) -> PartialVMResult<()> {
+    fn verify_common(&self, verifier_config: &VerifierConfig) -> PartialVMResult<()> {
+        if let Some(max_basic_blocks) = verifier_config.max_basic_blocks {
+            let num_blocks = self.function_view.cfg().num_blocks() as usize;
+            if num_blocks > max_basic_blocks {
+                return Err(
+                    PartialVMError::new(StatusCode::TOO_MANY_BASIC_BLOCKS).with_message(format!(
+                        "{} exceeds the limit of {}",
+                        num_blocks, max_basic_blocks
+                    )),
+                );
+            }
+        }
         StackUsageVerifier::verify(&self.resolver, &self.function_view)?;
         type_safety::verify(&self.resolver, &self.function_view)?;
-        locals_safety::verify(&self.resolver, &self.function_view)?;
-        reference_safety::verify(&self.resolver, &self.function_view, &self.name_def_map)
+        // The abstract interpreters share the budget for the function
+        let mut meter = Meter::new(verifier_config.max_per_fun_meter_units);
+        locals_safety::verify(&self.resolver, &self.function_view, &mut meter)?;
+        reference_safety::verify(
+            &self.resolver,
+            &self.function_view,
+            &self.name_def_map,
+            &mut meter,
+        )
     }
 }
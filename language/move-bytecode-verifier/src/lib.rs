@@ -18,6 +18,8 @@ pub mod dependencies;
 pub mod friends;
 pub mod instantiation_loops;
 pub mod instruction_consistency;
+pub mod limits;
+pub mod meter;
 pub mod script_signature;
 pub mod signature;
 pub mod struct_defs;
@@ -26,6 +28,7 @@ pub mod verifier;
 pub use check_duplication::DuplicationChecker;
 pub use code_unit_verifier::CodeUnitVerifier;
 pub use instruction_consistency::InstructionConsistency;
+pub use limits::LimitsVerifier;
 pub use script_signature::{
     legacy_script_signature_checks, no_additional_script_signature_checks, FnCheckScriptSignature,
 };
@@ -0,0 +1,174 @@
+// Copyright (c) The Diem Core Contributors
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module implements a checker for the complexity limits of `VerifierConfig` that can be
+//! checked without analyzing code: the size and depth of types, the number of fields of structs,
+//! the number of dependencies, and the size and number of locals of functions. It runs right after
+//! the bounds checker, so that the other passes never see a module exceeding these limits.
+use crate::verifier::VerifierConfig;
+use move_binary_format::{
+    access::{ModuleAccess, ScriptAccess},
+    errors::{Location, PartialVMError, PartialVMResult, VMResult},
+    file_format::{
+        CodeUnit, CompiledModule, CompiledScript, Constant, Signature, SignatureToken,
+        StructFieldInformation, TableIndex,
+    },
+    IndexKind,
+};
+use move_core_types::vm_status::StatusCode;
+
+pub struct LimitsVerifier<'a> {
+    config: &'a VerifierConfig,
+}
+
+impl<'a> LimitsVerifier<'a> {
+    pub fn verify_module(config: &'a VerifierConfig, module: &CompiledModule) -> VMResult<()> {
+        Self::verify_module_impl(config, module)
+            .map_err(|e| e.finish(Location::Module(module.self_id())))
+    }
+
+    fn verify_module_impl(
+        config: &'a VerifierConfig,
+        module: &CompiledModule,
+    ) -> PartialVMResult<()> {
+        let limits = Self { config };
+        limits.verify_dependencies(module.immediate_dependencies().len())?;
+        limits.verify_signatures(module.signatures())?;
+        limits.verify_constants(module.constant_pool())?;
+        for (idx, struct_def) in module.struct_defs().iter().enumerate() {
+            limits
+                .verify_struct_fields(&struct_def.field_information)
+                .map_err(|err| err.at_index(IndexKind::StructDefinition, idx as TableIndex))?;
+        }
+        for (idx, function_def) in module.function_defs().iter().enumerate() {
+            if let Some(code) = &function_def.code {
+                let function_handle = module.function_handle_at(function_def.function);
+                limits
+                    .verify_code_unit(
+                        code,
+                        module.signature_at(function_handle.parameters),
+                        module.signature_at(code.locals),
+                    )
+                    .map_err(|err| {
+                        err.at_index(IndexKind::FunctionDefinition, idx as TableIndex)
+                    })?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn verify_script(config: &'a VerifierConfig, script: &CompiledScript) -> VMResult<()> {
+        Self::verify_script_impl(config, script).map_err(|e| e.finish(Location::Script))
+    }
+
+    fn verify_script_impl(
+        config: &'a VerifierConfig,
+        script: &CompiledScript,
+    ) -> PartialVMResult<()> {
+        let limits = Self { config };
+        limits.verify_dependencies(script.module_handles().len())?;
+        limits.verify_signatures(script.signatures())?;
+        limits.verify_constants(script.constant_pool())?;
+        limits.verify_code_unit(
+            script.code(),
+            script.signature_at(script.parameters),
+            script.signature_at(script.code().locals),
+        )
+    }
+
+    fn verify_dependencies(&self, count: usize) -> PartialVMResult<()> {
+        check_limit(
+            count,
+            self.config.max_dependencies,
+            StatusCode::TOO_MANY_DEPENDENCIES,
+        )
+    }
+
+    fn verify_signatures(&self, signatures: &[Signature]) -> PartialVMResult<()> {
+        for (idx, signature) in signatures.iter().enumerate() {
+            for ty in &signature.0 {
+                self.verify_type(ty)
+                    .map_err(|err| err.at_index(IndexKind::Signature, idx as TableIndex))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn verify_constants(&self, constants: &[Constant]) -> PartialVMResult<()> {
+        for (idx, constant) in constants.iter().enumerate() {
+            self.verify_type(&constant.type_)
+                .map_err(|err| err.at_index(IndexKind::ConstantPool, idx as TableIndex))?;
+        }
+        Ok(())
+    }
+
+    fn verify_struct_fields(
+        &self,
+        field_information: &StructFieldInformation,
+    ) -> PartialVMResult<()> {
+        let fields = match field_information {
+            StructFieldInformation::Native => return Ok(()),
+            StructFieldInformation::Declared(fields) => fields,
+        };
+        check_limit(
+            fields.len(),
+            self.config.max_fields_in_struct,
+            StatusCode::TOO_MANY_FIELDS,
+        )?;
+        for field in fields {
+            self.verify_type(&field.signature.0)?;
+        }
+        Ok(())
+    }
+
+    fn verify_code_unit(
+        &self,
+        code: &CodeUnit,
+        parameters: &Signature,
+        locals: &Signature,
+    ) -> PartialVMResult<()> {
+        check_limit(
+            code.code.len(),
+            self.config.max_function_size,
+            StatusCode::TOO_MANY_INSTRUCTIONS,
+        )?;
+        check_limit(
+            parameters.len() + locals.len(),
+            self.config.max_locals,
+            StatusCode::TOO_MANY_LOCALS,
+        )
+    }
+
+    fn verify_type(&self, ty: &SignatureToken) -> PartialVMResult<()> {
+        if self.config.max_type_nodes.is_none()
+            && self.config.max_generic_instantiation_depth.is_none()
+        {
+            return Ok(());
+        }
+        let mut nodes = 0;
+        let mut max_depth = 0;
+        for (_, depth) in ty.preorder_traversal_with_depth() {
+            nodes += 1;
+            max_depth = max_depth.max(depth);
+        }
+        check_limit(
+            nodes,
+            self.config.max_type_nodes,
+            StatusCode::TOO_MANY_TYPE_NODES,
+        )?;
+        check_limit(
+            max_depth,
+            self.config.max_generic_instantiation_depth,
+            StatusCode::MAX_GENERIC_INSTANTIATION_DEPTH_REACHED,
+        )
+    }
+}
+
+fn check_limit(value: usize, limit: Option<usize>, status: StatusCode) -> PartialVMResult<()> {
+    match limit {
+        Some(limit) if value > limit => Err(PartialVMError::new(status)
+            .with_message(format!("{} exceeds the limit of {}", value, limit))),
+        _ => Ok(()),
+    }
+}
@@ -4,7 +4,10 @@
 
 //! This module defines the abstract state for the local safety analysis.
 
-use crate::absint::{AbstractDomain, JoinResult};
+use crate::{
+    absint::{AbstractDomain, JoinResult},
+    meter::{Meter, JOIN_BASE_COST, PER_JOIN_ITEM_COST},
+};
 use move_binary_format::{
     binary_views::{BinaryIndexedView, FunctionView},
     errors::{PartialVMError, PartialVMResult},
@@ -130,7 +133,9 @@ impl AbstractState {
 
 impl AbstractDomain for AbstractState {
     /// attempts to join state to self and returns the result
-    fn join(&mut self, state: &AbstractState) -> JoinResult {
+    fn join(&mut self, state: &AbstractState, meter: &mut Meter) -> PartialVMResult<JoinResult> {
+        meter.add(JOIN_BASE_COST)?;
+        meter.add_items(PER_JOIN_ITEM_COST, self.local_states.len())?;
         let joined = Self::join_(self, state);
         assert!(self.local_states.len() == joined.local_states.len());
         let locals_unchanged = self
@@ -139,10 +144,10 @@ impl AbstractDomain for AbstractState {
             .zip(&joined.local_states)
             .all(|(self_state, other_state)| self_state == other_state);
         if locals_unchanged {
-            JoinResult::Unchanged
+            Ok(JoinResult::Unchanged)
         } else {
             *self = joined;
-            JoinResult::Changed
+            Ok(JoinResult::Changed)
         }
     }
 }
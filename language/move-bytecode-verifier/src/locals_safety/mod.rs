@@ -8,7 +8,10 @@
 
 mod abstract_state;
 
-use crate::absint::{AbstractInterpreter, TransferFunctions};
+use crate::{
+    absint::{AbstractInterpreter, TransferFunctions},
+    meter::Meter,
+};
 use abstract_state::{AbstractState, LocalState};
 use move_binary_format::{
     binary_views::{BinaryIndexedView, FunctionView},
@@ -20,9 +23,10 @@ use move_core_types::vm_status::StatusCode;
 pub(crate) fn verify<'a>(
     resolver: &BinaryIndexedView,
     function_view: &'a FunctionView<'a>,
+    meter: &mut Meter,
 ) -> PartialVMResult<()> {
     let initial_state = AbstractState::new(resolver, function_view)?;
-    LocalsSafetyAnalysis().analyze_function(initial_state, function_view)
+    LocalsSafetyAnalysis().analyze_function(initial_state, function_view, meter)
 }
 
 fn execute_inner(
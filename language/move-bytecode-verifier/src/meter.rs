@@ -0,0 +1,59 @@
+// Copyright (c) The Diem Core Contributors
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module implements the meter which bounds the work of the abstract interpreters used by
+//! the verifier. The analyses charge a cost for each instruction they execute and for each join
+//! of abstract states, proportional to the size of the states, and verification fails with
+//! `PROGRAM_TOO_COMPLEX` once the budget set by `VerifierConfig::max_per_fun_meter_units` is
+//! exhausted.
+use move_binary_format::errors::{PartialVMError, PartialVMResult};
+use move_core_types::vm_status::StatusCode;
+
+/// The cost of executing an instruction in an abstract interpreter.
+pub(crate) const STEP_BASE_COST: u128 = 10;
+/// The cost of a join of two abstract states, not counting their size.
+pub(crate) const JOIN_BASE_COST: u128 = 10;
+/// The cost of a join per item (local or reference) in the abstract states.
+pub(crate) const PER_JOIN_ITEM_COST: u128 = 4;
+
+pub struct Meter {
+    max_units: Option<u128>,
+    units: u128,
+}
+
+impl Meter {
+    /// Creates a meter which fails once more than `max_units` are consumed. A meter without
+    /// a maximum never fails.
+    pub fn new(max_units: Option<u128>) -> Self {
+        Self {
+            max_units,
+            units: 0,
+        }
+    }
+
+    /// Returns the number of units consumed so far.
+    pub fn units(&self) -> u128 {
+        self.units
+    }
+
+    /// Consumes `units`, failing if this exceeds the budget.
+    pub fn add(&mut self, units: u128) -> PartialVMResult<()> {
+        self.units = self.units.saturating_add(units);
+        match self.max_units {
+            Some(max_units) if self.units > max_units => Err(PartialVMError::new(
+                StatusCode::PROGRAM_TOO_COMPLEX,
+            )
+            .with_message(format!(
+                "verification consumed more than {} meter units",
+                max_units
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Consumes `units_per_item` for each of `items`, failing if this exceeds the budget.
+    pub fn add_items(&mut self, units_per_item: u128, items: usize) -> PartialVMResult<()> {
+        self.add(units_per_item.saturating_mul(items as u128))
+    }
+}
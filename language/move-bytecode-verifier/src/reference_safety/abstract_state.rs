@@ -3,7 +3,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 //! This module defines the abstract state for the type and memory safety analysis.
-use crate::absint::{AbstractDomain, JoinResult};
+use crate::{
+    absint::{AbstractDomain, JoinResult},
+    meter::{Meter, JOIN_BASE_COST, PER_JOIN_ITEM_COST},
+};
 use move_binary_format::{
     binary_views::FunctionView,
     errors::{PartialVMError, PartialVMResult},
@@ -685,7 +688,13 @@ impl AbstractState {
 
 impl AbstractDomain for AbstractState {
     /// attempts to join state to self and returns the result
-    fn join(&mut self, state: &AbstractState) -> JoinResult {
+    fn join(&mut self, state: &AbstractState, meter: &mut Meter) -> PartialVMResult<JoinResult> {
+        meter.add(JOIN_BASE_COST)?;
+        meter.add_items(PER_JOIN_ITEM_COST, self.locals.len() + state.locals.len())?;
+        meter.add_items(
+            PER_JOIN_ITEM_COST,
+            self.borrow_graph.all_refs().len() + state.borrow_graph.all_refs().len(),
+        )?;
         let joined = Self::join_(self, state);
         assert!(joined.is_canonical());
         assert!(self.num_locals == joined.num_locals);
@@ -694,10 +703,10 @@ impl AbstractDomain for AbstractState {
             .all(|idx| self.locals.get(&idx) == joined.locals.get(&idx));
         let borrow_graph_unchanged = self.borrow_graph.leq(&joined.borrow_graph);
         if locals_unchanged && borrow_graph_unchanged {
-            JoinResult::Unchanged
+            Ok(JoinResult::Unchanged)
         } else {
             *self = joined;
-            JoinResult::Changed
+            Ok(JoinResult::Changed)
         }
     }
 }
@@ -10,7 +10,10 @@
 
 mod abstract_state;
 
-use crate::absint::{AbstractInterpreter, TransferFunctions};
+use crate::{
+    absint::{AbstractInterpreter, TransferFunctions},
+    meter::Meter,
+};
 use abstract_state::{AbstractState, AbstractValue};
 use move_binary_format::{
     binary_views::{BinaryIndexedView, FunctionView},
@@ -49,11 +52,12 @@ pub(crate) fn verify<'a>(
     resolver: &'a BinaryIndexedView<'a>,
     function_view: &FunctionView,
     name_def_map: &'a HashMap<IdentifierIndex, FunctionDefinitionIndex>,
+    meter: &mut Meter,
 ) -> PartialVMResult<()> {
     let initial_state = AbstractState::new(function_view);
 
     let mut verifier = ReferenceSafetyAnalysis::new(resolver, function_view, name_def_map);
-    verifier.analyze_function(initial_state, function_view)
+    verifier.analyze_function(initial_state, function_view, meter)
 }
 
 fn call(
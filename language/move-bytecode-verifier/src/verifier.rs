@@ -7,8 +7,9 @@ use crate::{
     ability_field_requirements, check_duplication::DuplicationChecker,
     code_unit_verifier::CodeUnitVerifier, constants, friends,
     instantiation_loops::InstantiationLoopChecker, instruction_consistency::InstructionConsistency,
-    script_signature, script_signature::no_additional_script_signature_checks,
-    signature::SignatureChecker, struct_defs::RecursiveStructDefChecker,
+    limits::LimitsVerifier, script_signature,
+    script_signature::no_additional_script_signature_checks, signature::SignatureChecker,
+    struct_defs::RecursiveStructDefChecker,
 };
 use move_binary_format::{
     check_bounds::BoundsChecker,
@@ -16,9 +17,32 @@ use move_binary_format::{
     file_format::{CompiledModule, CompiledScript},
 };
 
+/// Limits on the complexity of the code accepted by the verifier. Each limit is disabled when
+/// set to `None`, which is the default.
+///
+/// Hosts that verify untrusted modules should set these limits to bound the time and memory
+/// spent in verification.
 #[derive(Debug, Clone, Default)]
 pub struct VerifierConfig {
+    /// Maximum nesting depth of loops in a function.
     pub max_loop_depth: Option<usize>,
+    /// Maximum number of instructions in a function.
+    pub max_function_size: Option<usize>,
+    /// Maximum number of basic blocks in a function.
+    pub max_basic_blocks: Option<usize>,
+    /// Maximum nesting depth of a type, e.g. `vector<vector<u8>>` has depth 3.
+    pub max_generic_instantiation_depth: Option<usize>,
+    /// Maximum number of nodes of a type, e.g. `S<u8, vector<u8>>` has 4 nodes.
+    pub max_type_nodes: Option<usize>,
+    /// Maximum number of locals of a function, including its parameters.
+    pub max_locals: Option<usize>,
+    /// Maximum number of fields of a struct.
+    pub max_fields_in_struct: Option<usize>,
+    /// Maximum number of modules a module depends on.
+    pub max_dependencies: Option<usize>,
+    /// Maximum number of units the abstract interpreters in `locals_safety` and
+    /// `reference_safety` may consume to verify a function. See `meter` for the costs.
+    pub max_per_fun_meter_units: Option<u128>,
 }
 
 /// Helper for a "canonical" verification of a module.
@@ -41,6 +65,7 @@ pub fn verify_module_with_config(config: &VerifierConfig, module: &CompiledModul
         // failed, we cannot safely index into module's handle to itself.
         e.finish(Location::Undefined)
     })?;
+    LimitsVerifier::verify_module(config, module)?;
     DuplicationChecker::verify_module(module)?;
     SignatureChecker::verify_module(module)?;
     InstructionConsistency::verify_module(module)?;
@@ -69,6 +94,7 @@ pub fn verify_script(script: &CompiledScript) -> VMResult<()> {
 
 pub fn verify_script_with_config(config: &VerifierConfig, script: &CompiledScript) -> VMResult<()> {
     BoundsChecker::verify_script(script).map_err(|e| e.finish(Location::Script))?;
+    LimitsVerifier::verify_script(config, script)?;
     DuplicationChecker::verify_script(script)?;
     SignatureChecker::verify_script(script)?;
     InstructionConsistency::verify_script(script)?;
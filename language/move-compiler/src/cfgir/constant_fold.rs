@@ -74,7 +74,7 @@ fn optimize_exp(e: &mut Exp) -> bool {
         //************************************
         E::Unit { .. }
         | E::Value(_)
-        | E::Constant(_)
+        | E::Constant(_, _)
         | E::UnresolvedError
         | E::Spec(_, _)
         | E::BorrowLocal(_, _)
@@ -405,6 +405,82 @@ const fn evalue_(loc: Loc, v: Value_) -> UnannotatedExp_ {
     E::Value(sp(loc, v))
 }
 
+//**************************************************************************************************
+// Unfoldable operations
+//**************************************************************************************************
+
+/// Finds an operation on values that could not be folded, e.g. because it overflows. Returns its
+/// location along with the reason it could not be folded
+pub fn unfoldable_operation(e: &Exp) -> Option<(Loc, String)> {
+    use UnannotatedExp_ as E;
+    match &e.exp.value {
+        E::Unit { .. }
+        | E::Value(_)
+        | E::Constant(_, _)
+        | E::UnresolvedError
+        | E::Spec(_, _)
+        | E::BorrowLocal(_, _)
+        | E::Move { .. }
+        | E::Copy { .. }
+        | E::Unreachable => None,
+
+        E::ModuleCall(mcall) => unfoldable_operation(&mcall.arguments),
+        E::Builtin(_, e)
+        | E::Freeze(e)
+        | E::Dereference(e)
+        | E::Borrow(_, e, _)
+        | E::UnaryExp(_, e)
+        | E::Vector(_, _, _, e) => unfoldable_operation(e),
+        E::Pack(_, _, fields) => fields.iter().find_map(|(_, _, e)| unfoldable_operation(e)),
+        E::ExpList(es) => es.iter().find_map(|item| match item {
+            ExpListItem::Single(e, _) | ExpListItem::Splat(_, e, _) => unfoldable_operation(e),
+        }),
+
+        E::BinopExp(e1, sp!(_, op_), e2) => {
+            if let Some(inner) = unfoldable_operation(e1).or_else(|| unfoldable_operation(e2)) {
+                return Some(inner);
+            }
+            let v1 = foldable_exp(e1)?;
+            foldable_exp(e2)?;
+            let ty = value_type_name(&v1)?;
+            let reason = match op_ {
+                BinOp_::Add | BinOp_::Mul => {
+                    format!("Arithmetic overflow. The result does not fit in '{}'", ty)
+                }
+                BinOp_::Sub => "Arithmetic underflow. The result is less than zero".to_string(),
+                BinOp_::Div | BinOp_::Mod => "Division by zero".to_string(),
+                BinOp_::Shl | BinOp_::Shr => format!(
+                    "The shift amount must be less than the number of bits in '{}'",
+                    ty
+                ),
+                _ => return None,
+            };
+            Some((e.exp.loc, reason))
+        }
+        E::Cast(inner, bt) => {
+            if let Some(inner) = unfoldable_operation(inner) {
+                return Some(inner);
+            }
+            foldable_exp(inner)?;
+            let reason = format!("The cast value does not fit in '{}'", bt);
+            Some((e.exp.loc, reason))
+        }
+    }
+}
+
+fn value_type_name(v: &Value_) -> Option<&'static str> {
+    use Value_ as V;
+    Some(match v {
+        V::U8(_) => "u8",
+        V::U16(_) => "u16",
+        V::U32(_) => "u32",
+        V::U64(_) => "u64",
+        V::U128(_) => "u128",
+        V::U256(_) => "u256",
+        V::Address(_) | V::Bool(_) | V::Vector(_, _) => return None,
+    })
+}
+
 //**************************************************************************************************
 // Foldable Value
 //**************************************************************************************************
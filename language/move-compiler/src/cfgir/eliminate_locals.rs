@@ -141,7 +141,7 @@ mod count {
     fn exp(context: &mut Context, parent_e: &Exp) {
         use UnannotatedExp_ as E;
         match &parent_e.exp.value {
-            E::Unit { .. } | E::Value(_) | E::Constant(_) | E::UnresolvedError => (),
+            E::Unit { .. } | E::Value(_) | E::Constant(_, _) | E::UnresolvedError => (),
             E::Spec(_, used_locals) => {
                 used_locals.keys().for_each(|var| context.used(var, false));
             }
@@ -215,7 +215,7 @@ mod count {
             | E::Move { .. }
             | E::Borrow(_, _, _) => false,
 
-            E::Unit { .. } | E::Value(_) | E::Constant(_) => true,
+            E::Unit { .. } | E::Value(_) | E::Constant(_, _) => true,
 
             E::Cast(e, _) => can_subst_exp_single(e),
             E::UnaryExp(op, e) => can_subst_exp_unary(op) && can_subst_exp_single(e),
@@ -357,7 +357,7 @@ mod eliminate {
 
             E::Unit { .. }
             | E::Value(_)
-            | E::Constant(_)
+            | E::Constant(_, _)
             | E::Spec(_, _)
             | E::UnresolvedError
             | E::BorrowLocal(_, _) => (),
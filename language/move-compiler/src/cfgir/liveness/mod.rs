@@ -120,7 +120,7 @@ fn lvalue(state: &mut LivenessState, sp!(_, l_): &LValue) {
 fn exp(state: &mut LivenessState, parent_e: &Exp) {
     use UnannotatedExp_ as E;
     match &parent_e.exp.value {
-        E::Unit { .. } | E::Value(_) | E::Constant(_) | E::UnresolvedError => (),
+        E::Unit { .. } | E::Value(_) | E::Constant(_, _) | E::UnresolvedError => (),
 
         E::BorrowLocal(_, var) | E::Copy { var, .. } | E::Move { var, .. } => {
             state.0.insert(*var);
@@ -175,6 +175,7 @@ pub fn last_usage(
     infinite_loop_starts: &BTreeSet<Label>,
 ) {
     let (final_invariants, per_command_states) = analyze(cfg, infinite_loop_starts);
+    let borrowed_locals = borrowed_locals(cfg);
     for (lbl, block) in cfg.blocks_mut() {
         let final_invariant = final_invariants
             .get(lbl)
@@ -185,11 +186,69 @@ pub fn last_usage(
             locals,
             final_invariant,
             command_states,
+            &borrowed_locals,
             block,
         )
     }
 }
 
+/// Locals that are borrowed anywhere in the function. A 'copy' of such a local cannot always be
+/// replaced by a 'move'
+fn borrowed_locals(cfg: &BlockCFG) -> BTreeSet<Var> {
+    fn exp(borrowed: &mut BTreeSet<Var>, e: &Exp) {
+        use UnannotatedExp_ as E;
+        match &e.exp.value {
+            E::BorrowLocal(_, var) => {
+                borrowed.insert(*var);
+            }
+            E::Unit { .. }
+            | E::Value(_)
+            | E::Constant(_, _)
+            | E::Move { .. }
+            | E::Copy { .. }
+            | E::Unreachable
+            | E::Spec(_, _)
+            | E::UnresolvedError => (),
+            E::ModuleCall(mcall) => exp(borrowed, &mcall.arguments),
+            E::Builtin(_, e)
+            | E::Vector(_, _, _, e)
+            | E::Freeze(e)
+            | E::Dereference(e)
+            | E::UnaryExp(_, e)
+            | E::Borrow(_, e, _)
+            | E::Cast(e, _) => exp(borrowed, e),
+            E::BinopExp(e1, _, e2) => {
+                exp(borrowed, e1);
+                exp(borrowed, e2)
+            }
+            E::Pack(_, _, fields) => fields.iter().for_each(|(_, _, e)| exp(borrowed, e)),
+            E::ExpList(es) => es.iter().for_each(|item| match item {
+                ExpListItem::Single(e, _) | ExpListItem::Splat(_, e, _) => exp(borrowed, e),
+            }),
+        }
+    }
+
+    let mut borrowed = BTreeSet::new();
+    for block in cfg.blocks().values() {
+        for sp!(_, cmd_) in block {
+            use Command_ as C;
+            match cmd_ {
+                C::Assign(_, e) => exp(&mut borrowed, e),
+                C::Abort(e)
+                | C::Return { exp: e, .. }
+                | C::IgnoreAndPop { exp: e, .. }
+                | C::JumpIf { cond: e, .. } => exp(&mut borrowed, e),
+                C::Mutate(el, er) => {
+                    exp(&mut borrowed, el);
+                    exp(&mut borrowed, er)
+                }
+                C::Jump { .. } | C::Break | C::Continue => (),
+            }
+        }
+    }
+    borrowed
+}
+
 mod last_usage {
     use crate::{
         cfgir::liveness::state::LivenessState,
@@ -198,9 +257,11 @@ mod last_usage {
             ast::*,
             translate::{display_var, DisplayVar},
         },
+        linters,
         parser::ast::{Ability_, Var},
         shared::{unique_map::*, *},
     };
+    use move_ir_types::location::*;
     use std::collections::{BTreeSet, VecDeque};
 
     struct Context<'a, 'b> {
@@ -208,6 +269,7 @@ mod last_usage {
         locals: &'a UniqueMap<Var, SingleType>,
         next_live: &'b BTreeSet<Var>,
         dropped_live: BTreeSet<Var>,
+        borrowed_locals: &'b BTreeSet<Var>,
     }
 
     impl<'a, 'b> Context<'a, 'b> {
@@ -216,12 +278,14 @@ mod last_usage {
             locals: &'a UniqueMap<Var, SingleType>,
             next_live: &'b BTreeSet<Var>,
             dropped_live: BTreeSet<Var>,
+            borrowed_locals: &'b BTreeSet<Var>,
         ) -> Self {
             Context {
                 env,
                 locals,
                 next_live,
                 dropped_live,
+                borrowed_locals,
             }
         }
 
@@ -229,6 +293,10 @@ mod last_usage {
             let ty = self.locals.get(local).unwrap();
             ty.value.abilities(ty.loc).has_ability_(Ability_::Drop)
         }
+
+        fn is_borrowed(&self, local: &Var) -> bool {
+            self.borrowed_locals.contains(local)
+        }
     }
 
     pub fn block(
@@ -236,6 +304,7 @@ mod last_usage {
         locals: &UniqueMap<Var, SingleType>,
         final_invariant: &LivenessState,
         command_states: &VecDeque<LivenessState>,
+        borrowed_locals: &BTreeSet<Var>,
         block: &mut BasicBlock,
     ) {
         let len = block.len();
@@ -257,7 +326,13 @@ mod last_usage {
                 .cloned()
                 .collect::<BTreeSet<_>>();
             command(
-                &mut Context::new(compilation_env, locals, next_data, dropped_live),
+                &mut Context::new(
+                    compilation_env,
+                    locals,
+                    next_data,
+                    dropped_live,
+                    borrowed_locals,
+                ),
                 cmd,
             )
         }
@@ -320,10 +395,25 @@ mod last_usage {
         }
     }
 
+    fn redundant_copy(context: &mut Context, loc: Loc, var: &Var) {
+        let v_str = match display_var(var.value()) {
+            DisplayVar::Tmp => return,
+            DisplayVar::Orig(v_str) => v_str,
+        };
+        let msg = format!(
+            "Local '{}' is not used after this copy. Consider 'move {}' instead",
+            v_str, v_str
+        );
+        context.env.add_lint_diag(
+            &linters::REDUNDANT_COPY,
+            diag!(Lint::RedundantCopy, (loc, msg)),
+        )
+    }
+
     fn exp(context: &mut Context, parent_e: &mut Exp) {
         use UnannotatedExp_ as E;
         match &mut parent_e.exp.value {
-            E::Unit { .. } | E::Value(_) | E::Constant(_) | E::UnresolvedError => (),
+            E::Unit { .. } | E::Value(_) | E::Constant(_, _) | E::UnresolvedError => (),
 
             E::BorrowLocal(_, var) | E::Move { var, .. } => {
                 // remove it from context to prevent accidental dropping in previous usages
@@ -348,6 +438,8 @@ mod last_usage {
                         var: *var,
                         annotation: MoveOpAnnotation::InferredLastUsage,
                     }
+                } else if var_is_dead && !context.is_borrowed(var) {
+                    redundant_copy(context, parent_e.exp.loc, var)
                 }
             }
 
@@ -7,6 +7,7 @@ use crate::{
         self,
         ast::{self as G, BasicBlock, BasicBlocks, BlockInfo},
         cfg::BlockCFG,
+        constant_fold,
     },
     diag,
     expansion::ast::{AbilitySet, ModuleIdent},
@@ -30,7 +31,12 @@ use std::{
 
 struct Context<'env> {
     env: &'env mut CompilationEnv,
+    pre_compiled_lib: Option<&'env FullyCompiledProgram>,
     struct_declared_abilities: UniqueMap<ModuleIdent, UniqueMap<StructName, AbilitySet>>,
+    // Constants are computed on demand, as their values can use other constants. The module is
+    // `None` for the constants of the script being translated
+    constant_definitions: BTreeMap<(Option<ModuleIdent>, ConstantName), H::Constant>,
+    constant_values: BTreeMap<(Option<ModuleIdent>, ConstantName), Option<Value>>,
     start: Option<Label>,
     loop_begin: Option<Label>,
     loop_end: Option<Label>,
@@ -46,7 +52,7 @@ struct Context<'env> {
 impl<'env> Context<'env> {
     pub fn new(
         env: &'env mut CompilationEnv,
-        pre_compiled_lib: Option<&FullyCompiledProgram>,
+        pre_compiled_lib: Option<&'env FullyCompiledProgram>,
         modules: &UniqueMap<ModuleIdent, H::ModuleDefinition>,
     ) -> Self {
         let all_modules = modules
@@ -63,9 +69,20 @@ impl<'env> Context<'env> {
                 .map(|(m, mdef)| (m, mdef.structs.ref_map(|_s, sdef| sdef.abilities.clone()))),
         )
         .unwrap();
+        let constant_definitions = modules
+            .key_cloned_iter()
+            .flat_map(|(m, mdef)| {
+                mdef.constants
+                    .key_cloned_iter()
+                    .map(move |(c, cdef)| ((Some(m), c), cdef.clone()))
+            })
+            .collect();
         Context {
             env,
+            pre_compiled_lib,
             struct_declared_abilities,
+            constant_definitions,
+            constant_values: BTreeMap::new(),
             next_label: None,
             loop_begin: None,
             loop_end: None,
@@ -149,9 +166,9 @@ impl<'env> Context<'env> {
 // Entry
 //**************************************************************************************************
 
-pub fn program(
-    compilation_env: &mut CompilationEnv,
-    pre_compiled_lib: Option<&FullyCompiledProgram>,
+pub fn program<'env>(
+    compilation_env: &'env mut CompilationEnv,
+    pre_compiled_lib: Option<&'env FullyCompiledProgram>,
     prog: H::Program,
 ) -> G::Program {
     let H::Program {
@@ -193,7 +210,7 @@ fn module(
         constants: hconstants,
     } = mdef;
 
-    let constants = hconstants.map(|name, c| constant(context, name, c));
+    let constants = hconstants.map(|name, c| constant(context, Some(module_ident), name, c));
     let functions = hfunctions.map(|name, f| function(context, name, f));
     (
         module_ident,
@@ -229,7 +246,11 @@ fn script(context: &mut Context, hscript: H::Script) -> G::Script {
         function_name,
         function: hfunction,
     } = hscript;
-    let constants = hconstants.map(|name, c| constant(context, name, c));
+    for (name, c) in hconstants.key_cloned_iter() {
+        context.constant_definitions.insert((None, name), c.clone());
+    }
+    let constants = hconstants.map(|name, c| constant(context, None, name, c));
+    context.constant_values.retain(|(m, _), _| m.is_some());
     let function = function(context, function_name, hfunction);
     G::Script {
         package_name,
@@ -245,16 +266,20 @@ fn script(context: &mut Context, hscript: H::Script) -> G::Script {
 // Functions
 //**************************************************************************************************
 
-fn constant(context: &mut Context, _name: ConstantName, c: H::Constant) -> G::Constant {
+fn constant(
+    context: &mut Context,
+    module: Option<ModuleIdent>,
+    name: ConstantName,
+    c: H::Constant,
+) -> G::Constant {
     let H::Constant {
         attributes,
         loc,
         signature,
-        value: (locals, block),
+        value: _,
     } = c;
 
-    let final_value = constant_(context, loc, signature.clone(), locals, block);
-    let value = final_value.and_then(move_value_from_exp);
+    let value = constant_value(context, module, name).map(move_value_from_value);
 
     G::Constant {
         attributes,
@@ -264,6 +289,42 @@ fn constant(context: &mut Context, _name: ConstantName, c: H::Constant) -> G::Co
     }
 }
 
+// Computes the value of the constant the first time it is needed, either for its own definition
+// or for the definition of another constant using it
+fn constant_value(
+    context: &mut Context,
+    module: Option<ModuleIdent>,
+    name: ConstantName,
+) -> Option<Value> {
+    let key = (module, name);
+    if let Some(value) = context.constant_values.get(&key) {
+        return value.clone();
+    }
+    let cdef = match context.constant_definitions.remove(&key) {
+        Some(cdef) => cdef,
+        None => {
+            let pre_compiled_module = module.and_then(|m| {
+                context
+                    .pre_compiled_lib
+                    .and_then(|pre_compiled| pre_compiled.hlir.modules.get(&m))
+            });
+            pre_compiled_module
+                .and_then(|mdef| mdef.constants.get(&name))
+                .expect("ICE unknown constant or cyclic constants not rejected in typing")
+                .clone()
+        }
+    };
+    let H::Constant {
+        loc,
+        signature,
+        value: (locals, block),
+        ..
+    } = cdef;
+    let value = constant_(context, loc, signature, locals, block).and_then(value_from_exp);
+    context.constant_values.insert(key, value.clone());
+    value
+}
+
 const CANNOT_FOLD: &str =
     "Invalid expression in 'const'. This expression could not be evaluated to a value";
 
@@ -279,6 +340,10 @@ fn constant_(
 
     initial_block(context, block);
     let (start, mut blocks, block_info) = context.finish_blocks();
+    if !inline_constants(context, &mut blocks) {
+        // The error was reported when computing the constant used
+        return None;
+    }
 
     let (mut cfg, infinite_loop_starts, errors) = BlockCFG::new(start, &mut blocks, &block_info);
     assert!(infinite_loop_starts.is_empty(), "{}", ICE_MSG);
@@ -343,21 +408,116 @@ fn check_constant_value(context: &mut Context, e: &H::Exp) {
     use H::UnannotatedExp_ as E;
     match &e.exp.value {
         E::Value(_) => (),
-        _ => context.env.add_diag(diag!(
-            BytecodeGeneration::UnfoldableConstant,
-            (e.exp.loc, CANNOT_FOLD)
-        )),
+        _ => {
+            let (loc, msg) = match constant_fold::unfoldable_operation(e) {
+                Some((loc, reason)) => (loc, format!("Invalid expression in 'const'. {}", reason)),
+                None => (e.exp.loc, CANNOT_FOLD.to_string()),
+            };
+            context
+                .env
+                .add_diag(diag!(BytecodeGeneration::UnfoldableConstant, (loc, msg)))
+        }
     }
 }
 
-fn move_value_from_exp(e: H::Exp) -> Option<MoveValue> {
+fn value_from_exp(e: H::Exp) -> Option<Value> {
     use H::UnannotatedExp_ as E;
     match e.exp.value {
-        E::Value(v) => Some(move_value_from_value(v)),
+        E::Value(v) => Some(v),
         _ => None,
     }
 }
 
+// Replaces the uses of other constants with their values, so that the constant can be folded.
+// Returns false if the value of a constant used could not be computed
+fn inline_constants(context: &mut Context, blocks: &mut BasicBlocks) -> bool {
+    let mut all_computed = true;
+    for block in blocks.values_mut() {
+        for cmd in block.iter_mut() {
+            all_computed &= inline_constants_cmd(context, cmd);
+        }
+    }
+    all_computed
+}
+
+fn inline_constants_cmd(context: &mut Context, sp!(_, cmd_): &mut H::Command) -> bool {
+    use H::Command_ as C;
+    match cmd_ {
+        C::Assign(_, e) => inline_constants_exp(context, e),
+        C::Mutate(el, er) => {
+            let computed_l = inline_constants_exp(context, el);
+            let computed_r = inline_constants_exp(context, er);
+            computed_l && computed_r
+        }
+        C::Return { exp: e, .. }
+        | C::Abort(e)
+        | C::IgnoreAndPop { exp: e, .. }
+        | C::JumpIf { cond: e, .. } => inline_constants_exp(context, e),
+        C::Jump { .. } | C::Break | C::Continue => true,
+    }
+}
+
+fn inline_constants_exp(context: &mut Context, e: &mut H::Exp) -> bool {
+    use H::UnannotatedExp_ as E;
+    let loc = e.exp.loc;
+    match &mut e.exp.value {
+        e_ @ E::Constant(_, _) => {
+            let (module, name) = match e_ {
+                E::Constant(m, c) => (*m, *c),
+                _ => unreachable!(),
+            };
+            match constant_value(context, module, name) {
+                Some(sp!(_, v_)) => {
+                    *e_ = E::Value(sp(loc, v_));
+                    true
+                }
+                None => false,
+            }
+        }
+
+        E::Unit { .. }
+        | E::Value(_)
+        | E::Move { .. }
+        | E::Copy { .. }
+        | E::BorrowLocal(_, _)
+        | E::Spec(_, _)
+        | E::Unreachable
+        | E::UnresolvedError => true,
+
+        E::ModuleCall(mcall) => inline_constants_exp(context, &mut mcall.arguments),
+        E::Builtin(_, e)
+        | E::Freeze(e)
+        | E::Vector(_, _, _, e)
+        | E::Dereference(e)
+        | E::UnaryExp(_, e)
+        | E::Borrow(_, e, _)
+        | E::Cast(e, _) => inline_constants_exp(context, e),
+        E::BinopExp(e1, _, e2) => {
+            let computed1 = inline_constants_exp(context, e1);
+            let computed2 = inline_constants_exp(context, e2);
+            computed1 && computed2
+        }
+        E::Pack(_, _, fields) => {
+            let mut all_computed = true;
+            for (_, _, e) in fields {
+                all_computed &= inline_constants_exp(context, e);
+            }
+            all_computed
+        }
+        E::ExpList(items) => {
+            let mut all_computed = true;
+            for item in items {
+                match item {
+                    H::ExpListItem::Single(e, _) | H::ExpListItem::Splat(_, e, _) => {
+                        all_computed &= inline_constants_exp(context, e)
+                    }
+                }
+            }
+            all_computed
+        }
+    }
+}
+
 pub(crate) fn move_value_from_value(sp!(_, v_): Value) -> MoveValue {
     move_value_from_value_(v_)
 }
@@ -8,7 +8,7 @@ use crate::{
     compiled_unit,
     compiled_unit::AnnotatedCompiledUnit,
     diagnostics::{codes::Severity, *},
-    expansion, hlir, interface_generator, naming, parser,
+    expansion, hlir, interface_generator, linters, naming, parser,
     parser::{comments::*, *},
     shared::{
         CompilationEnv, Flags, IndexedPackagePath, NamedAddressMap, NamedAddressMaps,
@@ -157,6 +157,11 @@ impl<'a> Compiler<'a> {
         self
     }
 
+    pub fn set_ide_mode(mut self, value: bool) -> Self {
+        self.flags = self.flags.set_ide_mode(value);
+        self
+    }
+
     pub fn set_interface_files_dir(mut self, dir: String) -> Self {
         assert!(self.interface_files_dir_opt.is_none());
         self.interface_files_dir_opt = Some(dir);
@@ -230,8 +235,9 @@ impl<'a> Compiler<'a> {
     }
 
     pub fn check_and_report(self) -> anyhow::Result<FilesSourceText> {
+        let format = self.flags.message_format();
         let (files, res) = self.check()?;
-        unwrap_or_report_diagnostics(&files, res);
+        unwrap_or_report_diagnostics_with_format(&files, res, format);
         Ok(files)
     }
 
@@ -249,9 +255,10 @@ impl<'a> Compiler<'a> {
     }
 
     pub fn build_and_report(self) -> anyhow::Result<(FilesSourceText, Vec<AnnotatedCompiledUnit>)> {
+        let format = self.flags.message_format();
         let (files, units_res) = self.build()?;
-        let (units, warnings) = unwrap_or_report_diagnostics(&files, units_res);
-        report_warnings(&files, warnings);
+        let (units, warnings) = unwrap_or_report_diagnostics_with_format(&files, units_res, format);
+        report_warnings_with_format(&files, warnings, format);
         Ok((files, units))
     }
 }
@@ -365,17 +372,20 @@ macro_rules! ast_stepped_compilers {
                 }
 
                 pub fn check_and_report(self, files: &FilesSourceText)  {
+                    let format = self.compilation_env.flags().message_format();
                     let errors_result = self.check();
-                    unwrap_or_report_diagnostics(&files, errors_result);
+                    unwrap_or_report_diagnostics_with_format(&files, errors_result, format);
                 }
 
                 pub fn build_and_report(
                     self,
                     files: &FilesSourceText,
                 ) -> Vec<AnnotatedCompiledUnit> {
+                    let format = self.compilation_env.flags().message_format();
                     let units_result = self.build();
-                    let (units, warnings) = unwrap_or_report_diagnostics(&files, units_result);
-                    report_warnings(&files, warnings);
+                    let (units, warnings) =
+                        unwrap_or_report_diagnostics_with_format(&files, units_result, format);
+                    report_warnings_with_format(&files, warnings, format);
                     units
                 }
             }
@@ -777,7 +787,14 @@ fn run(
         }
         PassResult::Naming(nprog) => {
             let tprog = typing::translate::program(compilation_env, pre_compiled_lib, nprog);
-            compilation_env.check_diags_at_or_above_severity(Severity::BlockingError)?;
+            // In IDE mode, the typed AST is kept even if it has errors, so that the language
+            // server can still use it. The errors are reported before lowering it any further.
+            if !compilation_env.flags().ide_mode() {
+                compilation_env.check_diags_at_or_above_severity(Severity::BlockingError)?;
+            }
+            if !compilation_env.has_diags_at_or_above_severity(Severity::BlockingError) {
+                linters::typing_program(compilation_env, &tprog);
+            }
             run(
                 compilation_env,
                 pre_compiled_lib,
@@ -787,6 +804,7 @@ fn run(
             )
         }
         PassResult::Typing(tprog) => {
+            compilation_env.check_diags_at_or_above_severity(Severity::BlockingError)?;
             let hprog = hlir::translate::program(compilation_env, pre_compiled_lib, tprog);
             compilation_env.check_diags_at_or_above_severity(Severity::Bug)?;
             run(
@@ -800,6 +818,8 @@ fn run(
         PassResult::HLIR(hprog) => {
             let cprog = cfgir::translate::program(compilation_env, pre_compiled_lib, hprog);
             compilation_env.check_diags_at_or_above_severity(Severity::NonblockingError)?;
+            linters::cfgir_program(compilation_env, &cprog);
+            compilation_env.check_diags_at_or_above_severity(Severity::NonblockingError)?;
             run(
                 compilation_env,
                 pre_compiled_lib,
@@ -30,6 +30,13 @@ pub const FLAVOR: &str = "flavor";
 
 pub const BYTECODE_VERSION: &str = "bytecode-version";
 
+pub const LINT: &str = "lint";
+
+pub const OPTIMIZE: &str = "optimize";
+pub const OPTIMIZE_SHORT: char = 'O';
+
+pub const MESSAGE_FORMAT: &str = "message-format";
+
 pub const COLOR_MODE_ENV_VAR: &str = "COLOR_MODE";
 
 pub const MOVE_COMPILED_INTERFACES_DIR: &str = "mv_interfaces";
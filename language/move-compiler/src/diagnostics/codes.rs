@@ -182,6 +182,7 @@ codes!(
                 (NOTE: this may become an error in the future)",
             severity: Warning
         },
+        CyclicConstant: { msg: "cyclic constant definition", severity: BlockingError },
     ],
     // errors for ability rules. mostly typing/translate
     AbilitySafety: [
@@ -236,7 +237,17 @@ codes!(
     ],
     Derivation: [
         DeriveFailed: { msg: "attribute derivation failed", severity: BlockingError }
-    ]
+    ],
+    // lints, only reported when linting is enabled. See `crate::linters`
+    Lint: [
+        InvalidLintAllow: { msg: "invalid 'lint_allow' attribute", severity: Warning },
+        RedundantCopy: { msg: "redundant 'copy'", severity: Warning },
+        NeedlessMutRef: { msg: "needless mutable reference", severity: Warning },
+        SelfAssignment: { msg: "self assignment", severity: Warning },
+        ConstantCondition: { msg: "constant condition", severity: Warning },
+        UnnecessaryCast: { msg: "unnecessary cast", severity: Warning },
+        ShadowedLocal: { msg: "shadowed local", severity: Warning },
+    ],
 );
 
 //**************************************************************************************************
@@ -269,6 +280,10 @@ impl DiagnosticInfo {
     pub fn severity(&self) -> Severity {
         self.severity
     }
+
+    pub fn set_severity(&mut self, severity: Severity) {
+        self.severity = severity
+    }
 }
 
 impl Severity {
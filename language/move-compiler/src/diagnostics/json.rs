@@ -0,0 +1,107 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The JSON representation of diagnostics. Lines and columns are 1-based; byte offsets are
+//! 0-based, with the end exclusive.
+
+use super::{codes::Severity, convert_loc, Diagnostic, FileMapping, Suggestion};
+use codespan_reporting::files::{Files, SimpleFiles};
+use move_ir_types::location::Loc;
+use move_symbol_pool::Symbol;
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub(super) struct JsonDiagnostic {
+    code: String,
+    severity: &'static str,
+    message: &'static str,
+    primary_label: JsonLabel,
+    secondary_labels: Vec<JsonLabel>,
+    notes: Vec<String>,
+    suggestions: Vec<JsonSuggestion>,
+}
+
+#[derive(Serialize)]
+struct JsonLabel {
+    span: JsonSpan,
+    message: String,
+}
+
+#[derive(Serialize)]
+struct JsonSuggestion {
+    message: String,
+    span: JsonSpan,
+    replacement: String,
+}
+
+#[derive(Serialize)]
+struct JsonSpan {
+    file: Symbol,
+    byte_start: usize,
+    byte_end: usize,
+    line_start: usize,
+    column_start: usize,
+    line_end: usize,
+    column_end: usize,
+}
+
+pub(super) fn diagnostic(
+    files: &SimpleFiles<Symbol, &str>,
+    file_mapping: &FileMapping,
+    diag: Diagnostic,
+) -> JsonDiagnostic {
+    let Diagnostic {
+        info,
+        primary_label,
+        secondary_labels,
+        notes,
+        suggestions,
+    } = diag;
+    let severity = match info.severity() {
+        Severity::Bug => "bug",
+        Severity::BlockingError | Severity::NonblockingError => "error",
+        Severity::Warning => "warning",
+    };
+    let (code, message) = info.render();
+    let label = |(loc, message)| JsonLabel {
+        span: span(files, file_mapping, loc),
+        message,
+    };
+    JsonDiagnostic {
+        code,
+        severity,
+        message,
+        primary_label: label(primary_label),
+        secondary_labels: secondary_labels.into_iter().map(label).collect(),
+        notes,
+        suggestions: suggestions
+            .into_iter()
+            .map(
+                |Suggestion {
+                     message,
+                     loc,
+                     replacement,
+                 }| JsonSuggestion {
+                    message,
+                    span: span(files, file_mapping, loc),
+                    replacement,
+                },
+            )
+            .collect(),
+    }
+}
+
+fn span(files: &SimpleFiles<Symbol, &str>, file_mapping: &FileMapping, loc: Loc) -> JsonSpan {
+    let (id, range) = convert_loc(file_mapping, loc);
+    let start = files.location(id, range.start).unwrap();
+    let end = files.location(id, range.end).unwrap();
+    JsonSpan {
+        file: *files.get(id).unwrap().name(),
+        byte_start: range.start,
+        byte_end: range.end,
+        line_start: start.line_number,
+        column_start: start.column_number,
+        line_end: end.line_number,
+        column_end: end.column_number,
+    }
+}
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod codes;
+mod json;
 
 use crate::{
     command_line::COLOR_MODE_ENV_VAR,
@@ -20,10 +21,14 @@ use codespan_reporting::{
 use move_command_line_common::{env::read_env_var, files::FileHash};
 use move_ir_types::location::*;
 use move_symbol_pool::Symbol;
+use serde::{Deserialize, Serialize};
 use std::{
     collections::{BTreeMap, HashMap, HashSet},
+    fmt,
+    io::Write,
     iter::FromIterator,
     ops::Range,
+    str::FromStr,
 };
 
 //**************************************************************************************************
@@ -43,6 +48,29 @@ pub struct Diagnostic {
     primary_label: (Loc, String),
     secondary_labels: Vec<(Loc, String)>,
     notes: Vec<String>,
+    suggestions: Vec<Suggestion>,
+}
+
+/// An edit fixing the problem reported by a diagnostic: the source at `loc` is to be replaced by
+/// `replacement`. Suggestions are only part of the JSON output.
+#[derive(PartialEq, Eq, Clone, Debug, Hash)]
+pub struct Suggestion {
+    message: String,
+    loc: Loc,
+    replacement: String,
+}
+
+/// How diagnostics are reported
+#[derive(
+    PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, Default, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum MessageFormat {
+    /// Reports rendered for people to read
+    #[default]
+    Human,
+    /// One JSON object per diagnostic and line, with stable codes, spans and suggested edits
+    Json,
 }
 
 #[derive(PartialEq, Eq, Hash, Clone, Debug, Default)]
@@ -56,39 +84,83 @@ pub struct Diagnostics {
 //**************************************************************************************************
 
 pub fn report_diagnostics(files: &FilesSourceText, diags: Diagnostics) -> ! {
+    report_diagnostics_with_format(files, diags, MessageFormat::Human)
+}
+
+pub fn report_diagnostics_with_format(
+    files: &FilesSourceText,
+    diags: Diagnostics,
+    format: MessageFormat,
+) -> ! {
     let should_exit = true;
-    report_diagnostics_impl(files, diags, should_exit);
+    report_diagnostics_impl(files, diags, format, should_exit);
     std::process::exit(1)
 }
 
 pub fn report_warnings(files: &FilesSourceText, warnings: Diagnostics) {
+    report_warnings_with_format(files, warnings, MessageFormat::Human)
+}
+
+pub fn report_warnings_with_format(
+    files: &FilesSourceText,
+    warnings: Diagnostics,
+    format: MessageFormat,
+) {
     if warnings.is_empty() {
         return;
     }
     debug_assert!(warnings.max_severity().unwrap() == Severity::Warning);
-    report_diagnostics_impl(files, warnings, false)
+    report_diagnostics_impl(files, warnings, format, false)
 }
 
-fn report_diagnostics_impl(files: &FilesSourceText, diags: Diagnostics, should_exit: bool) {
-    let color_choice = match read_env_var(COLOR_MODE_ENV_VAR).as_str() {
+/// The color choice for reporting diagnostics, as configured by the `COLOR_MODE` environment
+/// variable
+pub fn color_choice() -> ColorChoice {
+    match read_env_var(COLOR_MODE_ENV_VAR).as_str() {
         "NONE" => ColorChoice::Never,
         "ANSI" => ColorChoice::AlwaysAnsi,
         "ALWAYS" => ColorChoice::Always,
         _ => ColorChoice::Auto,
-    };
-    let mut writer = StandardStream::stderr(color_choice);
-    output_diagnostics(&mut writer, files, diags);
+    }
+}
+
+fn report_diagnostics_impl(
+    files: &FilesSourceText,
+    diags: Diagnostics,
+    format: MessageFormat,
+    should_exit: bool,
+) {
+    match format {
+        MessageFormat::Human => {
+            let mut writer = StandardStream::stderr(color_choice());
+            output_diagnostics(&mut writer, files, diags);
+        }
+        // Like cargo's, JSON diagnostics go to stdout, so that they are not mixed with the
+        // progress messages on stderr
+        MessageFormat::Json => {
+            let buffer = report_diagnostics_to_json_buffer(files, diags);
+            std::io::stdout().write_all(&buffer).unwrap();
+        }
+    }
     if should_exit {
         std::process::exit(1);
     }
 }
 
 pub fn unwrap_or_report_diagnostics<T>(files: &FilesSourceText, res: Result<T, Diagnostics>) -> T {
+    unwrap_or_report_diagnostics_with_format(files, res, MessageFormat::Human)
+}
+
+pub fn unwrap_or_report_diagnostics_with_format<T>(
+    files: &FilesSourceText,
+    res: Result<T, Diagnostics>,
+    format: MessageFormat,
+) -> T {
     match res {
         Ok(t) => t,
         Err(diags) => {
             assert!(!diags.is_empty());
-            report_diagnostics(files, diags)
+            report_diagnostics_with_format(files, diags, format)
         }
     }
 }
@@ -105,17 +177,49 @@ pub fn report_diagnostics_to_color_buffer(files: &FilesSourceText, diags: Diagno
     writer.into_inner()
 }
 
-fn output_diagnostics<W: WriteColor>(
-    writer: &mut W,
-    sources: &FilesSourceText,
+/// Renders the diagnostics as JSON, one object per line
+pub fn report_diagnostics_to_json_buffer(files: &FilesSourceText, diags: Diagnostics) -> Vec<u8> {
+    let (files, file_mapping) = simple_files(files);
+    let mut buffer = vec![];
+    for diag in sorted_unique(diags) {
+        let json = json::diagnostic(&files, &file_mapping, diag);
+        serde_json::to_writer(&mut buffer, &json).unwrap();
+        buffer.push(b'\n');
+    }
+    buffer
+}
+
+/// Reports the diagnostics to `buffer` in the given format, with colors if `colorize` is set and
+/// the format supports them
+pub fn report_diagnostics_to_buffer_with_format(
+    files: &FilesSourceText,
     diags: Diagnostics,
-) {
+    format: MessageFormat,
+    colorize: bool,
+) -> Vec<u8> {
+    match format {
+        MessageFormat::Human if colorize => report_diagnostics_to_color_buffer(files, diags),
+        MessageFormat::Human => report_diagnostics_to_buffer(files, diags),
+        MessageFormat::Json => report_diagnostics_to_json_buffer(files, diags),
+    }
+}
+
+fn simple_files(sources: &FilesSourceText) -> (SimpleFiles<Symbol, &str>, FileMapping) {
     let mut files = SimpleFiles::new();
     let mut file_mapping = HashMap::new();
     for (fhash, (fname, source)) in sources {
         let id = files.add(*fname, source.as_str());
         file_mapping.insert(*fhash, id);
     }
+    (files, file_mapping)
+}
+
+fn output_diagnostics<W: WriteColor>(
+    writer: &mut W,
+    sources: &FilesSourceText,
+    diags: Diagnostics,
+) {
+    let (files, file_mapping) = simple_files(sources);
     render_diagnostics(writer, &files, &file_mapping, diags);
 }
 
@@ -123,22 +227,27 @@ fn render_diagnostics(
     writer: &mut dyn WriteColor,
     files: &SimpleFiles<Symbol, &str>,
     file_mapping: &FileMapping,
-    mut diags: Diagnostics,
+    diags: Diagnostics,
 ) {
+    for diag in sorted_unique(diags) {
+        let rendered = render_diagnostic(file_mapping, diag);
+        emit(writer, &Config::default(), files, &rendered).unwrap()
+    }
+}
+
+/// The diagnostics in the order of their primary locations, without duplicates
+fn sorted_unique(mut diags: Diagnostics) -> Vec<Diagnostic> {
     diags.diagnostics.sort_by(|e1, e2| {
         let loc1: &Loc = &e1.primary_label.0;
         let loc2: &Loc = &e2.primary_label.0;
         loc1.cmp(loc2)
     });
     let mut seen: HashSet<Diagnostic> = HashSet::new();
-    for diag in diags.diagnostics {
-        if seen.contains(&diag) {
-            continue;
-        }
-        seen.insert(diag.clone());
-        let rendered = render_diagnostic(file_mapping, diag);
-        emit(writer, &Config::default(), files, &rendered).unwrap()
-    }
+    diags
+        .diagnostics
+        .into_iter()
+        .filter(|diag| seen.insert(diag.clone()))
+        .collect()
 }
 
 fn convert_loc(file_mapping: &FileMapping, loc: Loc) -> (FileId, Range<usize>) {
@@ -162,6 +271,7 @@ fn render_diagnostic(
         primary_label,
         secondary_labels,
         notes,
+        suggestions: _,
     } = diag;
     let mut diag = csr::diagnostic::Diagnostic::new(info.severity().into_codespan_severity());
     let (code, message) = info.render();
@@ -236,6 +346,7 @@ impl Diagnostics {
         self,
     ) -> Vec<(
         codespan_reporting::diagnostic::Severity,
+        String,
         &'static str,
         (Loc, String),
         Vec<(Loc, String)>,
@@ -248,9 +359,11 @@ impl Diagnostics {
                 primary_label,
                 secondary_labels,
                 notes,
+                suggestions: _,
             } = diag;
             let csr_diag = (
                 info.severity().into_codespan_severity(),
+                info.clone().render().0,
                 info.message(),
                 primary_label,
                 secondary_labels,
@@ -277,6 +390,7 @@ impl Diagnostic {
                 .map(|(loc, msg)| (loc, msg.to_string()))
                 .collect(),
             notes: notes.into_iter().map(|msg| msg.to_string()).collect(),
+            suggestions: vec![],
         }
     }
 
@@ -285,6 +399,15 @@ impl Diagnostic {
         self
     }
 
+    pub fn set_severity(mut self, severity: Severity) -> Self {
+        self.info.set_severity(severity);
+        self
+    }
+
+    pub fn primary_loc(&self) -> Loc {
+        self.primary_label.0
+    }
+
     #[allow(unused)]
     pub fn add_secondary_labels(
         &mut self,
@@ -314,6 +437,15 @@ impl Diagnostic {
     pub fn add_note(&mut self, msg: impl ToString) {
         self.notes.push(msg.to_string())
     }
+
+    /// Suggests replacing the source at `loc` by `replacement` to fix the problem
+    pub fn add_suggestion(&mut self, msg: impl ToString, loc: Loc, replacement: impl ToString) {
+        self.suggestions.push(Suggestion {
+            message: msg.to_string(),
+            loc,
+            replacement: replacement.to_string(),
+        })
+    }
 }
 
 #[macro_export]
@@ -364,6 +496,30 @@ impl From<Vec<Diagnostic>> for Diagnostics {
     }
 }
 
+impl fmt::Display for MessageFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MessageFormat::Human => write!(f, "human"),
+            MessageFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
+impl FromStr for MessageFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(MessageFormat::Human),
+            "json" => Ok(MessageFormat::Json),
+            _ => Err(format!(
+                "unknown message format '{}', expected 'human' or 'json'",
+                s
+            )),
+        }
+    }
+}
+
 impl From<Option<Diagnostic>> for Diagnostics {
     fn from(diagnostic_opt: Option<Diagnostic>) -> Self {
         Diagnostics::from(diagnostic_opt.map_or_else(Vec::new, |diag| vec![diag]))
@@ -13,7 +13,11 @@ use crate::{
     parser::ast::{
         self as P, Ability, ConstantName, Field, FunctionName, ModuleName, StructName, Var,
     },
-    shared::{known_attributes::AttributePosition, unique_map::UniqueMap, *},
+    shared::{
+        known_attributes::{AttributePosition, TestingAttribute},
+        unique_map::UniqueMap,
+        *,
+    },
     FullyCompiledProgram,
 };
 use move_command_line_common::parser::{parse_u16, parse_u256, parse_u32};
@@ -557,7 +561,14 @@ fn flatten_attributes(
     let all_attrs = attributes
         .into_iter()
         .flat_map(|attrs| attrs.value)
-        .flat_map(|attr| attribute(context, attr_position, attr))
+        .flat_map(|attr| {
+            attribute(
+                context,
+                attr_position,
+                /* in_expected_failure */ false,
+                attr,
+            )
+        })
         .collect::<Vec<_>>();
     unique_attributes(context, attr_position, false, all_attrs)
 }
@@ -628,6 +639,7 @@ fn unique_attributes(
 fn attribute(
     context: &mut Context,
     attr_position: AttributePosition,
+    in_expected_failure: bool,
     sp!(loc, attribute_): P::Attribute,
 ) -> Option<E::Attribute> {
     use E::Attribute_ as EA;
@@ -636,11 +648,18 @@ fn attribute(
         loc,
         match attribute_ {
             PA::Name(n) => EA::Name(n),
+            // The `location` of an expected failure names a module, so it is resolved as one
+            PA::Assigned(n, v)
+                if in_expected_failure && n.value.as_str() == TestingAttribute::ERROR_LOCATION =>
+            {
+                EA::Assigned(n, Box::new(location_attribute_value(context, *v)?))
+            }
             PA::Assigned(n, v) => EA::Assigned(n, Box::new(attribute_value(context, *v)?)),
             PA::Parameterized(n, sp!(_, pattrs_)) => {
+                let is_expected_failure = n.value.as_str() == TestingAttribute::EXPECTED_FAILURE;
                 let attrs = pattrs_
                     .into_iter()
-                    .map(|a| attribute(context, attr_position, a))
+                    .map(|a| attribute(context, attr_position, is_expected_failure, a))
                     .collect::<Option<Vec<_>>>()?;
                 EA::Parameterized(n, unique_attributes(context, attr_position, true, attrs))
             }
@@ -663,6 +682,61 @@ fn attribute_value(
     ))
 }
 
+fn location_attribute_value(
+    context: &mut Context,
+    sp!(loc, avalue_): P::AttributeValue,
+) -> Option<E::AttributeValue> {
+    use E::AttributeValue_ as EV;
+    use P::{AttributeValue_ as PV, LeadingNameAccess_ as LN, NameAccessChain_ as PN};
+    Some(sp(
+        loc,
+        match avalue_ {
+            PV::ModuleAccess(sp!(ident_loc, PN::Two(sp!(aloc, LN::AnonymousAddress(a)), n))) => {
+                let addr = Address::Numerical(None, sp(aloc, a));
+                let mident = sp(ident_loc, ModuleIdent_::new(addr, ModuleName(n)));
+                check_module_exists(context, &mident);
+                EV::Module(mident)
+            }
+            // A leading name is a module alias if one is in scope, and otherwise it is treated as
+            // a named address, e.g. `std::vector`
+            PV::ModuleAccess(sp!(ident_loc, PN::Two(sp!(aloc, LN::Name(n1)), n2)))
+                if context.aliases.module_alias_get(&n1).is_none()
+                    && context
+                        .named_address_mapping
+                        .as_ref()
+                        .map(|m| m.contains_key(&n1.value))
+                        .unwrap_or(false) =>
+            {
+                let addr = address(
+                    context,
+                    /* suggest_declaration */ false,
+                    sp(aloc, LN::Name(n1)),
+                );
+                let mident = sp(ident_loc, ModuleIdent_::new(addr, ModuleName(n2)));
+                check_module_exists(context, &mident);
+                EV::Module(mident)
+            }
+            PV::ModuleAccess(sp!(ident_loc, PN::One(n)))
+                if context.aliases.member_alias_get(&n).is_none()
+                    && context.aliases.module_alias_get(&n).is_some() =>
+            {
+                let sp!(_, mident_) = context.aliases.module_alias_get(&n).unwrap();
+                EV::Module(sp(ident_loc, mident_))
+            }
+            avalue_ => return attribute_value(context, sp(loc, avalue_)),
+        },
+    ))
+}
+
+fn check_module_exists(context: &mut Context, mident: &ModuleIdent) {
+    if !context.module_members.contains_key(mident) {
+        context.env.add_diag(diag!(
+            NameResolution::UnboundModule,
+            (mident.loc, format!("Unbound module '{}'", mident))
+        ));
+    }
+}
+
 //**************************************************************************************************
 // Aliases
 //**************************************************************************************************
@@ -1738,7 +1812,7 @@ fn sequence_item(context: &mut Context, sp!(loc, pitem_): P::SequenceItem) -> E:
             match b_opt {
                 None => {
                     assert!(context.env.has_errors());
-                    ES::Seq(sp(loc, E::Exp_::UnresolvedError))
+                    ES::Seq(sp(loc, unresolved_exp(context, loc, vec![e])))
                 }
                 Some(b) => ES::Bind(b, e),
             }
@@ -1799,7 +1873,7 @@ fn exp_(context: &mut Context, sp!(loc, pe_): P::Exp) -> E::Exp {
                 Some(en) => EE::Call(en, is_macro, tys_opt, ers),
                 None => {
                     assert!(context.env.has_errors());
-                    EE::UnresolvedError
+                    unresolved_exp(context, loc, ers.value)
                 }
             }
         }
@@ -1815,7 +1889,8 @@ fn exp_(context: &mut Context, sp!(loc, pe_): P::Exp) -> E::Exp {
                 Some(en) => EE::Pack(en, tys_opt, efields),
                 None => {
                     assert!(context.env.has_errors());
-                    EE::UnresolvedError
+                    let es = efields.into_iter().map(|(_, (_, e))| e).collect();
+                    unresolved_exp(context, loc, es)
                 }
             }
         }
@@ -1890,7 +1965,7 @@ fn exp_(context: &mut Context, sp!(loc, pe_): P::Exp) -> E::Exp {
             match l_opt {
                 None => {
                     assert!(context.env.has_errors());
-                    EE::UnresolvedError
+                    unresolved_exp(context, loc, vec![*er])
                 }
                 Some(LValue::Assigns(al)) => EE::Assign(al, er),
                 Some(LValue::Mutate(el)) => EE::Mutate(el, er),
@@ -1915,10 +1990,7 @@ fn exp_(context: &mut Context, sp!(loc, pe_): P::Exp) -> E::Exp {
                     "`{}` operator only allowed in specifications",
                     op.value.symbol()
                 );
-                context
-                    .env
-                    .add_diag(diag!(Syntax::SpecContextRestricted, (loc, msg)));
-                EE::UnresolvedError
+                spec_restricted_exp(context, loc, msg, vec![*pl, *pr])
             } else {
                 EE::BinopExp(exp(context, *pl), op, exp(context, *pr))
             }
@@ -1937,10 +2009,7 @@ fn exp_(context: &mut Context, sp!(loc, pe_): P::Exp) -> E::Exp {
                 EE::Index(exp(context, *e), exp(context, *i))
             } else {
                 let msg = "`_[_]` index operator only allowed in specifications";
-                context
-                    .env
-                    .add_diag(diag!(Syntax::SpecContextRestricted, (loc, msg)));
-                EE::UnresolvedError
+                spec_restricted_exp(context, loc, msg.to_owned(), vec![*e, *i])
             }
         }
         PE::Annotate(e, ty) => EE::Annotate(exp(context, *e), type_(context, ty)),
@@ -1955,11 +2024,42 @@ fn exp_(context: &mut Context, sp!(loc, pe_): P::Exp) -> E::Exp {
             let (spec_id, unbound_names) = context.bind_exp_spec(spec_block);
             EE::Spec(spec_id, unbound_names)
         }
-        PE::UnresolvedError => panic!("ICE error should have been thrown"),
+        // Left by the parser in place of code with syntax errors
+        PE::UnresolvedError => {
+            assert!(context.env.has_errors());
+            EE::UnresolvedError
+        }
     };
     sp(loc, e_)
 }
 
+fn spec_restricted_exp(context: &mut Context, loc: Loc, msg: String, pes: Vec<P::Exp>) -> E::Exp_ {
+    context
+        .env
+        .add_diag(diag!(Syntax::SpecContextRestricted, (loc, msg)));
+    let es = exps(context, pes);
+    unresolved_exp(context, loc, es)
+}
+
+/// Stands in for an expression that failed to expand. In IDE mode, its sub-expressions are kept,
+/// annotated with the error type, so that later passes still resolve the names used in them.
+fn unresolved_exp(context: &Context, loc: Loc, parts: Vec<E::Exp>) -> E::Exp_ {
+    use E::Exp_ as EE;
+    if !context.env.flags().ide_mode() || parts.is_empty() {
+        return EE::UnresolvedError;
+    }
+    let mut seq: E::Sequence = parts
+        .into_iter()
+        .map(|e| {
+            let eloc = e.loc;
+            let annotated = EE::Annotate(Box::new(e), sp(eloc, E::Type_::UnresolvedError));
+            sp(eloc, E::SequenceItem_::Seq(sp(eloc, annotated)))
+        })
+        .collect();
+    seq.push_back(sp(loc, E::SequenceItem_::Seq(sp(loc, EE::UnresolvedError))));
+    EE::Block(seq)
+}
+
 fn exp_dotted(context: &mut Context, sp!(loc, pdotted_): P::Exp) -> Option<E::ExpDotted> {
     use E::ExpDotted_ as EE;
     use P::Exp_ as PE;
@@ -2235,6 +2335,10 @@ fn assign(context: &mut Context, sp!(loc, e_): P::Exp) -> Option<E::LValue> {
             let efields = assign_unpack_fields(context, loc, pfields)?;
             EL::Unpack(en, tys_opt, efields)
         }
+        PE::UnresolvedError => {
+            assert!(context.env.has_errors());
+            return None;
+        }
         _ => {
             context.env.add_diag(diag!(
                 Syntax::InvalidLValue,
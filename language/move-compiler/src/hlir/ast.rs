@@ -311,7 +311,7 @@ pub enum UnannotatedExp_ {
         from_user: bool,
         var: Var,
     },
-    Constant(ConstantName),
+    Constant(Option<ModuleIdent>, ConstantName),
 
     ModuleCall(Box<ModuleCall>),
     Builtin(Box<BuiltinFunction>, Box<Exp>),
@@ -1088,7 +1088,8 @@ impl AstDebug for UnannotatedExp_ {
                 from_user: true,
                 var: v,
             } => w.write(&format!("copy@{}", v)),
-            E::Constant(c) => w.write(&format!("{}", c)),
+            E::Constant(None, c) => w.write(&format!("{}", c)),
+            E::Constant(Some(m), c) => w.write(format!("{}::{}", m, c)),
             E::ModuleCall(mcall) => {
                 mcall.ast_debug(w);
             }
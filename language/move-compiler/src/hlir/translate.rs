@@ -1131,10 +1131,9 @@ fn exp_impl(
             },
         },
         TE::Value(ev) => HE::Value(value(context, ev)),
-        TE::Constant(_m, c) => {
-            // Currently only private constants exist
-            HE::Constant(c)
-        }
+        // Function bodies can only use constants of their own module. Constant definitions can use
+        // constants of other modules, whose values are filled in when the constant is computed
+        TE::Constant(m, c) => HE::Constant(m, c),
         TE::Move { from_user, var } => {
             let annotation = if from_user {
                 MoveOpAnnotation::FromUser
@@ -1809,12 +1808,14 @@ fn check_trailing_unit(context: &mut Context, block: &mut Block) {
             let unreachable_msg = "Any code after this expression will not be reached";
             let info_msg = "A trailing ';' in an expression block implicitly adds a '()' value \
                         after the semicolon. That '()' value will not be reachable";
-            $context.env.add_diag(diag!(
+            let mut diag = diag!(
                 UnusedItem::TrailingSemi,
                 ($uloc, semi_msg),
                 ($loc, unreachable_msg),
                 ($uloc, info_msg),
-            ));
+            );
+            diag.add_suggestion("Remove the trailing ';'", $uloc, "");
+            $context.env.add_diag(diag);
             block.pop_back();
         }};
     }
@@ -1934,9 +1935,9 @@ fn check_unused_locals(
                 vstr
             )
         };
-        context
-            .env
-            .add_diag(diag!(UnusedItem::Variable, (loc, msg)));
+        let mut diag = diag!(UnusedItem::Variable, (loc, msg));
+        diag.add_suggestion("Prefix with an underscore", loc, format!("_{}", vstr));
+        context.env.add_diag(diag);
     }
     for v in &unused {
         locals.remove(v);
@@ -0,0 +1,155 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{CfgirLinter, NEEDLESS_MUT_REF};
+use crate::{
+    cfgir::ast as G,
+    diag,
+    hlir::ast::{
+        Command, Command_, Exp, ExpListItem, LValue, LValue_, SingleType_, UnannotatedExp_,
+    },
+    parser::ast::Var,
+    shared::CompilationEnv,
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+//**************************************************************************************************
+// Needless mutable reference
+//**************************************************************************************************
+
+/// A `&mut` parameter that is only ever frozen, dereferenced, or immutably borrowed from
+pub struct NeedlessMutRef;
+
+struct Usage {
+    // `&mut` parameters that are read at least once
+    read: BTreeSet<Var>,
+    // `&mut` parameters that are used mutably, or in a way that might need mutability
+    mutated: BTreeSet<Var>,
+}
+
+impl CfgirLinter for NeedlessMutRef {
+    fn visit_function(&mut self, env: &mut CompilationEnv, fdef: &G::Function) {
+        let blocks = match &fdef.body.value {
+            G::FunctionBody_::Native => return,
+            G::FunctionBody_::Defined { blocks, .. } => blocks,
+        };
+        let mut_params = fdef
+            .signature
+            .parameters
+            .iter()
+            .filter(|(v, ty)| {
+                !v.starts_with_underscore() && matches!(ty.value, SingleType_::Ref(true, _))
+            })
+            .map(|(v, ty)| (*v, ty.loc))
+            .collect::<BTreeMap<_, _>>();
+        if mut_params.is_empty() {
+            return;
+        }
+        let mut usage = Usage {
+            read: BTreeSet::new(),
+            mutated: BTreeSet::new(),
+        };
+        for block in blocks.values() {
+            for cmd in block {
+                command(&mut usage, cmd)
+            }
+        }
+        for (param, ty_loc) in mut_params {
+            if usage.read.contains(&param) && !usage.mutated.contains(&param) {
+                let msg = format!(
+                    "The parameter '{}' is never used mutably. Consider an immutable reference \
+                     '&' instead",
+                    param
+                );
+                env.add_lint_diag(
+                    &NEEDLESS_MUT_REF,
+                    diag!(
+                        Lint::NeedlessMutRef,
+                        (param.0.loc, msg),
+                        (ty_loc, "Declared as a mutable reference here"),
+                    ),
+                )
+            }
+        }
+    }
+}
+
+fn command(usage: &mut Usage, sp!(_, cmd_): &Command) {
+    use Command_ as C;
+    match cmd_ {
+        C::Assign(lvalues, e) => {
+            lvalues.iter().for_each(|l| lvalue(usage, l));
+            exp(usage, e)
+        }
+        C::Mutate(el, er) => {
+            exp(usage, el);
+            exp(usage, er)
+        }
+        C::Abort(e)
+        | C::Return { exp: e, .. }
+        | C::IgnoreAndPop { exp: e, .. }
+        | C::JumpIf { cond: e, .. } => exp(usage, e),
+        C::Jump { .. } | C::Break | C::Continue => (),
+    }
+}
+
+fn lvalue(usage: &mut Usage, sp!(_, l_): &LValue) {
+    match l_ {
+        LValue_::Ignore => (),
+        // reassigning the parameter, be conservative
+        LValue_::Var(v, _) => {
+            usage.mutated.insert(*v);
+        }
+        LValue_::Unpack(_, _, fields) => fields.iter().for_each(|(_, l)| lvalue(usage, l)),
+    }
+}
+
+/// The local if `e` is a use of it
+fn used_local(e: &Exp) -> Option<Var> {
+    match &e.exp.value {
+        UnannotatedExp_::Copy { var, .. } | UnannotatedExp_::Move { var, .. } => Some(*var),
+        _ => None,
+    }
+}
+
+fn exp(usage: &mut Usage, e: &Exp) {
+    use UnannotatedExp_ as E;
+    match &e.exp.value {
+        E::Unit { .. }
+        | E::Value(_)
+        | E::Constant(_, _)
+        | E::Unreachable
+        | E::Spec(_, _)
+        | E::UnresolvedError => (),
+
+        // any other use of the reference, e.g. passing it to a function, returning it, or writing
+        // through it, might need it to be mutable
+        E::Copy { var, .. } | E::Move { var, .. } | E::BorrowLocal(_, var) => {
+            usage.mutated.insert(*var);
+        }
+
+        E::Freeze(inner) | E::Dereference(inner) | E::Borrow(false, inner, _) => {
+            match used_local(inner) {
+                Some(var) => {
+                    usage.read.insert(var);
+                }
+                None => exp(usage, inner),
+            }
+        }
+
+        E::ModuleCall(call) => exp(usage, &call.arguments),
+        E::Builtin(_, e)
+        | E::Vector(_, _, _, e)
+        | E::UnaryExp(_, e)
+        | E::Borrow(true, e, _)
+        | E::Cast(e, _) => exp(usage, e),
+        E::BinopExp(e1, _, e2) => {
+            exp(usage, e1);
+            exp(usage, e2)
+        }
+        E::Pack(_, _, fields) => fields.iter().for_each(|(_, _, e)| exp(usage, e)),
+        E::ExpList(items) => items.iter().for_each(|item| match item {
+            ExpListItem::Single(e, _) | ExpListItem::Splat(_, e, _) => exp(usage, e),
+        }),
+    }
+}
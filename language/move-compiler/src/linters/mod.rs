@@ -0,0 +1,459 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Lints are optional checks over the typed AST and the CFGIR of the source modules and scripts,
+//! run when linting is enabled with `Flags::set_lint`. Each lint has a name, a code in the `Lint`
+//! diagnostic category, and a default `LintLevel` that can be overridden with a `LintConfig`.
+//! A lint can be silenced for a module, script, or function with `#[lint_allow(<name>, ...)]`.
+//!
+//! A new lint implements `TypingLinter` or `CfgirLinter`, is registered in `typing_linters` or
+//! `cfgir_linters`, and is listed in `ALL_LINTS`.
+
+mod cfgir_lints;
+mod typing_lints;
+
+use crate::{
+    cfgir::ast as G,
+    diag,
+    expansion::ast as E,
+    parser::ast::FunctionName,
+    shared::{
+        format_comma,
+        known_attributes::{KnownAttribute, LintAttribute},
+        CompilationEnv, Identifier,
+    },
+    typing::ast as T,
+};
+use move_ir_types::location::*;
+use move_symbol_pool::Symbol;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    str::FromStr,
+};
+
+//**************************************************************************************************
+// Levels and configuration
+//**************************************************************************************************
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LintLevel {
+    /// The lint is not reported
+    Allow,
+    /// The lint is reported as a warning
+    Warn,
+    /// The lint is reported as an error
+    Deny,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LintInfo {
+    pub name: &'static str,
+    pub default_level: LintLevel,
+    pub description: &'static str,
+}
+
+/// Lint levels that override the default level of each lint
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LintConfig {
+    levels: BTreeMap<String, LintLevel>,
+    // If set, only the modules and scripts of this package are linted
+    package: Option<Symbol>,
+}
+
+pub const REDUNDANT_COPY: LintInfo = LintInfo {
+    name: "redundant_copy",
+    default_level: LintLevel::Warn,
+    description: "'copy' of a local that is not used afterwards, where a 'move' would do",
+};
+
+pub const NEEDLESS_MUT_REF: LintInfo = LintInfo {
+    name: "needless_mut_ref",
+    default_level: LintLevel::Warn,
+    description: "'&mut' parameter that is only ever read",
+};
+
+pub const SELF_ASSIGNMENT: LintInfo = LintInfo {
+    name: "self_assignment",
+    default_level: LintLevel::Warn,
+    description: "assignment of a local to itself",
+};
+
+pub const CONSTANT_CONDITION: LintInfo = LintInfo {
+    name: "constant_condition",
+    default_level: LintLevel::Warn,
+    description: "'if' or 'while' condition that is always true or always false",
+};
+
+pub const UNNECESSARY_CAST: LintInfo = LintInfo {
+    name: "unnecessary_cast",
+    default_level: LintLevel::Warn,
+    description: "cast of a value to the type it already has",
+};
+
+pub const SHADOWED_LOCAL: LintInfo = LintInfo {
+    name: "shadowed_local",
+    default_level: LintLevel::Allow,
+    description: "'let' that shadows a local or parameter that is still in scope",
+};
+
+pub const ALL_LINTS: &[LintInfo] = &[
+    REDUNDANT_COPY,
+    NEEDLESS_MUT_REF,
+    SELF_ASSIGNMENT,
+    CONSTANT_CONDITION,
+    UNNECESSARY_CAST,
+    SHADOWED_LOCAL,
+];
+
+pub fn find_lint(name: &str) -> Option<&'static LintInfo> {
+    ALL_LINTS.iter().find(|lint| lint.name == name)
+}
+
+impl LintLevel {
+    pub const ALLOW: &'static str = "allow";
+    pub const WARN: &'static str = "warn";
+    pub const DENY: &'static str = "deny";
+}
+
+impl FromStr for LintLevel {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        Ok(match s {
+            Self::ALLOW => Self::Allow,
+            Self::WARN => Self::Warn,
+            Self::DENY => Self::Deny,
+            _ => anyhow::bail!(
+                "Invalid lint level '{}'. Expected one of '{}', '{}', or '{}'",
+                s,
+                Self::ALLOW,
+                Self::WARN,
+                Self::DENY
+            ),
+        })
+    }
+}
+
+impl fmt::Display for LintLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Allow => write!(f, "{}", Self::ALLOW),
+            Self::Warn => write!(f, "{}", Self::WARN),
+            Self::Deny => write!(f, "{}", Self::DENY),
+        }
+    }
+}
+
+impl LintConfig {
+    pub fn new(levels: BTreeMap<String, LintLevel>) -> Self {
+        Self {
+            levels,
+            package: None,
+        }
+    }
+
+    /// Restricts linting to the modules and scripts of `package`, e.g. to skip dependencies
+    /// compiled as targets
+    pub fn set_package(self, package: Symbol) -> Self {
+        Self {
+            package: Some(package),
+            ..self
+        }
+    }
+
+    fn lints_package(&self, package: Option<Symbol>) -> bool {
+        self.package.is_none() || self.package == package
+    }
+
+    /// A configuration reporting every lint at `level`
+    pub fn all(level: LintLevel) -> Self {
+        Self::new(
+            ALL_LINTS
+                .iter()
+                .map(|lint| (lint.name.to_string(), level))
+                .collect(),
+        )
+    }
+
+    pub fn level(&self, lint: &LintInfo) -> LintLevel {
+        self.levels
+            .get(lint.name)
+            .copied()
+            .unwrap_or(lint.default_level)
+    }
+}
+
+//**************************************************************************************************
+// Linters
+//**************************************************************************************************
+
+/// A lint over the typed AST. The hooks are called for every function of the source modules and
+/// scripts, visiting expressions before their subexpressions
+pub trait TypingLinter {
+    fn visit_function(&mut self, _env: &mut CompilationEnv, _fdef: &T::Function) {}
+
+    fn visit_exp(&mut self, _env: &mut CompilationEnv, _e: &T::Exp) {}
+
+    /// Called for the locals bound by a `let`, after visiting its right-hand side
+    fn visit_bind(&mut self, _env: &mut CompilationEnv, _lvalues: &T::LValueList) {}
+
+    fn enter_scope(&mut self) {}
+
+    fn exit_scope(&mut self) {}
+}
+
+/// A lint over the CFGIR, after borrow checking and optimization, called for every function of
+/// the source modules and scripts
+pub trait CfgirLinter {
+    fn visit_function(&mut self, env: &mut CompilationEnv, fdef: &G::Function);
+}
+
+fn typing_linters() -> Vec<Box<dyn TypingLinter>> {
+    vec![
+        Box::new(typing_lints::SelfAssignment),
+        Box::new(typing_lints::ConstantCondition),
+        Box::new(typing_lints::UnnecessaryCast),
+        Box::new(typing_lints::ShadowedLocal::default()),
+    ]
+}
+
+fn cfgir_linters() -> Vec<Box<dyn CfgirLinter>> {
+    vec![Box::new(cfgir_lints::NeedlessMutRef)]
+}
+
+//**************************************************************************************************
+// Entry points
+//**************************************************************************************************
+
+/// Registers the lint scopes of the source functions, then runs the typed AST lints over them.
+/// The scopes must be registered before any lint is reported, including the lints reported while
+/// translating to the CFGIR
+pub fn typing_program(env: &mut CompilationEnv, prog: &T::Program) {
+    if !env.flags().is_linting() {
+        return;
+    }
+    let config = env.flags().lint_config().clone();
+    let mut functions = vec![];
+    for (_, mdef) in prog.modules.key_cloned_iter() {
+        if !mdef.is_source_module || !config.lints_package(mdef.package_name) {
+            continue;
+        }
+        let module_allowed = allowed_lints(env, &mdef.attributes);
+        for (name, fdef) in mdef.functions.key_cloned_iter() {
+            add_function_scope(env, &module_allowed, name, fdef);
+            functions.push(fdef)
+        }
+    }
+    for script in prog.scripts.values() {
+        if !config.lints_package(script.package_name) {
+            continue;
+        }
+        let script_allowed = allowed_lints(env, &script.attributes);
+        add_function_scope(env, &script_allowed, script.function_name, &script.function);
+        functions.push(&script.function)
+    }
+
+    // lints assume a well typed program
+    if env.has_errors() {
+        return;
+    }
+    let mut linters = typing_linters();
+    for fdef in functions {
+        for linter in &mut linters {
+            linter.visit_function(env, fdef)
+        }
+        if let T::FunctionBody_::Defined(seq) = &fdef.body.value {
+            let mut context = TypingContext {
+                env,
+                linters: &mut linters,
+            };
+            context.sequence(seq)
+        }
+    }
+}
+
+/// Runs the CFGIR lints over the source functions
+pub fn cfgir_program(env: &mut CompilationEnv, prog: &G::Program) {
+    if !env.flags().is_linting() || env.has_errors() {
+        return;
+    }
+    let config = env.flags().lint_config();
+    let module_functions = prog
+        .modules
+        .key_cloned_iter()
+        .filter(|(_, mdef)| mdef.is_source_module && config.lints_package(mdef.package_name))
+        .flat_map(|(_, mdef)| mdef.functions.key_cloned_iter().map(|(_, fdef)| fdef));
+    let script_functions = prog
+        .scripts
+        .values()
+        .filter(|script| config.lints_package(script.package_name))
+        .map(|script| &script.function);
+    let functions = module_functions.chain(script_functions).collect::<Vec<_>>();
+    let mut linters = cfgir_linters();
+    for fdef in functions {
+        for linter in &mut linters {
+            linter.visit_function(env, fdef)
+        }
+    }
+}
+
+/// Lints are only reported within a source function, from its name to the end of its body
+fn add_function_scope(
+    env: &mut CompilationEnv,
+    outer_allowed: &BTreeSet<Symbol>,
+    name: FunctionName,
+    fdef: &T::Function,
+) {
+    let mut allowed = allowed_lints(env, &fdef.attributes);
+    allowed.extend(outer_allowed.iter().cloned());
+    let name_loc = name.loc();
+    let end = std::cmp::max(name_loc.end(), fdef.body.loc.end());
+    let loc = Loc::new(name_loc.file_hash(), name_loc.start(), end);
+    env.add_lint_scope(loc, allowed)
+}
+
+/// The lints named in `#[lint_allow(...)]`, warning about unknown names
+fn allowed_lints(env: &mut CompilationEnv, attributes: &E::Attributes) -> BTreeSet<Symbol> {
+    let mut allowed = BTreeSet::new();
+    let allow = attributes.get_(&E::AttributeName_::Known(KnownAttribute::Lint(
+        LintAttribute::Allow,
+    )));
+    let sp!(loc, attr) = match allow {
+        None => return allowed,
+        Some(attr) => attr,
+    };
+    let names = match attr {
+        E::Attribute_::Parameterized(_, names) => names,
+        E::Attribute_::Name(_) | E::Attribute_::Assigned(_, _) => {
+            let msg = format!(
+                "Expected a list of lints, e.g. '#[{}({})]'",
+                LintAttribute::ALLOW,
+                REDUNDANT_COPY.name
+            );
+            env.add_diag(diag!(Lint::InvalidLintAllow, (*loc, msg)));
+            return allowed;
+        }
+    };
+    for (_, _, sp!(nloc, name_attr)) in names {
+        match name_attr {
+            E::Attribute_::Name(n) if find_lint(&n.value).is_some() => {
+                allowed.insert(n.value);
+            }
+            E::Attribute_::Name(n) => {
+                let msg = format!(
+                    "Unknown lint '{}'. Expected one of: {}",
+                    n,
+                    format_comma(ALL_LINTS.iter().map(|lint| lint.name))
+                );
+                env.add_diag(diag!(Lint::InvalidLintAllow, (*nloc, msg)));
+            }
+            E::Attribute_::Assigned(_, _) | E::Attribute_::Parameterized(_, _) => {
+                let msg = "Expected the name of a lint";
+                env.add_diag(diag!(Lint::InvalidLintAllow, (*nloc, msg)));
+            }
+        }
+    }
+    allowed
+}
+
+//**************************************************************************************************
+// Typed AST traversal
+//**************************************************************************************************
+
+struct TypingContext<'env, 'l> {
+    env: &'env mut CompilationEnv,
+    linters: &'l mut Vec<Box<dyn TypingLinter>>,
+}
+
+impl<'env, 'l> TypingContext<'env, 'l> {
+    fn sequence(&mut self, seq: &T::Sequence) {
+        for linter in self.linters.iter_mut() {
+            linter.enter_scope()
+        }
+        for sp!(_, item_) in seq {
+            use T::SequenceItem_ as S;
+            match item_ {
+                S::Seq(e) => self.exp(e),
+                S::Declare(lvalues) => self.bind(lvalues),
+                S::Bind(lvalues, _, e) => {
+                    self.exp(e);
+                    self.bind(lvalues)
+                }
+            }
+        }
+        for linter in self.linters.iter_mut() {
+            linter.exit_scope()
+        }
+    }
+
+    fn bind(&mut self, lvalues: &T::LValueList) {
+        for linter in self.linters.iter_mut() {
+            linter.visit_bind(self.env, lvalues)
+        }
+    }
+
+    fn exp(&mut self, e: &T::Exp) {
+        use T::UnannotatedExp_ as E;
+        for linter in self.linters.iter_mut() {
+            linter.visit_exp(self.env, e)
+        }
+        match &e.exp.value {
+            E::Unit { .. }
+            | E::Value(_)
+            | E::Move { .. }
+            | E::Copy { .. }
+            | E::Use(_)
+            | E::Constant(_, _)
+            | E::BorrowLocal(_, _)
+            | E::Break
+            | E::Continue
+            | E::Spec(_, _)
+            | E::UnresolvedError => (),
+
+            E::ModuleCall(call) => self.exp(&call.arguments),
+            E::Builtin(_, args) | E::Vector(_, _, _, args) => self.exp(args),
+
+            E::IfElse(eb, et, ef) => {
+                self.exp(eb);
+                self.exp(et);
+                self.exp(ef)
+            }
+            E::While(eb, eloop) => {
+                self.exp(eb);
+                self.exp(eloop)
+            }
+            E::Loop { body, .. } => self.exp(body),
+            E::Block(seq) => self.sequence(seq),
+
+            E::Assign(_, _, e)
+            | E::Return(e)
+            | E::Abort(e)
+            | E::Dereference(e)
+            | E::UnaryExp(_, e)
+            | E::Borrow(_, e, _)
+            | E::TempBorrow(_, e)
+            | E::Cast(e, _)
+            | E::Annotate(e, _) => self.exp(e),
+            E::Mutate(el, er) | E::BinopExp(el, _, _, er) => {
+                self.exp(el);
+                self.exp(er)
+            }
+
+            E::Pack(_, _, _, fields) => {
+                for (_, _, (_, (_, fe))) in fields {
+                    self.exp(fe)
+                }
+            }
+            E::ExpList(items) => {
+                for item in items {
+                    match item {
+                        T::ExpListItem::Single(e, _) | T::ExpListItem::Splat(_, e, _) => {
+                            self.exp(e)
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,256 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{TypingLinter, CONSTANT_CONDITION, SELF_ASSIGNMENT, SHADOWED_LOCAL, UNNECESSARY_CAST};
+use crate::{
+    diag,
+    expansion::ast::Value_,
+    naming::ast::{BuiltinTypeName_, Type, TypeName_, Type_},
+    parser::ast::{BinOp_, UnaryOp_, Var},
+    shared::CompilationEnv,
+    typing::{
+        ast as T,
+        core::{self, Subst},
+    },
+};
+use move_core_types::u256::U256;
+use move_ir_types::location::*;
+use move_symbol_pool::Symbol;
+use std::collections::BTreeMap;
+
+//**************************************************************************************************
+// Self assignment
+//**************************************************************************************************
+
+/// `x = x`, including the pairwise case `(x, y) = (x, y)`
+pub struct SelfAssignment;
+
+impl TypingLinter for SelfAssignment {
+    fn visit_exp(&mut self, env: &mut CompilationEnv, e: &T::Exp) {
+        let (lvalues, rhs) = match &e.exp.value {
+            T::UnannotatedExp_::Assign(sp!(_, lvalues), _, rhs) => (lvalues, rhs),
+            _ => return,
+        };
+        let rhs_exps = match &rhs.exp.value {
+            T::UnannotatedExp_::ExpList(items) => items
+                .iter()
+                .map(|item| match item {
+                    T::ExpListItem::Single(e, _) => Some(e),
+                    T::ExpListItem::Splat(_, _, _) => None,
+                })
+                .collect::<Option<Vec<_>>>(),
+            _ => Some(vec![rhs.as_ref()]),
+        };
+        let rhs_exps = match rhs_exps {
+            Some(exps) if exps.len() == lvalues.len() => exps,
+            _ => return,
+        };
+        for (lvalue, rhs_exp) in lvalues.iter().zip(rhs_exps) {
+            let assigned = match &lvalue.value {
+                T::LValue_::Var(v, _) => v,
+                _ => continue,
+            };
+            if used_var(rhs_exp) == Some(assigned) {
+                let msg = format!("Assigning '{}' to itself has no effect", assigned);
+                env.add_lint_diag(
+                    &SELF_ASSIGNMENT,
+                    diag!(Lint::SelfAssignment, (lvalue.loc, msg)),
+                )
+            }
+        }
+    }
+}
+
+fn used_var(e: &T::Exp) -> Option<&Var> {
+    use T::UnannotatedExp_ as E;
+    match &e.exp.value {
+        E::Copy { var, .. } | E::Move { var, .. } | E::Use(var) => Some(var),
+        E::Annotate(e, _) => used_var(e),
+        _ => None,
+    }
+}
+
+//**************************************************************************************************
+// Constant condition
+//**************************************************************************************************
+
+/// An `if` or `while` condition built only from literals
+pub struct ConstantCondition;
+
+impl TypingLinter for ConstantCondition {
+    fn visit_exp(&mut self, env: &mut CompilationEnv, e: &T::Exp) {
+        use T::UnannotatedExp_ as E;
+        let (cond, is_while) = match &e.exp.value {
+            E::IfElse(cond, _, _) => (cond, false),
+            E::While(cond, _) => (cond, true),
+            _ => return,
+        };
+        let value = match constant_bool(cond) {
+            Some(value) => value,
+            None => return,
+        };
+        let mut diag = diag!(
+            Lint::ConstantCondition,
+            (
+                cond.exp.loc,
+                format!("This condition is always '{}'", value)
+            )
+        );
+        match (is_while, value) {
+            (true, true) => diag.add_note("Use 'loop' for a loop that only exits with 'break'"),
+            (true, false) => diag.add_note("The body of this loop is never executed"),
+            (false, true) => diag.add_note("The 'else' branch is never executed"),
+            (false, false) => diag.add_note("The 'if' branch is never executed"),
+        }
+        env.add_lint_diag(&CONSTANT_CONDITION, diag)
+    }
+}
+
+fn constant_bool(e: &T::Exp) -> Option<bool> {
+    use T::UnannotatedExp_ as E;
+    match &e.exp.value {
+        E::Value(sp!(_, Value_::Bool(b))) => Some(*b),
+        E::Annotate(e, _) => constant_bool(e),
+        E::UnaryExp(sp!(_, UnaryOp_::Not), e) => constant_bool(e).map(|b| !b),
+        E::BinopExp(lhs, sp!(_, op), _, rhs) => match op {
+            BinOp_::And => Some(constant_bool(lhs)? && constant_bool(rhs)?),
+            BinOp_::Or => Some(constant_bool(lhs)? || constant_bool(rhs)?),
+            BinOp_::Eq | BinOp_::Neq => {
+                let equal = match (constant_bool(lhs), constant_bool(rhs)) {
+                    (Some(l), Some(r)) => l == r,
+                    _ => constant_number(lhs)? == constant_number(rhs)?,
+                };
+                Some(equal == (*op == BinOp_::Eq))
+            }
+            BinOp_::Lt => Some(constant_number(lhs)? < constant_number(rhs)?),
+            BinOp_::Gt => Some(constant_number(lhs)? > constant_number(rhs)?),
+            BinOp_::Le => Some(constant_number(lhs)? <= constant_number(rhs)?),
+            BinOp_::Ge => Some(constant_number(lhs)? >= constant_number(rhs)?),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn constant_number(e: &T::Exp) -> Option<U256> {
+    use T::UnannotatedExp_ as E;
+    let value = match &e.exp.value {
+        E::Value(sp!(_, v)) => v,
+        E::Annotate(e, _) => return constant_number(e),
+        _ => return None,
+    };
+    Some(match value {
+        Value_::InferredNum(u) | Value_::U256(u) => *u,
+        Value_::U8(u) => U256::from(*u),
+        Value_::U16(u) => U256::from(*u),
+        Value_::U32(u) => U256::from(*u),
+        Value_::U64(u) => U256::from(*u),
+        Value_::U128(u) => U256::from(*u),
+        Value_::Address(_) | Value_::Bool(_) | Value_::Bytearray(_) => return None,
+    })
+}
+
+//**************************************************************************************************
+// Unnecessary cast
+//**************************************************************************************************
+
+/// `e as t` where `e` already has type `t`
+pub struct UnnecessaryCast;
+
+impl TypingLinter for UnnecessaryCast {
+    fn visit_exp(&mut self, env: &mut CompilationEnv, e: &T::Exp) {
+        let (inner, target) = match &e.exp.value {
+            T::UnannotatedExp_::Cast(inner, target) => (inner, target),
+            _ => return,
+        };
+        let inner_ty = builtin_type(&inner.ty);
+        if inner_ty.is_some() && inner_ty == builtin_type(target) {
+            let msg = format!(
+                "Unnecessary cast. The expression already has type {}",
+                core::error_format(target, &Subst::empty())
+            );
+            env.add_lint_diag(
+                &UNNECESSARY_CAST,
+                diag!(Lint::UnnecessaryCast, (e.exp.loc, msg)),
+            )
+        }
+    }
+}
+
+fn builtin_type(sp!(_, ty_): &Type) -> Option<BuiltinTypeName_> {
+    match ty_ {
+        Type_::Apply(_, sp!(_, TypeName_::Builtin(sp!(_, b_))), _) => Some(b_.clone()),
+        _ => None,
+    }
+}
+
+//**************************************************************************************************
+// Shadowed local
+//**************************************************************************************************
+
+/// A `let` binding a name that is already bound in an enclosing or the same scope
+#[derive(Default)]
+pub struct ShadowedLocal {
+    scopes: Vec<BTreeMap<Symbol, Loc>>,
+}
+
+impl ShadowedLocal {
+    fn declare(&mut self, env: &mut CompilationEnv, lvalue: &T::LValue) {
+        use T::LValue_ as L;
+        match &lvalue.value {
+            L::Ignore => (),
+            L::Var(v, _) => {
+                if v.starts_with_underscore() {
+                    return;
+                }
+                let name = v.0.value;
+                let previous = self.scopes.iter().rev().find_map(|scope| scope.get(&name));
+                if let Some(previous_loc) = previous {
+                    let msg = format!("This binding of '{}' shadows an earlier one", name);
+                    let previous_msg = format!("'{}' previously bound here", name);
+                    env.add_lint_diag(
+                        &SHADOWED_LOCAL,
+                        diag!(
+                            Lint::ShadowedLocal,
+                            (lvalue.loc, msg),
+                            (*previous_loc, previous_msg),
+                        ),
+                    )
+                }
+                self.scopes.last_mut().unwrap().insert(name, lvalue.loc);
+            }
+            L::Unpack(_, _, _, fields) | L::BorrowUnpack(_, _, _, _, fields) => {
+                for (_, _, (_, (_, field_lvalue))) in fields {
+                    self.declare(env, field_lvalue)
+                }
+            }
+        }
+    }
+}
+
+impl TypingLinter for ShadowedLocal {
+    fn visit_function(&mut self, _env: &mut CompilationEnv, fdef: &T::Function) {
+        let parameters = fdef
+            .signature
+            .parameters
+            .iter()
+            .filter(|(v, _)| !v.starts_with_underscore())
+            .map(|(v, _)| (v.0.value, v.0.loc))
+            .collect();
+        self.scopes = vec![parameters];
+    }
+
+    fn visit_bind(&mut self, env: &mut CompilationEnv, sp!(_, lvalues): &T::LValueList) {
+        for lvalue in lvalues {
+            self.declare(env, lvalue)
+        }
+    }
+
+    fn enter_scope(&mut self) {
+        self.scopes.push(BTreeMap::new())
+    }
+
+    fn exit_scope(&mut self) {
+        self.scopes.pop();
+    }
+}
@@ -916,7 +916,7 @@ fn exp_(context: &mut Context, e: E::Exp) -> N::Exp {
             match na_opt {
                 None => {
                     assert!(context.env.has_errors());
-                    NE::UnresolvedError
+                    unresolved_exp(context, eloc, vec![*ne])
                 }
                 Some(na) => NE::Assign(na, ne),
             }
@@ -927,7 +927,7 @@ fn exp_(context: &mut Context, e: E::Exp) -> N::Exp {
             match ndot_opt {
                 None => {
                     assert!(context.env.has_errors());
-                    NE::UnresolvedError
+                    unresolved_exp(context, eloc, vec![*ner])
                 }
                 Some(ndot) => NE::FieldMutate(ndot, ner),
             }
@@ -951,7 +951,13 @@ fn exp_(context: &mut Context, e: E::Exp) -> N::Exp {
             match context.resolve_struct_name(eloc, "construction", tn, etys_opt) {
                 None => {
                     assert!(context.env.has_errors());
-                    NE::UnresolvedError
+                    let nes = if context.env.flags().ide_mode() {
+                        let es = efields.into_iter().map(|(_, (_, e))| e).collect();
+                        exps(context, es)
+                    } else {
+                        vec![]
+                    };
+                    unresolved_exp(context, eloc, nes)
                 }
                 Some((m, sn, tys_opt)) => NE::Pack(
                     m,
@@ -1005,7 +1011,7 @@ fn exp_(context: &mut Context, e: E::Exp) -> N::Exp {
                         NameResolution::UnboundMacro,
                         (mloc, format!("Unbound macro '{}'", ma_)),
                     ));
-                    NE::UnresolvedError
+                    unresolved_exp(context, eloc, nes.value)
                 }
             }
         }
@@ -1018,7 +1024,7 @@ fn exp_(context: &mut Context, e: E::Exp) -> N::Exp {
                     match resolve_builtin_function(context, eloc, &n, ty_args) {
                         None => {
                             assert!(context.env.has_errors());
-                            NE::UnresolvedError
+                            unresolved_exp(context, eloc, nes.value)
                         }
                         Some(f) => NE::Builtin(sp(mloc, f), nes),
                     }
@@ -1029,12 +1035,12 @@ fn exp_(context: &mut Context, e: E::Exp) -> N::Exp {
                         NameResolution::UnboundUnscopedName,
                         (n.loc, format!("Unbound function '{}' in current scope", n)),
                     ));
-                    NE::UnresolvedError
+                    unresolved_exp(context, eloc, nes.value)
                 }
                 EA::ModuleAccess(m, n) => match context.resolve_module_function(mloc, &m, &n) {
                     None => {
                         assert!(context.env.has_errors());
-                        NE::UnresolvedError
+                        unresolved_exp(context, eloc, nes.value)
                     }
                     Some(_) => NE::ModuleCall(m, FunctionName(n), ty_args, nes),
                 },
@@ -1075,6 +1081,26 @@ fn exp_(context: &mut Context, e: E::Exp) -> N::Exp {
     sp(eloc, ne_)
 }
 
+/// Stands in for an expression that failed to resolve. In IDE mode, its sub-expressions are kept
+/// (each annotated with the error type, so that they add no constraints of their own) so that they
+/// still end up in the typed AST.
+fn unresolved_exp(context: &Context, loc: Loc, parts: Vec<N::Exp>) -> N::Exp_ {
+    use N::Exp_ as NE;
+    if !context.env.flags().ide_mode() || parts.is_empty() {
+        return NE::UnresolvedError;
+    }
+    let mut seq: N::Sequence = parts
+        .into_iter()
+        .map(|e| {
+            let eloc = e.loc;
+            let annotated = NE::Annotate(Box::new(e), sp(eloc, N::Type_::UnresolvedError));
+            sp(eloc, N::SequenceItem_::Seq(sp(eloc, annotated)))
+        })
+        .collect();
+    seq.push_back(sp(loc, N::SequenceItem_::Seq(sp(loc, NE::UnresolvedError))));
+    NE::Block(seq)
+}
+
 fn access_constant(context: &mut Context, ma: E::ModuleAccess) -> N::Exp_ {
     match context.resolve_constant(ma) {
         None => {
@@ -168,6 +168,9 @@ pub struct Lexer<'input> {
     cur_start: usize,
     cur_end: usize,
     token: Tok,
+    brace_depth: usize,
+    paren_depth: usize,
+    failed: bool,
 }
 
 impl<'input> Lexer<'input> {
@@ -181,6 +184,9 @@ impl<'input> Lexer<'input> {
             cur_start: 0,
             cur_end: 0,
             token: Tok::EOF,
+            brace_depth: 0,
+            paren_depth: 0,
+            failed: false,
         }
     }
 
@@ -204,6 +210,23 @@ impl<'input> Lexer<'input> {
         self.prev_end
     }
 
+    // The number of '{' tokens consumed so far that have not yet been closed by a '}'. The parser
+    // uses this to find the end of the enclosing block when recovering from a syntax error.
+    pub fn brace_depth(&self) -> usize {
+        self.brace_depth
+    }
+
+    // Like `brace_depth`, but for '(' and '[' tokens.
+    pub fn paren_depth(&self) -> usize {
+        self.paren_depth
+    }
+
+    // Whether the lexer has hit an error. The rest of the input cannot be tokenized, so after an
+    // error the lexer stays at end-of-file.
+    pub fn has_failed(&self) -> bool {
+        self.failed
+    }
+
     /// Strips line and block comments from input source, and collects documentation comments,
     /// putting them into a map indexed by the span of the comment region. Comments in the original
     /// source will be replaced by spaces, such that positions of source items stay unchanged.
@@ -385,8 +408,35 @@ impl<'input> Lexer<'input> {
         std::mem::take(&mut self.matched_doc_comments)
     }
 
+    // Returns the documentation comments matched so far without checking for unmatched ones. Used
+    // when parsing recovered from syntax errors, as the skipped code may hold comments that would
+    // otherwise have been matched.
+    pub fn take_matched_doc_comments(&mut self) -> MatchedFileCommentMap {
+        std::mem::take(&mut self.matched_doc_comments)
+    }
+
     pub fn advance(&mut self) -> Result<(), Diagnostic> {
+        match self.token {
+            Tok::LBrace => self.brace_depth += 1,
+            Tok::RBrace => self.brace_depth = self.brace_depth.saturating_sub(1),
+            Tok::LParen | Tok::LBracket => self.paren_depth += 1,
+            Tok::RParen | Tok::RBracket => self.paren_depth = self.paren_depth.saturating_sub(1),
+            _ => (),
+        }
         self.prev_end = self.cur_end;
+        match self.advance_() {
+            Ok(()) => Ok(()),
+            Err(diag) => {
+                self.failed = true;
+                self.cur_start = self.text.len();
+                self.cur_end = self.text.len();
+                self.token = Tok::EOF;
+                Err(diag)
+            }
+        }
+    }
+
+    fn advance_(&mut self) -> Result<(), Diagnostic> {
         let text = self.trim_whitespace_and_comments(self.cur_end)?;
         self.cur_start = self.text.len() - text.len();
         let (token, len) = find_token(self.file_hash, text, self.cur_start)?;
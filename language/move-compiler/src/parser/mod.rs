@@ -118,6 +118,11 @@ pub(crate) fn parse_program(
         attr_derivation::derive_from_attributes(compilation_env, named_address_maps.get(*idx), def);
     }
 
+    // In IDE mode, continue with the partial program the parser recovered, leaving the errors to
+    // be reported along with those of later passes
+    if compilation_env.flags().ide_mode() && !diags.is_empty() {
+        compilation_env.add_diags(std::mem::take(&mut diags));
+    }
     let res = if diags.is_empty() {
         let pprog = parser::ast::Program {
             named_address_maps,
@@ -195,13 +200,8 @@ fn parse_file(
         }
         Ok(()) => &source_buffer,
     };
-    let (defs, comments) = match parse_file_string(compilation_env, file_hash, buffer) {
-        Ok(defs_and_comments) => defs_and_comments,
-        Err(ds) => {
-            diags.extend(ds);
-            (vec![], MatchedFileCommentMap::new())
-        }
-    };
+    let (defs, comments, ds) = parse_file_string(compilation_env, file_hash, buffer);
+    diags.extend(ds);
     files.insert(file_hash, (fname, source_buffer));
     Ok((defs, comments, diags, file_hash))
 }
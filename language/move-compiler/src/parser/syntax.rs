@@ -21,11 +21,42 @@ use crate::{
 struct Context<'env, 'lexer, 'input> {
     env: &'env mut CompilationEnv,
     tokens: &'lexer mut Lexer<'input>,
+    // Syntax errors the parser has recovered from
+    diags: Diagnostics,
+    last_diag_loc: Option<Loc>,
+    lexer_error_reported: bool,
 }
 
 impl<'env, 'lexer, 'input> Context<'env, 'lexer, 'input> {
     fn new(env: &'env mut CompilationEnv, tokens: &'lexer mut Lexer<'input>) -> Self {
-        Self { env, tokens }
+        Self {
+            env,
+            tokens,
+            diags: Diagnostics::new(),
+            last_diag_loc: None,
+            lexer_error_reported: false,
+        }
+    }
+
+    // Records a syntax error that parsing recovers from. Errors that are likely caused by the
+    // previous one are dropped: those at the same location, and those at end-of-file, which are
+    // about constructs left unclosed. Once the lexer has failed, the rest of the file is seen as
+    // end-of-file, so only the lexer error itself is kept.
+    fn add_diag(&mut self, diag: Diagnostic) {
+        if self.lexer_error_reported {
+            return;
+        }
+        let loc = diag.primary_loc();
+        let lexer_error = self.tokens.has_failed();
+        self.lexer_error_reported = lexer_error;
+        if let Some(last_loc) = self.last_diag_loc {
+            let at_eof = !lexer_error && self.tokens.peek() == Tok::EOF;
+            if loc.start() == last_loc.start() || at_eof {
+                return;
+            }
+        }
+        self.last_diag_loc = Some(loc);
+        self.diags.add(diag);
     }
 }
 
@@ -74,6 +105,151 @@ fn add_type_args_ambiguity_label(loc: Loc, mut diag: Diagnostic) -> Diagnostic {
     diag
 }
 
+//**************************************************************************************************
+// Error Recovery
+//**************************************************************************************************
+
+// After a syntax error, the parser records it and skips ahead to a point where it can resume:
+// the start of the next module member, the end of the current sequence item, or the end of the
+// current expression. Skipped sequence items and expressions are replaced with
+// `Exp_::UnresolvedError`, so a file with syntax errors still produces a partial AST, and one
+// error does not hide the ones after it.
+
+// Whether the current token starts a module member and cannot occur inside of one
+fn at_module_member_start(context: &mut Context) -> bool {
+    match context.tokens.peek() {
+        Tok::Fun | Tok::Struct | Tok::Const | Tok::Public | Tok::Native | Tok::NumSign => true,
+        // Not the "friend" of "public(friend)"
+        Tok::Friend => !matches!(context.tokens.lookahead(), Ok(Tok::RParen)),
+        _ => false,
+    }
+}
+
+// Whether the current token starts a module or script
+fn at_definition_start(context: &mut Context) -> bool {
+    match context.tokens.peek() {
+        // Not the "module" of a "spec module" block
+        Tok::Module => matches!(
+            context.tokens.lookahead(),
+            Ok(Tok::Identifier | Tok::NumValue)
+        ),
+        // Not the "script" of "public(script)"
+        Tok::Script => matches!(context.tokens.lookahead(), Ok(Tok::LBrace)),
+        _ => false,
+    }
+}
+
+// Whether the current token cannot be part of the enclosing sequence, meaning that the sequence
+// is missing its closing brace
+fn at_sequence_end(context: &mut Context) -> bool {
+    context.tokens.peek() == Tok::EOF
+        || at_definition_start(context)
+        || at_module_member_start(context)
+}
+
+fn skip_token(context: &mut Context) {
+    if let Err(diag) = context.tokens.advance() {
+        context.add_diag(diag)
+    }
+}
+
+// Skip to the start of the next module or script.
+fn skip_to_definition(context: &mut Context) {
+    while context.tokens.peek() != Tok::EOF && !at_definition_start(context) {
+        skip_token(context)
+    }
+}
+
+// Skip to the start of the next module member, or to the "}" ending the module body, which is
+// at the brace depth `depth`.
+fn skip_to_module_member(context: &mut Context, depth: usize) {
+    loop {
+        if at_sequence_end(context) {
+            return;
+        }
+        if context.tokens.brace_depth() <= depth
+            && matches!(
+                context.tokens.peek(),
+                Tok::RBrace | Tok::Use | Tok::Spec | Tok::Invariant
+            )
+        {
+            return;
+        }
+        skip_token(context)
+    }
+}
+
+// Skip to the ";" ending the current sequence item or to the "}" ending the sequence, both at
+// the brace depth `depth`. Stops early at the start of a module member, as that means the
+// sequence is missing its "}".
+fn skip_to_sequence_item_end(context: &mut Context, depth: usize) {
+    loop {
+        if at_sequence_end(context) {
+            return;
+        }
+        if context.tokens.brace_depth() <= depth
+            && matches!(context.tokens.peek(), Tok::Semicolon | Tok::RBrace)
+        {
+            return;
+        }
+        skip_token(context)
+    }
+}
+
+// The position of an expression: its start and the brace and paren depths at its start
+#[derive(Clone, Copy)]
+struct ExpStart {
+    loc: usize,
+    brace_depth: usize,
+    paren_depth: usize,
+}
+
+fn exp_start(context: &Context) -> ExpStart {
+    ExpStart {
+        loc: context.tokens.start_loc(),
+        brace_depth: context.tokens.brace_depth(),
+        paren_depth: context.tokens.paren_depth(),
+    }
+}
+
+// Skip to the end of the expression at `start`: the next "," or ";" outside of any delimiters
+// opened in the expression, or a closing delimiter opened before it.
+fn skip_to_exp_end(context: &mut Context, start: ExpStart) {
+    loop {
+        if at_sequence_end(context) {
+            return;
+        }
+        let brace_depth = context.tokens.brace_depth();
+        let paren_depth = context.tokens.paren_depth();
+        match context.tokens.peek() {
+            Tok::Comma | Tok::Semicolon
+                if brace_depth <= start.brace_depth && paren_depth <= start.paren_depth =>
+            {
+                return
+            }
+            Tok::RBrace if brace_depth <= start.brace_depth => return,
+            Tok::RParen | Tok::RBracket if paren_depth <= start.paren_depth => return,
+            _ => (),
+        }
+        skip_token(context)
+    }
+}
+
+// Record the error `diag` found in the expression at `start`, skip the rest of the expression,
+// and return an error expression in its place.
+fn recover_exp(context: &mut Context, start: ExpStart, diag: Diagnostic) -> Exp {
+    let start_loc = start.loc;
+    context.add_diag(diag);
+    skip_to_exp_end(context, start);
+    let end_loc = std::cmp::max(start_loc, context.tokens.previous_end_loc());
+    spanned(
+        context.tokens.file_hash(),
+        start_loc,
+        end_loc,
+        Exp_::UnresolvedError,
+    )
+}
+
 //**************************************************************************************************
 // Miscellaneous Utilities
 //**************************************************************************************************
@@ -612,7 +788,7 @@ fn parse_attributes(context: &mut Context) -> Result<Vec<Attributes>, Diagnostic
 fn parse_exp_field(context: &mut Context) -> Result<(Field, Exp), Diagnostic> {
     let f = parse_field(context)?;
     let arg = if match_token(context.tokens, Tok::Colon)? {
-        parse_exp(context)?
+        parse_exp_or_error(context)
     } else {
         sp(
             f.loc(),
@@ -812,7 +988,7 @@ fn parse_sequence_item(context: &mut Context) -> Result<SequenceItem, Diagnostic
             None
         };
         if match_token(context.tokens, Tok::Equal)? {
-            let e = parse_exp(context)?;
+            let e = parse_exp_or_error(context);
             SequenceItem_::Bind(b, ty_opt, Box::new(e))
         } else {
             SequenceItem_::Declare(b, ty_opt)
@@ -835,7 +1011,11 @@ fn parse_sequence_item(context: &mut Context) -> Result<SequenceItem, Diagnostic
 //
 // Note that this does not include the opening brace of a block but it
 // does consume the closing right brace.
+//
+// A sequence item with a syntax error is replaced with an error expression. If the sequence is
+// missing its closing brace, the items parsed so far are returned.
 fn parse_sequence(context: &mut Context) -> Result<Sequence, Diagnostic> {
+    let depth = context.tokens.brace_depth();
     let mut uses = vec![];
     while context.tokens.peek() == Tok::Use {
         uses.push(parse_use_decl(vec![], context)?);
@@ -845,7 +1025,22 @@ fn parse_sequence(context: &mut Context) -> Result<Sequence, Diagnostic> {
     let mut last_semicolon_loc = None;
     let mut eopt = None;
     while context.tokens.peek() != Tok::RBrace {
-        let item = parse_sequence_item(context)?;
+        let start_loc = context.tokens.start_loc();
+        let (item, recovered) = match parse_sequence_item(context) {
+            Ok(item) => (item, false),
+            Err(diag) => {
+                context.add_diag(diag);
+                skip_to_sequence_item_end(context, depth);
+                let end_loc = std::cmp::max(start_loc, context.tokens.previous_end_loc());
+                let e = spanned(
+                    context.tokens.file_hash(),
+                    start_loc,
+                    end_loc,
+                    Exp_::UnresolvedError,
+                );
+                (sp(e.loc, SequenceItem_::Seq(Box::new(e))), true)
+            }
+        };
         if context.tokens.peek() == Tok::RBrace {
             // If the sequence ends with an expression that is not
             // followed by a semicolon, split out that expression
@@ -857,13 +1052,35 @@ fn parse_sequence(context: &mut Context) -> Result<Sequence, Diagnostic> {
                         value: e.value,
                     });
                 }
-                _ => return Err(unexpected_token_error(context.tokens, "';'")),
+                _ => {
+                    context.add_diag(unexpected_token_error(context.tokens, "';'"));
+                    seq.push(item);
+                }
             }
             break;
         }
         seq.push(item);
-        last_semicolon_loc = Some(current_token_loc(context.tokens));
-        consume_token(context.tokens, Tok::Semicolon)?;
+        if context.tokens.peek() == Tok::Semicolon {
+            last_semicolon_loc = Some(current_token_loc(context.tokens));
+            context.tokens.advance()?;
+            continue;
+        }
+        if !recovered {
+            context.add_diag(unexpected_token_error(context.tokens, "';'"));
+        }
+        if at_sequence_end(context) {
+            return Ok((uses, seq, last_semicolon_loc, Box::new(eopt)));
+        }
+        // Resume at the next item if one can start here, otherwise skip to the end of this one
+        if context.tokens.peek() != Tok::Let && !at_start_of_exp(context) {
+            skip_to_sequence_item_end(context, depth);
+            if at_sequence_end(context) {
+                return Ok((uses, seq, last_semicolon_loc, Box::new(eopt)));
+            }
+            if context.tokens.peek() == Tok::Semicolon {
+                context.tokens.advance()?;
+            }
+        }
     }
     context.tokens.advance()?; // consume the RBrace
     Ok((uses, seq, last_semicolon_loc, Box::new(eopt)))
@@ -940,7 +1157,7 @@ fn parse_term(context: &mut Context) -> Result<Exp, Diagnostic> {
                 context,
                 Tok::LBracket,
                 Tok::RBracket,
-                parse_exp,
+                |context| Ok(parse_exp_or_error(context)),
                 "a vector argument expression",
             )?;
             let args_end_loc = context.tokens.previous_end_loc();
@@ -1208,7 +1425,7 @@ fn parse_call_args(context: &mut Context) -> Result<Spanned<Vec<Exp>>, Diagnosti
         context,
         Tok::LParen,
         Tok::RParen,
-        parse_exp,
+        |context| Ok(parse_exp_or_error(context)),
         "a call argument expression",
     )?;
     let end_loc = context.tokens.previous_end_loc();
@@ -1285,7 +1502,7 @@ fn parse_exp(context: &mut Context) -> Result<Exp, Diagnostic> {
                 return parse_binop_exp(context, lhs, /* min_prec */ 1);
             }
             context.tokens.advance()?; // consume the "="
-            let rhs = Box::new(parse_exp(context)?);
+            let rhs = Box::new(parse_exp_or_error(context));
             Exp_::Assign(Box::new(lhs), rhs)
         }
     };
@@ -1293,6 +1510,12 @@ fn parse_exp(context: &mut Context) -> Result<Exp, Diagnostic> {
     Ok(spanned(context.tokens.file_hash(), start_loc, end_loc, exp))
 }
 
+// Parse an expression, recovering from a syntax error in it by returning an error expression.
+fn parse_exp_or_error(context: &mut Context) -> Exp {
+    let start = exp_start(context);
+    parse_exp(context).unwrap_or_else(|diag| recover_exp(context, start, diag))
+}
+
 // Get the precedence of a binary operator. The minimum precedence value
 // is 1, and larger values have higher precedence. For tokens that are not
 // binary operators, this returns a value of zero so that they will be
@@ -1357,7 +1580,9 @@ fn parse_binop_exp(context: &mut Context, lhs: Exp, min_prec: u32) -> Result<Exp
         context.tokens.advance()?;
         let op_end_loc = context.tokens.previous_end_loc();
 
-        let mut rhs = parse_unary_exp(context)?;
+        let rhs_start = exp_start(context);
+        let mut rhs =
+            parse_unary_exp(context).unwrap_or_else(|diag| recover_exp(context, rhs_start, diag));
 
         // If the next token is another binary operator with a higher
         // precedence, then recursively parse that expression as the RHS.
@@ -2338,84 +2563,42 @@ fn parse_module(
     consume_token(context.tokens, Tok::LBrace)?;
 
     let mut members = vec![];
-    while context.tokens.peek() != Tok::RBrace {
-        members.push({
-            let attributes = parse_attributes(context)?;
-            match context.tokens.peek() {
-                // Top-level specification constructs
-                Tok::Invariant => {
-                    context.tokens.match_doc_comments();
-                    ModuleMember::Spec(singleton_module_spec_block(
-                        context,
-                        context.tokens.start_loc(),
-                        attributes,
-                        parse_invariant,
-                    )?)
-                }
-                Tok::Spec => {
-                    match context.tokens.lookahead() {
-                        Ok(Tok::Fun) | Ok(Tok::Native) => {
-                            context.tokens.match_doc_comments();
-                            let start_loc = context.tokens.start_loc();
-                            context.tokens.advance()?;
-                            // Add an extra check for better error message
-                            // if old syntax is used
-                            if context.tokens.lookahead2() == Ok((Tok::Identifier, Tok::LBrace)) {
-                                return Err(unexpected_token_error(
-                                    context.tokens,
-                                    "only 'spec', drop the 'fun' keyword",
-                                ));
-                            }
-                            ModuleMember::Spec(singleton_module_spec_block(
-                                context,
-                                start_loc,
-                                attributes,
-                                parse_spec_function,
-                            )?)
-                        }
-                        _ => {
-                            // Regular spec block
-                            ModuleMember::Spec(parse_spec_block(attributes, context)?)
-                        }
+    let depth = context.tokens.brace_depth();
+    let mut unterminated = false;
+    loop {
+        if context.tokens.peek() == Tok::RBrace {
+            if context.tokens.brace_depth() <= depth {
+                break;
+            }
+            // A "}" closing a block that was left unclosed after an error, rather than the module
+            skip_token(context);
+            continue;
+        }
+        let member_start_loc = context.tokens.start_loc();
+        match parse_module_member(context) {
+            Ok(member) => members.push(member),
+            Err(diag) => {
+                let no_progress = context.tokens.start_loc() == member_start_loc;
+                if context.tokens.peek() == Tok::EOF || at_definition_start(context) {
+                    // The module is missing its closing brace. If an earlier error left a block
+                    // unclosed, that brace was likely taken to close the block.
+                    if !no_progress || context.diags.is_empty() {
+                        context.add_diag(diag);
                     }
+                    unterminated = true;
+                    break;
                 }
-                // Regular move constructs
-                Tok::Use => ModuleMember::Use(parse_use_decl(attributes, context)?),
-                Tok::Friend => ModuleMember::Friend(parse_friend_decl(attributes, context)?),
-                _ => {
-                    context.tokens.match_doc_comments();
-                    let start_loc = context.tokens.start_loc();
-                    let modifiers = parse_module_member_modifiers(context)?;
-                    match context.tokens.peek() {
-                        Tok::Const => ModuleMember::Constant(parse_constant_decl(
-                            attributes, start_loc, modifiers, context,
-                        )?),
-                        Tok::Fun => ModuleMember::Function(parse_function_decl(
-                            attributes, start_loc, modifiers, context,
-                        )?),
-                        Tok::Struct => ModuleMember::Struct(parse_struct_decl(
-                            attributes, start_loc, modifiers, context,
-                        )?),
-                        _ => {
-                            return Err(unexpected_token_error(
-                                context.tokens,
-                                &format!(
-                                    "a module member: '{}', '{}', '{}', '{}', '{}', or '{}'",
-                                    Tok::Spec,
-                                    Tok::Use,
-                                    Tok::Friend,
-                                    Tok::Const,
-                                    Tok::Fun,
-                                    Tok::Struct
-                                ),
-                            ))
-                        }
-                    }
+                context.add_diag(diag);
+                if no_progress {
+                    skip_token(context);
                 }
+                skip_to_module_member(context, depth);
             }
-        })
+        }
+    }
+    if !unterminated {
+        consume_token(context.tokens, Tok::RBrace)?;
     }
-    consume_token(context.tokens, Tok::RBrace)?;
     let loc = make_loc(
         context.tokens.file_hash(),
         start_loc,
@@ -2433,6 +2616,88 @@ fn parse_module(
     Ok(def)
 }
 
+// Parse a module member:
+//      ModuleMember =
+//          <Attributes>
+//          ( <Invariant> | <SpecBlock> | <UseDecl> | <FriendDecl> |
+//            <DocComments> <ModuleMemberModifiers>
+//              (<ConstantDecl> | <StructDecl> | <FunctionDecl>) )
+fn parse_module_member(context: &mut Context) -> Result<ModuleMember, Diagnostic> {
+    let attributes = parse_attributes(context)?;
+    Ok(match context.tokens.peek() {
+        // Top-level specification constructs
+        Tok::Invariant => {
+            context.tokens.match_doc_comments();
+            ModuleMember::Spec(singleton_module_spec_block(
+                context,
+                context.tokens.start_loc(),
+                attributes,
+                parse_invariant,
+            )?)
+        }
+        Tok::Spec => {
+            match context.tokens.lookahead() {
+                Ok(Tok::Fun) | Ok(Tok::Native) => {
+                    context.tokens.match_doc_comments();
+                    let start_loc = context.tokens.start_loc();
+                    context.tokens.advance()?;
+                    // Add an extra check for better error message
+                    // if old syntax is used
+                    if context.tokens.lookahead2() == Ok((Tok::Identifier, Tok::LBrace)) {
+                        return Err(unexpected_token_error(
+                            context.tokens,
+                            "only 'spec', drop the 'fun' keyword",
+                        ));
+                    }
+                    ModuleMember::Spec(singleton_module_spec_block(
+                        context,
+                        start_loc,
+                        attributes,
+                        parse_spec_function,
+                    )?)
+                }
+                _ => {
+                    // Regular spec block
+                    ModuleMember::Spec(parse_spec_block(attributes, context)?)
+                }
+            }
+        }
+        // Regular move constructs
+        Tok::Use => ModuleMember::Use(parse_use_decl(attributes, context)?),
+        Tok::Friend => ModuleMember::Friend(parse_friend_decl(attributes, context)?),
+        _ => {
+            context.tokens.match_doc_comments();
+            let start_loc = context.tokens.start_loc();
+            let modifiers = parse_module_member_modifiers(context)?;
+            match context.tokens.peek() {
+                Tok::Const => ModuleMember::Constant(parse_constant_decl(
+                    attributes, start_loc, modifiers, context,
+                )?),
+                Tok::Fun => ModuleMember::Function(parse_function_decl(
+                    attributes, start_loc, modifiers, context,
+                )?),
+                Tok::Struct => ModuleMember::Struct(parse_struct_decl(
+                    attributes, start_loc, modifiers, context,
+                )?),
+                _ => {
+                    return Err(unexpected_token_error(
+                        context.tokens,
+                        &format!(
+                            "a module member: '{}', '{}', '{}', '{}', '{}', or '{}'",
+                            Tok::Spec,
+                            Tok::Use,
+                            Tok::Friend,
+                            Tok::Const,
+                            Tok::Fun,
+                            Tok::Struct
+                        ),
+                    ))
+                }
+            }
+        }
+    })
+}
+
 //**************************************************************************************************
 // Scripts
 //**************************************************************************************************
@@ -3197,34 +3462,57 @@ fn singleton_module_spec_block(
 // Parse a file:
 //      File =
 //          (<Attributes> (<AddressBlock> | <Module> | <Script>))*
-fn parse_file(context: &mut Context) -> Result<Vec<Definition>, Diagnostic> {
+fn parse_file(context: &mut Context) -> Vec<Definition> {
     let mut defs = vec![];
     while context.tokens.peek() != Tok::EOF {
-        let attributes = parse_attributes(context)?;
-        defs.push(match context.tokens.peek() {
-            Tok::Spec | Tok::Module => Definition::Module(parse_module(attributes, context)?),
-            Tok::Script => Definition::Script(parse_script(attributes, context)?),
-            _ => Definition::Address(parse_address_block(attributes, context)?),
-        })
+        let start_loc = context.tokens.start_loc();
+        match parse_definition(context) {
+            Ok(def) => defs.push(def),
+            Err(diag) => {
+                context.add_diag(diag);
+                if context.tokens.start_loc() == start_loc {
+                    skip_token(context);
+                }
+                skip_to_definition(context);
+            }
+        }
     }
-    Ok(defs)
+    defs
+}
+
+fn parse_definition(context: &mut Context) -> Result<Definition, Diagnostic> {
+    let attributes = parse_attributes(context)?;
+    Ok(match context.tokens.peek() {
+        Tok::Spec | Tok::Module => Definition::Module(parse_module(attributes, context)?),
+        Tok::Script => Definition::Script(parse_script(attributes, context)?),
+        _ => Definition::Address(parse_address_block(attributes, context)?),
+    })
 }
 
-/// Parse the `input` string as a file of Move source code and return the
-/// result as either a pair of FileDefinition and doc comments or some Diagnostics. The `file` name
-/// is used to identify source locations in error messages.
+/// Parse the `input` string as a file of Move source code and return the resulting definitions
+/// and doc comments, along with the syntax errors found. Parsing recovers from syntax errors, so
+/// the definitions are a best-effort partial AST when there are errors. The `file` name is used
+/// to identify source locations in error messages.
 pub fn parse_file_string(
     env: &mut CompilationEnv,
     file_hash: FileHash,
     input: &str,
-) -> Result<(Vec<Definition>, MatchedFileCommentMap), Diagnostics> {
+) -> (Vec<Definition>, MatchedFileCommentMap, Diagnostics) {
     let mut tokens = Lexer::new(input, file_hash);
-    match tokens.advance() {
-        Err(err) => Err(Diagnostics::from(vec![err])),
-        Ok(..) => Ok(()),
-    }?;
-    match parse_file(&mut Context::new(env, &mut tokens)) {
-        Err(err) => Err(Diagnostics::from(vec![err])),
-        Ok(def) => Ok((def, tokens.check_and_get_doc_comments(env))),
+    if let Err(err) = tokens.advance() {
+        return (
+            vec![],
+            MatchedFileCommentMap::new(),
+            Diagnostics::from(vec![err]),
+        );
     }
+    let mut context = Context::new(env, &mut tokens);
+    let defs = parse_file(&mut context);
+    let diags = context.diags;
+    let comments = if diags.is_empty() {
+        tokens.check_and_get_doc_comments(env)
+    } else {
+        tokens.take_matched_doc_comments()
+    };
+    (defs, comments, diags)
 }
@@ -4,7 +4,8 @@
 
 use crate::{
     command_line as cli,
-    diagnostics::{codes::Severity, Diagnostic, Diagnostics},
+    diagnostics::{codes::Severity, Diagnostic, Diagnostics, MessageFormat},
+    linters::{LintConfig, LintInfo, LintLevel},
     naming::ast::ModuleDefinition,
 };
 use clap::*;
@@ -12,7 +13,7 @@ use move_ir_types::location::*;
 use move_symbol_pool::Symbol;
 use petgraph::{algo::astar as petgraph_astar, graphmap::DiGraphMap};
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
     fmt,
     hash::Hash,
     sync::atomic::{AtomicUsize, Ordering as AtomicOrdering},
@@ -173,6 +174,8 @@ pub type AttributeDeriver = dyn Fn(&mut CompilationEnv, &mut ModuleDefinition);
 pub struct CompilationEnv {
     flags: Flags,
     diags: Diagnostics,
+    // The locations of the source functions that are linted, with the lints allowed in each
+    lint_scopes: Vec<(Loc, BTreeSet<Symbol>)>,
     // TODO(tzakian): Remove the global counter and use this counter instead
     // pub counter: u64,
 }
@@ -182,6 +185,7 @@ impl CompilationEnv {
         Self {
             flags,
             diags: Diagnostics::new(),
+            lint_scopes: vec![],
         }
     }
 
@@ -193,6 +197,38 @@ impl CompilationEnv {
         self.diags.extend(diags)
     }
 
+    /// Marks the code at `loc` as subject to linting, except for the lints in `allowed`
+    pub fn add_lint_scope(&mut self, loc: Loc, allowed: BTreeSet<Symbol>) {
+        self.lint_scopes.push((loc, allowed))
+    }
+
+    /// Reports a lint at the level configured for it. The diagnostic is dropped if linting is
+    /// disabled, if the lint is allowed, or if it does not fall within a linted source function
+    pub fn add_lint_diag(&mut self, lint: &LintInfo, diag: Diagnostic) {
+        if !self.flags.is_linting() {
+            return;
+        }
+        let level = self.flags.lint_config().level(lint);
+        if level == LintLevel::Allow {
+            return;
+        }
+        let loc = diag.primary_loc();
+        let scope = self.lint_scopes.iter().find(|(scope_loc, _)| {
+            scope_loc.file_hash() == loc.file_hash()
+                && scope_loc.start() <= loc.start()
+                && loc.end() <= scope_loc.end()
+        });
+        match scope {
+            Some((_, allowed)) if !allowed.contains(&Symbol::from(lint.name)) => (),
+            Some(_) | None => return,
+        }
+        let diag = match level {
+            LintLevel::Deny => diag.set_severity(Severity::NonblockingError),
+            LintLevel::Allow | LintLevel::Warn => diag,
+        };
+        self.add_diag(diag)
+    }
+
     pub fn has_warnings_or_errors(&self) -> bool {
         !self.diags.is_empty()
     }
@@ -315,6 +351,36 @@ pub struct Flags {
     /// included only in tests, without creating the unit test code regular tests do.
     #[clap(skip)]
     keep_testing_functions: bool,
+
+    /// Run the linters over the source modules
+    #[clap(
+        long = cli::LINT,
+    )]
+    lint: bool,
+
+    /// Levels for the lints, overriding their defaults. Set by the package system from the
+    /// `[lints]` section of the manifest
+    #[clap(skip)]
+    lint_config: LintConfig,
+
+    /// Optimize the bytecode of the compiled modules
+    #[clap(
+        short = cli::OPTIMIZE_SHORT,
+        long = cli::OPTIMIZE,
+    )]
+    optimize: bool,
+
+    /// How diagnostics are reported: `human` or `json`
+    #[clap(
+        long = cli::MESSAGE_FORMAT,
+        default_value = "human",
+    )]
+    message_format: MessageFormat,
+
+    /// Internal flag used by the language server to keep compiling past errors, so that passes
+    /// produce best-effort ASTs for programs with errors.
+    #[clap(skip)]
+    ide_mode: bool,
 }
 
 impl Flags {
@@ -326,6 +392,11 @@ impl Flags {
             flavor: "".to_string(),
             bytecode_version: None,
             keep_testing_functions: false,
+            lint: false,
+            lint_config: LintConfig::default(),
+            optimize: false,
+            message_format: MessageFormat::Human,
+            ide_mode: false,
         }
     }
 
@@ -337,6 +408,11 @@ impl Flags {
             flavor: "".to_string(),
             bytecode_version: None,
             keep_testing_functions: false,
+            lint: false,
+            lint_config: LintConfig::default(),
+            optimize: false,
+            message_format: MessageFormat::Human,
+            ide_mode: false,
         }
     }
 
@@ -348,6 +424,11 @@ impl Flags {
             flavor: "".to_string(),
             bytecode_version: None,
             keep_testing_functions: false,
+            lint: false,
+            lint_config: LintConfig::default(),
+            optimize: false,
+            message_format: MessageFormat::Human,
+            ide_mode: false,
         }
     }
 
@@ -365,6 +446,41 @@ impl Flags {
         }
     }
 
+    pub fn set_lint(self, value: bool) -> Self {
+        Self {
+            lint: value,
+            ..self
+        }
+    }
+
+    pub fn set_lint_config(self, lint_config: LintConfig) -> Self {
+        Self {
+            lint_config,
+            ..self
+        }
+    }
+
+    pub fn set_optimize(self, value: bool) -> Self {
+        Self {
+            optimize: value,
+            ..self
+        }
+    }
+
+    pub fn set_message_format(self, message_format: MessageFormat) -> Self {
+        Self {
+            message_format,
+            ..self
+        }
+    }
+
+    pub fn set_ide_mode(self, value: bool) -> Self {
+        Self {
+            ide_mode: value,
+            ..self
+        }
+    }
+
     pub fn set_sources_shadow_deps(self, sources_shadow_deps: bool) -> Self {
         Self {
             shadow: sources_shadow_deps,
@@ -399,6 +515,26 @@ impl Flags {
     pub fn bytecode_version(&self) -> Option<u32> {
         self.bytecode_version
     }
+
+    pub fn is_linting(&self) -> bool {
+        self.lint
+    }
+
+    pub fn lint_config(&self) -> &LintConfig {
+        &self.lint_config
+    }
+
+    pub fn is_optimizing(&self) -> bool {
+        self.optimize
+    }
+
+    pub fn message_format(&self) -> MessageFormat {
+        self.message_format
+    }
+
+    pub fn ide_mode(&self) -> bool {
+        self.ide_mode
+    }
 }
 
 //**************************************************************************************************
@@ -427,6 +563,7 @@ pub mod known_attributes {
         Testing(TestingAttribute),
         Verification(VerificationAttribute),
         Native(NativeAttribute),
+        Lint(LintAttribute),
     }
 
     #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -435,6 +572,10 @@ pub mod known_attributes {
         TestOnly,
         // Is a test that will be run
         Test,
+        // Is a test that will be run many times with randomly generated arguments
+        RandomTest,
+        // Sets up the storage state that every test in the module starts from
+        BeforeEach,
         // This test is expected to fail
         ExpectedFailure,
     }
@@ -451,6 +592,12 @@ pub mod known_attributes {
         BytecodeInstruction,
     }
 
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    pub enum LintAttribute {
+        // Silences the listed lints for the annotated module or function
+        Allow,
+    }
+
     impl fmt::Display for AttributePosition {
         fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             match self {
@@ -472,6 +619,8 @@ pub mod known_attributes {
             Some(match attribute_str.as_ref() {
                 TestingAttribute::TEST => Self::Testing(TestingAttribute::Test),
                 TestingAttribute::TEST_ONLY => Self::Testing(TestingAttribute::TestOnly),
+                TestingAttribute::RANDOM_TEST => Self::Testing(TestingAttribute::RandomTest),
+                TestingAttribute::BEFORE_EACH => Self::Testing(TestingAttribute::BeforeEach),
                 TestingAttribute::EXPECTED_FAILURE => {
                     Self::Testing(TestingAttribute::ExpectedFailure)
                 }
@@ -481,6 +630,7 @@ pub mod known_attributes {
                 NativeAttribute::BYTECODE_INSTRUCTION => {
                     Self::Native(NativeAttribute::BytecodeInstruction)
                 }
+                LintAttribute::ALLOW => Self::Lint(LintAttribute::Allow),
                 _ => return None,
             })
         }
@@ -490,6 +640,7 @@ pub mod known_attributes {
                 Self::Testing(a) => a.name(),
                 Self::Verification(a) => a.name(),
                 Self::Native(a) => a.name(),
+                Self::Lint(a) => a.name(),
             }
         }
 
@@ -498,6 +649,7 @@ pub mod known_attributes {
                 Self::Testing(a) => a.expected_positions(),
                 Self::Verification(a) => a.expected_positions(),
                 Self::Native(a) => a.expected_positions(),
+                Self::Lint(a) => a.expected_positions(),
             }
         }
     }
@@ -506,12 +658,22 @@ pub mod known_attributes {
         pub const TEST: &'static str = "test";
         pub const EXPECTED_FAILURE: &'static str = "expected_failure";
         pub const TEST_ONLY: &'static str = "test_only";
+        pub const RANDOM_TEST: &'static str = "random_test";
+        pub const BEFORE_EACH: &'static str = "before_each";
         pub const CODE_ASSIGNMENT_NAME: &'static str = "abort_code";
+        pub const ARITHMETIC_ERROR_NAME: &'static str = "arithmetic_error";
+        pub const VECTOR_ERROR_NAME: &'static str = "vector_error";
+        pub const OUT_OF_GAS_NAME: &'static str = "out_of_gas";
+        pub const MAJOR_STATUS_NAME: &'static str = "major_status";
+        pub const MINOR_STATUS_NAME: &'static str = "minor_status";
+        pub const ERROR_LOCATION: &'static str = "location";
 
         pub const fn name(&self) -> &str {
             match self {
                 Self::Test => Self::TEST,
                 Self::TestOnly => Self::TEST_ONLY,
+                Self::RandomTest => Self::RANDOM_TEST,
+                Self::BeforeEach => Self::BEFORE_EACH,
                 Self::ExpectedFailure => Self::EXPECTED_FAILURE,
             }
         }
@@ -535,7 +697,9 @@ pub mod known_attributes {
                 Lazy::new(|| IntoIterator::into_iter([AttributePosition::Function]).collect());
             match self {
                 TestingAttribute::TestOnly => &*TEST_ONLY_POSITIONS,
-                TestingAttribute::Test => &*TEST_POSITIONS,
+                TestingAttribute::Test
+                | TestingAttribute::RandomTest
+                | TestingAttribute::BeforeEach => &*TEST_POSITIONS,
                 TestingAttribute::ExpectedFailure => &*EXPECTED_FAILURE_POSITIONS,
             }
         }
@@ -586,4 +750,28 @@ pub mod known_attributes {
             }
         }
     }
+
+    impl LintAttribute {
+        pub const ALLOW: &'static str = "lint_allow";
+
+        pub const fn name(&self) -> &str {
+            match self {
+                Self::Allow => Self::ALLOW,
+            }
+        }
+
+        pub fn expected_positions(&self) -> &'static BTreeSet<AttributePosition> {
+            static ALLOW_POSITIONS: Lazy<BTreeSet<AttributePosition>> = Lazy::new(|| {
+                IntoIterator::into_iter([
+                    AttributePosition::Module,
+                    AttributePosition::Script,
+                    AttributePosition::Function,
+                ])
+                .collect()
+            });
+            match self {
+                Self::Allow => &*ALLOW_POSITIONS,
+            }
+        }
+    }
 }
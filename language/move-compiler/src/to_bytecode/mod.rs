@@ -4,6 +4,7 @@
 
 #[macro_use]
 mod context;
+mod optimize;
 mod remove_fallthrough_jumps;
 pub mod translate;
 
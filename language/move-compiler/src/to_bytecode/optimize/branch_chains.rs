@@ -0,0 +1,58 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Retargets branches to unconditional branches at the final target of the chain, then removes
+//! the code that is no longer reachable and branches to the next instruction.
+
+use super::{branch_target, code_mut, remove_instructions};
+use move_binary_format::{
+    control_flow_graph::{ControlFlowGraph, VMControlFlowGraph},
+    file_format::{Bytecode, CodeOffset, CompiledModule, FunctionDefinitionIndex},
+};
+use move_bytecode_source_map::source_map::FunctionSourceMap;
+use std::collections::BTreeSet;
+
+pub fn optimize(
+    module: &mut CompiledModule,
+    idx: FunctionDefinitionIndex,
+    source_map: &mut FunctionSourceMap,
+) {
+    let code = &mut code_mut(module, idx).code;
+    let final_targets: Vec<_> = (0..code.len())
+        .map(|offset| final_target(code, offset as CodeOffset))
+        .collect();
+    for instr in code.iter_mut() {
+        if let Some(offset) = branch_target(instr) {
+            *offset = final_targets[*offset as usize];
+        }
+    }
+
+    let cfg = VMControlFlowGraph::new(code);
+    let mut keep = vec![false; code.len()];
+    for block in cfg.reachable_from(cfg.entry_block_id()) {
+        for offset in cfg.instr_indexes(block) {
+            keep[offset as usize] = true;
+        }
+    }
+    for offset in 0..code.len() {
+        if let Bytecode::Branch(target) = code[offset] {
+            let next = (offset + 1..code.len()).find(|next| keep[*next]);
+            if keep[offset] && next == Some(target as usize) {
+                keep[offset] = false;
+            }
+        }
+    }
+    remove_instructions(code, source_map, &keep);
+}
+
+/// Follows the unconditional branches starting at `offset`
+fn final_target(code: &[Bytecode], mut offset: CodeOffset) -> CodeOffset {
+    let mut seen = BTreeSet::new();
+    while let Bytecode::Branch(target) = code[offset as usize] {
+        if !seen.insert(offset) {
+            break;
+        }
+        offset = target;
+    }
+    offset
+}
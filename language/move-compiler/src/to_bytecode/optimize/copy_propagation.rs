@@ -0,0 +1,24 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Turns copies of locals into moves where the local is not read afterwards.
+
+use super::{code_mut, liveness};
+use move_binary_format::file_format::{Bytecode, CompiledModule, FunctionDefinitionIndex};
+use move_bytecode_source_map::source_map::FunctionSourceMap;
+
+pub fn optimize(
+    module: &mut CompiledModule,
+    idx: FunctionDefinitionIndex,
+    _source_map: &mut FunctionSourceMap,
+) {
+    let code = &mut code_mut(module, idx).code;
+    let live = liveness::live_after(code);
+    for (instr, live) in code.iter_mut().zip(&live) {
+        if let Bytecode::CopyLoc(local) = instr {
+            if !live.contains(local) {
+                *instr = Bytecode::MoveLoc(*local);
+            }
+        }
+    }
+}
@@ -0,0 +1,66 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Replaces stores to locals that are not read afterwards with pops, then removes pops of values
+//! loaded by the instruction just before, and stores to locals immediately moved back onto the
+//! stack.
+
+use super::{branch_target, code_mut, liveness, remove_instructions};
+use move_binary_format::file_format::{Bytecode, CompiledModule, FunctionDefinitionIndex};
+use move_bytecode_source_map::source_map::FunctionSourceMap;
+use std::collections::BTreeSet;
+
+pub fn optimize(
+    module: &mut CompiledModule,
+    idx: FunctionDefinitionIndex,
+    source_map: &mut FunctionSourceMap,
+) {
+    let code = &mut code_mut(module, idx).code;
+    let live = liveness::live_after(code);
+    for (instr, live) in code.iter_mut().zip(&live) {
+        if matches!(instr, Bytecode::StLoc(local) if !live.contains(local)) {
+            *instr = Bytecode::Pop;
+        }
+    }
+
+    let targets: BTreeSet<_> = code
+        .iter_mut()
+        .filter_map(|instr| branch_target(instr).map(|offset| *offset as usize))
+        .collect();
+    let mut keep = vec![true; code.len()];
+    for offset in 1..code.len() {
+        if !keep[offset - 1] || targets.contains(&offset) {
+            continue;
+        }
+        let redundant = match (&code[offset - 1], &code[offset]) {
+            (load, Bytecode::Pop) => is_load(load),
+            (Bytecode::StLoc(stored), Bytecode::MoveLoc(moved)) => {
+                stored == moved && !live[offset].contains(moved)
+            }
+            _ => false,
+        };
+        if redundant {
+            keep[offset - 1] = false;
+            keep[offset] = false;
+        }
+    }
+    remove_instructions(code, source_map, &keep);
+}
+
+/// Whether the instruction only pushes a value, without any other effect
+fn is_load(instr: &Bytecode) -> bool {
+    use Bytecode::*;
+    matches!(
+        instr,
+        CopyLoc(_)
+            | LdConst(_)
+            | LdTrue
+            | LdFalse
+            | LdU8(_)
+            | LdU16(_)
+            | LdU32(_)
+            | LdU64(_)
+            | LdU128(_)
+            | LdU256(_)
+    )
+}
@@ -0,0 +1,72 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use move_binary_format::{
+    control_flow_graph::{BlockId, ControlFlowGraph, VMControlFlowGraph},
+    file_format::{Bytecode, LocalIndex},
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Returns, for each instruction, the locals whose value may be read after it. Borrowed locals
+/// are live everywhere, as they can be read through references the analysis does not track.
+pub fn live_after(code: &[Bytecode]) -> Vec<BTreeSet<LocalIndex>> {
+    let cfg = VMControlFlowGraph::new(code);
+    let blocks = cfg.blocks();
+
+    let mut live_in: BTreeMap<BlockId, BTreeSet<LocalIndex>> = BTreeMap::new();
+    let live_out = |live_in: &BTreeMap<BlockId, BTreeSet<LocalIndex>>, block| {
+        cfg.successors(block)
+            .iter()
+            .flat_map(|succ| live_in.get(succ).into_iter().flatten().copied())
+            .collect::<BTreeSet<_>>()
+    };
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for block in blocks.iter().rev() {
+            let mut live = live_out(&live_in, *block);
+            for offset in (cfg.block_start(*block)..=cfg.block_end(*block)).rev() {
+                transfer(&code[offset as usize], &mut live);
+            }
+            if live_in.get(block) != Some(&live) {
+                live_in.insert(*block, live);
+                changed = true;
+            }
+        }
+    }
+
+    let borrowed = borrowed_locals(code);
+    let mut result = vec![BTreeSet::new(); code.len()];
+    for block in &blocks {
+        let mut live = live_out(&live_in, *block);
+        for offset in (cfg.block_start(*block)..=cfg.block_end(*block)).rev() {
+            result[offset as usize] = live.union(&borrowed).copied().collect();
+            transfer(&code[offset as usize], &mut live);
+        }
+    }
+    result
+}
+
+fn transfer(instr: &Bytecode, live: &mut BTreeSet<LocalIndex>) {
+    match instr {
+        Bytecode::StLoc(local) => {
+            live.remove(local);
+        }
+        Bytecode::CopyLoc(local)
+        | Bytecode::MoveLoc(local)
+        | Bytecode::MutBorrowLoc(local)
+        | Bytecode::ImmBorrowLoc(local) => {
+            live.insert(*local);
+        }
+        _ => (),
+    }
+}
+
+fn borrowed_locals(code: &[Bytecode]) -> BTreeSet<LocalIndex> {
+    code.iter()
+        .filter_map(|instr| match instr {
+            Bytecode::MutBorrowLoc(local) | Bytecode::ImmBorrowLoc(local) => Some(*local),
+            _ => None,
+        })
+        .collect()
+}
@@ -0,0 +1,347 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Optimizations of the bytecode of compiled modules, run when compiling with `--optimize`.
+//!
+//! `cfgir` optimizes the control flow graph of each function, but the translation to bytecode
+//! leaves stores, copies and branches that can only be seen to be redundant in the final code.
+//! Each pass rewrites the bodies of all functions in a module, after which the module is verified
+//! again; if the bytecode verifier rejects the result, the pass is discarded.
+
+mod branch_chains;
+mod copy_propagation;
+mod dead_stores;
+mod liveness;
+mod unused_locals;
+
+use move_binary_format::file_format as F;
+use move_bytecode_source_map::source_map::{FunctionSourceMap, SourceMap};
+use move_bytecode_verifier::verifier::verify_module;
+
+type Pass = fn(&mut F::CompiledModule, F::FunctionDefinitionIndex, &mut FunctionSourceMap);
+
+const PASSES: &[Pass] = &[
+    dead_stores::optimize,
+    copy_propagation::optimize,
+    branch_chains::optimize,
+    unused_locals::optimize,
+];
+
+pub fn module(module: &mut F::CompiledModule, source_map: &mut SourceMap) {
+    // Nothing can be said about passes over code which does not verify in the first place
+    if verify_module(module).is_err() {
+        return;
+    }
+    for pass in PASSES {
+        let mut optimized = module.clone();
+        let mut optimized_source_map = source_map.clone();
+        for i in 0..optimized.function_defs.len() {
+            let idx = F::FunctionDefinitionIndex(i as F::TableIndex);
+            if optimized.function_defs[i].code.is_none() {
+                continue;
+            }
+            let function_source_map = match optimized_source_map.get_function_source_map_mut(idx) {
+                Ok(function_source_map) => function_source_map,
+                Err(_) => return,
+            };
+            pass(&mut optimized, idx, function_source_map);
+        }
+        if verify_module(&optimized).is_ok() {
+            *module = optimized;
+            *source_map = optimized_source_map;
+        }
+    }
+    remove_unused_signatures(module);
+}
+
+/// Removes the signatures no longer referenced after the passes, such as the locals of functions
+/// whose unused locals were removed, renumbering the signatures after them.
+fn remove_unused_signatures(module: &mut F::CompiledModule) {
+    let mut used = vec![false; module.signatures.len()];
+    for idx in signature_indices_mut(module) {
+        used[idx.0 as usize] = true;
+    }
+    if used.iter().all(|used| *used) {
+        return;
+    }
+    // new_indices[i] is the new index of signature i, if it is still used
+    let mut new_indices = Vec::with_capacity(used.len());
+    let mut kept = 0;
+    for used in &used {
+        new_indices.push(kept);
+        if *used {
+            kept += 1;
+        }
+    }
+    for idx in signature_indices_mut(module) {
+        idx.0 = new_indices[idx.0 as usize];
+    }
+    let mut used = used.into_iter();
+    module.signatures.retain(|_| used.next().unwrap());
+}
+
+fn signature_indices_mut(
+    module: &mut F::CompiledModule,
+) -> impl Iterator<Item = &mut F::SignatureIndex> {
+    use F::Bytecode as B;
+    let handles = module
+        .function_handles
+        .iter_mut()
+        .flat_map(|handle| [&mut handle.parameters, &mut handle.return_]);
+    let function_instantiations = module
+        .function_instantiations
+        .iter_mut()
+        .map(|inst| &mut inst.type_parameters);
+    let struct_instantiations = module
+        .struct_def_instantiations
+        .iter_mut()
+        .map(|inst| &mut inst.type_parameters);
+    let field_instantiations = module
+        .field_instantiations
+        .iter_mut()
+        .map(|inst| &mut inst.type_parameters);
+    let code = module
+        .function_defs
+        .iter_mut()
+        .filter_map(|def| def.code.as_mut())
+        .flat_map(|code| {
+            let instrs = code.code.iter_mut().filter_map(|instr| match instr {
+                B::VecPack(idx, _)
+                | B::VecLen(idx)
+                | B::VecImmBorrow(idx)
+                | B::VecMutBorrow(idx)
+                | B::VecPushBack(idx)
+                | B::VecPopBack(idx)
+                | B::VecUnpack(idx, _)
+                | B::VecSwap(idx) => Some(idx),
+                _ => None,
+            });
+            std::iter::once(&mut code.locals).chain(instrs)
+        });
+    handles
+        .chain(function_instantiations)
+        .chain(struct_instantiations)
+        .chain(field_instantiations)
+        .chain(code)
+}
+
+fn code_mut(module: &mut F::CompiledModule, idx: F::FunctionDefinitionIndex) -> &mut F::CodeUnit {
+    module.function_defs[idx.0 as usize]
+        .code
+        .as_mut()
+        .expect("ICE native functions are not optimized")
+}
+
+fn branch_target(instr: &mut F::Bytecode) -> Option<&mut F::CodeOffset> {
+    match instr {
+        F::Bytecode::Branch(offset)
+        | F::Bytecode::BrTrue(offset)
+        | F::Bytecode::BrFalse(offset) => Some(offset),
+        _ => None,
+    }
+}
+
+/// Removes the instructions not marked in `keep`, retargeting branches and remapping the code
+/// offsets recorded in the source map. A branch to a removed instruction continues at the next
+/// instruction kept.
+fn remove_instructions(
+    code: &mut Vec<F::Bytecode>,
+    source_map: &mut FunctionSourceMap,
+    keep: &[bool],
+) {
+    // new_offsets[i] is the new offset of the first instruction kept at or after offset i
+    let mut new_offsets = Vec::with_capacity(code.len() + 1);
+    let mut kept = 0;
+    for keep_instr in keep {
+        new_offsets.push(kept);
+        if *keep_instr {
+            kept += 1;
+        }
+    }
+    new_offsets.push(kept);
+
+    *code = std::mem::take(code)
+        .into_iter()
+        .zip(keep)
+        .filter(|(_, keep_instr)| **keep_instr)
+        .map(|(mut instr, _)| {
+            if let Some(offset) = branch_target(&mut instr) {
+                *offset = new_offsets[*offset as usize];
+            }
+            instr
+        })
+        .collect();
+    // A location covers the instructions up to the next location. When several locations now
+    // start at the same instruction, the last of them is the one covering it.
+    source_map.code_map = std::mem::take(&mut source_map.code_map)
+        .into_iter()
+        .filter(|(offset, _)| new_offsets[*offset as usize] < kept)
+        .map(|(offset, loc)| (new_offsets[offset as usize], loc))
+        .collect();
+    for offset in source_map.nops.values_mut() {
+        *offset = new_offsets[*offset as usize];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_binary_format::access::ModuleAccess;
+    use move_command_line_common::files::FileHash;
+    use move_ir_types::location::Loc;
+    use F::{Bytecode::*, SignatureToken as T};
+
+    fn loc() -> Loc {
+        Loc::new(FileHash::empty(), 0, 0)
+    }
+
+    /// A module with a single public function `foo(u64): u64` with the given locals and code.
+    fn module_with(locals: Vec<T>, code: Vec<F::Bytecode>) -> F::CompiledModule {
+        let mut module = F::empty_module();
+        module.signatures.push(F::Signature(vec![T::U64]));
+        module.signatures.push(F::Signature(locals));
+        module.function_handles.push(F::FunctionHandle {
+            module: F::ModuleHandleIndex(0),
+            name: F::IdentifierIndex(module.identifiers.len() as F::TableIndex),
+            parameters: F::SignatureIndex(1),
+            return_: F::SignatureIndex(1),
+            type_parameters: vec![],
+        });
+        module
+            .identifiers
+            .push(move_core_types::identifier::Identifier::new("foo").unwrap());
+        module.function_defs.push(F::FunctionDefinition {
+            function: F::FunctionHandleIndex(0),
+            visibility: F::Visibility::Public,
+            is_entry: false,
+            acquires_global_resources: vec![],
+            code: Some(F::CodeUnit {
+                locals: F::SignatureIndex(2),
+                code,
+            }),
+        });
+        module
+    }
+
+    fn run(pass: Pass, locals: Vec<T>, code: Vec<F::Bytecode>) -> F::CompiledModule {
+        let mut module = module_with(locals, code);
+        pass(
+            &mut module,
+            F::FunctionDefinitionIndex(0),
+            &mut FunctionSourceMap::new(loc(), false),
+        );
+        module
+    }
+
+    fn code(module: &F::CompiledModule) -> &[F::Bytecode] {
+        &module.function_defs[0].code.as_ref().unwrap().code
+    }
+
+    #[test]
+    fn copy_propagation_moves_last_copy() {
+        let module = run(
+            copy_propagation::optimize,
+            vec![],
+            vec![CopyLoc(0), CopyLoc(0), Add, Ret],
+        );
+        assert_eq!(code(&module), &[CopyLoc(0), MoveLoc(0), Add, Ret]);
+    }
+
+    #[test]
+    fn copy_propagation_keeps_copies_read_in_loop() {
+        let instrs = vec![CopyLoc(0), Pop, LdTrue, BrTrue(0), CopyLoc(0), Ret];
+        let module = run(copy_propagation::optimize, vec![], instrs);
+        assert_eq!(
+            code(&module),
+            &[CopyLoc(0), Pop, LdTrue, BrTrue(0), MoveLoc(0), Ret]
+        );
+    }
+
+    #[test]
+    fn copy_propagation_keeps_copies_of_borrowed_locals() {
+        let instrs = vec![ImmBorrowLoc(0), Pop, CopyLoc(0), Ret];
+        let module = run(copy_propagation::optimize, vec![], instrs.clone());
+        assert_eq!(code(&module), instrs.as_slice());
+    }
+
+    #[test]
+    fn dead_stores_removes_unread_stores() {
+        let module = run(
+            dead_stores::optimize,
+            vec![T::U64, T::U64],
+            vec![CopyLoc(0), StLoc(1), LdU64(1), StLoc(2), MoveLoc(0), Ret],
+        );
+        assert_eq!(code(&module), &[MoveLoc(0), Ret]);
+    }
+
+    #[test]
+    fn dead_stores_removes_store_moved_back() {
+        let module = run(
+            dead_stores::optimize,
+            vec![T::U64],
+            vec![MoveLoc(0), StLoc(1), MoveLoc(1), Ret],
+        );
+        assert_eq!(code(&module), &[MoveLoc(0), Ret]);
+    }
+
+    #[test]
+    fn branch_chains_retargets_and_removes_branches() {
+        let module = run(
+            branch_chains::optimize,
+            vec![],
+            vec![
+                LdTrue,
+                BrTrue(4),
+                Branch(3),
+                Branch(5),
+                Branch(5),
+                MoveLoc(0),
+                Ret,
+            ],
+        );
+        assert_eq!(code(&module), &[LdTrue, BrTrue(2), MoveLoc(0), Ret]);
+    }
+
+    #[test]
+    fn unused_locals_renumbers_locals() {
+        let mut module = run(
+            unused_locals::optimize,
+            vec![T::Bool, T::U64],
+            vec![MoveLoc(0), StLoc(2), MoveLoc(2), Ret],
+        );
+        assert_eq!(code(&module), &[MoveLoc(0), StLoc(1), MoveLoc(1), Ret]);
+        let locals = module.function_defs[0].code.as_ref().unwrap().locals;
+        assert_eq!(module.signature_at(locals), &F::Signature(vec![T::U64]));
+        // The locals now share the signature of the parameters, and the signatures nothing
+        // refers to any more are dropped
+        remove_unused_signatures(&mut module);
+        assert_eq!(module.signatures, vec![F::Signature(vec![T::U64])]);
+        let handle = &module.function_handles[0];
+        assert_eq!(handle.parameters, F::SignatureIndex(0));
+        assert_eq!(handle.return_, F::SignatureIndex(0));
+        assert_eq!(
+            module.function_defs[0].code.as_ref().unwrap().locals,
+            F::SignatureIndex(0)
+        );
+    }
+
+    #[test]
+    fn module_does_not_grow_signatures() {
+        let mut module = module_with(
+            vec![T::U64, T::U64],
+            vec![CopyLoc(0), StLoc(1), LdU64(1), StLoc(2), MoveLoc(0), Ret],
+        );
+        let mut source_map = SourceMap::new(loc(), None);
+        source_map
+            .add_top_level_function_mapping(F::FunctionDefinitionIndex(0), loc(), false)
+            .unwrap();
+        super::module(&mut module, &mut source_map);
+
+        assert_eq!(code(&module), &[MoveLoc(0), Ret]);
+        assert_eq!(
+            module.signatures,
+            vec![F::Signature(vec![]), F::Signature(vec![T::U64])]
+        );
+        assert!(verify_module(&module).is_ok());
+    }
+}
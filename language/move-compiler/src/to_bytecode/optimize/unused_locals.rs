@@ -0,0 +1,85 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Removes the locals no instruction refers to, renumbering the locals after them.
+
+use super::code_mut;
+use move_binary_format::{
+    access::ModuleAccess,
+    file_format::{
+        Bytecode, CompiledModule, FunctionDefinitionIndex, LocalIndex, Signature, SignatureIndex,
+        TableIndex,
+    },
+};
+use move_bytecode_source_map::source_map::FunctionSourceMap;
+use std::collections::BTreeSet;
+
+pub fn optimize(
+    module: &mut CompiledModule,
+    idx: FunctionDefinitionIndex,
+    source_map: &mut FunctionSourceMap,
+) {
+    let handle = module.function_handle_at(module.function_defs[idx.0 as usize].function);
+    let num_params = module.signature_at(handle.parameters).len();
+    let code = code_mut(module, idx);
+    let used: BTreeSet<_> = code.code.iter().filter_map(local_of).collect();
+    let locals_idx = code.locals;
+    let locals = module.signature_at(locals_idx).0.clone();
+    let mut new_indices = (0..num_params)
+        .map(|i| Some(i as LocalIndex))
+        .collect::<Vec<_>>();
+    let mut new_locals = vec![];
+    let mut new_local_names = vec![];
+    for (i, local) in locals.into_iter().enumerate() {
+        let old_index = (num_params + i) as LocalIndex;
+        if used.contains(&old_index) {
+            new_indices.push(Some((num_params + new_locals.len()) as LocalIndex));
+            new_locals.push(local);
+            new_local_names.extend(source_map.locals.get(i).cloned());
+        } else {
+            new_indices.push(None);
+        }
+    }
+    if new_indices.iter().all(Option::is_some) {
+        return;
+    }
+
+    let new_signature = Signature(new_locals);
+    let signature_idx = match module.signatures.iter().position(|s| s == &new_signature) {
+        Some(position) => SignatureIndex(position as TableIndex),
+        None => {
+            module.signatures.push(new_signature);
+            SignatureIndex((module.signatures.len() - 1) as TableIndex)
+        }
+    };
+    let code = code_mut(module, idx);
+    code.locals = signature_idx;
+    for instr in &mut code.code {
+        if let Some(local) = local_of_mut(instr) {
+            *local = new_indices[*local as usize].expect("ICE used local removed");
+        }
+    }
+    source_map.locals = new_local_names;
+}
+
+fn local_of(instr: &Bytecode) -> Option<LocalIndex> {
+    match instr {
+        Bytecode::CopyLoc(local)
+        | Bytecode::MoveLoc(local)
+        | Bytecode::StLoc(local)
+        | Bytecode::MutBorrowLoc(local)
+        | Bytecode::ImmBorrowLoc(local) => Some(*local),
+        _ => None,
+    }
+}
+
+fn local_of_mut(instr: &mut Bytecode) -> Option<&mut LocalIndex> {
+    match instr {
+        Bytecode::CopyLoc(local)
+        | Bytecode::MoveLoc(local)
+        | Bytecode::StLoc(local)
+        | Bytecode::MutBorrowLoc(local)
+        | Bytecode::ImmBorrowLoc(local) => Some(local),
+        _ => None,
+    }
+}
@@ -70,6 +70,7 @@ pub struct Context<'env> {
 
     pub current_module: Option<ModuleIdent>,
     pub current_function: Option<FunctionName>,
+    pub checking_constant: bool,
     pub current_script_constants: Option<UniqueMap<ConstantName, ConstantInfo>>,
     pub return_type: Option<Type>,
     locals: UniqueMap<Var, Type>,
@@ -121,6 +122,7 @@ impl<'env> Context<'env> {
             subst: Subst::empty(),
             current_module: None,
             current_function: None,
+            checking_constant: false,
             current_script_constants: None,
             return_type: None,
             constraints: vec![],
@@ -726,7 +728,9 @@ pub fn make_constant_type(
         } = context.constant_info(m, c);
         (*defined_loc, signature.clone())
     };
-    if !in_current_module {
+    // Constants of other modules can still be used in constant definitions, as the value is
+    // computed at compile time
+    if !in_current_module && !context.checking_constant {
         let msg = match m {
             None => format!("Invalid access of '{}'", c),
             Some(mident) => format!("Invalid access of '{}::{}'", mident, c),
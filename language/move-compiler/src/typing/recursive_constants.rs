@@ -0,0 +1,221 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    diag,
+    diagnostics::Diagnostic,
+    expansion::ast::ModuleIdent,
+    parser::ast::ConstantName,
+    shared::{unique_map::UniqueMap, *},
+    typing::ast as T,
+};
+use move_ir_types::location::*;
+use move_symbol_pool::Symbol;
+use petgraph::{algo::tarjan_scc as petgraph_scc, graphmap::DiGraphMap};
+use std::{collections::BTreeMap, fmt};
+
+// A constant, qualified by its module. The module is `None` for constants declared in a script
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct QualifiedConstant(Option<ModuleIdent>, ConstantName);
+
+impl fmt::Display for QualifiedConstant {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.0 {
+            None => write!(f, "{}", self.1),
+            Some(m) => write!(f, "{}::{}", m, self.1),
+        }
+    }
+}
+
+struct Context {
+    constant_neighbors: BTreeMap<QualifiedConstant, BTreeMap<QualifiedConstant, Loc>>,
+    current_constant: Option<QualifiedConstant>,
+}
+
+impl Context {
+    fn new() -> Self {
+        Context {
+            constant_neighbors: BTreeMap::new(),
+            current_constant: None,
+        }
+    }
+
+    fn add_usage(&mut self, loc: Loc, module: &Option<ModuleIdent>, cname: &ConstantName) {
+        self.constant_neighbors
+            .entry(self.current_constant.unwrap())
+            .or_default()
+            .insert(QualifiedConstant(*module, *cname), loc);
+    }
+
+    fn constant_graph(&self) -> DiGraphMap<&QualifiedConstant, ()> {
+        let edges = self
+            .constant_neighbors
+            .iter()
+            .flat_map(|(parent, children)| children.keys().map(move |child| (parent, child)));
+        DiGraphMap::from_edges(edges)
+    }
+}
+
+//**************************************************************************************************
+// Program
+//**************************************************************************************************
+
+// Constants can refer to constants of other modules, so the cycles are checked across all modules
+// of the program. Scripts cannot be referred to, so each script is checked on its own
+pub fn program(
+    compilation_env: &mut CompilationEnv,
+    modules: &UniqueMap<ModuleIdent, T::ModuleDefinition>,
+    scripts: &BTreeMap<Symbol, T::Script>,
+) {
+    let context = &mut Context::new();
+    for (mident, mdef) in modules.key_cloned_iter() {
+        constants(context, Some(mident), &mdef.constants)
+    }
+    report_cycles(compilation_env, context);
+
+    for script in scripts.values() {
+        let context = &mut Context::new();
+        constants(context, None, &script.constants);
+        report_cycles(compilation_env, context);
+    }
+}
+
+fn constants(
+    context: &mut Context,
+    mident: Option<ModuleIdent>,
+    constants: &UniqueMap<ConstantName, T::Constant>,
+) {
+    for (cname, cdef) in constants.key_cloned_iter() {
+        assert!(context.current_constant.is_none(), "ICE constant not unset");
+        context.current_constant = Some(QualifiedConstant(mident, cname));
+        exp(context, &cdef.value);
+        context.current_constant = None;
+    }
+}
+
+fn report_cycles(compilation_env: &mut CompilationEnv, context: &Context) {
+    let graph = context.constant_graph();
+    // - get the strongly connected components
+    // - filter out single nodes that do not connect to themselves
+    // - report those cycles
+    petgraph_scc(&graph)
+        .into_iter()
+        .filter(|scc| scc.len() > 1 || graph.contains_edge(scc[0], scc[0]))
+        .for_each(|scc| compilation_env.add_diag(cycle_error(context, &graph, scc[0])))
+}
+
+//**************************************************************************************************
+// Expressions
+//**************************************************************************************************
+
+fn sequence(context: &mut Context, seq: &T::Sequence) {
+    seq.iter().for_each(|item| sequence_item(context, item))
+}
+
+fn sequence_item(context: &mut Context, item: &T::SequenceItem) {
+    use T::SequenceItem_ as S;
+    match &item.value {
+        S::Bind(_, _, te) | S::Seq(te) => exp(context, te),
+        S::Declare(_) => (),
+    }
+}
+
+fn exp(context: &mut Context, e: &T::Exp) {
+    use T::UnannotatedExp_ as E;
+    match &e.exp.value {
+        E::Constant(m, c) => context.add_usage(e.exp.loc, m, c),
+
+        E::Unit { .. }
+        | E::Value(_)
+        | E::Use(_)
+        | E::Move { .. }
+        | E::Copy { .. }
+        | E::BorrowLocal(_, _)
+        | E::Break
+        | E::Continue
+        | E::Spec(_, _)
+        | E::UnresolvedError => (),
+
+        E::ModuleCall(call) => exp(context, &call.arguments),
+
+        E::IfElse(eb, et, ef) => {
+            exp(context, eb);
+            exp(context, et);
+            exp(context, ef);
+        }
+        E::While(eb, eloop) => {
+            exp(context, eb);
+            exp(context, eloop);
+        }
+        E::Loop { body: eloop, .. } => exp(context, eloop),
+        E::Block(seq) => sequence(context, seq),
+        E::Assign(_, _, er) => exp(context, er),
+
+        E::Builtin(_, er)
+        | E::Vector(_, _, _, er)
+        | E::Return(er)
+        | E::Abort(er)
+        | E::Dereference(er)
+        | E::UnaryExp(_, er)
+        | E::Borrow(_, er, _)
+        | E::TempBorrow(_, er) => exp(context, er),
+        E::Mutate(el, er) | E::BinopExp(el, _, _, er) => {
+            exp(context, el);
+            exp(context, er)
+        }
+
+        E::Pack(_, _, _, fields) => {
+            for (_, _, (_, (_, fe))) in fields.iter() {
+                exp(context, fe)
+            }
+        }
+        E::ExpList(el) => {
+            for item in el {
+                match item {
+                    T::ExpListItem::Single(e, _) | T::ExpListItem::Splat(_, e, _) => {
+                        exp(context, e)
+                    }
+                }
+            }
+        }
+
+        E::Cast(e, _) | E::Annotate(e, _) => exp(context, e),
+    }
+}
+
+//**************************************************************************************************
+// Errors
+//**************************************************************************************************
+
+fn cycle_error(
+    context: &Context,
+    graph: &DiGraphMap<&QualifiedConstant, ()>,
+    cycle_node: &QualifiedConstant,
+) -> Diagnostic {
+    let cycle = shortest_cycle(graph, cycle_node);
+
+    let cycle_strings = cycle
+        .iter()
+        .map(|c| format!("'{}'", c))
+        .collect::<Vec<_>>()
+        .join(" uses ");
+
+    let get_loc =
+        |user: &QualifiedConstant, used: &QualifiedConstant| context.constant_neighbors[user][used];
+    let (used_loc, user, used) = match cycle.len() {
+        1 => (get_loc(cycle[0], cycle[0]), cycle[0], cycle[0]),
+        len => (
+            get_loc(cycle[len - 2], cycle[len - 1]),
+            cycle[len - 2],
+            cycle[len - 1],
+        ),
+    };
+
+    let use_msg = format!("Invalid use of '{}' in the value of '{}'.", used, user);
+    let cycle_msg = format!("Using this constant creates a cycle: {}", cycle_strings);
+    diag!(
+        TypeSafety::CyclicConstant,
+        (used_loc, use_msg),
+        (used_loc, cycle_msg)
+    )
+}
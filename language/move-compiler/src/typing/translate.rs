@@ -4,7 +4,7 @@
 
 use super::{
     core::{self, Context, Subst},
-    expand, globals, infinite_instantiations, recursive_structs,
+    expand, globals, infinite_instantiations, recursive_constants, recursive_structs,
 };
 use crate::{
     diag,
@@ -39,6 +39,7 @@ pub fn program(
 
     assert!(context.constraints.is_empty());
     recursive_structs::modules(context.env, &modules);
+    recursive_constants::program(context.env, &modules, &scripts);
     infinite_instantiations::modules(context.env, &modules);
     T::Program { modules, scripts }
 }
@@ -236,6 +237,7 @@ fn function_body(
 fn constant(context: &mut Context, _name: ConstantName, nconstant: N::Constant) -> T::Constant {
     assert!(context.constraints.is_empty());
     context.reset_for_module_item();
+    context.checking_constant = true;
 
     let N::Constant {
         attributes,
@@ -270,6 +272,7 @@ fn constant(context: &mut Context, _name: ConstantName, nconstant: N::Constant)
     expand::exp(context, &mut value);
 
     check_valid_constant::exp(context, &value);
+    context.checking_constant = false;
 
     T::Constant {
         attributes,
@@ -364,7 +367,9 @@ mod check_valid_constant {
             //*****************************************
             // Valid cases
             //*****************************************
-            E::Unit { .. } | E::Value(_) | E::Move { .. } | E::Copy { .. } => return,
+            E::Unit { .. } | E::Value(_) | E::Constant(_, _) | E::Move { .. } | E::Copy { .. } => {
+                return
+            }
             E::Block(seq) => {
                 sequence(context, seq);
                 return;
@@ -447,7 +452,6 @@ mod check_valid_constant {
                 }
                 "Structs are"
             }
-            E::Constant(_, _) => "Other constants are",
         };
         context.env.add_diag(diag!(
             TypeSafety::UnsupportedConstant,
@@ -8,8 +8,11 @@ use crate::{
     shared::NumericalAddress,
 };
 use move_core_types::{
-    account_address::AccountAddress, identifier::Identifier, language_storage::ModuleId,
-    value::MoveValue,
+    account_address::AccountAddress,
+    identifier::Identifier,
+    language_storage::ModuleId,
+    value::{MoveTypeLayout, MoveValue},
+    vm_status::StatusCode,
 };
 use std::collections::BTreeMap;
 
@@ -29,6 +32,9 @@ pub struct TestPlan {
 pub struct ModuleTestPlan {
     pub module_id: ModuleId,
     pub tests: BTreeMap<TestName, TestCase>,
+    // The #[before_each] function of the module, if any. Its effects on storage are shared by all
+    // tests in the module.
+    pub before_each: Option<SetupFunction>,
 }
 
 #[derive(Debug, Clone)]
@@ -36,6 +42,23 @@ pub struct TestCase {
     pub test_name: TestName,
     pub arguments: Vec<MoveValue>,
     pub expected_failure: Option<ExpectedFailure>,
+    // Set for #[random_test] functions: each parameter's name along with either its fixed value or
+    // the layout of the values to generate for it. `arguments` is empty for these tests.
+    pub random_arguments: Option<Vec<(String, TestArgument)>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SetupFunction {
+    pub function_name: String,
+    pub arguments: Vec<MoveValue>,
+}
+
+#[derive(Debug, Clone)]
+pub enum TestArgument {
+    // value assigned in the test attribute, e.g., a signer
+    Fixed(MoveValue),
+    // value generated anew for every run of the test
+    Generated(MoveTypeLayout),
 }
 
 #[derive(Debug, Clone)]
@@ -44,6 +67,19 @@ pub enum ExpectedFailure {
     Expected,
     // expected failure, abort code checked
     ExpectedWithCode(u64),
+    // expected failure with a specific VM error, e.g., an arithmetic error or an abort code raised
+    // from a specific module
+    ExpectedWithError(ExpectedMoveError),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpectedMoveError {
+    // the expected major status of the failure
+    pub status: StatusCode,
+    // the expected sub-status, e.g., the abort code for `StatusCode::ABORTED`. Not checked if `None`
+    pub sub_status: Option<u64>,
+    // the module the failure must be raised in. Not checked if `None`
+    pub location: Option<ModuleId>,
 }
 
 impl ModuleTestPlan {
@@ -51,11 +87,16 @@ impl ModuleTestPlan {
         addr: &NumericalAddress,
         module_name: &str,
         tests: BTreeMap<TestName, TestCase>,
+        before_each: Option<SetupFunction>,
     ) -> Self {
         let addr = AccountAddress::new((*addr).into_bytes());
         let name = Identifier::new(module_name.to_owned()).unwrap();
         let module_id = ModuleId::new(addr, name);
-        ModuleTestPlan { module_id, tests }
+        ModuleTestPlan {
+            module_id,
+            tests,
+            before_each,
+        }
     }
 }
 
@@ -6,25 +6,37 @@ use crate::{
     cfgir::ast as G,
     diag,
     expansion::ast::{self as E, Address, ModuleIdent, ModuleIdent_},
+    hlir::ast as H,
+    naming::ast::BuiltinTypeName_,
     shared::{
         known_attributes::{KnownAttribute, TestingAttribute},
         CompilationEnv, Identifier, NumericalAddress,
     },
-    unit_test::{ExpectedFailure, ModuleTestPlan, TestCase},
+    unit_test::{
+        ExpectedFailure, ExpectedMoveError, ModuleTestPlan, SetupFunction, TestArgument, TestCase,
+    },
+};
+use move_core_types::{
+    account_address::AccountAddress as MoveAddress,
+    identifier::Identifier as MoveIdentifier,
+    language_storage::ModuleId,
+    value::{MoveStructLayout, MoveTypeLayout, MoveValue},
+    vm_status::StatusCode,
 };
-use move_core_types::{account_address::AccountAddress as MoveAddress, value::MoveValue};
 use move_ir_types::location::Loc;
 use move_symbol_pool::Symbol;
 use std::collections::BTreeMap;
 
 struct Context<'env> {
     env: &'env mut CompilationEnv,
+    prog: &'env G::Program,
 }
 
 impl<'env> Context<'env> {
-    fn new(compilation_env: &'env mut CompilationEnv) -> Self {
+    fn new(compilation_env: &'env mut CompilationEnv, prog: &'env G::Program) -> Self {
         Self {
             env: compilation_env,
+            prog,
         }
     }
 
@@ -47,7 +59,7 @@ pub fn construct_test_plan(
     if !compilation_env.flags().is_testing() {
         return None;
     }
-    let mut context = Context::new(compilation_env);
+    let mut context = Context::new(compilation_env, prog);
     Some(
         prog.modules
             .key_cloned_iter()
@@ -75,14 +87,104 @@ fn construct_module_test_plan(
                 .map(|test_case| (fn_name.to_string(), test_case))
         })
         .collect();
+    let before_each = build_before_each(context, module);
 
     if tests.is_empty() {
         None
     } else {
         let sp!(_, ModuleIdent_ { address, module }) = &module_ident;
         let addr_bytes = context.resolve_address(address);
-        Some(ModuleTestPlan::new(&addr_bytes, &module.0.value, tests))
+        Some(ModuleTestPlan::new(
+            &addr_bytes,
+            &module.0.value,
+            tests,
+            before_each,
+        ))
+    }
+}
+
+fn build_before_each(context: &mut Context, module: &G::ModuleDefinition) -> Option<SetupFunction> {
+    let setup_functions: Vec<_> = module
+        .functions
+        .iter()
+        .filter_map(|(loc, fn_name, func)| {
+            let attr = func
+                .attributes
+                .get_(&E::AttributeName_::Known(KnownAttribute::Testing(
+                    TestingAttribute::BeforeEach,
+                )))?;
+            Some((loc, fn_name, func, attr))
+        })
+        .collect();
+    let (first, rest) = setup_functions.split_first()?;
+    // A module can have at most one #[before_each] function
+    for (fn_loc, _, _, attr) in rest {
+        let msg = "Multiple #[before_each] functions in the same module. At most one setup \
+                   function can be declared per module";
+        context.env.add_diag(diag!(
+            Attributes::InvalidUsage,
+            (attr.loc, msg),
+            (first.3.loc, "Previously declared here"),
+            (*fn_loc, "Error found in this setup function"),
+        ));
+    }
+    let (fn_loc, fn_name, function, attr) = first;
+    build_setup_info(context, *fn_loc, fn_name, function, attr)
+}
+
+fn build_setup_info(
+    context: &mut Context,
+    fn_loc: Loc,
+    fn_name: &str,
+    function: &G::Function,
+    setup_attribute: &E::Attribute,
+) -> Option<SetupFunction> {
+    const IN_THIS_SETUP_MSG: &str = "Error found in this setup function";
+
+    // A #[before_each] function cannot also be a test
+    for attr in [TestingAttribute::Test, TestingAttribute::RandomTest] {
+        if let Some(test_attribute) = function
+            .attributes
+            .get_(&E::AttributeName_::Known(KnownAttribute::Testing(attr)))
+        {
+            let msg = format!(
+                "Function annotated as both #[{}(...)] and #[{}(...)]. A setup function cannot \
+                 be a test",
+                TestingAttribute::BEFORE_EACH,
+                attr.name(),
+            );
+            context.env.add_diag(diag!(
+                Attributes::InvalidUsage,
+                (test_attribute.loc, msg),
+                (setup_attribute.loc, "Previously annotated here"),
+                (fn_loc, IN_THIS_SETUP_MSG),
+            ));
+            return None;
+        }
+    }
+
+    let setup_annotation_params = parse_test_attribute(context, setup_attribute, 0);
+    let mut arguments = Vec::new();
+    for (var, _) in &function.signature.parameters {
+        match setup_annotation_params.get(&var.value()) {
+            Some(value) => arguments.push(value.clone()),
+            None => {
+                let missing_param_msg = "Missing parameter assignment in setup function. \
+                                         Expected a parameter to be assigned in this attribute";
+                context.env.add_diag(diag!(
+                    Attributes::InvalidTest,
+                    (setup_attribute.loc, missing_param_msg),
+                    (var.loc(), "Corresponding to this parameter"),
+                    (fn_loc, IN_THIS_SETUP_MSG),
+                ))
+            }
+        }
     }
+
+    Some(SetupFunction {
+        function_name: fn_name.to_string(),
+        arguments,
+    })
 }
 
 fn build_test_info<'func>(
@@ -101,11 +203,12 @@ fn build_test_info<'func>(
     const IN_THIS_TEST_MSG: &str = "Error found in this test";
 
     let test_attribute_opt = get_attrs(TestingAttribute::Test);
+    let random_test_attribute_opt = get_attrs(TestingAttribute::RandomTest);
     let abort_attribute_opt = get_attrs(TestingAttribute::ExpectedFailure);
     let test_only_attribute_opt = get_attrs(TestingAttribute::TestOnly);
 
-    let test_attribute = match test_attribute_opt {
-        None => {
+    let test_attribute = match (test_attribute_opt, random_test_attribute_opt) {
+        (None, None) => {
             // expected failures cannot be annotated on non-#[test] functions
             if let Some(abort_attribute) = abort_attribute_opt {
                 let fn_msg = "Only functions defined as a test with #[test] can also have an \
@@ -119,8 +222,22 @@ fn build_test_info<'func>(
             }
             return None;
         }
-        Some(test_attribute) => test_attribute,
+        (Some(test_attribute), Some(random_test_attribute)) => {
+            // A #[test] function cannot also be annotated #[random_test]
+            let msg =
+                "Function annotated as both #[test(...)] and #[random_test(...)]. You need to \
+                       declare it as either one or the other";
+            context.env.add_diag(diag!(
+                Attributes::InvalidUsage,
+                (random_test_attribute.loc, msg),
+                (test_attribute.loc, PREVIOUSLY_ANNOTATED_MSG),
+                (fn_loc, IN_THIS_TEST_MSG),
+            ));
+            return None;
+        }
+        (Some(test_attribute), None) | (None, Some(test_attribute)) => test_attribute,
     };
+    let is_random_test = test_attribute_opt.is_none();
 
     // A #[test] function cannot also be annotated #[test_only]
     if let Some(test_only_attribute) = test_only_attribute_opt {
@@ -136,9 +253,24 @@ fn build_test_info<'func>(
 
     let test_annotation_params = parse_test_attribute(context, test_attribute, 0);
     let mut arguments = Vec::new();
-    for (var, _) in &function.signature.parameters {
+    let mut random_arguments = Vec::new();
+    for (var, ty) in &function.signature.parameters {
         match test_annotation_params.get(&var.value()) {
+            Some(value) if is_random_test => {
+                random_arguments.push((var.value().to_string(), TestArgument::Fixed(value.clone())))
+            }
             Some(value) => arguments.push(value.clone()),
+            // Parameters of random tests that are not assigned get generated values
+            None if is_random_test => match generated_argument_layout(context, ty) {
+                Ok(layout) => random_arguments
+                    .push((var.value().to_string(), TestArgument::Generated(layout))),
+                Err((loc, msg)) => context.env.add_diag(diag!(
+                    Attributes::InvalidTest,
+                    (loc, msg),
+                    (var.loc(), "Corresponding to this parameter"),
+                    (fn_loc, IN_THIS_TEST_MSG),
+                )),
+            },
             None => {
                 let missing_param_msg = "Missing test parameter assignment in test. Expected a \
                                          parameter to be assigned in this attribute";
@@ -161,9 +293,110 @@ fn build_test_info<'func>(
         test_name: fn_name.to_string(),
         arguments,
         expected_failure,
+        random_arguments: if is_random_test {
+            Some(random_arguments)
+        } else {
+            None
+        },
     })
 }
 
+//***************************************************************************
+// Random test argument layouts
+//***************************************************************************
+
+// Computes the layout from which values of type `ty` can be generated for a #[random_test]. On
+// failure, returns the location and a description of the offending type.
+fn generated_argument_layout(
+    context: &Context,
+    sp!(loc, ty): &H::SingleType,
+) -> Result<MoveTypeLayout, (Loc, String)> {
+    match ty {
+        H::SingleType_::Base(bt) => generated_base_type_layout(context, bt),
+        H::SingleType_::Ref(_, _) => Err((
+            *loc,
+            "Unsupported parameter type for a random test. Values cannot be generated for \
+             references"
+                .to_string(),
+        )),
+    }
+}
+
+fn generated_base_type_layout(
+    context: &Context,
+    sp!(loc, bt): &H::BaseType,
+) -> Result<MoveTypeLayout, (Loc, String)> {
+    use BuiltinTypeName_ as B;
+    use H::BaseType_ as HB;
+    let layout = match bt {
+        HB::Apply(_, sp!(_, H::TypeName_::Builtin(sp!(_, builtin))), ty_args) => match builtin {
+            B::Bool => MoveTypeLayout::Bool,
+            B::U8 => MoveTypeLayout::U8,
+            B::U16 => MoveTypeLayout::U16,
+            B::U32 => MoveTypeLayout::U32,
+            B::U64 => MoveTypeLayout::U64,
+            B::U128 => MoveTypeLayout::U128,
+            B::U256 => MoveTypeLayout::U256,
+            B::Address => MoveTypeLayout::Address,
+            B::Vector => match ty_args.as_slice() {
+                [elem_ty] => {
+                    MoveTypeLayout::Vector(Box::new(generated_base_type_layout(context, elem_ty)?))
+                }
+                _ => return Err((*loc, "Invalid vector type".to_string())),
+            },
+            B::Signer => {
+                return Err((
+                    *loc,
+                    "Unsupported parameter type for a random test. Values cannot be generated \
+                     for signers, assign them in the attribute instead"
+                        .to_string(),
+                ))
+            }
+        },
+        HB::Apply(_, sp!(_, H::TypeName_::ModuleType(m, s)), ty_args) => {
+            if !ty_args.is_empty() {
+                return Err((
+                    *loc,
+                    "Unsupported parameter type for a random test. Values cannot be generated \
+                     for generic structs"
+                        .to_string(),
+                ));
+            }
+            let fields = match context
+                .prog
+                .modules
+                .get(m)
+                .and_then(|mdef| mdef.structs.get(s))
+                .map(|sdef| &sdef.fields)
+            {
+                Some(H::StructFields::Defined(fields)) => fields,
+                _ => {
+                    return Err((
+                        *loc,
+                        format!(
+                            "Unsupported parameter type for a random test. Values cannot be \
+                             generated for struct '{}::{}'",
+                            m, s
+                        ),
+                    ))
+                }
+            };
+            let field_layouts = fields
+                .iter()
+                .map(|(_, field_ty)| generated_base_type_layout(context, field_ty))
+                .collect::<Result<_, _>>()?;
+            MoveTypeLayout::Struct(MoveStructLayout::Runtime(field_layouts))
+        }
+        HB::Param(_) | HB::Unreachable | HB::UnresolvedError => {
+            return Err((
+                *loc,
+                "Unsupported parameter type for a random test".to_string(),
+            ))
+        }
+    };
+    Ok(layout)
+}
+
 //***************************************************************************
 // Attribute parsers
 //***************************************************************************
@@ -185,7 +418,7 @@ fn parse_test_attribute(
         }
         EA::Name(nm) => {
             assert!(
-                nm.value.as_str() == TestingAttribute::Test.name() && depth == 0,
+                is_test_attribute_name(nm.value.as_str()) && depth == 0,
                 "ICE: We should only be parsing a raw test attribute"
             );
             BTreeMap::new()
@@ -217,7 +450,7 @@ fn parse_test_attribute(
         }
         EA::Parameterized(nm, attributes) => {
             assert!(
-                nm.value.as_str() == TestingAttribute::Test.name() && depth == 0,
+                is_test_attribute_name(nm.value.as_str()) && depth == 0,
                 "ICE: We should only be parsing a raw test attribute"
             );
             attributes
@@ -228,11 +461,18 @@ fn parse_test_attribute(
     }
 }
 
+// The attributes whose parameter assignments are parsed by `parse_test_attribute`
+fn is_test_attribute_name(name: &str) -> bool {
+    name == TestingAttribute::Test.name()
+        || name == TestingAttribute::RandomTest.name()
+        || name == TestingAttribute::BeforeEach.name()
+}
+
 fn parse_failure_attribute(
     context: &mut Context,
     sp!(aloc, expected_attr): &E::Attribute,
 ) -> Option<ExpectedFailure> {
-    use E::{AttributeValue_ as EAV, Attribute_ as EA, Value_ as EV};
+    use E::Attribute_ as EA;
     match expected_attr {
         EA::Name(nm) => {
             assert!(
@@ -256,82 +496,269 @@ fn parse_failure_attribute(
             None
         }
         EA::Parameterized(sp!(_, nm), attrs) => {
-            let mut attrs_vec = attrs.iter().map(|(_, _, attr)| attr).collect::<Vec<_>>();
-            if attrs_vec.len() != 1 {
-                let invalid_attr_msg = format!(
-                    "Invalid #[expected_failure(...)] attribute, expected 1 argument but found {}",
-                    attrs_vec.len()
-                );
-                context
-                    .env
-                    .add_diag(diag!(Attributes::InvalidValue, (*aloc, invalid_attr_msg)));
-                return None;
-            }
             assert!(
                 nm.as_str() == TestingAttribute::ExpectedFailure.name(),
                 "ICE: expected failure attribute must have the right name"
             );
-            let attr = attrs_vec.pop().unwrap();
-            match attr {
-                sp!(assign_loc, EA::Assigned(sp!(_, nm), value))
-                    if nm.as_str() == TestingAttribute::CODE_ASSIGNMENT_NAME =>
-                {
-                    match &**value {
-                        sp!(_, EAV::Value(sp!(_, EV::InferredNum(u))))
-                            if *u <= std::u64::MAX.into() =>
-                        {
-                            Some(ExpectedFailure::ExpectedWithCode(u.down_cast_lossy()))
-                        }
-                        sp!(_, EAV::Value(sp!(_, EV::U64(u)))) => {
-                            Some(ExpectedFailure::ExpectedWithCode(*u))
-                        }
-                        sp!(vloc, EAV::Value(sp!(_, EV::U8(_))))
-                        | sp!(vloc, EAV::Value(sp!(_, EV::U16(_))))
-                        | sp!(vloc, EAV::Value(sp!(_, EV::U32(_))))
-                        | sp!(vloc, EAV::Value(sp!(_, EV::U128(_))))
-                        | sp!(vloc, EAV::Value(sp!(_, EV::U256(_)))) => {
-                            let msg = "Invalid value in expected failure code assignment";
-                            context.env.add_diag(diag!(
-                                Attributes::InvalidValue,
-                                (*assign_loc, msg),
-                                (*vloc, "Annotated non-u64 literals are not permitted"),
-                            ));
-                            None
-                        }
-                        sp!(vloc, _) => {
-                            context.env.add_diag(diag!(
-                                Attributes::InvalidValue,
-                                (*vloc, "Invalid value in expected failure code assignment"),
-                                (*assign_loc, "Unsupported value in this assignment"),
-                            ));
-                            None
+            // The kind of failure expected, along with its sub-status if it is fixed by the kind
+            let mut failure_kind: Option<(Loc, StatusCode, Option<u64>)> = None;
+            let mut minor_status = None;
+            let mut location = None;
+            for (_, _, attr) in attrs.iter() {
+                let kind = match attr {
+                    sp!(loc, EA::Name(sp!(_, nm)))
+                        if nm.as_str() == TestingAttribute::ARITHMETIC_ERROR_NAME =>
+                    {
+                        (*loc, StatusCode::ARITHMETIC_ERROR, None)
+                    }
+                    sp!(loc, EA::Name(sp!(_, nm)))
+                        if nm.as_str() == TestingAttribute::VECTOR_ERROR_NAME =>
+                    {
+                        (*loc, StatusCode::VECTOR_OPERATION_ERROR, None)
+                    }
+                    sp!(loc, EA::Name(sp!(_, nm)))
+                        if nm.as_str() == TestingAttribute::OUT_OF_GAS_NAME =>
+                    {
+                        (*loc, StatusCode::OUT_OF_GAS, None)
+                    }
+                    sp!(assign_loc, EA::Assigned(sp!(_, nm), value))
+                        if nm.as_str() == TestingAttribute::CODE_ASSIGNMENT_NAME =>
+                    {
+                        let code = parse_abort_code(context, *assign_loc, value)?;
+                        (*assign_loc, StatusCode::ABORTED, Some(code))
+                    }
+                    sp!(assign_loc, EA::Assigned(sp!(_, nm), value))
+                        if nm.as_str() == TestingAttribute::MAJOR_STATUS_NAME =>
+                    {
+                        let status = parse_u64_value(context, *assign_loc, value)?;
+                        match StatusCode::try_from(status) {
+                            Ok(status) => (*assign_loc, status, None),
+                            Err(_) => {
+                                let msg = format!("Unknown VM status code {}", status);
+                                context.env.add_diag(diag!(
+                                    Attributes::InvalidValue,
+                                    (value.loc, msg),
+                                    (*assign_loc, "Invalid major status in this assignment"),
+                                ));
+                                return None;
+                            }
                         }
                     }
+                    sp!(assign_loc, EA::Assigned(sp!(_, nm), value))
+                        if nm.as_str() == TestingAttribute::MINOR_STATUS_NAME =>
+                    {
+                        minor_status =
+                            Some((*assign_loc, parse_u64_value(context, *assign_loc, value)?));
+                        continue;
+                    }
+                    sp!(assign_loc, EA::Assigned(sp!(_, nm), value))
+                        if nm.as_str() == TestingAttribute::ERROR_LOCATION =>
+                    {
+                        location = Some(parse_failure_location(context, *assign_loc, value)?);
+                        continue;
+                    }
+                    sp!(assign_loc, EA::Assigned(sp!(nmloc, _), _)) => {
+                        let invalid_name_msg = format!(
+                            "Invalid name in expected failure code assignment. Did you mean to \
+                             use '{}'?",
+                            TestingAttribute::CODE_ASSIGNMENT_NAME
+                        );
+                        context.env.add_diag(diag!(
+                            Attributes::InvalidName,
+                            (*nmloc, invalid_name_msg),
+                            (*assign_loc, "Invalid name in this assignment"),
+                        ));
+                        return None;
+                    }
+                    sp!(loc, _) => {
+                        let msg = "Unsupported attribute value for expected failure attribute";
+                        context.env.add_diag(diag!(
+                            Attributes::InvalidValue,
+                            (*aloc, msg),
+                            (*loc, "Unsupported value in this assignment")
+                        ));
+                        return None;
+                    }
+                };
+                if let Some((prev_loc, _, _)) = &failure_kind {
+                    let msg = format!(
+                        "Invalid #[expected_failure(...)] attribute, expected at most one of {}",
+                        failure_kind_names()
+                    );
+                    context.env.add_diag(diag!(
+                        Attributes::InvalidValue,
+                        (kind.0, msg),
+                        (*prev_loc, "Previously given here"),
+                    ));
+                    return None;
                 }
-                sp!(assign_loc, EA::Assigned(sp!(nmloc, _), _)) => {
-                    let invalid_name_msg = format!(
-                        "Invalid name in expected failure code assignment. Did you mean to use \
-                         '{}'?",
-                        TestingAttribute::CODE_ASSIGNMENT_NAME
+                failure_kind = Some(kind);
+            }
+
+            let (kind_loc, status, sub_status) = match failure_kind {
+                Some(kind) => kind,
+                None => {
+                    let msg = format!(
+                        "Invalid #[expected_failure(...)] attribute, expected one of {}",
+                        failure_kind_names()
+                    );
+                    context
+                        .env
+                        .add_diag(diag!(Attributes::InvalidValue, (*aloc, msg)));
+                    return None;
+                }
+            };
+            // The sub-status of an abort is its abort code, so it cannot be given separately
+            let sub_status = match (sub_status, minor_status) {
+                (Some(_), Some((minor_loc, _))) => {
+                    let msg = format!(
+                        "'{}' cannot be used together with '{}'",
+                        TestingAttribute::MINOR_STATUS_NAME,
+                        TestingAttribute::CODE_ASSIGNMENT_NAME,
                     );
                     context.env.add_diag(diag!(
-                        Attributes::InvalidName,
-                        (*nmloc, invalid_name_msg),
-                        (*assign_loc, "Invalid name in this assignment"),
+                        Attributes::InvalidValue,
+                        (minor_loc, msg),
+                        (kind_loc, "Abort code given here"),
+                    ));
+                    return None;
+                }
+                (sub_status, minor_status) => {
+                    sub_status.or_else(|| minor_status.map(|(_, minor_status)| minor_status))
+                }
+            };
+            Some(match (status, sub_status, location) {
+                // An abort code without a location is checked the same way as it always has been
+                (StatusCode::ABORTED, Some(code), None) => ExpectedFailure::ExpectedWithCode(code),
+                (status, sub_status, location) => {
+                    ExpectedFailure::ExpectedWithError(ExpectedMoveError {
+                        status,
+                        sub_status,
+                        location,
+                    })
+                }
+            })
+        }
+    }
+}
+
+fn failure_kind_names() -> String {
+    format!(
+        "'{}', '{}', '{}', '{}' or '{}'",
+        TestingAttribute::CODE_ASSIGNMENT_NAME,
+        TestingAttribute::ARITHMETIC_ERROR_NAME,
+        TestingAttribute::VECTOR_ERROR_NAME,
+        TestingAttribute::OUT_OF_GAS_NAME,
+        TestingAttribute::MAJOR_STATUS_NAME,
+    )
+}
+
+// Parses the value of an `abort_code` assignment: either a u64 literal, or the name of a u64
+// constant, e.g. `E_NOT_FOUND` or `M::E_NOT_FOUND`
+fn parse_abort_code(
+    context: &mut Context,
+    assign_loc: Loc,
+    value: &E::AttributeValue,
+) -> Option<u64> {
+    use E::{AttributeValue_ as EAV, ModuleAccess_ as EMA};
+    match value {
+        sp!(
+            vloc,
+            EAV::ModuleAccess(sp!(_, EMA::ModuleAccess(mident, cname)))
+        ) => {
+            let constant = context
+                .prog
+                .modules
+                .get(mident)
+                .and_then(|mdef| mdef.constants.get_(&cname.value));
+            match constant.map(|constant| &constant.value) {
+                Some(Some(MoveValue::U64(code))) => Some(*code),
+                // The constant could not be evaluated, which has already been reported
+                Some(None) => None,
+                Some(Some(_)) => {
+                    let msg = format!("Constant '{}::{}' is not a u64", mident, cname);
+                    context.env.add_diag(diag!(
+                        Attributes::InvalidValue,
+                        (*vloc, "Invalid value in expected failure code assignment"),
+                        (assign_loc, msg),
                     ));
                     None
                 }
-                sp!(loc, _) => {
-                    let msg = "Unsupported attribute value for expected failure attribute";
+                None => {
                     context.env.add_diag(diag!(
                         Attributes::InvalidValue,
-                        (*aloc, msg),
-                        (*loc, "Unsupported value in this assignment")
+                        (*vloc, "Invalid value in expected failure code assignment"),
+                        (assign_loc, "Unsupported value in this assignment"),
                     ));
                     None
                 }
             }
         }
+        _ => parse_u64_value(context, assign_loc, value),
+    }
+}
+
+fn parse_u64_value(
+    context: &mut Context,
+    assign_loc: Loc,
+    value: &E::AttributeValue,
+) -> Option<u64> {
+    use E::{AttributeValue_ as EAV, Value_ as EV};
+    match value {
+        sp!(_, EAV::Value(sp!(_, EV::InferredNum(u)))) if *u <= std::u64::MAX.into() => {
+            Some(u.down_cast_lossy())
+        }
+        sp!(_, EAV::Value(sp!(_, EV::U64(u)))) => Some(*u),
+        sp!(vloc, EAV::Value(sp!(_, EV::U8(_))))
+        | sp!(vloc, EAV::Value(sp!(_, EV::U16(_))))
+        | sp!(vloc, EAV::Value(sp!(_, EV::U32(_))))
+        | sp!(vloc, EAV::Value(sp!(_, EV::U128(_))))
+        | sp!(vloc, EAV::Value(sp!(_, EV::U256(_)))) => {
+            let msg = "Invalid value in expected failure code assignment";
+            context.env.add_diag(diag!(
+                Attributes::InvalidValue,
+                (assign_loc, msg),
+                (*vloc, "Annotated non-u64 literals are not permitted"),
+            ));
+            None
+        }
+        sp!(vloc, _) => {
+            context.env.add_diag(diag!(
+                Attributes::InvalidValue,
+                (*vloc, "Invalid value in expected failure code assignment"),
+                (assign_loc, "Unsupported value in this assignment"),
+            ));
+            None
+        }
+    }
+}
+
+// Parses the value of a `location` assignment, which must name a module, e.g. `Self` or `0x1::M`
+fn parse_failure_location(
+    context: &mut Context,
+    assign_loc: Loc,
+    value: &E::AttributeValue,
+) -> Option<ModuleId> {
+    match value {
+        sp!(
+            _,
+            E::AttributeValue_::Module(sp!(_, ModuleIdent_ { address, module }))
+        ) => {
+            let addr = MoveAddress::new(context.resolve_address(address).into_bytes());
+            let name = MoveIdentifier::new(module.value().as_str().to_owned()).unwrap();
+            Some(ModuleId::new(addr, name))
+        }
+        sp!(vloc, _) => {
+            context.env.add_diag(diag!(
+                Attributes::InvalidValue,
+                (*vloc, "Invalid location in expected failure attribute"),
+                (
+                    assign_loc,
+                    "Expected a module, e.g. 'Self' or '0x1::M', in this assignment"
+                ),
+            ));
+            None
+        }
     }
 }
 
@@ -0,0 +1,25 @@
+module 0x42::M {
+    fun call(x: u64) {
+        unknown_module::call(x + true);
+    }
+
+    fun pack(x: u64) {
+        unknown_module::S { f: x + true };
+    }
+
+    fun bind(x: u64) {
+        let unknown_module::S { f: _ } = x + true;
+    }
+
+    fun assign(x: u64) {
+        unknown_module::S { f: _ } = x + true;
+    }
+
+    fun spec_only_operator(x: u64) {
+        x + true ==> false;
+    }
+
+    fun spec_only_index(v: vector<u64>) {
+        v[1 + true];
+    }
+}
@@ -0,0 +1,20 @@
+address 0x42 {
+module X {
+    const MAX: u8 = 255;
+    const ZERO: u64 = 0;
+    const BIG: u128 = 340282366920938463463374607431768211455;
+}
+
+module M {
+    use 0x42::X;
+
+    const OVERFLOW: u8 = X::MAX + 1;
+    const DIV: u64 = 1 / X::ZERO;
+    const CAST: u64 = (X::BIG as u64);
+    const NESTED: u64 = (ONE + (X::MAX as u64)) * (0 - ONE);
+    const ONE: u64 = 1;
+
+    // no additional error for using a constant that could not be computed
+    const USES_OVERFLOW: u8 = OVERFLOW / 2;
+}
+}
@@ -0,0 +1,23 @@
+module 0x42::M {
+    const DEBUG: bool = false;
+
+    fun conditions(x: u64): u64 {
+        if (true) x = x + 1;
+        if (!false) x = x + 1;
+        if (1 > 2) x = x + 1;
+        if (true && x > 0) x = x + 1;
+        if (DEBUG) x = x + 1;
+        if (x > 2) x = x + 1;
+        while (false) x = x + 1;
+        x
+    }
+
+    fun infinite(): u64 {
+        let i = 0;
+        while (true) {
+            i = i + 1;
+            if (i > 10) break
+        };
+        i
+    }
+}
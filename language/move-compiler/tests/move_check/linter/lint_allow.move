@@ -0,0 +1,31 @@
+#[lint_allow(self_assignment)]
+module 0x42::M {
+    fun self_assign(x: u64): u64 {
+        x = x;
+        x
+    }
+
+    #[lint_allow(unnecessary_cast, constant_condition)]
+    fun allowed(x: u64): u64 {
+        if (true) x = (x as u64);
+        x
+    }
+
+    fun not_allowed(x: u64): u64 {
+        if (true) x = (x as u64);
+        x
+    }
+}
+
+module 0x42::N {
+    #[lint_allow(not_a_lint)]
+    fun unknown(x: u64): u64 {
+        x = x;
+        x
+    }
+
+    #[lint_allow]
+    fun no_lints(): u64 {
+        0
+    }
+}
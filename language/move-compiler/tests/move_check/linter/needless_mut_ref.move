@@ -0,0 +1,41 @@
+module 0x42::M {
+    struct S has drop { f: u64 }
+
+    fun read_field(s: &mut S): u64 {
+        s.f
+    }
+
+    fun read_value(x: &mut u64): u64 {
+        *x
+    }
+
+    fun freeze_it(s: &mut S): u64 {
+        read_imm(s)
+    }
+
+    fun read_imm(s: &S): u64 {
+        s.f
+    }
+
+    fun write_field(s: &mut S) {
+        s.f = 1
+    }
+
+    fun write_value(x: &mut u64) {
+        *x = 0
+    }
+
+    fun pass_along(s: &mut S) {
+        write_field(s)
+    }
+
+    fun return_it(s: &mut S): &mut S {
+        s
+    }
+
+    fun mut_borrow_field(s: &mut S): &mut u64 {
+        &mut s.f
+    }
+
+    fun unused(_s: &mut S) {}
+}
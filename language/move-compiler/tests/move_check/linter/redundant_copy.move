@@ -0,0 +1,28 @@
+module 0x42::M {
+    struct S has copy, drop { f: u64 }
+
+    fun consume(_s: S) {}
+
+    fun redundant(s: S) {
+        consume(copy s)
+    }
+
+    fun still_used(s: S): u64 {
+        consume(copy s);
+        s.f
+    }
+
+    fun borrowed(s: S): u64 {
+        let r = &s;
+        consume(copy s);
+        r.f
+    }
+
+    fun in_loop(s: S) {
+        let i = 0;
+        while (i < 10) {
+            consume(copy s);
+            i = i + 1;
+        }
+    }
+}
@@ -0,0 +1,16 @@
+module 0x42::M {
+    fun self_assign(x: u64): u64 {
+        x = x;
+        x
+    }
+
+    fun self_assign_pair(x: u64, y: u64): u64 {
+        (x, y) = (x, y);
+        x + y
+    }
+
+    fun swap(x: u64, y: u64): (u64, u64) {
+        (x, y) = (y, x);
+        (x, y)
+    }
+}
@@ -0,0 +1,31 @@
+module 0x42::M {
+    struct S has drop { f: u64 }
+
+    fun shadow_param(x: u64): u64 {
+        let x = x + 1;
+        x
+    }
+
+    fun shadow_in_block(): u64 {
+        let y = 1;
+        {
+            let y = y + 1;
+            y
+        }
+    }
+
+    fun shadow_unpack(s: S): u64 {
+        let f = 0;
+        let g = f;
+        let S { f } = s;
+        f + g
+    }
+
+    fun no_shadowing(): u64 {
+        { let z = 1; z };
+        let z = 2;
+        let _ignored = 0;
+        let _ignored = 1;
+        z
+    }
+}
@@ -0,0 +1,9 @@
+module 0x42::M {
+    fun casts(x: u64, y: u8): u128 {
+        let a = (x as u64);
+        let b = (y as u64);
+        let c = (1 as u64);
+        let d = ((a + b + c) as u128);
+        (d as u128)
+    }
+}
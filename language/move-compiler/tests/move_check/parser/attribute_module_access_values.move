@@ -0,0 +1,10 @@
+// only the location of an expected failure is resolved as a module, other attributes are unchanged
+module 0x42::M {
+    use 0x42::M as Alias;
+
+    #[attr0 = Self, attr1(location = Alias), location = Self]
+    fun f() { }
+
+    #[attr0(location = 0x42::M::f, abort_code = Alias::f), attr1 = std::unknown]
+    fun g() { }
+}
@@ -0,0 +1,9 @@
+module 0x42::M {
+    struct S { f: u64, g: }
+    fun f(x: u64): u64 { x }
+    const C: u64 = ;
+    fun g(): u64 { f(1) }
+    struct T { h: bool }
+    fun h(: u64) {}
+    fun k(): T { T { h: true } }
+}
@@ -0,0 +1,19 @@
+module 0x42::M {
+    fun f(x: u64): u64 {
+        let y = x + ;
+        let z = );
+        f(1, , 2);
+        let w = x
+        let v = vector[1, 2 +, 3];
+        y
+    }
+
+    fun g() {
+        let x = 1;
+        if (x == ) { }
+    }
+
+    fun h(): u64 {
+        let a = 0
+    }
+}
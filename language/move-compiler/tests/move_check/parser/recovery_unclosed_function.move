@@ -0,0 +1,13 @@
+module 0x42::M {
+    fun f(): u64 {
+        let x = 1;
+        x +
+
+    fun g(): u64 { 0 }
+
+    struct S has drop {}
+}
+
+module 0x42::N {
+    fun h() { let = 0; }
+}
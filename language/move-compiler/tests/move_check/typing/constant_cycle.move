@@ -0,0 +1,18 @@
+address 0x42 {
+module X {
+    const SELF: u64 = SELF + 1;
+
+    const A: u64 = B;
+    const B: u64 = C * 2;
+    const C: u64 = A;
+
+    const OK: u64 = 1;
+    const USES_OK: u64 = OK + OK;
+
+    const TO_Y: u64 = 0x42::Y::TO_X;
+}
+
+module Y {
+    const TO_X: u64 = 0x42::X::TO_Y + 1;
+}
+}
@@ -0,0 +1,28 @@
+address 0x42 {
+module X {
+    const C: u64 = 0;
+}
+
+module M {
+    use 0x42::X;
+
+    // constants can use the constants of other modules
+    const D: u64 = X::C + 1;
+
+    fun foo(): u64 {
+        // but functions cannot
+        X::C + D
+    }
+}
+}
+
+script {
+    use 0x42::X;
+
+    // the same holds for scripts
+    const E: u64 = X::C + 1;
+
+    fun main() {
+        let _ = X::C + E;
+    }
+}
@@ -0,0 +1,21 @@
+module 0x1::M {
+    #[before_each]
+    fun missing_signer(_s: signer) { }
+
+    #[before_each]
+    fun second_setup() { }
+
+    #[test]
+    fun t() { }
+}
+
+module 0x1::N {
+    #[test, before_each]
+    fun setup_and_test() { }
+}
+
+module 0x1::O {
+    #[before_each]
+    #[expected_failure]
+    fun expected_failure_setup() { }
+}
@@ -0,0 +1,4 @@
+module 0x1::M {
+    #[before_each]
+    struct NotAFunction {}
+}
@@ -0,0 +1,25 @@
+// Make sure that legal usage of setup functions is allowed
+module 0x1::M {
+    struct R has key { v: u64 }
+
+    // setup functions can take signers assigned in the attribute
+    #[before_each(a = @0x1, b = @0x2)]
+    fun setup(a: signer, b: signer) {
+        move_to(&a, R { v: 1 });
+        move_to(&b, R { v: 2 });
+    }
+
+    #[test]
+    fun t() acquires R {
+        assert!(borrow_global<R>(@0x1).v == 1, 0);
+    }
+}
+
+module 0x1::N {
+    // setup functions without parameters are allowed, and can be test only
+    #[test_only, before_each]
+    fun setup() { }
+
+    #[test]
+    fun t() { }
+}
@@ -0,0 +1,36 @@
+// Check that invalid kinds of expected failures are rejected
+module 0x1::M {
+    const E_NOT_A_U64: u8 = 1;
+
+    #[test]
+    #[expected_failure(abort_code = E_NOT_A_U64)]
+    fun bad_constant_type() { }
+
+    #[test]
+    #[expected_failure(abort_code = E_UNBOUND)]
+    fun unbound_constant() { }
+
+    #[test]
+    #[expected_failure(abort_code = 1, arithmetic_error)]
+    fun multiple_kinds() { }
+
+    #[test]
+    #[expected_failure(location = Self)]
+    fun missing_kind() { }
+
+    #[test]
+    #[expected_failure(abort_code = 1, minor_status = 1)]
+    fun minor_status_with_abort_code() { }
+
+    #[test]
+    #[expected_failure(major_status = 123456789)]
+    fun unknown_major_status() { }
+
+    #[test]
+    #[expected_failure(arithmetic_error, location = 1)]
+    fun invalid_location() { }
+
+    #[test]
+    #[expected_failure(division_by_zero)]
+    fun unknown_kind() { }
+}
@@ -0,0 +1,47 @@
+// Make sure that all kinds of expected failures are accepted
+module 0x1::A {
+    const E_FAILED: u64 = 1;
+}
+
+module 0x1::M {
+    #[test_only]
+    use 0x1::A;
+
+    const E_NOT_FOUND: u64 = 2;
+
+    #[test]
+    #[expected_failure(abort_code = E_NOT_FOUND)]
+    fun constant_code() { }
+
+    #[test]
+    #[expected_failure(abort_code = E_NOT_FOUND, location = Self)]
+    fun constant_code_with_location() { }
+
+    #[test]
+    #[expected_failure(abort_code = A::E_FAILED, location = A)]
+    fun other_module_constant_code() { }
+
+    #[test]
+    #[expected_failure(abort_code = 0x1::A::E_FAILED, location = 0x1::A)]
+    fun fully_qualified_constant_code() { }
+
+    #[test]
+    #[expected_failure(arithmetic_error, location = Self)]
+    fun arithmetic_error() { }
+
+    #[test]
+    #[expected_failure(out_of_gas)]
+    fun out_of_gas() { }
+
+    #[test]
+    #[expected_failure(vector_error, minor_status = 1, location = Self)]
+    fun vector_error() { }
+
+    #[test]
+    #[expected_failure(abort_code = 0x40001, location = std::option)]
+    fun named_address_location() { }
+
+    #[test]
+    #[expected_failure(major_status = 4016, location = Self)]
+    fun major_status() { }
+}
@@ -0,0 +1,10 @@
+// the location of an expected failure must be a known module
+module 0x1::M {
+    #[test]
+    #[expected_failure(arithmetic_error, location = 0x1::Unknown)]
+    fun unbound_location() { }
+
+    #[test]
+    #[expected_failure(arithmetic_error, location = std::unknown)]
+    fun unbound_named_location() { }
+}
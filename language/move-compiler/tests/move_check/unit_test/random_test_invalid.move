@@ -0,0 +1,18 @@
+module 0x1::M {
+    struct Box<T> has drop { t: T }
+
+    #[random_test]
+    public fun unassigned_signer(_s: signer) { }
+
+    #[random_test]
+    public fun reference(_x: &u64) { }
+
+    #[random_test]
+    public fun generic_struct(_b: Box<u64>) { }
+
+    #[random_test]
+    public fun type_parameter<T: drop>(_t: T) { }
+
+    #[test, random_test]
+    public fun both(_x: u64) { }
+}
@@ -0,0 +1,26 @@
+// Make sure that legal usage of random tests is allowed
+module 0x1::M {
+    struct Point has drop { x: u64, y: u64 }
+
+    struct Nested has drop { p: Point, tags: vector<u8> }
+
+    // random tests without parameters are allowed
+    #[random_test]
+    public fun a() { }
+
+    // all integer types, bools and addresses can be generated
+    #[random_test]
+    public fun b(_a: u8, _b: u16, _c: u32, _d: u64, _e: u128, _f: u256, _g: bool, _h: address) { }
+
+    // vectors and structs without type parameters can be generated
+    #[random_test]
+    public fun c(_a: vector<u64>, _b: Point, _c: vector<Nested>) { }
+
+    // signers are assigned in the attribute, other parameters are generated
+    #[random_test(_s = @0x1)]
+    public fun d(_s: signer, _x: u64) { }
+
+    // random tests can be expected to fail
+    #[random_test, expected_failure(abort_code = 0)]
+    public fun e(_x: u64) { abort 0 }
+}
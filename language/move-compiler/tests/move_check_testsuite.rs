@@ -11,6 +11,7 @@ use move_command_line_common::{
 use move_compiler::{
     compiled_unit::AnnotatedCompiledUnit,
     diagnostics::*,
+    linters::{LintConfig, LintLevel},
     shared::{Flags, NumericalAddress},
     unit_test, CommentMap, Compiler, SteppedCompiler, PASS_CFGIR, PASS_PARSER,
 };
@@ -20,10 +21,14 @@ const KEEP_TMP: &str = "KEEP";
 
 const TEST_EXT: &str = "unit_test";
 const VERIFICATION_EXT: &str = "verification";
+const IDE_EXT: &str = "ide";
 
 /// Root of tests which require to set flavor flags.
 const FLAVOR_PATH: &str = "flavors/";
 
+/// Root of tests which are compiled with every lint enabled.
+const LINTER_PATH: &str = "linter/";
+
 fn default_testing_addresses() -> BTreeMap<String, NumericalAddress> {
     let mapping = [
         ("std", "0x1"),
@@ -82,6 +87,26 @@ fn move_check_testsuite(path: &Path) -> datatest_stable::Result<()> {
         )?;
     }
 
+    // A case is marked that it should also be compiled in IDE mode by having a `path.ide` file.
+    if path.with_extension(IDE_EXT).exists() {
+        let ide_exp_path = format!(
+            "{}.ide.{}",
+            path.with_extension("").to_string_lossy(),
+            EXP_EXT
+        );
+        let ide_out_path = format!(
+            "{}.ide.{}",
+            path.with_extension("").to_string_lossy(),
+            OUT_EXT
+        );
+        run_test(
+            path,
+            Path::new(&ide_exp_path),
+            Path::new(&ide_out_path),
+            Flags::empty().set_ide_mode(true),
+        )?;
+    }
+
     let exp_path = path.with_extension(EXP_EXT);
     let out_path = path.with_extension(OUT_EXT);
 
@@ -98,6 +123,11 @@ fn move_check_testsuite(path: &Path) -> datatest_stable::Result<()> {
                 .to_string();
             flags = flags.set_flavor(flavor)
         }
+        Some(p) if p.contains(LINTER_PATH) => {
+            flags = flags
+                .set_lint(true)
+                .set_lint_config(LintConfig::all(LintLevel::Warn))
+        }
         _ => {}
     };
     run_test(path, &exp_path, &out_path, flags)?;
@@ -0,0 +1,41 @@
+//# publish
+module 0x42::X {
+    const BASE: u64 = 1000;
+    const SHIFT: u8 = 4;
+    const PREFIX: vector<u8> = b"move";
+    // uses a constant declared after it
+    const SCALED: u64 = BASE * FACTOR;
+    const FACTOR: u64 = 3;
+
+    public fun scaled(): u64 {
+        SCALED
+    }
+}
+
+//# publish
+module 0x42::Y {
+    use 0x42::X;
+
+    const MASK: u64 = (1 << X::SHIFT) - 1;
+    const TOTAL: u64 = X::SCALED + X::BASE / 2;
+    const SMALL: u8 = ((TOTAL % 256) as u8);
+    const BYTES: vector<vector<u8>> = vector[X::PREFIX, b"-", x"01"];
+
+    public fun check() {
+        assert!(X::scaled() == 3000, 0);
+        assert!(MASK == 15, 1);
+        assert!(TOTAL == 3500, 2);
+        assert!(SMALL == 172, 3);
+        assert!(BYTES == vector[b"move", b"-", x"01"], 4);
+    }
+}
+
+//# run
+script {
+    const LIMIT: u64 = 0x42::Y::TOTAL + 1;
+
+    fun main() {
+        0x42::Y::check();
+        assert!(LIMIT == 3501, 5);
+    }
+}
@@ -598,6 +598,20 @@ pub enum StatusCode {
     VEC_BORROW_ELEMENT_EXISTS_MUTABLE_BORROW_ERROR = 1110,
     // Loops are too deeply nested.
     LOOP_MAX_DEPTH_REACHED = 1111,
+    // A function has more instructions than allowed.
+    TOO_MANY_INSTRUCTIONS = 1112,
+    // A function has more basic blocks than allowed.
+    TOO_MANY_BASIC_BLOCKS = 1113,
+    // Type instantiations are too deeply nested.
+    MAX_GENERIC_INSTANTIATION_DEPTH_REACHED = 1114,
+    // A type has more nodes than allowed.
+    TOO_MANY_TYPE_NODES = 1115,
+    // A struct has more fields than allowed.
+    TOO_MANY_FIELDS = 1116,
+    // A module depends on more modules than allowed.
+    TOO_MANY_DEPENDENCIES = 1117,
+    // Verifying a function exceeded the metering budget of the verifier.
+    PROGRAM_TOO_COMPLEX = 1118,
 
     // These are errors that the VM might raise if a violation of internal
     // invariants takes place.
@@ -480,6 +480,15 @@ impl SourceMap {
             .ok_or_else(|| format_err!("Unable to get function source map"))
     }
 
+    pub fn get_function_source_map_mut(
+        &mut self,
+        fdef_idx: FunctionDefinitionIndex,
+    ) -> Result<&mut FunctionSourceMap> {
+        self.function_map
+            .get_mut(&fdef_idx.0)
+            .ok_or_else(|| format_err!("Unable to get function source map"))
+    }
+
     pub fn get_struct_source_map(
         &self,
         struct_def_idx: StructDefinitionIndex,
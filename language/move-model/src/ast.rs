@@ -67,6 +67,7 @@ pub struct SpecFunDecl {
 #[derive(Debug, Clone)]
 pub enum AttributeValue {
     Value(NodeId, Value),
+    Module(NodeId, ModuleName),
     Name(NodeId, Option<ModuleName>, Symbol),
 }
 
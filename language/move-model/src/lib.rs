@@ -348,7 +348,9 @@ fn add_move_lang_diagnostics(env: &mut GlobalEnv, diags: Diagnostics) {
         let loc = env.to_loc(&loc);
         Label::new(style, loc.file_id(), loc.span()).with_message(msg)
     };
-    for (severity, msg, primary_label, secondary_labels, notes) in diags.into_codespan_format() {
+    for (severity, _code, msg, primary_label, secondary_labels, notes) in
+        diags.into_codespan_format()
+    {
         let diag = Diagnostic::new(severity)
             .with_labels(vec![mk_label(true, primary_label)])
             .with_message(msg)
@@ -39,6 +39,7 @@ pub mod packed_types_analysis;
 pub mod pipeline_factory;
 pub mod reaching_def_analysis;
 pub mod read_write_set_analysis;
+pub mod security_check;
 pub mod spec_instrumentation;
 pub mod stackless_bytecode;
 pub mod stackless_bytecode_generator;
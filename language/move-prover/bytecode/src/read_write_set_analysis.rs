@@ -582,8 +582,9 @@ impl<'a> TransferFunctions for ReadWriteSetAnalysis<'a> {
                         }
                     }
                 }
-                CastU8 | CastU64 | CastU128 | Not | Add | Sub | Mul | Div | Mod | BitOr
-                | BitAnd | Xor | Shl | Shr | Lt | Gt | Le | Ge | Or | And => {
+                CastU8 | CastU16 | CastU32 | CastU64 | CastU128 | CastU256 | Not | Add | Sub
+                | Mul | Div | Mod | BitOr | BitAnd | Xor | Shl | Shr | Lt | Gt | Le | Ge | Or
+                | And => {
                     // These operations touch non-reference values; nothing to do
                 }
                 oper => unimplemented!("unsupported oper {:?}", oper),
@@ -624,6 +625,7 @@ fn call_native_function(
                 state.record_access(args[0], Access::Read, func_env)
             }
         }
+        ("bcs", "from_bytes") => (),
         ("signer", "borrow_address") => {
             if state.locals.local_exists(args[0], func_env) {
                 // treat as identity function
@@ -687,9 +689,22 @@ fn call_native_function(
         ("string", "internal_check_utf8")
         | ("string", "internal_is_char_boundary")
         | ("string", "internal_sub_string")
-        | ("string", "internal_index_of") => (),
+        | ("string", "internal_index_of")
+        | ("string", "internal_to_decimal")
+        | ("string", "internal_parse_decimal")
+        | ("string", "internal_hex_encode")
+        | ("string", "internal_hex_decode")
+        | ("string", "internal_char_count")
+        | ("string", "internal_decode_char")
+        | ("string", "internal_encode_char")
+        | ("string", "internal_format") => (),
         ("event", "write_to_event_store") => (),
-        ("hash", "sha3_256") | ("hash", "sha2_256") => (),
+        ("hash", "sha3_256")
+        | ("hash", "sha2_256")
+        | ("hash", "keccak256")
+        | ("hash", "blake2b_256")
+        | ("hash", "ripemd160") => (),
+        ("ed25519", _) | ("secp256k1", _) | ("bls12381", _) => (),
         ("Signature", "ed25519_validate_pubkey") | ("Signature", "ed25519_verify") => (),
         (m, f) => {
             panic!("Unsupported native function {:?}::{:?}", m, f)
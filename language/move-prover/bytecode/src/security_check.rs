@@ -0,0 +1,489 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Static checks for common security pitfalls in Move smart contracts. The checks are heuristics
+//! over the baseline stackless bytecode of the target modules, and each finding is reported as a
+//! warning on the `GlobalEnv`. The following patterns are flagged:
+//!
+//! - capability structs (structs named `*Cap` or `*Capability`) with the `copy` or `drop` ability,
+//! - public functions taking a signer that mutably borrow a resource at an address that is neither
+//!   derived from nor compared against the signer,
+//! - narrowing casts of arithmetic results computed from user-provided values without a bounds
+//!   check on the result,
+//! - resources (structs without `drop`) that are packed but never unpacked by their module,
+//! - `move_from` in public functions at an address that is neither derived from nor compared
+//!   against a signer.
+//!
+//! An address counts as checked if it is compared against a signer address on every path before
+//! the access. The bounds of arithmetic results are tracked flow-insensitively: a result counts as
+//! bounded if it is compared against a bound anywhere in the function.
+
+use crate::{
+    dataflow_analysis::{DataflowAnalysis, TransferFunctions},
+    dataflow_domains::{AbstractDomain, JoinResult},
+    function_target::FunctionTarget,
+    function_target_pipeline::{FunctionTargetProcessor, FunctionTargetsHolder, FunctionVariant},
+    stackless_bytecode::{Bytecode, Operation},
+    stackless_control_flow_graph::StacklessControlFlowGraph,
+};
+use codespan_reporting::diagnostic::Severity;
+use move_binary_format::file_format::{Ability, CodeOffset};
+use move_model::{
+    ast::TempIndex,
+    model::{FunctionEnv, GlobalEnv, ModuleEnv, QualifiedId, StructId},
+    ty::{PrimitiveType, Type},
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+pub struct SecurityCheckProcessor();
+
+impl SecurityCheckProcessor {
+    pub fn new() -> Box<Self> {
+        Box::new(SecurityCheckProcessor())
+    }
+}
+
+impl FunctionTargetProcessor for SecurityCheckProcessor {
+    fn name(&self) -> String {
+        "security_check".to_string()
+    }
+
+    fn is_single_run(&self) -> bool {
+        true
+    }
+
+    fn run(&self, env: &GlobalEnv, targets: &mut FunctionTargetsHolder) {
+        for module_env in env.get_modules() {
+            if !module_env.is_target() {
+                continue;
+            }
+            check_capabilities(&module_env);
+            check_indestructible_resources(&module_env, targets);
+            for func_env in module_env.get_functions() {
+                if func_env.is_native_or_intrinsic()
+                    || !targets.has_target(&func_env, &FunctionVariant::Baseline)
+                {
+                    continue;
+                }
+                let target = targets.get_target(&func_env, &FunctionVariant::Baseline);
+                if func_env.has_unknown_callers() {
+                    check_global_access(&target);
+                    check_narrowing_casts(&target);
+                }
+            }
+        }
+    }
+}
+
+// =================================================================================================
+// Struct checks
+
+/// Capabilities guard privileged operations. A capability that can be copied can be handed out
+/// freely by any holder, and one that can be dropped can be discarded to escape an obligation.
+fn check_capabilities(module_env: &ModuleEnv<'_>) {
+    let env = module_env.env;
+    for struct_env in module_env.get_structs() {
+        let name = struct_env.get_name().display(env.symbol_pool()).to_string();
+        if !name.ends_with("Cap") && !name.ends_with("Capability") {
+            continue;
+        }
+        let abilities = struct_env.get_abilities();
+        let unsafe_abilities = [(Ability::Copy, "copy"), (Ability::Drop, "drop")]
+            .into_iter()
+            .filter(|(ability, _)| abilities.has_ability(*ability))
+            .map(|(_, name)| format!("`{}`", name))
+            .collect::<Vec<_>>();
+        if unsafe_abilities.is_empty() {
+            continue;
+        }
+        env.diag(
+            Severity::Warning,
+            &struct_env.get_loc(),
+            &format!(
+                "Capability `{}` has the {} {}. Capabilities should be neither copyable nor \
+                 droppable",
+                struct_env.get_full_name_str(),
+                unsafe_abilities.join(" and "),
+                if unsafe_abilities.len() == 1 {
+                    "ability"
+                } else {
+                    "abilities"
+                }
+            ),
+        );
+    }
+}
+
+/// Structs can only be packed and unpacked by their declaring module. A resource the module packs
+/// but never unpacks can never be destroyed once created.
+fn check_indestructible_resources(module_env: &ModuleEnv<'_>, targets: &FunctionTargetsHolder) {
+    let mut packed = BTreeSet::new();
+    let mut unpacked = BTreeSet::new();
+    for func_env in module_env.get_functions() {
+        for (_, target) in targets.get_targets(&func_env) {
+            for bc in target.get_bytecode() {
+                match bc {
+                    Bytecode::Call(_, _, Operation::Pack(mid, sid, _), _, _) => {
+                        packed.insert(mid.qualified(*sid));
+                    }
+                    Bytecode::Call(_, _, Operation::Unpack(mid, sid, _), _, _) => {
+                        unpacked.insert(mid.qualified(*sid));
+                    }
+                    _ => (),
+                }
+            }
+        }
+    }
+    let env = module_env.env;
+    for qid in packed.difference(&unpacked) {
+        let struct_env = env.get_struct(*qid);
+        if struct_env.get_abilities().has_drop() {
+            continue;
+        }
+        env.diag(
+            Severity::Warning,
+            &struct_env.get_loc(),
+            &format!(
+                "Resource `{}` can be created but is never destroyed. Its module packs it but \
+                 never unpacks it",
+                struct_env.get_full_name_str()
+            ),
+        );
+    }
+}
+
+// =================================================================================================
+// Global storage checks
+
+/// Flags `borrow_global_mut` in functions taking a signer and `move_from` in any function, when the
+/// address is not authorized by a signer before the access.
+fn check_global_access(target: &FunctionTarget<'_>) {
+    let env = target.global_env();
+    let takes_signer = target
+        .get_parameters()
+        .any(|idx| target.get_local_type(idx).skip_reference().is_signer());
+    let authorized_at = authorized_addresses(target);
+    for (offset, bc) in target.get_bytecode().iter().enumerate() {
+        // Unreachable code is not analyzed
+        let authorized = match authorized_at.get(&(offset as CodeOffset)) {
+            Some(authorized) => authorized,
+            None => continue,
+        };
+        match bc {
+            Bytecode::Call(attr_id, dests, Operation::BorrowGlobal(mid, sid, _), srcs, _)
+                if takes_signer
+                    && target.get_local_type(dests[0]).is_mutable_reference()
+                    && !authorized.contains(&srcs[0]) =>
+            {
+                env.diag(
+                    Severity::Warning,
+                    &target.get_bytecode_loc(*attr_id),
+                    &format!(
+                        "Public function `{}` takes a signer but mutably borrows `{}` at an \
+                         address that is neither derived from nor checked against a signer",
+                        target.func_env.get_full_name_str(),
+                        struct_name(env, mid.qualified(*sid)),
+                    ),
+                );
+            }
+            Bytecode::Call(attr_id, _, Operation::MoveFrom(mid, sid, _), srcs, _)
+                if !authorized.contains(&srcs[0]) =>
+            {
+                env.diag(
+                    Severity::Warning,
+                    &target.get_bytecode_loc(*attr_id),
+                    &format!(
+                        "Public function `{}` moves `{}` from an address that is neither \
+                         derived from nor checked against a signer",
+                        target.func_env.get_full_name_str(),
+                        struct_name(env, mid.qualified(*sid)),
+                    ),
+                );
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Returns, for each reachable instruction, the temporaries that hold a signer or an address
+/// derived from a signer or compared for equality against one, on every path to the instruction.
+fn authorized_addresses(target: &FunctionTarget<'_>) -> BTreeMap<CodeOffset, BTreeSet<TempIndex>> {
+    let code = target.get_bytecode();
+    let cfg = StacklessControlFlowGraph::new_forward(code);
+    let analysis = AuthorizationAnalysis { target };
+    let mut initial_state = AuthorizationState::default();
+    for idx in target.get_parameters() {
+        if target.get_local_type(idx).skip_reference().is_signer() {
+            initial_state.authorize(idx);
+        }
+    }
+    let state_map = analysis.analyze_function(initial_state, code, &cfg);
+    analysis.state_per_instruction(state_map, code, &cfg, |before, _| before.authorized.clone())
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct AuthorizationState {
+    authorized: BTreeSet<TempIndex>,
+    /// The temporary each temporary was last assigned from, while both still hold the same value
+    copies: BTreeMap<TempIndex, TempIndex>,
+}
+
+impl AuthorizationState {
+    /// Authorizes `temp` and the temporaries holding a copy of the same value. Copies are treated
+    /// as aliasing in both directions, so that checking a copy of an address also authorizes the
+    /// original.
+    fn authorize(&mut self, temp: TempIndex) {
+        let mut todo = vec![temp];
+        while let Some(temp) = todo.pop() {
+            if !self.authorized.insert(temp) {
+                continue;
+            }
+            for (dest, src) in &self.copies {
+                if *dest == temp {
+                    todo.push(*src)
+                } else if *src == temp {
+                    todo.push(*dest)
+                }
+            }
+        }
+    }
+
+    fn kill(&mut self, temp: TempIndex) {
+        self.authorized.remove(&temp);
+        self.copies
+            .retain(|dest, src| *dest != temp && *src != temp);
+    }
+}
+
+impl AbstractDomain for AuthorizationState {
+    // An address is only authorized after a join if it is authorized on all incoming paths
+    fn join(&mut self, other: &Self) -> JoinResult {
+        let authorized = self
+            .authorized
+            .intersection(&other.authorized)
+            .cloned()
+            .collect::<BTreeSet<_>>();
+        let copies = self
+            .copies
+            .iter()
+            .filter(|(dest, src)| other.copies.get(dest) == Some(src))
+            .map(|(dest, src)| (*dest, *src))
+            .collect::<BTreeMap<_, _>>();
+        if authorized == self.authorized && copies == self.copies {
+            JoinResult::Unchanged
+        } else {
+            self.authorized = authorized;
+            self.copies = copies;
+            JoinResult::Changed
+        }
+    }
+}
+
+struct AuthorizationAnalysis<'a> {
+    target: &'a FunctionTarget<'a>,
+}
+
+impl<'a> TransferFunctions for AuthorizationAnalysis<'a> {
+    type State = AuthorizationState;
+    const BACKWARD: bool = false;
+
+    fn execute(&self, state: &mut AuthorizationState, instr: &Bytecode, _offset: CodeOffset) {
+        match instr {
+            Bytecode::Assign(_, dest, src, _) => {
+                let authorized = state.authorized.contains(src);
+                state.kill(*dest);
+                state.copies.insert(*dest, *src);
+                if authorized {
+                    state.authorize(*dest);
+                }
+            }
+            Bytecode::Call(_, dests, op, srcs, _) => {
+                let any_authorized = srcs.iter().any(|src| state.authorized.contains(src));
+                let propagates = match op {
+                    Operation::Function(mid, fid, _) => {
+                        let callee = self.target.global_env().get_function(mid.qualified(*fid));
+                        is_signer_address_fun(&callee)
+                    }
+                    Operation::ReadRef | Operation::FreezeRef => true,
+                    _ => false,
+                };
+                if matches!(op, Operation::Eq | Operation::Neq) && any_authorized {
+                    for src in srcs {
+                        state.authorize(*src);
+                    }
+                }
+                for dest in dests {
+                    state.kill(*dest);
+                    if propagates && any_authorized {
+                        state.authorize(*dest);
+                    }
+                }
+            }
+            _ => {
+                for temp in instr.modifies(self.target).0 {
+                    state.kill(temp);
+                }
+            }
+        }
+    }
+}
+
+impl<'a> DataflowAnalysis for AuthorizationAnalysis<'a> {}
+
+fn is_signer_address_fun(fun_env: &FunctionEnv<'_>) -> bool {
+    let env = fun_env.module_env.env;
+    fun_env.module_env.get_name().addr() == &env.get_stdlib_address()
+        && matches!(
+            fun_env.get_full_name_str().as_str(),
+            "signer::address_of" | "signer::borrow_address"
+        )
+}
+
+fn struct_name(env: &GlobalEnv, qid: QualifiedId<StructId>) -> String {
+    env.get_struct(qid).get_full_name_str()
+}
+
+// =================================================================================================
+// Arithmetic checks
+
+/// Flags narrowing casts of arithmetic results that depend on the function's parameters and whose
+/// value is never compared against a bound.
+fn check_narrowing_casts(target: &FunctionTarget<'_>) {
+    let env = target.global_env();
+    let arithmetic = user_arithmetic(target);
+    let bounded = bounded_temps(target);
+    for bc in target.get_bytecode() {
+        let (attr_id, dest, src) = match bc {
+            Bytecode::Call(
+                attr_id,
+                dests,
+                Operation::CastU8
+                | Operation::CastU16
+                | Operation::CastU32
+                | Operation::CastU64
+                | Operation::CastU128,
+                srcs,
+                _,
+            ) => (attr_id, dests[0], srcs[0]),
+            _ => continue,
+        };
+        if !arithmetic.contains(&src) || bounded.contains(&src) {
+            continue;
+        }
+        let (from, to) = match (
+            int_width(target.get_local_type(src)),
+            int_width(target.get_local_type(dest)),
+        ) {
+            (Some(from), Some(to)) => (from, to),
+            _ => continue,
+        };
+        if to < from {
+            env.diag(
+                Severity::Warning,
+                &target.get_bytecode_loc(*attr_id),
+                &format!(
+                    "Unchecked arithmetic on user-provided values is cast from `u{}` to `u{}`. \
+                     Consider checking the bounds of the result before the cast",
+                    from, to
+                ),
+            );
+        }
+    }
+}
+
+/// Returns the temporaries holding the result of arithmetic on values derived from the parameters.
+fn user_arithmetic(target: &FunctionTarget<'_>) -> BTreeSet<TempIndex> {
+    let mut user_values = target.get_parameters().collect::<BTreeSet<_>>();
+    let mut arithmetic = BTreeSet::new();
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for bc in target.get_bytecode() {
+            match bc {
+                Bytecode::Assign(_, dest, src, _) => {
+                    if user_values.contains(src) {
+                        changed |= user_values.insert(*dest);
+                    }
+                    if arithmetic.contains(src) {
+                        changed |= arithmetic.insert(*dest);
+                    }
+                }
+                Bytecode::Call(_, dests, op, srcs, _) => {
+                    use Operation::*;
+                    let is_arithmetic = matches!(op, Add | Sub | Mul | Div | Mod | Shl);
+                    let propagates = is_arithmetic
+                        || matches!(
+                            op,
+                            CastU8
+                                | CastU16
+                                | CastU32
+                                | CastU64
+                                | CastU128
+                                | CastU256
+                                | BitOr
+                                | BitAnd
+                                | Xor
+                                | Shr
+                                | ReadRef
+                        );
+                    if propagates && srcs.iter().any(|src| user_values.contains(src)) {
+                        for dest in dests {
+                            changed |= user_values.insert(*dest);
+                            if is_arithmetic {
+                                changed |= arithmetic.insert(*dest);
+                            }
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+    arithmetic
+}
+
+/// Returns the temporaries that are compared against a bound, including the temporaries they are
+/// assigned from or to.
+fn bounded_temps(target: &FunctionTarget<'_>) -> BTreeSet<TempIndex> {
+    let mut bounded = BTreeSet::new();
+    for bc in target.get_bytecode() {
+        if let Bytecode::Call(
+            _,
+            _,
+            Operation::Lt | Operation::Le | Operation::Gt | Operation::Ge,
+            srcs,
+            _,
+        ) = bc
+        {
+            bounded.extend(srcs.iter().copied());
+        }
+    }
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for bc in target.get_bytecode() {
+            match bc {
+                Bytecode::Assign(_, dest, src, _)
+                    if bounded.contains(src) || bounded.contains(dest) =>
+                {
+                    changed |= bounded.insert(*src);
+                    changed |= bounded.insert(*dest);
+                }
+                _ => (),
+            }
+        }
+    }
+    bounded
+}
+
+fn int_width(ty: &Type) -> Option<usize> {
+    match ty {
+        Type::Primitive(PrimitiveType::U8) => Some(8),
+        Type::Primitive(PrimitiveType::U16) => Some(16),
+        Type::Primitive(PrimitiveType::U32) => Some(32),
+        Type::Primitive(PrimitiveType::U64) => Some(64),
+        Type::Primitive(PrimitiveType::U128) => Some(128),
+        Type::Primitive(PrimitiveType::U256) => Some(256),
+        _ => None,
+    }
+}
@@ -0,0 +1,22 @@
+module 0x42::Capabilities {
+    struct AdminCap has copy, drop, store {}
+
+    struct MintCapability has drop {}
+
+    struct BurnCap has store {}
+
+    // not a capability
+    struct Config has copy, drop { value: u64 }
+
+    public fun new_caps(): (AdminCap, MintCapability, BurnCap) {
+        (AdminCap {}, MintCapability {}, BurnCap {})
+    }
+
+    public fun destroy_burn_cap(cap: BurnCap) {
+        let BurnCap {} = cap;
+    }
+
+    public fun config(): Config {
+        Config { value: 0 }
+    }
+}
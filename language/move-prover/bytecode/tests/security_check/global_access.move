@@ -0,0 +1,80 @@
+// dep: ../../move-stdlib/sources/signer.move
+module 0x42::GlobalAccess {
+    use std::signer;
+
+    struct Balance has key { value: u64 }
+
+    struct Admin has key { addr: address }
+
+    // ok: the resource is published under the signer
+    public fun publish(account: &signer) {
+        move_to(account, Balance { value: 0 });
+    }
+
+    // flagged: writes to an arbitrary address
+    public fun deposit(_account: &signer, to: address, amount: u64) acquires Balance {
+        let balance = borrow_global_mut<Balance>(to);
+        balance.value = balance.value + amount;
+    }
+
+    // ok: the address is derived from the signer
+    public fun withdraw(account: &signer, amount: u64) acquires Balance {
+        let balance = borrow_global_mut<Balance>(signer::address_of(account));
+        balance.value = balance.value - amount;
+    }
+
+    // ok: the address is checked against the signer
+    public fun set(account: &signer, addr: address, value: u64) acquires Balance {
+        assert!(signer::address_of(account) == addr, 1);
+        borrow_global_mut<Balance>(addr).value = value;
+    }
+
+    // flagged: the address is only checked after the resource is modified
+    public fun set_unchecked(account: &signer, addr: address, value: u64) acquires Balance {
+        borrow_global_mut<Balance>(addr).value = value;
+        assert!(signer::address_of(account) == addr, 1);
+    }
+
+    // flagged: the address is only checked on one of the paths to the access
+    public fun destroy_maybe_checked(account: &signer, addr: address, check: bool) acquires Balance {
+        if (check) assert!(signer::address_of(account) == addr, 1);
+        let Balance { value: _ } = move_from<Balance>(addr);
+    }
+
+    // ok: the address is checked on all paths to the access
+    public fun destroy_checked(account: &signer, addr: address, strict: bool) acquires Balance {
+        if (strict) assert!(signer::address_of(account) == addr, 1)
+        else assert!(addr == signer::address_of(account), 2);
+        let Balance { value: _ } = move_from<Balance>(addr);
+    }
+
+    // ok: no signer, so writes are not expected to be authorized by one
+    public fun bump(addr: address) acquires Balance {
+        let balance = borrow_global_mut<Balance>(addr);
+        balance.value = balance.value + 1;
+    }
+
+    // flagged: anyone can remove anyone's balance
+    public fun destroy(addr: address) acquires Balance {
+        let Balance { value: _ } = move_from<Balance>(addr);
+    }
+
+    // ok: the resource is moved from the signer's address
+    public fun close(account: &signer) acquires Balance {
+        let Balance { value: _ } = move_from<Balance>(signer::address_of(account));
+    }
+
+    // ok: not callable from outside the module
+    fun destroy_internal(addr: address) acquires Balance {
+        let Balance { value: _ } = move_from<Balance>(addr);
+    }
+
+    public fun set_admin(account: &signer) {
+        move_to(account, Admin { addr: signer::address_of(account) });
+    }
+
+    public fun remove_admin(account: &signer) acquires Admin, Balance {
+        let Admin { addr: _ } = move_from<Admin>(signer::address_of(account));
+        destroy_internal(signer::address_of(account));
+    }
+}
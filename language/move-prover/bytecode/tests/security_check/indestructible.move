@@ -0,0 +1,27 @@
+module 0x42::Indestructible {
+    // flagged: packed but never unpacked
+    struct Vault has key, store { value: u64 }
+
+    // ok: can be dropped
+    struct Receipt has drop { value: u64 }
+
+    // ok: can be unpacked
+    struct Ticket has store { value: u64 }
+
+    public fun new_vault(account: &signer, value: u64) {
+        move_to(account, Vault { value })
+    }
+
+    public fun new_receipt(value: u64): Receipt {
+        Receipt { value }
+    }
+
+    public fun new_ticket(value: u64): Ticket {
+        Ticket { value }
+    }
+
+    public fun redeem(ticket: Ticket): u64 {
+        let Ticket { value } = ticket;
+        value
+    }
+}
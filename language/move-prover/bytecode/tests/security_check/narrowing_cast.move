@@ -0,0 +1,38 @@
+module 0x42::NarrowingCast {
+    // flagged: the product is not checked before the cast
+    public fun price(amount: u64, rate: u64): u8 {
+        ((amount * rate) as u8)
+    }
+
+    // ok: the result is bounded before the cast
+    public fun checked_price(amount: u64, rate: u64): u8 {
+        let total = amount * rate;
+        assert!(total <= 255, 1);
+        (total as u8)
+    }
+
+    // ok: widening cast
+    public fun widen(amount: u64, rate: u64): u128 {
+        ((amount + rate) as u128)
+    }
+
+    // ok: no arithmetic before the cast
+    public fun narrow(amount: u64): u8 {
+        (amount as u8)
+    }
+
+    // flagged: the shift happens on a user-provided value
+    public entry fun shift(amount: u128) {
+        let shifted = amount << 8;
+        let _ = (shifted as u64);
+    }
+
+    // ok: not callable from outside the module
+    fun internal_price(amount: u64, rate: u64): u8 {
+        ((amount * rate) as u8)
+    }
+
+    public fun use_internal(): u8 {
+        internal_price(1, 2)
+    }
+}
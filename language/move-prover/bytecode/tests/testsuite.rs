@@ -27,6 +27,7 @@ use move_stackless_bytecode::{
     print_targets_for_test,
     reaching_def_analysis::ReachingDefProcessor,
     read_write_set_analysis::ReadWriteSetProcessor,
+    security_check::SecurityCheckProcessor,
     spec_instrumentation::SpecInstrumentationProcessor,
     usage_analysis::UsageProcessor,
     verification_analysis::VerificationAnalysisProcessor,
@@ -200,6 +201,11 @@ fn get_tested_transformation_pipeline(
             pipeline.add_processor(MonoAnalysisProcessor::new());
             Ok(Some(pipeline))
         }
+        "security_check" => {
+            let mut pipeline = FunctionTargetPipeline::default();
+            pipeline.add_processor(SecurityCheckProcessor::new());
+            Ok(Some(pipeline))
+        }
         "usage_analysis" => {
             let mut pipeline = FunctionTargetPipeline::default();
             pipeline.add_processor(UsageProcessor::new());
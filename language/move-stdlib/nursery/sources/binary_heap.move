@@ -0,0 +1,172 @@
+/// A min-heap backed by a vector, ordered by `compare::cmp`.
+///
+/// The smallest element can be read in constant time, and pushing or popping an element costs
+/// O(log n) comparisons. Every comparison serializes both elements, so small elements such as
+/// integers are considerably cheaper than large structs. A max-heap can be obtained by storing
+/// keys whose BCS order is reversed, e.g. `MAX_U64 - key`.
+module std::binary_heap {
+    use std::compare;
+    use std::errors;
+    use std::vector;
+
+    /// The heap is empty.
+    const EHEAP_EMPTY: u64 = 0;
+
+    /// The result of `compare::cmp` when the first value is smaller than the second
+    const LESS_THAN: u8 = 1;
+
+    struct BinaryHeap<T> has copy, drop, store {
+        data: vector<T>,
+    }
+
+    /// Return an empty heap.
+    public fun new<T>(): BinaryHeap<T> {
+        BinaryHeap { data: vector::empty() }
+    }
+    spec new {
+        aborts_if false;
+        ensures len(result.data) == 0;
+    }
+
+    /// Return a heap with the elements of `v`. This costs O(n) comparisons.
+    public fun from_vector<T>(v: vector<T>): BinaryHeap<T> {
+        let heap = BinaryHeap { data: v };
+        let i = vector::length(&heap.data) / 2;
+        while (i > 0) {
+            i = i - 1;
+            sift_down(&mut heap.data, i);
+        };
+        heap
+    }
+    spec from_vector {
+        pragma verify = false;
+        aborts_if false;
+        ensures len(result.data) == len(v);
+    }
+
+    /// Return the number of elements in the heap.
+    public fun length<T>(heap: &BinaryHeap<T>): u64 {
+        vector::length(&heap.data)
+    }
+    spec length {
+        aborts_if false;
+        ensures result == len(heap.data);
+    }
+
+    /// Return true iff the heap has no elements.
+    public fun is_empty<T>(heap: &BinaryHeap<T>): bool {
+        vector::is_empty(&heap.data)
+    }
+    spec is_empty {
+        aborts_if false;
+        ensures result == (len(heap.data) == 0);
+    }
+
+    /// Add `element` to the heap. This costs O(log n) comparisons.
+    public fun push<T>(heap: &mut BinaryHeap<T>, element: T) {
+        vector::push_back(&mut heap.data, element);
+        let i = vector::length(&heap.data) - 1;
+        while (i > 0) {
+            let parent = (i - 1) / 2;
+            if (!less(&heap.data, i, parent)) break;
+            vector::swap(&mut heap.data, i, parent);
+            i = parent;
+        };
+    }
+    spec push {
+        pragma verify = false;
+        aborts_if false;
+        ensures len(heap.data) == len(old(heap).data) + 1;
+    }
+
+    /// Return a reference to the smallest element. Aborts if the heap is empty.
+    public fun peek<T>(heap: &BinaryHeap<T>): &T {
+        assert!(!vector::is_empty(&heap.data), errors::invalid_state(EHEAP_EMPTY));
+        vector::borrow(&heap.data, 0)
+    }
+    spec peek {
+        aborts_if len(heap.data) == 0 with errors::INVALID_STATE;
+        ensures result == heap.data[0];
+    }
+
+    /// Remove and return the smallest element. Aborts if the heap is empty. This costs
+    /// O(log n) comparisons.
+    public fun pop<T>(heap: &mut BinaryHeap<T>): T {
+        assert!(!vector::is_empty(&heap.data), errors::invalid_state(EHEAP_EMPTY));
+        let element = vector::swap_remove(&mut heap.data, 0);
+        if (!vector::is_empty(&heap.data)) sift_down(&mut heap.data, 0);
+        element
+    }
+    spec pop {
+        pragma verify = false;
+        aborts_if len(heap.data) == 0 with errors::INVALID_STATE;
+        ensures len(heap.data) == len(old(heap).data) - 1;
+        ensures result == old(heap).data[0];
+    }
+
+    /// Destroy the heap, returning its elements in ascending order. This costs O(n log n)
+    /// comparisons.
+    public fun into_sorted_vector<T>(heap: BinaryHeap<T>): vector<T> {
+        let result = vector::empty();
+        while (!vector::is_empty(&heap.data)) {
+            vector::push_back(&mut result, pop(&mut heap));
+        };
+        let BinaryHeap { data } = heap;
+        vector::destroy_empty(data);
+        result
+    }
+    spec into_sorted_vector {
+        pragma verify = false;
+        aborts_if false;
+        ensures len(result) == len(heap.data);
+    }
+
+    /// Destroy an empty heap. Aborts if the heap is not empty.
+    public fun destroy_empty<T>(heap: BinaryHeap<T>) {
+        let BinaryHeap { data } = heap;
+        vector::destroy_empty(data);
+    }
+    spec destroy_empty {
+        aborts_if len(heap.data) != 0;
+    }
+
+    /// Move the element at index `i` down until neither of its children is smaller.
+    fun sift_down<T>(data: &mut vector<T>, i: u64) {
+        let len = vector::length(data);
+        loop {
+            let smallest = i;
+            let left = 2 * i + 1;
+            let right = left + 1;
+            if (left < len && less(data, left, smallest)) smallest = left;
+            if (right < len && less(data, right, smallest)) smallest = right;
+            if (smallest == i) break;
+            vector::swap(data, i, smallest);
+            i = smallest;
+        }
+    }
+    spec sift_down {
+        pragma opaque;
+        pragma verify = false;
+        aborts_if false;
+        ensures len(data) == len(old(data));
+    }
+
+    /// Return true iff the element at index `i` is smaller than the one at index `j`.
+    fun less<T>(data: &vector<T>, i: u64, j: u64): bool {
+        compare::cmp(vector::borrow(data, i), vector::borrow(data, j)) == LESS_THAN
+    }
+    spec less {
+        pragma opaque;
+        pragma verify = false;
+        aborts_if false;
+    }
+
+    // **************** SPECIFICATIONS ****************
+
+    spec module {} // switch documentation context to module level
+
+    /// The heap order is defined by `compare::cmp`, which is not specified in terms of the
+    /// elements themselves. The specifications of the functions that reorder the elements are
+    /// therefore not verified.
+    spec module {}
+}
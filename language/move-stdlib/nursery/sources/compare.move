@@ -1,5 +1,6 @@
 /// Utilities for comparing Move values based on their representation in BCS.
 module std::compare {
+    use std::bcs;
     use std::vector;
 
     // Move does not have signed integers, so we cannot use the usual 0, -1, 1 convention to
@@ -57,6 +58,29 @@ module std::compare {
         len_cmp
     }
 
+    /// Compare `v1` and `v2` by their BCS encodings, as described for `cmp_bcs_bytes`.
+    /// Returns either `EQUAL` (0u8), `LESS_THAN` (1u8), or `GREATER_THAN` (2u8).
+    ///
+    /// Each call serializes both values, so its gas cost is linear in the size of their
+    /// encodings. This is cheap for integers and addresses, but can dominate the cost of ordered
+    /// collections whose keys are large structs or vectors.
+    public fun cmp<T>(v1: &T, v2: &T): u8 {
+        cmp_bcs_bytes(&bcs::to_bytes(v1), &bcs::to_bytes(v2))
+    }
+    spec cmp {
+        // `cmp_bcs_bytes` has no specification, so this can not be verified. The specification
+        // exposes the properties the ordered collections rely on.
+        pragma verify = false;
+        pragma opaque;
+        aborts_if false;
+        ensures result == spec_cmp(v1, v2);
+        ensures result == EQUAL <==> v1 == v2;
+        ensures result <= GREATER_THAN;
+    }
+
+    /// The result of `cmp(v1, v2)`. The order is left uninterpreted.
+    spec fun spec_cmp<T>(v1: T, v2: T): u8;
+
     /// Compare two `u8`'s
     fun cmp_u8(i1: u8, i2: u8): u8 {
         if (i1 == i2) EQUAL
@@ -0,0 +1,316 @@
+/// A map backed by a vector of key-value entries sorted by key.
+///
+/// Keys are ordered by `compare::cmp`, i.e. by their BCS encodings. For unsigned integers this
+/// is the numeric order; for other types it is a fixed but possibly surprising order, see
+/// `compare::cmp_bcs_bytes`.
+///
+/// Looking up a key is a binary search with O(log n) comparisons, and the entries can be
+/// visited in order, also starting from a given key. Adding or removing a key additionally
+/// shifts all subsequent entries, which costs O(n) but much less per entry than a comparison.
+/// Every comparison serializes both keys, so small keys such as integers or addresses are
+/// considerably cheaper than large structs or vectors.
+module std::ordered_map {
+    use std::compare;
+    use std::errors;
+    use std::option::{Self, Option};
+    use std::vector;
+
+    /// The map already contains the key.
+    const EKEY_ALREADY_EXISTS: u64 = 0;
+    /// The map does not contain the key.
+    const EKEY_NOT_FOUND: u64 = 1;
+    /// The index is not smaller than the number of entries.
+    const EINDEX_OUT_OF_BOUNDS: u64 = 2;
+
+    /// The result of `compare::cmp` when the first value is smaller than the second
+    const LESS_THAN: u8 = 1;
+
+    struct OrderedMap<Key, Value> has copy, drop, store {
+        entries: vector<Entry<Key, Value>>,
+    }
+    spec OrderedMap {
+        invariant forall i in 0..len(entries), j in 0..len(entries):
+            entries[i].key == entries[j].key ==> i == j;
+    }
+
+    struct Entry<Key, Value> has copy, drop, store {
+        key: Key,
+        value: Value,
+    }
+
+    /// Return an empty map.
+    public fun new<Key, Value>(): OrderedMap<Key, Value> {
+        OrderedMap { entries: vector::empty() }
+    }
+    spec new {
+        aborts_if false;
+        ensures len(result.entries) == 0;
+    }
+
+    /// Return the number of entries in the map.
+    public fun length<Key, Value>(map: &OrderedMap<Key, Value>): u64 {
+        vector::length(&map.entries)
+    }
+    spec length {
+        aborts_if false;
+        ensures result == len(map.entries);
+    }
+
+    /// Return true iff the map has no entries.
+    public fun is_empty<Key, Value>(map: &OrderedMap<Key, Value>): bool {
+        vector::is_empty(&map.entries)
+    }
+    spec is_empty {
+        aborts_if false;
+        ensures result == (len(map.entries) == 0);
+    }
+
+    /// Return true iff the map contains `key`. This costs O(log n) comparisons.
+    public fun contains_key<Key, Value>(map: &OrderedMap<Key, Value>, key: &Key): bool {
+        let (found, _) = find(map, key);
+        found
+    }
+    spec contains_key {
+        aborts_if false;
+        ensures result == spec_contains_key(map, key);
+    }
+
+    /// Return a reference to the value of `key`. Aborts if the map does not contain `key`.
+    /// This costs O(log n) comparisons.
+    public fun borrow<Key, Value>(map: &OrderedMap<Key, Value>, key: &Key): &Value {
+        let (found, i) = find(map, key);
+        assert!(found, errors::invalid_argument(EKEY_NOT_FOUND));
+        &vector::borrow(&map.entries, i).value
+    }
+    spec borrow {
+        aborts_if !spec_contains_key(map, key) with errors::INVALID_ARGUMENT;
+        ensures result == spec_get(map, key);
+    }
+
+    /// Return a mutable reference to the value of `key`. Aborts if the map does not contain
+    /// `key`. This costs O(log n) comparisons.
+    public fun borrow_mut<Key, Value>(map: &mut OrderedMap<Key, Value>, key: &Key): &mut Value {
+        let (found, i) = find(map, key);
+        assert!(found, errors::invalid_argument(EKEY_NOT_FOUND));
+        &mut vector::borrow_mut(&mut map.entries, i).value
+    }
+    spec borrow_mut {
+        aborts_if !spec_contains_key(map, key) with errors::INVALID_ARGUMENT;
+    }
+
+    /// Add `key` with `value` to the map. Aborts if the map already contains `key`. This costs
+    /// O(log n) comparisons and shifts the O(n) entries with greater keys.
+    public fun add<Key, Value>(map: &mut OrderedMap<Key, Value>, key: Key, value: Value) {
+        let (found, i) = find(map, &key);
+        assert!(!found, errors::invalid_argument(EKEY_ALREADY_EXISTS));
+        insert_at(&mut map.entries, i, Entry { key, value });
+    }
+    spec add {
+        pragma verify = false;
+        aborts_if spec_contains_key(map, key) with errors::INVALID_ARGUMENT;
+        ensures len(map.entries) == len(old(map).entries) + 1;
+        ensures spec_contains_key(map, key);
+        ensures spec_get(map, key) == value;
+    }
+
+    /// Set the value of `key` to `value`, adding `key` if the map does not contain it yet.
+    /// Returns the replaced value, if any. This costs O(log n) comparisons, and shifts the O(n)
+    /// entries with greater keys if `key` is added.
+    public fun upsert<Key: drop, Value>(
+        map: &mut OrderedMap<Key, Value>,
+        key: Key,
+        value: Value
+    ): Option<Value> {
+        let (found, i) = find(map, &key);
+        if (found) {
+            vector::push_back(&mut map.entries, Entry { key, value });
+            // Move the new entry into the place of the old one
+            let Entry { key: _, value: old_value } = vector::swap_remove(&mut map.entries, i);
+            option::some(old_value)
+        } else {
+            insert_at(&mut map.entries, i, Entry { key, value });
+            option::none()
+        }
+    }
+    spec upsert {
+        pragma verify = false;
+        aborts_if false;
+        ensures spec_contains_key(map, key);
+        ensures spec_get(map, key) == value;
+        ensures spec_contains_key(old(map), key) ==> result == option::spec_some(spec_get(old(map), key));
+        ensures !spec_contains_key(old(map), key) ==> result == option::spec_none();
+    }
+
+    /// Remove `key` from the map, and return it together with its value. Aborts if the map does
+    /// not contain `key`. This costs O(log n) comparisons and shifts the O(n) entries with
+    /// greater keys.
+    public fun remove<Key, Value>(map: &mut OrderedMap<Key, Value>, key: &Key): (Key, Value) {
+        let (found, i) = find(map, key);
+        assert!(found, errors::invalid_argument(EKEY_NOT_FOUND));
+        let Entry { key, value } = vector::remove(&mut map.entries, i);
+        (key, value)
+    }
+    spec remove {
+        pragma verify = false;
+        aborts_if !spec_contains_key(map, key) with errors::INVALID_ARGUMENT;
+        ensures len(map.entries) == len(old(map).entries) - 1;
+        ensures !spec_contains_key(map, key);
+        ensures result_1 == key;
+        ensures result_2 == spec_get(old(map), key);
+    }
+
+    /// Return the index of the first entry whose key is not smaller than `key`, or the number
+    /// of entries if there is none. Together with `borrow_entry`, this visits the entries in
+    /// order starting from `key`. This costs O(log n) comparisons.
+    public fun lower_bound<Key, Value>(map: &OrderedMap<Key, Value>, key: &Key): u64 {
+        let lo = 0;
+        let hi = vector::length(&map.entries);
+        while (lo < hi) {
+            let mid = lo + (hi - lo) / 2;
+            if (compare::cmp(&vector::borrow(&map.entries, mid).key, key) == LESS_THAN) {
+                lo = mid + 1
+            } else {
+                hi = mid
+            }
+        };
+        lo
+    }
+    spec lower_bound {
+        pragma opaque;
+        pragma verify = false;
+        aborts_if false;
+        ensures result <= len(map.entries);
+    }
+
+    /// Return references to the key and the value of the `i`th entry in key order. Aborts if
+    /// `i` is out of bounds.
+    public fun borrow_entry<Key, Value>(map: &OrderedMap<Key, Value>, i: u64): (&Key, &Value) {
+        assert!(i < vector::length(&map.entries), errors::invalid_argument(EINDEX_OUT_OF_BOUNDS));
+        let entry = vector::borrow(&map.entries, i);
+        (&entry.key, &entry.value)
+    }
+    spec borrow_entry {
+        aborts_if i >= len(map.entries) with errors::INVALID_ARGUMENT;
+        ensures result_1 == map.entries[i].key;
+        ensures result_2 == map.entries[i].value;
+    }
+
+    /// Return the keys of the map in order.
+    public fun keys<Key: copy, Value>(map: &OrderedMap<Key, Value>): vector<Key> {
+        keys_between(map, 0, vector::length(&map.entries))
+    }
+    spec keys {
+        pragma verify = false;
+        aborts_if false;
+        ensures len(result) == len(map.entries);
+        ensures forall i in 0..len(result): result[i] == map.entries[i].key;
+    }
+
+    /// Return the keys that are not smaller than `lower` and smaller than `upper`, in order.
+    /// This costs O(log n) comparisons plus the size of the result.
+    public fun keys_in_range<Key: copy, Value>(
+        map: &OrderedMap<Key, Value>,
+        lower: &Key,
+        upper: &Key
+    ): vector<Key> {
+        let start = lower_bound(map, lower);
+        let end = lower_bound(map, upper);
+        keys_between(map, start, end)
+    }
+    spec keys_in_range {
+        pragma verify = false;
+        aborts_if false;
+        ensures len(result) <= len(map.entries);
+    }
+
+    /// Destroy the map, returning its keys and values in key order.
+    public fun to_vec_pair<Key, Value>(map: OrderedMap<Key, Value>): (vector<Key>, vector<Value>) {
+        let OrderedMap { entries } = map;
+        let keys = vector::empty();
+        let values = vector::empty();
+        vector::reverse(&mut entries);
+        while (!vector::is_empty(&entries)) {
+            let Entry { key, value } = vector::pop_back(&mut entries);
+            vector::push_back(&mut keys, key);
+            vector::push_back(&mut values, value);
+        };
+        vector::destroy_empty(entries);
+        (keys, values)
+    }
+    spec to_vec_pair {
+        pragma verify = false;
+        aborts_if false;
+        ensures len(result_1) == len(map.entries) && len(result_2) == len(map.entries);
+        ensures forall i in 0..len(map.entries): result_1[i] == map.entries[i].key;
+        ensures forall i in 0..len(map.entries): result_2[i] == map.entries[i].value;
+    }
+
+    /// Destroy an empty map. Aborts if the map is not empty.
+    public fun destroy_empty<Key, Value>(map: OrderedMap<Key, Value>) {
+        let OrderedMap { entries } = map;
+        vector::destroy_empty(entries);
+    }
+    spec destroy_empty {
+        aborts_if len(map.entries) != 0;
+    }
+
+    /// Return `(true, i)` if the `i`th entry has the key `key`, and otherwise `(false, i)`
+    /// where `i` is the index at which `key` would be inserted.
+    fun find<Key, Value>(map: &OrderedMap<Key, Value>, key: &Key): (bool, u64) {
+        let i = lower_bound(map, key);
+        let found = i < vector::length(&map.entries) && &vector::borrow(&map.entries, i).key == key;
+        (found, i)
+    }
+    spec find {
+        pragma opaque;
+        pragma verify = false;
+        aborts_if false;
+        ensures result_2 <= len(map.entries);
+        ensures result_1 == spec_contains_key(map, key);
+        ensures result_1 ==> map.entries[result_2].key == key;
+    }
+
+    /// Move `entry` to index `i` of `entries`, shifting the subsequent entries.
+    fun insert_at<Key, Value>(entries: &mut vector<Entry<Key, Value>>, i: u64, entry: Entry<Key, Value>) {
+        vector::push_back(entries, entry);
+        let j = vector::length(entries) - 1;
+        while (j > i) {
+            vector::swap(entries, j - 1, j);
+            j = j - 1;
+        };
+    }
+    spec insert_at {
+        pragma opaque;
+        pragma verify = false;
+        aborts_if false;
+        ensures len(entries) == len(old(entries)) + 1;
+        ensures entries[i] == entry;
+    }
+
+    /// Return copies of the keys of the entries `start` (inclusive) to `end` (exclusive).
+    fun keys_between<Key: copy, Value>(map: &OrderedMap<Key, Value>, start: u64, end: u64): vector<Key> {
+        let keys = vector::empty();
+        while (start < end) {
+            vector::push_back(&mut keys, vector::borrow(&map.entries, start).key);
+            start = start + 1;
+        };
+        keys
+    }
+
+    // **************** SPECIFICATIONS ****************
+
+    spec module {} // switch documentation context to module level
+
+    /// The order of the keys is defined by `compare::cmp`, which is not specified in terms of
+    /// the keys themselves. The specifications of the functions that depend on the order are
+    /// therefore not verified.
+    spec module {}
+
+    spec fun spec_contains_key<Key, Value>(map: OrderedMap<Key, Value>, key: Key): bool {
+        exists i in 0..len(map.entries): map.entries[i].key == key
+    }
+
+    spec fun spec_get<Key, Value>(map: OrderedMap<Key, Value>, key: Key): Value {
+        map.entries[choose i in 0..len(map.entries) where map.entries[i].key == key].value
+    }
+}
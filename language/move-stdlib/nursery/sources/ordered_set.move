@@ -0,0 +1,197 @@
+/// A set backed by a vector of elements sorted by `compare::cmp`.
+///
+/// This is the set counterpart of `ordered_map`, with the same gas profile: membership tests
+/// are a binary search with O(log n) comparisons, while adding or removing an element also
+/// shifts the O(n) subsequent elements. Every comparison serializes both elements, so small
+/// elements such as integers or addresses are considerably cheaper than large structs.
+module std::ordered_set {
+    use std::compare;
+    use std::errors;
+    use std::vector;
+
+    /// The set already contains the element.
+    const EELEMENT_ALREADY_EXISTS: u64 = 0;
+    /// The set does not contain the element.
+    const EELEMENT_NOT_FOUND: u64 = 1;
+    /// The index is not smaller than the number of elements.
+    const EINDEX_OUT_OF_BOUNDS: u64 = 2;
+
+    /// The result of `compare::cmp` when the first value is smaller than the second
+    const LESS_THAN: u8 = 1;
+
+    struct OrderedSet<T> has copy, drop, store {
+        elements: vector<T>,
+    }
+    spec OrderedSet {
+        invariant forall i in 0..len(elements), j in 0..len(elements):
+            elements[i] == elements[j] ==> i == j;
+    }
+
+    /// Return an empty set.
+    public fun new<T>(): OrderedSet<T> {
+        OrderedSet { elements: vector::empty() }
+    }
+    spec new {
+        aborts_if false;
+        ensures len(result.elements) == 0;
+    }
+
+    /// Return the number of elements in the set.
+    public fun length<T>(set: &OrderedSet<T>): u64 {
+        vector::length(&set.elements)
+    }
+    spec length {
+        aborts_if false;
+        ensures result == len(set.elements);
+    }
+
+    /// Return true iff the set has no elements.
+    public fun is_empty<T>(set: &OrderedSet<T>): bool {
+        vector::is_empty(&set.elements)
+    }
+    spec is_empty {
+        aborts_if false;
+        ensures result == (len(set.elements) == 0);
+    }
+
+    /// Return true iff the set contains `element`. This costs O(log n) comparisons.
+    public fun contains<T>(set: &OrderedSet<T>, element: &T): bool {
+        let (found, _) = find(set, element);
+        found
+    }
+    spec contains {
+        aborts_if false;
+        ensures result == spec_contains(set, element);
+    }
+
+    /// Add `element` to the set. Aborts if the set already contains `element`. This costs
+    /// O(log n) comparisons and shifts the O(n) greater elements.
+    public fun add<T>(set: &mut OrderedSet<T>, element: T) {
+        let (found, i) = find(set, &element);
+        assert!(!found, errors::invalid_argument(EELEMENT_ALREADY_EXISTS));
+        vector::push_back(&mut set.elements, element);
+        let j = vector::length(&set.elements) - 1;
+        while (j > i) {
+            vector::swap(&mut set.elements, j - 1, j);
+            j = j - 1;
+        };
+    }
+    spec add {
+        pragma verify = false;
+        aborts_if spec_contains(set, element) with errors::INVALID_ARGUMENT;
+        ensures len(set.elements) == len(old(set).elements) + 1;
+        ensures spec_contains(set, element);
+    }
+
+    /// Remove `element` from the set and return it. Aborts if the set does not contain
+    /// `element`. This costs O(log n) comparisons and shifts the O(n) greater elements.
+    public fun remove<T>(set: &mut OrderedSet<T>, element: &T): T {
+        let (found, i) = find(set, element);
+        assert!(found, errors::invalid_argument(EELEMENT_NOT_FOUND));
+        vector::remove(&mut set.elements, i)
+    }
+    spec remove {
+        pragma verify = false;
+        aborts_if !spec_contains(set, element) with errors::INVALID_ARGUMENT;
+        ensures len(set.elements) == len(old(set).elements) - 1;
+        ensures !spec_contains(set, element);
+        ensures result == element;
+    }
+
+    /// Return the index of the first element that is not smaller than `element`, or the
+    /// number of elements if there is none. This costs O(log n) comparisons.
+    public fun lower_bound<T>(set: &OrderedSet<T>, element: &T): u64 {
+        let lo = 0;
+        let hi = vector::length(&set.elements);
+        while (lo < hi) {
+            let mid = lo + (hi - lo) / 2;
+            if (compare::cmp(vector::borrow(&set.elements, mid), element) == LESS_THAN) {
+                lo = mid + 1
+            } else {
+                hi = mid
+            }
+        };
+        lo
+    }
+    spec lower_bound {
+        pragma opaque;
+        pragma verify = false;
+        aborts_if false;
+        ensures result <= len(set.elements);
+    }
+
+    /// Return a reference to the `i`th smallest element. Aborts if `i` is out of bounds.
+    public fun borrow<T>(set: &OrderedSet<T>, i: u64): &T {
+        assert!(i < vector::length(&set.elements), errors::invalid_argument(EINDEX_OUT_OF_BOUNDS));
+        vector::borrow(&set.elements, i)
+    }
+    spec borrow {
+        aborts_if i >= len(set.elements) with errors::INVALID_ARGUMENT;
+        ensures result == set.elements[i];
+    }
+
+    /// Return the elements that are not smaller than `lower` and smaller than `upper`, in
+    /// order. This costs O(log n) comparisons plus the size of the result.
+    public fun range<T: copy>(set: &OrderedSet<T>, lower: &T, upper: &T): vector<T> {
+        let start = lower_bound(set, lower);
+        let end = lower_bound(set, upper);
+        let result = vector::empty();
+        while (start < end) {
+            vector::push_back(&mut result, *vector::borrow(&set.elements, start));
+            start = start + 1;
+        };
+        result
+    }
+    spec range {
+        pragma verify = false;
+        aborts_if false;
+        ensures len(result) <= len(set.elements);
+    }
+
+    /// Destroy the set, returning its elements in order.
+    public fun into_vector<T>(set: OrderedSet<T>): vector<T> {
+        let OrderedSet { elements } = set;
+        elements
+    }
+    spec into_vector {
+        aborts_if false;
+        ensures result == set.elements;
+    }
+
+    /// Destroy an empty set. Aborts if the set is not empty.
+    public fun destroy_empty<T>(set: OrderedSet<T>) {
+        let OrderedSet { elements } = set;
+        vector::destroy_empty(elements);
+    }
+    spec destroy_empty {
+        aborts_if len(set.elements) != 0;
+    }
+
+    /// Return `(true, i)` if the `i`th element is `element`, and otherwise `(false, i)` where
+    /// `i` is the index at which `element` would be inserted.
+    fun find<T>(set: &OrderedSet<T>, element: &T): (bool, u64) {
+        let i = lower_bound(set, element);
+        let found = i < vector::length(&set.elements) && vector::borrow(&set.elements, i) == element;
+        (found, i)
+    }
+    spec find {
+        pragma opaque;
+        pragma verify = false;
+        aborts_if false;
+        ensures result_2 <= len(set.elements);
+        ensures result_1 == spec_contains(set, element);
+        ensures result_1 ==> set.elements[result_2] == element;
+    }
+
+    // **************** SPECIFICATIONS ****************
+
+    spec module {} // switch documentation context to module level
+
+    /// As in `ordered_map`, the specifications of the functions that depend on the order of
+    /// the elements are not verified.
+    spec module {}
+
+    spec fun spec_contains<T>(set: OrderedSet<T>, element: T): bool {
+        exists i in 0..len(set.elements): set.elements[i] == element
+    }
+}
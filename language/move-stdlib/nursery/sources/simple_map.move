@@ -0,0 +1,251 @@
+/// A map backed by a vector of key-value entries in no particular order.
+///
+/// Keys are compared with `==`, so any type can be used as a key. Looking up, adding or
+/// removing a key scans the entries one by one, so the gas cost of these operations grows
+/// linearly with the size of the map. This makes `SimpleMap` a good fit for small maps, e.g.
+/// with tens of entries. Larger maps should use `ordered_map`, which finds keys with a binary
+/// search, or the `Table` extension, which does not keep the entries in a single value.
+module std::simple_map {
+    use std::errors;
+    use std::option::{Self, Option};
+    use std::vector;
+
+    /// The map already contains the key.
+    const EKEY_ALREADY_EXISTS: u64 = 0;
+    /// The map does not contain the key.
+    const EKEY_NOT_FOUND: u64 = 1;
+
+    struct SimpleMap<Key, Value> has copy, drop, store {
+        data: vector<Element<Key, Value>>,
+    }
+    spec SimpleMap {
+        invariant forall i in 0..len(data), j in 0..len(data): data[i].key == data[j].key ==> i == j;
+    }
+
+    struct Element<Key, Value> has copy, drop, store {
+        key: Key,
+        value: Value,
+    }
+
+    /// Return an empty map.
+    public fun new<Key, Value>(): SimpleMap<Key, Value> {
+        SimpleMap { data: vector::empty() }
+    }
+    spec new {
+        aborts_if false;
+        ensures len(result.data) == 0;
+    }
+
+    /// Return the number of entries in the map.
+    public fun length<Key, Value>(map: &SimpleMap<Key, Value>): u64 {
+        vector::length(&map.data)
+    }
+    spec length {
+        aborts_if false;
+        ensures result == len(map.data);
+    }
+
+    /// Return true iff the map has no entries.
+    public fun is_empty<Key, Value>(map: &SimpleMap<Key, Value>): bool {
+        vector::is_empty(&map.data)
+    }
+    spec is_empty {
+        aborts_if false;
+        ensures result == (len(map.data) == 0);
+    }
+
+    /// Return true iff the map contains `key`. This costs O(n) comparisons.
+    public fun contains_key<Key, Value>(map: &SimpleMap<Key, Value>, key: &Key): bool {
+        let (found, _) = find(map, key);
+        found
+    }
+    spec contains_key {
+        aborts_if false;
+        ensures result == spec_contains_key(map, key);
+    }
+
+    /// Return a reference to the value of `key`. Aborts if the map does not contain `key`.
+    /// This costs O(n) comparisons.
+    public fun borrow<Key, Value>(map: &SimpleMap<Key, Value>, key: &Key): &Value {
+        let (found, i) = find(map, key);
+        assert!(found, errors::invalid_argument(EKEY_NOT_FOUND));
+        &vector::borrow(&map.data, i).value
+    }
+    spec borrow {
+        aborts_if !spec_contains_key(map, key) with errors::INVALID_ARGUMENT;
+        ensures result == spec_get(map, key);
+    }
+
+    /// Return a mutable reference to the value of `key`. Aborts if the map does not contain
+    /// `key`. This costs O(n) comparisons.
+    public fun borrow_mut<Key, Value>(map: &mut SimpleMap<Key, Value>, key: &Key): &mut Value {
+        let (found, i) = find(map, key);
+        assert!(found, errors::invalid_argument(EKEY_NOT_FOUND));
+        &mut vector::borrow_mut(&mut map.data, i).value
+    }
+    spec borrow_mut {
+        aborts_if !spec_contains_key(map, key) with errors::INVALID_ARGUMENT;
+    }
+
+    /// Add `key` with `value` to the map. Aborts if the map already contains `key`. This costs
+    /// O(n) comparisons.
+    public fun add<Key, Value>(map: &mut SimpleMap<Key, Value>, key: Key, value: Value) {
+        let (found, _) = find(map, &key);
+        assert!(!found, errors::invalid_argument(EKEY_ALREADY_EXISTS));
+        vector::push_back(&mut map.data, Element { key, value });
+    }
+    spec add {
+        aborts_if spec_contains_key(map, key) with errors::INVALID_ARGUMENT;
+        ensures len(map.data) == len(old(map).data) + 1;
+        ensures spec_contains_key(map, key);
+        ensures spec_get(map, key) == value;
+    }
+
+    /// Set the value of `key` to `value`, adding `key` if the map does not contain it yet.
+    /// Returns the replaced key and value, if any. This costs O(n) comparisons.
+    public fun upsert<Key, Value>(
+        map: &mut SimpleMap<Key, Value>,
+        key: Key,
+        value: Value
+    ): (Option<Key>, Option<Value>) {
+        let (found, i) = find(map, &key);
+        vector::push_back(&mut map.data, Element { key, value });
+        if (found) {
+            // Move the new entry into the place of the old one
+            let Element { key, value } = vector::swap_remove(&mut map.data, i);
+            (option::some(key), option::some(value))
+        } else {
+            (option::none(), option::none())
+        }
+    }
+    spec upsert {
+        pragma verify = false;
+        aborts_if false;
+        ensures spec_contains_key(map, key);
+        ensures spec_get(map, key) == value;
+        ensures spec_contains_key(old(map), key) ==> option::is_some(result_2);
+        ensures spec_contains_key(old(map), key) ==> option::borrow(result_2) == spec_get(old(map), key);
+        ensures !spec_contains_key(old(map), key) ==> option::is_none(result_2);
+    }
+
+    /// Remove `key` from the map, and return it together with its value. Aborts if the map does
+    /// not contain `key`. This costs O(n) comparisons; the order of the remaining entries is
+    /// not preserved.
+    public fun remove<Key, Value>(map: &mut SimpleMap<Key, Value>, key: &Key): (Key, Value) {
+        let (found, i) = find(map, key);
+        assert!(found, errors::invalid_argument(EKEY_NOT_FOUND));
+        let Element { key, value } = vector::swap_remove(&mut map.data, i);
+        (key, value)
+    }
+    spec remove {
+        pragma verify = false;
+        aborts_if !spec_contains_key(map, key) with errors::INVALID_ARGUMENT;
+        ensures len(map.data) == len(old(map).data) - 1;
+        ensures !spec_contains_key(map, key);
+        ensures result_1 == key;
+        ensures result_2 == spec_get(old(map), key);
+    }
+
+    /// Return the keys of the map, in the order of the entries.
+    public fun keys<Key: copy, Value>(map: &SimpleMap<Key, Value>): vector<Key> {
+        let keys = vector::empty();
+        let i = 0;
+        let len = vector::length(&map.data);
+        while (i < len) {
+            vector::push_back(&mut keys, vector::borrow(&map.data, i).key);
+            i = i + 1;
+        };
+        keys
+    }
+    spec keys {
+        pragma verify = false;
+        aborts_if false;
+        ensures len(result) == len(map.data);
+        ensures forall i in 0..len(result): result[i] == map.data[i].key;
+    }
+
+    /// Return the values of the map, in the order of the entries.
+    public fun values<Key, Value: copy>(map: &SimpleMap<Key, Value>): vector<Value> {
+        let values = vector::empty();
+        let i = 0;
+        let len = vector::length(&map.data);
+        while (i < len) {
+            vector::push_back(&mut values, vector::borrow(&map.data, i).value);
+            i = i + 1;
+        };
+        values
+    }
+    spec values {
+        pragma verify = false;
+        aborts_if false;
+        ensures len(result) == len(map.data);
+        ensures forall i in 0..len(result): result[i] == map.data[i].value;
+    }
+
+    /// Destroy the map, returning its keys and values in the order of the entries.
+    public fun to_vec_pair<Key, Value>(map: SimpleMap<Key, Value>): (vector<Key>, vector<Value>) {
+        let SimpleMap { data } = map;
+        let keys = vector::empty();
+        let values = vector::empty();
+        vector::reverse(&mut data);
+        while (!vector::is_empty(&data)) {
+            let Element { key, value } = vector::pop_back(&mut data);
+            vector::push_back(&mut keys, key);
+            vector::push_back(&mut values, value);
+        };
+        vector::destroy_empty(data);
+        (keys, values)
+    }
+    spec to_vec_pair {
+        pragma verify = false;
+        aborts_if false;
+        ensures len(result_1) == len(map.data) && len(result_2) == len(map.data);
+        ensures forall i in 0..len(map.data): result_1[i] == map.data[i].key;
+        ensures forall i in 0..len(map.data): result_2[i] == map.data[i].value;
+    }
+
+    /// Destroy an empty map. Aborts if the map is not empty.
+    public fun destroy_empty<Key, Value>(map: SimpleMap<Key, Value>) {
+        let SimpleMap { data } = map;
+        vector::destroy_empty(data);
+    }
+    spec destroy_empty {
+        aborts_if len(map.data) != 0;
+    }
+
+    /// Return `(true, i)` if the `i`th entry has the key `key`, and `(false, 0)` otherwise.
+    fun find<Key, Value>(map: &SimpleMap<Key, Value>, key: &Key): (bool, u64) {
+        let i = 0;
+        let n = vector::length(&map.data);
+        while ({
+            spec {
+                invariant i <= n;
+                invariant n == len(map.data);
+                invariant forall j in 0..i: map.data[j].key != key;
+            };
+            i < n
+        }) {
+            if (&vector::borrow(&map.data, i).key == key) return (true, i);
+            i = i + 1;
+        };
+        (false, 0)
+    }
+    spec find {
+        pragma opaque;
+        aborts_if false;
+        ensures result_1 ==> result_2 < len(map.data) && map.data[result_2].key == key;
+        ensures !result_1 ==> !spec_contains_key(map, key);
+    }
+
+    // **************** SPECIFICATIONS ****************
+
+    spec module {} // switch documentation context to module level
+
+    spec fun spec_contains_key<Key, Value>(map: SimpleMap<Key, Value>, key: Key): bool {
+        exists i in 0..len(map.data): map.data[i].key == key
+    }
+
+    spec fun spec_get<Key, Value>(map: SimpleMap<Key, Value>, key: Key): Value {
+        map.data[choose i in 0..len(map.data) where map.data[i].key == key].value
+    }
+}
@@ -0,0 +1,39 @@
+#[test_only]
+module std::binary_heap_tests {
+    use std::binary_heap;
+
+    #[test]
+    fun push_and_pop_in_order() {
+        let heap = binary_heap::new<u64>();
+        binary_heap::push(&mut heap, 5);
+        binary_heap::push(&mut heap, 300);
+        binary_heap::push(&mut heap, 1);
+        binary_heap::push(&mut heap, 5);
+        binary_heap::push(&mut heap, 2);
+        assert!(binary_heap::length(&heap) == 5, 0);
+        assert!(*binary_heap::peek(&heap) == 1, 1);
+        assert!(binary_heap::pop(&mut heap) == 1, 2);
+        assert!(binary_heap::pop(&mut heap) == 2, 3);
+        assert!(binary_heap::pop(&mut heap) == 5, 4);
+        assert!(binary_heap::pop(&mut heap) == 5, 5);
+        assert!(binary_heap::pop(&mut heap) == 300, 6);
+        assert!(binary_heap::is_empty(&heap), 7);
+        binary_heap::destroy_empty(heap);
+    }
+
+    #[test]
+    fun from_vector_sorts() {
+        let heap = binary_heap::from_vector(vector[9, 4, 7, 1, 8, 2, 6, 3, 5, 0]);
+        assert!(*binary_heap::peek(&heap) == 0, 0);
+        assert!(binary_heap::into_sorted_vector(heap) == vector[0, 1, 2, 3, 4, 5, 6, 7, 8, 9], 1);
+        let empty = binary_heap::from_vector<u64>(vector[]);
+        assert!(binary_heap::into_sorted_vector(empty) == vector[], 2);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 1)]
+    fun pop_empty() {
+        let heap = binary_heap::new<u64>();
+        binary_heap::pop(&mut heap);
+    }
+}
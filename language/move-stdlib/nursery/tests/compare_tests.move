@@ -83,4 +83,13 @@ module std::compareTests {
         assert!(compare::cmp_bcs_bytes(&bcs::to_bytes(&x"01"), &bcs::to_bytes(&x"0000")) == GREATER_THAN, 0); // sensible
         assert!(compare::cmp_bcs_bytes(&bcs::to_bytes(&x"0001"), &bcs::to_bytes(&x"0100")) == GREATER_THAN, 0); // potentially confusing
     }
+
+    #[test]
+    fun cmp_matches_cmp_bcs_bytes() {
+        assert!(compare::cmp(&1u64, &1u64) == EQUAL, 0);
+        assert!(compare::cmp(&1u64, &2u64) == LESS_THAN, 1);
+        assert!(compare::cmp(&256u64, &255u64) == GREATER_THAN, 2);
+        assert!(compare::cmp(&x"0100", &x"0001") == LESS_THAN, 3);
+        assert!(compare::cmp(&@0x100, &@0x001) == LESS_THAN, 4);
+    }
 }
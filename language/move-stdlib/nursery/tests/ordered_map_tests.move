@@ -0,0 +1,93 @@
+#[test_only]
+module std::ordered_map_tests {
+    use std::option;
+    use std::ordered_map;
+
+    #[test]
+    fun keys_are_sorted() {
+        let map = ordered_map::new<u64, u64>();
+        ordered_map::add(&mut map, 30, 3);
+        ordered_map::add(&mut map, 10, 1);
+        ordered_map::add(&mut map, 256, 25);
+        ordered_map::add(&mut map, 20, 2);
+        assert!(ordered_map::length(&map) == 4, 0);
+        assert!(ordered_map::keys(&map) == vector[10, 20, 30, 256], 1);
+        let (key, value) = ordered_map::borrow_entry(&map, 1);
+        assert!(*key == 20 && *value == 2, 2);
+        let (keys, values) = ordered_map::to_vec_pair(map);
+        assert!(keys == vector[10, 20, 30, 256], 3);
+        assert!(values == vector[1, 2, 3, 25], 4);
+    }
+
+    #[test]
+    fun borrow_and_remove() {
+        let map = ordered_map::new<u64, u64>();
+        ordered_map::add(&mut map, 2, 20);
+        ordered_map::add(&mut map, 1, 10);
+        ordered_map::add(&mut map, 3, 30);
+        assert!(ordered_map::contains_key(&map, &2), 0);
+        assert!(!ordered_map::contains_key(&map, &4), 1);
+        assert!(!ordered_map::contains_key(&map, &0), 2);
+        *ordered_map::borrow_mut(&mut map, &3) = 31;
+        assert!(*ordered_map::borrow(&map, &3) == 31, 3);
+
+        let (key, value) = ordered_map::remove(&mut map, &2);
+        assert!(key == 2 && value == 20, 4);
+        assert!(ordered_map::keys(&map) == vector[1, 3], 5);
+        ordered_map::remove(&mut map, &1);
+        ordered_map::remove(&mut map, &3);
+        assert!(ordered_map::is_empty(&map), 6);
+        ordered_map::destroy_empty(map);
+    }
+
+    #[test]
+    fun upsert() {
+        let map = ordered_map::new<u64, u64>();
+        assert!(option::is_none(&ordered_map::upsert(&mut map, 2, 20)), 0);
+        assert!(option::is_none(&ordered_map::upsert(&mut map, 1, 10)), 1);
+        assert!(ordered_map::upsert(&mut map, 2, 21) == option::some(20), 2);
+        assert!(ordered_map::keys(&map) == vector[1, 2], 3);
+        assert!(*ordered_map::borrow(&map, &2) == 21, 4);
+    }
+
+    #[test]
+    fun range_queries() {
+        let map = ordered_map::new<u64, bool>();
+        let i = 0;
+        while (i < 10) {
+            ordered_map::add(&mut map, i * 10, true);
+            i = i + 1;
+        };
+        assert!(ordered_map::lower_bound(&map, &0) == 0, 0);
+        assert!(ordered_map::lower_bound(&map, &25) == 3, 1);
+        assert!(ordered_map::lower_bound(&map, &30) == 3, 2);
+        assert!(ordered_map::lower_bound(&map, &100) == 10, 3);
+        assert!(ordered_map::keys_in_range(&map, &25, &60) == vector[30, 40, 50], 4);
+        assert!(ordered_map::keys_in_range(&map, &20, &21) == vector[20], 5);
+        assert!(ordered_map::keys_in_range(&map, &95, &200) == vector[], 6);
+        assert!(ordered_map::keys_in_range(&map, &60, &25) == vector[], 7);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 7)]
+    fun add_existing_key() {
+        let map = ordered_map::new<u64, u64>();
+        ordered_map::add(&mut map, 1, 10);
+        ordered_map::add(&mut map, 1, 11);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 263)]
+    fun remove_missing_key() {
+        let map = ordered_map::new<u64, u64>();
+        ordered_map::add(&mut map, 1, 10);
+        ordered_map::remove(&mut map, &2);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 519)]
+    fun borrow_entry_out_of_bounds() {
+        let map = ordered_map::new<u64, u64>();
+        ordered_map::borrow_entry(&map, 0);
+    }
+}
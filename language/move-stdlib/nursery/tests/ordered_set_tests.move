@@ -0,0 +1,48 @@
+#[test_only]
+module std::ordered_set_tests {
+    use std::ordered_set;
+
+    #[test]
+    fun elements_are_sorted() {
+        let set = ordered_set::new<u64>();
+        ordered_set::add(&mut set, 5);
+        ordered_set::add(&mut set, 1);
+        ordered_set::add(&mut set, 1000);
+        ordered_set::add(&mut set, 3);
+        assert!(ordered_set::length(&set) == 4, 0);
+        assert!(*ordered_set::borrow(&set, 0) == 1, 1);
+        assert!(*ordered_set::borrow(&set, 3) == 1000, 2);
+        assert!(ordered_set::contains(&set, &3), 3);
+        assert!(!ordered_set::contains(&set, &4), 4);
+        assert!(ordered_set::into_vector(set) == vector[1, 3, 5, 1000], 5);
+    }
+
+    #[test]
+    fun remove_and_range() {
+        let set = ordered_set::new<u64>();
+        let i = 0;
+        while (i < 8) {
+            ordered_set::add(&mut set, i);
+            i = i + 1;
+        };
+        assert!(ordered_set::remove(&mut set, &4) == 4, 0);
+        assert!(ordered_set::range(&set, &2, &7) == vector[2, 3, 5, 6], 1);
+        assert!(ordered_set::lower_bound(&set, &4) == 4, 2);
+        assert!(ordered_set::range(&set, &8, &10) == vector[], 3);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 7)]
+    fun add_existing_element() {
+        let set = ordered_set::new<u64>();
+        ordered_set::add(&mut set, 1);
+        ordered_set::add(&mut set, 1);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 263)]
+    fun remove_missing_element() {
+        let set = ordered_set::new<u64>();
+        ordered_set::remove(&mut set, &1);
+    }
+}
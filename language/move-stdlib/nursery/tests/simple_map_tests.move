@@ -0,0 +1,71 @@
+#[test_only]
+module std::simple_map_tests {
+    use std::option;
+    use std::simple_map;
+
+    #[test]
+    fun add_borrow_remove() {
+        let map = simple_map::new<u64, u64>();
+        assert!(simple_map::is_empty(&map), 0);
+        simple_map::add(&mut map, 1, 10);
+        simple_map::add(&mut map, 2, 20);
+        assert!(simple_map::length(&map) == 2, 1);
+        assert!(simple_map::contains_key(&map, &1), 2);
+        assert!(!simple_map::contains_key(&map, &3), 3);
+        assert!(*simple_map::borrow(&map, &2) == 20, 4);
+
+        *simple_map::borrow_mut(&mut map, &1) = 11;
+        assert!(*simple_map::borrow(&map, &1) == 11, 5);
+
+        let (key, value) = simple_map::remove(&mut map, &1);
+        assert!(key == 1 && value == 11, 6);
+        assert!(!simple_map::contains_key(&map, &1), 7);
+        let (_, _) = simple_map::remove(&mut map, &2);
+        simple_map::destroy_empty(map);
+    }
+
+    #[test]
+    fun upsert() {
+        let map = simple_map::new<u64, u64>();
+        let (key, value) = simple_map::upsert(&mut map, 1, 10);
+        assert!(option::is_none(&key) && option::is_none(&value), 0);
+        simple_map::add(&mut map, 2, 20);
+        let (key, value) = simple_map::upsert(&mut map, 1, 11);
+        assert!(key == option::some(1) && value == option::some(10), 1);
+        assert!(*simple_map::borrow(&map, &1) == 11, 2);
+        assert!(simple_map::keys(&map) == vector[1, 2], 3);
+        assert!(simple_map::values(&map) == vector[11, 20], 4);
+    }
+
+    #[test]
+    fun to_vec_pair() {
+        let map = simple_map::new<u64, bool>();
+        simple_map::add(&mut map, 3, true);
+        simple_map::add(&mut map, 1, false);
+        let (keys, values) = simple_map::to_vec_pair(map);
+        assert!(keys == vector[3, 1], 0);
+        assert!(values == vector[true, false], 1);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 7)]
+    fun add_existing_key() {
+        let map = simple_map::new<u64, u64>();
+        simple_map::add(&mut map, 1, 10);
+        simple_map::add(&mut map, 1, 11);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 263)]
+    fun borrow_missing_key() {
+        let map = simple_map::new<u64, u64>();
+        simple_map::borrow(&map, &1);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 263)]
+    fun remove_missing_key() {
+        let map = simple_map::new<u64, u64>();
+        simple_map::remove(&mut map, &1);
+    }
+}
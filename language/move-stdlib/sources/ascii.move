@@ -135,6 +135,68 @@ module std::ascii {
        byte
     }
 
+    /// Returns a copy of `string` with the lowercase letters `a` to `z` converted to uppercase, and
+    /// all other characters unchanged.
+    public fun to_uppercase(string: &String): String {
+       let bytes = vector::empty();
+       let n = vector::length(&string.bytes);
+       let i = 0;
+       while ({
+           spec {
+               invariant i <= n;
+               invariant len(bytes) == i;
+               invariant forall j in 0..i: is_valid_char(bytes[j]);
+           };
+           i < n
+       }) {
+           vector::push_back(&mut bytes, char_to_uppercase(*vector::borrow(&string.bytes, i)));
+           i = i + 1;
+       };
+       String { bytes }
+    }
+    spec to_uppercase {
+        ensures len(result.bytes) == len(string.bytes);
+    }
+
+    /// Returns a copy of `string` with the uppercase letters `A` to `Z` converted to lowercase, and
+    /// all other characters unchanged.
+    public fun to_lowercase(string: &String): String {
+       let bytes = vector::empty();
+       let n = vector::length(&string.bytes);
+       let i = 0;
+       while ({
+           spec {
+               invariant i <= n;
+               invariant len(bytes) == i;
+               invariant forall j in 0..i: is_valid_char(bytes[j]);
+           };
+           i < n
+       }) {
+           vector::push_back(&mut bytes, char_to_lowercase(*vector::borrow(&string.bytes, i)));
+           i = i + 1;
+       };
+       String { bytes }
+    }
+    spec to_lowercase {
+        ensures len(result.bytes) == len(string.bytes);
+    }
+
+    /// Returns the uppercase counterpart of `byte` if it is a lowercase letter, and `byte` otherwise.
+    fun char_to_uppercase(byte: u8): u8 {
+       if (byte >= 0x61 && byte <= 0x7A) byte - 0x20 else byte
+    }
+    spec char_to_uppercase {
+        ensures is_valid_char(byte) ==> is_valid_char(result);
+    }
+
+    /// Returns the lowercase counterpart of `byte` if it is an uppercase letter, and `byte` otherwise.
+    fun char_to_lowercase(byte: u8): u8 {
+       if (byte >= 0x41 && byte <= 0x5A) byte + 0x20 else byte
+    }
+    spec char_to_lowercase {
+        ensures is_valid_char(byte) ==> is_valid_char(result);
+    }
+
     /// Returns `true` if `b` is a valid ASCII character. Returns `false` otherwise.
     public fun is_valid_char(b: u8): bool {
        b <= 0x7F
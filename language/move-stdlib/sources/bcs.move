@@ -6,6 +6,16 @@ module std::bcs {
     /// Return the binary representation of `v` in BCS (Binary Canonical Serialization) format
     native public fun to_bytes<MoveValue>(v: &MoveValue): vector<u8>;
 
+    /// Return the value whose BCS representation is `bytes`. Aborts if `bytes` is not the
+    /// canonical encoding of a value of type `MoveValue`.
+    ///
+    /// Since this bypasses the constructors of structs, `MoveValue` may only be built from
+    /// primitives, vectors, `std::string::String` (whose bytes must be valid UTF-8) and structs
+    /// declared in the calling module. This aborts for any other struct, for a `signer`, and for
+    /// types with the `key` ability. Use `std::bcs_reader` to parse other payloads field by
+    /// field through the constructors of their modules.
+    native public fun from_bytes<MoveValue>(bytes: vector<u8>): MoveValue;
+
     // ==============================
     // Module Specification
     spec module {} // switch to module documentation context
@@ -14,4 +24,16 @@ module std::bcs {
         /// Native function which is defined in the prover's prelude.
         native fun serialize<MoveValue>(v: &MoveValue): vector<u8>;
     }
+
+    spec from_bytes {
+        pragma opaque;
+        aborts_if [abstract] !spec_is_deserializable<MoveValue>(bytes);
+        ensures [abstract] result == spec_deserialize<MoveValue>(bytes);
+        ensures [abstract] serialize(result) == bytes;
+    }
+
+    /// Deserialization is modeled with uninterpreted functions.
+    spec fun spec_is_deserializable<MoveValue>(bytes: vector<u8>): bool;
+
+    spec fun spec_deserialize<MoveValue>(bytes: vector<u8>): MoveValue;
 }
@@ -0,0 +1,361 @@
+/// A streaming reader for BCS (Binary Canonical Serialization) encoded bytes.
+///
+/// A `BcsReader` holds the bytes and a cursor, and each `peel_*` function decodes one value at
+/// the cursor and advances it. This allows a module to parse a payload field by field, e.g. a
+/// struct serialized off-chain, without requiring a Move type with the same layout:
+///
+/// ```
+/// let reader = bcs_reader::new(bytes);
+/// let owner = bcs_reader::peel_address(&mut reader);
+/// let amount = bcs_reader::peel_u64(&mut reader);
+/// assert!(bcs_reader::is_empty(&reader), EUNEXPECTED_BYTES);
+/// ```
+///
+/// Vectors are encoded as their length followed by their elements, and an `Option` as a vector
+/// of length 0 or 1. Elements of types without a dedicated function can be read by calling
+/// `peel_vec_length` and then peeling each element.
+module std::bcs_reader {
+    use std::bcs;
+    use std::option::{Self, Option};
+    use std::vector;
+
+    /// There are not enough bytes left to decode the value.
+    const EOUT_OF_BYTES: u64 = 0x20001;
+    /// A bool is encoded as a byte other than 0 or 1.
+    const EMALFORMED_BOOL: u64 = 0x10002;
+    /// A length is not a canonical ULEB128 encoding, or is larger than `MAX_LENGTH`.
+    const EMALFORMED_LENGTH: u64 = 0x10003;
+    /// An option is encoded with a length other than 0 or 1.
+    const EMALFORMED_OPTION: u64 = 0x10004;
+
+    /// The largest length of a sequence accepted by BCS.
+    const MAX_LENGTH: u64 = 2147483647;
+
+    struct BcsReader has copy, drop, store {
+        bytes: vector<u8>,
+        cursor: u64,
+    }
+    spec BcsReader {
+        invariant cursor <= len(bytes);
+    }
+
+    /// Return a reader positioned at the start of `bytes`.
+    public fun new(bytes: vector<u8>): BcsReader {
+        BcsReader { bytes, cursor: 0 }
+    }
+    spec new {
+        aborts_if false;
+        ensures result.bytes == bytes;
+        ensures result.cursor == 0;
+    }
+
+    /// Return the number of bytes that have not been read yet.
+    public fun remaining(reader: &BcsReader): u64 {
+        vector::length(&reader.bytes) - reader.cursor
+    }
+    spec remaining {
+        aborts_if false;
+        ensures result == len(reader.bytes) - reader.cursor;
+    }
+
+    /// Return true iff all bytes have been read.
+    public fun is_empty(reader: &BcsReader): bool {
+        reader.cursor == vector::length(&reader.bytes)
+    }
+    spec is_empty {
+        aborts_if false;
+        ensures result == (reader.cursor == len(reader.bytes));
+    }
+
+    /// Destroy the reader, returning the bytes that have not been read yet.
+    public fun into_remainder_bytes(reader: BcsReader): vector<u8> {
+        let BcsReader { bytes, cursor } = reader;
+        let n = vector::length(&bytes);
+        let result = vector::empty();
+        while (cursor < n) {
+            vector::push_back(&mut result, *vector::borrow(&bytes, cursor));
+            cursor = cursor + 1;
+        };
+        result
+    }
+    spec into_remainder_bytes {
+        pragma verify = false;
+        aborts_if false;
+        ensures result == reader.bytes[reader.cursor..len(reader.bytes)];
+    }
+
+    /// Read a `bool`. Aborts if the byte is neither 0 nor 1.
+    public fun peel_bool(reader: &mut BcsReader): bool {
+        let byte = peel_u8(reader);
+        assert!(byte <= 1, EMALFORMED_BOOL);
+        byte == 1
+    }
+    spec peel_bool {
+        aborts_if reader.cursor >= len(reader.bytes) with EOUT_OF_BYTES;
+        aborts_if reader.bytes[reader.cursor] > 1 with EMALFORMED_BOOL;
+        ensures reader.cursor == old(reader).cursor + 1;
+        ensures result == (old(reader).bytes[old(reader).cursor] == 1);
+    }
+
+    /// Read a `u8`.
+    public fun peel_u8(reader: &mut BcsReader): u8 {
+        assert!(reader.cursor < vector::length(&reader.bytes), EOUT_OF_BYTES);
+        let byte = *vector::borrow(&reader.bytes, reader.cursor);
+        reader.cursor = reader.cursor + 1;
+        byte
+    }
+    spec peel_u8 {
+        aborts_if reader.cursor >= len(reader.bytes) with EOUT_OF_BYTES;
+        ensures reader.cursor == old(reader).cursor + 1;
+        ensures result == old(reader).bytes[old(reader).cursor];
+    }
+
+    /// Read a `u16`.
+    public fun peel_u16(reader: &mut BcsReader): u16 {
+        (peel_le_bytes(reader, 2) as u16)
+    }
+    spec peel_u16 {
+        include PeelAbortsIf { n: 2 };
+        ensures reader.cursor == old(reader).cursor + 2;
+    }
+
+    /// Read a `u32`.
+    public fun peel_u32(reader: &mut BcsReader): u32 {
+        (peel_le_bytes(reader, 4) as u32)
+    }
+    spec peel_u32 {
+        include PeelAbortsIf { n: 4 };
+        ensures reader.cursor == old(reader).cursor + 4;
+    }
+
+    /// Read a `u64`.
+    public fun peel_u64(reader: &mut BcsReader): u64 {
+        (peel_le_bytes(reader, 8) as u64)
+    }
+    spec peel_u64 {
+        include PeelAbortsIf { n: 8 };
+        ensures reader.cursor == old(reader).cursor + 8;
+    }
+
+    /// Read a `u128`.
+    public fun peel_u128(reader: &mut BcsReader): u128 {
+        (peel_le_bytes(reader, 16) as u128)
+    }
+    spec peel_u128 {
+        include PeelAbortsIf { n: 16 };
+        ensures reader.cursor == old(reader).cursor + 16;
+    }
+
+    /// Read a `u256`.
+    public fun peel_u256(reader: &mut BcsReader): u256 {
+        peel_le_bytes(reader, 32)
+    }
+    spec peel_u256 {
+        include PeelAbortsIf { n: 32 };
+        ensures reader.cursor == old(reader).cursor + 32;
+    }
+
+    /// Read an `address`.
+    public fun peel_address(reader: &mut BcsReader): address {
+        // Addresses have a fixed length that depends on the platform, so take it from the
+        // encoding of an address rather than hardcoding it.
+        let n = vector::length(&bcs::to_bytes(&@0x0));
+        assert!(remaining(reader) >= n, EOUT_OF_BYTES);
+        let bytes = vector::empty();
+        let i = 0;
+        while (i < n) {
+            vector::push_back(&mut bytes, *vector::borrow(&reader.bytes, reader.cursor + i));
+            i = i + 1;
+        };
+        reader.cursor = reader.cursor + n;
+        bcs::from_bytes(bytes)
+    }
+    spec peel_address {
+        pragma verify = false;
+        ensures reader.cursor > old(reader).cursor;
+    }
+
+    /// Read the length of a vector, encoded as ULEB128. Aborts if the encoding is not canonical
+    /// or the length is larger than the largest length accepted by BCS.
+    public fun peel_vec_length(reader: &mut BcsReader): u64 {
+        let length = 0;
+        let shift = 0;
+        loop {
+            // A length of at most `MAX_LENGTH` is encoded in at most 5 bytes.
+            assert!(shift < 35, EMALFORMED_LENGTH);
+            let byte = peel_u8(reader);
+            length = length | (((byte & 0x7f) as u64) << shift);
+            if (byte & 0x80 == 0) {
+                // A trailing zero byte would encode the same length with more bytes.
+                assert!(shift == 0 || byte != 0, EMALFORMED_LENGTH);
+                break
+            };
+            shift = shift + 7;
+        };
+        assert!(length <= MAX_LENGTH, EMALFORMED_LENGTH);
+        length
+    }
+    spec peel_vec_length {
+        pragma verify = false;
+        ensures result <= MAX_LENGTH;
+        ensures reader.cursor > old(reader).cursor;
+    }
+
+    /// Read a `vector<u8>`.
+    public fun peel_vec_u8(reader: &mut BcsReader): vector<u8> {
+        let n = peel_vec_length(reader);
+        assert!(remaining(reader) >= n, EOUT_OF_BYTES);
+        let result = vector::empty();
+        let i = 0;
+        while (i < n) {
+            vector::push_back(&mut result, *vector::borrow(&reader.bytes, reader.cursor + i));
+            i = i + 1;
+        };
+        reader.cursor = reader.cursor + n;
+        result
+    }
+    spec peel_vec_u8 {
+        pragma verify = false;
+        ensures reader.cursor >= old(reader).cursor + len(result);
+    }
+
+    /// Read a `vector<vector<u8>>`.
+    public fun peel_vec_vec_u8(reader: &mut BcsReader): vector<vector<u8>> {
+        let n = peel_vec_length(reader);
+        let result = vector::empty();
+        while (n > 0) {
+            vector::push_back(&mut result, peel_vec_u8(reader));
+            n = n - 1;
+        };
+        result
+    }
+    spec peel_vec_vec_u8 {
+        pragma verify = false;
+    }
+
+    /// Read a `vector<bool>`.
+    public fun peel_vec_bool(reader: &mut BcsReader): vector<bool> {
+        let n = peel_vec_length(reader);
+        let result = vector::empty();
+        while (n > 0) {
+            vector::push_back(&mut result, peel_bool(reader));
+            n = n - 1;
+        };
+        result
+    }
+    spec peel_vec_bool {
+        pragma verify = false;
+    }
+
+    /// Read a `vector<u64>`.
+    public fun peel_vec_u64(reader: &mut BcsReader): vector<u64> {
+        let n = peel_vec_length(reader);
+        let result = vector::empty();
+        while (n > 0) {
+            vector::push_back(&mut result, peel_u64(reader));
+            n = n - 1;
+        };
+        result
+    }
+    spec peel_vec_u64 {
+        pragma verify = false;
+    }
+
+    /// Read a `vector<u128>`.
+    public fun peel_vec_u128(reader: &mut BcsReader): vector<u128> {
+        let n = peel_vec_length(reader);
+        let result = vector::empty();
+        while (n > 0) {
+            vector::push_back(&mut result, peel_u128(reader));
+            n = n - 1;
+        };
+        result
+    }
+    spec peel_vec_u128 {
+        pragma verify = false;
+    }
+
+    /// Read a `vector<address>`.
+    public fun peel_vec_address(reader: &mut BcsReader): vector<address> {
+        let n = peel_vec_length(reader);
+        let result = vector::empty();
+        while (n > 0) {
+            vector::push_back(&mut result, peel_address(reader));
+            n = n - 1;
+        };
+        result
+    }
+    spec peel_vec_address {
+        pragma verify = false;
+    }
+
+    /// Read the tag of an `Option`, returning true iff the option is set. The value of a set
+    /// option follows the tag.
+    public fun peel_option_tag(reader: &mut BcsReader): bool {
+        let tag = peel_vec_length(reader);
+        assert!(tag <= 1, EMALFORMED_OPTION);
+        tag == 1
+    }
+    spec peel_option_tag {
+        pragma verify = false;
+    }
+
+    /// Read an `Option<u64>`.
+    public fun peel_option_u64(reader: &mut BcsReader): Option<u64> {
+        if (peel_option_tag(reader)) {
+            option::some(peel_u64(reader))
+        } else {
+            option::none()
+        }
+    }
+    spec peel_option_u64 {
+        pragma verify = false;
+    }
+
+    /// Read an `Option<address>`.
+    public fun peel_option_address(reader: &mut BcsReader): Option<address> {
+        if (peel_option_tag(reader)) {
+            option::some(peel_address(reader))
+        } else {
+            option::none()
+        }
+    }
+    spec peel_option_address {
+        pragma verify = false;
+    }
+
+    /// Read an unsigned integer of `n` bytes, which are in little-endian order.
+    fun peel_le_bytes(reader: &mut BcsReader, n: u8): u256 {
+        assert!(remaining(reader) >= (n as u64), EOUT_OF_BYTES);
+        let value = 0;
+        let i = 0;
+        while (i < n) {
+            let byte = *vector::borrow(&reader.bytes, reader.cursor + (i as u64));
+            value = value | ((byte as u256) << (8 * i));
+            i = i + 1;
+        };
+        reader.cursor = reader.cursor + (n as u64);
+        value
+    }
+    spec peel_le_bytes {
+        pragma opaque;
+        pragma verify = false;
+        include PeelAbortsIf;
+        ensures reader.cursor == old(reader).cursor + n;
+        ensures result < (1 << (8 * n));
+    }
+
+    spec schema PeelAbortsIf {
+        reader: BcsReader;
+        n: u64;
+        aborts_if len(reader.bytes) - reader.cursor < n with EOUT_OF_BYTES;
+    }
+
+    // **************** SPECIFICATIONS ****************
+
+    spec module {} // switch documentation context to module level
+
+    /// The functions that decode a value with a loop only specify how they advance the cursor,
+    /// and are not verified.
+    spec module {}
+}
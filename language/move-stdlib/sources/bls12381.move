@@ -0,0 +1,92 @@
+/// BLS signatures over the BLS12-381 curve, following the proof-of-possession scheme of the
+/// IETF BLS signature draft with public keys in G1 and signatures in G2.
+///
+/// Public keys are 48 bytes and signatures 96 bytes, both compressed. Public keys are checked to
+/// be in the prime-order subgroup and not the identity, and signatures to be in the prime-order
+/// subgroup, before they are used; malformed input makes verification return false.
+///
+/// Aggregating public keys is only safe against rogue-key attacks if every key was first checked
+/// with `verify_proof_of_possession`, e.g. when it was registered.
+module std::bls12381 {
+    /// Return true iff `public_key` is a valid public key.
+    native public fun validate_public_key(public_key: vector<u8>): bool;
+    spec validate_public_key {
+        pragma opaque;
+        aborts_if false;
+        ensures result == spec_validate_public_key(public_key);
+    }
+
+    /// Return true iff `proof` shows that the owner of `public_key` knows its secret key, i.e. is
+    /// a signature of the public key itself under the proof-of-possession domain.
+    native public fun verify_proof_of_possession(public_key: vector<u8>, proof: vector<u8>): bool;
+    spec verify_proof_of_possession {
+        pragma opaque;
+        aborts_if false;
+        ensures result == spec_verify_proof_of_possession(public_key, proof);
+        ensures result ==> spec_validate_public_key(public_key);
+    }
+
+    /// Return true iff `signature` is a valid signature of `message` by `public_key`.
+    native public fun verify_signature(signature: vector<u8>, public_key: vector<u8>, message: vector<u8>): bool;
+    spec verify_signature {
+        pragma opaque;
+        aborts_if false;
+        ensures result == spec_verify_signature(signature, public_key, message);
+        ensures result ==> spec_validate_public_key(public_key);
+    }
+
+    /// Return true iff `aggregate_signature` aggregates valid signatures of `messages[i]` by
+    /// `public_keys[i]` for all `i`. Returns false if there are no messages or if the numbers of
+    /// messages and public keys differ. This costs one pairing per message.
+    native public fun verify_aggregate_signature(
+        aggregate_signature: vector<u8>,
+        public_keys: vector<vector<u8>>,
+        messages: vector<vector<u8>>,
+    ): bool;
+    spec verify_aggregate_signature {
+        pragma opaque;
+        aborts_if false;
+        ensures result == spec_verify_aggregate_signature(aggregate_signature, public_keys, messages);
+        ensures result ==> len(public_keys) == len(messages) && len(messages) > 0;
+    }
+
+    /// Return true iff `multisignature` aggregates valid signatures of the same `message` by all
+    /// of `public_keys`. Returns false if there are no public keys. This costs two pairings
+    /// regardless of the number of keys.
+    native public fun verify_multisignature(
+        multisignature: vector<u8>,
+        public_keys: vector<vector<u8>>,
+        message: vector<u8>,
+    ): bool;
+    spec verify_multisignature {
+        pragma opaque;
+        aborts_if false;
+        ensures result == spec_verify_multisignature(multisignature, public_keys, message);
+        ensures result ==> len(public_keys) > 0;
+    }
+
+    // **************** SPECIFICATIONS ****************
+
+    spec module {} // switch documentation context to module level
+
+    /// Signature verification is modeled with uninterpreted functions.
+    spec module {}
+
+    spec fun spec_validate_public_key(public_key: vector<u8>): bool;
+
+    spec fun spec_verify_proof_of_possession(public_key: vector<u8>, proof: vector<u8>): bool;
+
+    spec fun spec_verify_signature(signature: vector<u8>, public_key: vector<u8>, message: vector<u8>): bool;
+
+    spec fun spec_verify_aggregate_signature(
+        aggregate_signature: vector<u8>,
+        public_keys: vector<vector<u8>>,
+        messages: vector<vector<u8>>,
+    ): bool;
+
+    spec fun spec_verify_multisignature(
+        multisignature: vector<u8>,
+        public_keys: vector<vector<u8>>,
+        message: vector<u8>,
+    ): bool;
+}
@@ -0,0 +1,367 @@
+/// Defines a decimal fixed-point numeric type with a configurable number of
+/// fractional digits.
+module std::decimal {
+    use std::math256;
+    use std::rounding::{Self, RoundingMode};
+
+    /// A non-negative decimal number `value / 10^scale`. Unlike a binary
+    /// fixed-point number, a decimal fraction with at most `scale` digits is
+    /// represented exactly, e.g., 0.1 with any scale of at least 1.
+    struct Decimal has copy, drop, store {
+        value: u128,
+        scale: u8,
+    }
+    spec Decimal {
+        invariant scale <= MAX_SCALE;
+    }
+
+    /// The largest number of fractional digits. This bounds the intermediate
+    /// values of `mul` and `div` to 256 bits.
+    const MAX_SCALE: u8 = 18;
+
+    const MAX_U128: u256 = 340282366920938463463374607431768211455;
+
+    /// The scale is larger than `MAX_SCALE`
+    const EINVALID_SCALE: u64 = 0x10001;
+    /// The result would be too large to be held in a `Decimal`
+    const EOVERFLOW: u64 = 0x20002;
+    /// The difference would be negative
+    const ENEGATIVE_RESULT: u64 = 0x10003;
+    /// A division by zero was encountered
+    const EDIVISION_BY_ZERO: u64 = 0x10004;
+
+    /// The result of `compare` when the first value is smaller than the second
+    const LESS_THAN: u8 = 0;
+    /// The result of `compare` when both values are equal
+    const EQUAL: u8 = 1;
+    /// The result of `compare` when the first value is greater than the second
+    const GREATER_THAN: u8 = 2;
+
+    /// Create the decimal `value / 10^scale`. Aborts if `scale` is larger
+    /// than `MAX_SCALE`.
+    public fun new(value: u128, scale: u8): Decimal {
+        assert!(scale <= MAX_SCALE, EINVALID_SCALE);
+        Decimal { value, scale }
+    }
+    spec new {
+        aborts_if scale > MAX_SCALE with EINVALID_SCALE;
+        ensures result.value == value && result.scale == scale;
+    }
+
+    /// Create the decimal with the integer value `val` and `scale`
+    /// fractional digits. Aborts if `scale` is larger than `MAX_SCALE` or if
+    /// the result overflows.
+    public fun from_u128(val: u128, scale: u8): Decimal {
+        assert!(scale <= MAX_SCALE, EINVALID_SCALE);
+        let value = (val as u256) * pow10(scale);
+        assert!(value <= MAX_U128, EOVERFLOW);
+        Decimal { value: (value as u128), scale }
+    }
+    spec from_u128 {
+        pragma opaque;
+        aborts_if scale > MAX_SCALE with EINVALID_SCALE;
+        aborts_if val * spec_pow10(scale) > MAX_U128 with EOVERFLOW;
+        ensures result.value == val * spec_pow10(scale) && result.scale == scale;
+    }
+
+    /// Return zero with `scale` fractional digits.
+    public fun zero(scale: u8): Decimal {
+        new(0, scale)
+    }
+    spec zero {
+        aborts_if scale > MAX_SCALE with EINVALID_SCALE;
+        ensures result.value == 0 && result.scale == scale;
+    }
+
+    /// Accessor for the unscaled value.
+    public fun value(num: Decimal): u128 {
+        num.value
+    }
+
+    /// Accessor for the number of fractional digits.
+    public fun scale(num: Decimal): u8 {
+        num.scale
+    }
+
+    /// Returns true if the number is zero.
+    public fun is_zero(num: Decimal): bool {
+        num.value == 0
+    }
+
+    /// Convert the number to `scale` fractional digits, rounding according
+    /// to `mode` if digits are dropped. Aborts if `scale` is larger than
+    /// `MAX_SCALE` or if the result overflows.
+    public fun rescale(num: Decimal, scale: u8, mode: RoundingMode): Decimal {
+        assert!(scale <= MAX_SCALE, EINVALID_SCALE);
+        let value = if (scale >= num.scale) {
+            (num.value as u256) * pow10(scale - num.scale)
+        } else {
+            rounding::div_u256((num.value as u256), pow10(num.scale - scale), mode)
+        };
+        assert!(value <= MAX_U128, EOVERFLOW);
+        Decimal { value: (value as u128), scale }
+    }
+    spec rescale {
+        pragma opaque;
+        let value = if (scale >= num.scale) {
+            num.value * spec_pow10(scale - num.scale)
+        } else {
+            rounding::spec_div_u256(num.value, spec_pow10(num.scale - scale), mode)
+        };
+        aborts_if scale > MAX_SCALE with EINVALID_SCALE;
+        aborts_if value > MAX_U128 with EOVERFLOW;
+        ensures result.value == value && result.scale == scale;
+    }
+
+    /// Convert the number to an integer, rounding according to `mode`.
+    public fun to_u128(num: Decimal, mode: RoundingMode): u128 {
+        (rounding::div_u256((num.value as u256), pow10(num.scale), mode) as u128)
+    }
+    spec to_u128 {
+        pragma opaque;
+        aborts_if false;
+        ensures result == rounding::spec_div_u256(num.value, spec_pow10(num.scale), mode);
+    }
+
+    /// Returns the sum of the two numbers, with the larger of their scales.
+    /// This will abort if the sum overflows.
+    public fun add(num1: Decimal, num2: Decimal): Decimal {
+        let scale = max_scale(num1, num2);
+        let sum = aligned(num1, scale) + aligned(num2, scale);
+        assert!(sum <= MAX_U128, EOVERFLOW);
+        Decimal { value: (sum as u128), scale }
+    }
+    spec add {
+        pragma opaque;
+        let scale = spec_max_scale(num1, num2);
+        let sum = spec_aligned(num1, scale) + spec_aligned(num2, scale);
+        aborts_if sum > MAX_U128 with EOVERFLOW;
+        ensures result.value == sum && result.scale == scale;
+    }
+
+    /// Returns the difference of the two numbers, with the larger of their
+    /// scales. This will abort if `num2` is larger than `num1`, or if a
+    /// number overflows when converted to the larger scale.
+    public fun sub(num1: Decimal, num2: Decimal): Decimal {
+        let scale = max_scale(num1, num2);
+        let value1 = aligned(num1, scale);
+        let value2 = aligned(num2, scale);
+        assert!(value1 >= value2, ENEGATIVE_RESULT);
+        let difference = value1 - value2;
+        assert!(difference <= MAX_U128, EOVERFLOW);
+        Decimal { value: (difference as u128), scale }
+    }
+    spec sub {
+        pragma opaque;
+        let scale = spec_max_scale(num1, num2);
+        let value1 = spec_aligned(num1, scale);
+        let value2 = spec_aligned(num2, scale);
+        aborts_if value1 < value2 with ENEGATIVE_RESULT;
+        aborts_if value1 - value2 > MAX_U128 with EOVERFLOW;
+        ensures result.value == value1 - value2 && result.scale == scale;
+    }
+
+    /// Returns the product of the two numbers with the larger of their
+    /// scales, rounded according to `mode`. This will abort if the product
+    /// overflows.
+    public fun mul(num1: Decimal, num2: Decimal, mode: RoundingMode): Decimal {
+        let scale = max_scale(num1, num2);
+        // The exact product has `num1.scale + num2.scale` fractional digits.
+        let product = rounding::div_u256(
+            (num1.value as u256) * (num2.value as u256),
+            pow10(num1.scale + num2.scale - scale),
+            mode
+        );
+        assert!(product <= MAX_U128, EOVERFLOW);
+        Decimal { value: (product as u128), scale }
+    }
+    spec mul {
+        pragma opaque;
+        let scale = spec_max_scale(num1, num2);
+        let product = rounding::spec_div_u256(
+            num1.value * num2.value,
+            spec_pow10(num1.scale + num2.scale - scale),
+            mode
+        );
+        aborts_if product > MAX_U128 with EOVERFLOW;
+        ensures result.value == product && result.scale == scale;
+    }
+
+    /// Returns the quotient of the two numbers with the larger of their
+    /// scales, rounded according to `mode`. This will abort if the divisor is
+    /// zero or if the quotient overflows.
+    public fun div(num1: Decimal, num2: Decimal, mode: RoundingMode): Decimal {
+        assert!(num2.value != 0, EDIVISION_BY_ZERO);
+        let scale = max_scale(num1, num2);
+        // Scale the dividend so that the quotient has `scale` fractional
+        // digits. With scales of at most 18 digits, this fits in a u256.
+        let dividend = (num1.value as u256) * pow10(scale + num2.scale - num1.scale);
+        let quotient = rounding::div_u256(dividend, (num2.value as u256), mode);
+        assert!(quotient <= MAX_U128, EOVERFLOW);
+        Decimal { value: (quotient as u128), scale }
+    }
+    spec div {
+        pragma opaque;
+        let scale = spec_max_scale(num1, num2);
+        let quotient = rounding::spec_div_u256(
+            num1.value * spec_pow10(scale + num2.scale - num1.scale),
+            num2.value,
+            mode
+        );
+        aborts_if num2.value == 0 with EDIVISION_BY_ZERO;
+        aborts_if quotient > MAX_U128 with EOVERFLOW;
+        ensures result.value == quotient && result.scale == scale;
+    }
+
+    /// Returns `base` raised to the power of `exponent`, with the scale of
+    /// `base`. Every intermediate product is rounded according to `mode`, so
+    /// the result may differ from the exactly rounded power in the last
+    /// digits. This will abort if an intermediate product overflows.
+    public fun pow(base: Decimal, exponent: u64, mode: RoundingMode): Decimal {
+        let result = from_u128(1, base.scale);
+        let b = base;
+        let e = exponent;
+        while ({
+            spec {
+                invariant result.scale == base.scale && b.scale == base.scale;
+                invariant spec_pow(result.value, b.value, e, base.scale, mode)
+                    == spec_pow(spec_pow10(base.scale), base.value, exponent, base.scale, mode);
+            };
+            e > 0
+        }) {
+            if (e % 2 == 1) result = mul(result, b, mode);
+            e = e / 2;
+            // Only square the base if it is needed, as the last square may
+            // overflow even if the result does not.
+            if (e > 0) b = mul(b, b, mode);
+        };
+        result
+    }
+    spec pow {
+        pragma opaque;
+        // Whether a rounded intermediate product overflows depends on the
+        // order of the products, so the abort condition is left unspecified.
+        pragma aborts_if_is_partial;
+        ensures result.scale == base.scale;
+        ensures result.value
+            == spec_pow(spec_pow10(base.scale), base.value, exponent, base.scale, mode);
+        ensures exponent == 0 ==> result.value == spec_pow10(base.scale);
+        ensures exponent == 1 ==> result == base;
+    }
+    /// Multiplies `acc` by `base` raised to the power of `exponent`, where
+    /// both values have `scale` decimal places, rounding each intermediate
+    /// product in the same order as `pow`.
+    spec fun spec_pow(acc: num, base: num, exponent: num, scale: num, mode: RoundingMode): num {
+        if (exponent == 0) {
+            acc
+        } else {
+            let next_acc = if (exponent % 2 == 1) {
+                rounding::spec_div_u256(acc * base, spec_pow10(scale), mode)
+            } else {
+                acc
+            };
+            let next_base = if (exponent / 2 > 0) {
+                rounding::spec_div_u256(base * base, spec_pow10(scale), mode)
+            } else {
+                base
+            };
+            spec_pow(next_acc, next_base, exponent / 2, scale, mode)
+        }
+    }
+
+    /// Compares the two numbers by their values, regardless of their scales,
+    /// returning `LESS_THAN` (0), `EQUAL` (1) or `GREATER_THAN` (2).
+    public fun compare(num1: Decimal, num2: Decimal): u8 {
+        let scale = max_scale(num1, num2);
+        let value1 = aligned(num1, scale);
+        let value2 = aligned(num2, scale);
+        if (value1 < value2) {
+            LESS_THAN
+        } else if (value1 == value2) {
+            EQUAL
+        } else {
+            GREATER_THAN
+        }
+    }
+    spec compare {
+        pragma opaque;
+        let scale = spec_max_scale(num1, num2);
+        let value1 = spec_aligned(num1, scale);
+        let value2 = spec_aligned(num2, scale);
+        aborts_if false;
+        ensures value1 < value2 ==> result == LESS_THAN;
+        ensures value1 == value2 ==> result == EQUAL;
+        ensures value1 > value2 ==> result == GREATER_THAN;
+    }
+
+    /// Returns the smaller of the two numbers.
+    public fun min(num1: Decimal, num2: Decimal): Decimal {
+        if (compare(num1, num2) == GREATER_THAN) num2 else num1
+    }
+    spec min {
+        aborts_if false;
+        ensures result == num1 || result == num2;
+    }
+
+    /// Returns the larger of the two numbers.
+    public fun max(num1: Decimal, num2: Decimal): Decimal {
+        if (compare(num1, num2) == LESS_THAN) num2 else num1
+    }
+    spec max {
+        aborts_if false;
+        ensures result == num1 || result == num2;
+    }
+
+    /// Return `10^exponent`.
+    fun pow10(exponent: u8): u256 {
+        math256::pow(10, exponent)
+    }
+    spec pow10 {
+        aborts_if spec_pow10(exponent) > MAX_U256;
+        ensures result == spec_pow10(exponent);
+    }
+
+    /// Return the larger of the scales of the two numbers.
+    fun max_scale(num1: Decimal, num2: Decimal): u8 {
+        if (num1.scale > num2.scale) num1.scale else num2.scale
+    }
+    spec max_scale {
+        aborts_if false;
+        ensures result == spec_max_scale(num1, num2);
+    }
+
+    /// Return the unscaled value of `num` converted to `scale` fractional
+    /// digits, where `scale` is at least `num.scale`. As both scales are at
+    /// most 18, this does not overflow.
+    fun aligned(num: Decimal, scale: u8): u256 {
+        (num.value as u256) * pow10(scale - num.scale)
+    }
+    spec aligned {
+        aborts_if scale < num.scale;
+        ensures result == spec_aligned(num, scale);
+    }
+
+    // **************** SPECIFICATIONS ****************
+
+    spec module {} // switch documentation context to module level
+
+    spec fun spec_pow10(exponent: num): num {
+        math256::spec_pow(10, exponent)
+    }
+
+    spec fun spec_max_scale(num1: Decimal, num2: Decimal): u8 {
+        if (num1.scale > num2.scale) {
+            num1.scale
+        } else {
+            num2.scale
+        }
+    }
+
+    spec fun spec_aligned(num: Decimal, scale: num): num {
+        num.value * spec_pow10(scale - num.scale)
+    }
+
+    spec module {
+        pragma aborts_if_is_strict;
+    }
+}
@@ -0,0 +1,37 @@
+/// Ed25519 signature verification, as specified in RFC 8032.
+///
+/// Public keys are 32 bytes and signatures are 64 bytes. Verification is strict: public keys
+/// of small order and signatures with a non-canonical `S` are rejected, so a valid signature
+/// cannot be altered into another valid signature for the same message and key.
+module std::ed25519 {
+    /// Return true iff `public_key` is a valid Ed25519 public key, i.e. a point on the curve
+    /// that is not of small order.
+    native public fun validate_public_key(public_key: vector<u8>): bool;
+    spec validate_public_key {
+        pragma opaque;
+        aborts_if false;
+        ensures result == spec_validate_public_key(public_key);
+    }
+
+    /// Return true iff `signature` is a valid signature of `message` by `public_key`. Returns
+    /// false for malformed signatures and public keys.
+    native public fun verify(signature: vector<u8>, public_key: vector<u8>, message: vector<u8>): bool;
+    spec verify {
+        pragma opaque;
+        aborts_if false;
+        ensures result == spec_verify(signature, public_key, message);
+        ensures result ==> spec_validate_public_key(public_key);
+    }
+
+    // **************** SPECIFICATIONS ****************
+
+    spec module {} // switch documentation context to module level
+
+    /// Signature verification is modeled with uninterpreted functions, so specifications can
+    /// distinguish code paths with valid and invalid signatures but cannot construct signatures.
+    spec module {}
+
+    spec fun spec_validate_public_key(public_key: vector<u8>): bool;
+
+    spec fun spec_verify(signature: vector<u8>, public_key: vector<u8>, message: vector<u8>): bool;
+}
@@ -0,0 +1,421 @@
+/// Defines a fixed-point numeric type with a 64-bit integer part and
+/// a 64-bit fractional part.
+
+module std::fixed_point64 {
+    use std::rounding::{Self, RoundingMode};
+
+    /// Define a fixed-point numeric type with 64 fractional bits.
+    /// This is just a u128 integer but it is wrapped in a struct to
+    /// make a unique type. This is a binary representation, so decimal
+    /// values may not be exactly representable, but it provides more
+    /// than 19 decimal digits of precision both before and after the
+    /// decimal point (38 digits total).
+    struct FixedPoint64 has copy, drop, store { value: u128 }
+
+    const MAX_U128: u256 = 340282366920938463463374607431768211455;
+
+    /// The denominator provided was zero
+    const EDENOMINATOR: u64 = 0x10001;
+    /// The quotient value would be too large to be held in a `u128`
+    const EDIVISION: u64 = 0x20002;
+    /// The multiplied value would be too large to be held in a `u128`
+    const EMULTIPLICATION: u64 = 0x20003;
+    /// A division by zero was encountered
+    const EDIVISION_BY_ZERO: u64 = 0x10004;
+    /// The computed ratio when converting to a `FixedPoint64` would be unrepresentable
+    const ERATIO_OUT_OF_RANGE: u64 = 0x20005;
+    /// The sum would be too large to be held in a `FixedPoint64`
+    const EADDITION: u64 = 0x20006;
+    /// The difference would be negative
+    const ESUBTRACTION: u64 = 0x10007;
+
+    /// The result of `compare` when the first value is smaller than the second
+    const LESS_THAN: u8 = 0;
+    /// The result of `compare` when both values are equal
+    const EQUAL: u8 = 1;
+    /// The result of `compare` when the first value is greater than the second
+    const GREATER_THAN: u8 = 2;
+
+    /// Multiply a u128 integer by a fixed-point number, truncating any
+    /// fractional part of the product. This will abort if the product
+    /// overflows.
+    public fun multiply_u128(val: u128, multiplier: FixedPoint64): u128 {
+        // The product of two 128 bit values has 256 bits, so perform the
+        // multiplication with u256 types and keep the full 256 bit product
+        // to avoid losing accuracy.
+        let unscaled_product = (val as u256) * (multiplier.value as u256);
+        // The unscaled product has 64 fractional bits (from the multiplier)
+        // so rescale it by shifting away the low bits.
+        let product = unscaled_product >> 64;
+        // Check whether the value is too large.
+        assert!(product <= MAX_U128, EMULTIPLICATION);
+        (product as u128)
+    }
+    spec multiply_u128 {
+        pragma opaque;
+        include MultiplyAbortsIf;
+        ensures result == spec_multiply_u128(val, multiplier);
+    }
+    spec schema MultiplyAbortsIf {
+        val: num;
+        multiplier: FixedPoint64;
+        aborts_if spec_multiply_u128(val, multiplier) > MAX_U128 with EMULTIPLICATION;
+    }
+    spec fun spec_multiply_u128(val: num, multiplier: FixedPoint64): num {
+        (val * multiplier.value) >> 64
+    }
+
+    /// Divide a u128 integer by a fixed-point number, truncating any
+    /// fractional part of the quotient. This will abort if the divisor
+    /// is zero or if the quotient overflows.
+    public fun divide_u128(val: u128, divisor: FixedPoint64): u128 {
+        // Check for division by zero.
+        assert!(divisor.value != 0, EDIVISION_BY_ZERO);
+        // First convert to 256 bits and then shift left to
+        // add 64 fractional zero bits to the dividend.
+        let scaled_value = (val as u256) << 64;
+        let quotient = scaled_value / (divisor.value as u256);
+        // Check whether the value is too large.
+        assert!(quotient <= MAX_U128, EDIVISION);
+        (quotient as u128)
+    }
+    spec divide_u128 {
+        pragma opaque;
+        include DivideAbortsIf;
+        ensures result == spec_divide_u128(val, divisor);
+    }
+    spec schema DivideAbortsIf {
+        val: num;
+        divisor: FixedPoint64;
+        aborts_if divisor.value == 0 with EDIVISION_BY_ZERO;
+        aborts_if spec_divide_u128(val, divisor) > MAX_U128 with EDIVISION;
+    }
+    spec fun spec_divide_u128(val: num, divisor: FixedPoint64): num {
+        (val << 64) / divisor.value
+    }
+
+    /// Create a fixed-point value from a rational number specified by its
+    /// numerator and denominator. This will abort if the denominator is zero.
+    /// It will also abort if the numerator is nonzero and the ratio is not in
+    /// the range 2^-64 .. 2^64-1. The ratio is truncated; use `div` on two
+    /// values created with `create_from_u128` for other rounding modes.
+    public fun create_from_rational(numerator: u128, denominator: u128): FixedPoint64 {
+        // Scale the numerator to have 64 fractional bits, so that the quotient
+        // will have 64 fractional bits.
+        let scaled_numerator = (numerator as u256) << 64;
+        assert!(denominator != 0, EDENOMINATOR);
+        let quotient = scaled_numerator / (denominator as u256);
+        assert!(quotient != 0 || numerator == 0, ERATIO_OUT_OF_RANGE);
+        // Return the quotient as a fixed-point number. We first need to check whether the cast
+        // can succeed.
+        assert!(quotient <= MAX_U128, ERATIO_OUT_OF_RANGE);
+        FixedPoint64 { value: (quotient as u128) }
+    }
+    spec create_from_rational {
+        pragma opaque;
+        include CreateFromRationalAbortsIf;
+        ensures result == spec_create_from_rational(numerator, denominator);
+    }
+    spec schema CreateFromRationalAbortsIf {
+        numerator: u128;
+        denominator: u128;
+        let scaled_numerator = numerator << 64;
+        let quotient = scaled_numerator / denominator;
+        aborts_if denominator == 0 with EDENOMINATOR;
+        aborts_if quotient == 0 && scaled_numerator != 0 with ERATIO_OUT_OF_RANGE;
+        aborts_if quotient > MAX_U128 with ERATIO_OUT_OF_RANGE;
+    }
+    spec fun spec_create_from_rational(numerator: num, denominator: num): FixedPoint64 {
+        FixedPoint64{value: (numerator << 64) / denominator}
+    }
+
+    /// Create a fixedpoint value from a raw value.
+    public fun create_from_raw_value(value: u128): FixedPoint64 {
+        FixedPoint64 { value }
+    }
+    spec create_from_raw_value {
+        pragma opaque;
+        aborts_if false;
+        ensures result.value == value;
+    }
+
+    /// Accessor for the raw u128 value.
+    public fun get_raw_value(num: FixedPoint64): u128 {
+        num.value
+    }
+
+    /// Returns true if the ratio is zero.
+    public fun is_zero(num: FixedPoint64): bool {
+        num.value == 0
+    }
+
+    /// Returns the smaller of the two FixedPoint64 numbers.
+    public fun min(num1: FixedPoint64, num2: FixedPoint64): FixedPoint64 {
+        if (num1.value < num2.value) {
+            num1
+        } else {
+            num2
+        }
+    }
+    spec min {
+        pragma opaque;
+        aborts_if false;
+        ensures result == spec_min(num1, num2);
+    }
+    spec fun spec_min(num1: FixedPoint64, num2: FixedPoint64): FixedPoint64 {
+        if (num1.value < num2.value) {
+            num1
+        } else {
+            num2
+        }
+    }
+
+    /// Returns the larger of the two FixedPoint64 numbers.
+    public fun max(num1: FixedPoint64, num2: FixedPoint64): FixedPoint64 {
+        if (num1.value > num2.value) {
+            num1
+        } else {
+            num2
+        }
+    }
+    spec max {
+        pragma opaque;
+        aborts_if false;
+        ensures result == spec_max(num1, num2);
+    }
+    spec fun spec_max(num1: FixedPoint64, num2: FixedPoint64): FixedPoint64 {
+        if (num1.value > num2.value) {
+            num1
+        } else {
+            num2
+        }
+    }
+
+    /// Compares the two FixedPoint64 numbers, returning `LESS_THAN` (0),
+    /// `EQUAL` (1) or `GREATER_THAN` (2).
+    public fun compare(num1: FixedPoint64, num2: FixedPoint64): u8 {
+        if (num1.value < num2.value) {
+            LESS_THAN
+        } else if (num1.value == num2.value) {
+            EQUAL
+        } else {
+            GREATER_THAN
+        }
+    }
+    spec compare {
+        pragma opaque;
+        aborts_if false;
+        ensures num1.value < num2.value ==> result == LESS_THAN;
+        ensures num1.value == num2.value ==> result == EQUAL;
+        ensures num1.value > num2.value ==> result == GREATER_THAN;
+    }
+
+    /// Create a fixedpoint value from a u128 value.
+    public fun create_from_u128(val: u128): FixedPoint64 {
+        let value = (val as u256) << 64;
+        assert!(value <= MAX_U128, ERATIO_OUT_OF_RANGE);
+        FixedPoint64{value: (value as u128)}
+    }
+    spec create_from_u128 {
+        pragma opaque;
+        include CreateFromU128AbortsIf;
+        ensures result == spec_create_from_u128(val);
+    }
+    spec schema CreateFromU128AbortsIf {
+        val: num;
+        let scaled_value = val << 64;
+        aborts_if scaled_value > MAX_U128 with ERATIO_OUT_OF_RANGE;
+    }
+    spec fun spec_create_from_u128(val: num): FixedPoint64 {
+        FixedPoint64 {value: val << 64}
+    }
+
+    /// Returns the sum of the two FixedPoint64 numbers. This will abort if
+    /// the sum overflows.
+    public fun add(num1: FixedPoint64, num2: FixedPoint64): FixedPoint64 {
+        let sum = (num1.value as u256) + (num2.value as u256);
+        assert!(sum <= MAX_U128, EADDITION);
+        FixedPoint64 { value: (sum as u128) }
+    }
+    spec add {
+        pragma opaque;
+        aborts_if num1.value + num2.value > MAX_U128 with EADDITION;
+        ensures result.value == num1.value + num2.value;
+    }
+
+    /// Returns the difference of the two FixedPoint64 numbers. This will
+    /// abort if `num2` is larger than `num1`.
+    public fun sub(num1: FixedPoint64, num2: FixedPoint64): FixedPoint64 {
+        assert!(num1.value >= num2.value, ESUBTRACTION);
+        FixedPoint64 { value: num1.value - num2.value }
+    }
+    spec sub {
+        pragma opaque;
+        aborts_if num1.value < num2.value with ESUBTRACTION;
+        ensures result.value == num1.value - num2.value;
+    }
+
+    /// Returns the product of the two FixedPoint64 numbers, rounded to 64
+    /// fractional bits according to `mode`. This will abort if the product
+    /// overflows.
+    public fun mul(num1: FixedPoint64, num2: FixedPoint64, mode: RoundingMode): FixedPoint64 {
+        // The unscaled product has 128 fractional bits and fits in a u256.
+        let unscaled_product = (num1.value as u256) * (num2.value as u256);
+        let product = rounding::div_u256(unscaled_product, 1 << 64, mode);
+        assert!(product <= MAX_U128, EMULTIPLICATION);
+        FixedPoint64 { value: (product as u128) }
+    }
+    spec mul {
+        pragma opaque;
+        let product = spec_mul(num1.value, num2.value, mode);
+        aborts_if product > MAX_U128 with EMULTIPLICATION;
+        ensures result.value == product;
+    }
+
+    /// Returns the quotient of the two FixedPoint64 numbers, rounded to 64
+    /// fractional bits according to `mode`. This will abort if the divisor
+    /// is zero or if the quotient overflows.
+    public fun div(num1: FixedPoint64, num2: FixedPoint64, mode: RoundingMode): FixedPoint64 {
+        assert!(num2.value != 0, EDIVISION_BY_ZERO);
+        // Add 64 fractional zero bits to the dividend, so that the quotient
+        // has 64 fractional bits.
+        let scaled_value = (num1.value as u256) << 64;
+        let quotient = rounding::div_u256(scaled_value, (num2.value as u256), mode);
+        assert!(quotient <= MAX_U128, EDIVISION);
+        FixedPoint64 { value: (quotient as u128) }
+    }
+    spec div {
+        pragma opaque;
+        let quotient = rounding::spec_div_u256(num1.value * spec_one(), num2.value, mode);
+        aborts_if num2.value == 0 with EDIVISION_BY_ZERO;
+        aborts_if quotient > MAX_U128 with EDIVISION;
+        ensures result.value == quotient;
+    }
+
+    /// Returns `base` raised to the power of `exponent`. Every intermediate
+    /// product is rounded according to `mode`, so for fractional bases the
+    /// result may differ from the exactly rounded power in the last bits.
+    /// This will abort if an intermediate product overflows.
+    public fun pow(base: FixedPoint64, exponent: u64, mode: RoundingMode): FixedPoint64 {
+        let result = FixedPoint64 { value: 1 << 64 };
+        let b = base;
+        let e = exponent;
+        while ({
+            spec {
+                invariant spec_pow(result.value, b.value, e, mode)
+                    == spec_pow(spec_one(), base.value, exponent, mode);
+            };
+            e > 0
+        }) {
+            if (e % 2 == 1) result = mul(result, b, mode);
+            e = e / 2;
+            // Only square the base if it is needed, as the last square may
+            // overflow even if the result does not.
+            if (e > 0) b = mul(b, b, mode);
+        };
+        result
+    }
+    spec pow {
+        pragma opaque;
+        // Whether a rounded intermediate product overflows depends on the
+        // order of the products, so the abort condition is left unspecified.
+        pragma aborts_if_is_partial;
+        ensures result.value == spec_pow(spec_one(), base.value, exponent, mode);
+        ensures exponent == 0 ==> result.value == spec_one();
+        ensures exponent == 1 ==> result == base;
+    }
+    /// Multiplies `acc` by `base` raised to the power of `exponent`, rounding
+    /// each intermediate product in the same order as `pow`.
+    spec fun spec_pow(acc: num, base: num, exponent: num, mode: RoundingMode): num {
+        if (exponent == 0) {
+            acc
+        } else {
+            let next_acc = if (exponent % 2 == 1) spec_mul(acc, base, mode) else acc;
+            let next_base = if (exponent / 2 > 0) spec_mul(base, base, mode) else base;
+            spec_pow(next_acc, next_base, exponent / 2, mode)
+        }
+    }
+    spec fun spec_mul(value1: num, value2: num, mode: RoundingMode): num {
+        rounding::spec_div_u256(value1 * value2, spec_one(), mode)
+    }
+
+    /// Returns the largest integer less than or equal to a given number.
+    public fun floor(num: FixedPoint64): u128 {
+        num.value >> 64
+    }
+    spec floor {
+        pragma opaque;
+        aborts_if false;
+        ensures result == spec_floor(num);
+    }
+    spec fun spec_floor(val: FixedPoint64): u128 {
+        let fractional = val.value % (1 << 64);
+        if (fractional == 0) {
+            val.value >> 64
+        } else {
+            (val.value - fractional) >> 64
+        }
+    }
+
+    /// Rounds up the given FixedPoint64 to the next largest integer.
+    public fun ceil(num: FixedPoint64): u128 {
+        let floored_num = floor(num) << 64;
+        if (num.value == floored_num) {
+            return floored_num >> 64
+        };
+        let val = ((floored_num as u256) + (1 << 64));
+        (val >> 64 as u128)
+    }
+    spec ceil {
+        pragma opaque;
+        aborts_if false;
+        ensures result == spec_ceil(num);
+    }
+    spec fun spec_ceil(val: FixedPoint64): u128 {
+        let fractional = val.value % (1 << 64);
+        let one = 1 << 64;
+        if (fractional == 0) {
+            val.value >> 64
+        } else {
+            (val.value - fractional + one) >> 64
+        }
+    }
+
+    /// Returns the value of a FixedPoint64 to the nearest integer.
+    public fun round(num: FixedPoint64): u128 {
+        let floored_num = floor(num) << 64;
+        let boundary = floored_num + ((1 << 64) / 2);
+        if (num.value < boundary) {
+            floored_num >> 64
+        } else {
+            ceil(num)
+        }
+    }
+    spec round {
+        pragma opaque;
+        aborts_if false;
+        ensures result == spec_round(num);
+    }
+    spec fun spec_round(val: FixedPoint64): u128 {
+        let fractional = val.value % (1 << 64);
+        let boundary = (1 << 64) / 2;
+        let one = 1 << 64;
+        if (fractional < boundary) {
+            (val.value - fractional) >> 64
+        } else {
+            (val.value - fractional + one) >> 64
+        }
+    }
+
+    // **************** SPECIFICATIONS ****************
+
+    spec module {} // switch documentation context to module level
+
+    spec fun spec_one(): num {
+        1 << 64
+    }
+
+    spec module {
+        pragma aborts_if_is_strict;
+    }
+}
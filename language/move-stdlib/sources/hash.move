@@ -1,8 +1,20 @@
-/// Module which defines SHA hashes for byte vectors.
+/// Module which defines SHA, Keccak, BLAKE2 and RIPEMD hashes for byte vectors.
 ///
 /// The functions in this module are natively declared both in the Move runtime
 /// as in the Move prover's prelude.
 module std::hash {
     native public fun sha2_256(data: vector<u8>): vector<u8>;
     native public fun sha3_256(data: vector<u8>): vector<u8>;
+
+    /// Return the 32-byte Keccak-256 hash of `data`, as used by Ethereum. This differs from
+    /// `sha3_256` in the padding of the input.
+    native public fun keccak256(data: vector<u8>): vector<u8>;
+
+    /// Return the BLAKE2b hash of `data` with a 32-byte output, as specified in RFC 7693.
+    native public fun blake2b_256(data: vector<u8>): vector<u8>;
+
+    /// Return the 20-byte RIPEMD-160 hash of `data`. With its short output, RIPEMD-160 offers
+    /// at most 80 bits of collision resistance; it is provided for compatibility with Bitcoin
+    /// addresses and should not be used for new designs.
+    native public fun ripemd160(data: vector<u8>): vector<u8>;
 }
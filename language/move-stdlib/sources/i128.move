@@ -0,0 +1,317 @@
+/// A signed 128-bit integer type, `I128`, represented in two's complement on top of a `u128`.
+/// All operations abort on overflow, as the builtin unsigned integer operations do.
+module std::i128 {
+    /// The sign bit, which is also the representation of the smallest `I128` value, -2^127
+    const MIN_BITS: u128 = 170141183460469231731687303715884105728;
+    /// The representation of the largest `I128` value, 2^127 - 1
+    const MAX_BITS: u128 = 170141183460469231731687303715884105727;
+    /// The largest `u128` value
+    const MAX_U128: u128 = 340282366920938463463374607431768211455;
+
+    /// The result does not fit in an `I128`
+    const EOVERFLOW: u64 = 0x20001;
+    /// The divisor is zero
+    const EDIVISION_BY_ZERO: u64 = 0x10002;
+    /// The value is negative and can not be converted to an unsigned integer
+    const ENEGATIVE: u64 = 0x10003;
+
+    /// The result of `compare` when the first value is smaller than the second
+    const LESS_THAN: u8 = 0;
+    /// The result of `compare` when both values are equal
+    const EQUAL: u8 = 1;
+    /// The result of `compare` when the first value is greater than the second
+    const GREATER_THAN: u8 = 2;
+
+    /// A signed 128-bit integer in two's complement representation.
+    struct I128 has copy, drop, store {
+        bits: u128
+    }
+
+    /// Return zero.
+    public fun zero(): I128 {
+        I128 { bits: 0 }
+    }
+    spec zero {
+        aborts_if false;
+        ensures spec_value(result) == 0;
+    }
+
+    /// Return the `I128` with value `v`. Aborts if `v` is greater than 2^127 - 1.
+    public fun from(v: u128): I128 {
+        assert!(v <= MAX_BITS, EOVERFLOW);
+        I128 { bits: v }
+    }
+    spec from {
+        aborts_if v > MAX_BITS with EOVERFLOW;
+        ensures spec_value(result) == v;
+    }
+
+    /// Return the `I128` with value `-v`. Aborts if `v` is greater than 2^127.
+    public fun neg_from(v: u128): I128 {
+        assert!(v <= MIN_BITS, EOVERFLOW);
+        I128 { bits: twos_complement(v) }
+    }
+    spec neg_from {
+        aborts_if v > MIN_BITS with EOVERFLOW;
+        ensures spec_value(result) == 0 - v;
+    }
+
+    /// Return the `I128` with the two's complement representation `bits`.
+    public fun from_bits(bits: u128): I128 {
+        I128 { bits }
+    }
+    spec from_bits {
+        aborts_if false;
+        ensures result.bits == bits;
+    }
+
+    /// Return the two's complement representation of `x`.
+    public fun bits(x: I128): u128 {
+        x.bits
+    }
+    spec bits {
+        aborts_if false;
+        ensures result == x.bits;
+    }
+
+    /// Return the value of `x` as a `u128`. Aborts if `x` is negative.
+    public fun as_u128(x: I128): u128 {
+        assert!(!is_neg(x), ENEGATIVE);
+        x.bits
+    }
+    spec as_u128 {
+        aborts_if x.bits >= MIN_BITS with ENEGATIVE;
+        ensures result == spec_value(x);
+    }
+
+    /// Return true if `x` is smaller than zero.
+    public fun is_neg(x: I128): bool {
+        x.bits >= MIN_BITS
+    }
+    spec is_neg {
+        aborts_if false;
+        ensures result == (spec_value(x) < 0);
+    }
+
+    /// Return `-x`. Aborts if `x` is -2^127, whose negation does not fit in an `I128`.
+    public fun neg(x: I128): I128 {
+        assert!(x.bits != MIN_BITS, EOVERFLOW);
+        I128 { bits: twos_complement(x.bits) }
+    }
+    spec neg {
+        aborts_if x.bits == MIN_BITS with EOVERFLOW;
+        ensures spec_value(result) == 0 - spec_value(x);
+    }
+
+    /// Return the absolute value of `x`. Aborts if `x` is -2^127.
+    public fun abs(x: I128): I128 {
+        if (is_neg(x)) neg(x) else x
+    }
+    spec abs {
+        aborts_if x.bits == MIN_BITS with EOVERFLOW;
+        ensures spec_value(result) == spec_abs(x);
+    }
+
+    /// Return the absolute value of `x` as a `u128`. Unlike `abs`, this never aborts.
+    public fun abs_u128(x: I128): u128 {
+        if (is_neg(x)) twos_complement(x.bits) else x.bits
+    }
+    spec abs_u128 {
+        aborts_if false;
+        ensures result == spec_abs(x);
+    }
+
+    /// Return `a + b`. Aborts if the sum does not fit in an `I128`.
+    public fun add(a: I128, b: I128): I128 {
+        let sum = I128 { bits: wrapping_add(a.bits, b.bits) };
+        // The sum overflows iff both operands have the same sign and the sum has the other one
+        assert!(is_neg(a) != is_neg(b) || is_neg(sum) == is_neg(a), EOVERFLOW);
+        sum
+    }
+    spec add {
+        pragma opaque;
+        aborts_if !spec_in_range(spec_value(a) + spec_value(b)) with EOVERFLOW;
+        ensures spec_value(result) == spec_value(a) + spec_value(b);
+    }
+
+    /// Return `a - b`. Aborts if the difference does not fit in an `I128`.
+    public fun sub(a: I128, b: I128): I128 {
+        let difference = I128 { bits: wrapping_add(a.bits, twos_complement(b.bits)) };
+        // The difference overflows iff the operands have different signs and the difference
+        // does not have the sign of `a`
+        assert!(is_neg(a) == is_neg(b) || is_neg(difference) == is_neg(a), EOVERFLOW);
+        difference
+    }
+    spec sub {
+        pragma opaque;
+        aborts_if !spec_in_range(spec_value(a) - spec_value(b)) with EOVERFLOW;
+        ensures spec_value(result) == spec_value(a) - spec_value(b);
+    }
+
+    /// Return `a * b`. Aborts if the product does not fit in an `I128`.
+    public fun mul(a: I128, b: I128): I128 {
+        let product = (abs_u128(a) as u256) * (abs_u128(b) as u256);
+        if (is_neg(a) != is_neg(b)) {
+            assert!(product <= (MIN_BITS as u256), EOVERFLOW);
+            I128 { bits: twos_complement((product as u128)) }
+        } else {
+            assert!(product <= (MAX_BITS as u256), EOVERFLOW);
+            I128 { bits: (product as u128) }
+        }
+    }
+    spec mul {
+        pragma opaque;
+        aborts_if !spec_in_range(spec_value(a) * spec_value(b)) with EOVERFLOW;
+        ensures spec_value(result) == spec_value(a) * spec_value(b);
+    }
+
+    /// Return `a / b`, rounding towards zero. Aborts if `b` is zero, or if `a` is -2^127 and
+    /// `b` is -1.
+    public fun div(a: I128, b: I128): I128 {
+        assert!(b.bits != 0, EDIVISION_BY_ZERO);
+        let quotient = abs_u128(a) / abs_u128(b);
+        if (is_neg(a) != is_neg(b)) neg_from(quotient) else from(quotient)
+    }
+    spec div {
+        pragma opaque;
+        aborts_if b.bits == 0 with EDIVISION_BY_ZERO;
+        aborts_if a.bits == MIN_BITS && b.bits == MAX_U128 with EOVERFLOW;
+        ensures spec_abs(result) == spec_abs(a) / spec_abs(b);
+        ensures spec_value(result) != 0 ==>
+            (spec_value(result) < 0) == ((spec_value(a) < 0) != (spec_value(b) < 0));
+    }
+
+    /// Return the remainder of `a / b`, which has the sign of `a`, so that
+    /// `add(mul(div(a, b), b), rem(a, b)) == a`. Aborts if `b` is zero.
+    public fun rem(a: I128, b: I128): I128 {
+        assert!(b.bits != 0, EDIVISION_BY_ZERO);
+        let remainder = abs_u128(a) % abs_u128(b);
+        if (is_neg(a)) neg_from(remainder) else from(remainder)
+    }
+    spec rem {
+        pragma opaque;
+        aborts_if b.bits == 0 with EDIVISION_BY_ZERO;
+        ensures spec_abs(result) == spec_abs(a) % spec_abs(b);
+        ensures spec_value(result) != 0 ==> (spec_value(result) < 0) == (spec_value(a) < 0);
+    }
+
+    /// Compare `a` and `b`, returning `LESS_THAN` (0), `EQUAL` (1) or `GREATER_THAN` (2).
+    public fun compare(a: I128, b: I128): u8 {
+        if (a.bits == b.bits) {
+            EQUAL
+        } else if (is_neg(a) != is_neg(b)) {
+            if (is_neg(a)) LESS_THAN else GREATER_THAN
+        } else {
+            // With equal signs, the order of the representations is the order of the values
+            if (a.bits < b.bits) LESS_THAN else GREATER_THAN
+        }
+    }
+    spec compare {
+        aborts_if false;
+        ensures spec_value(a) < spec_value(b) ==> result == LESS_THAN;
+        ensures spec_value(a) == spec_value(b) ==> result == EQUAL;
+        ensures spec_value(a) > spec_value(b) ==> result == GREATER_THAN;
+    }
+
+    /// Return true if `a` is smaller than `b`.
+    public fun lt(a: I128, b: I128): bool {
+        compare(a, b) == LESS_THAN
+    }
+    spec lt {
+        aborts_if false;
+        ensures result == (spec_value(a) < spec_value(b));
+    }
+
+    /// Return true if `a` is smaller than or equal to `b`.
+    public fun le(a: I128, b: I128): bool {
+        compare(a, b) != GREATER_THAN
+    }
+    spec le {
+        aborts_if false;
+        ensures result == (spec_value(a) <= spec_value(b));
+    }
+
+    /// Return true if `a` is greater than `b`.
+    public fun gt(a: I128, b: I128): bool {
+        compare(a, b) == GREATER_THAN
+    }
+    spec gt {
+        aborts_if false;
+        ensures result == (spec_value(a) > spec_value(b));
+    }
+
+    /// Return true if `a` is greater than or equal to `b`.
+    public fun ge(a: I128, b: I128): bool {
+        compare(a, b) != LESS_THAN
+    }
+    spec ge {
+        aborts_if false;
+        ensures result == (spec_value(a) >= spec_value(b));
+    }
+
+    /// Return the smaller of `a` and `b`.
+    public fun min(a: I128, b: I128): I128 {
+        if (lt(a, b)) a else b
+    }
+    spec min {
+        aborts_if false;
+        ensures spec_value(result) <= spec_value(a) && spec_value(result) <= spec_value(b);
+        ensures result == a || result == b;
+    }
+
+    /// Return the larger of `a` and `b`.
+    public fun max(a: I128, b: I128): I128 {
+        if (gt(a, b)) a else b
+    }
+    spec max {
+        aborts_if false;
+        ensures spec_value(result) >= spec_value(a) && spec_value(result) >= spec_value(b);
+        ensures result == a || result == b;
+    }
+
+    /// Return `2^128 - x` modulo 2^128, i.e. the representation of `-x`.
+    fun twos_complement(x: u128): u128 {
+        if (x == 0) 0 else MAX_U128 - x + 1
+    }
+    spec twos_complement {
+        aborts_if false;
+        ensures x == 0 ==> result == 0;
+        ensures x != 0 ==> result == MAX_U128 - x + 1;
+    }
+
+    /// Return `a + b` modulo 2^128.
+    fun wrapping_add(a: u128, b: u128): u128 {
+        if (a > MAX_U128 - b) a - (MAX_U128 - b) - 1 else a + b
+    }
+    spec wrapping_add {
+        aborts_if false;
+        ensures result == (a + b) % (MAX_U128 + 1);
+    }
+
+    // ============================================================================================
+    // Helper specification functions
+
+    spec fun spec_value(x: I128): num {
+        if (x.bits >= MIN_BITS) {
+            x.bits - (MAX_U128 + 1)
+        } else {
+            x.bits
+        }
+    }
+
+    spec fun spec_abs(x: I128): num {
+        if (spec_value(x) < 0) {
+            0 - spec_value(x)
+        } else {
+            spec_value(x)
+        }
+    }
+
+    spec fun spec_in_range(v: num): bool {
+        0 - MIN_BITS <= v && v <= MAX_BITS
+    }
+
+    spec module {
+        pragma aborts_if_is_strict;
+    }
+}
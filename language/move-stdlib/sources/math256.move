@@ -0,0 +1,282 @@
+/// Math utilities for the `u256` type: checked, saturating and wrapping arithmetic,
+/// `mul_div` with a 512-bit intermediate product, and integer `pow`, `sqrt` and `log2`.
+module std::math256 {
+    use std::option::{Self, Option};
+
+    /// The largest `u256` value
+    const MAX_U256: u256 = 115792089237316195423570985008687907853269984665640564039457584007913129639935;
+    /// The largest `u128` value, used to split a `u256` into two halves
+    const MAX_U128: u256 = 340282366920938463463374607431768211455;
+
+    /// The divisor is zero
+    const EDIVISION_BY_ZERO: u64 = 0x10001;
+    /// The result does not fit in a `u256`
+    const EOVERFLOW: u64 = 0x20002;
+    /// The lower bound is greater than the upper bound
+    const EINVALID_RANGE: u64 = 0x10003;
+    /// The logarithm of zero is undefined
+    const ELOG2_OF_ZERO: u64 = 0x10004;
+
+    /// Return the largest `u256` value.
+    public fun max_value(): u256 {
+        MAX_U256
+    }
+    spec max_value {
+        aborts_if false;
+        ensures result == MAX_U256;
+    }
+
+    /// Return `a + b`, or `none` if the sum does not fit in a `u256`.
+    public fun checked_add(a: u256, b: u256): Option<u256> {
+        if (a > MAX_U256 - b) option::none() else option::some(a + b)
+    }
+    spec checked_add {
+        aborts_if false;
+        ensures a + b > MAX_U256 ==> result == option::spec_none();
+        ensures a + b <= MAX_U256 ==> result == option::spec_some(a + b);
+    }
+
+    /// Return `a - b`, or `none` if `b` is greater than `a`.
+    public fun checked_sub(a: u256, b: u256): Option<u256> {
+        if (a < b) option::none() else option::some(a - b)
+    }
+    spec checked_sub {
+        aborts_if false;
+        ensures a < b ==> result == option::spec_none();
+        ensures a >= b ==> result == option::spec_some(a - b);
+    }
+
+    /// Return `a * b`, or `none` if the product does not fit in a `u256`.
+    public fun checked_mul(a: u256, b: u256): Option<u256> {
+        if (a != 0 && b > MAX_U256 / a) option::none() else option::some(a * b)
+    }
+    spec checked_mul {
+        aborts_if false;
+        ensures a * b > MAX_U256 ==> result == option::spec_none();
+        ensures a * b <= MAX_U256 ==> result == option::spec_some(a * b);
+    }
+
+    /// Return `a + b`, or the largest `u256` if the sum does not fit in a `u256`.
+    public fun saturating_add(a: u256, b: u256): u256 {
+        if (a > MAX_U256 - b) MAX_U256 else a + b
+    }
+    spec saturating_add {
+        aborts_if false;
+        ensures a + b > MAX_U256 ==> result == MAX_U256;
+        ensures a + b <= MAX_U256 ==> result == a + b;
+    }
+
+    /// Return `a - b`, or zero if `b` is greater than `a`.
+    public fun saturating_sub(a: u256, b: u256): u256 {
+        if (a < b) 0 else a - b
+    }
+    spec saturating_sub {
+        aborts_if false;
+        ensures a < b ==> result == 0;
+        ensures a >= b ==> result == a - b;
+    }
+
+    /// Return `a * b`, or the largest `u256` if the product does not fit in a `u256`.
+    public fun saturating_mul(a: u256, b: u256): u256 {
+        if (a != 0 && b > MAX_U256 / a) MAX_U256 else a * b
+    }
+    spec saturating_mul {
+        aborts_if false;
+        ensures a * b > MAX_U256 ==> result == MAX_U256;
+        ensures a * b <= MAX_U256 ==> result == a * b;
+    }
+
+    /// Return `a + b` modulo `2^256`.
+    public fun wrapping_add(a: u256, b: u256): u256 {
+        if (a > MAX_U256 - b) a - (MAX_U256 - b) - 1 else a + b
+    }
+    spec wrapping_add {
+        aborts_if false;
+        ensures result == (a + b) % (MAX_U256 + 1);
+    }
+
+    /// Return `a - b` modulo `2^256`.
+    public fun wrapping_sub(a: u256, b: u256): u256 {
+        if (a < b) MAX_U256 - (b - a) + 1 else a - b
+    }
+    spec wrapping_sub {
+        aborts_if false;
+        ensures result == (a + MAX_U256 + 1 - b) % (MAX_U256 + 1);
+    }
+
+    /// Return `a * b` modulo `2^256`.
+    public fun wrapping_mul(a: u256, b: u256): u256 {
+        // Split both operands into 128-bit halves. The product of the high halves only
+        // contributes to bits beyond 256, and of the cross products only their low halves
+        // contribute to the result.
+        let (a_lo, a_hi) = (a & MAX_U128, a >> 128);
+        let (b_lo, b_hi) = (b & MAX_U128, b >> 128);
+        let cross = ((a_hi * b_lo) & MAX_U128) + ((a_lo * b_hi) & MAX_U128);
+        wrapping_add(a_lo * b_lo, cross << 128)
+    }
+    spec wrapping_mul {
+        // Non-linear arithmetic over bit operations is out of reach of the solver.
+        pragma verify = false;
+        aborts_if false;
+        ensures result == (a * b) % (MAX_U256 + 1);
+    }
+
+    /// Return `a * b / c`, rounding down. The product is computed without overflow, so
+    /// this only aborts if `c` is zero or if the quotient does not fit in a `u256`.
+    native public fun mul_div(a: u256, b: u256, c: u256): u256;
+    spec mul_div {
+        pragma opaque;
+        aborts_if c == 0 with EDIVISION_BY_ZERO;
+        aborts_if a * b / c > MAX_U256 with EOVERFLOW;
+        ensures result == a * b / c;
+    }
+
+    /// Return `base` raised to the power of `exponent`. Aborts if the result does not fit in
+    /// a `u256`.
+    public fun pow(base: u256, exponent: u8): u256 {
+        let result = 1;
+        let b = base;
+        let e = exponent;
+        while ({
+            spec {
+                invariant result * spec_pow(b, e) == spec_pow(base, exponent);
+            };
+            e > 0
+        }) {
+            if (e % 2 == 1) result = result * b;
+            e = e / 2;
+            // Only square the base if it is needed, as the last square may overflow even if
+            // the result does not
+            if (e > 0) b = b * b;
+        };
+        result
+    }
+    spec pow {
+        pragma opaque;
+        // An intermediate square only overflows if the result does too, but showing that needs
+        // induction over the exponent, so the abort condition is only proved to be sufficient.
+        pragma aborts_if_is_partial;
+        aborts_if spec_pow(base, exponent) > MAX_U256 with EXECUTION_FAILURE;
+        ensures result == spec_pow(base, exponent);
+    }
+
+    /// Return the square root of `x`, rounding down.
+    public fun sqrt(x: u256): u256 {
+        // Binary search for the root in `[low, high)`. `high` starts at `2^128`, whose square
+        // is larger than any `u256`, so `mid * mid` never overflows.
+        let low = 0;
+        let high = 340282366920938463463374607431768211456;
+        while ({
+            spec {
+                invariant low < high && high <= 340282366920938463463374607431768211456;
+                invariant low * low <= x;
+                invariant x < high * high;
+            };
+            high - low > 1
+        }) {
+            let mid = (low + high) / 2;
+            if (mid * mid <= x) low = mid else high = mid;
+        };
+        low
+    }
+    spec sqrt {
+        pragma opaque;
+        aborts_if false;
+        ensures result * result <= x;
+        ensures (result + 1) * (result + 1) > x;
+    }
+
+    /// Return the base 2 logarithm of `x`, rounding down. Aborts if `x` is zero.
+    public fun log2(x: u256): u8 {
+        assert!(x != 0, ELOG2_OF_ZERO);
+        let result = 0;
+        if (x >> 128 != 0) {
+            x = x >> 128;
+            result = result + 128;
+        };
+        if (x >> 64 != 0) {
+            x = x >> 64;
+            result = result + 64;
+        };
+        if (x >> 32 != 0) {
+            x = x >> 32;
+            result = result + 32;
+        };
+        if (x >> 16 != 0) {
+            x = x >> 16;
+            result = result + 16;
+        };
+        if (x >> 8 != 0) {
+            x = x >> 8;
+            result = result + 8;
+        };
+        if (x >> 4 != 0) {
+            x = x >> 4;
+            result = result + 4;
+        };
+        if (x >> 2 != 0) {
+            x = x >> 2;
+            result = result + 2;
+        };
+        if (x >> 1 != 0) result = result + 1;
+        result
+    }
+    spec log2 {
+        pragma opaque;
+        aborts_if x == 0 with ELOG2_OF_ZERO;
+        ensures spec_pow(2, result) <= x;
+        ensures x < spec_pow(2, result + 1);
+    }
+
+    /// Return the smaller of `a` and `b`.
+    public fun min(a: u256, b: u256): u256 {
+        if (a < b) a else b
+    }
+    spec min {
+        aborts_if false;
+        ensures result <= a && result <= b;
+        ensures result == a || result == b;
+    }
+
+    /// Return the larger of `a` and `b`.
+    public fun max(a: u256, b: u256): u256 {
+        if (a > b) a else b
+    }
+    spec max {
+        aborts_if false;
+        ensures result >= a && result >= b;
+        ensures result == a || result == b;
+    }
+
+    /// Return `x` restricted to the range `[lower, upper]`. Aborts if `lower` is greater than
+    /// `upper`.
+    public fun clamp(x: u256, lower: u256, upper: u256): u256 {
+        assert!(lower <= upper, EINVALID_RANGE);
+        if (x < lower) lower else if (x > upper) upper else x
+    }
+    spec clamp {
+        aborts_if lower > upper with EINVALID_RANGE;
+        ensures x < lower ==> result == lower;
+        ensures x > upper ==> result == upper;
+        ensures lower <= x && x <= upper ==> result == x;
+    }
+
+    // ============================================================================================
+    // Helper specification functions
+
+    /// `base` raised to the power of `exponent`, defined by repeated squaring so that each
+    /// iteration of the loop in `pow` unfolds it exactly once.
+    spec fun spec_pow(base: num, exponent: num): num {
+        if (exponent == 0) {
+            1
+        } else if (exponent % 2 == 0) {
+            spec_pow(base * base, exponent / 2)
+        } else {
+            base * spec_pow(base * base, exponent / 2)
+        }
+    }
+
+    spec module {
+        pragma aborts_if_is_strict;
+    }
+}
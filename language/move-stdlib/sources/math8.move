@@ -0,0 +1,257 @@
+/// Math utilities for the `u8` type: checked, saturating and wrapping arithmetic,
+/// `mul_div` with a 128-bit intermediate product, and integer `pow`, `sqrt` and `log2`.
+module std::math8 {
+    use std::option::{Self, Option};
+
+    /// The largest `u8` value
+    const MAX_U8: u8 = 255;
+
+    /// The divisor is zero
+    const EDIVISION_BY_ZERO: u64 = 0x10001;
+    /// The result does not fit in a `u8`
+    const EOVERFLOW: u64 = 0x20002;
+    /// The lower bound is greater than the upper bound
+    const EINVALID_RANGE: u64 = 0x10003;
+    /// The logarithm of zero is undefined
+    const ELOG2_OF_ZERO: u64 = 0x10004;
+
+    /// Return the largest `u8` value.
+    public fun max_value(): u8 {
+        MAX_U8
+    }
+    spec max_value {
+        aborts_if false;
+        ensures result == MAX_U8;
+    }
+
+    /// Return `a + b`, or `none` if the sum does not fit in a `u8`.
+    public fun checked_add(a: u8, b: u8): Option<u8> {
+        if (a > MAX_U8 - b) option::none() else option::some(a + b)
+    }
+    spec checked_add {
+        aborts_if false;
+        ensures a + b > MAX_U8 ==> result == option::spec_none();
+        ensures a + b <= MAX_U8 ==> result == option::spec_some(a + b);
+    }
+
+    /// Return `a - b`, or `none` if `b` is greater than `a`.
+    public fun checked_sub(a: u8, b: u8): Option<u8> {
+        if (a < b) option::none() else option::some(a - b)
+    }
+    spec checked_sub {
+        aborts_if false;
+        ensures a < b ==> result == option::spec_none();
+        ensures a >= b ==> result == option::spec_some(a - b);
+    }
+
+    /// Return `a * b`, or `none` if the product does not fit in a `u8`.
+    public fun checked_mul(a: u8, b: u8): Option<u8> {
+        if (a != 0 && b > MAX_U8 / a) option::none() else option::some(a * b)
+    }
+    spec checked_mul {
+        aborts_if false;
+        ensures a * b > MAX_U8 ==> result == option::spec_none();
+        ensures a * b <= MAX_U8 ==> result == option::spec_some(a * b);
+    }
+
+    /// Return `a + b`, or the largest `u8` if the sum does not fit in a `u8`.
+    public fun saturating_add(a: u8, b: u8): u8 {
+        if (a > MAX_U8 - b) MAX_U8 else a + b
+    }
+    spec saturating_add {
+        aborts_if false;
+        ensures a + b > MAX_U8 ==> result == MAX_U8;
+        ensures a + b <= MAX_U8 ==> result == a + b;
+    }
+
+    /// Return `a - b`, or zero if `b` is greater than `a`.
+    public fun saturating_sub(a: u8, b: u8): u8 {
+        if (a < b) 0 else a - b
+    }
+    spec saturating_sub {
+        aborts_if false;
+        ensures a < b ==> result == 0;
+        ensures a >= b ==> result == a - b;
+    }
+
+    /// Return `a * b`, or the largest `u8` if the product does not fit in a `u8`.
+    public fun saturating_mul(a: u8, b: u8): u8 {
+        if (a != 0 && b > MAX_U8 / a) MAX_U8 else a * b
+    }
+    spec saturating_mul {
+        aborts_if false;
+        ensures a * b > MAX_U8 ==> result == MAX_U8;
+        ensures a * b <= MAX_U8 ==> result == a * b;
+    }
+
+    /// Return `a + b` modulo `2^8`.
+    public fun wrapping_add(a: u8, b: u8): u8 {
+        if (a > MAX_U8 - b) a - (MAX_U8 - b) - 1 else a + b
+    }
+    spec wrapping_add {
+        aborts_if false;
+        ensures result == (a + b) % (MAX_U8 + 1);
+    }
+
+    /// Return `a - b` modulo `2^8`.
+    public fun wrapping_sub(a: u8, b: u8): u8 {
+        if (a < b) MAX_U8 - (b - a) + 1 else a - b
+    }
+    spec wrapping_sub {
+        aborts_if false;
+        ensures result == (a + MAX_U8 + 1 - b) % (MAX_U8 + 1);
+    }
+
+    /// Return `a * b` modulo `2^8`.
+    public fun wrapping_mul(a: u8, b: u8): u8 {
+        (((a as u128) * (b as u128)) % ((MAX_U8 as u128) + 1) as u8)
+    }
+    spec wrapping_mul {
+        aborts_if false;
+        ensures result == (a * b) % (MAX_U8 + 1);
+    }
+
+    /// Return `a * b / c`, rounding down. The product is computed without overflow, so
+    /// this only aborts if `c` is zero or if the quotient does not fit in a `u8`.
+    public fun mul_div(a: u8, b: u8, c: u8): u8 {
+        assert!(c != 0, EDIVISION_BY_ZERO);
+        let result = (a as u128) * (b as u128) / (c as u128);
+        assert!(result <= (MAX_U8 as u128), EOVERFLOW);
+        (result as u8)
+    }
+    spec mul_div {
+        pragma opaque;
+        aborts_if c == 0 with EDIVISION_BY_ZERO;
+        aborts_if a * b / c > MAX_U8 with EOVERFLOW;
+        ensures result == a * b / c;
+    }
+
+    /// Return `base` raised to the power of `exponent`. Aborts if the result does not fit in
+    /// a `u8`.
+    public fun pow(base: u8, exponent: u8): u8 {
+        let result = 1;
+        let b = base;
+        let e = exponent;
+        while ({
+            spec {
+                invariant result * spec_pow(b, e) == spec_pow(base, exponent);
+            };
+            e > 0
+        }) {
+            if (e % 2 == 1) result = result * b;
+            e = e / 2;
+            // Only square the base if it is needed, as the last square may overflow even if
+            // the result does not
+            if (e > 0) b = b * b;
+        };
+        result
+    }
+    spec pow {
+        pragma opaque;
+        // An intermediate square only overflows if the result does too, but showing that needs
+        // induction over the exponent, so the abort condition is only proved to be sufficient.
+        pragma aborts_if_is_partial;
+        aborts_if spec_pow(base, exponent) > MAX_U8 with EXECUTION_FAILURE;
+        ensures result == spec_pow(base, exponent);
+    }
+
+    /// Return the square root of `x`, rounding down.
+    public fun sqrt(x: u8): u8 {
+        // Binary search for the root in `[low, high)`. `high` starts at `2^4`, whose square
+        // is larger than any `u8`, so `mid * mid` never overflows.
+        let low = 0;
+        let high = 16;
+        while ({
+            spec {
+                invariant low < high && high <= 16;
+                invariant low * low <= x;
+                invariant x < high * high;
+            };
+            high - low > 1
+        }) {
+            let mid = (low + high) / 2;
+            if (mid * mid <= x) low = mid else high = mid;
+        };
+        low
+    }
+    spec sqrt {
+        pragma opaque;
+        aborts_if false;
+        ensures result * result <= x;
+        ensures (result + 1) * (result + 1) > x;
+    }
+
+    /// Return the base 2 logarithm of `x`, rounding down. Aborts if `x` is zero.
+    public fun log2(x: u8): u8 {
+        assert!(x != 0, ELOG2_OF_ZERO);
+        let result = 0;
+        if (x >> 4 != 0) {
+            x = x >> 4;
+            result = result + 4;
+        };
+        if (x >> 2 != 0) {
+            x = x >> 2;
+            result = result + 2;
+        };
+        if (x >> 1 != 0) result = result + 1;
+        result
+    }
+    spec log2 {
+        pragma opaque;
+        aborts_if x == 0 with ELOG2_OF_ZERO;
+        ensures spec_pow(2, result) <= x;
+        ensures x < spec_pow(2, result + 1);
+    }
+
+    /// Return the smaller of `a` and `b`.
+    public fun min(a: u8, b: u8): u8 {
+        if (a < b) a else b
+    }
+    spec min {
+        aborts_if false;
+        ensures result <= a && result <= b;
+        ensures result == a || result == b;
+    }
+
+    /// Return the larger of `a` and `b`.
+    public fun max(a: u8, b: u8): u8 {
+        if (a > b) a else b
+    }
+    spec max {
+        aborts_if false;
+        ensures result >= a && result >= b;
+        ensures result == a || result == b;
+    }
+
+    /// Return `x` restricted to the range `[lower, upper]`. Aborts if `lower` is greater than
+    /// `upper`.
+    public fun clamp(x: u8, lower: u8, upper: u8): u8 {
+        assert!(lower <= upper, EINVALID_RANGE);
+        if (x < lower) lower else if (x > upper) upper else x
+    }
+    spec clamp {
+        aborts_if lower > upper with EINVALID_RANGE;
+        ensures x < lower ==> result == lower;
+        ensures x > upper ==> result == upper;
+        ensures lower <= x && x <= upper ==> result == x;
+    }
+
+    // ============================================================================================
+    // Helper specification functions
+
+    /// `base` raised to the power of `exponent`, defined by repeated squaring so that each
+    /// iteration of the loop in `pow` unfolds it exactly once.
+    spec fun spec_pow(base: num, exponent: num): num {
+        if (exponent == 0) {
+            1
+        } else if (exponent % 2 == 0) {
+            spec_pow(base * base, exponent / 2)
+        } else {
+            base * spec_pow(base * base, exponent / 2)
+        }
+    }
+
+    spec module {
+        pragma aborts_if_is_strict;
+    }
+}
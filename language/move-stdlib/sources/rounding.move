@@ -0,0 +1,96 @@
+/// Rounding modes for the `fixed_point64` and `decimal` types, and integer division with an
+/// explicit rounding mode.
+module std::rounding {
+    /// Round towards zero, i.e. truncate
+    const DOWN: u8 = 0;
+    /// Round away from zero
+    const UP: u8 = 1;
+    /// Round to the nearest value, and away from zero if both are equally near
+    const HALF_UP: u8 = 2;
+    /// Round to the nearest value, and to the even one if both are equally near
+    const HALF_EVEN: u8 = 3;
+
+    /// The divisor is zero
+    const EDIVISION_BY_ZERO: u64 = 0x10001;
+
+    /// A rounding mode. Values of this type can only be created by the functions below, so
+    /// every value is one of the four modes.
+    struct RoundingMode has copy, drop, store {
+        mode: u8
+    }
+    spec RoundingMode {
+        invariant mode <= HALF_EVEN;
+    }
+
+    /// Round towards zero, i.e. truncate.
+    public fun down(): RoundingMode {
+        RoundingMode { mode: DOWN }
+    }
+
+    /// Round away from zero.
+    public fun up(): RoundingMode {
+        RoundingMode { mode: UP }
+    }
+
+    /// Round to the nearest value, and away from zero if both are equally near.
+    public fun half_up(): RoundingMode {
+        RoundingMode { mode: HALF_UP }
+    }
+
+    /// Round to the nearest value, and to the even one if both are equally near. This is
+    /// also known as banker's rounding, and avoids the upwards bias of `half_up` when many
+    /// rounded values are summed up.
+    public fun half_even(): RoundingMode {
+        RoundingMode { mode: HALF_EVEN }
+    }
+
+    /// Return `n / d` rounded according to `mode`. Aborts if `d` is zero.
+    public fun div_u256(n: u256, d: u256, mode: RoundingMode): u256 {
+        assert!(d != 0, EDIVISION_BY_ZERO);
+        let quotient = n / d;
+        let remainder = n % d;
+        // A non-zero remainder implies `d > 1`, so incrementing the quotient can not overflow
+        if (remainder == 0 || mode.mode == DOWN) {
+            quotient
+        } else if (mode.mode == UP) {
+            quotient + 1
+        } else {
+            // Compare the remainder against the other part of the divisor instead of doubling
+            // it, which could overflow
+            let rest = d - remainder;
+            if (remainder > rest) {
+                quotient + 1
+            } else if (remainder < rest) {
+                quotient
+            } else if (mode.mode == HALF_UP || quotient % 2 == 1) {
+                quotient + 1
+            } else {
+                quotient
+            }
+        }
+    }
+    spec div_u256 {
+        pragma opaque;
+        aborts_if d == 0 with EDIVISION_BY_ZERO;
+        ensures result == spec_div_u256(n, d, mode);
+    }
+
+    /// The quotient `n / d`, rounded according to `mode`.
+    spec fun spec_div_u256(n: num, d: num, mode: RoundingMode): num {
+        if (mode.mode == DOWN) {
+            n / d
+        } else if (mode.mode == UP) {
+            (n + d - 1) / d
+        } else if (mode.mode == HALF_UP || 2 * (n % d) != d) {
+            (2 * n + d) / (2 * d)
+        } else if ((n / d) % 2 == 0) {
+            n / d
+        } else {
+            n / d + 1
+        }
+    }
+
+    spec module {
+        pragma aborts_if_is_strict;
+    }
+}
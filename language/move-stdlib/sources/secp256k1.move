@@ -0,0 +1,58 @@
+/// ECDSA signatures over the secp256k1 curve, as used by Bitcoin and Ethereum.
+///
+/// Messages are 32-byte hashes, e.g. computed with `hash::keccak256`, and signatures are 64
+/// bytes `r || s`. Signatures must have a low `S`, i.e. `s <= n / 2`, so they are not malleable.
+/// Public keys are 64 bytes `x || y`, the SEC1 uncompressed encoding without its `0x04` prefix;
+/// `ecdsa_verify` also accepts the 33-byte compressed encoding.
+module std::secp256k1 {
+    use std::option::{Self, Option};
+
+    /// Recover the public key that produced `signature` on `message`, where `recovery_id`
+    /// (0 to 3) selects among the candidate keys. Returns none for malformed input and for
+    /// signatures from which no key can be recovered.
+    ///
+    /// A recovered key only proves that the signature is valid for that key; callers must still
+    /// compare it against the expected key or an address derived from it.
+    public fun ecdsa_recover(message: vector<u8>, recovery_id: u8, signature: vector<u8>): Option<vector<u8>> {
+        let (public_key, success) = ecdsa_recover_internal(message, recovery_id, signature);
+        if (success) option::some(public_key) else option::none()
+    }
+    spec ecdsa_recover {
+        aborts_if false;
+        ensures option::is_some(result) == spec_ecdsa_recover_success(message, recovery_id, signature);
+        ensures option::is_some(result) ==>
+            option::borrow(result) == spec_ecdsa_recover(message, recovery_id, signature);
+    }
+
+    /// Return true iff `signature` is a valid signature of `message` by `public_key`. Returns
+    /// false for malformed input.
+    native public fun ecdsa_verify(message: vector<u8>, signature: vector<u8>, public_key: vector<u8>): bool;
+    spec ecdsa_verify {
+        pragma opaque;
+        aborts_if false;
+        ensures result == spec_ecdsa_verify(message, signature, public_key);
+    }
+
+    /// Return the recovered public key and true, or an empty vector and false on failure.
+    native fun ecdsa_recover_internal(message: vector<u8>, recovery_id: u8, signature: vector<u8>): (vector<u8>, bool);
+    spec ecdsa_recover_internal {
+        pragma opaque;
+        aborts_if false;
+        ensures result_2 == spec_ecdsa_recover_success(message, recovery_id, signature);
+        ensures result_2 ==> result_1 == spec_ecdsa_recover(message, recovery_id, signature);
+        ensures result_2 ==> len(result_1) == 64;
+    }
+
+    // **************** SPECIFICATIONS ****************
+
+    spec module {} // switch documentation context to module level
+
+    /// Signature verification and key recovery are modeled with uninterpreted functions.
+    spec module {}
+
+    spec fun spec_ecdsa_recover_success(message: vector<u8>, recovery_id: u8, signature: vector<u8>): bool;
+
+    spec fun spec_ecdsa_recover(message: vector<u8>, recovery_id: u8, signature: vector<u8>): vector<u8>;
+
+    spec fun spec_ecdsa_verify(message: vector<u8>, signature: vector<u8>, public_key: vector<u8>): bool;
+}
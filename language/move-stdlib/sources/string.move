@@ -1,5 +1,6 @@
 /// The `string` module defines the `String` type which represents UTF8 encoded strings.
 module std::string {
+    use std::bcs;
     use std::vector;
     use std::option::{Self, Option};
 
@@ -9,6 +10,19 @@ module std::string {
     /// Index out of range.
     const EINVALID_INDEX: u64 = 2;
 
+    /// A format template has an unmatched brace, or a number of placeholders that differs from
+    /// the number of arguments.
+    const EINVALID_FORMAT: u64 = 3;
+
+    /// A code point that is not a Unicode scalar value.
+    const EINVALID_CHAR: u64 = 4;
+
+    const MAX_U8: u256 = 255;
+    const MAX_U16: u256 = 65535;
+    const MAX_U32: u256 = 4294967295;
+    const MAX_U64: u256 = 18446744073709551615;
+    const MAX_U128: u256 = 340282366920938463463374607431768211455;
+
     /// A `String` holds a sequence of bytes which is guaranteed to be in utf8 format.
     struct String has copy, drop, store {
         bytes: vector<u8>,
@@ -85,10 +99,203 @@ module std::string {
         internal_index_of(&s.bytes, &r.bytes)
     }
 
+    /// Returns the decimal representation of a `u8`.
+    public fun from_u8(n: u8): String {
+        String{bytes: internal_to_decimal((n as u256))}
+    }
+
+    /// Returns the decimal representation of a `u16`.
+    public fun from_u16(n: u16): String {
+        String{bytes: internal_to_decimal((n as u256))}
+    }
+
+    /// Returns the decimal representation of a `u32`.
+    public fun from_u32(n: u32): String {
+        String{bytes: internal_to_decimal((n as u256))}
+    }
+
+    /// Returns the decimal representation of a `u64`.
+    public fun from_u64(n: u64): String {
+        String{bytes: internal_to_decimal((n as u256))}
+    }
+
+    /// Returns the decimal representation of a `u128`.
+    public fun from_u128(n: u128): String {
+        String{bytes: internal_to_decimal((n as u256))}
+    }
+
+    /// Returns the decimal representation of a `u256`.
+    public fun from_u256(n: u256): String {
+        String{bytes: internal_to_decimal(n)}
+    }
+
+    /// Returns the representation of an address as `0x` followed by lowercase hex digits, including
+    /// leading zeros.
+    public fun from_address(a: address): String {
+        let bytes = b"0x";
+        vector::append(&mut bytes, internal_hex_encode(&bcs::to_bytes(&a)));
+        String{bytes}
+    }
+
+    /// Parses a string of decimal digits as a `u8`. Returns `none` if the string contains any
+    /// other character or the number is too large.
+    public fun to_u8(s: &String): Option<u8> {
+        let (n, ok) = internal_parse_decimal(&s.bytes);
+        if (ok && n <= MAX_U8) option::some((n as u8)) else option::none()
+    }
+
+    /// Parses a string of decimal digits as a `u16`. Returns `none` if the string contains any
+    /// other character or the number is too large.
+    public fun to_u16(s: &String): Option<u16> {
+        let (n, ok) = internal_parse_decimal(&s.bytes);
+        if (ok && n <= MAX_U16) option::some((n as u16)) else option::none()
+    }
+
+    /// Parses a string of decimal digits as a `u32`. Returns `none` if the string contains any
+    /// other character or the number is too large.
+    public fun to_u32(s: &String): Option<u32> {
+        let (n, ok) = internal_parse_decimal(&s.bytes);
+        if (ok && n <= MAX_U32) option::some((n as u32)) else option::none()
+    }
+
+    /// Parses a string of decimal digits as a `u64`. Returns `none` if the string contains any
+    /// other character or the number is too large.
+    public fun to_u64(s: &String): Option<u64> {
+        let (n, ok) = internal_parse_decimal(&s.bytes);
+        if (ok && n <= MAX_U64) option::some((n as u64)) else option::none()
+    }
+
+    /// Parses a string of decimal digits as a `u128`. Returns `none` if the string contains any
+    /// other character or the number is too large.
+    public fun to_u128(s: &String): Option<u128> {
+        let (n, ok) = internal_parse_decimal(&s.bytes);
+        if (ok && n <= MAX_U128) option::some((n as u128)) else option::none()
+    }
+
+    /// Parses a string of decimal digits as a `u256`. Returns `none` if the string contains any
+    /// other character or the number is too large.
+    public fun to_u256(s: &String): Option<u256> {
+        let (n, ok) = internal_parse_decimal(&s.bytes);
+        if (ok) option::some(n) else option::none()
+    }
+
+    /// Parses `0x` followed by at most twice the address length hex digits as an address, where
+    /// leading zeros may be omitted. Returns `none` if the string is not of this form.
+    public fun to_address(s: &String): Option<address> {
+        let digits = &s.bytes;
+        let l = vector::length(digits);
+        let width = 2 * vector::length(&bcs::to_bytes(&@0x0));
+        if (l < 3 || l > width + 2 || *vector::borrow(digits, 0) != 0x30 || *vector::borrow(digits, 1) != 0x78) {
+            return option::none()
+        };
+        // Pad to the full width with leading zeros, since hex decoding needs an even length.
+        let padded = vector::empty();
+        let i = l - 2;
+        while (i < width) {
+            vector::push_back(&mut padded, 0x30);
+            i = i + 1;
+        };
+        let i = 2;
+        while (i < l) {
+            vector::push_back(&mut padded, *vector::borrow(digits, i));
+            i = i + 1;
+        };
+        let (bytes, ok) = internal_hex_decode(&padded);
+        if (ok) option::some(bcs::from_bytes(bytes)) else option::none()
+    }
+
+    /// Returns the lowercase hex encoding of `bytes`, without a `0x` prefix.
+    public fun hex_encode(bytes: &vector<u8>): String {
+        String{bytes: internal_hex_encode(bytes)}
+    }
+
+    /// Decodes a string of an even number of hex digits, without a `0x` prefix. Both lowercase and
+    /// uppercase digits are accepted. Returns `none` if the string is not of this form.
+    public fun hex_decode(s: &String): Option<vector<u8>> {
+        let (bytes, ok) = internal_hex_decode(&s.bytes);
+        if (ok) option::some(bytes) else option::none()
+    }
+
+    /// Returns `template` with each `{}` replaced by the next element of `args`, and `{{` and `}}`
+    /// replaced by literal braces. Aborts if a brace is unmatched or the number of `{}` differs from
+    /// the number of arguments.
+    ///
+    /// For example, `format(&utf8(b"{} + {} = {}"), vector[from_u64(1), from_u64(2), from_u64(3)])`
+    /// returns `1 + 2 = 3`.
+    public fun format(template: &String, args: vector<String>): String {
+        let arg_bytes = vector::empty();
+        vector::reverse(&mut args);
+        while (!vector::is_empty(&args)) {
+            let String{bytes} = vector::pop_back(&mut args);
+            vector::push_back(&mut arg_bytes, bytes);
+        };
+        let (bytes, ok) = internal_format(&template.bytes, arg_bytes);
+        assert!(ok, EINVALID_FORMAT);
+        String{bytes}
+    }
+
+    /// Returns the length of this string, in characters. This costs time linear in the length in
+    /// bytes.
+    public fun length_chars(s: &String): u64 {
+        internal_char_count(&s.bytes)
+    }
+
+    /// Returns the code point of the character starting at byte index `i`, and the byte index of
+    /// the next character. The index must be at a valid utf8 char boundary and smaller than the
+    /// length. A string can be iterated character by character as follows:
+    ///
+    /// ```
+    /// let i = 0;
+    /// while (i < string::length(&s)) {
+    ///     let (c, next) = string::next_char(&s, i);
+    ///     ...
+    ///     i = next;
+    /// }
+    /// ```
+    public fun next_char(s: &String, i: u64): (u32, u64) {
+        let bytes = &s.bytes;
+        assert!(i < vector::length(bytes) && internal_is_char_boundary(bytes, i), EINVALID_INDEX);
+        internal_decode_char(bytes, i)
+    }
+
+    /// Returns the code points of the characters of this string.
+    public fun chars(s: &String): vector<u32> {
+        let result = vector::empty();
+        let l = vector::length(&s.bytes);
+        let i = 0;
+        while (i < l) {
+            let (c, next) = internal_decode_char(&s.bytes, i);
+            vector::push_back(&mut result, c);
+            i = next;
+        };
+        result
+    }
+
+    /// Returns the string consisting of the character with code point `c`. Aborts if `c` is not a
+    /// Unicode scalar value, i.e. a surrogate or larger than `0x10FFFF`.
+    public fun from_char(c: u32): String {
+        let (bytes, ok) = internal_encode_char(c);
+        assert!(ok, EINVALID_CHAR);
+        String{bytes}
+    }
+
+    /// Appends the character with code point `c`. Aborts if `c` is not a Unicode scalar value.
+    public fun push_char(s: &mut String, c: u32) {
+        append(s, from_char(c))
+    }
+
 
     // Native API
     native fun internal_check_utf8(v: &vector<u8>): bool;
     native fun internal_is_char_boundary(v: &vector<u8>, i: u64): bool;
     native fun internal_sub_string(v: &vector<u8>, i: u64, j: u64): vector<u8>;
     native fun internal_index_of(v: &vector<u8>, r: &vector<u8>): u64;
+    native fun internal_to_decimal(n: u256): vector<u8>;
+    native fun internal_parse_decimal(v: &vector<u8>): (u256, bool);
+    native fun internal_hex_encode(v: &vector<u8>): vector<u8>;
+    native fun internal_hex_decode(v: &vector<u8>): (vector<u8>, bool);
+    native fun internal_char_count(v: &vector<u8>): u64;
+    native fun internal_decode_char(v: &vector<u8>, i: u64): (u32, u64);
+    native fun internal_encode_char(c: u32): (vector<u8>, bool);
+    native fun internal_format(template: &vector<u8>, args: vector<vector<u8>>): (vector<u8>, bool);
 }
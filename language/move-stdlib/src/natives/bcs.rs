@@ -5,7 +5,10 @@
 use crate::natives::helpers::make_module_natives;
 use move_binary_format::errors::PartialVMResult;
 use move_core_types::{
+    account_address::AccountAddress,
     gas_algebra::{InternalGas, InternalGasPerByte, NumBytes},
+    language_storage::{ModuleId, StructTag},
+    value::{MoveStruct, MoveStructLayout, MoveTypeLayout, MoveValue},
     vm_status::sub_status::NFE_BCS_SERIALIZATION_FAILURE,
 };
 use move_vm_runtime::native_functions::{NativeContext, NativeFunction};
@@ -89,16 +92,189 @@ pub fn make_native_to_bytes(gas_params: ToBytesGasParameters) -> NativeFunction
     )
 }
 
+/***************************************************************************************************
+ * native fun from_bytes
+ *
+ *   gas cost: base_cost + size_of(input) * input_unit_cost
+ *
+ *             If the type has `key`, contains a `signer` or a struct declared outside of the
+ *             calling module other than `String`, or the bytes are not a valid encoding of a
+ *             value of the type, an additional failure_cost is charged.
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct FromBytesGasParameters {
+    pub base: InternalGas,
+    pub per_byte_deserialized: InternalGasPerByte,
+    pub failure: InternalGas,
+}
+
+fn is_string(type_: &StructTag, move_std_addr: AccountAddress) -> bool {
+    type_.address == move_std_addr
+        && type_.module.as_str() == "string"
+        && type_.name.as_str() == "String"
+}
+
+/// Return true if a function in `caller` may create a value of the given layout from bytes.
+/// This holds for primitives, `std::string::String` and vectors of them, and for structs
+/// declared in `caller` whose fields satisfy the same rule. Any other struct, or a `signer`,
+/// could be forged in a state that the module declaring it never allows.
+fn is_deserializable(
+    layout: &MoveTypeLayout,
+    caller: Option<&ModuleId>,
+    move_std_addr: AccountAddress,
+) -> bool {
+    match layout {
+        MoveTypeLayout::Signer => false,
+        MoveTypeLayout::Vector(elem) => is_deserializable(elem, caller, move_std_addr),
+        MoveTypeLayout::Struct(MoveStructLayout::WithTypes { type_, fields }) => {
+            if is_string(type_, move_std_addr) {
+                return true;
+            }
+            let declared_by_caller = match caller {
+                Some(module_id) => {
+                    module_id.address() == &type_.address
+                        && module_id.name() == type_.module.as_ident_str()
+                }
+                None => false,
+            };
+            declared_by_caller
+                && fields
+                    .iter()
+                    .all(|field| is_deserializable(&field.layout, caller, move_std_addr))
+        }
+        // fully annotated layouts always carry the struct type
+        MoveTypeLayout::Struct(_) => false,
+        MoveTypeLayout::Bool
+        | MoveTypeLayout::U8
+        | MoveTypeLayout::U16
+        | MoveTypeLayout::U32
+        | MoveTypeLayout::U64
+        | MoveTypeLayout::U128
+        | MoveTypeLayout::U256
+        | MoveTypeLayout::Address => true,
+    }
+}
+
+/// Return true if every `std::string::String` in the value holds valid UTF-8.
+fn has_valid_strings(value: &MoveValue, move_std_addr: AccountAddress) -> bool {
+    match value {
+        MoveValue::Vector(elems) => elems
+            .iter()
+            .all(|elem| has_valid_strings(elem, move_std_addr)),
+        MoveValue::Struct(MoveStruct::WithTypes { type_, fields }) => {
+            if is_string(type_, move_std_addr) {
+                let bytes = match fields.first() {
+                    Some((_, MoveValue::Vector(bytes))) => bytes
+                        .iter()
+                        .map(|byte| match byte {
+                            MoveValue::U8(byte) => Some(*byte),
+                            _ => None,
+                        })
+                        .collect::<Option<Vec<_>>>(),
+                    _ => None,
+                };
+                match bytes {
+                    Some(bytes) => std::str::from_utf8(&bytes).is_ok(),
+                    None => false,
+                }
+            } else {
+                fields
+                    .iter()
+                    .all(|(_, field)| has_valid_strings(field, move_std_addr))
+            }
+        }
+        _ => true,
+    }
+}
+
+/// Rust implementation of Move's `native public fun from_bytes<T>(vector<u8>): T`
+#[inline]
+fn native_from_bytes(
+    gas_params: &FromBytesGasParameters,
+    context: &mut NativeContext,
+    mut ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+    move_std_addr: AccountAddress,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.len() == 1);
+    debug_assert!(args.len() == 1);
+
+    // pop type and bytes
+    let bytes = pop_arg!(args, Vec<u8>);
+    let arg_type = ty_args.pop().unwrap();
+
+    let mut cost =
+        gas_params.base + gas_params.per_byte_deserialized * NumBytes::new(bytes.len() as u64);
+
+    // values with `key` are resources and must only be created by their defining module
+    if context.type_abilities(&arg_type)?.has_key() {
+        cost += gas_params.failure;
+        return Ok(NativeResult::err(cost, NFE_BCS_SERIALIZATION_FAILURE));
+    }
+
+    // check the type against the caller, and that the strings in the bytes are valid
+    let valid = match context.type_to_fully_annotated_layout(&arg_type)? {
+        Some(layout) if is_deserializable(&layout, context.caller(), move_std_addr) => {
+            match MoveValue::simple_deserialize(&bytes, &layout) {
+                Ok(value) => has_valid_strings(&value, move_std_addr),
+                Err(_) => false,
+            }
+        }
+        _ => false,
+    };
+    if !valid {
+        cost += gas_params.failure;
+        return Ok(NativeResult::err(cost, NFE_BCS_SERIALIZATION_FAILURE));
+    }
+
+    // get type layout and deserialize value
+    let val = match context.type_to_type_layout(&arg_type)? {
+        Some(layout) => Value::simple_deserialize(&bytes, &layout),
+        None => None,
+    };
+    let val = match val {
+        Some(val) => val,
+        None => {
+            cost += gas_params.failure;
+            return Ok(NativeResult::err(cost, NFE_BCS_SERIALIZATION_FAILURE));
+        }
+    };
+
+    Ok(NativeResult::ok(cost, smallvec![val]))
+}
+
+pub fn make_native_from_bytes(
+    gas_params: FromBytesGasParameters,
+    move_std_addr: AccountAddress,
+) -> NativeFunction {
+    Arc::new(
+        move |context, ty_args, args| -> PartialVMResult<NativeResult> {
+            native_from_bytes(&gas_params, context, ty_args, args, move_std_addr)
+        },
+    )
+}
+
 /***************************************************************************************************
  * module
  **************************************************************************************************/
 #[derive(Debug, Clone)]
 pub struct GasParameters {
     pub to_bytes: ToBytesGasParameters,
+    pub from_bytes: FromBytesGasParameters,
 }
 
-pub fn make_all(gas_params: GasParameters) -> impl Iterator<Item = (String, NativeFunction)> {
-    let natives = [("to_bytes", make_native_to_bytes(gas_params.to_bytes))];
+pub fn make_all(
+    gas_params: GasParameters,
+    move_std_addr: AccountAddress,
+) -> impl Iterator<Item = (String, NativeFunction)> {
+    let natives = [
+        ("to_bytes", make_native_to_bytes(gas_params.to_bytes)),
+        (
+            "from_bytes",
+            make_native_from_bytes(gas_params.from_bytes, move_std_addr),
+        ),
+    ];
 
     make_module_natives(natives)
 }
@@ -0,0 +1,370 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Implementation of native functions for BLS signatures over the BLS12-381 curve.
+//!
+//! This follows the proof-of-possession scheme of the
+//! [IETF BLS signature draft](https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05)
+//! with 48-byte public keys in G1 and 96-byte signatures in G2, both compressed. Public keys are
+//! checked to be in the prime-order subgroup and not the identity before they are used.
+
+use crate::natives::helpers::make_module_natives;
+use blst::{
+    min_pk::{PublicKey, Signature},
+    BLST_ERROR,
+};
+use move_binary_format::errors::PartialVMResult;
+use move_core_types::gas_algebra::{
+    InternalGas, InternalGasPerArg, InternalGasPerByte, NumArgs, NumBytes,
+};
+use move_vm_runtime::native_functions::{NativeContext, NativeFunction};
+use move_vm_types::{
+    loaded_data::runtime_types::Type, natives::function::NativeResult, pop_arg, values::Value,
+};
+use smallvec::smallvec;
+use std::{collections::VecDeque, sync::Arc};
+
+/// The domain separation tag for signatures.
+const DST_SIGNATURE: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+/// The domain separation tag for proofs of possession.
+const DST_PROOF_OF_POSSESSION: &[u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// The length of a compressed public key.
+const PUBLIC_KEY_LENGTH: usize = 48;
+/// The length of a compressed signature.
+const SIGNATURE_LENGTH: usize = 96;
+
+/// Deserialize and validate a compressed public key.
+fn deserialize_public_key(bytes: &[u8]) -> Option<PublicKey> {
+    if bytes.len() != PUBLIC_KEY_LENGTH {
+        return None;
+    }
+    PublicKey::key_validate(bytes).ok()
+}
+
+/// Deserialize a compressed signature, checking that it is in the prime-order subgroup.
+fn deserialize_signature(bytes: &[u8]) -> Option<Signature> {
+    if bytes.len() != SIGNATURE_LENGTH {
+        return None;
+    }
+    Signature::sig_validate(bytes, false).ok()
+}
+
+/// Deserialize and validate a vector of public keys, as passed from Move.
+fn deserialize_public_keys(values: Vec<Value>) -> PartialVMResult<Option<Vec<PublicKey>>> {
+    let mut public_keys = Vec::with_capacity(values.len());
+    for value in values {
+        match deserialize_public_key(&value.value_as::<Vec<u8>>()?) {
+            Some(public_key) => public_keys.push(public_key),
+            None => return Ok(None),
+        }
+    }
+    Ok(Some(public_keys))
+}
+
+/***************************************************************************************************
+ * native fun validate_public_key
+ *
+ *   gas cost: base_cost
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct ValidatePublicKeyGasParameters {
+    pub base: InternalGas,
+}
+
+fn native_validate_public_key(
+    gas_params: &ValidatePublicKeyGasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(args.len() == 1);
+
+    let public_key = pop_arg!(args, Vec<u8>);
+    let valid = deserialize_public_key(&public_key).is_some();
+
+    Ok(NativeResult::ok(
+        gas_params.base,
+        smallvec![Value::bool(valid)],
+    ))
+}
+
+pub fn make_native_validate_public_key(
+    gas_params: ValidatePublicKeyGasParameters,
+) -> NativeFunction {
+    Arc::new(
+        move |context, ty_args, args| -> PartialVMResult<NativeResult> {
+            native_validate_public_key(&gas_params, context, ty_args, args)
+        },
+    )
+}
+
+/***************************************************************************************************
+ * native fun verify_proof_of_possession
+ *
+ *   gas cost: base_cost
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct VerifyProofOfPossessionGasParameters {
+    pub base: InternalGas,
+}
+
+fn native_verify_proof_of_possession(
+    gas_params: &VerifyProofOfPossessionGasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(args.len() == 2);
+
+    let proof = pop_arg!(args, Vec<u8>);
+    let public_key = pop_arg!(args, Vec<u8>);
+
+    let verified = match (
+        deserialize_signature(&proof),
+        deserialize_public_key(&public_key),
+    ) {
+        (Some(proof), Some(key)) => {
+            proof.verify(
+                false,
+                &public_key,
+                DST_PROOF_OF_POSSESSION,
+                &[],
+                &key,
+                false,
+            ) == BLST_ERROR::BLST_SUCCESS
+        }
+        _ => false,
+    };
+
+    Ok(NativeResult::ok(
+        gas_params.base,
+        smallvec![Value::bool(verified)],
+    ))
+}
+
+pub fn make_native_verify_proof_of_possession(
+    gas_params: VerifyProofOfPossessionGasParameters,
+) -> NativeFunction {
+    Arc::new(
+        move |context, ty_args, args| -> PartialVMResult<NativeResult> {
+            native_verify_proof_of_possession(&gas_params, context, ty_args, args)
+        },
+    )
+}
+
+/***************************************************************************************************
+ * native fun verify_signature
+ *
+ *   gas cost: base_cost + unit_cost * message_length_in_bytes
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct VerifySignatureGasParameters {
+    pub base: InternalGas,
+    pub per_byte: InternalGasPerByte,
+}
+
+fn native_verify_signature(
+    gas_params: &VerifySignatureGasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(args.len() == 3);
+
+    let message = pop_arg!(args, Vec<u8>);
+    let public_key = pop_arg!(args, Vec<u8>);
+    let signature = pop_arg!(args, Vec<u8>);
+
+    let cost = gas_params.base + gas_params.per_byte * NumBytes::new(message.len() as u64);
+
+    let verified = match (
+        deserialize_signature(&signature),
+        deserialize_public_key(&public_key),
+    ) {
+        (Some(signature), Some(public_key)) => {
+            signature.verify(false, &message, DST_SIGNATURE, &[], &public_key, false)
+                == BLST_ERROR::BLST_SUCCESS
+        }
+        _ => false,
+    };
+
+    Ok(NativeResult::ok(cost, smallvec![Value::bool(verified)]))
+}
+
+pub fn make_native_verify_signature(gas_params: VerifySignatureGasParameters) -> NativeFunction {
+    Arc::new(
+        move |context, ty_args, args| -> PartialVMResult<NativeResult> {
+            native_verify_signature(&gas_params, context, ty_args, args)
+        },
+    )
+}
+
+/***************************************************************************************************
+ * native fun verify_aggregate_signature
+ *
+ *   gas cost: base_cost + per_message_cost * num_messages + unit_cost * total_message_bytes
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct VerifyAggregateSignatureGasParameters {
+    pub base: InternalGas,
+    pub per_message: InternalGasPerArg,
+    pub per_byte: InternalGasPerByte,
+}
+
+fn native_verify_aggregate_signature(
+    gas_params: &VerifyAggregateSignatureGasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(args.len() == 3);
+
+    let messages = pop_arg!(args, Vec<Value>)
+        .into_iter()
+        .map(|value| value.value_as::<Vec<u8>>())
+        .collect::<PartialVMResult<Vec<_>>>()?;
+    let public_keys = pop_arg!(args, Vec<Value>);
+    let signature = pop_arg!(args, Vec<u8>);
+
+    let message_bytes: usize = messages.iter().map(|message| message.len()).sum();
+    let cost = gas_params.base
+        + gas_params.per_message * NumArgs::new(messages.len() as u64)
+        + gas_params.per_byte * NumBytes::new(message_bytes as u64);
+
+    if messages.is_empty() || messages.len() != public_keys.len() {
+        return Ok(NativeResult::ok(cost, smallvec![Value::bool(false)]));
+    }
+
+    let verified = match (
+        deserialize_signature(&signature),
+        deserialize_public_keys(public_keys)?,
+    ) {
+        (Some(signature), Some(public_keys)) => {
+            let messages = messages.iter().map(|m| m.as_slice()).collect::<Vec<_>>();
+            let public_keys = public_keys.iter().collect::<Vec<_>>();
+            signature.aggregate_verify(false, &messages, DST_SIGNATURE, &public_keys, false)
+                == BLST_ERROR::BLST_SUCCESS
+        }
+        _ => false,
+    };
+
+    Ok(NativeResult::ok(cost, smallvec![Value::bool(verified)]))
+}
+
+pub fn make_native_verify_aggregate_signature(
+    gas_params: VerifyAggregateSignatureGasParameters,
+) -> NativeFunction {
+    Arc::new(
+        move |context, ty_args, args| -> PartialVMResult<NativeResult> {
+            native_verify_aggregate_signature(&gas_params, context, ty_args, args)
+        },
+    )
+}
+
+/***************************************************************************************************
+ * native fun verify_multisignature
+ *
+ *   gas cost: base_cost + per_public_key_cost * num_public_keys + unit_cost * message_length_in_bytes
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct VerifyMultisignatureGasParameters {
+    pub base: InternalGas,
+    pub per_public_key: InternalGasPerArg,
+    pub per_byte: InternalGasPerByte,
+}
+
+fn native_verify_multisignature(
+    gas_params: &VerifyMultisignatureGasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(args.len() == 3);
+
+    let message = pop_arg!(args, Vec<u8>);
+    let public_keys = pop_arg!(args, Vec<Value>);
+    let signature = pop_arg!(args, Vec<u8>);
+
+    let cost = gas_params.base
+        + gas_params.per_public_key * NumArgs::new(public_keys.len() as u64)
+        + gas_params.per_byte * NumBytes::new(message.len() as u64);
+
+    if public_keys.is_empty() {
+        return Ok(NativeResult::ok(cost, smallvec![Value::bool(false)]));
+    }
+
+    let verified = match (
+        deserialize_signature(&signature),
+        deserialize_public_keys(public_keys)?,
+    ) {
+        (Some(signature), Some(public_keys)) => {
+            let public_keys = public_keys.iter().collect::<Vec<_>>();
+            signature.fast_aggregate_verify(false, &message, DST_SIGNATURE, &public_keys)
+                == BLST_ERROR::BLST_SUCCESS
+        }
+        _ => false,
+    };
+
+    Ok(NativeResult::ok(cost, smallvec![Value::bool(verified)]))
+}
+
+pub fn make_native_verify_multisignature(
+    gas_params: VerifyMultisignatureGasParameters,
+) -> NativeFunction {
+    Arc::new(
+        move |context, ty_args, args| -> PartialVMResult<NativeResult> {
+            native_verify_multisignature(&gas_params, context, ty_args, args)
+        },
+    )
+}
+
+/***************************************************************************************************
+ * module
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct GasParameters {
+    pub validate_public_key: ValidatePublicKeyGasParameters,
+    pub verify_proof_of_possession: VerifyProofOfPossessionGasParameters,
+    pub verify_signature: VerifySignatureGasParameters,
+    pub verify_aggregate_signature: VerifyAggregateSignatureGasParameters,
+    pub verify_multisignature: VerifyMultisignatureGasParameters,
+}
+
+pub fn make_all(gas_params: GasParameters) -> impl Iterator<Item = (String, NativeFunction)> {
+    let natives = [
+        (
+            "validate_public_key",
+            make_native_validate_public_key(gas_params.validate_public_key),
+        ),
+        (
+            "verify_proof_of_possession",
+            make_native_verify_proof_of_possession(gas_params.verify_proof_of_possession),
+        ),
+        (
+            "verify_signature",
+            make_native_verify_signature(gas_params.verify_signature),
+        ),
+        (
+            "verify_aggregate_signature",
+            make_native_verify_aggregate_signature(gas_params.verify_aggregate_signature),
+        ),
+        (
+            "verify_multisignature",
+            make_native_verify_multisignature(gas_params.verify_multisignature),
+        ),
+    ];
+
+    make_module_natives(natives)
+}
@@ -0,0 +1,161 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Implementation of native functions for Ed25519 signatures as specified in
+//! [RFC8032](https://tools.ietf.org/html/rfc8032).
+//!
+//! Verification is strict: public keys and signature points of small order, and signatures
+//! with a non-canonical `S`, are rejected so that signatures are not malleable.
+
+use crate::natives::helpers::make_module_natives;
+use curve25519_dalek::edwards::CompressedEdwardsY;
+use ed25519_dalek::{PublicKey, Signature, PUBLIC_KEY_LENGTH, SIGNATURE_LENGTH};
+use move_binary_format::errors::PartialVMResult;
+use move_core_types::gas_algebra::{InternalGas, InternalGasPerByte, NumBytes};
+use move_vm_runtime::native_functions::{NativeContext, NativeFunction};
+use move_vm_types::{
+    loaded_data::runtime_types::Type, natives::function::NativeResult, pop_arg, values::Value,
+};
+use smallvec::smallvec;
+use std::{cmp::Ordering, collections::VecDeque, sync::Arc};
+
+/// The order of the Ed25519 base point, in little-endian byte order.
+const L: [u8; 32] = [
+    0xed, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9, 0xde, 0x14,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10,
+];
+
+/// Deserialize a public key, rejecting points that are not on the curve or of small order.
+fn deserialize_public_key(bytes: &[u8]) -> Option<PublicKey> {
+    let bits: [u8; PUBLIC_KEY_LENGTH] = bytes.try_into().ok()?;
+    let point = CompressedEdwardsY(bits).decompress()?;
+    if point.is_small_order() {
+        return None;
+    }
+    PublicKey::from_bytes(bytes).ok()
+}
+
+/// Deserialize a signature, rejecting signatures whose `S` is not smaller than `L`.
+fn deserialize_signature(bytes: &[u8]) -> Option<Signature> {
+    if bytes.len() != SIGNATURE_LENGTH {
+        return None;
+    }
+    for i in (0..32).rev() {
+        match bytes[32 + i].cmp(&L[i]) {
+            Ordering::Less => return Signature::from_bytes(bytes).ok(),
+            Ordering::Greater => return None,
+            Ordering::Equal => (),
+        }
+    }
+    // S == L
+    None
+}
+
+/***************************************************************************************************
+ * native fun validate_public_key
+ *
+ *   gas cost: base_cost
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct ValidatePublicKeyGasParameters {
+    pub base: InternalGas,
+}
+
+fn native_validate_public_key(
+    gas_params: &ValidatePublicKeyGasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(args.len() == 1);
+
+    let public_key = pop_arg!(args, Vec<u8>);
+    let valid = deserialize_public_key(&public_key).is_some();
+
+    Ok(NativeResult::ok(
+        gas_params.base,
+        smallvec![Value::bool(valid)],
+    ))
+}
+
+pub fn make_native_validate_public_key(
+    gas_params: ValidatePublicKeyGasParameters,
+) -> NativeFunction {
+    Arc::new(
+        move |context, ty_args, args| -> PartialVMResult<NativeResult> {
+            native_validate_public_key(&gas_params, context, ty_args, args)
+        },
+    )
+}
+
+/***************************************************************************************************
+ * native fun verify
+ *
+ *   gas cost: base_cost + unit_cost * message_length_in_bytes
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct VerifyGasParameters {
+    pub base: InternalGas,
+    pub per_byte: InternalGasPerByte,
+}
+
+fn native_verify(
+    gas_params: &VerifyGasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(args.len() == 3);
+
+    let message = pop_arg!(args, Vec<u8>);
+    let public_key = pop_arg!(args, Vec<u8>);
+    let signature = pop_arg!(args, Vec<u8>);
+
+    let cost = gas_params.base + gas_params.per_byte * NumBytes::new(message.len() as u64);
+
+    let verified = match (
+        deserialize_signature(&signature),
+        deserialize_public_key(&public_key),
+    ) {
+        (Some(signature), Some(public_key)) => {
+            public_key.verify_strict(&message, &signature).is_ok()
+        }
+        _ => false,
+    };
+
+    Ok(NativeResult::ok(cost, smallvec![Value::bool(verified)]))
+}
+
+pub fn make_native_verify(gas_params: VerifyGasParameters) -> NativeFunction {
+    Arc::new(
+        move |context, ty_args, args| -> PartialVMResult<NativeResult> {
+            native_verify(&gas_params, context, ty_args, args)
+        },
+    )
+}
+
+/***************************************************************************************************
+ * module
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct GasParameters {
+    pub validate_public_key: ValidatePublicKeyGasParameters,
+    pub verify: VerifyGasParameters,
+}
+
+pub fn make_all(gas_params: GasParameters) -> impl Iterator<Item = (String, NativeFunction)> {
+    let natives = [
+        (
+            "validate_public_key",
+            make_native_validate_public_key(gas_params.validate_public_key),
+        ),
+        ("verify", make_native_verify(gas_params.verify)),
+    ];
+
+    make_module_natives(natives)
+}
@@ -3,16 +3,22 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::natives::helpers::make_module_natives;
+use blake2::{
+    digest::{Update, VariableOutput},
+    VarBlake2b,
+};
 use move_binary_format::errors::PartialVMResult;
 use move_core_types::gas_algebra::{InternalGas, InternalGasPerByte, NumBytes};
 use move_vm_runtime::native_functions::{NativeContext, NativeFunction};
 use move_vm_types::{
     loaded_data::runtime_types::Type, natives::function::NativeResult, pop_arg, values::Value,
 };
+use ripemd160::Ripemd160;
 use sha2::{Digest, Sha256};
 use sha3::Sha3_256;
 use smallvec::smallvec;
 use std::{collections::VecDeque, sync::Arc};
+use tiny_keccak::{Hasher, Keccak};
 
 /***************************************************************************************************
  * native fun sha2_256
@@ -108,6 +114,137 @@ pub fn make_native_sha3_256(gas_params: Sha3_256GasParameters) -> NativeFunction
     )
 }
 
+/***************************************************************************************************
+ * native fun keccak256
+ *
+ *   gas cost: base_cost + unit_cost * input_length_in_bytes
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct Keccak256GasParameters {
+    pub base: InternalGas,
+    pub per_byte: InternalGasPerByte,
+}
+
+#[inline]
+fn native_keccak256(
+    gas_params: &Keccak256GasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let hash_arg = pop_arg!(arguments, Vec<u8>);
+
+    let cost = gas_params.base + gas_params.per_byte * NumBytes::new(hash_arg.len() as u64);
+
+    let mut hasher = Keccak::v256();
+    hasher.update(&hash_arg);
+    let mut hash_vec = vec![0u8; 32];
+    hasher.finalize(&mut hash_vec);
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(hash_vec)],
+    ))
+}
+
+pub fn make_native_keccak256(gas_params: Keccak256GasParameters) -> NativeFunction {
+    Arc::new(
+        move |context, ty_args, args| -> PartialVMResult<NativeResult> {
+            native_keccak256(&gas_params, context, ty_args, args)
+        },
+    )
+}
+
+/***************************************************************************************************
+ * native fun blake2b_256
+ *
+ *   gas cost: base_cost + unit_cost * input_length_in_bytes
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct Blake2b256GasParameters {
+    pub base: InternalGas,
+    pub per_byte: InternalGasPerByte,
+}
+
+#[inline]
+fn native_blake2b_256(
+    gas_params: &Blake2b256GasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let hash_arg = pop_arg!(arguments, Vec<u8>);
+
+    let cost = gas_params.base + gas_params.per_byte * NumBytes::new(hash_arg.len() as u64);
+
+    let mut hasher = VarBlake2b::new(32).expect("32 bytes is a valid BLAKE2b output size");
+    hasher.update(&hash_arg);
+    let hash_vec = hasher.finalize_boxed().into_vec();
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(hash_vec)],
+    ))
+}
+
+pub fn make_native_blake2b_256(gas_params: Blake2b256GasParameters) -> NativeFunction {
+    Arc::new(
+        move |context, ty_args, args| -> PartialVMResult<NativeResult> {
+            native_blake2b_256(&gas_params, context, ty_args, args)
+        },
+    )
+}
+
+/***************************************************************************************************
+ * native fun ripemd160
+ *
+ *   gas cost: base_cost + unit_cost * input_length_in_bytes
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct Ripemd160GasParameters {
+    pub base: InternalGas,
+    pub per_byte: InternalGasPerByte,
+}
+
+#[inline]
+fn native_ripemd160(
+    gas_params: &Ripemd160GasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let hash_arg = pop_arg!(arguments, Vec<u8>);
+
+    let cost = gas_params.base + gas_params.per_byte * NumBytes::new(hash_arg.len() as u64);
+
+    let hash_vec = Ripemd160::digest(hash_arg.as_slice()).to_vec();
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(hash_vec)],
+    ))
+}
+
+pub fn make_native_ripemd160(gas_params: Ripemd160GasParameters) -> NativeFunction {
+    Arc::new(
+        move |context, ty_args, args| -> PartialVMResult<NativeResult> {
+            native_ripemd160(&gas_params, context, ty_args, args)
+        },
+    )
+}
+
 /***************************************************************************************************
  * module
  **************************************************************************************************/
@@ -115,12 +252,21 @@ pub fn make_native_sha3_256(gas_params: Sha3_256GasParameters) -> NativeFunction
 pub struct GasParameters {
     pub sha2_256: Sha2_256GasParameters,
     pub sha3_256: Sha3_256GasParameters,
+    pub keccak256: Keccak256GasParameters,
+    pub blake2b_256: Blake2b256GasParameters,
+    pub ripemd160: Ripemd160GasParameters,
 }
 
 pub fn make_all(gas_params: GasParameters) -> impl Iterator<Item = (String, NativeFunction)> {
     let natives = [
         ("sha2_256", make_native_sha2_256(gas_params.sha2_256)),
         ("sha3_256", make_native_sha3_256(gas_params.sha3_256)),
+        ("keccak256", make_native_keccak256(gas_params.keccak256)),
+        (
+            "blake2b_256",
+            make_native_blake2b_256(gas_params.blake2b_256),
+        ),
+        ("ripemd160", make_native_ripemd160(gas_params.ripemd160)),
     ];
 
     make_module_natives(natives)
@@ -0,0 +1,87 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Implementation of native functions for `u256` math that need more than 256 bits.
+
+use crate::natives::helpers::make_module_natives;
+use move_binary_format::errors::PartialVMResult;
+use move_core_types::{gas_algebra::InternalGas, u256::U256};
+use move_vm_runtime::native_functions::{NativeContext, NativeFunction};
+use move_vm_types::{
+    loaded_data::runtime_types::Type, natives::function::NativeResult, pop_arg, values::Value,
+};
+use num::BigUint;
+use smallvec::smallvec;
+use std::{collections::VecDeque, sync::Arc};
+
+/// Abort code when the divisor is zero, matching `std::math256::EDIVISION_BY_ZERO`
+const EDIVISION_BY_ZERO: u64 = 0x10001;
+/// Abort code when the quotient does not fit in a `u256`, matching `std::math256::EOVERFLOW`
+const EOVERFLOW: u64 = 0x20002;
+
+/***************************************************************************************************
+ * native fun mul_div
+ *
+ *   gas cost: base_cost
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct MulDivGasParameters {
+    pub base: InternalGas,
+}
+
+fn to_big(value: U256) -> BigUint {
+    BigUint::from_bytes_le(&value.to_le_bytes())
+}
+
+fn native_mul_div(
+    gas_params: &MulDivGasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(args.len() == 3);
+    let c = pop_arg!(args, U256);
+    let b = pop_arg!(args, U256);
+    let a = pop_arg!(args, U256);
+
+    if c == U256::zero() {
+        return Ok(NativeResult::err(gas_params.base, EDIVISION_BY_ZERO));
+    }
+    // The product has at most 512 bits
+    let quotient = to_big(a) * to_big(b) / to_big(c);
+    if quotient.bits() > 256 {
+        return Ok(NativeResult::err(gas_params.base, EOVERFLOW));
+    }
+    let mut bytes = quotient.to_bytes_le();
+    bytes.resize(32, 0);
+    let result = U256::from_le_bytes(&bytes.try_into().unwrap());
+
+    Ok(NativeResult::ok(
+        gas_params.base,
+        smallvec![Value::u256(result)],
+    ))
+}
+
+pub fn make_native_mul_div(gas_params: MulDivGasParameters) -> NativeFunction {
+    Arc::new(
+        move |context, ty_args, args| -> PartialVMResult<NativeResult> {
+            native_mul_div(&gas_params, context, ty_args, args)
+        },
+    )
+}
+
+/***************************************************************************************************
+ * module
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct GasParameters {
+    pub mul_div: MulDivGasParameters,
+}
+
+pub fn make_all(gas_params: GasParameters) -> impl Iterator<Item = (String, NativeFunction)> {
+    let natives = [("mul_div", make_native_mul_div(gas_params.mul_div))];
+
+    make_module_natives(natives)
+}
@@ -3,9 +3,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod bcs;
+pub mod bls12381;
 pub mod debug;
+pub mod ed25519;
 pub mod event;
 pub mod hash;
+pub mod math256;
+pub mod secp256k1;
 pub mod signer;
 pub mod string;
 pub mod type_name;
@@ -21,7 +25,11 @@ use move_vm_runtime::native_functions::{make_table_from_iter, NativeFunctionTabl
 #[derive(Debug, Clone)]
 pub struct GasParameters {
     pub bcs: bcs::GasParameters,
+    pub bls12381: bls12381::GasParameters,
+    pub ed25519: ed25519::GasParameters,
     pub hash: hash::GasParameters,
+    pub math256: math256::GasParameters,
+    pub secp256k1: secp256k1::GasParameters,
     pub signer: signer::GasParameters,
     pub string: string::GasParameters,
     pub type_name: type_name::GasParameters,
@@ -40,6 +48,38 @@ impl GasParameters {
                     legacy_min_output_size: 0.into(),
                     failure: 0.into(),
                 },
+                from_bytes: bcs::FromBytesGasParameters {
+                    base: 0.into(),
+                    per_byte_deserialized: 0.into(),
+                    failure: 0.into(),
+                },
+            },
+            bls12381: bls12381::GasParameters {
+                validate_public_key: bls12381::ValidatePublicKeyGasParameters { base: 0.into() },
+                verify_proof_of_possession: bls12381::VerifyProofOfPossessionGasParameters {
+                    base: 0.into(),
+                },
+                verify_signature: bls12381::VerifySignatureGasParameters {
+                    base: 0.into(),
+                    per_byte: 0.into(),
+                },
+                verify_aggregate_signature: bls12381::VerifyAggregateSignatureGasParameters {
+                    base: 0.into(),
+                    per_message: 0.into(),
+                    per_byte: 0.into(),
+                },
+                verify_multisignature: bls12381::VerifyMultisignatureGasParameters {
+                    base: 0.into(),
+                    per_public_key: 0.into(),
+                    per_byte: 0.into(),
+                },
+            },
+            ed25519: ed25519::GasParameters {
+                validate_public_key: ed25519::ValidatePublicKeyGasParameters { base: 0.into() },
+                verify: ed25519::VerifyGasParameters {
+                    base: 0.into(),
+                    per_byte: 0.into(),
+                },
             },
 
             hash: hash::GasParameters {
@@ -53,6 +93,25 @@ impl GasParameters {
                     per_byte: 0.into(),
                     legacy_min_input_len: 0.into(),
                 },
+                keccak256: hash::Keccak256GasParameters {
+                    base: 0.into(),
+                    per_byte: 0.into(),
+                },
+                blake2b_256: hash::Blake2b256GasParameters {
+                    base: 0.into(),
+                    per_byte: 0.into(),
+                },
+                ripemd160: hash::Ripemd160GasParameters {
+                    base: 0.into(),
+                    per_byte: 0.into(),
+                },
+            },
+            math256: math256::GasParameters {
+                mul_div: math256::MulDivGasParameters { base: 0.into() },
+            },
+            secp256k1: secp256k1::GasParameters {
+                ecdsa_recover: secp256k1::EcdsaRecoverGasParameters { base: 0.into() },
+                ecdsa_verify: secp256k1::EcdsaVerifyGasParameters { base: 0.into() },
             },
             type_name: type_name::GasParameters {
                 get: type_name::GetGasParameters {
@@ -78,6 +137,32 @@ impl GasParameters {
                     per_byte_pattern: 0.into(),
                     per_byte_searched: 0.into(),
                 },
+                to_decimal: string::ToDecimalGasParameters {
+                    base: 0.into(),
+                    per_byte: 0.into(),
+                },
+                parse_decimal: string::ParseDecimalGasParameters {
+                    base: 0.into(),
+                    per_byte: 0.into(),
+                },
+                hex_encode: string::HexEncodeGasParameters {
+                    base: 0.into(),
+                    per_byte: 0.into(),
+                },
+                hex_decode: string::HexDecodeGasParameters {
+                    base: 0.into(),
+                    per_byte: 0.into(),
+                },
+                char_count: string::CharCountGasParameters {
+                    base: 0.into(),
+                    per_byte: 0.into(),
+                },
+                decode_char: string::DecodeCharGasParameters { base: 0.into() },
+                encode_char: string::EncodeCharGasParameters { base: 0.into() },
+                format: string::FormatGasParameters {
+                    base: 0.into(),
+                    per_byte: 0.into(),
+                },
             },
             vector: vector::GasParameters {
                 empty: vector::EmptyGasParameters { base: 0.into() },
@@ -116,8 +201,12 @@ pub fn all_natives(
         };
     }
 
-    add_natives!("bcs", bcs::make_all(gas_params.bcs));
+    add_natives!("bcs", bcs::make_all(gas_params.bcs, move_std_addr));
+    add_natives!("bls12381", bls12381::make_all(gas_params.bls12381));
+    add_natives!("ed25519", ed25519::make_all(gas_params.ed25519));
     add_natives!("hash", hash::make_all(gas_params.hash));
+    add_natives!("math256", math256::make_all(gas_params.math256));
+    add_natives!("secp256k1", secp256k1::make_all(gas_params.secp256k1));
     add_natives!("signer", signer::make_all(gas_params.signer));
     add_natives!("string", string::make_all(gas_params.string));
     add_natives!("type_name", type_name::make_all(gas_params.type_name));
@@ -0,0 +1,160 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Implementation of native functions for ECDSA signatures over the secp256k1 curve.
+//!
+//! Signatures are 64 bytes `r || s` and must have a low `S`, i.e. `s <= n / 2`, so that they
+//! are not malleable. Public keys are returned as 64 bytes `x || y`, without the `0x04` prefix of
+//! the SEC1 uncompressed encoding.
+
+use crate::natives::helpers::make_module_natives;
+use libsecp256k1::{Message, PublicKey, RecoveryId, Signature};
+use move_binary_format::errors::PartialVMResult;
+use move_core_types::gas_algebra::InternalGas;
+use move_vm_runtime::native_functions::{NativeContext, NativeFunction};
+use move_vm_types::{
+    loaded_data::runtime_types::Type, natives::function::NativeResult, pop_arg, values::Value,
+};
+use smallvec::smallvec;
+use std::{collections::VecDeque, sync::Arc};
+
+/// Deserialize a signature, rejecting signatures with a high `S`.
+fn deserialize_signature(bytes: &[u8]) -> Option<Signature> {
+    let signature = Signature::parse_standard_slice(bytes).ok()?;
+    if signature.s.is_high() {
+        return None;
+    }
+    Some(signature)
+}
+
+/// Deserialize a 64-byte uncompressed or 33-byte compressed public key.
+fn deserialize_public_key(bytes: &[u8]) -> Option<PublicKey> {
+    if bytes.len() != 64 && bytes.len() != 33 {
+        return None;
+    }
+    PublicKey::parse_slice(bytes, None).ok()
+}
+
+/// Recover the public key that signed the 32-byte `message`.
+fn recover(message: &[u8], recovery_id: u8, signature: &[u8]) -> Option<PublicKey> {
+    let message = Message::parse_slice(message).ok()?;
+    let recovery_id = RecoveryId::parse(recovery_id).ok()?;
+    let signature = deserialize_signature(signature)?;
+    libsecp256k1::recover(&message, &signature, &recovery_id).ok()
+}
+
+/***************************************************************************************************
+ * native fun ecdsa_recover_internal
+ *
+ *   gas cost: base_cost
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct EcdsaRecoverGasParameters {
+    pub base: InternalGas,
+}
+
+fn native_ecdsa_recover_internal(
+    gas_params: &EcdsaRecoverGasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(args.len() == 3);
+
+    let signature = pop_arg!(args, Vec<u8>);
+    let recovery_id = pop_arg!(args, u8);
+    let message = pop_arg!(args, Vec<u8>);
+
+    let (public_key, success) = match recover(&message, recovery_id, &signature) {
+        Some(public_key) => (public_key.serialize()[1..].to_vec(), true),
+        None => (vec![], false),
+    };
+    Ok(NativeResult::ok(
+        gas_params.base,
+        smallvec![Value::vector_u8(public_key), Value::bool(success)],
+    ))
+}
+
+pub fn make_native_ecdsa_recover_internal(gas_params: EcdsaRecoverGasParameters) -> NativeFunction {
+    Arc::new(
+        move |context, ty_args, args| -> PartialVMResult<NativeResult> {
+            native_ecdsa_recover_internal(&gas_params, context, ty_args, args)
+        },
+    )
+}
+
+/***************************************************************************************************
+ * native fun ecdsa_verify
+ *
+ *   gas cost: base_cost
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct EcdsaVerifyGasParameters {
+    pub base: InternalGas,
+}
+
+fn native_ecdsa_verify(
+    gas_params: &EcdsaVerifyGasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(args.len() == 3);
+
+    let public_key = pop_arg!(args, Vec<u8>);
+    let signature = pop_arg!(args, Vec<u8>);
+    let message = pop_arg!(args, Vec<u8>);
+
+    let verified = match (
+        Message::parse_slice(&message),
+        deserialize_signature(&signature),
+        deserialize_public_key(&public_key),
+    ) {
+        (Ok(message), Some(signature), Some(public_key)) => {
+            libsecp256k1::verify(&message, &signature, &public_key)
+        }
+        _ => false,
+    };
+
+    Ok(NativeResult::ok(
+        gas_params.base,
+        smallvec![Value::bool(verified)],
+    ))
+}
+
+pub fn make_native_ecdsa_verify(gas_params: EcdsaVerifyGasParameters) -> NativeFunction {
+    Arc::new(
+        move |context, ty_args, args| -> PartialVMResult<NativeResult> {
+            native_ecdsa_verify(&gas_params, context, ty_args, args)
+        },
+    )
+}
+
+/***************************************************************************************************
+ * module
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct GasParameters {
+    pub ecdsa_recover: EcdsaRecoverGasParameters,
+    pub ecdsa_verify: EcdsaVerifyGasParameters,
+}
+
+pub fn make_all(gas_params: GasParameters) -> impl Iterator<Item = (String, NativeFunction)> {
+    let natives = [
+        (
+            "ecdsa_recover_internal",
+            make_native_ecdsa_recover_internal(gas_params.ecdsa_recover),
+        ),
+        (
+            "ecdsa_verify",
+            make_native_ecdsa_verify(gas_params.ecdsa_verify),
+        ),
+    ];
+
+    make_module_natives(natives)
+}
@@ -6,7 +6,10 @@
 
 use crate::natives::helpers::make_module_natives;
 use move_binary_format::errors::PartialVMResult;
-use move_core_types::gas_algebra::{InternalGas, InternalGasPerByte, NumBytes};
+use move_core_types::{
+    gas_algebra::{InternalGas, InternalGasPerByte, NumBytes},
+    u256::U256,
+};
 use move_vm_runtime::native_functions::{NativeContext, NativeFunction};
 use move_vm_types::{
     loaded_data::runtime_types::Type,
@@ -14,6 +17,7 @@ use move_vm_types::{
     pop_arg,
     values::{Value, VectorRef},
 };
+use smallvec::smallvec;
 use std::{collections::VecDeque, sync::Arc};
 
 // The implementation approach delegates all utf8 handling to Rust.
@@ -190,6 +194,370 @@ pub fn make_native_index_of(gas_params: IndexOfGasParameters) -> NativeFunction
     )
 }
 
+/***************************************************************************************************
+ * native fun internal_to_decimal
+ *
+ *   gas cost: base_cost + unit_cost * output_length_in_bytes
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct ToDecimalGasParameters {
+    pub base: InternalGas,
+    pub per_byte: InternalGasPerByte,
+}
+
+fn native_to_decimal(
+    gas_params: &ToDecimalGasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(args.len() == 1);
+    let n = pop_arg!(args, U256);
+    let s = n.to_string();
+
+    let cost = gas_params.base + gas_params.per_byte * NumBytes::new(s.len() as u64);
+    NativeResult::map_partial_vm_result_one(cost, Ok(Value::vector_u8(s.into_bytes())))
+}
+
+pub fn make_native_to_decimal(gas_params: ToDecimalGasParameters) -> NativeFunction {
+    Arc::new(
+        move |context, ty_args, args| -> PartialVMResult<NativeResult> {
+            native_to_decimal(&gas_params, context, ty_args, args)
+        },
+    )
+}
+
+/***************************************************************************************************
+ * native fun internal_parse_decimal
+ *
+ *   gas cost: base_cost + unit_cost * length_in_bytes
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct ParseDecimalGasParameters {
+    pub base: InternalGas,
+    pub per_byte: InternalGasPerByte,
+}
+
+/// Parse a non-empty sequence of decimal digits, returning `None` on any other character or
+/// if the number does not fit into a `u256`.
+fn parse_decimal(bytes: &[u8]) -> Option<U256> {
+    if bytes.is_empty() {
+        return None;
+    }
+    let ten = U256::from(10u8);
+    bytes.iter().try_fold(U256::zero(), |n, byte| {
+        if !byte.is_ascii_digit() {
+            return None;
+        }
+        n.checked_mul(ten)?.checked_add(U256::from(byte - b'0'))
+    })
+}
+
+fn native_parse_decimal(
+    gas_params: &ParseDecimalGasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(args.len() == 1);
+    let s_arg = pop_arg!(args, VectorRef);
+    let s_ref = s_arg.as_bytes_ref();
+    let cost = gas_params.base + gas_params.per_byte * NumBytes::new(s_ref.as_slice().len() as u64);
+
+    let (n, ok) = match parse_decimal(s_ref.as_slice()) {
+        Some(n) => (n, true),
+        None => (U256::zero(), false),
+    };
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::u256(n), Value::bool(ok)],
+    ))
+}
+
+pub fn make_native_parse_decimal(gas_params: ParseDecimalGasParameters) -> NativeFunction {
+    Arc::new(
+        move |context, ty_args, args| -> PartialVMResult<NativeResult> {
+            native_parse_decimal(&gas_params, context, ty_args, args)
+        },
+    )
+}
+
+/***************************************************************************************************
+ * native fun internal_hex_encode
+ *
+ *   gas cost: base_cost + unit_cost * length_in_bytes
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct HexEncodeGasParameters {
+    pub base: InternalGas,
+    pub per_byte: InternalGasPerByte,
+}
+
+fn native_hex_encode(
+    gas_params: &HexEncodeGasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(args.len() == 1);
+    let v_arg = pop_arg!(args, VectorRef);
+    let v_ref = v_arg.as_bytes_ref();
+    let encoded = hex::encode(v_ref.as_slice());
+
+    let cost = gas_params.base + gas_params.per_byte * NumBytes::new(v_ref.as_slice().len() as u64);
+    NativeResult::map_partial_vm_result_one(cost, Ok(Value::vector_u8(encoded.into_bytes())))
+}
+
+pub fn make_native_hex_encode(gas_params: HexEncodeGasParameters) -> NativeFunction {
+    Arc::new(
+        move |context, ty_args, args| -> PartialVMResult<NativeResult> {
+            native_hex_encode(&gas_params, context, ty_args, args)
+        },
+    )
+}
+
+/***************************************************************************************************
+ * native fun internal_hex_decode
+ *
+ *   gas cost: base_cost + unit_cost * length_in_bytes
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct HexDecodeGasParameters {
+    pub base: InternalGas,
+    pub per_byte: InternalGasPerByte,
+}
+
+fn native_hex_decode(
+    gas_params: &HexDecodeGasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(args.len() == 1);
+    let s_arg = pop_arg!(args, VectorRef);
+    let s_ref = s_arg.as_bytes_ref();
+    let cost = gas_params.base + gas_params.per_byte * NumBytes::new(s_ref.as_slice().len() as u64);
+
+    let (decoded, ok) = match hex::decode(s_ref.as_slice()) {
+        Ok(decoded) => (decoded, true),
+        Err(_) => (vec![], false),
+    };
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(decoded), Value::bool(ok)],
+    ))
+}
+
+pub fn make_native_hex_decode(gas_params: HexDecodeGasParameters) -> NativeFunction {
+    Arc::new(
+        move |context, ty_args, args| -> PartialVMResult<NativeResult> {
+            native_hex_decode(&gas_params, context, ty_args, args)
+        },
+    )
+}
+
+/***************************************************************************************************
+ * native fun internal_char_count
+ *
+ *   gas cost: base_cost + unit_cost * length_in_bytes
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct CharCountGasParameters {
+    pub base: InternalGas,
+    pub per_byte: InternalGasPerByte,
+}
+
+fn native_char_count(
+    gas_params: &CharCountGasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(args.len() == 1);
+    let s_arg = pop_arg!(args, VectorRef);
+    let s_ref = s_arg.as_bytes_ref();
+    let s_str = unsafe {
+        // This is safe because we guarantee the bytes to be utf8.
+        std::str::from_utf8_unchecked(s_ref.as_slice())
+    };
+    let count = s_str.chars().count();
+
+    let cost = gas_params.base + gas_params.per_byte * NumBytes::new(s_str.len() as u64);
+    NativeResult::map_partial_vm_result_one(cost, Ok(Value::u64(count as u64)))
+}
+
+pub fn make_native_char_count(gas_params: CharCountGasParameters) -> NativeFunction {
+    Arc::new(
+        move |context, ty_args, args| -> PartialVMResult<NativeResult> {
+            native_char_count(&gas_params, context, ty_args, args)
+        },
+    )
+}
+
+/***************************************************************************************************
+ * native fun internal_decode_char
+ *
+ *   gas cost: base_cost
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct DecodeCharGasParameters {
+    pub base: InternalGas,
+}
+
+fn native_decode_char(
+    gas_params: &DecodeCharGasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(args.len() == 2);
+    let i = pop_arg!(args, u64) as usize;
+    let s_arg = pop_arg!(args, VectorRef);
+    let s_ref = s_arg.as_bytes_ref();
+    let s_str = unsafe {
+        // This is safe because we guarantee the bytes to be utf8.
+        std::str::from_utf8_unchecked(s_ref.as_slice())
+    };
+
+    let c = match s_str.get(i..).and_then(|rest| rest.chars().next()) {
+        Some(c) => c,
+        None => {
+            // TODO: what abort code should we use here?
+            return Ok(NativeResult::err(gas_params.base, 1));
+        }
+    };
+    Ok(NativeResult::ok(
+        gas_params.base,
+        smallvec![Value::u32(c as u32), Value::u64((i + c.len_utf8()) as u64)],
+    ))
+}
+
+pub fn make_native_decode_char(gas_params: DecodeCharGasParameters) -> NativeFunction {
+    Arc::new(
+        move |context, ty_args, args| -> PartialVMResult<NativeResult> {
+            native_decode_char(&gas_params, context, ty_args, args)
+        },
+    )
+}
+
+/***************************************************************************************************
+ * native fun internal_encode_char
+ *
+ *   gas cost: base_cost
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct EncodeCharGasParameters {
+    pub base: InternalGas,
+}
+
+fn native_encode_char(
+    gas_params: &EncodeCharGasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(args.len() == 1);
+    let code_point = pop_arg!(args, u32);
+
+    let (encoded, ok) = match char::from_u32(code_point) {
+        Some(c) => (c.to_string().into_bytes(), true),
+        None => (vec![], false),
+    };
+    Ok(NativeResult::ok(
+        gas_params.base,
+        smallvec![Value::vector_u8(encoded), Value::bool(ok)],
+    ))
+}
+
+pub fn make_native_encode_char(gas_params: EncodeCharGasParameters) -> NativeFunction {
+    Arc::new(
+        move |context, ty_args, args| -> PartialVMResult<NativeResult> {
+            native_encode_char(&gas_params, context, ty_args, args)
+        },
+    )
+}
+
+/***************************************************************************************************
+ * native fun internal_format
+ *
+ *   gas cost: base_cost + unit_cost * (template_length_in_bytes + args_length_in_bytes)
+ *
+ **************************************************************************************************/
+#[derive(Debug, Clone)]
+pub struct FormatGasParameters {
+    pub base: InternalGas,
+    pub per_byte: InternalGasPerByte,
+}
+
+/// Replace each `{}` in `template` with the next element of `args`, where `{{` and `}}` stand for
+/// literal braces. Returns `None` if a brace is unmatched or the number of placeholders differs
+/// from the number of arguments.
+fn format(template: &[u8], args: &[Vec<u8>]) -> Option<Vec<u8>> {
+    let mut result = Vec::with_capacity(template.len());
+    let mut args = args.iter();
+    let mut i = 0;
+    while i < template.len() {
+        match (template[i], template.get(i + 1)) {
+            (b'{', Some(b'{')) | (b'}', Some(b'}')) => result.push(template[i]),
+            (b'{', Some(b'}')) => result.extend_from_slice(args.next()?),
+            (b'{', _) | (b'}', _) => return None,
+            (byte, _) => {
+                result.push(byte);
+                i += 1;
+                continue;
+            }
+        }
+        i += 2;
+    }
+    if args.next().is_some() {
+        return None;
+    }
+    Some(result)
+}
+
+fn native_format(
+    gas_params: &FormatGasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(args.len() == 2);
+    let format_args = pop_arg!(args, Vec<Value>)
+        .into_iter()
+        .map(|value| value.value_as::<Vec<u8>>())
+        .collect::<PartialVMResult<Vec<_>>>()?;
+    let template_arg = pop_arg!(args, VectorRef);
+    let template_ref = template_arg.as_bytes_ref();
+
+    let args_bytes: usize = format_args.iter().map(|arg| arg.len()).sum();
+    let cost = gas_params.base
+        + gas_params.per_byte * NumBytes::new((template_ref.as_slice().len() + args_bytes) as u64);
+
+    let (formatted, ok) = match format(template_ref.as_slice(), &format_args) {
+        Some(formatted) => (formatted, true),
+        None => (vec![], false),
+    };
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(formatted), Value::bool(ok)],
+    ))
+}
+
+pub fn make_native_format(gas_params: FormatGasParameters) -> NativeFunction {
+    Arc::new(
+        move |context, ty_args, args| -> PartialVMResult<NativeResult> {
+            native_format(&gas_params, context, ty_args, args)
+        },
+    )
+}
+
 /***************************************************************************************************
  * module
  **************************************************************************************************/
@@ -199,6 +567,14 @@ pub struct GasParameters {
     pub is_char_boundary: IsCharBoundaryGasParameters,
     pub sub_string: SubStringGasParameters,
     pub index_of: IndexOfGasParameters,
+    pub to_decimal: ToDecimalGasParameters,
+    pub parse_decimal: ParseDecimalGasParameters,
+    pub hex_encode: HexEncodeGasParameters,
+    pub hex_decode: HexDecodeGasParameters,
+    pub char_count: CharCountGasParameters,
+    pub decode_char: DecodeCharGasParameters,
+    pub encode_char: EncodeCharGasParameters,
+    pub format: FormatGasParameters,
 }
 
 pub fn make_all(gas_params: GasParameters) -> impl Iterator<Item = (String, NativeFunction)> {
@@ -219,6 +595,35 @@ pub fn make_all(gas_params: GasParameters) -> impl Iterator<Item = (String, Nati
             "internal_index_of",
             make_native_index_of(gas_params.index_of),
         ),
+        (
+            "internal_to_decimal",
+            make_native_to_decimal(gas_params.to_decimal),
+        ),
+        (
+            "internal_parse_decimal",
+            make_native_parse_decimal(gas_params.parse_decimal),
+        ),
+        (
+            "internal_hex_encode",
+            make_native_hex_encode(gas_params.hex_encode),
+        ),
+        (
+            "internal_hex_decode",
+            make_native_hex_decode(gas_params.hex_decode),
+        ),
+        (
+            "internal_char_count",
+            make_native_char_count(gas_params.char_count),
+        ),
+        (
+            "internal_decode_char",
+            make_native_decode_char(gas_params.decode_char),
+        ),
+        (
+            "internal_encode_char",
+            make_native_encode_char(gas_params.encode_char),
+        ),
+        ("internal_format", make_native_format(gas_params.format)),
     ];
 
     make_module_natives(natives)
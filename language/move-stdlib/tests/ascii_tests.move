@@ -117,4 +117,13 @@ module std::ascii_tests {
             i = i + 1;
         };
     }
+
+    #[test]
+    fun test_case_conversion() {
+        let s = ascii::string(b"Hello, World! 123 @[`{");
+        assert!(ascii::to_uppercase(&s) == ascii::string(b"HELLO, WORLD! 123 @[`{"), 0);
+        assert!(ascii::to_lowercase(&s) == ascii::string(b"hello, world! 123 @[`{"), 1);
+        let empty = ascii::string(b"");
+        assert!(ascii::to_uppercase(&empty) == empty, 2);
+    }
 }
@@ -0,0 +1,145 @@
+#[test_only]
+module std::bcs_reader_tests {
+    use std::bcs;
+    use std::bcs_reader;
+    use std::option;
+    use std::vector;
+
+    #[test]
+    fun peel_integers() {
+        let bytes = bcs::to_bytes(&1u8);
+        vector::append(&mut bytes, bcs::to_bytes(&0x1234u16));
+        vector::append(&mut bytes, bcs::to_bytes(&0x12345678u32));
+        vector::append(&mut bytes, bcs::to_bytes(&18446744073709551615u64));
+        vector::append(&mut bytes, bcs::to_bytes(&0x0102030405060708090a0b0c0d0e0f10u128));
+        vector::append(&mut bytes, bcs::to_bytes(&340282366920938463463374607431768211456u256));
+        let reader = bcs_reader::new(bytes);
+        assert!(bcs_reader::peel_u8(&mut reader) == 1, 0);
+        assert!(bcs_reader::peel_u16(&mut reader) == 0x1234, 1);
+        assert!(bcs_reader::peel_u32(&mut reader) == 0x12345678, 2);
+        assert!(bcs_reader::peel_u64(&mut reader) == 18446744073709551615, 3);
+        assert!(bcs_reader::peel_u128(&mut reader) == 0x0102030405060708090a0b0c0d0e0f10, 4);
+        assert!(bcs_reader::peel_u256(&mut reader) == 340282366920938463463374607431768211456, 5);
+        assert!(bcs_reader::is_empty(&reader), 6);
+    }
+
+    #[test]
+    fun peel_bool_and_address() {
+        let addr = @0x89b9f9d1fadc027cf9532d6f99041522;
+        let bytes = x"0100";
+        vector::append(&mut bytes, bcs::to_bytes(&addr));
+        let reader = bcs_reader::new(bytes);
+        assert!(bcs_reader::peel_bool(&mut reader), 0);
+        assert!(!bcs_reader::peel_bool(&mut reader), 1);
+        assert!(bcs_reader::peel_address(&mut reader) == addr, 2);
+        assert!(bcs_reader::remaining(&reader) == 0, 3);
+    }
+
+    #[test]
+    fun peel_vec_length() {
+        let reader = bcs_reader::new(x"007f8001ffffffff07");
+        assert!(bcs_reader::peel_vec_length(&mut reader) == 0, 0);
+        assert!(bcs_reader::peel_vec_length(&mut reader) == 127, 1);
+        assert!(bcs_reader::peel_vec_length(&mut reader) == 128, 2);
+        assert!(bcs_reader::peel_vec_length(&mut reader) == 2147483647, 3);
+        assert!(bcs_reader::is_empty(&reader), 4);
+    }
+
+    #[test]
+    fun peel_vectors() {
+        let nested = vector::empty();
+        vector::push_back(&mut nested, x"01");
+        vector::push_back(&mut nested, x"");
+        let bools = vector::singleton(true);
+        vector::push_back(&mut bools, false);
+        let u64s = vector::singleton(1u64);
+        vector::push_back(&mut u64s, 2);
+        let u128s = vector::singleton(3u128);
+        let addrs = vector[@0x1, @0x2];
+        let bytes = bcs::to_bytes(&x"abcd");
+        vector::append(&mut bytes, bcs::to_bytes(&nested));
+        vector::append(&mut bytes, bcs::to_bytes(&bools));
+        vector::append(&mut bytes, bcs::to_bytes(&u64s));
+        vector::append(&mut bytes, bcs::to_bytes(&u128s));
+        vector::append(&mut bytes, bcs::to_bytes(&addrs));
+        let reader = bcs_reader::new(bytes);
+        assert!(bcs_reader::peel_vec_u8(&mut reader) == x"abcd", 0);
+        assert!(bcs_reader::peel_vec_vec_u8(&mut reader) == nested, 1);
+        assert!(bcs_reader::peel_vec_bool(&mut reader) == bools, 2);
+        assert!(bcs_reader::peel_vec_u64(&mut reader) == u64s, 3);
+        assert!(bcs_reader::peel_vec_u128(&mut reader) == u128s, 4);
+        assert!(bcs_reader::peel_vec_address(&mut reader) == addrs, 5);
+        assert!(bcs_reader::is_empty(&reader), 6);
+    }
+
+    #[test]
+    fun peel_options() {
+        let bytes = bcs::to_bytes(&option::some(5u64));
+        vector::append(&mut bytes, bcs::to_bytes(&option::none<u64>()));
+        vector::append(&mut bytes, bcs::to_bytes(&option::some(@0x1)));
+        let reader = bcs_reader::new(bytes);
+        assert!(bcs_reader::peel_option_u64(&mut reader) == option::some(5), 0);
+        assert!(bcs_reader::peel_option_u64(&mut reader) == option::none(), 1);
+        assert!(bcs_reader::peel_option_address(&mut reader) == option::some(@0x1), 2);
+        assert!(bcs_reader::is_empty(&reader), 3);
+    }
+
+    #[test]
+    fun into_remainder_bytes() {
+        let reader = bcs_reader::new(x"01020304");
+        bcs_reader::peel_u8(&mut reader);
+        assert!(bcs_reader::into_remainder_bytes(reader) == x"020304", 0);
+        let reader = bcs_reader::new(x"01");
+        bcs_reader::peel_u8(&mut reader);
+        assert!(bcs_reader::into_remainder_bytes(reader) == x"", 1);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x20001, location = std::bcs_reader)]
+    fun peel_u64_out_of_bytes() {
+        let reader = bcs_reader::new(x"01020304");
+        bcs_reader::peel_u64(&mut reader);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x20001, location = std::bcs_reader)]
+    fun peel_vec_u8_out_of_bytes() {
+        let reader = bcs_reader::new(x"030102");
+        bcs_reader::peel_vec_u8(&mut reader);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10002, location = std::bcs_reader)]
+    fun peel_bool_malformed() {
+        let reader = bcs_reader::new(x"02");
+        bcs_reader::peel_bool(&mut reader);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10003, location = std::bcs_reader)]
+    fun peel_vec_length_non_canonical() {
+        let reader = bcs_reader::new(x"8000");
+        bcs_reader::peel_vec_length(&mut reader);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10003, location = std::bcs_reader)]
+    fun peel_vec_length_too_large() {
+        let reader = bcs_reader::new(x"8080808008");
+        bcs_reader::peel_vec_length(&mut reader);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10003, location = std::bcs_reader)]
+    fun peel_vec_length_too_long() {
+        let reader = bcs_reader::new(x"808080808001");
+        bcs_reader::peel_vec_length(&mut reader);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10004, location = std::bcs_reader)]
+    fun peel_option_malformed() {
+        let reader = bcs_reader::new(x"02");
+        bcs_reader::peel_option_tag(&mut reader);
+    }
+}
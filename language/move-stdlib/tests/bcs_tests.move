@@ -1,6 +1,9 @@
 #[test_only]
 module std::bcs_tests {
     use std::bcs;
+    use std::fixed_point32::{Self, FixedPoint32};
+    use std::string::{Self, String};
+    use std::vector;
 
     struct Box<T> has copy, drop, store { x: T }
     struct Box3<T> has copy, drop, store { x: Box<Box<T>> }
@@ -10,6 +13,10 @@ module std::bcs_tests {
     struct Box63<T> has copy, drop, store { x: Box31<Box31<T>> }
     struct Box127<T> has copy, drop, store { x: Box63<Box63<T>> }
 
+    struct Resource has key { x: u64 }
+    struct Pair has copy, drop { a: u8, b: vector<u64> }
+    struct HasSigner has drop { s: signer }
+
     #[test]
     fun bcs_address() {
         let addr = @0x89b9f9d1fadc027cf9532d6f99041522;
@@ -100,4 +107,102 @@ module std::bcs_tests {
     fun encode_129() {
         bcs::to_bytes(&Box { x: box127(true) });
     }
+
+    #[test]
+    fun from_bytes_primitives() {
+        assert!(bcs::from_bytes<bool>(x"01") == true, 0);
+        assert!(bcs::from_bytes<u8>(x"ff") == 255, 1);
+        assert!(bcs::from_bytes<u16>(x"0201") == 258, 2);
+        assert!(bcs::from_bytes<u64>(x"0100000000000000") == 1, 3);
+        assert!(bcs::from_bytes<u256>(bcs::to_bytes(&1u256)) == 1, 4);
+        let addr = @0x89b9f9d1fadc027cf9532d6f99041522;
+        assert!(bcs::from_bytes<address>(bcs::to_bytes(&addr)) == addr, 5);
+    }
+
+    #[test]
+    fun from_bytes_round_trip() {
+        let b = vector::singleton(1);
+        vector::push_back(&mut b, 2);
+        let pair = Pair { a: 7, b };
+        assert!(bcs::from_bytes<Pair>(bcs::to_bytes(&pair)) == pair, 0);
+        assert!(bcs::from_bytes<vector<u8>>(x"020f10") == x"0f10", 1);
+        assert!(bcs::from_bytes<Box3<u64>>(bcs::to_bytes(&box3(42))) == box3(42), 2);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 453)]
+    fun from_bytes_trailing_bytes() {
+        bcs::from_bytes<u8>(x"0102");
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 453)]
+    fun from_bytes_too_short() {
+        bcs::from_bytes<u64>(x"01000000");
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 453)]
+    fun from_bytes_invalid_bool() {
+        bcs::from_bytes<bool>(x"02");
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 453)]
+    fun from_bytes_non_canonical_length() {
+        bcs::from_bytes<vector<u8>>(x"8000");
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 453)]
+    fun from_bytes_key() {
+        let Resource { x: _ } = bcs::from_bytes<Resource>(x"0100000000000000");
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 453)]
+    fun from_bytes_signer() {
+        bcs::from_bytes<signer>(bcs::to_bytes(&@0x1));
+    }
+
+    #[test]
+    fun from_bytes_string() {
+        let s = string::utf8(b"caf\xc3\xa9");
+        assert!(bcs::from_bytes<String>(bcs::to_bytes(&s)) == s, 0);
+        let v = vector::singleton(s);
+        assert!(bcs::from_bytes<vector<String>>(bcs::to_bytes(&v)) == v, 1);
+        let boxed = Box { x: s };
+        assert!(bcs::from_bytes<Box<String>>(bcs::to_bytes(&boxed)) == boxed, 2);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 453)]
+    fun from_bytes_invalid_utf8() {
+        bcs::from_bytes<String>(x"01ff");
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 453)]
+    fun from_bytes_foreign_struct() {
+        bcs::from_bytes<FixedPoint32>(x"0100000000000000");
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 453)]
+    fun from_bytes_foreign_struct_in_own_struct() {
+        let boxed = Box { x: fixed_point32::create_from_raw_value(1) };
+        bcs::from_bytes<Box<FixedPoint32>>(bcs::to_bytes(&boxed));
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 453)]
+    fun from_bytes_signer_in_vector() {
+        bcs::from_bytes<vector<signer>>(x"00");
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 453)]
+    fun from_bytes_signer_in_own_struct() {
+        bcs::from_bytes<HasSigner>(bcs::to_bytes(&@0x1));
+    }
 }
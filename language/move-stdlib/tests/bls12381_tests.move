@@ -0,0 +1,93 @@
+#[test_only]
+module std::bls12381_tests {
+    use std::bls12381;
+    use std::vector;
+
+    // Keys generated from the seeds 0x0101...01, 0x0202...02 and 0x0303...03
+    const PUBLIC_KEY_0: vector<u8> = x"95a254501b7733239ed3cec4d56737977bd09ede881d8a234560e83e5525017add3b1dcc3eabfb85e12a4131b19c253b";
+    const PUBLIC_KEY_1: vector<u8> = x"ac80a5e08c712d5f08f0306ad743f7d8c215d982489b84a1d6ba805733d94c006e8938f9089a75db3ffa135af33bc69a";
+    const PUBLIC_KEY_2: vector<u8> = x"96df714a5cc9ddd2298546dce3d6d3827762a6d5b1c2a91e5ca93c9c898b1b4319cc105c493212a55b63080732ec2249";
+
+    const PROOF_OF_POSSESSION_0: vector<u8> = x"846aa12a4402eb67cb92a497e0716db573c817a4163783153f0ddca475f4870200049d8e9ed35087c786059c1f26fc9d0d39e3098f1bae074c062f84f24353210666bd58c0d9be3ff76ba9dd9ce905c5b602a12e78a04350275faacce8b7137d";
+    const PROOF_OF_POSSESSION_1: vector<u8> = x"b1b22261eeb641b36d4f701f7e5635c5dd0ee53102e7ad8c11594be0d785f0bb5d75bd063ec2caa415e953f85e6e18e110d7ae595d18940e60894bd0a39eb157c1f646ee0f2079d64bd7f4e3c6cbc297e74ce69f3ae4e0728f915f1aac3cdf9b";
+
+    // Signatures of b"hello move"
+    const SIGNATURE_0: vector<u8> = x"8f7ab170c45dc31ec9d9491f4e13dce4262e8d91ebb15c32858f2285f586ab2efa76aa9820b6d782b4368b752068688c12a6a3bb12d5dcf826b2b4dda6d6e3ec251ce87b7f85e5fb21e7aaa6edc5bdeb4fb08f5ebd38ce499cbee6d6b75e0f5b";
+    const MULTISIGNATURE: vector<u8> = x"b7c17d172967398e92e7dc0632200c72ac77460aa84123dc1a2c3dab6c3dd2717d11211099afaadb5a928d89803ad80910d70fe4aa4b8b188baa386f3d04e6b64bf97c7ff0368aeeba7d848220bee52eb70fa2baf9d626cb249b285e9b0ccbc0";
+
+    // Aggregate of the signatures of b"message 0", b"message 1" and b"message 2" by the respective keys
+    const AGGREGATE_SIGNATURE: vector<u8> = x"8409a1db5530e05a72b6bf796c67eb52ac506143a74e91ad0c12ec03033436ab467100450a6e52720a4c1819e05e57b505f6639c1f4f6f494769bcc20144b714c2979305a684433cf0b58fc618bc1d4a7fd7e54fe6a9b3d181b58baf55d75c05";
+
+    // Vectors of byte vectors are built at runtime because they cannot be constants
+    fun public_keys(): vector<vector<u8>> {
+        let public_keys = vector::empty();
+        vector::push_back(&mut public_keys, PUBLIC_KEY_0);
+        vector::push_back(&mut public_keys, PUBLIC_KEY_1);
+        vector::push_back(&mut public_keys, PUBLIC_KEY_2);
+        public_keys
+    }
+
+    fun messages(first: vector<u8>, second: vector<u8>, third: vector<u8>): vector<vector<u8>> {
+        let messages = vector::empty();
+        vector::push_back(&mut messages, first);
+        vector::push_back(&mut messages, second);
+        vector::push_back(&mut messages, third);
+        messages
+    }
+
+    #[test]
+    fun validate_public_key() {
+        assert!(bls12381::validate_public_key(PUBLIC_KEY_0), 0);
+        // The compressed identity point
+        let identity = x"c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000";
+        assert!(!bls12381::validate_public_key(identity), 1);
+        assert!(!bls12381::validate_public_key(x"95a2"), 2);
+    }
+
+    #[test]
+    fun verify_proof_of_possession() {
+        assert!(bls12381::verify_proof_of_possession(PUBLIC_KEY_0, PROOF_OF_POSSESSION_0), 0);
+        assert!(bls12381::verify_proof_of_possession(PUBLIC_KEY_1, PROOF_OF_POSSESSION_1), 1);
+        assert!(!bls12381::verify_proof_of_possession(PUBLIC_KEY_0, PROOF_OF_POSSESSION_1), 2);
+        // A signature of a message is not a proof of possession
+        assert!(!bls12381::verify_proof_of_possession(PUBLIC_KEY_0, SIGNATURE_0), 3);
+    }
+
+    #[test]
+    fun verify_signature() {
+        assert!(bls12381::verify_signature(SIGNATURE_0, PUBLIC_KEY_0, b"hello move"), 0);
+        assert!(!bls12381::verify_signature(SIGNATURE_0, PUBLIC_KEY_1, b"hello move"), 1);
+        assert!(!bls12381::verify_signature(SIGNATURE_0, PUBLIC_KEY_0, b"hello world"), 2);
+        assert!(!bls12381::verify_signature(x"", PUBLIC_KEY_0, b"hello move"), 3);
+    }
+
+    #[test]
+    fun verify_aggregate_signature() {
+        let messages = messages(b"message 0", b"message 1", b"message 2");
+        assert!(bls12381::verify_aggregate_signature(AGGREGATE_SIGNATURE, public_keys(), messages), 0);
+        // The messages must be signed by the corresponding keys
+        let swapped = messages(b"message 1", b"message 0", b"message 2");
+        assert!(!bls12381::verify_aggregate_signature(AGGREGATE_SIGNATURE, public_keys(), swapped), 1);
+        // The numbers of keys and messages must match
+        vector::pop_back(&mut messages);
+        assert!(!bls12381::verify_aggregate_signature(AGGREGATE_SIGNATURE, public_keys(), messages), 2);
+        assert!(!bls12381::verify_aggregate_signature(AGGREGATE_SIGNATURE, vector::empty(), vector::empty()), 3);
+    }
+
+    #[test]
+    fun verify_multisignature() {
+        assert!(bls12381::verify_multisignature(MULTISIGNATURE, public_keys(), b"hello move"), 0);
+        assert!(!bls12381::verify_multisignature(MULTISIGNATURE, public_keys(), b"hello world"), 1);
+        // All keys must have signed
+        let keys = public_keys();
+        vector::pop_back(&mut keys);
+        assert!(!bls12381::verify_multisignature(MULTISIGNATURE, keys, b"hello move"), 2);
+        assert!(!bls12381::verify_multisignature(MULTISIGNATURE, vector::empty(), b"hello move"), 3);
+        // A single signature is a multisignature of one key
+        assert!(bls12381::verify_multisignature(SIGNATURE_0, vector::singleton(PUBLIC_KEY_0), b"hello move"), 4);
+        // An invalid key in the set makes verification fail
+        let keys = public_keys();
+        vector::push_back(&mut keys, x"95a2");
+        assert!(!bls12381::verify_multisignature(MULTISIGNATURE, keys, b"hello move"), 5);
+    }
+}
@@ -0,0 +1,164 @@
+#[test_only]
+module std::decimal_tests {
+    use std::decimal;
+    use std::rounding;
+
+    const MAX_U128: u128 = 340282366920938463463374607431768211455;
+
+    #[test]
+    fun create() {
+        let d = decimal::new(12345, 2); // 123.45
+        assert!(decimal::value(d) == 12345, 0);
+        assert!(decimal::scale(d) == 2, 1);
+        let d = decimal::from_u128(7, 3); // 7.000
+        assert!(decimal::value(d) == 7000, 2);
+        assert!(decimal::is_zero(decimal::zero(18)), 3);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10001)]
+    fun create_invalid_scale() {
+        decimal::new(1, 19);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x20002)]
+    fun from_u128_overflow() {
+        decimal::from_u128(MAX_U128 / 100 + 1, 2);
+    }
+
+    #[test]
+    fun rescale() {
+        let d = decimal::new(12345, 2); // 123.45
+        assert!(decimal::value(decimal::rescale(d, 4, rounding::down())) == 1234500, 0);
+        assert!(decimal::value(decimal::rescale(d, 1, rounding::down())) == 1234, 1);
+        assert!(decimal::value(decimal::rescale(d, 1, rounding::up())) == 1235, 2);
+        assert!(decimal::value(decimal::rescale(d, 1, rounding::half_up())) == 1235, 3);
+        assert!(decimal::value(decimal::rescale(d, 1, rounding::half_even())) == 1234, 4);
+        assert!(decimal::value(decimal::rescale(d, 0, rounding::half_even())) == 123, 5);
+    }
+
+    #[test]
+    fun to_u128() {
+        let d = decimal::new(2500, 3); // 2.5
+        assert!(decimal::to_u128(d, rounding::down()) == 2, 0);
+        assert!(decimal::to_u128(d, rounding::up()) == 3, 1);
+        assert!(decimal::to_u128(d, rounding::half_up()) == 3, 2);
+        assert!(decimal::to_u128(d, rounding::half_even()) == 2, 3);
+    }
+
+    #[test]
+    fun add_and_sub_align_scales() {
+        let a = decimal::new(15, 1); // 1.5
+        let b = decimal::new(25, 2); // 0.25
+        let sum = decimal::add(a, b);
+        assert!(decimal::value(sum) == 175 && decimal::scale(sum) == 2, 0);
+        let difference = decimal::sub(a, b);
+        assert!(decimal::value(difference) == 125 && decimal::scale(difference) == 2, 1);
+        // 0.1 + 0.2 == 0.3, exactly
+        let sum = decimal::add(decimal::new(1, 1), decimal::new(2, 1));
+        assert!(sum == decimal::new(3, 1), 2);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x20002)]
+    fun add_overflow() {
+        decimal::add(decimal::new(MAX_U128, 0), decimal::new(1, 0));
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10003)]
+    fun sub_negative() {
+        decimal::sub(decimal::new(1, 1), decimal::new(11, 2));
+    }
+
+    #[test]
+    fun mul_rounding() {
+        let a = decimal::new(15, 1); // 1.5
+        let b = decimal::new(25, 2); // 0.25
+        // 1.5 * 0.25 = 0.375
+        assert!(decimal::value(decimal::mul(a, b, rounding::down())) == 37, 0);
+        assert!(decimal::value(decimal::mul(a, b, rounding::up())) == 38, 1);
+        assert!(decimal::value(decimal::mul(a, b, rounding::half_up())) == 38, 2);
+        assert!(decimal::value(decimal::mul(a, b, rounding::half_even())) == 38, 3);
+        // 0.5 * 0.5 = 0.25 rounded to one digit
+        let half = decimal::new(5, 1);
+        assert!(decimal::value(decimal::mul(half, half, rounding::half_up())) == 3, 4);
+        assert!(decimal::value(decimal::mul(half, half, rounding::half_even())) == 2, 5);
+        // The full range of the maximum scale does not overflow the intermediate product
+        let one = decimal::from_u128(1, 18);
+        let big = decimal::new(MAX_U128, 18);
+        assert!(decimal::mul(big, one, rounding::down()) == big, 6);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x20002)]
+    fun mul_overflow() {
+        let big = decimal::from_u128(18446744073709551616, 0); // 2^64
+        decimal::mul(big, big, rounding::down());
+    }
+
+    #[test]
+    fun div_rounding() {
+        let one = decimal::from_u128(1, 4);
+        let three = decimal::from_u128(3, 0);
+        // 1 / 3 = 0.3333...
+        assert!(decimal::value(decimal::div(one, three, rounding::down())) == 3333, 0);
+        assert!(decimal::value(decimal::div(one, three, rounding::up())) == 3334, 1);
+        assert!(decimal::value(decimal::div(one, three, rounding::half_up())) == 3333, 2);
+        let two = decimal::from_u128(2, 18);
+        // 2 / 3 with 18 digits
+        let q = decimal::div(two, three, rounding::half_up());
+        assert!(decimal::value(q) == 666666666666666667 && decimal::scale(q) == 18, 3);
+        let big = decimal::new(MAX_U128, 18);
+        assert!(decimal::div(big, decimal::from_u128(1, 18), rounding::down()) == big, 4);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10004)]
+    fun div_by_zero() {
+        decimal::div(decimal::new(1, 0), decimal::zero(2), rounding::down());
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x20002)]
+    fun div_overflow() {
+        decimal::div(decimal::new(MAX_U128, 0), decimal::new(1, 1), rounding::down());
+    }
+
+    #[test]
+    fun pow() {
+        let d = decimal::new(11, 1); // 1.1
+        assert!(decimal::pow(d, 0, rounding::down()) == decimal::new(10, 1), 0);
+        assert!(decimal::pow(d, 1, rounding::down()) == d, 1);
+        // 1.1^2 = 1.21
+        assert!(decimal::value(decimal::pow(d, 2, rounding::down())) == 12, 2);
+        assert!(decimal::value(decimal::pow(d, 2, rounding::up())) == 13, 3);
+        let d = decimal::new(15, 2); // 0.15
+        // 0.15^3 = 0.003375 with intermediate rounding
+        assert!(decimal::value(decimal::pow(decimal::rescale(d, 6, rounding::down()), 3, rounding::down())) == 3375, 4);
+        let ten = decimal::from_u128(10, 0);
+        assert!(decimal::value(decimal::pow(ten, 38, rounding::down())) == 100000000000000000000000000000000000000, 5);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x20002)]
+    fun pow_overflow() {
+        decimal::pow(decimal::from_u128(10, 0), 39, rounding::down());
+    }
+
+    #[test]
+    fun compare_min_max() {
+        let a = decimal::new(15, 1); // 1.5
+        let b = decimal::new(150, 2); // 1.50
+        let c = decimal::new(151, 2); // 1.51
+        assert!(decimal::compare(a, b) == 1, 0);
+        assert!(decimal::compare(a, c) == 0, 1);
+        assert!(decimal::compare(c, a) == 2, 2);
+        assert!(decimal::min(c, a) == a, 3);
+        assert!(decimal::max(a, c) == c, 4);
+        // Values that would overflow when aligned still compare correctly
+        let big = decimal::new(MAX_U128, 0);
+        assert!(decimal::compare(big, decimal::new(1, 18)) == 2, 5);
+    }
+}
@@ -0,0 +1,33 @@
+#[test_only]
+module std::ed25519_tests {
+    use std::ed25519;
+
+    // Test 2 of RFC 8032, section 7.1
+    const PUBLIC_KEY: vector<u8> = x"3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c";
+    const MESSAGE: vector<u8> = x"72";
+    const SIGNATURE: vector<u8> = x"92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00";
+
+    #[test]
+    fun validate_public_key() {
+        assert!(ed25519::validate_public_key(PUBLIC_KEY), 0);
+        // The identity point has small order
+        assert!(!ed25519::validate_public_key(x"0100000000000000000000000000000000000000000000000000000000000000"), 1);
+        assert!(!ed25519::validate_public_key(x"3d4017c3"), 2);
+    }
+
+    #[test]
+    fun verify_rfc8032_vector() {
+        assert!(ed25519::verify(SIGNATURE, PUBLIC_KEY, MESSAGE), 0);
+        assert!(!ed25519::verify(SIGNATURE, PUBLIC_KEY, x"73"), 1);
+        assert!(!ed25519::verify(SIGNATURE, PUBLIC_KEY, x""), 2);
+    }
+
+    #[test]
+    fun verify_rejects_malformed_input() {
+        assert!(!ed25519::verify(x"", PUBLIC_KEY, MESSAGE), 0);
+        assert!(!ed25519::verify(SIGNATURE, x"", MESSAGE), 1);
+        // S = L is not canonical
+        let non_canonical = x"92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69daedd3f55c1a631258d69cf7a2def9de1400000000000000000000000000000010";
+        assert!(!ed25519::verify(non_canonical, PUBLIC_KEY, MESSAGE), 2);
+    }
+}
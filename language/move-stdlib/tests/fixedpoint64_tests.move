@@ -0,0 +1,205 @@
+#[test_only]
+module std::fixed_point64_tests {
+    use std::fixed_point64;
+    use std::rounding;
+
+    const MAX_U128: u128 = 340282366920938463463374607431768211455;
+
+    #[test]
+    #[expected_failure(abort_code = 0x10001)]
+    fun create_div_zero() {
+        // A denominator of zero should cause an arithmetic error.
+        fixed_point64::create_from_rational(2, 0);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x20005)]
+    fun create_overflow() {
+        // The maximum value is 2^64 - 1. Check that anything larger aborts
+        // with an overflow.
+        fixed_point64::create_from_rational(18446744073709551616, 1); // 2^64
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x20005)]
+    fun create_underflow() {
+        // The minimum non-zero value is 2^-64. Check that anything smaller
+        // aborts.
+        fixed_point64::create_from_rational(1, 36893488147419103232); // 2^-65
+    }
+
+    #[test]
+    fun create_zero() {
+        let x = fixed_point64::create_from_rational(0, 1);
+        assert!(fixed_point64::is_zero(x), 0);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10004)]
+    fun divide_by_zero() {
+        // Dividing by zero should cause an arithmetic error.
+        let f = fixed_point64::create_from_raw_value(0);
+        fixed_point64::divide_u128(1, f);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x20002)]
+    fun divide_overflow_small_divisor() {
+        let f = fixed_point64::create_from_raw_value(1); // 2^-64
+        // Divide 2^64 by the minimum fractional value. This should overflow.
+        fixed_point64::divide_u128(18446744073709551616, f);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x20003)]
+    fun multiply_overflow_small_multiplier() {
+        let f = fixed_point64::create_from_rational(3, 2); // 1.5
+        // Multiply the maximum u128 value by 1.5. This should overflow.
+        fixed_point64::multiply_u128(MAX_U128, f);
+    }
+
+    #[test]
+    fun exact_multiply_and_divide() {
+        let f = fixed_point64::create_from_rational(3, 4); // 0.75
+        assert!(fixed_point64::multiply_u128(12, f) == 9, 0);
+        assert!(fixed_point64::divide_u128(9, f) == 12, 1);
+    }
+
+    #[test]
+    fun multiply_truncates() {
+        let f = fixed_point64::create_from_rational(1, 3); // 0.333...
+        // multiply_u128 does NOT round -- it truncates -- so values that
+        // are not perfectly representable in binary may be off by one.
+        assert!(fixed_point64::multiply_u128(9, f) == 2, 0);
+    }
+
+    #[test]
+    fun create_from_rational_max_numerator_denominator() {
+        // Test creating a 1.0 fraction from the maximum u128 value.
+        let f = fixed_point64::create_from_rational(MAX_U128, MAX_U128);
+        assert!(fixed_point64::get_raw_value(f) == 18446744073709551616, 0); // 1.0
+    }
+
+    #[test]
+    fun create_from_u128() {
+        let one = fixed_point64::create_from_u128(1);
+        assert!(fixed_point64::get_raw_value(one) == 18446744073709551616, 0);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x20005)]
+    fun create_from_u128_too_large() {
+        fixed_point64::create_from_u128(18446744073709551616); // 2^64
+    }
+
+    #[test]
+    fun add_and_sub() {
+        let one = fixed_point64::create_from_u128(1);
+        let half = fixed_point64::create_from_rational(1, 2);
+        let sum = fixed_point64::add(one, half);
+        assert!(sum == fixed_point64::create_from_rational(3, 2), 0);
+        assert!(fixed_point64::sub(sum, one) == half, 1);
+        assert!(fixed_point64::is_zero(fixed_point64::sub(half, half)), 2);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x20006)]
+    fun add_overflow() {
+        let max = fixed_point64::create_from_raw_value(MAX_U128);
+        fixed_point64::add(max, fixed_point64::create_from_raw_value(1));
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10007)]
+    fun sub_negative() {
+        let one = fixed_point64::create_from_u128(1);
+        fixed_point64::sub(fixed_point64::create_from_rational(1, 2), one);
+    }
+
+    #[test]
+    fun mul_rounding() {
+        let three_halves = fixed_point64::create_from_rational(3, 2);
+        let product = fixed_point64::mul(three_halves, three_halves, rounding::down());
+        assert!(product == fixed_point64::create_from_rational(9, 4), 0);
+        // 2^-64 * 0.5 = 2^-65, which is exactly half of the smallest fraction
+        let tiny = fixed_point64::create_from_raw_value(1);
+        let half = fixed_point64::create_from_rational(1, 2);
+        assert!(fixed_point64::get_raw_value(fixed_point64::mul(tiny, half, rounding::down())) == 0, 1);
+        assert!(fixed_point64::get_raw_value(fixed_point64::mul(tiny, half, rounding::up())) == 1, 2);
+        assert!(fixed_point64::get_raw_value(fixed_point64::mul(tiny, half, rounding::half_up())) == 1, 3);
+        assert!(fixed_point64::get_raw_value(fixed_point64::mul(tiny, half, rounding::half_even())) == 0, 4);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x20003)]
+    fun mul_overflow() {
+        let big = fixed_point64::create_from_u128(4294967296); // 2^32
+        fixed_point64::mul(big, big, rounding::down());
+    }
+
+    #[test]
+    fun div_rounding() {
+        let one = fixed_point64::create_from_u128(1);
+        let three = fixed_point64::create_from_u128(3);
+        let third_down = fixed_point64::div(one, three, rounding::down());
+        let third_up = fixed_point64::div(one, three, rounding::up());
+        assert!(fixed_point64::get_raw_value(third_down) == 6148914691236517205, 0);
+        assert!(fixed_point64::get_raw_value(third_up) == 6148914691236517206, 1);
+        // 1/3 = 0.0101... in binary, so it is rounded down to nearest
+        assert!(fixed_point64::div(one, three, rounding::half_up()) == third_down, 2);
+        let two = fixed_point64::create_from_u128(2);
+        assert!(fixed_point64::div(three, two, rounding::down()) == fixed_point64::create_from_rational(3, 2), 3);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10004)]
+    fun div_by_zero() {
+        let one = fixed_point64::create_from_u128(1);
+        fixed_point64::div(one, fixed_point64::create_from_raw_value(0), rounding::down());
+    }
+
+    #[test]
+    fun pow() {
+        let two = fixed_point64::create_from_u128(2);
+        let half = fixed_point64::create_from_rational(1, 2);
+        assert!(fixed_point64::pow(two, 0, rounding::down()) == fixed_point64::create_from_u128(1), 0);
+        assert!(fixed_point64::pow(two, 1, rounding::down()) == two, 1);
+        assert!(fixed_point64::pow(two, 10, rounding::down()) == fixed_point64::create_from_u128(1024), 2);
+        assert!(fixed_point64::pow(two, 63, rounding::down()) == fixed_point64::create_from_u128(9223372036854775808), 3);
+        assert!(fixed_point64::pow(half, 3, rounding::down()) == fixed_point64::create_from_rational(1, 8), 4);
+        // 2^-65 is not representable
+        assert!(fixed_point64::is_zero(fixed_point64::pow(half, 65, rounding::down())), 5);
+        assert!(fixed_point64::get_raw_value(fixed_point64::pow(half, 65, rounding::up())) == 1, 6);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x20003)]
+    fun pow_overflow() {
+        fixed_point64::pow(fixed_point64::create_from_u128(2), 64, rounding::down());
+    }
+
+    #[test]
+    fun compare_min_max() {
+        let one = fixed_point64::create_from_u128(1);
+        let two = fixed_point64::create_from_u128(2);
+        assert!(fixed_point64::compare(one, two) == 0, 0);
+        assert!(fixed_point64::compare(one, one) == 1, 1);
+        assert!(fixed_point64::compare(two, one) == 2, 2);
+        assert!(fixed_point64::min(two, one) == one, 3);
+        assert!(fixed_point64::max(one, two) == two, 4);
+    }
+
+    #[test]
+    fun floor_ceil_round() {
+        let three_point_five = fixed_point64::create_from_rational(7, 2);
+        assert!(fixed_point64::floor(three_point_five) == 3, 0);
+        assert!(fixed_point64::ceil(three_point_five) == 4, 1);
+        assert!(fixed_point64::round(three_point_five) == 4, 2);
+        let num = fixed_point64::create_from_rational(499, 1000);
+        assert!(fixed_point64::round(num) == 0, 3);
+        let one = fixed_point64::create_from_u128(1);
+        assert!(fixed_point64::ceil(one) == 1, 4);
+        let max = fixed_point64::create_from_raw_value(MAX_U128);
+        assert!(fixed_point64::ceil(max) == 18446744073709551616, 5);
+    }
+}
@@ -15,4 +15,27 @@ module std::hash_tests {
         let expected_output = x"3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532";
         assert!(hash::sha3_256(input) == expected_output, 0);
     }
+
+    #[test]
+    fun keccak256_expected_hash() {
+        let input = x"616263";
+        let expected_output = x"4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45";
+        assert!(hash::keccak256(input) == expected_output, 0);
+        // Keccak-256 and SHA3-256 differ only in their padding
+        assert!(hash::keccak256(input) != hash::sha3_256(input), 1);
+    }
+
+    #[test]
+    fun blake2b_256_expected_hash() {
+        let input = x"616263";
+        let expected_output = x"bddd813c634239723171ef3fee98579b94964e3bb1cb3e427262c8c068d52319";
+        assert!(hash::blake2b_256(input) == expected_output, 0);
+    }
+
+    #[test]
+    fun ripemd160_expected_hash() {
+        let input = x"616263";
+        let expected_output = x"8eb208f7e05d987a9b044a8e98c6b087f15a0bfc";
+        assert!(hash::ripemd160(input) == expected_output, 0);
+    }
 }
@@ -0,0 +1,198 @@
+#[test_only]
+module std::i128_tests {
+    use std::i128::{Self, I128};
+
+    const MAX: u128 = 170141183460469231731687303715884105727;
+    const MIN_ABS: u128 = 170141183460469231731687303715884105728;
+
+    fun pos(v: u128): I128 {
+        i128::from(v)
+    }
+
+    fun neg(v: u128): I128 {
+        i128::neg_from(v)
+    }
+
+    #[test]
+    fun representation() {
+        assert!(i128::bits(i128::zero()) == 0, 0);
+        assert!(i128::bits(neg(0)) == 0, 1);
+        assert!(i128::bits(neg(1)) == 340282366920938463463374607431768211455, 2);
+        assert!(i128::bits(neg(MIN_ABS)) == MIN_ABS, 3);
+        assert!(i128::bits(pos(MAX)) == MAX, 4);
+        assert!(i128::from_bits(MIN_ABS) == neg(MIN_ABS), 5);
+        assert!(i128::is_neg(neg(1)), 6);
+        assert!(!i128::is_neg(neg(0)), 7);
+        assert!(!i128::is_neg(pos(MAX)), 8);
+        assert!(i128::as_u128(pos(MAX)) == MAX, 9);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x20001)]
+    fun from_overflow() {
+        i128::from(MIN_ABS);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x20001)]
+    fun neg_from_overflow() {
+        i128::neg_from(MIN_ABS + 1);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10003)]
+    fun as_u128_negative() {
+        i128::as_u128(neg(1));
+    }
+
+    #[test]
+    fun neg_and_abs() {
+        assert!(i128::neg(pos(5)) == neg(5), 0);
+        assert!(i128::neg(neg(5)) == pos(5), 1);
+        assert!(i128::neg(i128::zero()) == i128::zero(), 2);
+        assert!(i128::neg(pos(MAX)) == neg(MAX), 3);
+        assert!(i128::abs(neg(7)) == pos(7), 4);
+        assert!(i128::abs(pos(7)) == pos(7), 5);
+        assert!(i128::abs_u128(neg(MIN_ABS)) == MIN_ABS, 6);
+        assert!(i128::abs_u128(pos(MAX)) == MAX, 7);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x20001)]
+    fun neg_min() {
+        i128::neg(neg(MIN_ABS));
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x20001)]
+    fun abs_min() {
+        i128::abs(neg(MIN_ABS));
+    }
+
+    #[test]
+    fun add() {
+        assert!(i128::add(pos(2), pos(3)) == pos(5), 0);
+        assert!(i128::add(pos(2), neg(3)) == neg(1), 1);
+        assert!(i128::add(neg(2), pos(3)) == pos(1), 2);
+        assert!(i128::add(neg(2), neg(3)) == neg(5), 3);
+        assert!(i128::add(pos(MAX), neg(MIN_ABS)) == neg(1), 4);
+        assert!(i128::add(neg(MIN_ABS - 1), neg(1)) == neg(MIN_ABS), 5);
+        assert!(i128::add(pos(MAX - 1), pos(1)) == pos(MAX), 6);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x20001)]
+    fun add_overflow() {
+        i128::add(pos(MAX), pos(1));
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x20001)]
+    fun add_underflow() {
+        i128::add(neg(MIN_ABS), neg(1));
+    }
+
+    #[test]
+    fun sub() {
+        assert!(i128::sub(pos(2), pos(3)) == neg(1), 0);
+        assert!(i128::sub(pos(2), neg(3)) == pos(5), 1);
+        assert!(i128::sub(neg(2), pos(3)) == neg(5), 2);
+        assert!(i128::sub(neg(2), neg(3)) == pos(1), 3);
+        assert!(i128::sub(neg(1), pos(MAX)) == neg(MIN_ABS), 4);
+        assert!(i128::sub(pos(0), pos(MAX)) == neg(MAX), 5);
+        assert!(i128::sub(neg(MIN_ABS), neg(MIN_ABS)) == i128::zero(), 6);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x20001)]
+    fun sub_overflow() {
+        i128::sub(pos(0), neg(MIN_ABS));
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x20001)]
+    fun sub_underflow() {
+        i128::sub(neg(2), pos(MAX));
+    }
+
+    #[test]
+    fun mul() {
+        assert!(i128::mul(pos(6), pos(7)) == pos(42), 0);
+        assert!(i128::mul(pos(6), neg(7)) == neg(42), 1);
+        assert!(i128::mul(neg(6), neg(7)) == pos(42), 2);
+        assert!(i128::mul(neg(6), i128::zero()) == i128::zero(), 3);
+        assert!(i128::mul(neg(MIN_ABS), pos(1)) == neg(MIN_ABS), 4);
+        assert!(i128::mul(pos(MIN_ABS / 2), neg(2)) == neg(MIN_ABS), 5);
+        assert!(i128::mul(pos(MAX), neg(1)) == neg(MAX), 6);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x20001)]
+    fun mul_overflow() {
+        i128::mul(pos(MIN_ABS / 2), pos(2));
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x20001)]
+    fun mul_min_by_minus_one() {
+        i128::mul(neg(MIN_ABS), neg(1));
+    }
+
+    #[test]
+    fun div_and_rem() {
+        assert!(i128::div(pos(7), pos(2)) == pos(3), 0);
+        assert!(i128::div(neg(7), pos(2)) == neg(3), 1);
+        assert!(i128::div(pos(7), neg(2)) == neg(3), 2);
+        assert!(i128::div(neg(7), neg(2)) == pos(3), 3);
+        assert!(i128::rem(pos(7), pos(2)) == pos(1), 4);
+        assert!(i128::rem(neg(7), pos(2)) == neg(1), 5);
+        assert!(i128::rem(pos(7), neg(2)) == pos(1), 6);
+        assert!(i128::rem(neg(7), neg(2)) == neg(1), 7);
+        assert!(i128::div(neg(MIN_ABS), pos(1)) == neg(MIN_ABS), 8);
+        assert!(i128::rem(neg(MIN_ABS), neg(1)) == i128::zero(), 9);
+        let a = neg(12345);
+        let b = pos(67);
+        assert!(i128::add(i128::mul(i128::div(a, b), b), i128::rem(a, b)) == a, 10);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10002)]
+    fun div_by_zero() {
+        i128::div(pos(1), i128::zero());
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10002)]
+    fun rem_by_zero() {
+        i128::rem(pos(1), i128::zero());
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x20001)]
+    fun div_min_by_minus_one() {
+        i128::div(neg(MIN_ABS), neg(1));
+    }
+
+    #[test]
+    fun compare() {
+        assert!(i128::compare(neg(1), pos(1)) == 0, 0);
+        assert!(i128::compare(pos(1), pos(1)) == 1, 1);
+        assert!(i128::compare(pos(1), neg(1)) == 2, 2);
+        assert!(i128::compare(neg(2), neg(1)) == 0, 3);
+        assert!(i128::compare(neg(MIN_ABS), pos(MAX)) == 0, 4);
+        assert!(i128::lt(neg(2), neg(1)), 5);
+        assert!(!i128::lt(neg(1), neg(1)), 6);
+        assert!(i128::le(neg(1), neg(1)), 7);
+        assert!(i128::gt(pos(0), neg(1)), 8);
+        assert!(i128::ge(pos(0), pos(0)), 9);
+        assert!(!i128::ge(neg(MIN_ABS), pos(0)), 10);
+    }
+
+    #[test]
+    fun min_max() {
+        assert!(i128::min(neg(3), pos(2)) == neg(3), 0);
+        assert!(i128::min(pos(3), pos(2)) == pos(2), 1);
+        assert!(i128::max(neg(3), pos(2)) == pos(2), 2);
+        assert!(i128::max(neg(3), neg(2)) == neg(2), 3);
+    }
+}
@@ -0,0 +1,174 @@
+#[test_only]
+module std::math128_tests {
+    use std::option;
+    use std::math128;
+
+    const MAX: u128 = 340282366920938463463374607431768211455;
+
+    #[test]
+    fun checked() {
+        assert!(math128::checked_add(MAX - 1, 1) == option::some(MAX), 0);
+        assert!(option::is_none(&math128::checked_add(MAX, 1)), 1);
+        assert!(math128::checked_sub(1, 1) == option::some(0), 2);
+        assert!(option::is_none(&math128::checked_sub(0, 1)), 3);
+        assert!(math128::checked_mul(MAX, 1) == option::some(MAX), 4);
+        assert!(math128::checked_mul(0, MAX) == option::some(0), 5);
+        assert!(option::is_none(&math128::checked_mul(MAX / 2 + 1, 2)), 6);
+    }
+
+    #[test]
+    fun saturating() {
+        assert!(math128::saturating_add(MAX, 1) == MAX, 0);
+        assert!(math128::saturating_add(1, 2) == 3, 1);
+        assert!(math128::saturating_sub(0, 1) == 0, 2);
+        assert!(math128::saturating_sub(3, 2) == 1, 3);
+        assert!(math128::saturating_mul(MAX, 2) == MAX, 4);
+        assert!(math128::saturating_mul(3, 5) == 15, 5);
+    }
+
+    #[test]
+    fun wrapping() {
+        assert!(math128::wrapping_add(MAX, 1) == 0, 0);
+        assert!(math128::wrapping_add(MAX, MAX) == MAX - 1, 1);
+        assert!(math128::wrapping_add(1, 2) == 3, 2);
+        assert!(math128::wrapping_sub(0, 1) == MAX, 3);
+        assert!(math128::wrapping_sub(1, MAX) == 2, 4);
+        assert!(math128::wrapping_sub(3, 2) == 1, 5);
+        assert!(math128::wrapping_mul(MAX, MAX) == 1, 6);
+        assert!(math128::wrapping_mul(MAX, 2) == MAX - 1, 7);
+        assert!(math128::wrapping_mul(18446744073709551616, 18446744073709551616) == 0, 8);
+        assert!(math128::wrapping_mul(3, 5) == 15, 9);
+    }
+
+    #[test]
+    fun mul_div() {
+        assert!(math128::mul_div(MAX, MAX, MAX) == MAX, 0);
+        assert!(math128::mul_div(MAX, 3, 4) == MAX / 4 * 3 + 2, 1);
+        assert!(math128::mul_div(7, 3, 2) == 10, 2);
+        assert!(math128::mul_div(0, MAX, 1) == 0, 3);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10001)]
+    fun mul_div_by_zero() {
+        math128::mul_div(1, 1, 0);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x20002)]
+    fun mul_div_overflow() {
+        math128::mul_div(MAX, 2, 1);
+    }
+
+    #[test]
+    fun pow() {
+        assert!(math128::pow(0, 0) == 1, 0);
+        assert!(math128::pow(MAX, 0) == 1, 1);
+        assert!(math128::pow(MAX, 1) == MAX, 2);
+        assert!(math128::pow(3, 4) == 81, 3);
+        assert!(math128::pow(2, 127) == 1 << 127, 4);
+        assert!(math128::pow(0, 255) == 0, 5);
+        assert!(math128::pow(1, 255) == 1, 6);
+    }
+
+    #[test]
+    #[expected_failure(arithmetic_error, location = std::math128)]
+    fun pow_overflow() {
+        math128::pow(MAX, 2);
+    }
+
+    #[test]
+    fun sqrt() {
+        assert!(math128::sqrt(0) == 0, 0);
+        assert!(math128::sqrt(1) == 1, 1);
+        assert!(math128::sqrt(3) == 1, 2);
+        assert!(math128::sqrt(4) == 2, 3);
+        assert!(math128::sqrt(99) == 9, 4);
+        assert!(math128::sqrt(MAX) == 18446744073709551615, 5);
+        assert!(math128::sqrt(340282366920938463426481119284349108225) == 18446744073709551615, 6);
+        assert!(math128::sqrt(340282366920938463426481119284349108224) == 18446744073709551614, 7);
+    }
+
+    #[test]
+    fun log2() {
+        assert!(math128::log2(1) == 0, 0);
+        assert!(math128::log2(2) == 1, 1);
+        assert!(math128::log2(3) == 1, 2);
+        assert!(math128::log2(MAX) == 127, 3);
+        assert!(math128::log2(1 << 127) == 127, 4);
+        assert!(math128::log2((1 << 127) - 1) == 126, 5);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10004)]
+    fun log2_of_zero() {
+        math128::log2(0);
+    }
+
+    #[test]
+    fun min_max_clamp() {
+        assert!(math128::min(1, 2) == 1, 0);
+        assert!(math128::max(1, 2) == 2, 1);
+        assert!(math128::max_value() == MAX, 2);
+        assert!(math128::clamp(0, 1, 3) == 1, 3);
+        assert!(math128::clamp(2, 1, 3) == 2, 4);
+        assert!(math128::clamp(MAX, 1, 3) == 3, 5);
+        assert!(math128::clamp(1, 1, 1) == 1, 6);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10003)]
+    fun clamp_invalid_range() {
+        math128::clamp(1, 3, 2);
+    }
+
+    #[test]
+    fun sqrt_around_largest_squares() {
+        assert!(math128::sqrt(340282366920938463389587631136930004996) == 18446744073709551614, 0);
+        assert!(math128::sqrt(340282366920938463389587631136930004995) == 18446744073709551613, 1);
+        assert!(math128::sqrt(1 << 127) == 13043817825332782212, 2);
+    }
+
+    #[test]
+    fun log2_around_every_power_of_two() {
+        let log: u8 = 1;
+        while (log < 128) {
+            assert!(math128::log2(1 << log) == log, (log as u64));
+            assert!(math128::log2((1 << log) - 1) == log - 1, (log as u64));
+            log = log + 1;
+        };
+    }
+
+    #[test]
+    fun mul_div_near_max() {
+        assert!(math128::mul_div(MAX - 1, MAX - 1, MAX) == MAX - 2, 0);
+        assert!(math128::mul_div(MAX, MAX - 1, MAX - 1) == MAX, 1);
+        // The product only fits in the 256-bit intermediate
+        assert!(math128::mul_div(1 << 64, 1 << 64, 2) == 1 << 127, 2);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x20002)]
+    fun mul_div_overflow_by_one() {
+        math128::mul_div(MAX, MAX, MAX - 1);
+    }
+
+    #[test]
+    fun pow_largest_powers() {
+        assert!(math128::pow(3, 80) == 147808829414345923316083210206383297601, 0);
+        assert!(math128::pow(18446744073709551615, 2) == 340282366920938463426481119284349108225, 1);
+        assert!(math128::pow(2, 127) == 1 << 127, 2);
+    }
+
+    #[test]
+    #[expected_failure(arithmetic_error, location = std::math128)]
+    fun pow_overflow_by_one_factor() {
+        math128::pow(3, 81);
+    }
+
+    #[test]
+    #[expected_failure(arithmetic_error, location = std::math128)]
+    fun pow_overflow_of_square() {
+        math128::pow(1 << 64, 2);
+    }
+}
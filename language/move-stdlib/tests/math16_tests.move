@@ -0,0 +1,177 @@
+#[test_only]
+module std::math16_tests {
+    use std::option;
+    use std::math16;
+
+    const MAX: u16 = 65535;
+
+    #[test]
+    fun checked() {
+        assert!(math16::checked_add(MAX - 1, 1) == option::some(MAX), 0);
+        assert!(option::is_none(&math16::checked_add(MAX, 1)), 1);
+        assert!(math16::checked_sub(1, 1) == option::some(0), 2);
+        assert!(option::is_none(&math16::checked_sub(0, 1)), 3);
+        assert!(math16::checked_mul(MAX, 1) == option::some(MAX), 4);
+        assert!(math16::checked_mul(0, MAX) == option::some(0), 5);
+        assert!(option::is_none(&math16::checked_mul(MAX / 2 + 1, 2)), 6);
+    }
+
+    #[test]
+    fun saturating() {
+        assert!(math16::saturating_add(MAX, 1) == MAX, 0);
+        assert!(math16::saturating_add(1, 2) == 3, 1);
+        assert!(math16::saturating_sub(0, 1) == 0, 2);
+        assert!(math16::saturating_sub(3, 2) == 1, 3);
+        assert!(math16::saturating_mul(MAX, 2) == MAX, 4);
+        assert!(math16::saturating_mul(3, 5) == 15, 5);
+    }
+
+    #[test]
+    fun wrapping() {
+        assert!(math16::wrapping_add(MAX, 1) == 0, 0);
+        assert!(math16::wrapping_add(MAX, MAX) == MAX - 1, 1);
+        assert!(math16::wrapping_add(1, 2) == 3, 2);
+        assert!(math16::wrapping_sub(0, 1) == MAX, 3);
+        assert!(math16::wrapping_sub(1, MAX) == 2, 4);
+        assert!(math16::wrapping_sub(3, 2) == 1, 5);
+        assert!(math16::wrapping_mul(MAX, MAX) == 1, 6);
+        assert!(math16::wrapping_mul(MAX, 2) == MAX - 1, 7);
+        assert!(math16::wrapping_mul(256, 256) == 0, 8);
+        assert!(math16::wrapping_mul(3, 5) == 15, 9);
+    }
+
+    #[test]
+    fun mul_div() {
+        assert!(math16::mul_div(MAX, MAX, MAX) == MAX, 0);
+        assert!(math16::mul_div(MAX, 3, 4) == MAX / 4 * 3 + 2, 1);
+        assert!(math16::mul_div(7, 3, 2) == 10, 2);
+        assert!(math16::mul_div(0, MAX, 1) == 0, 3);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10001)]
+    fun mul_div_by_zero() {
+        math16::mul_div(1, 1, 0);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x20002)]
+    fun mul_div_overflow() {
+        math16::mul_div(MAX, 2, 1);
+    }
+
+    #[test]
+    fun pow() {
+        assert!(math16::pow(0, 0) == 1, 0);
+        assert!(math16::pow(MAX, 0) == 1, 1);
+        assert!(math16::pow(MAX, 1) == MAX, 2);
+        assert!(math16::pow(3, 4) == 81, 3);
+        assert!(math16::pow(2, 15) == 1 << 15, 4);
+        assert!(math16::pow(0, 255) == 0, 5);
+        assert!(math16::pow(1, 255) == 1, 6);
+    }
+
+    #[test]
+    #[expected_failure(arithmetic_error, location = std::math16)]
+    fun pow_overflow() {
+        math16::pow(MAX, 2);
+    }
+
+    #[test]
+    fun sqrt() {
+        assert!(math16::sqrt(0) == 0, 0);
+        assert!(math16::sqrt(1) == 1, 1);
+        assert!(math16::sqrt(3) == 1, 2);
+        assert!(math16::sqrt(4) == 2, 3);
+        assert!(math16::sqrt(99) == 9, 4);
+        assert!(math16::sqrt(MAX) == 255, 5);
+        assert!(math16::sqrt(65025) == 255, 6);
+        assert!(math16::sqrt(65024) == 254, 7);
+    }
+
+    #[test]
+    fun log2() {
+        assert!(math16::log2(1) == 0, 0);
+        assert!(math16::log2(2) == 1, 1);
+        assert!(math16::log2(3) == 1, 2);
+        assert!(math16::log2(MAX) == 15, 3);
+        assert!(math16::log2(1 << 15) == 15, 4);
+        assert!(math16::log2((1 << 15) - 1) == 14, 5);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10004)]
+    fun log2_of_zero() {
+        math16::log2(0);
+    }
+
+    #[test]
+    fun min_max_clamp() {
+        assert!(math16::min(1, 2) == 1, 0);
+        assert!(math16::max(1, 2) == 2, 1);
+        assert!(math16::max_value() == MAX, 2);
+        assert!(math16::clamp(0, 1, 3) == 1, 3);
+        assert!(math16::clamp(2, 1, 3) == 2, 4);
+        assert!(math16::clamp(MAX, 1, 3) == 3, 5);
+        assert!(math16::clamp(1, 1, 1) == 1, 6);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10003)]
+    fun clamp_invalid_range() {
+        math16::clamp(1, 3, 2);
+    }
+
+    #[test]
+    fun sqrt_around_every_square() {
+        let root: u16 = 1;
+        while (root <= 255) {
+            assert!(math16::sqrt(root * root) == root, (root as u64));
+            assert!(math16::sqrt(root * root - 1) == root - 1, (root as u64));
+            root = root + 1;
+        };
+    }
+
+    #[test]
+    fun log2_around_every_power_of_two() {
+        let log: u8 = 1;
+        while (log < 16) {
+            assert!(math16::log2(1 << log) == log, (log as u64));
+            assert!(math16::log2((1 << log) - 1) == log - 1, (log as u64));
+            log = log + 1;
+        };
+    }
+
+    #[test]
+    fun mul_div_near_max() {
+        assert!(math16::mul_div(MAX - 1, MAX - 1, MAX) == MAX - 2, 0);
+        assert!(math16::mul_div(MAX, MAX - 1, MAX - 1) == MAX, 1);
+        // The product only fits in the 128-bit intermediate
+        assert!(math16::mul_div(256, 256, 2) == 32768, 2);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x20002)]
+    fun mul_div_overflow_by_one() {
+        math16::mul_div(MAX, MAX, MAX - 1);
+    }
+
+    #[test]
+    fun pow_largest_powers() {
+        assert!(math16::pow(3, 10) == 59049, 0);
+        assert!(math16::pow(255, 2) == 65025, 1);
+        assert!(math16::pow(2, 15) == 32768, 2);
+    }
+
+    #[test]
+    #[expected_failure(arithmetic_error, location = std::math16)]
+    fun pow_overflow_by_one_factor() {
+        math16::pow(3, 11);
+    }
+
+    #[test]
+    #[expected_failure(arithmetic_error, location = std::math16)]
+    fun pow_overflow_of_square() {
+        math16::pow(256, 2);
+    }
+}
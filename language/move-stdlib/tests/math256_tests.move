@@ -0,0 +1,184 @@
+#[test_only]
+module std::math256_tests {
+    use std::option;
+    use std::math256;
+
+    const MAX: u256 = 115792089237316195423570985008687907853269984665640564039457584007913129639935;
+
+    #[test]
+    fun checked() {
+        assert!(math256::checked_add(MAX - 1, 1) == option::some(MAX), 0);
+        assert!(option::is_none(&math256::checked_add(MAX, 1)), 1);
+        assert!(math256::checked_sub(1, 1) == option::some(0), 2);
+        assert!(option::is_none(&math256::checked_sub(0, 1)), 3);
+        assert!(math256::checked_mul(MAX, 1) == option::some(MAX), 4);
+        assert!(math256::checked_mul(0, MAX) == option::some(0), 5);
+        assert!(option::is_none(&math256::checked_mul(MAX / 2 + 1, 2)), 6);
+    }
+
+    #[test]
+    fun saturating() {
+        assert!(math256::saturating_add(MAX, 1) == MAX, 0);
+        assert!(math256::saturating_add(1, 2) == 3, 1);
+        assert!(math256::saturating_sub(0, 1) == 0, 2);
+        assert!(math256::saturating_sub(3, 2) == 1, 3);
+        assert!(math256::saturating_mul(MAX, 2) == MAX, 4);
+        assert!(math256::saturating_mul(3, 5) == 15, 5);
+    }
+
+    #[test]
+    fun wrapping() {
+        assert!(math256::wrapping_add(MAX, 1) == 0, 0);
+        assert!(math256::wrapping_add(MAX, MAX) == MAX - 1, 1);
+        assert!(math256::wrapping_add(1, 2) == 3, 2);
+        assert!(math256::wrapping_sub(0, 1) == MAX, 3);
+        assert!(math256::wrapping_sub(1, MAX) == 2, 4);
+        assert!(math256::wrapping_sub(3, 2) == 1, 5);
+        assert!(math256::wrapping_mul(MAX, MAX) == 1, 6);
+        assert!(math256::wrapping_mul(MAX, 2) == MAX - 1, 7);
+        assert!(math256::wrapping_mul(340282366920938463463374607431768211456, 340282366920938463463374607431768211456) == 0, 8);
+        assert!(math256::wrapping_mul(3, 5) == 15, 9);
+    }
+
+    #[test]
+    fun mul_div() {
+        assert!(math256::mul_div(MAX, MAX, MAX) == MAX, 0);
+        assert!(math256::mul_div(MAX, 3, 4) == MAX / 4 * 3 + 2, 1);
+        assert!(math256::mul_div(7, 3, 2) == 10, 2);
+        assert!(math256::mul_div(0, MAX, 1) == 0, 3);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10001)]
+    fun mul_div_by_zero() {
+        math256::mul_div(1, 1, 0);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x20002)]
+    fun mul_div_overflow() {
+        math256::mul_div(MAX, 2, 1);
+    }
+
+    #[test]
+    fun pow() {
+        assert!(math256::pow(0, 0) == 1, 0);
+        assert!(math256::pow(MAX, 0) == 1, 1);
+        assert!(math256::pow(MAX, 1) == MAX, 2);
+        assert!(math256::pow(3, 4) == 81, 3);
+        assert!(math256::pow(2, 255) == 1 << 255, 4);
+        assert!(math256::pow(0, 255) == 0, 5);
+        assert!(math256::pow(1, 255) == 1, 6);
+    }
+
+    #[test]
+    #[expected_failure(arithmetic_error, location = std::math256)]
+    fun pow_overflow() {
+        math256::pow(MAX, 2);
+    }
+
+    #[test]
+    fun sqrt() {
+        assert!(math256::sqrt(0) == 0, 0);
+        assert!(math256::sqrt(1) == 1, 1);
+        assert!(math256::sqrt(3) == 1, 2);
+        assert!(math256::sqrt(4) == 2, 3);
+        assert!(math256::sqrt(99) == 9, 4);
+        assert!(math256::sqrt(MAX) == 340282366920938463463374607431768211455, 5);
+        assert!(math256::sqrt(115792089237316195423570985008687907852589419931798687112530834793049593217025) == 340282366920938463463374607431768211455, 6);
+        assert!(math256::sqrt(115792089237316195423570985008687907852589419931798687112530834793049593217024) == 340282366920938463463374607431768211454, 7);
+    }
+
+    #[test]
+    fun log2() {
+        assert!(math256::log2(1) == 0, 0);
+        assert!(math256::log2(2) == 1, 1);
+        assert!(math256::log2(3) == 1, 2);
+        assert!(math256::log2(MAX) == 255, 3);
+        assert!(math256::log2(1 << 255) == 255, 4);
+        assert!(math256::log2((1 << 255) - 1) == 254, 5);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10004)]
+    fun log2_of_zero() {
+        math256::log2(0);
+    }
+
+    #[test]
+    fun min_max_clamp() {
+        assert!(math256::min(1, 2) == 1, 0);
+        assert!(math256::max(1, 2) == 2, 1);
+        assert!(math256::max_value() == MAX, 2);
+        assert!(math256::clamp(0, 1, 3) == 1, 3);
+        assert!(math256::clamp(2, 1, 3) == 2, 4);
+        assert!(math256::clamp(MAX, 1, 3) == 3, 5);
+        assert!(math256::clamp(1, 1, 1) == 1, 6);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10003)]
+    fun clamp_invalid_range() {
+        math256::clamp(1, 3, 2);
+    }
+
+    #[test]
+    fun wrapping_mul_across_halves() {
+        // (2^128 + 1)^2 = 2^256 + 2^129 + 1
+        assert!(math256::wrapping_mul((1 << 128) + 1, (1 << 128) + 1) == (1 << 129) + 1, 0);
+        // -1 * 2^128 = 2^256 - 2^128
+        assert!(math256::wrapping_mul(MAX, 1 << 128) == MAX - (1 << 128) + 1, 1);
+        assert!(math256::wrapping_mul(1 << 255, 2) == 0, 2);
+    }
+
+    #[test]
+    fun sqrt_around_largest_squares() {
+        assert!(math256::sqrt(115792089237316195423570985008687907851908855197956810185604085578186056794116) == 340282366920938463463374607431768211454, 0);
+        assert!(math256::sqrt(115792089237316195423570985008687907851908855197956810185604085578186056794115) == 340282366920938463463374607431768211453, 1);
+        assert!(math256::sqrt(1 << 255) == 240615969168004511545033772477625056927, 2);
+    }
+
+    #[test]
+    fun log2_around_every_power_of_two() {
+        let log: u8 = 1;
+        while (log < 255) {
+            assert!(math256::log2(1 << log) == log, (log as u64));
+            assert!(math256::log2((1 << log) - 1) == log - 1, (log as u64));
+            log = log + 1;
+        };
+        assert!(math256::log2(1 << 255) == 255, 255);
+    }
+
+    #[test]
+    fun mul_div_near_max() {
+        assert!(math256::mul_div(MAX - 1, MAX - 1, MAX) == MAX - 2, 0);
+        assert!(math256::mul_div(MAX, MAX - 1, MAX - 1) == MAX, 1);
+        // The product needs the full 512 bits of the native implementation
+        assert!(math256::mul_div(1 << 128, 1 << 128, 2) == 1 << 255, 2);
+        assert!(math256::mul_div(MAX, 1 << 128, 1 << 129) == MAX / 2, 3);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x20002)]
+    fun mul_div_overflow_by_one() {
+        math256::mul_div(MAX, MAX, MAX - 1);
+    }
+
+    #[test]
+    fun pow_largest_powers() {
+        assert!(math256::pow(3, 161) == 65542350158517637872691969508970705427701150314738255642438471845988797065603, 0);
+        assert!(math256::pow(340282366920938463463374607431768211455, 2) == 115792089237316195423570985008687907852589419931798687112530834793049593217025, 1);
+    }
+
+    #[test]
+    #[expected_failure(arithmetic_error, location = std::math256)]
+    fun pow_overflow_by_one_factor() {
+        math256::pow(3, 162);
+    }
+
+    #[test]
+    #[expected_failure(arithmetic_error, location = std::math256)]
+    fun pow_overflow_of_square() {
+        math256::pow(1 << 128, 2);
+    }
+}
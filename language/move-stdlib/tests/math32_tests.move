@@ -0,0 +1,175 @@
+#[test_only]
+module std::math32_tests {
+    use std::option;
+    use std::math32;
+
+    const MAX: u32 = 4294967295;
+
+    #[test]
+    fun checked() {
+        assert!(math32::checked_add(MAX - 1, 1) == option::some(MAX), 0);
+        assert!(option::is_none(&math32::checked_add(MAX, 1)), 1);
+        assert!(math32::checked_sub(1, 1) == option::some(0), 2);
+        assert!(option::is_none(&math32::checked_sub(0, 1)), 3);
+        assert!(math32::checked_mul(MAX, 1) == option::some(MAX), 4);
+        assert!(math32::checked_mul(0, MAX) == option::some(0), 5);
+        assert!(option::is_none(&math32::checked_mul(MAX / 2 + 1, 2)), 6);
+    }
+
+    #[test]
+    fun saturating() {
+        assert!(math32::saturating_add(MAX, 1) == MAX, 0);
+        assert!(math32::saturating_add(1, 2) == 3, 1);
+        assert!(math32::saturating_sub(0, 1) == 0, 2);
+        assert!(math32::saturating_sub(3, 2) == 1, 3);
+        assert!(math32::saturating_mul(MAX, 2) == MAX, 4);
+        assert!(math32::saturating_mul(3, 5) == 15, 5);
+    }
+
+    #[test]
+    fun wrapping() {
+        assert!(math32::wrapping_add(MAX, 1) == 0, 0);
+        assert!(math32::wrapping_add(MAX, MAX) == MAX - 1, 1);
+        assert!(math32::wrapping_add(1, 2) == 3, 2);
+        assert!(math32::wrapping_sub(0, 1) == MAX, 3);
+        assert!(math32::wrapping_sub(1, MAX) == 2, 4);
+        assert!(math32::wrapping_sub(3, 2) == 1, 5);
+        assert!(math32::wrapping_mul(MAX, MAX) == 1, 6);
+        assert!(math32::wrapping_mul(MAX, 2) == MAX - 1, 7);
+        assert!(math32::wrapping_mul(65536, 65536) == 0, 8);
+        assert!(math32::wrapping_mul(3, 5) == 15, 9);
+    }
+
+    #[test]
+    fun mul_div() {
+        assert!(math32::mul_div(MAX, MAX, MAX) == MAX, 0);
+        assert!(math32::mul_div(MAX, 3, 4) == MAX / 4 * 3 + 2, 1);
+        assert!(math32::mul_div(7, 3, 2) == 10, 2);
+        assert!(math32::mul_div(0, MAX, 1) == 0, 3);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10001)]
+    fun mul_div_by_zero() {
+        math32::mul_div(1, 1, 0);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x20002)]
+    fun mul_div_overflow() {
+        math32::mul_div(MAX, 2, 1);
+    }
+
+    #[test]
+    fun pow() {
+        assert!(math32::pow(0, 0) == 1, 0);
+        assert!(math32::pow(MAX, 0) == 1, 1);
+        assert!(math32::pow(MAX, 1) == MAX, 2);
+        assert!(math32::pow(3, 4) == 81, 3);
+        assert!(math32::pow(2, 31) == 1 << 31, 4);
+        assert!(math32::pow(0, 255) == 0, 5);
+        assert!(math32::pow(1, 255) == 1, 6);
+    }
+
+    #[test]
+    #[expected_failure(arithmetic_error, location = std::math32)]
+    fun pow_overflow() {
+        math32::pow(MAX, 2);
+    }
+
+    #[test]
+    fun sqrt() {
+        assert!(math32::sqrt(0) == 0, 0);
+        assert!(math32::sqrt(1) == 1, 1);
+        assert!(math32::sqrt(3) == 1, 2);
+        assert!(math32::sqrt(4) == 2, 3);
+        assert!(math32::sqrt(99) == 9, 4);
+        assert!(math32::sqrt(MAX) == 65535, 5);
+        assert!(math32::sqrt(4294836225) == 65535, 6);
+        assert!(math32::sqrt(4294836224) == 65534, 7);
+    }
+
+    #[test]
+    fun log2() {
+        assert!(math32::log2(1) == 0, 0);
+        assert!(math32::log2(2) == 1, 1);
+        assert!(math32::log2(3) == 1, 2);
+        assert!(math32::log2(MAX) == 31, 3);
+        assert!(math32::log2(1 << 31) == 31, 4);
+        assert!(math32::log2((1 << 31) - 1) == 30, 5);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10004)]
+    fun log2_of_zero() {
+        math32::log2(0);
+    }
+
+    #[test]
+    fun min_max_clamp() {
+        assert!(math32::min(1, 2) == 1, 0);
+        assert!(math32::max(1, 2) == 2, 1);
+        assert!(math32::max_value() == MAX, 2);
+        assert!(math32::clamp(0, 1, 3) == 1, 3);
+        assert!(math32::clamp(2, 1, 3) == 2, 4);
+        assert!(math32::clamp(MAX, 1, 3) == 3, 5);
+        assert!(math32::clamp(1, 1, 1) == 1, 6);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10003)]
+    fun clamp_invalid_range() {
+        math32::clamp(1, 3, 2);
+    }
+
+    #[test]
+    fun sqrt_around_largest_squares() {
+        assert!(math32::sqrt(4294836225) == 65535, 0);
+        assert!(math32::sqrt(4294836224) == 65534, 1);
+        assert!(math32::sqrt(4294705156) == 65534, 2);
+        assert!(math32::sqrt(4294705155) == 65533, 3);
+        assert!(math32::sqrt(1 << 31) == 46340, 4);
+    }
+
+    #[test]
+    fun log2_around_every_power_of_two() {
+        let log: u8 = 1;
+        while (log < 32) {
+            assert!(math32::log2(1 << log) == log, (log as u64));
+            assert!(math32::log2((1 << log) - 1) == log - 1, (log as u64));
+            log = log + 1;
+        };
+    }
+
+    #[test]
+    fun mul_div_near_max() {
+        assert!(math32::mul_div(MAX - 1, MAX - 1, MAX) == MAX - 2, 0);
+        assert!(math32::mul_div(MAX, MAX - 1, MAX - 1) == MAX, 1);
+        assert!(math32::mul_div(65536, 65536, 2) == 1 << 31, 2);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x20002)]
+    fun mul_div_overflow_by_one() {
+        math32::mul_div(MAX, MAX, MAX - 1);
+    }
+
+    #[test]
+    fun pow_largest_powers() {
+        assert!(math32::pow(3, 20) == 3486784401, 0);
+        assert!(math32::pow(65535, 2) == 4294836225, 1);
+        assert!(math32::pow(2, 31) == 1 << 31, 2);
+    }
+
+    #[test]
+    #[expected_failure(arithmetic_error, location = std::math32)]
+    fun pow_overflow_by_one_factor() {
+        math32::pow(3, 21);
+    }
+
+    #[test]
+    #[expected_failure(arithmetic_error, location = std::math32)]
+    fun pow_overflow_of_square() {
+        math32::pow(65536, 2);
+    }
+}
@@ -0,0 +1,173 @@
+#[test_only]
+module std::math64_tests {
+    use std::option;
+    use std::math64;
+
+    const MAX: u64 = 18446744073709551615;
+
+    #[test]
+    fun checked() {
+        assert!(math64::checked_add(MAX - 1, 1) == option::some(MAX), 0);
+        assert!(option::is_none(&math64::checked_add(MAX, 1)), 1);
+        assert!(math64::checked_sub(1, 1) == option::some(0), 2);
+        assert!(option::is_none(&math64::checked_sub(0, 1)), 3);
+        assert!(math64::checked_mul(MAX, 1) == option::some(MAX), 4);
+        assert!(math64::checked_mul(0, MAX) == option::some(0), 5);
+        assert!(option::is_none(&math64::checked_mul(MAX / 2 + 1, 2)), 6);
+    }
+
+    #[test]
+    fun saturating() {
+        assert!(math64::saturating_add(MAX, 1) == MAX, 0);
+        assert!(math64::saturating_add(1, 2) == 3, 1);
+        assert!(math64::saturating_sub(0, 1) == 0, 2);
+        assert!(math64::saturating_sub(3, 2) == 1, 3);
+        assert!(math64::saturating_mul(MAX, 2) == MAX, 4);
+        assert!(math64::saturating_mul(3, 5) == 15, 5);
+    }
+
+    #[test]
+    fun wrapping() {
+        assert!(math64::wrapping_add(MAX, 1) == 0, 0);
+        assert!(math64::wrapping_add(MAX, MAX) == MAX - 1, 1);
+        assert!(math64::wrapping_add(1, 2) == 3, 2);
+        assert!(math64::wrapping_sub(0, 1) == MAX, 3);
+        assert!(math64::wrapping_sub(1, MAX) == 2, 4);
+        assert!(math64::wrapping_sub(3, 2) == 1, 5);
+        assert!(math64::wrapping_mul(MAX, MAX) == 1, 6);
+        assert!(math64::wrapping_mul(MAX, 2) == MAX - 1, 7);
+        assert!(math64::wrapping_mul(4294967296, 4294967296) == 0, 8);
+        assert!(math64::wrapping_mul(3, 5) == 15, 9);
+    }
+
+    #[test]
+    fun mul_div() {
+        assert!(math64::mul_div(MAX, MAX, MAX) == MAX, 0);
+        assert!(math64::mul_div(MAX, 3, 4) == MAX / 4 * 3 + 2, 1);
+        assert!(math64::mul_div(7, 3, 2) == 10, 2);
+        assert!(math64::mul_div(0, MAX, 1) == 0, 3);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10001)]
+    fun mul_div_by_zero() {
+        math64::mul_div(1, 1, 0);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x20002)]
+    fun mul_div_overflow() {
+        math64::mul_div(MAX, 2, 1);
+    }
+
+    #[test]
+    fun pow() {
+        assert!(math64::pow(0, 0) == 1, 0);
+        assert!(math64::pow(MAX, 0) == 1, 1);
+        assert!(math64::pow(MAX, 1) == MAX, 2);
+        assert!(math64::pow(3, 4) == 81, 3);
+        assert!(math64::pow(2, 63) == 1 << 63, 4);
+        assert!(math64::pow(0, 255) == 0, 5);
+        assert!(math64::pow(1, 255) == 1, 6);
+    }
+
+    #[test]
+    #[expected_failure(arithmetic_error, location = std::math64)]
+    fun pow_overflow() {
+        math64::pow(MAX, 2);
+    }
+
+    #[test]
+    fun sqrt() {
+        assert!(math64::sqrt(0) == 0, 0);
+        assert!(math64::sqrt(1) == 1, 1);
+        assert!(math64::sqrt(3) == 1, 2);
+        assert!(math64::sqrt(4) == 2, 3);
+        assert!(math64::sqrt(99) == 9, 4);
+        assert!(math64::sqrt(MAX) == 4294967295, 5);
+        assert!(math64::sqrt(18446744065119617025) == 4294967295, 6);
+        assert!(math64::sqrt(18446744065119617024) == 4294967294, 7);
+    }
+
+    #[test]
+    fun log2() {
+        assert!(math64::log2(1) == 0, 0);
+        assert!(math64::log2(2) == 1, 1);
+        assert!(math64::log2(3) == 1, 2);
+        assert!(math64::log2(MAX) == 63, 3);
+        assert!(math64::log2(1 << 63) == 63, 4);
+        assert!(math64::log2((1 << 63) - 1) == 62, 5);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10004)]
+    fun log2_of_zero() {
+        math64::log2(0);
+    }
+
+    #[test]
+    fun min_max_clamp() {
+        assert!(math64::min(1, 2) == 1, 0);
+        assert!(math64::max(1, 2) == 2, 1);
+        assert!(math64::max_value() == MAX, 2);
+        assert!(math64::clamp(0, 1, 3) == 1, 3);
+        assert!(math64::clamp(2, 1, 3) == 2, 4);
+        assert!(math64::clamp(MAX, 1, 3) == 3, 5);
+        assert!(math64::clamp(1, 1, 1) == 1, 6);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10003)]
+    fun clamp_invalid_range() {
+        math64::clamp(1, 3, 2);
+    }
+
+    #[test]
+    fun sqrt_around_largest_squares() {
+        assert!(math64::sqrt(18446744056529682436) == 4294967294, 0);
+        assert!(math64::sqrt(18446744056529682435) == 4294967293, 1);
+        assert!(math64::sqrt(1 << 63) == 3037000499, 2);
+    }
+
+    #[test]
+    fun log2_around_every_power_of_two() {
+        let log: u8 = 1;
+        while (log < 64) {
+            assert!(math64::log2(1 << log) == log, (log as u64));
+            assert!(math64::log2((1 << log) - 1) == log - 1, (log as u64));
+            log = log + 1;
+        };
+    }
+
+    #[test]
+    fun mul_div_near_max() {
+        assert!(math64::mul_div(MAX - 1, MAX - 1, MAX) == MAX - 2, 0);
+        assert!(math64::mul_div(MAX, MAX - 1, MAX - 1) == MAX, 1);
+        assert!(math64::mul_div(4294967296, 4294967296, 2) == 1 << 63, 2);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x20002)]
+    fun mul_div_overflow_by_one() {
+        math64::mul_div(MAX, MAX, MAX - 1);
+    }
+
+    #[test]
+    fun pow_largest_powers() {
+        assert!(math64::pow(3, 40) == 12157665459056928801, 0);
+        assert!(math64::pow(4294967295, 2) == 18446744065119617025, 1);
+        assert!(math64::pow(10, 19) == 10000000000000000000, 2);
+    }
+
+    #[test]
+    #[expected_failure(arithmetic_error, location = std::math64)]
+    fun pow_overflow_by_one_factor() {
+        math64::pow(3, 41);
+    }
+
+    #[test]
+    #[expected_failure(arithmetic_error, location = std::math64)]
+    fun pow_overflow_of_square() {
+        math64::pow(4294967296, 2);
+    }
+}
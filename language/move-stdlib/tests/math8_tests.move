@@ -0,0 +1,179 @@
+#[test_only]
+module std::math8_tests {
+    use std::option;
+    use std::math8;
+
+    const MAX: u8 = 255;
+
+    #[test]
+    fun checked() {
+        assert!(math8::checked_add(MAX - 1, 1) == option::some(MAX), 0);
+        assert!(option::is_none(&math8::checked_add(MAX, 1)), 1);
+        assert!(math8::checked_sub(1, 1) == option::some(0), 2);
+        assert!(option::is_none(&math8::checked_sub(0, 1)), 3);
+        assert!(math8::checked_mul(MAX, 1) == option::some(MAX), 4);
+        assert!(math8::checked_mul(0, MAX) == option::some(0), 5);
+        assert!(option::is_none(&math8::checked_mul(MAX / 2 + 1, 2)), 6);
+    }
+
+    #[test]
+    fun saturating() {
+        assert!(math8::saturating_add(MAX, 1) == MAX, 0);
+        assert!(math8::saturating_add(1, 2) == 3, 1);
+        assert!(math8::saturating_sub(0, 1) == 0, 2);
+        assert!(math8::saturating_sub(3, 2) == 1, 3);
+        assert!(math8::saturating_mul(MAX, 2) == MAX, 4);
+        assert!(math8::saturating_mul(3, 5) == 15, 5);
+    }
+
+    #[test]
+    fun wrapping() {
+        assert!(math8::wrapping_add(MAX, 1) == 0, 0);
+        assert!(math8::wrapping_add(MAX, MAX) == MAX - 1, 1);
+        assert!(math8::wrapping_add(1, 2) == 3, 2);
+        assert!(math8::wrapping_sub(0, 1) == MAX, 3);
+        assert!(math8::wrapping_sub(1, MAX) == 2, 4);
+        assert!(math8::wrapping_sub(3, 2) == 1, 5);
+        assert!(math8::wrapping_mul(MAX, MAX) == 1, 6);
+        assert!(math8::wrapping_mul(MAX, 2) == MAX - 1, 7);
+        assert!(math8::wrapping_mul(16, 16) == 0, 8);
+        assert!(math8::wrapping_mul(3, 5) == 15, 9);
+    }
+
+    #[test]
+    fun mul_div() {
+        assert!(math8::mul_div(MAX, MAX, MAX) == MAX, 0);
+        assert!(math8::mul_div(MAX, 3, 4) == MAX / 4 * 3 + 2, 1);
+        assert!(math8::mul_div(7, 3, 2) == 10, 2);
+        assert!(math8::mul_div(0, MAX, 1) == 0, 3);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10001)]
+    fun mul_div_by_zero() {
+        math8::mul_div(1, 1, 0);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x20002)]
+    fun mul_div_overflow() {
+        math8::mul_div(MAX, 2, 1);
+    }
+
+    #[test]
+    fun pow() {
+        assert!(math8::pow(0, 0) == 1, 0);
+        assert!(math8::pow(MAX, 0) == 1, 1);
+        assert!(math8::pow(MAX, 1) == MAX, 2);
+        assert!(math8::pow(3, 4) == 81, 3);
+        assert!(math8::pow(2, 7) == 1 << 7, 4);
+        assert!(math8::pow(0, 255) == 0, 5);
+        assert!(math8::pow(1, 255) == 1, 6);
+    }
+
+    #[test]
+    #[expected_failure(arithmetic_error, location = std::math8)]
+    fun pow_overflow() {
+        math8::pow(MAX, 2);
+    }
+
+    #[test]
+    fun sqrt() {
+        assert!(math8::sqrt(0) == 0, 0);
+        assert!(math8::sqrt(1) == 1, 1);
+        assert!(math8::sqrt(3) == 1, 2);
+        assert!(math8::sqrt(4) == 2, 3);
+        assert!(math8::sqrt(99) == 9, 4);
+        assert!(math8::sqrt(MAX) == 15, 5);
+        assert!(math8::sqrt(225) == 15, 6);
+        assert!(math8::sqrt(224) == 14, 7);
+    }
+
+    #[test]
+    fun log2() {
+        assert!(math8::log2(1) == 0, 0);
+        assert!(math8::log2(2) == 1, 1);
+        assert!(math8::log2(3) == 1, 2);
+        assert!(math8::log2(MAX) == 7, 3);
+        assert!(math8::log2(1 << 7) == 7, 4);
+        assert!(math8::log2((1 << 7) - 1) == 6, 5);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10004)]
+    fun log2_of_zero() {
+        math8::log2(0);
+    }
+
+    #[test]
+    fun min_max_clamp() {
+        assert!(math8::min(1, 2) == 1, 0);
+        assert!(math8::max(1, 2) == 2, 1);
+        assert!(math8::max_value() == MAX, 2);
+        assert!(math8::clamp(0, 1, 3) == 1, 3);
+        assert!(math8::clamp(2, 1, 3) == 2, 4);
+        assert!(math8::clamp(MAX, 1, 3) == 3, 5);
+        assert!(math8::clamp(1, 1, 1) == 1, 6);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10003)]
+    fun clamp_invalid_range() {
+        math8::clamp(1, 3, 2);
+    }
+
+    #[test]
+    fun sqrt_of_every_value() {
+        let x: u16 = 0;
+        while (x <= 255) {
+            let root = (math8::sqrt((x as u8)) as u16);
+            assert!(root * root <= x && x < (root + 1) * (root + 1), (x as u64));
+            x = x + 1;
+        };
+    }
+
+    #[test]
+    fun log2_of_every_value() {
+        let x: u16 = 1;
+        while (x <= 255) {
+            let log = math8::log2((x as u8));
+            assert!(1 << log <= x && x < 2 << log, (x as u64));
+            x = x + 1;
+        };
+    }
+
+    #[test]
+    fun mul_div_near_max() {
+        // 254 * 254 / 255 = 253.0039..., which must round down
+        assert!(math8::mul_div(254, 254, MAX) == 253, 0);
+        assert!(math8::mul_div(MAX, 254, 254) == MAX, 1);
+        assert!(math8::mul_div(MAX, MAX, 255) == MAX, 2);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x20002)]
+    fun mul_div_overflow_by_one() {
+        // 255 * 255 / 254 = 256.0039...
+        math8::mul_div(MAX, MAX, 254);
+    }
+
+    #[test]
+    fun pow_largest_powers() {
+        assert!(math8::pow(3, 5) == 243, 0);
+        assert!(math8::pow(15, 2) == 225, 1);
+        // The last square of the base would be 16 * 16, which must not be computed
+        assert!(math8::pow(2, 7) == 128, 2);
+    }
+
+    #[test]
+    #[expected_failure(arithmetic_error, location = std::math8)]
+    fun pow_overflow_by_one_factor() {
+        math8::pow(3, 6);
+    }
+
+    #[test]
+    #[expected_failure(arithmetic_error, location = std::math8)]
+    fun pow_overflow_of_square() {
+        math8::pow(16, 2);
+    }
+}
@@ -0,0 +1,60 @@
+#[test_only]
+module std::rounding_tests {
+    use std::rounding::{div_u256, down, up, half_up, half_even};
+
+    const MAX_U256: u256 =
+        115792089237316195423570985008687907853269984665640564039457584007913129639935;
+
+    #[test]
+    fun exact_quotient_is_not_rounded() {
+        assert!(div_u256(12, 4, down()) == 3, 0);
+        assert!(div_u256(12, 4, up()) == 3, 1);
+        assert!(div_u256(12, 4, half_up()) == 3, 2);
+        assert!(div_u256(12, 4, half_even()) == 3, 3);
+        assert!(div_u256(0, 7, up()) == 0, 4);
+    }
+
+    #[test]
+    fun below_half() {
+        // 13 / 4 = 3.25
+        assert!(div_u256(13, 4, down()) == 3, 0);
+        assert!(div_u256(13, 4, up()) == 4, 1);
+        assert!(div_u256(13, 4, half_up()) == 3, 2);
+        assert!(div_u256(13, 4, half_even()) == 3, 3);
+    }
+
+    #[test]
+    fun above_half() {
+        // 15 / 4 = 3.75
+        assert!(div_u256(15, 4, down()) == 3, 0);
+        assert!(div_u256(15, 4, up()) == 4, 1);
+        assert!(div_u256(15, 4, half_up()) == 4, 2);
+        assert!(div_u256(15, 4, half_even()) == 4, 3);
+    }
+
+    #[test]
+    fun exactly_half() {
+        // 5 / 2 = 2.5 and 7 / 2 = 3.5
+        assert!(div_u256(5, 2, down()) == 2, 0);
+        assert!(div_u256(5, 2, up()) == 3, 1);
+        assert!(div_u256(5, 2, half_up()) == 3, 2);
+        assert!(div_u256(5, 2, half_even()) == 2, 3);
+        assert!(div_u256(7, 2, half_up()) == 4, 4);
+        assert!(div_u256(7, 2, half_even()) == 4, 5);
+    }
+
+    #[test]
+    fun large_operands_do_not_overflow() {
+        assert!(div_u256(MAX_U256, 2, up()) == MAX_U256 / 2 + 1, 0);
+        assert!(div_u256(MAX_U256, 2, half_even()) == MAX_U256 / 2 + 1, 1);
+        assert!(div_u256(MAX_U256 - 1, MAX_U256, half_up()) == 1, 2);
+        assert!(div_u256(MAX_U256 / 2, MAX_U256, half_up()) == 0, 3);
+        assert!(div_u256(MAX_U256, MAX_U256, down()) == 1, 4);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10001)]
+    fun divide_by_zero() {
+        div_u256(1, 0, down());
+    }
+}
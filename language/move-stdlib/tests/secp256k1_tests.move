@@ -0,0 +1,46 @@
+#[test_only]
+module std::secp256k1_tests {
+    use std::hash;
+    use std::option;
+    use std::secp256k1;
+
+    // Signed with the secret key 0x1111...11
+    const PUBLIC_KEY: vector<u8> = x"4f355bdcb7cc0af728ef3cceb9615d90684bb5b2ca5f859ab0f0b704075871aa385b6b1b8ead809ca67454d9683fcf2ba03456d6fe2c4abe2b07f0fbdbb2f1c1";
+    const COMPRESSED_PUBLIC_KEY: vector<u8> = x"034f355bdcb7cc0af728ef3cceb9615d90684bb5b2ca5f859ab0f0b704075871aa";
+    const SIGNATURE: vector<u8> = x"41e4e63e18f5aaa6957d3929bdc3d90d33af69df609468b2bfdae322d2a8ddf974ff8a4bb03f52f70d75de9ae9fbffc8041636ba588983814704f095ed1c644d";
+    // The same signature with `s` replaced by `n - s`
+    const HIGH_S_SIGNATURE: vector<u8> = x"41e4e63e18f5aaa6957d3929bdc3d90d33af69df609468b2bfdae322d2a8ddf98b0075b44fc0ad08f28a216516040036b698a62c56bf1cba78cd6df6e319dcf4";
+
+    fun message(): vector<u8> {
+        hash::keccak256(b"hello move")
+    }
+
+    #[test]
+    fun ecdsa_recover() {
+        let recovered = secp256k1::ecdsa_recover(message(), 0, SIGNATURE);
+        assert!(recovered == option::some(PUBLIC_KEY), 0);
+        // A different recovery id yields a different key
+        let recovered = secp256k1::ecdsa_recover(message(), 1, SIGNATURE);
+        assert!(option::is_some(&recovered) && recovered != option::some(PUBLIC_KEY), 1);
+        // A different message yields a different key
+        let recovered = secp256k1::ecdsa_recover(hash::keccak256(b"hello world"), 0, SIGNATURE);
+        assert!(recovered != option::some(PUBLIC_KEY), 2);
+    }
+
+    #[test]
+    fun ecdsa_recover_rejects_malformed_input() {
+        assert!(option::is_none(&secp256k1::ecdsa_recover(b"hello move", 0, SIGNATURE)), 0);
+        assert!(option::is_none(&secp256k1::ecdsa_recover(message(), 4, SIGNATURE)), 1);
+        assert!(option::is_none(&secp256k1::ecdsa_recover(message(), 0, x"41e4")), 2);
+        assert!(option::is_none(&secp256k1::ecdsa_recover(message(), 1, HIGH_S_SIGNATURE)), 3);
+    }
+
+    #[test]
+    fun ecdsa_verify() {
+        assert!(secp256k1::ecdsa_verify(message(), SIGNATURE, PUBLIC_KEY), 0);
+        assert!(secp256k1::ecdsa_verify(message(), SIGNATURE, COMPRESSED_PUBLIC_KEY), 1);
+        assert!(!secp256k1::ecdsa_verify(hash::keccak256(b"hello world"), SIGNATURE, PUBLIC_KEY), 2);
+        assert!(!secp256k1::ecdsa_verify(message(), HIGH_S_SIGNATURE, PUBLIC_KEY), 3);
+        assert!(!secp256k1::ecdsa_verify(message(), SIGNATURE, x"04"), 4);
+    }
+}
@@ -1,6 +1,9 @@
 #[test_only]
 module std::string_tests {
+    use std::bcs;
+    use std::option;
     use std::string;
+    use std::vector;
 
     #[test]
     fun test_valid_utf8() {
@@ -75,4 +78,157 @@ module std::string_tests {
         string::insert(&mut s, 1, string::utf8(b"xy"));
         assert!(s == string::utf8(b"axybcd"), 22)
     }
+
+    #[test]
+    fun test_from_integers() {
+        assert!(string::from_u8(0) == string::utf8(b"0"), 22);
+        assert!(string::from_u8(255) == string::utf8(b"255"), 22);
+        assert!(string::from_u16(65535) == string::utf8(b"65535"), 22);
+        assert!(string::from_u32(4294967295) == string::utf8(b"4294967295"), 22);
+        assert!(string::from_u64(18446744073709551615) == string::utf8(b"18446744073709551615"), 22);
+        assert!(string::from_u128(1000000) == string::utf8(b"1000000"), 22);
+        assert!(
+            string::from_u256(115792089237316195423570985008687907853269984665640564039457584007913129639935)
+                == string::utf8(b"115792089237316195423570985008687907853269984665640564039457584007913129639935"),
+            22
+        );
+    }
+
+    #[test]
+    fun test_to_integers() {
+        assert!(string::to_u8(&string::utf8(b"255")) == option::some(255), 22);
+        assert!(string::to_u8(&string::utf8(b"256")) == option::none(), 22);
+        assert!(string::to_u16(&string::utf8(b"00065535")) == option::some(65535), 22);
+        assert!(string::to_u16(&string::utf8(b"65536")) == option::none(), 22);
+        assert!(string::to_u32(&string::utf8(b"4294967296")) == option::none(), 22);
+        assert!(string::to_u64(&string::utf8(b"18446744073709551615")) == option::some(18446744073709551615), 22);
+        assert!(string::to_u64(&string::utf8(b"18446744073709551616")) == option::none(), 22);
+        assert!(string::to_u128(&string::utf8(b"42")) == option::some(42), 22);
+        assert!(
+            string::to_u256(&string::utf8(b"115792089237316195423570985008687907853269984665640564039457584007913129639935"))
+                == option::some(115792089237316195423570985008687907853269984665640564039457584007913129639935),
+            22
+        );
+        assert!(
+            string::to_u256(&string::utf8(b"115792089237316195423570985008687907853269984665640564039457584007913129639936"))
+                == option::none(),
+            22
+        );
+    }
+
+    #[test]
+    fun test_to_integers_invalid() {
+        assert!(string::to_u64(&string::utf8(b"")) == option::none(), 22);
+        assert!(string::to_u64(&string::utf8(b"-1")) == option::none(), 22);
+        assert!(string::to_u64(&string::utf8(b"+1")) == option::none(), 22);
+        assert!(string::to_u64(&string::utf8(b"1 ")) == option::none(), 22);
+        assert!(string::to_u64(&string::utf8(b"0x10")) == option::none(), 22);
+    }
+
+    #[test]
+    fun test_addresses() {
+        let s = string::from_address(@0x1);
+        assert!(string::sub_string(&s, 0, 2) == string::utf8(b"0x"), 22);
+        assert!(string::length(&s) == 2 + 2 * vector::length(&bcs::to_bytes(&@0x1)), 22);
+        assert!(string::to_address(&s) == option::some(@0x1), 22);
+        assert!(string::to_address(&string::utf8(b"0x1")) == option::some(@0x1), 22);
+        assert!(string::to_address(&string::utf8(b"0xCAFE")) == option::some(@0xcafe), 22);
+        assert!(string::to_address(&string::utf8(b"0x")) == option::none(), 22);
+        assert!(string::to_address(&string::utf8(b"cafe")) == option::none(), 22);
+        assert!(string::to_address(&string::utf8(b"0xcafg")) == option::none(), 22);
+        let too_long = s;
+        string::append_utf8(&mut too_long, b"0");
+        assert!(string::to_address(&too_long) == option::none(), 22);
+    }
+
+    #[test]
+    fun test_hex() {
+        assert!(string::hex_encode(&x"00ff10ab") == string::utf8(b"00ff10ab"), 22);
+        assert!(string::hex_encode(&x"") == string::utf8(b""), 22);
+        assert!(string::hex_decode(&string::utf8(b"00FF10ab")) == option::some(x"00ff10ab"), 22);
+        assert!(string::hex_decode(&string::utf8(b"abc")) == option::none(), 22);
+        assert!(string::hex_decode(&string::utf8(b"zz")) == option::none(), 22);
+    }
+
+    #[test]
+    fun test_format() {
+        let args = vector::empty();
+        vector::push_back(&mut args, string::from_u64(1));
+        vector::push_back(&mut args, string::utf8(b"two"));
+        let s = string::format(&string::utf8(b"{} and {} in {{braces}}"), args);
+        assert!(s == string::utf8(b"1 and two in {braces}"), 22);
+        let s = string::format(&string::utf8(b"no placeholders"), vector::empty());
+        assert!(s == string::utf8(b"no placeholders"), 22);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 3, location = std::string)]
+    fun test_format_too_few_args() {
+        string::format(&string::utf8(b"{} {}"), vector::singleton(string::utf8(b"a")));
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 3, location = std::string)]
+    fun test_format_too_many_args() {
+        string::format(&string::utf8(b"{}"), vector[string::utf8(b"a"), string::utf8(b"b")]);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 3, location = std::string)]
+    fun test_format_unmatched_brace() {
+        string::format(&string::utf8(b"{0}"), vector::empty());
+    }
+
+    #[test]
+    fun test_chars() {
+        // "a", a sparkle heart (U+1F496) and an e with acute accent (U+00E9)
+        let s = string::utf8(vector[97, 240, 159, 146, 150, 195, 169]);
+        assert!(string::length(&s) == 7, 22);
+        assert!(string::length_chars(&s) == 3, 22);
+        let (c, next) = string::next_char(&s, 0);
+        assert!(c == 97 && next == 1, 22);
+        let (c, next) = string::next_char(&s, 1);
+        assert!(c == 0x1F496 && next == 5, 22);
+        let (c, next) = string::next_char(&s, 5);
+        assert!(c == 0xE9 && next == 7, 22);
+        let expected = vector::singleton(97u32);
+        vector::push_back(&mut expected, 0x1F496);
+        vector::push_back(&mut expected, 0xE9);
+        assert!(string::chars(&s) == expected, 22);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 2, location = std::string)]
+    fun test_next_char_invalid_boundary() {
+        let s = string::utf8(vector[240, 159, 146, 150]);
+        string::next_char(&s, 1);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 2, location = std::string)]
+    fun test_next_char_at_end() {
+        let s = string::utf8(b"ab");
+        string::next_char(&s, 2);
+    }
+
+    #[test]
+    fun test_from_char() {
+        assert!(string::from_char(97) == string::utf8(b"a"), 22);
+        assert!(string::from_char(0x1F496) == string::utf8(vector[240, 159, 146, 150]), 22);
+        let s = string::utf8(b"caf");
+        string::push_char(&mut s, 0xE9);
+        assert!(s == string::utf8(vector[99, 97, 102, 195, 169]), 22);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 4, location = std::string)]
+    fun test_from_char_surrogate() {
+        string::from_char(0xD800);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 4, location = std::string)]
+    fun test_from_char_too_large() {
+        string::from_char(0x110000);
+    }
 }
@@ -10,3 +10,4 @@ mod loader_tests;
 mod mutated_accounts_tests;
 mod nested_loop_tests;
 mod return_value_tests;
+mod upgrade_policy_tests;
@@ -45,6 +45,7 @@ fn test_publish_module_with_nested_loops() {
             ),
             VerifierConfig {
                 max_loop_depth: Some(2),
+                ..Default::default()
             },
         )
         .unwrap();
@@ -64,6 +65,7 @@ fn test_publish_module_with_nested_loops() {
             ),
             VerifierConfig {
                 max_loop_depth: Some(1),
+                ..Default::default()
             },
         )
         .unwrap();
@@ -109,6 +111,7 @@ fn test_run_script_with_nested_loops() {
             ),
             VerifierConfig {
                 max_loop_depth: Some(2),
+                ..Default::default()
             },
         )
         .unwrap();
@@ -129,6 +132,7 @@ fn test_run_script_with_nested_loops() {
             ),
             VerifierConfig {
                 max_loop_depth: Some(1),
+                ..Default::default()
             },
         )
         .unwrap();
@@ -0,0 +1,126 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::compiler::{as_module, compile_units};
+use move_binary_format::{errors::VMResult, upgrade_policy::UpgradePolicy, CompiledModule};
+use move_core_types::{account_address::AccountAddress, vm_status::StatusCode};
+use move_vm_runtime::move_vm::MoveVM;
+use move_vm_test_utils::InMemoryStorage;
+use move_vm_types::gas::UnmeteredGasMeter;
+
+const TEST_ADDR: AccountAddress = AccountAddress::new([42; AccountAddress::LENGTH]);
+
+const V1: &str = r#"
+    module {{ADDR}}::M {
+        public fun f(): u64 { 1 }
+    }
+"#;
+
+// Adds a function to V1.
+const V2: &str = r#"
+    module {{ADDR}}::M {
+        public fun f(): u64 { 1 }
+        public fun g(): u64 { 2 }
+    }
+"#;
+
+// Changes the body of a function of V1.
+const V3: &str = r#"
+    module {{ADDR}}::M {
+        public fun f(): u64 { 3 }
+    }
+"#;
+
+fn compile(code: &str, policy: Option<UpgradePolicy>) -> CompiledModule {
+    let code = code.replace("{{ADDR}}", &format!("0x{}", TEST_ADDR));
+    let mut units = compile_units(&code).unwrap();
+    let mut module = as_module(units.pop().unwrap());
+    if let Some(policy) = policy {
+        policy.record(&mut module);
+    }
+    module
+}
+
+fn publish(storage: &mut InMemoryStorage, module: &CompiledModule, relax: bool) -> VMResult<()> {
+    let mut blob = vec![];
+    module.serialize(&mut blob).unwrap();
+    let vm = MoveVM::new(vec![]).unwrap();
+    let mut sess = vm.new_session(storage);
+    if relax {
+        sess.publish_module_bundle_relax_compatibility(
+            vec![blob],
+            TEST_ADDR,
+            &mut UnmeteredGasMeter,
+        )?;
+    } else {
+        sess.publish_module(blob, TEST_ADDR, &mut UnmeteredGasMeter)?;
+    }
+    let (changes, _) = sess.finish()?;
+    storage.apply(changes).unwrap();
+    Ok(())
+}
+
+fn assert_rejected(result: VMResult<()>) {
+    assert_eq!(
+        result.unwrap_err().major_status(),
+        StatusCode::BACKWARD_INCOMPATIBLE_MODULE_UPDATE
+    );
+}
+
+#[test]
+fn compatible_allows_body_changes() {
+    let mut storage = InMemoryStorage::new();
+    let policy = Some(UpgradePolicy::Compatible);
+    publish(&mut storage, &compile(V1, policy), false).unwrap();
+    publish(&mut storage, &compile(V3, policy), false).unwrap();
+}
+
+#[test]
+fn additive_only() {
+    let mut storage = InMemoryStorage::new();
+    let policy = Some(UpgradePolicy::AdditiveOnly);
+    publish(&mut storage, &compile(V1, policy), false).unwrap();
+    publish(&mut storage, &compile(V2, policy), false).unwrap();
+    // Changing a function body is rejected, even without compatibility checks.
+    assert_rejected(publish(&mut storage, &compile(V3, policy), true));
+}
+
+#[test]
+fn dependency_only_rejects_additions() {
+    let mut storage = InMemoryStorage::new();
+    let policy = Some(UpgradePolicy::DependencyOnly);
+    publish(&mut storage, &compile(V1, policy), false).unwrap();
+    assert_rejected(publish(&mut storage, &compile(V2, policy), false));
+}
+
+#[test]
+fn immutable_allows_only_republishing() {
+    let mut storage = InMemoryStorage::new();
+    let policy = Some(UpgradePolicy::Immutable);
+    publish(&mut storage, &compile(V1, policy), false).unwrap();
+    publish(&mut storage, &compile(V1, policy), false).unwrap();
+    assert_rejected(publish(&mut storage, &compile(V2, policy), true));
+}
+
+#[test]
+fn policies_cannot_be_relaxed() {
+    let mut storage = InMemoryStorage::new();
+    publish(
+        &mut storage,
+        &compile(V1, Some(UpgradePolicy::AdditiveOnly)),
+        false,
+    )
+    .unwrap();
+    assert_rejected(publish(
+        &mut storage,
+        &compile(V2, Some(UpgradePolicy::Compatible)),
+        false,
+    ));
+    assert_rejected(publish(&mut storage, &compile(V2, None), false));
+    publish(
+        &mut storage,
+        &compile(V2, Some(UpgradePolicy::Immutable)),
+        false,
+    )
+    .unwrap();
+}
@@ -15,7 +15,7 @@ use move_binary_format::{
 use move_core_types::{
     account_address::AccountAddress,
     gas_algebra::{NumArgs, NumBytes},
-    language_storage::TypeTag,
+    language_storage::{ModuleId, TypeTag},
     vm_status::{StatusCode, StatusType},
 };
 use move_vm_types::{
@@ -181,6 +181,7 @@ impl Interpreter {
                             data_store,
                             gas_meter,
                             extensions,
+                            current_frame.function.module_id(),
                             func,
                             vec![],
                         )?;
@@ -227,7 +228,13 @@ impl Interpreter {
 
                     if func.is_native() {
                         self.call_native(
-                            &resolver, data_store, gas_meter, extensions, func, ty_args,
+                            &resolver,
+                            data_store,
+                            gas_meter,
+                            extensions,
+                            current_frame.function.module_id(),
+                            func,
+                            ty_args,
                         )?;
                         current_frame.pc += 1; // advance past the Call instruction in the caller
                         continue;
@@ -272,6 +279,7 @@ impl Interpreter {
         data_store: &mut dyn DataStore,
         gas_meter: &mut impl GasMeter,
         extensions: &mut NativeContextExtensions,
+        caller: Option<&ModuleId>,
         function: Arc<Function>,
         ty_args: Vec<Type>,
     ) -> VMResult<()> {
@@ -281,6 +289,7 @@ impl Interpreter {
             data_store,
             gas_meter,
             extensions,
+            caller,
             function.clone(),
             ty_args,
         )
@@ -302,6 +311,7 @@ impl Interpreter {
         data_store: &mut dyn DataStore,
         gas_meter: &mut impl GasMeter,
         extensions: &mut NativeContextExtensions,
+        caller: Option<&ModuleId>,
         function: Arc<Function>,
         ty_args: Vec<Type>,
     ) -> PartialVMResult<()> {
@@ -311,7 +321,7 @@ impl Interpreter {
         for _ in 0..expected_args {
             args.push_front(self.operand_stack.pop()?);
         }
-        let mut native_context = NativeContext::new(self, data_store, resolver, extensions);
+        let mut native_context = NativeContext::new(self, data_store, resolver, extensions, caller);
         let native_function = function.get_native()?;
 
         gas_meter.charge_native_function_before_execution(
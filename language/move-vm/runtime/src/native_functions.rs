@@ -5,11 +5,14 @@
 use crate::{
     interpreter::Interpreter, loader::Resolver, native_extensions::NativeContextExtensions,
 };
-use move_binary_format::errors::{ExecutionState, PartialVMError, PartialVMResult};
+use move_binary_format::{
+    errors::{ExecutionState, PartialVMError, PartialVMResult},
+    file_format::AbilitySet,
+};
 use move_core_types::{
     account_address::AccountAddress,
     identifier::Identifier,
-    language_storage::TypeTag,
+    language_storage::{ModuleId, TypeTag},
     value::MoveTypeLayout,
     vm_status::{StatusCode, StatusType},
 };
@@ -94,6 +97,7 @@ pub struct NativeContext<'a, 'b> {
     data_store: &'a mut dyn DataStore,
     resolver: &'a Resolver<'a>,
     extensions: &'a mut NativeContextExtensions<'b>,
+    caller: Option<&'a ModuleId>,
 }
 
 impl<'a, 'b> NativeContext<'a, 'b> {
@@ -102,12 +106,14 @@ impl<'a, 'b> NativeContext<'a, 'b> {
         data_store: &'a mut dyn DataStore,
         resolver: &'a Resolver<'a>,
         extensions: &'a mut NativeContextExtensions<'b>,
+        caller: Option<&'a ModuleId>,
     ) -> Self {
         Self {
             interpreter,
             data_store,
             resolver,
             extensions,
+            caller,
         }
     }
 }
@@ -159,6 +165,10 @@ impl<'a, 'b> NativeContext<'a, 'b> {
         }
     }
 
+    pub fn type_abilities(&self, ty: &Type) -> PartialVMResult<AbilitySet> {
+        self.resolver.loader().abilities(ty)
+    }
+
     pub fn extensions(&self) -> &NativeContextExtensions<'b> {
         self.extensions
     }
@@ -167,6 +177,12 @@ impl<'a, 'b> NativeContext<'a, 'b> {
         self.extensions
     }
 
+    /// Get the module of the function which called the native function, or `None` if it was
+    /// called from a script.
+    pub fn caller(&self) -> Option<&ModuleId> {
+        self.caller
+    }
+
     /// Get count stack frames, including the one of the called native function. This
     /// allows a native function to reflect about its caller.
     pub fn stack_frames(&self, count: usize) -> ExecutionState {
@@ -15,7 +15,9 @@ use move_binary_format::{
     compatibility::Compatibility,
     errors::{verification_error, Location, PartialVMError, PartialVMResult, VMResult},
     file_format::LocalIndex,
-    normalized, CompiledModule, IndexKind,
+    normalized,
+    upgrade_policy::UpgradePolicy,
+    CompiledModule, IndexKind,
 };
 use move_bytecode_verifier::{script_signature, VerifierConfig};
 use move_core_types::{
@@ -105,22 +107,24 @@ impl VMRuntime {
         // Collect ids for modules that are published together
         let mut bundle_unverified = BTreeSet::new();
 
-        // For now, we assume that all modules can be republished, as long as the new module is
-        // backward compatible with the old module.
-        //
-        // TODO: in the future, we may want to add restrictions on module republishing, possibly by
-        // changing the bytecode format to include an `is_upgradable` flag in the CompiledModule.
+        // Modules can be republished as long as the new module is backward compatible with the
+        // old module, and the upgrade policy recorded in the old module, if any, allows the
+        // upgrade. Upgrade policies are enforced regardless of `compat`.
         for module in &compiled_modules {
             let module_id = module.self_id();
 
-            if data_store.exists_module(&module_id)? && compat.need_check_compat() {
+            if data_store.exists_module(&module_id)? {
                 let old_module_ref = self.loader.load_module(&module_id, data_store)?;
                 let old_module = old_module_ref.module();
-                let old_m = normalized::Module::new(old_module);
-                let new_m = normalized::Module::new(module);
-                compat
-                    .check(&old_m, &new_m)
+                UpgradePolicy::check_upgrade(old_module, module)
                     .map_err(|e| e.finish(Location::Undefined))?;
+                if compat.need_check_compat() {
+                    let old_m = normalized::Module::new(old_module);
+                    let new_m = normalized::Module::new(module);
+                    compat
+                        .check(&old_m, &new_m)
+                        .map_err(|e| e.finish(Location::Undefined))?;
+                }
             }
             if !bundle_unverified.insert(module_id) {
                 return Err(PartialVMError::new(StatusCode::DUPLICATE_MODULE_NAME)
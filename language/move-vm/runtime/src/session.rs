@@ -181,7 +181,8 @@ impl<'r, 'l, S: MoveResolver> Session<'r, 'l, S> {
     /// one shall not proceed with effect generation.
     ///
     /// This operation performs compatibility checks if a module is replaced. See also
-    /// `move_binary_format::compatibility`.
+    /// `move_binary_format::compatibility`. The upgrade policy recorded in a replaced module
+    /// is enforced as well, see `move_binary_format::upgrade_policy`.
     pub fn publish_module_bundle(
         &mut self,
         modules: Vec<Vec<u8>>,
@@ -197,7 +198,8 @@ impl<'r, 'l, S: MoveResolver> Session<'r, 'l, S> {
         )
     }
 
-    /// Same like `publish_module_bundle` but with a custom compatibility check.
+    /// Same like `publish_module_bundle` but with a custom compatibility check. Upgrade policies
+    /// are enforced regardless of `compat_config`.
     pub fn publish_module_bundle_with_compat_config(
         &mut self,
         modules: Vec<Vec<u8>>,
@@ -23,6 +23,10 @@ impl Build {
             return Ok(());
         }
         let architecture = config.architecture.unwrap_or(Architecture::Move);
+        let config = BuildConfig {
+            lint: true,
+            ..config
+        };
 
         match architecture {
             Architecture::Move | Architecture::AsyncMove => {
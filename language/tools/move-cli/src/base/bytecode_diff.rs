@@ -0,0 +1,37 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use clap::*;
+use move_binary_format::CompiledModule;
+use move_disassembler::bytecode_diff::BytecodeDiff as ModuleDiff;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Compare the bytecode of two versions of a module, ignoring the renumbering of table entries
+#[derive(Parser)]
+#[clap(name = "bytecode-diff")]
+pub struct BytecodeDiff {
+    /// The bytecode file of the old version of the module
+    #[clap(parse(from_os_str))]
+    pub old: PathBuf,
+    /// The bytecode file of the new version of the module
+    #[clap(parse(from_os_str))]
+    pub new: PathBuf,
+}
+
+impl BytecodeDiff {
+    pub fn execute(self) -> anyhow::Result<()> {
+        let diff = ModuleDiff::new(&read_module(&self.old)?, &read_module(&self.new)?)?;
+        print!("{}", diff);
+        Ok(())
+    }
+}
+
+fn read_module(path: &Path) -> anyhow::Result<CompiledModule> {
+    let bytes = fs::read(path)
+        .map_err(|err| anyhow::anyhow!("Unable to read '{}': {}", path.display(), err))?;
+    CompiledModule::deserialize(&bytes)
+        .map_err(|err| anyhow::anyhow!("Unable to deserialize '{}': {}", path.display(), err))
+}
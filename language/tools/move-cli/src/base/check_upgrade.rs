@@ -0,0 +1,149 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use super::reroot_path;
+use clap::*;
+use move_binary_format::{
+    access::ModuleAccess,
+    compatibility::{Compatibility, Incompatibility},
+    file_format::{FunctionDefinitionIndex, StructDefinitionIndex},
+    normalized, CompiledModule,
+};
+use move_compiler::compiled_unit::{CompiledUnit, NamedCompiledModule};
+use move_ir_types::location::Loc;
+use move_package::BuildConfig;
+use std::{fs, path::PathBuf};
+
+/// Check whether the package's version of a module is a compatible upgrade of its published
+/// bytecode, and list every breaking change
+#[derive(Parser)]
+#[clap(name = "check-upgrade")]
+pub struct CheckUpgrade {
+    /// The bytecode of the old version of the module
+    #[clap(parse(from_os_str))]
+    pub old_module: PathBuf,
+    /// The name of the module in the package to compare against. Defaults to the name of the
+    /// old module
+    #[clap(long = "name")]
+    pub module_name: Option<String>,
+}
+
+impl CheckUpgrade {
+    pub fn execute(self, path: Option<PathBuf>, config: BuildConfig) -> anyhow::Result<()> {
+        // Read the old module before rerooting changes the working directory.
+        let bytes = fs::read(&self.old_module)?;
+        let old_module = CompiledModule::deserialize(&bytes).map_err(|err| {
+            anyhow::anyhow!(
+                "Unable to deserialize '{}': {}",
+                self.old_module.display(),
+                err
+            )
+        })?;
+        let rerooted_path = reroot_path(path)?;
+        // Make sure the package is built
+        let package = config.compile_package(&rerooted_path, &mut Vec::new())?;
+        let module_name = self
+            .module_name
+            .unwrap_or_else(|| old_module.self_id().name().to_string());
+        let unit = package.get_module_by_name_from_root(&module_name)?;
+        let (new_module, source_map) = match &unit.unit {
+            CompiledUnit::Module(NamedCompiledModule {
+                module, source_map, ..
+            }) => (module, source_map),
+            CompiledUnit::Script(_) => unreachable!("found a script by module name"),
+        };
+
+        let incompatibilities = Compatibility::report(
+            &normalized::Module::new(&old_module),
+            &normalized::Module::new(new_module),
+        );
+        if incompatibilities.is_empty() {
+            println!(
+                "Module {} is a compatible upgrade of {}",
+                new_module.self_id().short_str_lossless(),
+                old_module.self_id().short_str_lossless()
+            );
+            return Ok(());
+        }
+
+        let source = fs::read_to_string(&unit.source_path)?;
+        for incompatibility in &incompatibilities {
+            // Point at the changed item in the new version, or at the module if it was removed.
+            let loc = incompatibility
+                .change
+                .struct_name()
+                .and_then(|name| {
+                    let idx = new_module.struct_defs().iter().position(|def| {
+                        new_module
+                            .identifier_at(new_module.struct_handle_at(def.struct_handle).name)
+                            == name.as_ident_str()
+                    })?;
+                    let struct_map = source_map
+                        .get_struct_source_map(StructDefinitionIndex(idx as u16))
+                        .ok()?;
+                    Some(struct_map.definition_location)
+                })
+                .or_else(|| {
+                    let name = incompatibility.change.function_name()?;
+                    let idx = new_module.function_defs().iter().position(|def| {
+                        new_module.identifier_at(new_module.function_handle_at(def.function).name)
+                            == name.as_ident_str()
+                    })?;
+                    let function_map = source_map
+                        .get_function_source_map(FunctionDefinitionIndex(idx as u16))
+                        .ok()?;
+                    Some(function_map.definition_location)
+                })
+                .unwrap_or(source_map.definition_location);
+            let (line, column) = line_and_column(&source, loc);
+            println!(
+                "error[{}]: {}\n  --> {}:{}:{}",
+                kind(incompatibility),
+                incompatibility.change,
+                unit.source_path.display(),
+                line,
+                column
+            );
+        }
+
+        if incompatibilities
+            .iter()
+            .all(Incompatibility::is_layout_only)
+        {
+            println!("Only struct layouts changed. Dependent modules continue to link, but published structs need to be migrated.");
+        } else if incompatibilities
+            .iter()
+            .all(Incompatibility::is_linking_only)
+        {
+            println!("Only the linking API changed. Published structs remain readable, but dependent modules need to be updated and redeployed.");
+        }
+        anyhow::bail!(
+            "Found {} breaking change(s) in module {}",
+            incompatibilities.len(),
+            new_module.self_id().short_str_lossless()
+        )
+    }
+}
+
+/// Names the guarantees an incompatibility breaks.
+fn kind(incompatibility: &Incompatibility) -> String {
+    let mut kinds = vec![];
+    if incompatibility.breaks_linking {
+        kinds.push("linking");
+    }
+    if incompatibility.breaks_friend_linking {
+        kinds.push("friend linking");
+    }
+    if incompatibility.breaks_layout {
+        kinds.push("layout");
+    }
+    kinds.join(", ")
+}
+
+/// Returns the one-based line and column at which `loc` starts in `source`.
+fn line_and_column(source: &str, loc: Loc) -> (usize, usize) {
+    let prefix = &source[..(loc.start() as usize).min(source.len())];
+    let line = prefix.matches('\n').count() + 1;
+    let column = prefix.len() - prefix.rfind('\n').map_or(0, |idx| idx + 1) + 1;
+    (line, column)
+}
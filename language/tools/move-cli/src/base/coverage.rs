@@ -5,12 +5,19 @@ use super::reroot_path;
 use clap::*;
 use move_compiler::compiled_unit::{CompiledUnit, NamedCompiledModule};
 use move_coverage::{
-    coverage_map::CoverageMap, format_csv_summary, format_human_summary,
-    source_coverage::SourceCoverageBuilder, summary::summarize_inst_cov,
+    coverage_map::CoverageMap,
+    format_csv_summary, format_human_summary,
+    source_coverage::{output_cobertura, output_lcov, FileCoverage, SourceCoverageBuilder},
+    summary::summarize_inst_cov,
 };
 use move_disassembler::disassembler::Disassembler;
-use move_package::BuildConfig;
-use std::path::PathBuf;
+use move_package::{compilation::compiled_package::CompiledPackage, BuildConfig};
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 #[derive(Parser)]
 pub enum CoverageSummaryOptions {
@@ -36,6 +43,24 @@ pub enum CoverageSummaryOptions {
         #[clap(long = "module")]
         module_name: String,
     },
+    /// Export line and branch coverage for all modules in this package in the LCOV format
+    #[clap(name = "lcov")]
+    Lcov {
+        /// Path to write the LCOV tracefile to. Printed to stdout if not present
+        #[clap(long = "output-path", short = 'o')]
+        output_path: Option<PathBuf>,
+    },
+    /// Export line and branch coverage for all modules in this package as a Cobertura XML report
+    #[clap(name = "cobertura")]
+    Cobertura {
+        /// Path to write the report to. Printed to stdout if not present
+        #[clap(long = "output-path", short = 'o')]
+        output_path: Option<PathBuf>,
+        /// The time to record as the generation time of the report, in milliseconds since the
+        /// Unix epoch. Defaults to the current time
+        #[clap(long = "timestamp")]
+        timestamp: Option<u128>,
+    },
 }
 
 /// Inspect test coverage for this package. A previous test run with the `--coverage` flag must
@@ -71,9 +96,8 @@ impl Coverage {
                 };
                 let source_coverage = SourceCoverageBuilder::new(module, &coverage_map, source_map);
                 source_coverage
-                    .compute_source_coverage(source_path)
-                    .output_source_coverage(&mut std::io::stdout())
-                    .unwrap();
+                    .compute_source_coverage(source_path)?
+                    .output_source_coverage(&mut std::io::stdout())?;
             }
             CoverageSummaryOptions::Summary {
                 functions,
@@ -104,7 +128,58 @@ impl Coverage {
                 disassembler.add_coverage_map(coverage_map.to_unified_exec_map());
                 println!("{}", disassembler.disassemble()?);
             }
+            CoverageSummaryOptions::Lcov { output_path } => {
+                let files = file_coverage(&package, &coverage_map, &path)?;
+                output_lcov(&files, &mut output_writer(output_path)?)?;
+            }
+            CoverageSummaryOptions::Cobertura {
+                output_path,
+                timestamp,
+            } => {
+                let files = file_coverage(&package, &coverage_map, &path)?;
+                let timestamp = match timestamp {
+                    Some(timestamp) => timestamp,
+                    None => SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis(),
+                };
+                output_cobertura(
+                    &files,
+                    package.compiled_package_info.package_name.as_str(),
+                    timestamp,
+                    &mut output_writer(output_path)?,
+                )?;
+            }
         }
         Ok(())
     }
 }
+
+/// Compute the line and branch coverage of all modules in `package`, with source paths relative to
+/// the package root at `path`.
+fn file_coverage(
+    package: &CompiledPackage,
+    coverage_map: &CoverageMap,
+    path: &Path,
+) -> anyhow::Result<Vec<FileCoverage>> {
+    let mut files = vec![];
+    for unit in package.root_modules() {
+        if let CompiledUnit::Module(NamedCompiledModule {
+            module, source_map, ..
+        }) = &unit.unit
+        {
+            let mut file_coverage = SourceCoverageBuilder::new(module, coverage_map, source_map)
+                .compute_line_coverage(&unit.source_path)?;
+            if let Ok(relative_path) = file_coverage.source_path.strip_prefix(path) {
+                file_coverage.source_path = relative_path.to_path_buf();
+            }
+            files.push(file_coverage)
+        }
+    }
+    Ok(files)
+}
+
+fn output_writer(output_path: Option<PathBuf>) -> io::Result<Box<dyn Write>> {
+    Ok(match output_path {
+        Some(output_path) => Box::new(File::create(output_path)?),
+        None => Box::new(io::stdout()),
+    })
+}
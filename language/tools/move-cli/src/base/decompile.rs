@@ -0,0 +1,76 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use super::reroot_path;
+use clap::*;
+use move_binary_format::CompiledModule;
+use move_compiler::compiled_unit::{CompiledUnit, NamedCompiledModule};
+use move_decompiler::Decompiler;
+use move_package::BuildConfig;
+use std::{fs, path::PathBuf};
+
+/// Reconstruct Move source code from the bytecode of a module
+#[derive(Parser)]
+#[clap(name = "decompile")]
+pub struct Decompile {
+    /// The package name. If not provided defaults to current package modules only
+    #[clap(long = "package")]
+    pub package_name: Option<String>,
+    /// The name of the module in the package to decompile
+    #[clap(long = "name", required_unless_present = "bytecode")]
+    pub module_name: Option<String>,
+    /// Decompile the module in this bytecode file instead, resolving its dependencies in the
+    /// package
+    #[clap(long = "bytecode", parse(from_os_str), conflicts_with_all = &["module-name", "package-name"])]
+    pub bytecode: Option<PathBuf>,
+}
+
+impl Decompile {
+    pub fn execute(self, path: Option<PathBuf>, config: BuildConfig) -> anyhow::Result<()> {
+        // Read the bytecode file before rerooting changes the working directory.
+        let bytecode = match &self.bytecode {
+            Some(path) => {
+                let bytes = fs::read(path)?;
+                Some(CompiledModule::deserialize(&bytes).map_err(|err| {
+                    anyhow::anyhow!("Unable to deserialize '{}': {}", path.display(), err)
+                })?)
+            }
+            None => None,
+        };
+        let rerooted_path = reroot_path(path)?;
+        // Make sure the package is built
+        let package = config.compile_package(&rerooted_path, &mut Vec::new())?;
+        let dependencies = package.all_modules().filter_map(|unit| match &unit.unit {
+            CompiledUnit::Module(NamedCompiledModule { module, .. }) => Some(module),
+            CompiledUnit::Script(_) => None,
+        });
+
+        let source = match (&bytecode, self.module_name) {
+            (Some(module), _) => Decompiler::new(module, dependencies).decompile()?,
+            (None, Some(module_name)) => {
+                let needle_package = self
+                    .package_name
+                    .as_deref()
+                    .unwrap_or(package.compiled_package_info.package_name.as_str());
+                match package.get_module_by_name(needle_package, &module_name) {
+                    Ok(unit) => match &unit.unit {
+                        CompiledUnit::Module(NamedCompiledModule {
+                            module, source_map, ..
+                        }) => Decompiler::new(module, dependencies)
+                            .with_source_map(source_map)
+                            .decompile()?,
+                        CompiledUnit::Script(_) => unreachable!("found a script by module name"),
+                    },
+                    Err(_) => anyhow::bail!(
+                        "Unable to find module with name '{}' in package '{}'",
+                        module_name,
+                        needle_package,
+                    ),
+                }
+            }
+            (None, None) => unreachable!("clap requires a module name or a bytecode file"),
+        };
+        print!("{}", source);
+        Ok(())
+    }
+}
@@ -0,0 +1,78 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use super::reroot_path;
+use anyhow::bail;
+use clap::*;
+use codespan_reporting::{diagnostic::Severity, term::termcolor::StandardStream};
+use move_compiler::diagnostics;
+use move_package::{BuildConfig, ModelConfig};
+use move_stackless_bytecode::{
+    function_target_pipeline::{FunctionTargetPipeline, FunctionTargetsHolder},
+    security_check::SecurityCheckProcessor,
+};
+use std::path::PathBuf;
+
+/// Run the linters over the package at `path` without writing any build artifacts. The lint
+/// levels are read from the `[lints]` section of the package manifest.
+#[derive(Parser)]
+#[clap(name = "lint")]
+pub struct Lint {
+    /// Also run the security checks over the package's bytecode, e.g. for capabilities that can
+    /// be copied or global storage writes not authorized by a signer. Fails if any check reports
+    /// a finding.
+    #[clap(long = "security")]
+    pub security: bool,
+}
+
+impl Lint {
+    pub fn execute(self, path: Option<PathBuf>, config: BuildConfig) -> anyhow::Result<()> {
+        let rerooted_path = reroot_path(path)?;
+        let install_dir = tempfile::tempdir()?;
+        let config = BuildConfig {
+            lint: true,
+            generate_docs: false,
+            generate_abis: false,
+            install_dir: Some(install_dir.path().to_path_buf()),
+            ..config
+        };
+        config
+            .clone()
+            .compile_package(&rerooted_path, &mut std::io::stderr())?;
+        if !self.security {
+            return Ok(());
+        }
+
+        let model = config.move_model_for_package(
+            &rerooted_path,
+            ModelConfig {
+                all_files_as_targets: false,
+                target_filter: None,
+            },
+        )?;
+        let mut error_writer = StandardStream::stderr(diagnostics::color_choice());
+        if model.has_errors() {
+            model.report_diag(&mut error_writer, Severity::Error);
+            bail!("Errors encountered while building the model for the security checks");
+        }
+        // the compiler warnings were already reported by the lint pass
+        model.clear_diag();
+
+        let mut targets = FunctionTargetsHolder::default();
+        for module_env in model.get_modules() {
+            for func_env in module_env.get_functions() {
+                targets.add_target(&func_env)
+            }
+        }
+        let mut pipeline = FunctionTargetPipeline::default();
+        pipeline.add_processor(SecurityCheckProcessor::new());
+        pipeline.run(&model, &mut targets);
+
+        let findings = model.diag_count(Severity::Warning);
+        model.report_diag(&mut error_writer, Severity::Warning);
+        if findings > 0 {
+            bail!("Security checks reported {} finding(s)", findings);
+        }
+        Ok(())
+    }
+}
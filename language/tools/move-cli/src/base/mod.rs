@@ -2,11 +2,15 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod build;
+pub mod bytecode_diff;
+pub mod check_upgrade;
 pub mod coverage;
+pub mod decompile;
 pub mod disassemble;
 pub mod docgen;
 pub mod errmap;
 pub mod info;
+pub mod lint;
 pub mod movey_login;
 pub mod movey_upload;
 pub mod new;
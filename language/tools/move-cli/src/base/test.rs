@@ -7,14 +7,14 @@ use anyhow::Result;
 use clap::*;
 use move_command_line_common::files::{FileHash, MOVE_COVERAGE_MAP_EXTENSION};
 use move_compiler::{
-    diagnostics::{self, codes::Severity},
+    diagnostics::{self, codes::Severity, MessageFormat},
     shared::{NumberFormat, NumericalAddress},
     unit_test::{plan_builder::construct_test_plan, TestPlan},
-    PASS_CFGIR,
+    Compiler, PASS_CFGIR,
 };
 use move_coverage::coverage_map::{output_map_to_file, CoverageMap};
 use move_package::{compilation::build_plan::BuildPlan, BuildConfig};
-use move_unit_test::UnitTestingConfig;
+use move_unit_test::{TestReportFormat, UnitTestingConfig};
 use std::{
     collections::HashMap,
     fs,
@@ -75,6 +75,19 @@ pub struct Test {
     /// Collect coverage information for later use with the various `package coverage` subcommands
     #[clap(long = "coverage")]
     pub compute_coverage: bool,
+    /// The format of the test results. With `junit` or `json`, only the machine-readable report is
+    /// printed to stdout and build progress is printed to stderr.
+    #[clap(long = "format", arg_enum, default_value = "human")]
+    pub report_format: TestReportFormat,
+    /// The number of sets of arguments each `#[random_test]` is run with.
+    #[clap(long = "random_test_iterations", default_value = "100")]
+    pub random_test_iterations: u64,
+    /// The seed used to generate the arguments of `#[random_test]`s. Picked at random if not set.
+    #[clap(long = "seed")]
+    pub seed: Option<u64>,
+    /// A JSON file of resources to publish in global storage before running any test
+    #[clap(long = "fixtures", parse(from_os_str))]
+    pub fixtures: Option<PathBuf>,
 
     /// Use the EVM-based execution backend.
     /// Does not work with --stackless.
@@ -90,6 +103,12 @@ impl Test {
         config: BuildConfig,
         natives: Vec<NativeFunctionRecord>,
     ) -> anyhow::Result<()> {
+        // Resolve the fixtures file before moving to the package root
+        let fixtures = self
+            .fixtures
+            .as_deref()
+            .map(Path::canonicalize)
+            .transpose()?;
         let rerooted_path = reroot_path(path)?;
         let Self {
             instruction_execution_bound,
@@ -102,6 +121,10 @@ impl Test {
             check_stackless_vm,
             verbose_mode,
             compute_coverage,
+            report_format,
+            random_test_iterations,
+            seed,
+            fixtures: _,
             #[cfg(feature = "evm-backend")]
             evm,
         } = self;
@@ -115,6 +138,10 @@ impl Test {
             check_stackless_vm,
             verbose: verbose_mode,
             ignore_compile_warnings,
+            report_format,
+            random_test_iterations,
+            seed,
+            fixtures: fixtures.map(|fixtures| fixtures.to_string_lossy().to_string()),
             #[cfg(feature = "evm-backend")]
             evm,
 
@@ -189,15 +216,19 @@ pub fn run_move_unit_tests<W: Write + Send>(
         })
         .collect();
     let root_package = resolution_graph.root_package.package.name;
+    let message_format = resolution_graph.build_options.message_format;
     let build_plan = BuildPlan::create(resolution_graph)?;
     // Compile the package. We need to intercede in the compilation, process being performed by the
     // Move package system, to first grab the compilation env, construct the test plan from it, and
     // then save it, before resuming the rest of the compilation and returning the results and
     // control back to the Move package system.
-    build_plan.compile_with_driver(writer, |compiler| {
+    let driver = |compiler: Compiler| {
         let (files, comments_and_compiler_res) = compiler.run::<PASS_CFGIR>().unwrap();
-        let (_, compiler) =
-            diagnostics::unwrap_or_report_diagnostics(&files, comments_and_compiler_res);
+        let (_, compiler) = diagnostics::unwrap_or_report_diagnostics_with_format(
+            &files,
+            comments_and_compiler_res,
+            message_format,
+        );
         let (mut compiler, cfgir) = compiler.into_ast();
         let compilation_env = compiler.compilation_env();
         let built_test_plan = construct_test_plan(compilation_env, Some(root_package), &cfgir);
@@ -208,15 +239,27 @@ pub fn run_move_unit_tests<W: Write + Send>(
                 Severity::Warning
             },
         ) {
-            diagnostics::report_diagnostics(&files, diags);
+            diagnostics::report_diagnostics_with_format(&files, diags, message_format);
         }
 
         let compilation_result = compiler.at_cfgir(cfgir).build();
 
-        let (units, _) = diagnostics::unwrap_or_report_diagnostics(&files, compilation_result);
+        let (units, _) = diagnostics::unwrap_or_report_diagnostics_with_format(
+            &files,
+            compilation_result,
+            message_format,
+        );
         test_plan = Some((built_test_plan, files.clone(), units.clone()));
         Ok((files, units))
-    })?;
+    };
+    // Keep build progress out of machine-readable test reports and diagnostics.
+    if unit_test_config.report_format == TestReportFormat::Human
+        && message_format == MessageFormat::Human
+    {
+        build_plan.compile_with_driver(writer, driver)?;
+    } else {
+        build_plan.compile_with_driver(&mut std::io::stderr(), driver)?;
+    }
 
     let (test_plan, mut files, units) = test_plan.unwrap();
     files.extend(dep_file_map);
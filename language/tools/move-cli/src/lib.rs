@@ -3,8 +3,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use base::{
-    build::Build, coverage::Coverage, disassemble::Disassemble, docgen::Docgen, errmap::Errmap,
-    info::Info, movey_login::MoveyLogin, movey_upload::MoveyUpload, new::New, prove::Prove,
+    build::Build, bytecode_diff::BytecodeDiff, check_upgrade::CheckUpgrade, coverage::Coverage,
+    decompile::Decompile, disassemble::Disassemble, docgen::Docgen, errmap::Errmap, info::Info,
+    lint::Lint, movey_login::MoveyLogin, movey_upload::MoveyUpload, new::New, prove::Prove,
     test::Test,
 };
 use move_package::BuildConfig;
@@ -65,11 +66,15 @@ pub struct MoveCLI {
 #[derive(Parser)]
 pub enum Command {
     Build(Build),
+    BytecodeDiff(BytecodeDiff),
+    CheckUpgrade(CheckUpgrade),
     Coverage(Coverage),
+    Decompile(Decompile),
     Disassemble(Disassemble),
     Docgen(Docgen),
     Errmap(Errmap),
     Info(Info),
+    Lint(Lint),
     MoveyUpload(MoveyUpload),
     New(New),
     Prove(Prove),
@@ -110,11 +115,15 @@ pub fn run_cli(
     //         2. The CostTable only affects sandbox runs, but not unit tests, which use a unit cost table.
     match cmd {
         Command::Build(c) => c.execute(move_args.package_path, move_args.build_config),
+        Command::BytecodeDiff(c) => c.execute(),
+        Command::CheckUpgrade(c) => c.execute(move_args.package_path, move_args.build_config),
         Command::Coverage(c) => c.execute(move_args.package_path, move_args.build_config),
+        Command::Decompile(c) => c.execute(move_args.package_path, move_args.build_config),
         Command::Disassemble(c) => c.execute(move_args.package_path, move_args.build_config),
         Command::Docgen(c) => c.execute(move_args.package_path, move_args.build_config),
         Command::Errmap(c) => c.execute(move_args.package_path, move_args.build_config),
         Command::Info(c) => c.execute(move_args.package_path, move_args.build_config),
+        Command::Lint(c) => c.execute(move_args.package_path, move_args.build_config),
         Command::MoveyUpload(c) => c.execute(move_args.package_path),
         Command::New(c) => c.execute_with_defaults(move_args.package_path),
         Command::Prove(c) => c.execute(move_args.package_path, move_args.build_config),
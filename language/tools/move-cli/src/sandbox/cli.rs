@@ -11,6 +11,7 @@ use crate::{
 };
 use anyhow::Result;
 use clap::Parser;
+use move_binary_format::upgrade_policy::UpgradePolicy;
 use move_core_types::{
     errmap::ErrorMapping, language_storage::TypeTag, parser,
     transaction_argument::TransactionArgument,
@@ -52,6 +53,12 @@ pub enum SandboxCommand {
             multiple_occurrences(true)
         )]
         override_ordering: Option<Vec<String>>,
+        /// Record this upgrade policy in the published modules: one of `compatible`,
+        /// `additive-only`, `dependency-only` or `immutable`. The policy recorded in a
+        /// published module restricts how it can be republished, even if breaking changes are
+        /// ignored.
+        #[clap(long = "upgrade-policy")]
+        upgrade_policy: Option<UpgradePolicy>,
     },
     /// Run a Move script that reads/writes resources stored on disk in `storage-dir`.
     /// The script must be defined in the package.
@@ -195,6 +202,7 @@ impl SandboxCommand {
                 with_deps,
                 bundle,
                 override_ordering,
+                upgrade_policy,
             } => {
                 let context =
                     PackageContext::new(&move_args.package_path, &move_args.build_config)?;
@@ -209,6 +217,7 @@ impl SandboxCommand {
                     *with_deps,
                     *bundle,
                     override_ordering.as_ref().map(|o| o.as_slice()),
+                    *upgrade_policy,
                     move_args.verbose,
                 )
             }
@@ -10,9 +10,10 @@ use crate::{
     NativeFunctionRecord,
 };
 use anyhow::{bail, Result};
-use move_binary_format::errors::Location;
+use move_binary_format::{errors::Location, upgrade_policy::UpgradePolicy, CompiledModule};
+use move_bytecode_utils::module_cache::GetModule;
 use move_command_line_common::env::get_bytecode_version_from_env;
-use move_package::compilation::compiled_package::CompiledPackage;
+use move_package::compilation::compiled_package::{CompiledPackage, CompiledUnitWithSource};
 use move_vm_runtime::move_vm::MoveVM;
 use move_vm_test_utils::gas_schedule::CostTable;
 use std::collections::BTreeMap;
@@ -27,6 +28,7 @@ pub fn publish(
     with_deps: bool,
     bundle: bool,
     override_ordering: Option<&[String]>,
+    upgrade_policy: Option<UpgradePolicy>,
     verbose: bool,
 ) -> Result<()> {
     // collect all modules compiled
@@ -83,6 +85,31 @@ pub fn publish(
 
     let bytecode_version = get_bytecode_version_from_env();
 
+    // check the upgrade policies of republished modules before publishing anything, so that
+    // violations are reported in full and even if breaking changes are ignored
+    let mut violated = false;
+    for unit in &modules_to_publish {
+        let id = module(&unit.unit)?.self_id();
+        let old_module = match state.get_module_by_id(&id)? {
+            Some(old_module) => old_module,
+            None => continue,
+        };
+        let new_module =
+            CompiledModule::deserialize(&serialize(unit, upgrade_policy, bytecode_version)?)?;
+        let violations = UpgradePolicy::upgrade_violations(&old_module, &new_module)?;
+        if !violations.is_empty() {
+            println!("Upgrade of module {} violates its upgrade policy:", id);
+            for violation in violations {
+                println!("  - {}", violation);
+            }
+            violated = true;
+        }
+    }
+    if violated {
+        eprintln!("Publishing aborted.");
+        return Ok(());
+    }
+
     // use the the publish_module API from the VM if we do not allow breaking changes
     if !ignore_breaking_changes {
         let vm = MoveVM::new(natives).unwrap();
@@ -95,7 +122,7 @@ pub fn publish(
             let mut sender_opt = None;
             let mut module_bytes_vec = vec![];
             for unit in &modules_to_publish {
-                let module_bytes = unit.unit.serialize(bytecode_version);
+                let module_bytes = serialize(unit, upgrade_policy, bytecode_version)?;
                 module_bytes_vec.push(module_bytes);
 
                 let module_address = *module(&unit.unit)?.self_id().address();
@@ -135,7 +162,7 @@ pub fn publish(
         } else {
             // publish modules sequentially, one module at a time
             for unit in &modules_to_publish {
-                let module_bytes = unit.unit.serialize(bytecode_version);
+                let module_bytes = serialize(unit, upgrade_policy, bytecode_version)?;
                 let id = module(&unit.unit)?.self_id();
                 let sender = *id.address();
 
@@ -169,7 +196,7 @@ pub fn publish(
         let mut serialized_modules = vec![];
         for unit in modules_to_publish {
             let id = module(&unit.unit)?.self_id();
-            let module_bytes = unit.unit.serialize(bytecode_version);
+            let module_bytes = serialize(unit, upgrade_policy, bytecode_version)?;
             serialized_modules.push((id, module_bytes));
         }
         state.save_modules(&serialized_modules)?;
@@ -177,3 +204,20 @@ pub fn publish(
 
     Ok(())
 }
+
+/// Serializes the module of `unit`, recording `upgrade_policy` in its metadata if set.
+fn serialize(
+    unit: &CompiledUnitWithSource,
+    upgrade_policy: Option<UpgradePolicy>,
+    bytecode_version: Option<u32>,
+) -> Result<Vec<u8>> {
+    let policy = match upgrade_policy {
+        Some(policy) => policy,
+        None => return Ok(unit.unit.serialize(bytecode_version)),
+    };
+    let mut module = module(&unit.unit)?.clone();
+    policy.record(&mut module);
+    let mut bytes = vec![];
+    module.serialize_for_version(bytecode_version, &mut bytes)?;
+    Ok(bytes)
+}
@@ -0,0 +1,25 @@
+module diff::vault {
+    const ELOCKED: u64 = 2;
+    const EEMPTY: u64 = 1;
+
+    struct Vault has key {
+        balance: u64,
+        locked: bool,
+    }
+
+    public fun lock(vault: &mut Vault) {
+        assert!(!vault.locked, ELOCKED);
+        vault.locked = true;
+    }
+
+    public fun deposit(vault: &mut Vault, amount: u64) {
+        vault.balance = vault.balance + amount;
+    }
+
+    public fun withdraw(vault: &mut Vault, amount: u64): u64 {
+        assert!(!vault.locked, ELOCKED);
+        assert!(vault.balance >= amount, EEMPTY);
+        vault.balance = vault.balance - amount;
+        amount
+    }
+}
@@ -0,0 +1,21 @@
+module diff::vault {
+    const EEMPTY: u64 = 1;
+
+    struct Vault has key {
+        balance: u64,
+    }
+
+    public fun deposit(vault: &mut Vault, amount: u64) {
+        vault.balance = vault.balance + amount;
+    }
+
+    public fun withdraw(vault: &mut Vault, amount: u64): u64 {
+        assert!(vault.balance >= amount, EEMPTY);
+        vault.balance = vault.balance - amount;
+        amount
+    }
+
+    public fun is_empty(vault: &Vault): bool {
+        vault.balance == 0
+    }
+}
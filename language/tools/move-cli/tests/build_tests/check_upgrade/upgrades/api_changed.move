@@ -0,0 +1,26 @@
+module upgrades::coin {
+    struct Coin has store {
+        value: u128,
+    }
+
+    struct Vault has key {
+        coins: vector<Coin>,
+    }
+
+    public fun mint(value: u128): Coin {
+        Coin { value }
+    }
+
+    public fun value(coin: &Coin): u128 {
+        coin.value
+    }
+
+    public fun split(coin: &mut Coin, value: u128): Coin {
+        coin.value = coin.value - value;
+        Coin { value }
+    }
+
+    fun noop() {}
+}
+
+module upgrades::bank {}
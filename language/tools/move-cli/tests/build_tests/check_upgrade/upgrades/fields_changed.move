@@ -0,0 +1,28 @@
+module upgrades::coin {
+    friend upgrades::bank;
+
+    struct Coin has store, drop {
+        value: u64,
+        frozen: bool,
+    }
+
+    struct Vault has key {
+        coins: vector<Coin>,
+    }
+
+    public fun mint(value: u64): Coin {
+        Coin { value, frozen: false }
+    }
+
+    public fun value(coin: &Coin): u64 {
+        coin.value
+    }
+
+    public(friend) fun burn(coin: Coin) {
+        let Coin { value: _, frozen: _ } = coin;
+    }
+
+    public entry fun noop() {}
+}
+
+module upgrades::bank {}
@@ -0,0 +1,18 @@
+module std::counter {
+    struct Counter has key {
+        value: u64,
+    }
+
+    public fun count_down(n: u64): u64 {
+        let steps = 0;
+        while (n > 0) {
+            if (n % 2 == 0) n = n / 2 else n = n - 1;
+            steps = steps + 1;
+        };
+        steps
+    }
+
+    public fun increment(counter: &mut Counter) {
+        counter.value = counter.value + 1;
+    }
+}
@@ -0,0 +1,7 @@
+module 0x42::m {
+public fun foo(x: u64): u64 {
+    let y = 1;
+    y = 2;
+    1 + 1
+}
+}
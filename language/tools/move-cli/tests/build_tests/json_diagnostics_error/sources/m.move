@@ -0,0 +1,6 @@
+module 0x42::m {
+public fun foo(x: u64): bool {
+    let y = 1;
+    x + y
+}
+}
@@ -0,0 +1,8 @@
+module 0x42::m {
+    public fun f(x: u64, flag: &mut bool): u64 {
+        let y = (x as u64);
+        let x = x + y;
+        if (*flag) x = x;
+        x
+    }
+}
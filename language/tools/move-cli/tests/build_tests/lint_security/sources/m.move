@@ -0,0 +1,18 @@
+module 0x42::m {
+    struct AdminCap has copy, store {}
+
+    struct Vault has key { value: u64 }
+
+    public fun new_cap(): AdminCap {
+        AdminCap {}
+    }
+
+    public fun open(account: &signer) {
+        move_to(account, Vault { value: 0 })
+    }
+
+    public fun close(addr: address): u64 acquires Vault {
+        let Vault { value } = move_from<Vault>(addr);
+        value
+    }
+}
@@ -0,0 +1,15 @@
+module 0x42::m {
+    struct S has copy, drop { f: u64 }
+
+    public fun f(s: S, flag: &mut bool): u64 {
+        if (*flag) consume(copy s);
+        0
+    }
+
+    #[lint_allow(needless_mut_ref)]
+    public fun g(flag: &mut bool): bool {
+        *flag
+    }
+
+    fun consume(_s: S) {}
+}
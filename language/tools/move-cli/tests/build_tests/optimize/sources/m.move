@@ -0,0 +1,31 @@
+module opt::m {
+    public fun pick(flag: bool, x: u64, y: u64): u64 {
+        let r = x;
+        if (flag) {
+            r = y;
+        };
+        r
+    }
+
+    public fun discard(x: u64): u64 {
+        let y = x + 1;
+        let _z = y;
+        x
+    }
+
+    public fun last_copy(x: u64): u64 {
+        let y = copy x + 1;
+        y * copy x
+    }
+
+    public fun nested(a: bool, b: bool): u64 {
+        if (a) {
+            if (b) { 1 } else { 2 }
+        } else {
+            loop {
+                if (b) break;
+            };
+            3
+        }
+    }
+}
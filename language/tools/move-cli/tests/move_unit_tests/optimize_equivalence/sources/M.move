@@ -0,0 +1,139 @@
+/// Functions with the shapes the bytecode optimizer rewrites, and tests pinning their results,
+/// so that running the tests with and without `-O` checks that the optimized code behaves the
+/// same.
+module A::M {
+    use std::vector;
+
+    struct Pair<T: copy + drop> has copy, drop { first: T, second: T }
+
+    /// Copies of locals that are not read afterwards become moves.
+    public fun last_copies(x: u64, y: u64): u64 {
+        let sum = copy x + copy y;
+        sum * copy x
+    }
+
+    /// Stores to locals that are never read become pops.
+    public fun dead_stores(x: u64): u64 {
+        let unused = x * 2;
+        let _ignored = unused;
+        let kept = x + 1;
+        kept
+    }
+
+    /// Nested branches and loops leave chains of jumps behind.
+    public fun classify(a: u64, b: u64): u64 {
+        if (a > b) {
+            if (a > 2 * b) { 3 } else { 2 }
+        } else if (a == b) {
+            1
+        } else {
+            let i = a;
+            while (i < b) {
+                if (i % 7 == 6) break;
+                i = i + 1;
+            };
+            i
+        }
+    }
+
+    /// Locals read through references must keep their copies.
+    public fun through_reference(x: u64): u64 {
+        let y = x;
+        let r = &mut y;
+        *r = *r + 1;
+        let z = copy y;
+        y + z
+    }
+
+    /// The loop reads the counter on every iteration, so its copies must stay.
+    public fun sum_to(n: u64): u64 {
+        let i = 0;
+        let sum = 0;
+        loop {
+            if (i > n) break;
+            sum = sum + copy i;
+            i = i + 1;
+        };
+        sum
+    }
+
+    /// Vector instructions refer to signatures, which must survive the removal of unused ones.
+    public fun vector_sum(v: vector<u64>): u64 {
+        let unused = vector::length(&v);
+        let _ = unused;
+        let sum = 0;
+        while (!vector::is_empty(&v)) {
+            sum = sum + vector::pop_back(&mut v);
+        };
+        sum
+    }
+
+    public fun swap<T: copy + drop>(pair: Pair<T>): Pair<T> {
+        let Pair { first, second } = pair;
+        let unused = copy first;
+        let _ = unused;
+        Pair { first: second, second: first }
+    }
+
+    public fun checked_div(a: u64, b: u64): u64 {
+        let zero = b == 0;
+        if (zero) abort 42;
+        a / b
+    }
+
+    #[test]
+    fun test_last_copies() {
+        assert!(last_copies(2, 3) == 10, 0);
+        assert!(last_copies(0, 7) == 0, 1);
+    }
+
+    #[test]
+    fun test_dead_stores() {
+        assert!(dead_stores(4) == 5, 0);
+    }
+
+    #[test]
+    fun test_classify() {
+        assert!(classify(7, 3) == 3, 0);
+        assert!(classify(5, 3) == 2, 1);
+        assert!(classify(3, 3) == 1, 2);
+        assert!(classify(1, 4) == 4, 3);
+        assert!(classify(1, 20) == 6, 4);
+    }
+
+    #[test]
+    fun test_through_reference() {
+        assert!(through_reference(1) == 4, 0);
+    }
+
+    #[test]
+    fun test_sum_to() {
+        assert!(sum_to(0) == 0, 0);
+        assert!(sum_to(10) == 55, 1);
+    }
+
+    #[test]
+    fun test_vector_sum() {
+        assert!(vector_sum(vector[1, 2, 3]) == 6, 0);
+        assert!(vector_sum(vector[]) == 0, 1);
+    }
+
+    #[test]
+    fun test_swap() {
+        let swapped = swap(Pair { first: 1u8, second: 2u8 });
+        assert!(swapped.first == 2 && swapped.second == 1, 0);
+        let swapped = swap(Pair { first: true, second: false });
+        assert!(!swapped.first && swapped.second, 1);
+    }
+
+    #[test]
+    fun test_checked_div() {
+        assert!(checked_div(7, 2) == 3, 0);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 42, location = Self)]
+    fun test_checked_div_by_zero() {
+        checked_div(1, 0);
+    }
+}
@@ -0,0 +1,47 @@
+module 0x42::Branches {
+    const EINVALID: u64 = 1;
+
+    /// The number of steps to reach 1, e.g. `if (x == 1) 0 else ...`
+    public fun steps(x: u64): u64 {
+        let steps = 0;
+        while (x > 1) {
+            if (x % 2 == 0) x = x / 2 else x = 3 * x + 1;
+            steps = steps + 1;
+        };
+        steps
+    }
+
+    public fun in_range(x: u64, lo: u64, hi: u64): bool {
+        lo <= x && /* || */ x <= hi
+    }
+
+    public fun either(a: bool, b: vector<u8>): bool {
+        a || b == b"while && if"
+    }
+
+    public fun checked(x: u64): u64 {
+        assert!(x < 100 || x == 1000, EINVALID);
+        x
+    }
+
+    #[test]
+    fun test_steps() {
+        assert!(steps(6) == 8, 0)
+    }
+
+    #[test]
+    fun test_in_range() {
+        assert!(in_range(5, 1, 10), 0);
+        assert!(!in_range(0, 1, 10), 0);
+    }
+
+    #[test]
+    fun test_either() {
+        assert!(either(true, b""), 0)
+    }
+
+    #[test]
+    fun test_checked() {
+        checked(1000);
+    }
+}
@@ -0,0 +1,5 @@
+module upgrades::counter {
+    public fun start(): u64 {
+        0
+    }
+}
@@ -0,0 +1,9 @@
+module upgrades::counter {
+    public fun start(): u64 {
+        0
+    }
+
+    public fun next(n: u64): u64 {
+        n + 1
+    }
+}
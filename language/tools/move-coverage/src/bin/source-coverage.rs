@@ -71,6 +71,7 @@ fn main() {
 
     source_cov
         .compute_source_coverage(source_path)
+        .unwrap()
         .output_source_coverage(&mut coverage_writer)
         .unwrap();
 }
@@ -5,22 +5,29 @@
 #![forbid(unsafe_code)]
 
 use crate::coverage_map::CoverageMap;
+use anyhow::{anyhow, bail, Context, Result};
 use codespan::{Files, Span};
 use colored::*;
 use move_binary_format::{
     access::ModuleAccess,
-    file_format::{CodeOffset, FunctionDefinitionIndex},
+    file_format::{Bytecode, CodeOffset, FunctionDefinitionIndex},
     CompiledModule,
 };
 use move_bytecode_source_map::source_map::SourceMap;
-use move_core_types::identifier::Identifier;
-use move_ir_types::location::Loc;
+use move_command_line_common::{address::NumericalAddress, files::FileHash};
+use move_compiler::{
+    naming::ast::BuiltinFunction_,
+    parser::ast::{self as P, BinOp_, NameAccessChain_},
+    Compiler, PASS_PARSER,
+};
+use move_core_types::{identifier::Identifier, language_storage::ModuleId};
+use move_ir_types::{location::Loc, sp};
 use serde::Serialize;
 use std::{
     collections::BTreeMap,
     fs,
     io::{self, Write},
-    path::Path,
+    path::{Path, PathBuf},
 };
 
 #[derive(Clone, Debug, Serialize)]
@@ -29,12 +36,72 @@ pub struct FunctionSourceCoverage {
     pub uncovered_locations: Vec<Loc>,
 }
 
+/// The execution counts of the instructions of a (non-native) function, used to compute line and
+/// branch coverage. Locations are `None` if they are missing from the source map.
+#[derive(Clone, Debug, Serialize)]
+pub struct FunctionExecution {
+    pub definition_location: Option<Loc>,
+    /// The source location and execution count of each instruction, indexed by code offset
+    pub instructions: Vec<(Option<Loc>, u64)>,
+    /// The conditional branches of the function, with the code offsets of the instruction executed
+    /// if the condition is true and if it is false, respectively
+    pub branches: BTreeMap<CodeOffset, [CodeOffset; 2]>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct SourceCoverageBuilder<'a> {
+    module_id: ModuleId,
     uncovered_locations: BTreeMap<Identifier, FunctionSourceCoverage>,
+    function_executions: BTreeMap<Identifier, FunctionExecution>,
     source_map: &'a SourceMap,
 }
 
+/// Line and branch coverage of a single function. Lines are 1-based.
+#[derive(Debug, Serialize)]
+pub struct FunctionLineCoverage {
+    pub name: Identifier,
+    pub line: u32,
+    /// The number of times the function was called
+    pub hits: u64,
+    /// The execution count of each line with code. A line is counted as executed as often as the
+    /// most executed instruction starting on it.
+    pub lines: BTreeMap<u32, u64>,
+    pub branches: Vec<BranchLineCoverage>,
+}
+
+/// The source construct a conditional branch was compiled from.
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+pub enum BranchKind {
+    If,
+    While,
+    And,
+    Or,
+    Assert,
+    /// A branch that could not be mapped to a known construct
+    Other,
+}
+
+/// Coverage of a conditional branch in the source code.
+#[derive(Debug, Serialize)]
+pub struct BranchLineCoverage {
+    pub kind: BranchKind,
+    pub line: u32,
+    pub column: u32,
+    /// The number of times the branch was executed
+    pub hits: u64,
+    /// The number of times the condition was true and false, respectively. As these are
+    /// derived from the execution counts of the branch targets, they are capped at `hits`.
+    pub arms: [u64; 2],
+}
+
+/// Line and branch coverage of a module, against the source file it is defined in.
+#[derive(Debug, Serialize)]
+pub struct FileCoverage {
+    pub module_id: ModuleId,
+    pub source_path: PathBuf,
+    pub functions: Vec<FunctionLineCoverage>,
+}
+
 #[derive(Debug, Serialize, Eq, PartialEq, Ord, PartialOrd)]
 pub enum AbstractSegment {
     Bounded { start: u32, end: u32 },
@@ -53,6 +120,8 @@ pub type AnnotatedLine = Vec<StringSegment>;
 #[derive(Debug, Serialize)]
 pub struct SourceCoverage {
     pub annotated_lines: Vec<AnnotatedLine>,
+    /// The conditional branches in the source, in order of their position in the file
+    pub branches: Vec<BranchLineCoverage>,
 }
 
 impl<'a> SourceCoverageBuilder<'a> {
@@ -67,6 +136,61 @@ impl<'a> SourceCoverageBuilder<'a> {
             .module_maps
             .get(&(*module_name.address(), module_name.name().to_owned()));
 
+        let function_executions = module
+            .function_defs()
+            .iter()
+            .enumerate()
+            .filter_map(|(function_def_idx, function_def)| {
+                let code_unit = function_def.code.as_ref()?;
+                let fn_handle = module.function_handle_at(function_def.function);
+                let fn_name = module.identifier_at(fn_handle.name).to_owned();
+                let function_def_idx = FunctionDefinitionIndex(function_def_idx as u16);
+                let function_coverage =
+                    module_map.and_then(|fn_map| fn_map.function_maps.get(&fn_name));
+                let instructions = (0..code_unit.code.len())
+                    .map(|code_offset| {
+                        let loc = source_map
+                            .get_code_location(function_def_idx, code_offset as CodeOffset)
+                            .ok();
+                        let count = function_coverage
+                            .and_then(|cov| cov.get(&(code_offset as u64)))
+                            .copied()
+                            .unwrap_or(0);
+                        (loc, count)
+                    })
+                    .collect();
+                let branches = code_unit
+                    .code
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(code_offset, instr)| {
+                        let code_offset = code_offset as CodeOffset;
+                        match instr {
+                            Bytecode::BrTrue(target) => {
+                                Some((code_offset, [*target, code_offset + 1]))
+                            }
+                            Bytecode::BrFalse(target) => {
+                                Some((code_offset, [code_offset + 1, *target]))
+                            }
+                            _ => None,
+                        }
+                    })
+                    .collect();
+                let definition_location = source_map
+                    .get_function_source_map(function_def_idx)
+                    .ok()
+                    .map(|function_map| function_map.definition_location);
+                Some((
+                    fn_name,
+                    FunctionExecution {
+                        definition_location,
+                        instructions,
+                        branches,
+                    },
+                ))
+            })
+            .collect();
+
         let uncovered_locations: BTreeMap<Identifier, FunctionSourceCoverage> = module
             .function_defs()
             .iter()
@@ -127,17 +251,99 @@ impl<'a> SourceCoverageBuilder<'a> {
             .collect();
 
         Self {
+            module_id: module_name,
             uncovered_locations,
+            function_executions,
             source_map,
         }
     }
 
-    pub fn compute_source_coverage(&self, file_path: &Path) -> SourceCoverage {
-        let file_contents = fs::read_to_string(file_path).unwrap();
-        assert!(
-            self.source_map.check(&file_contents),
-            "File contents out of sync with source map"
-        );
+    /// Compute the line and branch coverage of the module against its source file at `file_path`.
+    /// Fails if the file cannot be read, or does not match the source map of the module.
+    pub fn compute_line_coverage(&self, file_path: &Path) -> Result<FileCoverage> {
+        let file_contents = read_source_file(self.source_map, file_path)?;
+        let branch_kinds = source_branch_kinds(file_path)?;
+        let file_hash = FileHash::new(&file_contents);
+        let mut files = Files::new();
+        let file_id = files.add(file_path.as_os_str().to_os_string(), file_contents);
+        let invalid_location = |loc: Loc| {
+            format!(
+                "Invalid source location {}..{} in {}",
+                loc.start(),
+                loc.end(),
+                file_path.display()
+            )
+        };
+        let location_of = |loc: Loc| {
+            files
+                .location(file_id, loc.start())
+                .with_context(|| invalid_location(loc))
+        };
+
+        let mut functions = vec![];
+        for (name, execution) in &self.function_executions {
+            let missing_location =
+                || anyhow!("Missing source location in the source map of '{}'", name);
+            let loc_at = |code_offset: CodeOffset| {
+                execution
+                    .instructions
+                    .get(code_offset as usize)
+                    .and_then(|(loc, _)| *loc)
+                    .ok_or_else(missing_location)
+            };
+            let count_at = |code_offset: CodeOffset| {
+                execution
+                    .instructions
+                    .get(code_offset as usize)
+                    .map_or(0, |(_, count)| *count)
+            };
+
+            let mut lines = BTreeMap::new();
+            for (code_offset, (_, count)) in execution.instructions.iter().enumerate() {
+                let line = location_of(loc_at(code_offset as CodeOffset)?)?.line.0 + 1;
+                let line_count = lines.entry(line).or_insert(0);
+                *line_count = std::cmp::max(*line_count, *count);
+            }
+            let mut branches = vec![];
+            for (code_offset, [if_true, if_false]) in &execution.branches {
+                let hits = count_at(*code_offset);
+                let loc = loc_at(*code_offset)?;
+                let location = location_of(loc)?;
+                // Branches of code from other files, e.g. of inlined functions, are not classified
+                let kind = match branch_kinds.get(&(loc.start(), loc.end())) {
+                    Some(kind) if loc.file_hash() == file_hash => *kind,
+                    _ => BranchKind::Other,
+                };
+                branches.push(BranchLineCoverage {
+                    kind,
+                    line: location.line.0 + 1,
+                    column: location.column.0 + 1,
+                    hits,
+                    arms: [
+                        std::cmp::min(hits, count_at(*if_true)),
+                        std::cmp::min(hits, count_at(*if_false)),
+                    ],
+                });
+            }
+            let definition_location = execution.definition_location.ok_or_else(missing_location)?;
+            functions.push(FunctionLineCoverage {
+                name: name.clone(),
+                line: location_of(definition_location)?.line.0 + 1,
+                hits: count_at(0),
+                lines,
+                branches,
+            });
+        }
+
+        Ok(FileCoverage {
+            module_id: self.module_id.clone(),
+            source_path: file_path.to_path_buf(),
+            functions,
+        })
+    }
+
+    pub fn compute_source_coverage(&self, file_path: &Path) -> Result<SourceCoverage> {
+        let file_contents = read_source_file(self.source_map, file_path)?;
         let mut files = Files::new();
         let file_id = files.add(file_path.as_os_str().to_os_string(), file_contents.clone());
 
@@ -145,8 +351,8 @@ impl<'a> SourceCoverageBuilder<'a> {
 
         for (_, fn_cov) in self.uncovered_locations.iter() {
             for span in merge_spans(fn_cov.clone()).into_iter() {
-                let start_loc = files.location(file_id, span.start()).unwrap();
-                let end_loc = files.location(file_id, span.end()).unwrap();
+                let start_loc = files.location(file_id, span.start())?;
+                let end_loc = files.location(file_id, span.end())?;
                 let start_line = start_loc.line.0;
                 let end_line = end_loc.line.0;
                 let segments = uncovered_segments
@@ -221,10 +427,34 @@ impl<'a> SourceCoverageBuilder<'a> {
             }
         }
 
-        SourceCoverage { annotated_lines }
+        let mut branches: Vec<_> = self
+            .compute_line_coverage(file_path)?
+            .functions
+            .into_iter()
+            .flat_map(|function| function.branches)
+            .collect();
+        branches.sort_by_key(|branch| (branch.line, branch.column));
+
+        Ok(SourceCoverage {
+            annotated_lines,
+            branches,
+        })
     }
 }
 
+/// Read the source file at `file_path`, checking that it is the file `source_map` was created for.
+fn read_source_file(source_map: &SourceMap, file_path: &Path) -> Result<String> {
+    let file_contents = fs::read_to_string(file_path)
+        .with_context(|| format!("Unable to read source file {}", file_path.display()))?;
+    if !source_map.check(&file_contents) {
+        bail!(
+            "File contents of {} out of sync with source map",
+            file_path.display()
+        )
+    }
+    Ok(file_contents)
+}
+
 impl SourceCoverage {
     pub fn output_source_coverage<W: Write>(&self, output_writer: &mut W) -> io::Result<()> {
         for line in self.annotated_lines.iter() {
@@ -236,10 +466,464 @@ impl SourceCoverage {
             }
             writeln!(output_writer)?;
         }
+        self.output_branch_coverage(output_writer)
+    }
+
+    /// Write which arms of each conditional branch were taken, e.g. whether an `assert!` ever
+    /// aborted.
+    pub fn output_branch_coverage<W: Write>(&self, output_writer: &mut W) -> io::Result<()> {
+        if self.branches.is_empty() {
+            return Ok(());
+        }
+        writeln!(output_writer)?;
+        writeln!(output_writer, "Branch coverage:")?;
+        for branch in &self.branches {
+            write!(
+                output_writer,
+                "{}:{} {} ",
+                branch.line,
+                branch.column,
+                branch.kind.name()
+            )?;
+            if branch.hits == 0 {
+                write!(output_writer, "{}", "(not reached)".bold().red())?;
+            } else {
+                write!(output_writer, "(")?;
+                for (i, (arm, count)) in branch
+                    .kind
+                    .arm_names()
+                    .iter()
+                    .zip(branch.arms.iter())
+                    .enumerate()
+                {
+                    if i > 0 {
+                        write!(output_writer, ", ")?;
+                    }
+                    if *count == 0 {
+                        let not_taken = format!("{}: not taken", arm);
+                        write!(output_writer, "{}", not_taken.bold().red())?;
+                    } else {
+                        let taken = format!("{}: {}", arm, count);
+                        write!(output_writer, "{}", taken.green())?;
+                    }
+                }
+                write!(output_writer, ")")?;
+            }
+            writeln!(output_writer)?;
+        }
         Ok(())
     }
 }
 
+impl FileCoverage {
+    /// The execution count of each line with code in the file.
+    pub fn lines(&self) -> BTreeMap<u32, u64> {
+        let mut lines = BTreeMap::new();
+        for function in &self.functions {
+            for (line, count) in &function.lines {
+                let line_count = lines.entry(*line).or_insert(0);
+                *line_count = std::cmp::max(*line_count, *count);
+            }
+        }
+        lines
+    }
+
+    /// The conditional branches in the file, in order of their position in the file.
+    pub fn branches(&self) -> Vec<&BranchLineCoverage> {
+        let mut branches: Vec<_> = self
+            .functions
+            .iter()
+            .flat_map(|function| function.branches.iter())
+            .collect();
+        branches.sort_by_key(|branch| (branch.line, branch.column));
+        branches
+    }
+}
+
+impl BranchKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            BranchKind::If => "if",
+            BranchKind::While => "while",
+            BranchKind::And => "&&",
+            BranchKind::Or => "||",
+            BranchKind::Assert => "assert!",
+            BranchKind::Other => "branch",
+        }
+    }
+
+    /// Descriptions of the arms taken if the condition is true and if it is false, respectively.
+    pub fn arm_names(&self) -> [&'static str; 2] {
+        match self {
+            BranchKind::If => ["then", "else"],
+            BranchKind::While => ["loop body", "loop exit"],
+            BranchKind::And => ["right operand", "short-circuit"],
+            BranchKind::Or => ["short-circuit", "right operand"],
+            BranchKind::Assert => ["passed", "aborted"],
+            BranchKind::Other => ["true", "false"],
+        }
+    }
+}
+
+impl BranchLineCoverage {
+    /// The number of arms of this branch that were taken.
+    pub fn arms_taken(&self) -> usize {
+        self.arms.iter().filter(|count| **count > 0).count()
+    }
+}
+
+/// Write the line, branch and function coverage of `files` in the LCOV tracefile format.
+pub fn output_lcov<W: Write>(files: &[FileCoverage], output_writer: &mut W) -> io::Result<()> {
+    for file in files {
+        writeln!(output_writer, "TN:")?;
+        writeln!(output_writer, "SF:{}", file.source_path.display())?;
+        for function in &file.functions {
+            writeln!(output_writer, "FN:{},{}", function.line, function.name)?;
+        }
+        for function in &file.functions {
+            writeln!(output_writer, "FNDA:{},{}", function.hits, function.name)?;
+        }
+        writeln!(output_writer, "FNF:{}", file.functions.len())?;
+        writeln!(
+            output_writer,
+            "FNH:{}",
+            file.functions.iter().filter(|f| f.hits > 0).count()
+        )?;
+
+        let branches = file.branches();
+        for (block, branch) in branches.iter().enumerate() {
+            for (arm, count) in branch.arms.iter().enumerate() {
+                if branch.hits == 0 {
+                    // The branch was never reached
+                    writeln!(output_writer, "BRDA:{},{},{},-", branch.line, block, arm)?;
+                } else {
+                    writeln!(
+                        output_writer,
+                        "BRDA:{},{},{},{}",
+                        branch.line, block, arm, count
+                    )?;
+                }
+            }
+        }
+        writeln!(output_writer, "BRF:{}", branches.len() * 2)?;
+        writeln!(
+            output_writer,
+            "BRH:{}",
+            branches.iter().map(|b| b.arms_taken()).sum::<usize>()
+        )?;
+
+        let lines = file.lines();
+        for (line, count) in &lines {
+            writeln!(output_writer, "DA:{},{}", line, count)?;
+        }
+        writeln!(output_writer, "LF:{}", lines.len())?;
+        writeln!(
+            output_writer,
+            "LH:{}",
+            lines.values().filter(|count| **count > 0).count()
+        )?;
+        writeln!(output_writer, "end_of_record")?;
+    }
+    Ok(())
+}
+
+/// Write the line and branch coverage of `files`, which make up the package `package_name`, as a
+/// Cobertura XML report. `timestamp` is the time the report was generated at, in milliseconds
+/// since the Unix epoch.
+pub fn output_cobertura<W: Write>(
+    files: &[FileCoverage],
+    package_name: &str,
+    timestamp: u128,
+    output_writer: &mut W,
+) -> io::Result<()> {
+    let file_lines: Vec<_> = files.iter().map(|file| file.lines()).collect();
+    let (lines_covered, lines_valid) = line_counts(file_lines.iter().flat_map(|l| l.values()));
+    let (branches_covered, branches_valid) =
+        branch_counts(files.iter().flat_map(|file| file.branches()));
+    let line_rate = rate(lines_covered, lines_valid);
+    let branch_rate = rate(branches_covered, branches_valid);
+
+    writeln!(output_writer, r#"<?xml version="1.0" ?>"#)?;
+    writeln!(
+        output_writer,
+        r#"<!DOCTYPE coverage SYSTEM "http://cobertura.sourceforge.net/xml/coverage-04.dtd">"#
+    )?;
+    writeln!(
+        output_writer,
+        r#"<coverage line-rate="{}" branch-rate="{}" lines-covered="{}" lines-valid="{}" branches-covered="{}" branches-valid="{}" complexity="0" version="1.9" timestamp="{}">"#,
+        line_rate,
+        branch_rate,
+        lines_covered,
+        lines_valid,
+        branches_covered,
+        branches_valid,
+        timestamp
+    )?;
+    writeln!(output_writer, "  <sources>")?;
+    writeln!(output_writer, "    <source>.</source>")?;
+    writeln!(output_writer, "  </sources>")?;
+    writeln!(output_writer, "  <packages>")?;
+    writeln!(
+        output_writer,
+        r#"    <package name="{}" line-rate="{}" branch-rate="{}" complexity="0">"#,
+        xml_escape(package_name),
+        line_rate,
+        branch_rate
+    )?;
+    writeln!(output_writer, "      <classes>")?;
+    for (file, lines) in files.iter().zip(file_lines.iter()) {
+        let branches = file.branches();
+        writeln!(
+            output_writer,
+            r#"        <class name="{}" filename="{}" line-rate="{}" branch-rate="{}" complexity="0">"#,
+            format_module_id(&file.module_id),
+            xml_escape(&file.source_path.display().to_string()),
+            rate_of_lines(lines),
+            rate_of_branches(branches.iter().copied()),
+        )?;
+        writeln!(output_writer, "          <methods>")?;
+        for function in &file.functions {
+            writeln!(
+                output_writer,
+                r#"            <method name="{}" signature="" line-rate="{}" branch-rate="{}" complexity="0">"#,
+                function.name,
+                rate_of_lines(&function.lines),
+                rate_of_branches(function.branches.iter()),
+            )?;
+            output_cobertura_lines(
+                &function.lines,
+                &function.branches.iter().collect::<Vec<_>>(),
+                "              ",
+                output_writer,
+            )?;
+            writeln!(output_writer, "            </method>")?;
+        }
+        writeln!(output_writer, "          </methods>")?;
+        output_cobertura_lines(lines, &branches, "          ", output_writer)?;
+        writeln!(output_writer, "        </class>")?;
+    }
+    writeln!(output_writer, "      </classes>")?;
+    writeln!(output_writer, "    </package>")?;
+    writeln!(output_writer, "  </packages>")?;
+    writeln!(output_writer, "</coverage>")?;
+    Ok(())
+}
+
+fn output_cobertura_lines<W: Write>(
+    lines: &BTreeMap<u32, u64>,
+    branches: &[&BranchLineCoverage],
+    indent: &str,
+    output_writer: &mut W,
+) -> io::Result<()> {
+    writeln!(output_writer, "{}<lines>", indent)?;
+    for (line, count) in lines {
+        let line_branches: Vec<_> = branches
+            .iter()
+            .copied()
+            .filter(|branch| branch.line == *line)
+            .collect();
+        if line_branches.is_empty() {
+            writeln!(
+                output_writer,
+                r#"{}  <line number="{}" hits="{}" branch="false"/>"#,
+                indent, line, count
+            )?;
+        } else {
+            let (covered, valid) = branch_counts(line_branches.into_iter());
+            writeln!(
+                output_writer,
+                r#"{}  <line number="{}" hits="{}" branch="true" condition-coverage="{}% ({}/{})"/>"#,
+                indent,
+                line,
+                count,
+                covered * 100 / valid,
+                covered,
+                valid
+            )?;
+        }
+    }
+    writeln!(output_writer, "{}</lines>", indent)
+}
+
+fn line_counts<'a>(counts: impl Iterator<Item = &'a u64>) -> (usize, usize) {
+    counts.fold((0, 0), |(covered, valid), count| {
+        (covered + (*count > 0) as usize, valid + 1)
+    })
+}
+
+fn branch_counts<'a>(branches: impl Iterator<Item = &'a BranchLineCoverage>) -> (usize, usize) {
+    branches.fold((0, 0), |(covered, valid), branch| {
+        (covered + branch.arms_taken(), valid + branch.arms.len())
+    })
+}
+
+fn rate_of_lines(lines: &BTreeMap<u32, u64>) -> String {
+    let (covered, valid) = line_counts(lines.values());
+    rate(covered, valid)
+}
+
+fn rate_of_branches<'a>(branches: impl Iterator<Item = &'a BranchLineCoverage>) -> String {
+    let (covered, valid) = branch_counts(branches);
+    rate(covered, valid)
+}
+
+/// The ratio `covered / valid` as used in Cobertura reports, which is 1 if there is nothing to
+/// cover.
+fn rate(covered: usize, valid: usize) -> String {
+    if valid == 0 {
+        "1".to_string()
+    } else {
+        format!("{:.4}", covered as f64 / valid as f64)
+    }
+}
+
+fn format_module_id(module_id: &ModuleId) -> String {
+    format!(
+        "0x{}::{}",
+        module_id.address().short_str_lossless(),
+        module_id.name()
+    )
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// The kinds of the conditional branches in the source file at `file_path`, by the start and end
+/// of the expression they are compiled from. The compiler attributes the branch of an `if`,
+/// `while` or `assert!` to the whole expression, and that of a short-circuiting `&&` or `||` to the
+/// binary operation.
+fn source_branch_kinds(file_path: &Path) -> Result<BTreeMap<(u32, u32), BranchKind>> {
+    let path = file_path.to_string_lossy().to_string();
+    let (_, res) = Compiler::from_files(
+        vec![path],
+        vec![],
+        BTreeMap::<String, NumericalAddress>::new(),
+    )
+    .run::<PASS_PARSER>()?;
+    let (_, compiler) =
+        res.map_err(|_| anyhow!("Unable to parse source file {}", file_path.display()))?;
+    let (_, program) = compiler.into_ast();
+
+    let mut kinds = BTreeMap::new();
+    for package_def in &program.source_definitions {
+        match &package_def.def {
+            P::Definition::Module(module) => module_branch_kinds(&mut kinds, module),
+            P::Definition::Address(address_def) => {
+                for module in &address_def.modules {
+                    module_branch_kinds(&mut kinds, module)
+                }
+            }
+            P::Definition::Script(script) => function_branch_kinds(&mut kinds, &script.function),
+        }
+    }
+    Ok(kinds)
+}
+
+fn module_branch_kinds(kinds: &mut BTreeMap<(u32, u32), BranchKind>, module: &P::ModuleDefinition) {
+    for member in &module.members {
+        if let P::ModuleMember::Function(function) = member {
+            function_branch_kinds(kinds, function)
+        }
+    }
+}
+
+fn function_branch_kinds(kinds: &mut BTreeMap<(u32, u32), BranchKind>, function: &P::Function) {
+    if let P::FunctionBody_::Defined(seq) = &function.body.value {
+        sequence_branch_kinds(kinds, seq)
+    }
+}
+
+fn sequence_branch_kinds(
+    kinds: &mut BTreeMap<(u32, u32), BranchKind>,
+    (_, items, _, last): &P::Sequence,
+) {
+    for item in items {
+        match &item.value {
+            P::SequenceItem_::Seq(e) | P::SequenceItem_::Bind(_, _, e) => {
+                exp_branch_kinds(kinds, e)
+            }
+            P::SequenceItem_::Declare(_, _) => (),
+        }
+    }
+    if let Some(e) = last.as_ref() {
+        exp_branch_kinds(kinds, e)
+    }
+}
+
+fn exp_branch_kinds(kinds: &mut BTreeMap<(u32, u32), BranchKind>, e: &P::Exp) {
+    use P::Exp_ as E;
+    let mut add = |kind| {
+        kinds.insert((e.loc.start(), e.loc.end()), kind);
+    };
+    match &e.value {
+        E::IfElse(econd, et, ef_opt) => {
+            add(BranchKind::If);
+            exp_branch_kinds(kinds, econd);
+            exp_branch_kinds(kinds, et);
+            if let Some(ef) = ef_opt {
+                exp_branch_kinds(kinds, ef)
+            }
+        }
+        E::While(econd, ebody) => {
+            add(BranchKind::While);
+            exp_branch_kinds(kinds, econd);
+            exp_branch_kinds(kinds, ebody)
+        }
+        E::BinopExp(el, op, er) => {
+            match op.value {
+                BinOp_::And => add(BranchKind::And),
+                BinOp_::Or => add(BranchKind::Or),
+                _ => (),
+            }
+            exp_branch_kinds(kinds, el);
+            exp_branch_kinds(kinds, er)
+        }
+        E::Call(name, is_macro, _, args) => {
+            if let sp!(_, NameAccessChain_::One(n)) = name {
+                if *is_macro && n.value.as_str() == BuiltinFunction_::ASSERT_MACRO {
+                    add(BranchKind::Assert)
+                }
+            }
+            args.value.iter().for_each(|e| exp_branch_kinds(kinds, e))
+        }
+
+        E::Pack(_, _, fields) => fields.iter().for_each(|(_, e)| exp_branch_kinds(kinds, e)),
+        E::Vector(_, _, sp!(_, es)) | E::ExpList(es) => {
+            es.iter().for_each(|e| exp_branch_kinds(kinds, e))
+        }
+        E::Block(seq) => sequence_branch_kinds(kinds, seq),
+        E::Assign(el, er) | E::Index(el, er) => {
+            exp_branch_kinds(kinds, el);
+            exp_branch_kinds(kinds, er)
+        }
+        E::Return(Some(e))
+        | E::Loop(e)
+        | E::Abort(e)
+        | E::Dereference(e)
+        | E::UnaryExp(_, e)
+        | E::Borrow(_, e)
+        | E::Dot(e, _)
+        | E::Cast(e, _)
+        | E::Annotate(e, _) => exp_branch_kinds(kinds, e),
+
+        // Specifications are not compiled to bytecode
+        E::Spec(_) | E::Lambda(_, _) | E::Quant(_, _, _, _, _) => (),
+        E::Value(_)
+        | E::Move(_)
+        | E::Copy(_)
+        | E::Name(_, _)
+        | E::Unit
+        | E::Return(None)
+        | E::Break
+        | E::Continue
+        | E::UnresolvedError => (),
+    }
+}
+
 fn merge_spans(cov: FunctionSourceCoverage) -> Vec<Span> {
     if cov.uncovered_locations.is_empty() {
         return vec![];
@@ -0,0 +1,412 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The expressions and statements the decompiler reconstructs, and their rendering as Move
+//! source. Names of variables, functions and types are already resolved to strings when the
+//! tree is built, so rendering only needs to take care of precedence and layout.
+
+use std::fmt::Write;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+    BitOr,
+    BitAnd,
+    Xor,
+    Shl,
+    Shr,
+    Lt,
+    Gt,
+    Le,
+    Ge,
+    Or,
+    And,
+    Eq,
+    Neq,
+}
+
+impl BinOp {
+    fn symbol(self) -> &'static str {
+        use BinOp::*;
+        match self {
+            Add => "+",
+            Sub => "-",
+            Mul => "*",
+            Div => "/",
+            Mod => "%",
+            BitOr => "|",
+            BitAnd => "&",
+            Xor => "^",
+            Shl => "<<",
+            Shr => ">>",
+            Lt => "<",
+            Gt => ">",
+            Le => "<=",
+            Ge => ">=",
+            Or => "||",
+            And => "&&",
+            Eq => "==",
+            Neq => "!=",
+        }
+    }
+
+    /// The precedence of the operator, as assigned by the Move parser.
+    fn precedence(self) -> u8 {
+        use BinOp::*;
+        match self {
+            Or => 3,
+            And => 4,
+            Eq | Neq | Lt | Gt | Le | Ge => 5,
+            BitOr => 7,
+            Xor => 8,
+            BitAnd => 9,
+            Shl | Shr => 10,
+            Add | Sub => 11,
+            Mul | Div | Mod => 12,
+        }
+    }
+
+    fn is_comparison(self) -> bool {
+        self.precedence() == 5
+    }
+
+    /// The comparison which holds exactly if this one does not, if this is a comparison.
+    fn negated(self) -> Option<BinOp> {
+        use BinOp::*;
+        match self {
+            Eq => Some(Neq),
+            Neq => Some(Eq),
+            Lt => Some(Ge),
+            Ge => Some(Lt),
+            Gt => Some(Le),
+            Le => Some(Gt),
+            _ => None,
+        }
+    }
+}
+
+const UNARY_PRECEDENCE: u8 = 20;
+const ATOM_PRECEDENCE: u8 = 30;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Exp {
+    /// A local variable.
+    Var(String),
+    /// A literal, already rendered.
+    Value(String),
+    /// A call of a function or builtin, with the callee (including type arguments) rendered.
+    Call(String, Vec<Exp>),
+    /// A struct value, with the struct (including type arguments) rendered.
+    Pack(String, Vec<(String, Exp)>),
+    /// A vector literal, with the element type rendered.
+    Vector(String, Vec<Exp>),
+    Borrow(bool, Box<Exp>),
+    Field(Box<Exp>, String),
+    Deref(Box<Exp>),
+    Not(Box<Exp>),
+    Binary(BinOp, Box<Exp>, Box<Exp>),
+    Cast(Box<Exp>, &'static str),
+}
+
+impl Exp {
+    /// Dereferences `e`, folding `*&p` into `p`.
+    pub fn deref(e: Exp) -> Exp {
+        match e {
+            Exp::Borrow(_, place) => *place,
+            e => Exp::Deref(Box::new(e)),
+        }
+    }
+
+    /// Borrows a field of the struct `e` refers to. As field access dereferences
+    /// automatically, a borrow `&p` is accessed as `p`.
+    pub fn borrow_field(is_mut: bool, e: Exp, field: String) -> Exp {
+        let base = match e {
+            Exp::Borrow(_, place) => *place,
+            e => e,
+        };
+        Exp::Borrow(is_mut, Box::new(Exp::Field(Box::new(base), field)))
+    }
+
+    /// Freezes the mutable reference `e`, folding `freeze(&mut p)` into `&p`.
+    pub fn freeze(e: Exp) -> Exp {
+        match e {
+            Exp::Borrow(_, place) => Exp::Borrow(false, place),
+            e => Exp::Call("freeze".to_string(), vec![e]),
+        }
+    }
+
+    /// The boolean negation of `e`, simplified where possible.
+    pub fn negate(e: Exp) -> Exp {
+        match e {
+            Exp::Not(e) => *e,
+            Exp::Binary(op, l, r) if op.negated().is_some() => {
+                Exp::Binary(op.negated().unwrap(), l, r)
+            }
+            e => Exp::Not(Box::new(e)),
+        }
+    }
+
+    fn precedence(&self) -> u8 {
+        match self {
+            Exp::Binary(op, ..) => op.precedence(),
+            Exp::Borrow(..) | Exp::Deref(_) | Exp::Not(_) => UNARY_PRECEDENCE,
+            _ => ATOM_PRECEDENCE,
+        }
+    }
+
+    fn render_operand(&self, out: &mut String, parenthesize: bool) {
+        if parenthesize {
+            out.push('(');
+            self.render(out);
+            out.push(')');
+        } else {
+            self.render(out);
+        }
+    }
+
+    pub fn render(&self, out: &mut String) {
+        match self {
+            Exp::Var(name) | Exp::Value(name) => out.push_str(name),
+            Exp::Call(callee, args) => {
+                out.push_str(callee);
+                out.push('(');
+                render_list(out, args);
+                out.push(')');
+            }
+            Exp::Pack(name, fields) => {
+                out.push_str(name);
+                if fields.is_empty() {
+                    out.push_str(" {}");
+                    return;
+                }
+                out.push_str(" { ");
+                for (i, (field, e)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        out.push_str(", ");
+                    }
+                    write!(out, "{}: ", field).unwrap();
+                    e.render(out);
+                }
+                out.push_str(" }");
+            }
+            Exp::Vector(ty, elems) => {
+                write!(out, "vector<{}>[", ty).unwrap();
+                render_list(out, elems);
+                out.push(']');
+            }
+            Exp::Borrow(is_mut, e) => {
+                out.push_str(if *is_mut { "&mut " } else { "&" });
+                e.render_operand(out, e.precedence() < UNARY_PRECEDENCE);
+            }
+            Exp::Field(e, field) => {
+                e.render_operand(out, e.precedence() < ATOM_PRECEDENCE);
+                write!(out, ".{}", field).unwrap();
+            }
+            Exp::Deref(e) => {
+                out.push('*');
+                e.render_operand(out, e.precedence() < UNARY_PRECEDENCE);
+            }
+            Exp::Not(e) => {
+                out.push('!');
+                e.render_operand(out, e.precedence() < UNARY_PRECEDENCE);
+            }
+            Exp::Binary(op, l, r) => {
+                // Binary operators are left associative, except for comparisons which do not
+                // associate at all.
+                let prec = op.precedence();
+                let l_parens =
+                    l.precedence() < prec || (op.is_comparison() && l.precedence() == prec);
+                l.render_operand(out, l_parens);
+                write!(out, " {} ", op.symbol()).unwrap();
+                r.render_operand(out, r.precedence() <= prec);
+            }
+            Exp::Cast(e, ty) => {
+                out.push('(');
+                e.render_operand(out, e.precedence() < UNARY_PRECEDENCE);
+                write!(out, " as {})", ty).unwrap();
+            }
+        }
+    }
+}
+
+fn render_list(out: &mut String, exps: &[Exp]) {
+    for (i, e) in exps.iter().enumerate() {
+        if i > 0 {
+            out.push_str(", ");
+        }
+        e.render(out);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Stmt {
+    /// `x = e;`, or `(x, y) = e;` for a call returning multiple values.
+    Assign(Vec<String>, Exp),
+    /// `S { f: x, g: y } = e;`
+    Unpack(String, Vec<(String, String)>, Exp),
+    /// `*r = e;`, or `p = e;` if the reference is a borrow `&p`.
+    WriteRef(Exp, Exp),
+    /// An expression evaluated for its effects.
+    Eval(Exp),
+    /// `let _ = e;`, dropping a value.
+    Drop(Exp),
+    Return(Vec<Exp>),
+    Abort(Exp),
+    Break,
+    Continue,
+    If(Exp, Vec<Stmt>, Vec<Stmt>),
+    While(Exp, Vec<Stmt>),
+    Loop(Vec<Stmt>),
+    /// The values a function body evaluates to, written without `return`.
+    Result(Vec<Exp>),
+}
+
+impl Stmt {
+    /// Whether control never continues after the statement.
+    pub fn diverges(&self) -> bool {
+        match self {
+            Stmt::Return(_) | Stmt::Abort(_) | Stmt::Break | Stmt::Continue => true,
+            Stmt::If(_, then, els) => diverges(then) && diverges(els),
+            _ => false,
+        }
+    }
+
+    /// Whether the statement is a control construct, which needs no `;` at the end of a block.
+    fn is_control(&self) -> bool {
+        matches!(
+            self,
+            Stmt::Return(_)
+                | Stmt::Abort(_)
+                | Stmt::Break
+                | Stmt::Continue
+                | Stmt::If(..)
+                | Stmt::While(..)
+                | Stmt::Loop(_)
+                | Stmt::Result(_)
+        )
+    }
+
+    fn render(&self, out: &mut String, indent: usize) {
+        match self {
+            Stmt::Assign(lhs, e) => {
+                if lhs.len() == 1 {
+                    out.push_str(&lhs[0]);
+                } else {
+                    write!(out, "({})", lhs.join(", ")).unwrap();
+                }
+                out.push_str(" = ");
+                e.render(out);
+            }
+            Stmt::Unpack(name, fields, e) => {
+                out.push_str(name);
+                if fields.is_empty() {
+                    out.push_str(" {}");
+                } else {
+                    let fields = fields
+                        .iter()
+                        .map(|(f, x)| format!("{}: {}", f, x))
+                        .collect::<Vec<_>>();
+                    write!(out, " {{ {} }}", fields.join(", ")).unwrap();
+                }
+                out.push_str(" = ");
+                e.render(out);
+            }
+            Stmt::WriteRef(r, e) => {
+                match r {
+                    Exp::Borrow(_, place) => place.render(out),
+                    r => Exp::Deref(Box::new(r.clone())).render(out),
+                }
+                out.push_str(" = ");
+                e.render(out);
+            }
+            Stmt::Eval(e) => e.render(out),
+            Stmt::Drop(e) => {
+                out.push_str("let _ = ");
+                e.render(out);
+            }
+            Stmt::Return(es) => {
+                out.push_str("return");
+                if !es.is_empty() {
+                    out.push(' ');
+                    render_values(out, es);
+                }
+            }
+            Stmt::Abort(e) => {
+                out.push_str("abort ");
+                e.render(out);
+            }
+            Stmt::Break => out.push_str("break"),
+            Stmt::Continue => out.push_str("continue"),
+            Stmt::If(cond, then, els) => {
+                out.push_str("if (");
+                cond.render(out);
+                out.push_str(") ");
+                render_block(out, then, indent);
+                match els.as_slice() {
+                    [] => {}
+                    [nested @ Stmt::If(..)] => {
+                        out.push_str(" else ");
+                        nested.render(out, indent);
+                    }
+                    _ => {
+                        out.push_str(" else ");
+                        render_block(out, els, indent);
+                    }
+                }
+            }
+            Stmt::While(cond, body) => {
+                out.push_str("while (");
+                cond.render(out);
+                out.push_str(") ");
+                render_block(out, body, indent);
+            }
+            Stmt::Loop(body) => {
+                out.push_str("loop ");
+                render_block(out, body, indent);
+            }
+            Stmt::Result(es) => render_values(out, es),
+        }
+    }
+}
+
+pub(crate) fn diverges(stmts: &[Stmt]) -> bool {
+    matches!(stmts.last(), Some(stmt) if stmt.diverges())
+}
+
+fn render_values(out: &mut String, es: &[Exp]) {
+    if es.len() == 1 {
+        es[0].render(out);
+    } else {
+        out.push('(');
+        render_list(out, es);
+        out.push(')');
+    }
+}
+
+/// Renders `stmts` as a block `{ ... }`, whose statements are indented one level deeper than
+/// `indent`.
+pub(crate) fn render_block(out: &mut String, stmts: &[Stmt], indent: usize) {
+    if stmts.is_empty() {
+        out.push_str("{}");
+        return;
+    }
+    out.push_str("{\n");
+    render_stmts(out, stmts, indent + 1);
+    write!(out, "{}}}", "    ".repeat(indent)).unwrap();
+}
+
+/// Renders `stmts` one per line at the given indentation level.
+pub(crate) fn render_stmts(out: &mut String, stmts: &[Stmt], indent: usize) {
+    for (i, stmt) in stmts.iter().enumerate() {
+        out.push_str(&"    ".repeat(indent));
+        stmt.render(out, indent);
+        if i + 1 < stmts.len() || !stmt.is_control() {
+            out.push(';');
+        }
+        out.push('\n');
+    }
+}
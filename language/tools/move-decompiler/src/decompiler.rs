@@ -0,0 +1,1260 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    ast::{render_stmts, BinOp, Exp, Stmt},
+    structure::{self, Block, BlockId, Terminator},
+};
+use anyhow::{bail, Result};
+use move_binary_format::{
+    access::ModuleAccess,
+    file_format::{
+        Ability, AbilitySet, Bytecode as MoveBytecode, CompiledModule, Constant as MoveConstant,
+        SignatureToken, StructDefinitionIndex, StructFieldInformation, Visibility,
+    },
+};
+use move_bytecode_source_map::source_map::{SourceMap, SourceName};
+use move_bytecode_utils::Modules;
+use move_core_types::{
+    account_address::AccountAddress,
+    identifier::Identifier,
+    language_storage::ModuleId,
+    value::{MoveTypeLayout, MoveValue},
+};
+use move_model::{
+    model::{FunId, FunctionEnv, GlobalEnv, ModuleEnv, ModuleId as ModelModuleId, StructId},
+    run_bytecode_model_builder,
+    ty::{PrimitiveType, Type},
+};
+use move_stackless_bytecode::{
+    stackless_bytecode::{Bytecode, Constant, Label, Operation},
+    stackless_bytecode_generator::StacklessBytecodeGenerator,
+};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write,
+};
+
+/// Reconstructs Move source code from a compiled module.
+///
+/// Functions are decompiled from their stackless bytecode: values the bytecode passes on the
+/// operand stack are folded back into nested expressions, and loops and conditionals are
+/// recovered from the control flow graph. Locals and type parameters are named after the source
+/// map if one is provided. A function whose control flow cannot be expressed in Move, which
+/// can only be the case for bytecode not produced by the Move compiler, is emitted with a
+/// comment and a body which aborts, so the output still compiles.
+pub struct Decompiler<'a> {
+    module: &'a CompiledModule,
+    dependencies: Vec<&'a CompiledModule>,
+    source_map: Option<&'a SourceMap>,
+}
+
+impl<'a> Decompiler<'a> {
+    /// Creates a decompiler for `module`. The `dependencies` must include all modules `module`
+    /// depends on, directly or transitively, and `0x1::vector` if the module uses vector
+    /// instructions. Other modules are ignored.
+    pub fn new(
+        module: &'a CompiledModule,
+        dependencies: impl IntoIterator<Item = &'a CompiledModule>,
+    ) -> Self {
+        Self {
+            module,
+            dependencies: dependencies.into_iter().collect(),
+            source_map: None,
+        }
+    }
+
+    /// Uses the names of `source_map` for locals and type parameters.
+    pub fn with_source_map(mut self, source_map: &'a SourceMap) -> Self {
+        self.source_map = Some(source_map);
+        self
+    }
+
+    /// Returns the source code of the module.
+    pub fn decompile(&self) -> Result<String> {
+        let (module, placeholders) = replace_vector_constants(self.module)?;
+        let modules = self.collect_modules(&module)?;
+        let modules = Modules::new(modules);
+        let dependency_graph = modules.compute_dependency_graph();
+        let env = run_bytecode_model_builder(dependency_graph.compute_topological_order()?)?;
+        let self_id = self.module.self_id();
+        let module_env = env
+            .get_modules()
+            .find(|module_env| module_env.get_verified_module().self_id() == self_id)
+            .expect("module is part of the environment");
+        ModuleRenderer::new(&env, module_env, &module, self.source_map, placeholders).render()
+    }
+
+    /// Collects `module`, which stands for the decompiled module, and its dependencies from
+    /// which the model is built.
+    fn collect_modules<'m>(
+        &'m self,
+        module: &'m CompiledModule,
+    ) -> Result<Vec<&'m CompiledModule>> {
+        let self_id = module.self_id();
+        let mut available = self
+            .dependencies
+            .iter()
+            .map(|module| (module.self_id(), *module))
+            .collect::<BTreeMap<_, _>>();
+        available.insert(self_id.clone(), module);
+
+        let mut collected = BTreeSet::from([self_id.clone()]);
+        let mut todo = vec![module];
+        while let Some(module) = todo.pop() {
+            for handle in module.module_handles() {
+                let id = module.module_id_for_handle(handle);
+                if collected.insert(id.clone()) {
+                    match available.get(&id) {
+                        Some(dependency) => todo.push(dependency),
+                        None => bail!("missing dependency `{}` of `{}`", id, module.self_id()),
+                    }
+                }
+            }
+        }
+        // Vector instructions are represented as calls of the functions of `0x1::vector`.
+        let vector_id = vector_module_id();
+        if uses_vector_instructions(module)
+            && collected.insert(vector_id.clone())
+            && !available.contains_key(&vector_id)
+        {
+            bail!(
+                "missing dependency `{}`, which is required to decompile vector instructions",
+                vector_id
+            )
+        }
+        Ok(collected.iter().map(|id| available[id]).collect())
+    }
+}
+
+/// The stackless bytecode only supports vector constants of bytes and addresses. Replaces all
+/// other vector constants of `module` by distinct byte vectors, returning the patched module and
+/// a map from each placeholder to the type and rendered value of the constant it replaces.
+fn replace_vector_constants(
+    module: &CompiledModule,
+) -> Result<(CompiledModule, BTreeMap<Vec<u8>, (SignatureToken, String)>)> {
+    let mut module = module.clone();
+    let existing = module
+        .constant_pool
+        .iter()
+        .map(|constant| constant.data.clone())
+        .collect::<BTreeSet<_>>();
+    let byte_vector = MoveTypeLayout::Vector(Box::new(MoveTypeLayout::U8));
+    let mut placeholders = BTreeMap::new();
+    for (idx, constant) in module.constant_pool.iter_mut().enumerate() {
+        match &constant.type_ {
+            SignatureToken::Vector(elem)
+                if !matches!(**elem, SignatureToken::U8 | SignatureToken::Address) => {}
+            _ => continue,
+        }
+        let value = match constant.deserialize_constant() {
+            Some(value) => value,
+            None => bail!("malformed constant at index {}", idx),
+        };
+        let mut bytes = format!("decompiler placeholder {}", idx).into_bytes();
+        let placeholder = loop {
+            let placeholder = MoveConstant::serialize_constant(
+                &byte_vector,
+                &MoveValue::vector_u8(bytes.clone()),
+            )
+            .expect("byte vectors can be serialized");
+            if !existing.contains(&placeholder.data) {
+                break placeholder;
+            }
+            bytes.push(b'_');
+        };
+        let rendered = render_move_value(&value, &constant.type_);
+        let type_ = std::mem::replace(constant, placeholder).type_;
+        placeholders.insert(bytes, (type_, rendered));
+    }
+    Ok((module, placeholders))
+}
+
+/// Renders a value of a constant of type `ty`.
+fn render_move_value(value: &MoveValue, ty: &SignatureToken) -> String {
+    match (value, ty) {
+        (MoveValue::Vector(elems), SignatureToken::Vector(elem_ty)) => {
+            if let SignatureToken::U8 = **elem_ty {
+                let hex = elems
+                    .iter()
+                    .map(|elem| match elem {
+                        MoveValue::U8(byte) => format!("{:02x}", byte),
+                        _ => unreachable!("elements of a byte vector are bytes"),
+                    })
+                    .collect::<String>();
+                return format!("x\"{}\"", hex);
+            }
+            let elems = elems
+                .iter()
+                .map(|elem| render_move_value(elem, elem_ty))
+                .collect::<Vec<_>>();
+            format!(
+                "vector<{}>[{}]",
+                render_signature_token(elem_ty),
+                elems.join(", ")
+            )
+        }
+        (MoveValue::Bool(b), _) => b.to_string(),
+        (MoveValue::U8(n), _) => format!("{}u8", n),
+        (MoveValue::U16(n), _) => format!("{}u16", n),
+        (MoveValue::U32(n), _) => format!("{}u32", n),
+        (MoveValue::U64(n), _) => n.to_string(),
+        (MoveValue::U128(n), _) => format!("{}u128", n),
+        (MoveValue::U256(n), _) => format!("{}u256", n),
+        (MoveValue::Address(addr), _) => format!("@0x{:x}", addr),
+        (value, _) => unreachable!("constants cannot hold {:?}", value),
+    }
+}
+
+/// Renders the type of a constant.
+fn render_signature_token(ty: &SignatureToken) -> String {
+    match ty {
+        SignatureToken::Bool => "bool".to_string(),
+        SignatureToken::U8 => "u8".to_string(),
+        SignatureToken::U16 => "u16".to_string(),
+        SignatureToken::U32 => "u32".to_string(),
+        SignatureToken::U64 => "u64".to_string(),
+        SignatureToken::U128 => "u128".to_string(),
+        SignatureToken::U256 => "u256".to_string(),
+        SignatureToken::Address => "address".to_string(),
+        SignatureToken::Vector(elem) => format!("vector<{}>", render_signature_token(elem)),
+        ty => unreachable!("constants cannot have type {:?}", ty),
+    }
+}
+
+fn vector_module_id() -> ModuleId {
+    ModuleId::new(AccountAddress::ONE, Identifier::new("vector").unwrap())
+}
+
+fn uses_vector_instructions(module: &CompiledModule) -> bool {
+    use MoveBytecode::*;
+    module
+        .function_defs()
+        .iter()
+        .filter_map(|def| def.code.as_ref())
+        .flat_map(|code| &code.code)
+        .any(|bc| {
+            matches!(
+                bc,
+                VecPack(..)
+                    | VecLen(_)
+                    | VecImmBorrow(_)
+                    | VecMutBorrow(_)
+                    | VecPushBack(_)
+                    | VecPopBack(_)
+                    | VecUnpack(..)
+                    | VecSwap(_)
+            )
+        })
+}
+
+/// Renders a module of the model as source code.
+struct ModuleRenderer<'env> {
+    env: &'env GlobalEnv,
+    module_env: ModuleEnv<'env>,
+    module: &'env CompiledModule,
+    source_map: Option<&'env SourceMap>,
+    /// The vector constants replaced by placeholders, see `replace_vector_constants`.
+    placeholders: BTreeMap<Vec<u8>, (SignatureToken, String)>,
+    /// The aliases of the modules referenced by the module.
+    aliases: RefCell<BTreeMap<ModuleId, String>>,
+    /// The modules for which a `use` declaration is needed.
+    used_modules: RefCell<BTreeSet<ModuleId>>,
+}
+
+impl<'env> ModuleRenderer<'env> {
+    fn new(
+        env: &'env GlobalEnv,
+        module_env: ModuleEnv<'env>,
+        module: &'env CompiledModule,
+        source_map: Option<&'env SourceMap>,
+        placeholders: BTreeMap<Vec<u8>, (SignatureToken, String)>,
+    ) -> Self {
+        let renderer = Self {
+            env,
+            module_env,
+            module,
+            source_map,
+            placeholders,
+            aliases: RefCell::new(BTreeMap::new()),
+            used_modules: RefCell::new(BTreeSet::new()),
+        };
+        // Assign aliases in the order of the module handles, so they do not depend on the order
+        // in which the module refers to them.
+        for handle in module.module_handles() {
+            let id = module.module_id_for_handle(handle);
+            if id != module.self_id() {
+                renderer.assign_alias(&id);
+            }
+        }
+        renderer
+    }
+
+    fn render(&self) -> Result<String> {
+        let module = self.module;
+        let mut items = vec![];
+        for handle in module.friend_decls() {
+            let id = module.module_id_for_handle(handle);
+            items.push(format!("    friend {};\n", render_module_id(&id)));
+        }
+        for idx in 0..module.struct_defs().len() {
+            items.push(self.render_struct(StructDefinitionIndex(idx as u16)));
+        }
+        let mut functions = self.module_env.get_functions().collect::<Vec<_>>();
+        functions.sort_by_key(|fun| fun.get_def_idx());
+        for fun in &functions {
+            items.push(self.render_function(fun));
+        }
+
+        let mut uses = String::new();
+        for id in self.used_modules.borrow().iter() {
+            let alias = &self.aliases.borrow()[id];
+            if alias.as_str() == id.name().as_str() {
+                writeln!(uses, "    use {};", render_module_id(id))?;
+            } else {
+                writeln!(uses, "    use {} as {};", render_module_id(id), alias)?;
+            }
+        }
+        if !uses.is_empty() {
+            items.insert(0, uses);
+        }
+
+        let mut out = format!("module {} {{\n", render_module_id(&module.self_id()));
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 {
+                out.push('\n');
+            }
+            out.push_str(item);
+        }
+        out.push_str("}\n");
+        Ok(out)
+    }
+
+    /// Returns the alias under which the module is referenced, and records that a `use`
+    /// declaration is needed for it.
+    fn alias(&self, id: &ModuleId) -> String {
+        self.used_modules.borrow_mut().insert(id.clone());
+        self.assign_alias(id)
+    }
+
+    fn assign_alias(&self, id: &ModuleId) -> String {
+        let mut aliases = self.aliases.borrow_mut();
+        if let Some(alias) = aliases.get(id) {
+            return alias.clone();
+        }
+        let self_name = self.module.self_id().name().to_string();
+        let mut alias = id.name().to_string();
+        let mut suffix = 1;
+        while alias == self_name || aliases.values().any(|other| other == &alias) {
+            alias = format!("{}_{}", id.name(), suffix);
+            suffix += 1;
+        }
+        aliases.insert(id.clone(), alias.clone());
+        alias
+    }
+
+    /// Returns `name`, qualified with the alias of the module `module_id` if that is not the
+    /// module being rendered.
+    fn qualify(&self, module_id: ModelModuleId, name: &str) -> String {
+        if module_id == self.module_env.get_id() {
+            return name.to_string();
+        }
+        let id = self
+            .env
+            .get_module(module_id)
+            .get_verified_module()
+            .self_id();
+        format!("{}::{}", self.alias(&id), name)
+    }
+
+    fn struct_name(&self, module_id: ModelModuleId, struct_id: StructId) -> String {
+        let name = struct_id.symbol();
+        self.qualify(module_id, &self.env.symbol_pool().string(name))
+    }
+
+    fn field_name(&self, module_id: ModelModuleId, struct_id: StructId, offset: usize) -> String {
+        let module_env = self.env.get_module(module_id);
+        let struct_env = module_env.get_struct(struct_id);
+        let name = struct_env.get_field_by_offset(offset).get_name();
+        self.env.symbol_pool().string(name).to_string()
+    }
+
+    fn fun_name(&self, module_id: ModelModuleId, fun_id: FunId) -> String {
+        self.qualify(module_id, &self.env.symbol_pool().string(fun_id.symbol()))
+    }
+
+    fn render_type(&self, ty: &Type, type_params: &[String]) -> String {
+        match ty {
+            Type::Primitive(prim) => match prim {
+                PrimitiveType::Bool => "bool",
+                PrimitiveType::U8 => "u8",
+                PrimitiveType::U16 => "u16",
+                PrimitiveType::U32 => "u32",
+                PrimitiveType::U64 => "u64",
+                PrimitiveType::U128 => "u128",
+                PrimitiveType::U256 => "u256",
+                PrimitiveType::Address => "address",
+                PrimitiveType::Signer => "signer",
+                PrimitiveType::Num | PrimitiveType::Range | PrimitiveType::EventStore => {
+                    unreachable!("specification type in bytecode")
+                }
+            }
+            .to_string(),
+            Type::Vector(elem) => format!("vector<{}>", self.render_type(elem, type_params)),
+            Type::Struct(module_id, struct_id, tys) => format!(
+                "{}{}",
+                self.struct_name(*module_id, *struct_id),
+                self.render_type_args(tys, type_params)
+            ),
+            Type::TypeParameter(idx) => type_params[*idx as usize].clone(),
+            Type::Reference(is_mut, ty) => format!(
+                "&{}{}",
+                if *is_mut { "mut " } else { "" },
+                self.render_type(ty, type_params)
+            ),
+            _ => unreachable!("unexpected type in bytecode: {:?}", ty),
+        }
+    }
+
+    fn render_type_args(&self, tys: &[Type], type_params: &[String]) -> String {
+        if tys.is_empty() {
+            return String::new();
+        }
+        let tys = tys
+            .iter()
+            .map(|ty| self.render_type(ty, type_params))
+            .collect::<Vec<_>>();
+        format!("<{}>", tys.join(", "))
+    }
+
+    fn render_struct(&self, idx: StructDefinitionIndex) -> String {
+        let module = self.module;
+        let def = module.struct_def_at(idx);
+        let handle = module.struct_handle_at(def.struct_handle);
+        let names = self
+            .source_map
+            .and_then(|source_map| source_map.get_struct_source_map(idx).ok())
+            .map(|struct_map| struct_map.type_parameters.as_slice());
+        let type_params = type_param_names(names, handle.type_parameters.len());
+
+        let mut out = String::from("    ");
+        if matches!(def.field_information, StructFieldInformation::Native) {
+            out.push_str("native ");
+        }
+        write!(out, "struct {}", module.identifier_at(handle.name)).unwrap();
+        if !handle.type_parameters.is_empty() {
+            let params = handle
+                .type_parameters
+                .iter()
+                .zip(&type_params)
+                .map(|(param, name)| {
+                    let phantom = if param.is_phantom { "phantom " } else { "" };
+                    format!(
+                        "{}{}{}",
+                        phantom,
+                        name,
+                        render_constraints(param.constraints)
+                    )
+                })
+                .collect::<Vec<_>>();
+            write!(out, "<{}>", params.join(", ")).unwrap();
+        }
+        if handle.abilities != AbilitySet::EMPTY {
+            write!(out, " has {}", render_abilities(handle.abilities, ", ")).unwrap();
+        }
+        match &def.field_information {
+            StructFieldInformation::Native => out.push_str(";\n"),
+            StructFieldInformation::Declared(fields) => {
+                out.push_str(" {\n");
+                for field in fields {
+                    let ty = self.module_env.globalize_signature(&field.signature.0);
+                    writeln!(
+                        out,
+                        "        {}: {},",
+                        module.identifier_at(field.name),
+                        self.render_type(&ty, &type_params)
+                    )
+                    .unwrap();
+                }
+                out.push_str("    }\n");
+            }
+        }
+        out
+    }
+
+    fn render_function(&self, fun: &FunctionEnv) -> String {
+        let module = self.module;
+        let def_idx = fun.get_def_idx();
+        let def = module.function_def_at(def_idx);
+        let handle = module.function_handle_at(def.function);
+        let function_map = self
+            .source_map
+            .and_then(|source_map| source_map.get_function_source_map(def_idx).ok());
+        let type_params = type_param_names(
+            function_map.map(|function_map| function_map.type_parameters.as_slice()),
+            handle.type_parameters.len(),
+        );
+
+        let mut out = String::from("    ");
+        match def.visibility {
+            Visibility::Public => out.push_str("public "),
+            Visibility::Friend => out.push_str("public(friend) "),
+            Visibility::Private => {}
+        }
+        if def.is_entry {
+            out.push_str("entry ");
+        }
+        if def.is_native() {
+            out.push_str("native ");
+        }
+        write!(out, "fun {}", module.identifier_at(handle.name)).unwrap();
+        if !handle.type_parameters.is_empty() {
+            let params = handle
+                .type_parameters
+                .iter()
+                .zip(&type_params)
+                .map(|(constraints, name)| format!("{}{}", name, render_constraints(*constraints)))
+                .collect::<Vec<_>>();
+            write!(out, "<{}>", params.join(", ")).unwrap();
+        }
+
+        let num_params = fun.get_parameter_count();
+        let mut names = NameAllocator::default();
+        let param_names = (0..num_params)
+            .map(|idx| {
+                let source_name = function_map.and_then(|map| map.parameters.get(idx));
+                names.allocate(source_name, || format!("arg{}", idx))
+            })
+            .collect::<Vec<_>>();
+        let params = fun
+            .get_parameter_types()
+            .iter()
+            .zip(&param_names)
+            .map(|(ty, name)| format!("{}: {}", name, self.render_type(ty, &type_params)))
+            .collect::<Vec<_>>();
+        write!(out, "({})", params.join(", ")).unwrap();
+        let return_types = fun
+            .get_return_types()
+            .iter()
+            .map(|ty| self.render_type(ty, &type_params))
+            .collect::<Vec<_>>();
+        match return_types.len() {
+            0 => {}
+            1 => write!(out, ": {}", return_types[0]).unwrap(),
+            _ => write!(out, ": ({})", return_types.join(", ")).unwrap(),
+        }
+        if !def.acquires_global_resources.is_empty() {
+            let acquires = def
+                .acquires_global_resources
+                .iter()
+                .map(|idx| {
+                    let handle = module.struct_handle_at(module.struct_def_at(*idx).struct_handle);
+                    module.identifier_at(handle.name).to_string()
+                })
+                .collect::<Vec<_>>();
+            write!(out, " acquires {}", acquires.join(", ")).unwrap();
+        }
+        if def.is_native() {
+            out.push_str(";\n");
+            return out;
+        }
+
+        let mut local_names = param_names;
+        for idx in num_params..fun.get_local_count() {
+            let source_name = function_map.and_then(|map| map.locals.get(idx - num_params));
+            local_names.push(names.allocate(source_name, || format!("local{}", idx)));
+        }
+        out.push(' ');
+        match FunctionDecompiler::new(self, fun, &type_params, local_names, names)
+            .and_then(|decompiler| decompiler.decompile())
+        {
+            Ok(body) => out.push_str(&body),
+            Err(err) => {
+                write!(
+                    out,
+                    "{{\n        // could not decompile: {}\n        abort 0\n    }}",
+                    err
+                )
+                .unwrap();
+            }
+        }
+        out.push('\n');
+        out
+    }
+
+    fn render_constant(&self, constant: &Constant) -> String {
+        match constant {
+            Constant::Bool(b) => b.to_string(),
+            Constant::U8(n) => format!("{}u8", n),
+            Constant::U16(n) => format!("{}u16", n),
+            Constant::U32(n) => format!("{}u32", n),
+            Constant::U64(n) => n.to_string(),
+            Constant::U128(n) => format!("{}u128", n),
+            Constant::U256(n) => format!("{}u256", n),
+            Constant::Address(addr) => format!("@0x{:x}", addr),
+            Constant::ByteArray(bytes) if self.placeholders.contains_key(bytes) => {
+                self.placeholders[bytes].1.clone()
+            }
+            Constant::ByteArray(bytes) => {
+                let hex = bytes
+                    .iter()
+                    .map(|byte| format!("{:02x}", byte))
+                    .collect::<String>();
+                format!("x\"{}\"", hex)
+            }
+            Constant::AddressArray(addrs) => {
+                let addrs = addrs
+                    .iter()
+                    .map(|addr| format!("@0x{:x}", addr))
+                    .collect::<Vec<_>>();
+                format!("vector<address>[{}]", addrs.join(", "))
+            }
+        }
+    }
+}
+
+fn render_module_id(id: &ModuleId) -> String {
+    format!("0x{}::{}", id.address().short_str_lossless(), id.name())
+}
+
+fn render_abilities(abilities: AbilitySet, separator: &str) -> String {
+    abilities
+        .into_iter()
+        .map(|ability| match ability {
+            Ability::Copy => "copy",
+            Ability::Drop => "drop",
+            Ability::Store => "store",
+            Ability::Key => "key",
+        })
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+fn render_constraints(constraints: AbilitySet) -> String {
+    if constraints == AbilitySet::EMPTY {
+        String::new()
+    } else {
+        format!(": {}", render_abilities(constraints, " + "))
+    }
+}
+
+/// Returns the names of type parameters, taken from the source map if available.
+fn type_param_names(source_names: Option<&[SourceName]>, count: usize) -> Vec<String> {
+    let mut names = NameAllocator::default();
+    (0..count)
+        .map(|idx| {
+            let source_name = source_names.and_then(|source_names| source_names.get(idx));
+            names.allocate(source_name, || format!("T{}", idx))
+        })
+        .collect()
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {}
+        _ => return false,
+    }
+    name != "_" && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Hands out unique variable names.
+#[derive(Default)]
+struct NameAllocator {
+    used: BTreeSet<String>,
+}
+
+impl NameAllocator {
+    /// Allocates a name based on the name from the source map, or on `fallback` if there is no
+    /// usable one. The compiler names locals like `x#1#0`, of which `x` is the source name.
+    fn allocate(
+        &mut self,
+        source_name: Option<&SourceName>,
+        fallback: impl FnOnce() -> String,
+    ) -> String {
+        let base = source_name
+            .map(|(name, _)| name.split('#').next().unwrap_or_default())
+            .filter(|name| is_identifier(name))
+            .map(str::to_string)
+            .unwrap_or_else(fallback);
+        let mut name = base.clone();
+        let mut suffix = 1;
+        while !self.used.insert(name.clone()) {
+            name = format!("{}_{}", base, suffix);
+            suffix += 1;
+        }
+        name
+    }
+}
+
+/// An instruction of the stackless bytecode, where the instructions a vector literal
+/// translates to are recombined.
+enum Instr<'c> {
+    Code(&'c Bytecode),
+    VecPack(usize, &'c Type, Vec<usize>),
+}
+
+impl<'c> Instr<'c> {
+    /// Returns the temporaries the instruction assigns and reads.
+    fn operands(&self) -> (Vec<usize>, Vec<usize>) {
+        match self {
+            Instr::Code(bc) => match bc {
+                Bytecode::Assign(_, dst, src, _) => (vec![*dst], vec![*src]),
+                Bytecode::Call(_, dsts, _, srcs, _) => (dsts.clone(), srcs.clone()),
+                Bytecode::Ret(_, srcs) => (vec![], srcs.clone()),
+                Bytecode::Load(_, dst, _) => (vec![*dst], vec![]),
+                Bytecode::Branch(_, _, _, cond) => (vec![], vec![*cond]),
+                Bytecode::Abort(_, code) => (vec![], vec![*code]),
+                _ => (vec![], vec![]),
+            },
+            Instr::VecPack(dst, _, srcs) => (vec![*dst], srcs.clone()),
+        }
+    }
+}
+
+/// Decompiles the body of a function.
+struct FunctionDecompiler<'r, 'env> {
+    renderer: &'r ModuleRenderer<'env>,
+    type_params: &'r [String],
+    /// The names of all locals and temporaries.
+    names: Vec<String>,
+    num_params: usize,
+    num_locals: usize,
+    local_types: Vec<Type>,
+    code: Vec<Bytecode>,
+}
+
+impl<'r, 'env> FunctionDecompiler<'r, 'env> {
+    fn new(
+        renderer: &'r ModuleRenderer<'env>,
+        fun: &'r FunctionEnv<'env>,
+        type_params: &'r [String],
+        mut names: Vec<String>,
+        mut allocator: NameAllocator,
+    ) -> Result<Self> {
+        let mut data = StacklessBytecodeGenerator::new(fun).generate_function();
+        // Temporaries holding a placeholder have the type of the constant it replaces.
+        for bc in &data.code {
+            if let Bytecode::Load(_, dst, Constant::ByteArray(bytes)) = bc {
+                if let Some((ty, _)) = renderer.placeholders.get(bytes) {
+                    data.local_types[*dst] = renderer.module_env.globalize_signature(ty);
+                }
+            }
+        }
+        let num_locals = names.len();
+        for idx in num_locals..data.local_types.len() {
+            names.push(allocator.allocate(None, || format!("t{}", idx)));
+        }
+        Ok(Self {
+            renderer,
+            type_params,
+            names,
+            num_params: fun.get_parameter_count(),
+            num_locals,
+            local_types: data.local_types,
+            code: data.code,
+        })
+    }
+
+    fn decompile(&self) -> Result<String> {
+        let (blocks, labels) = self.split_blocks();
+        let instrs = blocks
+            .iter()
+            .map(|range| self.recombine(&self.code[range.0..range.1]))
+            .collect::<Vec<_>>();
+
+        // Count definitions and uses of temporaries, and where they are.
+        let n = self.local_types.len();
+        let mut defs = vec![0; n];
+        let mut uses = vec![0; n];
+        let mut def_block = vec![usize::MAX; n];
+        let mut use_block = vec![usize::MAX; n];
+        for (block, block_instrs) in instrs.iter().enumerate() {
+            for instr in block_instrs {
+                let (dsts, srcs) = instr.operands();
+                for dst in dsts {
+                    defs[dst] += 1;
+                    def_block[dst] = block;
+                }
+                for src in srcs {
+                    uses[src] += 1;
+                    use_block[src] = block;
+                }
+            }
+        }
+        let foldable = (0..n)
+            .map(|idx| {
+                idx >= self.num_locals
+                    && defs[idx] == 1
+                    && uses[idx] == 1
+                    && def_block[idx] == use_block[idx]
+            })
+            .collect::<Vec<_>>();
+
+        let mut declared = BTreeSet::new();
+        let mut structured_blocks = vec![];
+        for (block, block_instrs) in instrs.iter().enumerate() {
+            let mut builder = BlockBuilder {
+                decompiler: self,
+                labels: &labels,
+                foldable: &foldable,
+                declared: &mut declared,
+                pending: vec![],
+                stmts: vec![],
+            };
+            let mut terminator = None;
+            let mut idx = 0;
+            while idx < block_instrs.len() && terminator.is_none() {
+                let (next_terminator, consumed) =
+                    builder.instr(&block_instrs[idx], &block_instrs[idx + 1..])?;
+                terminator = next_terminator;
+                idx += 1 + consumed;
+            }
+            // A block without a terminator falls through to the next one.
+            let terminator = match terminator {
+                Some(terminator) => terminator,
+                None if block + 1 < instrs.len() => {
+                    builder.flush();
+                    Terminator::Jump(block + 1)
+                }
+                None => bail!("code falls off the end of the function"),
+            };
+            structured_blocks.push(Block {
+                stmts: builder.stmts,
+                terminator,
+            });
+        }
+        let stmts = structure::structure(&structured_blocks, 0)?;
+
+        let mut out = String::from("{\n");
+        for idx in declared {
+            if idx >= self.num_params {
+                writeln!(
+                    out,
+                    "        let {}: {};",
+                    self.names[idx],
+                    self.renderer
+                        .render_type(&self.local_types[idx], self.type_params)
+                )?;
+            }
+        }
+        render_stmts(&mut out, &stmts, 2);
+        out.push_str("    }");
+        Ok(out)
+    }
+
+    /// Splits the code into basic blocks, returning the range of each block, and the block each
+    /// label starts.
+    fn split_blocks(&self) -> (Vec<(usize, usize)>, BTreeMap<Label, BlockId>) {
+        let mut blocks = vec![];
+        let mut labels = BTreeMap::new();
+        let mut start = 0;
+        for (offset, bc) in self.code.iter().enumerate() {
+            if let Bytecode::Label(_, label) = bc {
+                if offset > start {
+                    blocks.push((start, offset));
+                    start = offset;
+                }
+                labels.insert(*label, blocks.len());
+            }
+            if matches!(
+                bc,
+                Bytecode::Jump(..) | Bytecode::Branch(..) | Bytecode::Ret(..) | Bytecode::Abort(..)
+            ) {
+                blocks.push((start, offset + 1));
+                start = offset + 1;
+            }
+        }
+        if start < self.code.len() {
+            blocks.push((start, self.code.len()));
+        }
+        (blocks, labels)
+    }
+
+    fn is_vector_function(&self, op: &Operation, name: &str) -> bool {
+        match op {
+            Operation::Function(module_id, fun_id, _) => {
+                let env = self.renderer.env;
+                env.get_module(*module_id).get_verified_module().self_id() == vector_module_id()
+                    && env.symbol_pool().string(fun_id.symbol()).as_str() == name
+            }
+            _ => false,
+        }
+    }
+
+    /// Recombines the instructions of a block into `Instr`s. A vector literal is translated into
+    /// a call of `vector::empty`, a borrow of the new vector, and a call of `vector::push_back`
+    /// for each element.
+    fn recombine<'c>(&self, code: &'c [Bytecode]) -> Vec<Instr<'c>> {
+        let mut instrs = vec![];
+        let mut i = 0;
+        while i < code.len() {
+            if let Bytecode::Call(_, dsts, op @ Operation::Function(_, _, tys), srcs, _) = &code[i]
+            {
+                if srcs.is_empty() && dsts.len() == 1 && self.is_vector_function(op, "empty") {
+                    let vec = dsts[0];
+                    if let Some(Bytecode::Call(_, refs, Operation::BorrowLoc, borrowed, _)) =
+                        code.get(i + 1)
+                    {
+                        let mut elems = vec![];
+                        let mut j = i + 2;
+                        while let Some(Bytecode::Call(_, dsts, op, srcs, _)) = code.get(j) {
+                            if !(dsts.is_empty()
+                                && srcs.len() == 2
+                                && srcs[0] == refs[0]
+                                && self.is_vector_function(op, "push_back"))
+                            {
+                                break;
+                            }
+                            elems.push(srcs[1]);
+                            j += 1;
+                        }
+                        if vec >= self.num_locals
+                            && borrowed[0] == vec
+                            && refs[0] >= self.num_locals
+                            && !elems.is_empty()
+                        {
+                            instrs.push(Instr::VecPack(vec, &tys[0], elems));
+                            i = j;
+                            continue;
+                        }
+                    }
+                }
+            }
+            instrs.push(Instr::Code(&code[i]));
+            i += 1;
+        }
+        instrs
+    }
+}
+
+/// Translates the instructions of a basic block into statements. Values the bytecode keeps on
+/// the stack are pending until they are consumed by an instruction, which allows to fold them
+/// into its expression, as they are guaranteed to be used exactly once and in stack order.
+struct BlockBuilder<'d, 'r, 'env> {
+    decompiler: &'d FunctionDecompiler<'r, 'env>,
+    labels: &'d BTreeMap<Label, BlockId>,
+    foldable: &'d [bool],
+    /// The locals and temporaries which are referenced.
+    declared: &'d mut BTreeSet<usize>,
+    pending: Vec<(usize, Exp)>,
+    stmts: Vec<Stmt>,
+}
+
+impl<'d, 'r, 'env> BlockBuilder<'d, 'r, 'env> {
+    fn name(&mut self, idx: usize) -> String {
+        self.declared.insert(idx);
+        self.decompiler.names[idx].clone()
+    }
+
+    fn var(&mut self, idx: usize) -> Exp {
+        Exp::Var(self.name(idx))
+    }
+
+    fn render_type(&self, ty: &Type) -> String {
+        self.decompiler
+            .renderer
+            .render_type(ty, self.decompiler.type_params)
+    }
+
+    fn is_mut_ref(&self, idx: usize) -> bool {
+        matches!(self.decompiler.local_types[idx], Type::Reference(true, _))
+    }
+
+    /// Returns the expressions for `srcs`, which were pushed in this order.
+    fn args(&mut self, srcs: &[usize]) -> Vec<Exp> {
+        let mut args = vec![None; srcs.len()];
+        for (idx, src) in srcs.iter().enumerate().rev() {
+            match self.pending.last() {
+                Some((pending, _)) if pending == src => {
+                    args[idx] = self.pending.pop().map(|(_, exp)| exp);
+                }
+                _ => break,
+            }
+        }
+        args.into_iter()
+            .zip(srcs)
+            .map(|(arg, src)| arg.unwrap_or_else(|| self.var(*src)))
+            .collect()
+    }
+
+    /// Assigns the values still pending to their temporaries.
+    fn flush(&mut self) {
+        for (idx, exp) in std::mem::take(&mut self.pending) {
+            let name = self.name(idx);
+            self.stmts.push(Stmt::Assign(vec![name], exp));
+        }
+    }
+
+    fn emit(&mut self, stmt: Stmt) {
+        self.flush();
+        self.stmts.push(stmt);
+    }
+
+    fn define(&mut self, dst: usize, exp: Exp) {
+        if self.foldable[dst] {
+            self.pending.push((dst, exp));
+        } else {
+            let name = self.name(dst);
+            self.emit(Stmt::Assign(vec![name], exp));
+        }
+    }
+
+    fn block(&self, label: &Label) -> BlockId {
+        self.labels[label]
+    }
+
+    /// Translates an instruction, returning the terminator if it ends the block, and how many
+    /// of the `following` instructions were translated along with it.
+    fn instr(&mut self, instr: &Instr, following: &[Instr]) -> Result<(Option<Terminator>, usize)> {
+        let bc = match instr {
+            Instr::VecPack(dst, ty, srcs) => {
+                let elems = self.args(srcs);
+                let ty = self.render_type(ty);
+                self.define(*dst, Exp::Vector(ty, elems));
+                return Ok((None, 0));
+            }
+            Instr::Code(bc) => bc,
+        };
+        match bc {
+            Bytecode::Assign(_, dst, src, _) => {
+                let mut args = self.args(&[*src]);
+                self.define(*dst, args.remove(0));
+            }
+            Bytecode::Load(_, dst, constant) => {
+                let value = self.decompiler.renderer.render_constant(constant);
+                self.define(*dst, Exp::Value(value));
+            }
+            Bytecode::Call(_, dsts, op, srcs, _) => {
+                return Ok((None, self.call(dsts, op, srcs, following)?));
+            }
+            Bytecode::Ret(_, srcs) => {
+                let values = self.args(srcs);
+                self.flush();
+                return Ok((Some(Terminator::Return(values)), 0));
+            }
+            Bytecode::Abort(_, code) => {
+                let mut args = self.args(&[*code]);
+                self.flush();
+                return Ok((Some(Terminator::Abort(args.remove(0))), 0));
+            }
+            Bytecode::Branch(_, then, els, cond) => {
+                let mut args = self.args(&[*cond]);
+                self.flush();
+                let terminator =
+                    Terminator::Branch(args.remove(0), self.block(then), self.block(els));
+                return Ok((Some(terminator), 0));
+            }
+            Bytecode::Jump(_, label) => {
+                self.flush();
+                return Ok((Some(Terminator::Jump(self.block(label))), 0));
+            }
+            Bytecode::Label(..) | Bytecode::Nop(_) => {}
+            Bytecode::SaveMem(..) | Bytecode::SaveSpecVar(..) | Bytecode::Prop(..) => {
+                bail!("unexpected specification instruction")
+            }
+        }
+        Ok((None, 0))
+    }
+
+    /// Returns the left-hand sides for the `dsts` of an instruction with multiple results.
+    /// Results which the `following` instructions store in locals or drop right away are
+    /// bound to the local or to `_` directly, returning how many instructions this covers.
+    fn bind_results(&mut self, dsts: &[usize], following: &[Instr]) -> (Vec<String>, usize) {
+        // Maps results to the local they are stored in, or to `None` if they are dropped.
+        let mut bindings: BTreeMap<usize, Option<usize>> = BTreeMap::new();
+        for instr in following {
+            let (src, local) = match instr {
+                Instr::Code(Bytecode::Assign(_, dst, src, _))
+                    if *dst < self.decompiler.num_locals =>
+                {
+                    (*src, Some(*dst))
+                }
+                Instr::Code(Bytecode::Call(_, _, Operation::Destroy, srcs, _)) => (srcs[0], None),
+                _ => break,
+            };
+            if !dsts.contains(&src)
+                || bindings.contains_key(&src)
+                || (local.is_some() && bindings.values().any(|other| *other == local))
+            {
+                break;
+            }
+            bindings.insert(src, local);
+        }
+        let consumed = bindings.len();
+        let lhs = dsts
+            .iter()
+            .map(|dst| match bindings.get(dst) {
+                Some(Some(local)) => self.name(*local),
+                Some(None) => "_".to_string(),
+                None => self.name(*dst),
+            })
+            .collect();
+        (lhs, consumed)
+    }
+
+    fn call(
+        &mut self,
+        dsts: &[usize],
+        op: &Operation,
+        srcs: &[usize],
+        following: &[Instr],
+    ) -> Result<usize> {
+        // Dropping a variable has no effect.
+        if matches!(op, Operation::Destroy) {
+            match self.pending.last() {
+                Some((idx, Exp::Var(_))) if *idx == srcs[0] => {
+                    self.pending.pop();
+                    return Ok(0);
+                }
+                Some((idx, _)) if *idx == srcs[0] => {}
+                _ => return Ok(0),
+            }
+        }
+        let renderer = self.decompiler.renderer;
+        let type_params = self.decompiler.type_params;
+        // The operands of these operations are pushed in reverse order.
+        let mut args = if matches!(op, Operation::WriteRef | Operation::MoveTo(..)) {
+            let reversed = srcs.iter().rev().copied().collect::<Vec<_>>();
+            let mut args = self.args(&reversed);
+            args.reverse();
+            args
+        } else {
+            self.args(srcs)
+        };
+        let struct_type = |mid: &ModelModuleId, sid: &StructId, tys: &[Type]| {
+            format!(
+                "{}{}",
+                renderer.struct_name(*mid, *sid),
+                renderer.render_type_args(tys, type_params)
+            )
+        };
+        let exp = match op {
+            Operation::Function(mid, fid, tys) => Exp::Call(
+                format!(
+                    "{}{}",
+                    renderer.fun_name(*mid, *fid),
+                    renderer.render_type_args(tys, type_params)
+                ),
+                args,
+            ),
+            Operation::Pack(mid, sid, tys) => {
+                let fields = (0..args.len())
+                    .map(|offset| renderer.field_name(*mid, *sid, offset))
+                    .zip(args)
+                    .collect();
+                Exp::Pack(struct_type(mid, sid, tys), fields)
+            }
+            Operation::Unpack(mid, sid, tys) => {
+                let (lhs, consumed) = self.bind_results(dsts, following);
+                let fields = (0..dsts.len())
+                    .map(|offset| renderer.field_name(*mid, *sid, offset))
+                    .zip(lhs)
+                    .collect();
+                self.emit(Stmt::Unpack(
+                    struct_type(mid, sid, tys),
+                    fields,
+                    args.remove(0),
+                ));
+                return Ok(consumed);
+            }
+            Operation::MoveTo(mid, sid, tys) => {
+                let value = args.remove(0);
+                args.push(value);
+                let callee = format!("move_to<{}>", struct_type(mid, sid, tys));
+                self.emit(Stmt::Eval(Exp::Call(callee, args)));
+                return Ok(0);
+            }
+            Operation::MoveFrom(mid, sid, tys) => {
+                Exp::Call(format!("move_from<{}>", struct_type(mid, sid, tys)), args)
+            }
+            Operation::Exists(mid, sid, tys) => {
+                Exp::Call(format!("exists<{}>", struct_type(mid, sid, tys)), args)
+            }
+            Operation::BorrowLoc => Exp::Borrow(self.is_mut_ref(dsts[0]), Box::new(args.remove(0))),
+            Operation::BorrowField(mid, sid, _, offset) => Exp::borrow_field(
+                self.is_mut_ref(dsts[0]),
+                args.remove(0),
+                renderer.field_name(*mid, *sid, *offset),
+            ),
+            Operation::BorrowGlobal(mid, sid, tys) => {
+                let builtin = if self.is_mut_ref(dsts[0]) {
+                    "borrow_global_mut"
+                } else {
+                    "borrow_global"
+                };
+                Exp::Call(format!("{}<{}>", builtin, struct_type(mid, sid, tys)), args)
+            }
+            Operation::GetField(mid, sid, _, offset) => {
+                let base = match args.remove(0) {
+                    Exp::Borrow(_, place) => *place,
+                    base => base,
+                };
+                Exp::Field(Box::new(base), renderer.field_name(*mid, *sid, *offset))
+            }
+            Operation::GetGlobal(mid, sid, tys) => Exp::Deref(Box::new(Exp::Call(
+                format!("borrow_global<{}>", struct_type(mid, sid, tys)),
+                args,
+            ))),
+            Operation::Destroy => {
+                let stmt = match args.remove(0) {
+                    call @ Exp::Call(..) => Stmt::Eval(call),
+                    exp => Stmt::Drop(exp),
+                };
+                self.emit(stmt);
+                return Ok(0);
+            }
+            Operation::ReadRef => Exp::deref(args.remove(0)),
+            Operation::WriteRef => {
+                let reference = args.remove(0);
+                self.emit(Stmt::WriteRef(reference, args.remove(0)));
+                return Ok(0);
+            }
+            Operation::FreezeRef => Exp::freeze(args.remove(0)),
+            Operation::CastU8 => Exp::Cast(Box::new(args.remove(0)), "u8"),
+            Operation::CastU16 => Exp::Cast(Box::new(args.remove(0)), "u16"),
+            Operation::CastU32 => Exp::Cast(Box::new(args.remove(0)), "u32"),
+            Operation::CastU64 => Exp::Cast(Box::new(args.remove(0)), "u64"),
+            Operation::CastU128 => Exp::Cast(Box::new(args.remove(0)), "u128"),
+            Operation::CastU256 => Exp::Cast(Box::new(args.remove(0)), "u256"),
+            Operation::Not => Exp::negate(args.remove(0)),
+            _ => {
+                let op = match op {
+                    Operation::Add => BinOp::Add,
+                    Operation::Sub => BinOp::Sub,
+                    Operation::Mul => BinOp::Mul,
+                    Operation::Div => BinOp::Div,
+                    Operation::Mod => BinOp::Mod,
+                    Operation::BitOr => BinOp::BitOr,
+                    Operation::BitAnd => BinOp::BitAnd,
+                    Operation::Xor => BinOp::Xor,
+                    Operation::Shl => BinOp::Shl,
+                    Operation::Shr => BinOp::Shr,
+                    Operation::Lt => BinOp::Lt,
+                    Operation::Gt => BinOp::Gt,
+                    Operation::Le => BinOp::Le,
+                    Operation::Ge => BinOp::Ge,
+                    Operation::Or => BinOp::Or,
+                    Operation::And => BinOp::And,
+                    Operation::Eq => BinOp::Eq,
+                    Operation::Neq => BinOp::Neq,
+                    _ => bail!("unexpected operation {:?}", op),
+                };
+                let rhs = args.pop().unwrap();
+                let lhs = args.pop().unwrap();
+                Exp::Binary(op, Box::new(lhs), Box::new(rhs))
+            }
+        };
+        match dsts {
+            [] => self.emit(Stmt::Eval(exp)),
+            [dst] => self.define(*dst, exp),
+            _ => {
+                let (lhs, consumed) = self.bind_results(dsts, following);
+                self.emit(Stmt::Assign(lhs, exp));
+                return Ok(consumed);
+            }
+        }
+        Ok(0)
+    }
+}
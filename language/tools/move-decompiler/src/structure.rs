@@ -0,0 +1,444 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Reconstruction of structured control flow from the basic blocks of a function.
+//!
+//! Loops are the natural loops of the control flow graph, which must be reducible. A loop is
+//! rendered as `while` if its header only evaluates the loop condition, and as `loop` otherwise;
+//! jumps to the header of the innermost loop become `continue`, and jumps to its exit `break`.
+//! A loop may have at most one exit, as Move has no labeled `break`.
+//!
+//! For a conditional branch, the arms are the code reached from either successor up to the
+//! join point, where control merges again. The join point of a branch is a block immediately
+//! dominated by the branch which is not exclusively entered through one of the arms. Code after
+//! a `return`, `abort`, `break` or `continue` never reaches the join point, so early exits do not
+//! prevent the arms from being closed.
+
+use crate::ast::{diverges, Exp, Stmt};
+use anyhow::{bail, Result};
+use move_stackless_bytecode::graph::Graph;
+use std::collections::{BTreeMap, BTreeSet};
+
+pub(crate) type BlockId = usize;
+
+pub(crate) enum Terminator {
+    Jump(BlockId),
+    Branch(Exp, BlockId, BlockId),
+    Return(Vec<Exp>),
+    Abort(Exp),
+}
+
+impl Terminator {
+    fn successors(&self) -> Vec<BlockId> {
+        match self {
+            Terminator::Jump(target) => vec![*target],
+            Terminator::Branch(_, then, els) => vec![*then, *els],
+            Terminator::Return(_) | Terminator::Abort(_) => vec![],
+        }
+    }
+}
+
+pub(crate) struct Block {
+    pub stmts: Vec<Stmt>,
+    pub terminator: Terminator,
+}
+
+struct Loop {
+    body: BTreeSet<BlockId>,
+    exit: Option<BlockId>,
+}
+
+struct Structurer<'a> {
+    blocks: &'a [Block],
+    /// The predecessors of each reachable block, once per edge.
+    preds: Vec<Vec<BlockId>>,
+    /// The immediate dominator of each reachable block; the entry is its own dominator.
+    idom: Vec<Option<BlockId>>,
+    /// The blocks each block immediately dominates, in reverse postorder.
+    dom_children: Vec<Vec<BlockId>>,
+    loops: BTreeMap<BlockId, Loop>,
+}
+
+/// Structures the blocks of a function, starting at `entry`, into a statement list.
+pub(crate) fn structure(blocks: &[Block], entry: BlockId) -> Result<Vec<Stmt>> {
+    let structurer = Structurer::new(blocks, entry)?;
+    let mut stmts = structurer.seq(entry, None, &mut vec![], false)?;
+    match stmts.last() {
+        Some(Stmt::Return(values)) if values.is_empty() => {
+            stmts.pop();
+        }
+        Some(Stmt::Return(values)) => {
+            let values = values.clone();
+            *stmts.last_mut().unwrap() = Stmt::Result(values);
+        }
+        _ => {}
+    }
+    Ok(stmts)
+}
+
+impl<'a> Structurer<'a> {
+    fn new(blocks: &'a [Block], entry: BlockId) -> Result<Self> {
+        let n = blocks.len();
+
+        // Compute a reverse postorder of the reachable blocks.
+        let mut postorder = vec![];
+        let mut visited = vec![false; n];
+        let mut stack = vec![(entry, 0)];
+        visited[entry] = true;
+        while let Some((block, next)) = stack.pop() {
+            let succs = blocks[block].terminator.successors();
+            if next < succs.len() {
+                stack.push((block, next + 1));
+                let succ = succs[next];
+                if !visited[succ] {
+                    visited[succ] = true;
+                    stack.push((succ, 0));
+                }
+            } else {
+                postorder.push(block);
+            }
+        }
+        let rpo = postorder.into_iter().rev().collect::<Vec<_>>();
+        let mut rpo_index = vec![usize::MAX; n];
+        for (i, block) in rpo.iter().enumerate() {
+            rpo_index[*block] = i;
+        }
+
+        let mut preds = vec![vec![]; n];
+        let mut edges = vec![];
+        for block in &rpo {
+            for succ in blocks[*block].terminator.successors() {
+                preds[succ].push(*block);
+                edges.push((*block, succ));
+            }
+        }
+
+        // Compute immediate dominators with the iterative algorithm of Cooper, Harvey and
+        // Kennedy.
+        let mut idom = vec![None; n];
+        idom[entry] = Some(entry);
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for block in rpo.iter().skip(1) {
+                let mut new_idom: Option<BlockId> = None;
+                for pred in &preds[*block] {
+                    if idom[*pred].is_none() {
+                        continue;
+                    }
+                    new_idom = Some(match new_idom {
+                        None => *pred,
+                        Some(mut other) => {
+                            let mut pred = *pred;
+                            while pred != other {
+                                while rpo_index[pred] > rpo_index[other] {
+                                    pred = idom[pred].unwrap();
+                                }
+                                while rpo_index[other] > rpo_index[pred] {
+                                    other = idom[other].unwrap();
+                                }
+                            }
+                            pred
+                        }
+                    });
+                }
+                if idom[*block] != new_idom {
+                    idom[*block] = new_idom;
+                    changed = true;
+                }
+            }
+        }
+        let mut dom_children = vec![vec![]; n];
+        for block in rpo.iter().skip(1) {
+            dom_children[idom[*block].unwrap()].push(*block);
+        }
+
+        // Collect the natural loops, merging loops with the same header.
+        let natural_loops = match Graph::new(entry, rpo.clone(), edges).compute_reducible() {
+            Some(loops) => loops,
+            None => bail!("irreducible control flow"),
+        };
+        let mut bodies: BTreeMap<BlockId, BTreeSet<BlockId>> = BTreeMap::new();
+        for natural_loop in natural_loops {
+            bodies
+                .entry(natural_loop.loop_header)
+                .or_default()
+                .extend(natural_loop.loop_body);
+        }
+        // Process inner loops first, so that the blocks they absorb are part of the enclosing
+        // loops as well.
+        let mut headers = bodies.keys().copied().collect::<Vec<_>>();
+        headers.sort_by_key(|header| bodies[header].len());
+        let mut loops: BTreeMap<BlockId, Loop> = BTreeMap::new();
+        for header in headers {
+            let mut body = bodies.remove(&header).unwrap();
+            for (inner_header, inner_loop) in &loops {
+                if body.contains(inner_header) {
+                    body.extend(inner_loop.body.iter().copied());
+                }
+            }
+            let exit = Self::absorb_exits(blocks, &preds, &mut body)?;
+            loops.insert(header, Loop { body, exit });
+        }
+
+        Ok(Self {
+            blocks,
+            preds,
+            idom,
+            dom_children,
+            loops,
+        })
+    }
+
+    /// Extends the natural loop `body` until it has at most one exit, and returns that exit.
+    /// A natural loop only consists of the blocks on a cycle, so code which leaves the loop, like
+    /// `if (c) { x = 1; break }` or `if (c) return x`, is outside of it. A block outside the
+    /// loop which is only entered from within the loop is therefore moved into it. If all exits
+    /// could be moved, the one leading to the most code remains the exit.
+    fn absorb_exits(
+        blocks: &[Block],
+        preds: &[Vec<BlockId>],
+        body: &mut BTreeSet<BlockId>,
+    ) -> Result<Option<BlockId>> {
+        loop {
+            let exits = body
+                .iter()
+                .flat_map(|block| blocks[*block].terminator.successors())
+                .filter(|succ| !body.contains(succ))
+                .collect::<BTreeSet<_>>();
+            if exits.len() <= 1 {
+                return Ok(exits.into_iter().next());
+            }
+            let mut absorbable = exits
+                .iter()
+                .copied()
+                .filter(|exit| preds[*exit].iter().all(|pred| body.contains(pred)))
+                .collect::<Vec<_>>();
+            if absorbable.is_empty() {
+                bail!("loop with multiple exits");
+            }
+            if absorbable.len() == exits.len() {
+                let reach = |start: BlockId| {
+                    let mut seen = BTreeSet::from([start]);
+                    let mut todo = vec![start];
+                    while let Some(block) = todo.pop() {
+                        for succ in blocks[block].terminator.successors() {
+                            if !body.contains(&succ) && seen.insert(succ) {
+                                todo.push(succ);
+                            }
+                        }
+                    }
+                    seen.len()
+                };
+                let (idx, _) = absorbable
+                    .iter()
+                    .enumerate()
+                    .max_by_key(|(_, exit)| reach(**exit))
+                    .unwrap();
+                absorbable.remove(idx);
+            }
+            body.extend(absorbable);
+        }
+    }
+
+    fn dominates(&self, dominator: BlockId, mut block: BlockId) -> bool {
+        loop {
+            if block == dominator {
+                return true;
+            }
+            match self.idom[block] {
+                Some(parent) if parent != block => block = parent,
+                _ => return false,
+            }
+        }
+    }
+
+    /// Structures the code starting at `start` until control reaches `stop`. `frames` are the
+    /// headers of the enclosing loops, innermost last. If `skip_header` is set, `start` is the
+    /// header of the innermost loop, and the code of its body is produced.
+    fn seq(
+        &self,
+        start: BlockId,
+        stop: Option<BlockId>,
+        frames: &mut Vec<BlockId>,
+        skip_header: bool,
+    ) -> Result<Vec<Stmt>> {
+        let mut out = vec![];
+        let mut current = start;
+        let mut is_header = skip_header;
+        loop {
+            let next = if !is_header && self.loops.contains_key(&current) {
+                let exit = self.structure_loop(current, frames, &mut out)?;
+                match exit {
+                    Some(exit) => self.goto(exit, stop, frames, &mut out)?,
+                    None => None,
+                }
+            } else {
+                let block = &self.blocks[current];
+                out.extend(block.stmts.iter().cloned());
+                match &block.terminator {
+                    Terminator::Return(values) => {
+                        out.push(Stmt::Return(values.clone()));
+                        None
+                    }
+                    Terminator::Abort(code) => {
+                        out.push(Stmt::Abort(code.clone()));
+                        None
+                    }
+                    Terminator::Jump(target) => self.goto(*target, stop, frames, &mut out)?,
+                    Terminator::Branch(cond, then, els) => {
+                        let join = self.join(current, *then, *els, frames).or(stop);
+                        let then = self.arm(*then, join, frames)?;
+                        let els = self.arm(*els, join, frames)?;
+                        push_if(&mut out, cond.clone(), then, els);
+                        join.filter(|join| Some(*join) != stop)
+                    }
+                }
+            };
+            is_header = false;
+            match next {
+                Some(next) => current = next,
+                None => return Ok(out),
+            }
+        }
+    }
+
+    /// Structures the code of a branch arm starting at `target` until control reaches `join`.
+    fn arm(
+        &self,
+        target: BlockId,
+        join: Option<BlockId>,
+        frames: &mut Vec<BlockId>,
+    ) -> Result<Vec<Stmt>> {
+        let mut out = vec![];
+        match self.goto(target, join, frames, &mut out)? {
+            Some(start) => self.seq(start, join, frames, false),
+            None => Ok(out),
+        }
+    }
+
+    /// Handles a jump to `target`, emitting `break` or `continue` for jumps leaving the
+    /// current loop iteration. Returns the block to continue with, if any.
+    fn goto(
+        &self,
+        target: BlockId,
+        stop: Option<BlockId>,
+        frames: &[BlockId],
+        out: &mut Vec<Stmt>,
+    ) -> Result<Option<BlockId>> {
+        if Some(target) == stop {
+            return Ok(None);
+        }
+        if let Some(header) = frames.last() {
+            let current_loop = &self.loops[header];
+            if target == *header {
+                out.push(Stmt::Continue);
+                return Ok(None);
+            }
+            if Some(target) == current_loop.exit {
+                out.push(Stmt::Break);
+                return Ok(None);
+            }
+            if !current_loop.body.contains(&target) {
+                bail!("jump out of a nested loop");
+            }
+        }
+        Ok(Some(target))
+    }
+
+    /// Structures the loop with the given header, returning its exit.
+    fn structure_loop(
+        &self,
+        header: BlockId,
+        frames: &mut Vec<BlockId>,
+        out: &mut Vec<Stmt>,
+    ) -> Result<Option<BlockId>> {
+        let current_loop = &self.loops[&header];
+        let block = &self.blocks[header];
+        frames.push(header);
+        let stmt = match &block.terminator {
+            Terminator::Branch(cond, then, els)
+                if block.stmts.is_empty()
+                    && Some(*els) == current_loop.exit
+                    && current_loop.body.contains(then) =>
+            {
+                Stmt::While(cond.clone(), strip_continue(self.arm(*then, None, frames)?))
+            }
+            Terminator::Branch(cond, then, els)
+                if block.stmts.is_empty()
+                    && Some(*then) == current_loop.exit
+                    && current_loop.body.contains(els) =>
+            {
+                Stmt::While(
+                    Exp::negate(cond.clone()),
+                    strip_continue(self.arm(*els, None, frames)?),
+                )
+            }
+            _ => Stmt::Loop(strip_continue(self.seq(header, None, frames, true)?)),
+        };
+        frames.pop();
+        out.push(stmt);
+        Ok(current_loop.exit)
+    }
+
+    /// Determines the join point of the branch at `block`, if control merges again after it.
+    fn join(
+        &self,
+        block: BlockId,
+        then: BlockId,
+        els: BlockId,
+        frames: &[BlockId],
+    ) -> Option<BlockId> {
+        let current_loop = frames.last().map(|header| (header, &self.loops[header]));
+        self.dom_children[block]
+            .iter()
+            .copied()
+            .filter(|child| match current_loop {
+                Some((header, current_loop)) => {
+                    child != header && current_loop.body.contains(child)
+                }
+                None => true,
+            })
+            .find(|child| !((*child == then || *child == els) && self.is_arm_entry(*child, block)))
+    }
+
+    /// Whether `block` is entered exclusively from `branch`, apart from back edges.
+    fn is_arm_entry(&self, block: BlockId, branch: BlockId) -> bool {
+        let mut forward_preds = self.preds[block]
+            .iter()
+            .filter(|pred| !self.dominates(block, **pred));
+        forward_preds.next() == Some(&branch) && forward_preds.next().is_none()
+    }
+}
+
+/// Appends the conditional `if (cond) then else els` to `out`, preferring early exits over
+/// `else` branches.
+fn push_if(out: &mut Vec<Stmt>, cond: Exp, then: Vec<Stmt>, els: Vec<Stmt>) {
+    match (then.is_empty(), els.is_empty()) {
+        (true, true) => out.push(Stmt::Eval(cond)),
+        (false, true) => out.push(Stmt::If(cond, then, vec![])),
+        (true, false) => out.push(Stmt::If(Exp::negate(cond), els, vec![])),
+        _ if diverges(&then) => {
+            out.push(Stmt::If(cond, then, vec![]));
+            out.extend(els);
+        }
+        _ if diverges(&els) => {
+            out.push(Stmt::If(Exp::negate(cond), els, vec![]));
+            out.extend(then);
+        }
+        _ => out.push(Stmt::If(cond, then, els)),
+    }
+}
+
+/// Removes `continue` statements at the end of a loop body, which are implied.
+fn strip_continue(mut stmts: Vec<Stmt>) -> Vec<Stmt> {
+    match stmts.pop() {
+        Some(Stmt::Continue) => {}
+        Some(Stmt::If(cond, then, els)) => {
+            push_if(&mut stmts, cond, strip_continue(then), strip_continue(els))
+        }
+        Some(stmt) => stmts.push(stmt),
+        None => {}
+    }
+    stmts
+}
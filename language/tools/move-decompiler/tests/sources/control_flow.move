@@ -0,0 +1,77 @@
+module 0x42::control_flow {
+    const EINVALID: u64 = 1;
+
+    fun max(a: u64, b: u64): u64 {
+        if (a > b) a else b
+    }
+
+    fun sign(x: u64, y: u64): u8 {
+        if (x < y) {
+            0
+        } else if (x == y) {
+            1
+        } else {
+            2
+        }
+    }
+
+    fun sum(n: u64): u64 {
+        let i = 0;
+        let sum = 0;
+        while (i < n) {
+            sum = sum + i;
+            i = i + 1;
+        };
+        sum
+    }
+
+    fun find(v: &vector<u64>, x: u64): (bool, u64) {
+        let i = 0;
+        let n = std::vector::length(v);
+        while (i < n) {
+            if (*std::vector::borrow(v, i) == x) return (true, i);
+            i = i + 1;
+        };
+        (false, 0)
+    }
+
+    fun first_even(v: &vector<u64>): u64 {
+        let i = 0;
+        let found = 0;
+        loop {
+            if (i >= std::vector::length(v)) break;
+            let x = *std::vector::borrow(v, i);
+            i = i + 1;
+            if (x % 2 == 1) continue;
+            found = x;
+            break
+        };
+        found
+    }
+
+    fun nested(n: u64): u64 {
+        let count = 0;
+        let i = 0;
+        while (i < n) {
+            let j = 0;
+            while (j < i) {
+                if (j % 3 == 0) {
+                    count = count + 1;
+                };
+                j = j + 1;
+            };
+            i = i + 1;
+        };
+        count
+    }
+
+    fun checked(x: u64): u64 {
+        assert!(x > 0, EINVALID);
+        if (x > 100) abort 2;
+        x - 1
+    }
+
+    fun logic(a: bool, b: bool, c: bool): bool {
+        (a && b) || !c
+    }
+}
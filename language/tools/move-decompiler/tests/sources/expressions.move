@@ -0,0 +1,86 @@
+module 0x42::expressions {
+    use std::option::{Self, Option};
+    use std::vector;
+
+    friend 0x42::friend_of_expressions;
+
+    struct Coin has store, drop {
+        value: u64,
+    }
+
+    struct Wallet has key {
+        coins: vector<Coin>,
+        owner: address,
+    }
+
+    struct Pair<T: copy + drop, phantom U> has copy, drop {
+        first: T,
+        second: T,
+    }
+
+    public fun arithmetic(a: u64, b: u64, c: u8): u128 {
+        let x = (a + b) * (a - b) / 3 % 7;
+        let y = a << c | b >> 2 & 0xff ^ a;
+        ((x + y) as u128) + (c as u128)
+    }
+
+    public fun literals(): (vector<u8>, address, vector<u64>, u256) {
+        (b"hello", @0x1, vector[1, 2, 3], 1000u256)
+    }
+
+    public fun pair<T: copy + drop>(x: T): Pair<T, u8> {
+        Pair { first: x, second: x }
+    }
+
+    public fun swap<T: copy + drop, U>(p: Pair<T, U>): Pair<T, U> {
+        let Pair { first, second } = p;
+        Pair { first: second, second: first }
+    }
+
+    public fun references(p: &mut Pair<u64, bool>) {
+        let first = &mut p.first;
+        *first = *first + 1;
+        p.second = p.first * 2;
+    }
+
+    public(friend) fun publish(account: &signer) {
+        move_to(account, Wallet { coins: vector::empty(), owner: @0x42 });
+    }
+
+    public fun deposit(addr: address, value: u64) acquires Wallet {
+        let wallet = borrow_global_mut<Wallet>(addr);
+        vector::push_back(&mut wallet.coins, Coin { value });
+    }
+
+    public fun balance(addr: address): u64 acquires Wallet {
+        if (!exists<Wallet>(addr)) return 0;
+        let coins = &borrow_global<Wallet>(addr).coins;
+        let total = 0;
+        let i = 0;
+        while (i < vector::length(coins)) {
+            total = total + vector::borrow(coins, i).value;
+            i = i + 1;
+        };
+        total
+    }
+
+    public entry fun destroy(addr: address) acquires Wallet {
+        let Wallet { coins, owner: _ } = move_from<Wallet>(addr);
+        while (!vector::is_empty(&coins)) {
+            let Coin { value: _ } = vector::pop_back(&mut coins);
+        };
+        vector::destroy_empty(coins);
+    }
+
+    public fun maybe(x: u64): Option<u64> {
+        if (x == 0) option::none() else option::some(x)
+    }
+}
+
+module 0x42::friend_of_expressions {
+    use 0x42::expressions;
+
+    public fun call(account: &signer) {
+        expressions::publish(account);
+    }
+}
@@ -0,0 +1,59 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use move_compiler::{
+    compiled_unit::AnnotatedCompiledUnit, diagnostics::report_diagnostics_to_buffer, Compiler,
+};
+use move_decompiler::Decompiler;
+use std::{collections::BTreeMap, fs};
+
+/// Decompiles the standard library, with and without source maps, and checks that the result
+/// compiles again.
+#[test]
+fn decompile_stdlib() {
+    let (_, units) = Compiler::from_files(
+        move_stdlib::move_stdlib_files(),
+        vec![],
+        move_stdlib::move_stdlib_named_addresses(),
+    )
+    .build_and_report()
+    .unwrap();
+    let modules = units
+        .into_iter()
+        .map(|unit| match unit {
+            AnnotatedCompiledUnit::Module(module) => module.named_module,
+            AnnotatedCompiledUnit::Script(_) => panic!("unexpected script"),
+        })
+        .collect::<Vec<_>>();
+
+    for with_source_map in [true, false] {
+        let dir = tempfile::tempdir().unwrap();
+        let mut paths = vec![];
+        for module in &modules {
+            let mut decompiler =
+                Decompiler::new(&module.module, modules.iter().map(|module| &module.module));
+            if with_source_map {
+                decompiler = decompiler.with_source_map(&module.source_map);
+            }
+            let source = decompiler.decompile().unwrap();
+            assert!(
+                !source.contains("could not decompile"),
+                "failed to decompile a function:\n{}",
+                source
+            );
+            let path = dir.path().join(format!("{}.move", module.name));
+            fs::write(&path, source).unwrap();
+            paths.push(path.to_string_lossy().to_string());
+        }
+
+        let (files, units) = Compiler::from_files(paths, vec![], BTreeMap::<String, _>::new())
+            .build()
+            .unwrap();
+        if let Err(diags) = units {
+            panic!(
+                "decompiled standard library does not compile:\n{}",
+                String::from_utf8_lossy(&report_diagnostics_to_buffer(&files, diags))
+            );
+        }
+    }
+}
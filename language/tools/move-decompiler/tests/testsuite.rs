@@ -0,0 +1,76 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, bail, Result};
+use move_binary_format::CompiledModule;
+use move_bytecode_source_map::source_map::SourceMap;
+use move_command_line_common::testing::EXP_EXT;
+use move_compiler::{
+    compiled_unit::AnnotatedCompiledUnit, diagnostics::report_diagnostics_to_buffer, Compiler,
+};
+use move_decompiler::Decompiler;
+use move_prover_test_utils::baseline_test::verify_or_update_baseline;
+use std::{fs, path::Path};
+
+/// Compiles `targets` against the standard library, returning the compiled modules of the
+/// targets and their source maps.
+fn compile(targets: Vec<String>, deps: Vec<String>) -> Result<Vec<(CompiledModule, SourceMap)>> {
+    let (files, units) =
+        Compiler::from_files(targets, deps, move_stdlib::move_stdlib_named_addresses()).build()?;
+    let units = match units {
+        Ok((units, _warnings)) => units,
+        Err(diags) => bail!(
+            "{}",
+            String::from_utf8_lossy(&report_diagnostics_to_buffer(&files, diags))
+        ),
+    };
+    units
+        .into_iter()
+        .map(|unit| match unit {
+            AnnotatedCompiledUnit::Module(module) => {
+                Ok((module.named_module.module, module.named_module.source_map))
+            }
+            AnnotatedCompiledUnit::Script(_) => Err(anyhow!("scripts are not supported")),
+        })
+        .collect()
+}
+
+fn test_runner(path: &Path) -> datatest_stable::Result<()> {
+    let stdlib = compile(move_stdlib::move_stdlib_files(), vec![])?;
+    let modules = compile(
+        vec![path.to_string_lossy().to_string()],
+        move_stdlib::move_stdlib_files(),
+    )?;
+    let dependencies = stdlib
+        .iter()
+        .chain(&modules)
+        .map(|(module, _)| module)
+        .collect::<Vec<_>>();
+
+    let mut decompiled = String::new();
+    for (module, source_map) in &modules {
+        let source = Decompiler::new(module, dependencies.iter().copied())
+            .with_source_map(source_map)
+            .decompile()?;
+        if !decompiled.is_empty() {
+            decompiled.push('\n');
+        }
+        decompiled.push_str(&source);
+    }
+
+    // The decompiled modules must compile again.
+    let dir = tempfile::tempdir()?;
+    let decompiled_path = dir.path().join("decompiled.move");
+    fs::write(&decompiled_path, &decompiled)?;
+    if let Err(err) = compile(
+        vec![decompiled_path.to_string_lossy().to_string()],
+        move_stdlib::move_stdlib_files(),
+    ) {
+        return Err(format!("decompiled code does not compile:\n{}\n{}", decompiled, err).into());
+    }
+
+    verify_or_update_baseline(&path.with_extension(EXP_EXT), &decompiled)?;
+    Ok(())
+}
+
+datatest_stable::harness!(test_runner, "tests/sources", r".*\.move$");
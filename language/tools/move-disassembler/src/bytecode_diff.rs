@@ -0,0 +1,545 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Compares two versions of a compiled module.
+//!
+//! Diffing disassembly text is noisy: adding an entry to any of the module's tables renumbers the
+//! entries after it, and with them every instruction referring to those entries. Instead, structs
+//! and functions are aligned by name and instructions are compared with the table entries they
+//! refer to in place of their indices. The tables themselves, which have no names, are aligned by
+//! the entries they hold.
+
+use crate::disassembler::Disassembler;
+use anyhow::{format_err, Result};
+use move_binary_format::{
+    binary_views::BinaryIndexedView,
+    file_format::{
+        CompiledModule, FieldHandle, FunctionDefinitionIndex, SignatureToken,
+        StructDefinitionIndex, StructFieldInformation, StructHandleIndex, TableIndex,
+    },
+};
+use move_core_types::{identifier::Identifier, language_storage::ModuleId};
+use move_ir_types::location::Spanned;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+
+/// The number of unchanged lines shown around each change in a definition.
+const CONTEXT_LINES: usize = 2;
+
+/// A line of a definition, as it appears in the old version, the new version or both.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DiffLine {
+    Same(String),
+    Removed(String),
+    Added(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DefinitionChange {
+    Added,
+    Removed,
+    Changed(Vec<DiffLine>),
+}
+
+/// A struct or function that differs between the two versions of a module.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DefinitionDiff {
+    /// Either `struct` or `fun`.
+    pub kind: &'static str,
+    pub name: Identifier,
+    pub change: DefinitionChange,
+}
+
+/// The differences between the entries of one of the module's tables.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PoolDiff {
+    pub table: &'static str,
+    pub removed: Vec<String>,
+    pub added: Vec<String>,
+    /// The number of entries found in both versions, but at a different index.
+    pub renumbered: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BytecodeDiff {
+    pub old_id: ModuleId,
+    pub new_id: ModuleId,
+    pub definitions: Vec<DefinitionDiff>,
+    pub pools: Vec<PoolDiff>,
+}
+
+impl BytecodeDiff {
+    pub fn new(old: &CompiledModule, new: &CompiledModule) -> Result<Self> {
+        let old = ModuleView::new(old)?;
+        let new = ModuleView::new(new)?;
+
+        let mut definitions = vec![];
+        for (kind, old_defs, new_defs) in [
+            ("struct", old.structs()?, new.structs()?),
+            ("fun", old.functions()?, new.functions()?),
+        ] {
+            let names: BTreeSet<_> = old_defs.keys().chain(new_defs.keys()).collect();
+            for name in names {
+                let change = match (old_defs.get(name), new_defs.get(name)) {
+                    (Some(_), None) => DefinitionChange::Removed,
+                    (None, Some(_)) => DefinitionChange::Added,
+                    (Some(old_lines), Some(new_lines)) if old_lines != new_lines => {
+                        DefinitionChange::Changed(diff_lines(old_lines, new_lines))
+                    }
+                    _ => continue,
+                };
+                definitions.push(DefinitionDiff {
+                    kind,
+                    name: name.clone(),
+                    change,
+                });
+            }
+        }
+
+        let old_pools = old.pools()?;
+        let new_pools = new.pools()?;
+        let pools = old_pools
+            .into_iter()
+            .zip(new_pools)
+            .filter_map(|((table, old_entries), (_, new_entries))| {
+                let diff = diff_pool(table, &old_entries, &new_entries);
+                let unchanged =
+                    diff.removed.is_empty() && diff.added.is_empty() && diff.renumbered == 0;
+                (!unchanged).then_some(diff)
+            })
+            .collect();
+
+        Ok(Self {
+            old_id: old.module.self_id(),
+            new_id: new.module.self_id(),
+            definitions,
+            pools,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.old_id == self.new_id && self.definitions.is_empty() && self.pools.is_empty()
+    }
+}
+
+impl fmt::Display for BytecodeDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "No differences");
+        }
+        if self.old_id != self.new_id {
+            writeln!(
+                f,
+                "module {} renamed to {}",
+                self.old_id.short_str_lossless(),
+                self.new_id.short_str_lossless()
+            )?;
+        }
+        for def in &self.definitions {
+            match &def.change {
+                DefinitionChange::Added => writeln!(f, "+ {} {}", def.kind, def.name)?,
+                DefinitionChange::Removed => writeln!(f, "- {} {}", def.kind, def.name)?,
+                DefinitionChange::Changed(lines) => {
+                    writeln!(f, "~ {} {}", def.kind, def.name)?;
+                    write_hunks(f, lines)?;
+                }
+            }
+        }
+        for pool in &self.pools {
+            write!(f, "~ {}", pool.table)?;
+            if pool.renumbered > 0 {
+                write!(f, " ({} renumbered)", pool.renumbered)?;
+            }
+            writeln!(f)?;
+            for entry in &pool.removed {
+                writeln!(f, "    - {}", entry)?;
+            }
+            for entry in &pool.added {
+                writeln!(f, "    + {}", entry)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes the changed lines, with up to `CONTEXT_LINES` unchanged lines around them.
+fn write_hunks(f: &mut fmt::Formatter<'_>, lines: &[DiffLine]) -> fmt::Result {
+    let changed: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, DiffLine::Same(_)))
+        .map(|(i, _)| i)
+        .collect();
+    let shown = |i: usize| {
+        changed
+            .iter()
+            .any(|c| i + CONTEXT_LINES >= *c && i <= *c + CONTEXT_LINES)
+    };
+    let mut skipped = false;
+    for (i, line) in lines.iter().enumerate() {
+        if !shown(i) {
+            skipped = true;
+            continue;
+        }
+        if skipped {
+            writeln!(f, "    ...")?;
+            skipped = false;
+        }
+        match line {
+            DiffLine::Same(l) => writeln!(f, "      {}", l)?,
+            DiffLine::Removed(l) => writeln!(f, "    - {}", l)?,
+            DiffLine::Added(l) => writeln!(f, "    + {}", l)?,
+        }
+    }
+    if skipped {
+        writeln!(f, "    ...")?;
+    }
+    Ok(())
+}
+
+/// Computes a line diff from a longest common subsequence of `old` and `new`.
+fn diff_lines(old: &[String], new: &[String]) -> Vec<DiffLine> {
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            lines.push(DiffLine::Same(old[i].clone()));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            lines.push(DiffLine::Removed(old[i].clone()));
+            i += 1;
+        } else {
+            lines.push(DiffLine::Added(new[j].clone()));
+            j += 1;
+        }
+    }
+    lines.extend(old[i..].iter().cloned().map(DiffLine::Removed));
+    lines.extend(new[j..].iter().cloned().map(DiffLine::Added));
+    lines
+}
+
+fn diff_pool(table: &'static str, old: &[String], new: &[String]) -> PoolDiff {
+    let old_indices: BTreeMap<&String, usize> =
+        old.iter().enumerate().map(|(i, e)| (e, i)).collect();
+    let new_indices: BTreeMap<&String, usize> =
+        new.iter().enumerate().map(|(i, e)| (e, i)).collect();
+    PoolDiff {
+        table,
+        removed: old
+            .iter()
+            .filter(|e| !new_indices.contains_key(e))
+            .cloned()
+            .collect(),
+        added: new
+            .iter()
+            .filter(|e| !old_indices.contains_key(e))
+            .cloned()
+            .collect(),
+        renumbered: old_indices
+            .iter()
+            .filter(|(e, i)| matches!(new_indices.get(*e), Some(j) if j != *i))
+            .count(),
+    }
+}
+
+/// Removes the table index following the name of a disassembled instruction, e.g. the `[3]` of
+/// `Call[3](f(u64))`.
+fn strip_index(instruction: &str) -> String {
+    if let Some(open) = instruction.find(['[', '(']) {
+        if instruction[open..].starts_with('[') {
+            if let Some(close) = instruction[open..].find(']') {
+                let index = &instruction[open + 1..open + close];
+                if !index.is_empty() && index.chars().all(|c| c.is_ascii_digit()) {
+                    return format!(
+                        "{}{}",
+                        &instruction[..open],
+                        &instruction[open + close + 1..]
+                    );
+                }
+            }
+        }
+    }
+    instruction.to_string()
+}
+
+struct ModuleView<'a> {
+    module: &'a CompiledModule,
+    view: BinaryIndexedView<'a>,
+    disassembler: Disassembler<'a>,
+}
+
+impl<'a> ModuleView<'a> {
+    fn new(module: &'a CompiledModule) -> Result<Self> {
+        let view = BinaryIndexedView::Module(module);
+        let no_loc = Spanned::unsafe_no_loc(()).loc;
+        Ok(Self {
+            module,
+            view,
+            disassembler: Disassembler::from_view(view, no_loc)?,
+        })
+    }
+
+    fn structs(&self) -> Result<BTreeMap<Identifier, Vec<String>>> {
+        (0..self.module.struct_defs.len())
+            .map(|i| {
+                let idx = StructDefinitionIndex(i as TableIndex);
+                let def = &self.module.struct_defs[i];
+                let name = self.struct_name(def.struct_handle);
+                let lines = self
+                    .disassembler
+                    .disassemble_struct_def(idx)?
+                    .lines()
+                    .map(|line| line.trim().trim_end_matches(',').to_string())
+                    .collect();
+                Ok((name, lines))
+            })
+            .collect()
+    }
+
+    fn functions(&self) -> Result<BTreeMap<Identifier, Vec<String>>> {
+        (0..self.module.function_defs.len())
+            .map(|i| {
+                let idx = FunctionDefinitionIndex(i as TableIndex);
+                let handle = self
+                    .view
+                    .function_handle_at(self.module.function_defs[i].function);
+                let name = self.view.identifier_at(handle.name).to_owned();
+                let mut lines = vec![self.disassembler.disassemble_function_signature(idx)?];
+                lines.extend(
+                    self.disassembler
+                        .disassemble_function_code(idx)?
+                        .iter()
+                        .map(|instruction| strip_index(instruction)),
+                );
+                Ok((name, lines))
+            })
+            .collect()
+    }
+
+    /// Renders the entries of each of the module's tables, in the order of the tables.
+    fn pools(&self) -> Result<Vec<(&'static str, Vec<String>)>> {
+        let view = &self.view;
+        let module = self.module;
+        Ok(vec![
+            (
+                "identifiers",
+                view.identifiers().iter().map(|i| i.to_string()).collect(),
+            ),
+            (
+                "address identifiers",
+                view.address_identifiers()
+                    .iter()
+                    .map(|a| a.to_hex_literal())
+                    .collect(),
+            ),
+            (
+                "module handles",
+                view.module_handles()
+                    .iter()
+                    .map(|h| view.module_id_for_handle(h).short_str_lossless())
+                    .collect(),
+            ),
+            (
+                "struct handles",
+                (0..view.struct_handles().len())
+                    .map(|i| self.qualified_struct_name(StructHandleIndex(i as TableIndex)))
+                    .collect(),
+            ),
+            (
+                "function handles",
+                view.function_handles()
+                    .iter()
+                    .map(|h| {
+                        let module_id = view.module_id_for_handle(view.module_handle_at(h.module));
+                        format!(
+                            "{}::{}{}: {} -> {}",
+                            module_id.short_str_lossless(),
+                            view.identifier_at(h.name),
+                            self.type_parameters(h.type_parameters.len()),
+                            self.signature(&view.signature_at(h.parameters).0),
+                            self.signature(&view.signature_at(h.return_).0)
+                        )
+                    })
+                    .collect(),
+            ),
+            (
+                "field handles",
+                module
+                    .field_handles
+                    .iter()
+                    .map(|h| self.field(h))
+                    .collect::<Result<_>>()?,
+            ),
+            (
+                "struct instantiations",
+                module
+                    .struct_def_instantiations
+                    .iter()
+                    .map(|inst| {
+                        let def = &module.struct_defs[inst.def.0 as usize];
+                        format!(
+                            "{}{}",
+                            self.struct_name(def.struct_handle),
+                            self.type_arguments(&view.signature_at(inst.type_parameters).0)
+                        )
+                    })
+                    .collect(),
+            ),
+            (
+                "function instantiations",
+                module
+                    .function_instantiations
+                    .iter()
+                    .map(|inst| {
+                        let handle = view.function_handle_at(inst.handle);
+                        let module_id =
+                            view.module_id_for_handle(view.module_handle_at(handle.module));
+                        format!(
+                            "{}::{}{}",
+                            module_id.short_str_lossless(),
+                            view.identifier_at(handle.name),
+                            self.type_arguments(&view.signature_at(inst.type_parameters).0)
+                        )
+                    })
+                    .collect(),
+            ),
+            (
+                "field instantiations",
+                module
+                    .field_instantiations
+                    .iter()
+                    .map(|inst| {
+                        Ok(format!(
+                            "{}{}",
+                            self.field(&module.field_handles[inst.handle.0 as usize])?,
+                            self.type_arguments(&view.signature_at(inst.type_parameters).0)
+                        ))
+                    })
+                    .collect::<Result<_>>()?,
+            ),
+            (
+                "signatures",
+                view.signatures()
+                    .iter()
+                    .map(|s| self.signature(&s.0))
+                    .collect(),
+            ),
+            (
+                "constants",
+                view.constant_pool()
+                    .iter()
+                    .map(|c| {
+                        let value = c
+                            .deserialize_constant()
+                            .map_or_else(|| format!("{:?}", c.data), |v| v.to_string());
+                        format!("{}: {}", self.type_(&c.type_), value)
+                    })
+                    .collect(),
+            ),
+            (
+                "friends",
+                module
+                    .friend_decls
+                    .iter()
+                    .map(|h| view.module_id_for_handle(h).short_str_lossless())
+                    .collect(),
+            ),
+        ])
+    }
+
+    fn struct_name(&self, idx: StructHandleIndex) -> Identifier {
+        self.view
+            .identifier_at(self.view.struct_handle_at(idx).name)
+            .to_owned()
+    }
+
+    fn qualified_struct_name(&self, idx: StructHandleIndex) -> String {
+        let handle = self.view.struct_handle_at(idx);
+        let module_id = self
+            .view
+            .module_id_for_handle(self.view.module_handle_at(handle.module));
+        format!(
+            "{}::{}",
+            module_id.short_str_lossless(),
+            self.view.identifier_at(handle.name)
+        )
+    }
+
+    fn field(&self, handle: &FieldHandle) -> Result<String> {
+        let def = self
+            .module
+            .struct_defs
+            .get(handle.owner.0 as usize)
+            .ok_or_else(|| format_err!("Invalid struct definition index in field handle"))?;
+        let field = match &def.field_information {
+            StructFieldInformation::Declared(fields) => fields.get(handle.field as usize),
+            StructFieldInformation::Native => None,
+        }
+        .ok_or_else(|| format_err!("Invalid field index in field handle"))?;
+        Ok(format!(
+            "{}.{}",
+            self.struct_name(def.struct_handle),
+            self.view.identifier_at(field.name)
+        ))
+    }
+
+    fn type_parameters(&self, count: usize) -> String {
+        if count == 0 {
+            return String::new();
+        }
+        let names: Vec<_> = (0..count).map(|i| format!("T{}", i)).collect();
+        format!("<{}>", names.join(", "))
+    }
+
+    fn type_arguments(&self, tys: &[SignatureToken]) -> String {
+        if tys.is_empty() {
+            return String::new();
+        }
+        let tys: Vec<_> = tys.iter().map(|ty| self.type_(ty)).collect();
+        format!("<{}>", tys.join(", "))
+    }
+
+    fn signature(&self, tys: &[SignatureToken]) -> String {
+        let tys: Vec<_> = tys.iter().map(|ty| self.type_(ty)).collect();
+        format!("({})", tys.join(", "))
+    }
+
+    fn type_(&self, ty: &SignatureToken) -> String {
+        use SignatureToken::*;
+        match ty {
+            Bool => "bool".to_string(),
+            U8 => "u8".to_string(),
+            U16 => "u16".to_string(),
+            U32 => "u32".to_string(),
+            U64 => "u64".to_string(),
+            U128 => "u128".to_string(),
+            U256 => "u256".to_string(),
+            Address => "address".to_string(),
+            Signer => "signer".to_string(),
+            Vector(ty) => format!("vector<{}>", self.type_(ty)),
+            Struct(idx) => self.qualified_struct_name(*idx),
+            StructInstantiation(idx, tys) => format!(
+                "{}{}",
+                self.qualified_struct_name(*idx),
+                self.type_arguments(tys)
+            ),
+            Reference(ty) => format!("&{}", self.type_(ty)),
+            MutableReference(ty) => format!("&mut {}", self.type_(ty)),
+            TypeParameter(idx) => format!("T{}", idx),
+        }
+    }
+}
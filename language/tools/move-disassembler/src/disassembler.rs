@@ -1160,6 +1160,68 @@ impl<'a> Disassembler<'a> {
         ))
     }
 
+    /// Disassembles the declaration of the function defined at `function_definition_index`,
+    /// without its locals or code.
+    pub fn disassemble_function_signature(
+        &self,
+        function_definition_index: FunctionDefinitionIndex,
+    ) -> Result<String> {
+        let function_definition = self.get_function_def(function_definition_index)?;
+        let function_handle = self
+            .source_mapper
+            .bytecode
+            .function_handle_at(function_definition.function);
+        self.disassemble_function_def(
+            self.source_mapper
+                .source_map
+                .get_function_source_map(function_definition_index)?,
+            Some((function_definition, function_handle)),
+            self.source_mapper
+                .bytecode
+                .identifier_at(function_handle.name),
+            &function_handle.type_parameters,
+            function_handle.parameters,
+            None,
+        )
+    }
+
+    /// Disassembles the code of the function defined at `function_definition_index`, one string
+    /// per instruction, without program counters, basic blocks or coverage information. Native
+    /// functions have no instructions.
+    pub fn disassemble_function_code(
+        &self,
+        function_definition_index: FunctionDefinitionIndex,
+    ) -> Result<Vec<String>> {
+        let function_definition = self.get_function_def(function_definition_index)?;
+        let code = match &function_definition.code {
+            Some(code) => code,
+            None => return Ok(vec![]),
+        };
+        let function_source_map = self
+            .source_mapper
+            .source_map
+            .get_function_source_map(function_definition_index)?;
+        let parameters = self.source_mapper.bytecode.signature_at(
+            self.source_mapper
+                .bytecode
+                .function_handle_at(function_definition.function)
+                .parameters,
+        );
+        let locals_sigs = self.source_mapper.bytecode.signature_at(code.locals);
+        code.code
+            .iter()
+            .map(|instruction| {
+                self.disassemble_instruction(
+                    parameters,
+                    instruction,
+                    locals_sigs,
+                    function_source_map,
+                    &function_source_map.definition_location,
+                )
+            })
+            .collect()
+    }
+
     pub fn disassemble(&self) -> Result<String> {
         let name_opt = self.source_mapper.source_map.module_name_opt.as_ref();
         let name = name_opt.map(|(addr, n)| format!("{}.{}", addr.short_str_lossless(), n));
@@ -2,4 +2,5 @@
 // Copyright (c) The Move Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod bytecode_diff;
 pub mod disassembler;
@@ -9,7 +9,9 @@ use crate::{
 use anyhow::Result;
 use move_compiler::{
     compiled_unit::AnnotatedCompiledUnit,
-    diagnostics::{report_diagnostics_to_color_buffer, report_warnings, FilesSourceText},
+    diagnostics::{
+        report_diagnostics_to_buffer_with_format, report_warnings_with_format, FilesSourceText,
+    },
     Compiler,
 };
 use petgraph::algo::toposort;
@@ -109,16 +111,22 @@ impl BuildPlan {
 
     /// Compilation process does not exit even if warnings/failures are encountered
     pub fn compile_no_exit<W: Write>(&self, writer: &mut W) -> Result<CompiledPackage> {
+        let format = self.resolution_graph.build_options.message_format;
         self.compile_with_driver(writer, |compiler| {
             let (files, units_res) = compiler.build()?;
             match units_res {
                 Ok((units, warning_diags)) => {
-                    report_warnings(&files, warning_diags);
+                    report_warnings_with_format(&files, warning_diags, format);
                     Ok((files, units))
                 }
                 Err(error_diags) => {
                     assert!(!error_diags.is_empty());
-                    let diags_buf = report_diagnostics_to_color_buffer(&files, error_diags);
+                    let diags_buf = report_diagnostics_to_buffer_with_format(
+                        &files,
+                        error_diags,
+                        format,
+                        /* colorize */ true,
+                    );
                     if let Err(err) = std::io::stdout().write_all(&diags_buf) {
                         anyhow::bail!("Cannot output compiler diagnostics: {}", err);
                     }
@@ -29,6 +29,7 @@ use move_compiler::{
         self, AnnotatedCompiledUnit, CompiledUnit, NamedCompiledModule, NamedCompiledScript,
     },
     diagnostics::FilesSourceText,
+    linters::LintConfig,
     shared::{Flags, NamedAddressMap, NumericalAddress, PackagePaths},
     Compiler,
 };
@@ -570,11 +571,26 @@ impl CompiledPackage {
             &resolved_package,
             transitive_dependencies,
         )?;
-        let flags = if resolution_graph.build_options.test_mode {
+        let mut flags = if resolution_graph.build_options.test_mode {
             Flags::testing()
         } else {
             Flags::empty()
         };
+        if resolution_graph.build_options.lint {
+            let lint_levels = resolved_package
+                .source_package
+                .lints
+                .iter()
+                .map(|(name, level)| (name.to_string(), *level))
+                .collect();
+            flags = flags
+                .set_lint(true)
+                .set_lint_config(LintConfig::new(lint_levels).set_package(root_package_name));
+        }
+        if resolution_graph.build_options.optimize {
+            flags = flags.set_optimize(true);
+        }
+        flags = flags.set_message_format(resolution_graph.build_options.message_format);
         // invoke the compiler
         let mut paths = deps_package_paths.clone();
         paths.push(sources_package_paths.clone());
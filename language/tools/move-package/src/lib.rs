@@ -11,6 +11,7 @@ pub mod source_package;
 
 use anyhow::{bail, Result};
 use clap::*;
+use move_compiler::diagnostics::MessageFormat;
 use move_core_types::account_address::AccountAddress;
 use move_model::model::GlobalEnv;
 use serde::{Deserialize, Serialize};
@@ -134,6 +135,22 @@ pub struct BuildConfig {
     /// Skip fetching latest git dependencies
     #[clap(long = "skip-fetch-latest-git-deps", global = true)]
     pub skip_fetch_latest_git_deps: bool,
+
+    /// Run the linters over the modules of the root package, at the levels set in the '[lints]'
+    /// section of its manifest
+    #[clap(skip)]
+    #[serde(default)]
+    pub lint: bool,
+
+    /// Optimize the bytecode of the compiled modules
+    #[clap(name = "optimize", short = 'O', long = "optimize", global = true)]
+    #[serde(default)]
+    pub optimize: bool,
+
+    /// How compiler diagnostics are reported: `human`, or `json` lines on stdout
+    #[clap(long = "message-format", global = true, default_value = "human")]
+    #[serde(default)]
+    pub message_format: MessageFormat,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, PartialOrd)]
@@ -5,6 +5,7 @@
 use crate::{package_hooks, source_package::parsed_manifest as PM, Architecture};
 use anyhow::{bail, format_err, Context, Result};
 use move_command_line_common::env::MOVE_HOME;
+use move_compiler::linters::{self, LintLevel};
 use move_core_types::account_address::{AccountAddress, AccountAddressParseError};
 use move_symbol_pool::symbol::Symbol;
 use std::{
@@ -23,6 +24,7 @@ const ADDRESSES_NAME: &str = "addresses";
 const DEV_ADDRESSES_NAME: &str = "dev-addresses";
 const DEPENDENCY_NAME: &str = "dependencies";
 const DEV_DEPENDENCY_NAME: &str = "dev-dependencies";
+const LINTS_NAME: &str = "lints";
 
 const KNOWN_NAMES: &[&str] = &[
     PACKAGE_NAME,
@@ -31,6 +33,7 @@ const KNOWN_NAMES: &[&str] = &[
     DEV_ADDRESSES_NAME,
     DEPENDENCY_NAME,
     DEV_DEPENDENCY_NAME,
+    LINTS_NAME,
 ];
 
 const REQUIRED_FIELDS: &[&str] = &[PACKAGE_NAME];
@@ -87,6 +90,12 @@ pub fn parse_source_manifest(tval: TV) -> Result<PM::SourceManifest> {
                 .transpose()
                 .context("Error parsing '[dev-dependencies]' section of manifest")?
                 .unwrap_or_default();
+            let lints = table
+                .remove(LINTS_NAME)
+                .map(parse_lints)
+                .transpose()
+                .context("Error parsing '[lints]' section of manifest")?
+                .unwrap_or_default();
             Ok(PM::SourceManifest {
                 package,
                 addresses,
@@ -94,6 +103,7 @@ pub fn parse_source_manifest(tval: TV) -> Result<PM::SourceManifest> {
                 build,
                 dependencies,
                 dev_dependencies,
+                lints,
             })
         }
         x => {
@@ -215,6 +225,38 @@ pub fn parse_build_info(tval: TV) -> Result<PM::BuildInfo> {
     }
 }
 
+pub fn parse_lints(tval: TV) -> Result<PM::LintLevels> {
+    match tval {
+        TV::Table(table) => {
+            let mut lints = BTreeMap::new();
+            for (lint_name, entry) in table.into_iter() {
+                if linters::find_lint(&lint_name).is_none() {
+                    bail!(
+                        "Unknown lint '{}'. Expected one of: {}",
+                        lint_name,
+                        linters::ALL_LINTS
+                            .iter()
+                            .map(|lint| lint.name)
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )
+                }
+                let level = entry
+                    .as_str()
+                    .ok_or_else(|| format_err!("Invalid level for lint '{}'", lint_name))?
+                    .parse::<LintLevel>()?;
+                lints.insert(Symbol::from(lint_name), level);
+            }
+            Ok(lints)
+        }
+        x => bail!(
+            "Malformed section in manifest {}. Expected a table, but encountered a {}",
+            x,
+            x.type_str()
+        ),
+    }
+}
+
 pub fn parse_addresses(tval: TV) -> Result<PM::AddressDeclarations> {
     match tval {
         TV::Table(table) => {
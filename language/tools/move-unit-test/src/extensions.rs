@@ -6,16 +6,19 @@
 //! Such extensions are enabled by cfg features and must be compiled into the test
 //! to be usable.
 
+use anyhow::Result;
+use move_core_types::effects::ChangeSet;
 use move_vm_runtime::native_extensions::NativeContextExtensions;
+use move_vm_test_utils::InMemoryStorage;
 use once_cell::sync::Lazy;
 use std::{fmt::Write, sync::Mutex};
 
+#[cfg(feature = "table-extension")]
+use anyhow::anyhow;
 #[cfg(feature = "table-extension")]
 use itertools::Itertools;
 #[cfg(feature = "table-extension")]
 use move_table_extension::NativeTableContext;
-#[cfg(feature = "table-extension")]
-use move_vm_test_utils::BlankStorage;
 
 static EXTENSION_HOOK: Lazy<
     Mutex<Option<Box<dyn Fn(&mut NativeContextExtensions<'_>) + Send + Sync>>>,
@@ -37,15 +40,19 @@ pub fn set_extension_hook(p: Box<dyn Fn(&mut NativeContextExtensions<'_>) + Send
     *EXTENSION_HOOK.lock().unwrap() = Some(p)
 }
 
-/// Create all available native context extensions.
+/// Create all available native context extensions for a session on `_storage`. Sessions whose
+/// changes are applied to the same storage must use different `_session_hash`es.
 #[allow(unused_mut, clippy::let_and_return)]
-pub(crate) fn new_extensions<'a>() -> NativeContextExtensions<'a> {
+pub(crate) fn new_extensions<'a>(
+    _storage: &'a InMemoryStorage,
+    _session_hash: [u8; 32],
+) -> NativeContextExtensions<'a> {
     let mut e = NativeContextExtensions::default();
     if let Some(h) = &*EXTENSION_HOOK.lock().unwrap() {
         (*h)(&mut e)
     }
     #[cfg(feature = "table-extension")]
-    create_table_extension(&mut e);
+    create_table_extension(&mut e, _storage, _session_hash);
     e
 }
 
@@ -56,12 +63,32 @@ pub(crate) fn print_change_sets<W: Write>(_w: &mut W, mut extensions: NativeCont
     print_table_extension(_w, &mut extensions);
 }
 
+/// Apply the changes of a session, including those of the native context extensions, to `storage`.
+pub(crate) fn apply_change_sets(
+    storage: &mut InMemoryStorage,
+    change_set: ChangeSet,
+    mut _extensions: NativeContextExtensions,
+) -> Result<()> {
+    storage.apply_extended(
+        change_set,
+        #[cfg(feature = "table-extension")]
+        _extensions
+            .remove::<NativeTableContext>()
+            .into_change_set()
+            .map_err(|err| anyhow!("Unable to compute table changes: {}", err))?,
+    )
+}
+
 // =============================================================================================
 // Table Extensions
 
 #[cfg(feature = "table-extension")]
-fn create_table_extension(extensions: &mut NativeContextExtensions) {
-    extensions.add(NativeTableContext::new([0u8; 32], &*DUMMY_RESOLVER));
+fn create_table_extension<'a>(
+    extensions: &mut NativeContextExtensions<'a>,
+    storage: &'a InMemoryStorage,
+    session_hash: [u8; 32],
+) {
+    extensions.add(NativeTableContext::new(session_hash, storage));
 }
 
 #[cfg(feature = "table-extension")]
@@ -96,20 +123,19 @@ fn print_table_extension<W: Write>(w: &mut W, extensions: &mut NativeContextExte
     }
 }
 
-#[cfg(feature = "table-extension")]
-static DUMMY_RESOLVER: Lazy<BlankStorage> = Lazy::new(|| BlankStorage);
-
 #[cfg(test)]
 mod tests {
     use crate::extensions::{new_extensions, set_extension_hook};
     use better_any::{Tid, TidAble};
     use move_vm_runtime::native_extensions::NativeContextExtensions;
+    use move_vm_test_utils::InMemoryStorage;
 
     /// A test that extension hooks work as expected.
     #[test]
     fn test_extension_hook() {
         set_extension_hook(Box::new(my_hook));
-        let ext = new_extensions();
+        let storage = InMemoryStorage::new();
+        let ext = new_extensions(&storage, [0u8; 32]);
         let _e = ext.get::<TestExtension>();
     }
 
@@ -0,0 +1,155 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Resource fixtures, used to pre-populate the global storage that all tests start from.
+//!
+//! A fixtures file is a JSON array of resources, e.g.
+//!
+//! ```json
+//! [
+//!     {
+//!         "address": "0x42",
+//!         "type": "0x1::coin::Balance",
+//!         "value": { "value": 100, "owner": "0x42", "name": "Coin" }
+//!     }
+//! ]
+//! ```
+//!
+//! Values are given in JSON form: integers as numbers or decimal strings, addresses as hex
+//! literals, vectors as arrays, and structs as objects keyed by field name. A `vector<u8>` can also
+//! be given as a `0x`-prefixed hex string or as a UTF-8 string.
+
+use anyhow::{anyhow, bail, Context, Result};
+use move_bytecode_utils::{layout::TypeLayoutBuilder, module_cache::ModuleCache};
+use move_core_types::{
+    account_address::AccountAddress,
+    language_storage::TypeTag,
+    parser::parse_struct_tag,
+    u256::U256,
+    value::{MoveStruct, MoveStructLayout, MoveTypeLayout, MoveValue},
+};
+use move_vm_test_utils::InMemoryStorage;
+use serde::Deserialize;
+use serde_json::Value;
+use std::{fs, path::Path};
+
+/// A resource to publish under an account before running any test.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Fixture {
+    /// The account the resource is published under, e.g. `0x42`
+    pub address: String,
+    /// The fully qualified type of the resource, e.g. `0x1::coin::Balance`
+    #[serde(rename = "type")]
+    pub type_: String,
+    /// The value of the resource
+    pub value: Value,
+}
+
+/// Publish the resources in the fixtures file at `path` to `storage`. The modules declaring the
+/// resources must already be published in `storage`.
+pub fn load_fixtures(storage: &mut InMemoryStorage, path: &Path) -> Result<()> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Unable to read fixtures file {}", path.display()))?;
+    let fixtures: Vec<Fixture> = serde_json::from_str(&contents)
+        .with_context(|| format!("Malformed fixtures file {}", path.display()))?;
+    for fixture in &fixtures {
+        publish_fixture(storage, fixture).with_context(|| {
+            format!(
+                "Invalid fixture for resource {} under {} in {}",
+                fixture.type_,
+                fixture.address,
+                path.display()
+            )
+        })?;
+    }
+    Ok(())
+}
+
+fn publish_fixture(storage: &mut InMemoryStorage, fixture: &Fixture) -> Result<()> {
+    let address = AccountAddress::from_hex_literal(&fixture.address)
+        .map_err(|_| anyhow!("Invalid address '{}'", fixture.address))?;
+    let struct_tag = parse_struct_tag(&fixture.type_)?;
+    let layout = TypeLayoutBuilder::build_with_fields(
+        &TypeTag::Struct(struct_tag.clone()),
+        &ModuleCache::new(&*storage),
+    )?;
+    let blob = json_to_move_value(&fixture.value, &layout)?
+        .simple_serialize()
+        .ok_or_else(|| anyhow!("Unable to serialize the value"))?;
+    storage.publish_or_overwrite_resource(address, struct_tag, blob);
+    Ok(())
+}
+
+fn json_to_move_value(value: &Value, layout: &MoveTypeLayout) -> Result<MoveValue> {
+    Ok(match (layout, value) {
+        (MoveTypeLayout::Bool, Value::Bool(b)) => MoveValue::Bool(*b),
+        (MoveTypeLayout::U8, _) => MoveValue::U8(json_to_integer(value)?.try_into()?),
+        (MoveTypeLayout::U16, _) => MoveValue::U16(json_to_integer(value)?.try_into()?),
+        (MoveTypeLayout::U32, _) => MoveValue::U32(json_to_integer(value)?.try_into()?),
+        (MoveTypeLayout::U64, _) => MoveValue::U64(json_to_integer(value)?.try_into()?),
+        (MoveTypeLayout::U128, _) => MoveValue::U128(json_to_integer(value)?),
+        (MoveTypeLayout::U256, Value::String(s)) => MoveValue::U256(
+            s.parse::<U256>()
+                .map_err(|_| anyhow!("Invalid u256 '{}'", s))?,
+        ),
+        (MoveTypeLayout::U256, Value::Number(_)) => {
+            MoveValue::U256(U256::from(json_to_integer(value)?))
+        }
+        (MoveTypeLayout::Address, Value::String(s)) => MoveValue::Address(
+            AccountAddress::from_hex_literal(s).map_err(|_| anyhow!("Invalid address '{}'", s))?,
+        ),
+        (MoveTypeLayout::Vector(elem_layout), Value::String(s))
+            if matches!(**elem_layout, MoveTypeLayout::U8) =>
+        {
+            let bytes = match s.strip_prefix("0x") {
+                Some(hex_str) => {
+                    hex::decode(hex_str).map_err(|_| anyhow!("Invalid hex string '{}'", s))?
+                }
+                None => s.as_bytes().to_vec(),
+            };
+            MoveValue::vector_u8(bytes)
+        }
+        (MoveTypeLayout::Vector(elem_layout), Value::Array(elems)) => MoveValue::Vector(
+            elems
+                .iter()
+                .map(|elem| json_to_move_value(elem, elem_layout))
+                .collect::<Result<_>>()?,
+        ),
+        (MoveTypeLayout::Struct(MoveStructLayout::WithFields(fields)), Value::Object(object)) => {
+            if let Some(unknown) = object
+                .keys()
+                .find(|key| !fields.iter().any(|field| field.name.as_str() == *key))
+            {
+                bail!("Unknown field '{}'", unknown)
+            }
+            MoveValue::Struct(MoveStruct::Runtime(
+                fields
+                    .iter()
+                    .map(|field| {
+                        let field_value = object
+                            .get(field.name.as_str())
+                            .ok_or_else(|| anyhow!("Missing field '{}'", field.name))?;
+                        json_to_move_value(field_value, &field.layout)
+                            .with_context(|| format!("In field '{}'", field.name))
+                    })
+                    .collect::<Result<_>>()?,
+            ))
+        }
+        (MoveTypeLayout::Signer, _) => bail!("Signers cannot be stored in fixtures"),
+        (layout, value) => bail!("Expected a value of type {}, got {}", layout, value),
+    })
+}
+
+fn json_to_integer(value: &Value) -> Result<u128> {
+    match value {
+        Value::Number(n) => n
+            .as_u64()
+            .map(u128::from)
+            .ok_or_else(|| anyhow!("Invalid unsigned integer {}", n)),
+        Value::String(s) => s
+            .parse::<u128>()
+            .map_err(|_| anyhow!("Invalid unsigned integer '{}'", s)),
+        _ => bail!("Expected an unsigned integer, got {}", value),
+    }
+}
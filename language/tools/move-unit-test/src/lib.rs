@@ -4,6 +4,8 @@
 
 pub mod cargo_runner;
 pub mod extensions;
+pub mod fixtures;
+pub mod random_test;
 pub mod test_reporter;
 pub mod test_runner;
 
@@ -21,14 +23,29 @@ use move_core_types::language_storage::ModuleId;
 use move_vm_runtime::native_functions::NativeFunctionTable;
 use std::{
     collections::BTreeMap,
-    io::{Result, Write},
+    io::{Error, ErrorKind, Result, Write},
     marker::Send,
+    path::Path,
     sync::Mutex,
 };
 
 /// The default value bounding the number of instructions executed in a test.
 const DEFAULT_EXECUTION_BOUND: u64 = 100_000;
 
+/// The default number of sets of arguments each random test is run with.
+const DEFAULT_RANDOM_TEST_ITERATIONS: u64 = 100;
+
+/// The format in which the results of a test run are reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ArgEnum)]
+pub enum TestReportFormat {
+    /// Human-readable progress and summary output
+    Human,
+    /// A JUnit XML report, with one test suite per module
+    Junit,
+    /// A JSON report with the outcome of every test
+    Json,
+}
+
 #[derive(Debug, Parser, Clone)]
 #[clap(author, version, about)]
 pub struct UnitTestingConfig {
@@ -114,6 +131,28 @@ pub struct UnitTestingConfig {
     #[clap(short = 'v', long = "verbose")]
     pub report_writeset: bool,
 
+    /// The format of the test results. `junit` and `json` print only the machine-readable report,
+    /// including per-test status, duration, instruction count and failure details.
+    #[clap(long = "format", arg_enum, default_value = "human")]
+    pub report_format: TestReportFormat,
+
+    /// The number of sets of arguments each `#[random_test]` is run with.
+    #[clap(
+        name = "random_test_iterations",
+        long = "random_test_iterations",
+        default_value = "100"
+    )]
+    pub random_test_iterations: u64,
+
+    /// The seed used to generate the arguments of `#[random_test]`s. A random seed is picked if
+    /// not set; the seed of a failing test is reported so that the failure can be reproduced.
+    #[clap(name = "seed", long = "seed")]
+    pub seed: Option<u64>,
+
+    /// A JSON file of resources to publish in global storage before running any test
+    #[clap(name = "fixtures", long = "fixtures")]
+    pub fixtures: Option<String>,
+
     /// Use the EVM-based execution backend.
     /// Does not work with --stackless.
     #[cfg(feature = "evm-backend")]
@@ -147,6 +186,10 @@ impl UnitTestingConfig {
             list: false,
             named_address_values: vec![],
             report_writeset: false,
+            report_format: TestReportFormat::Human,
+            random_test_iterations: DEFAULT_RANDOM_TEST_ITERATIONS,
+            seed: None,
+            fixtures: None,
 
             #[cfg(feature = "evm-backend")]
             evm: false,
@@ -237,7 +280,10 @@ impl UnitTestingConfig {
             return Ok((shared_writer.into_inner().unwrap(), true));
         }
 
-        writeln!(shared_writer.lock().unwrap(), "Running Move unit tests")?;
+        let human_readable = self.report_format == TestReportFormat::Human;
+        if human_readable {
+            writeln!(shared_writer.lock().unwrap(), "Running Move unit tests")?;
+        }
         let mut test_runner = TestRunner::new(
             self.instruction_execution_bound
                 .unwrap_or(DEFAULT_EXECUTION_BOUND),
@@ -250,25 +296,40 @@ impl UnitTestingConfig {
             native_function_table,
             verify_and_create_named_address_mapping(self.named_address_values.clone()).unwrap(),
             self.report_writeset,
+            self.random_test_iterations,
+            self.seed,
+            self.fixtures.as_deref().map(Path::new),
             #[cfg(feature = "evm-backend")]
             self.evm,
         )
-        .unwrap();
+        .map_err(|err| Error::new(ErrorKind::InvalidInput, format!("{:#}", err)))?;
 
         if let Some(filter_str) = &self.filter {
             test_runner.filter(filter_str)
         }
 
-        let test_results = test_runner.run(&shared_writer).unwrap();
-        if self.report_statistics {
-            test_results.report_statistics(&shared_writer)?;
-        }
+        // Per-test progress is only meaningful to humans; keep machine-readable output parseable.
+        let test_results = if human_readable {
+            test_runner.run(&shared_writer).unwrap()
+        } else {
+            test_runner.run(&Mutex::new(std::io::sink())).unwrap()
+        };
+
+        let ok = match self.report_format {
+            TestReportFormat::Human => {
+                if self.report_statistics {
+                    test_results.report_statistics(&shared_writer)?;
+                }
 
-        if self.report_writeset {
-            test_results.report_goldens(&shared_writer)?;
-        }
+                if self.report_writeset {
+                    test_results.report_goldens(&shared_writer)?;
+                }
 
-        let ok = test_results.summarize(&shared_writer)?;
+                test_results.summarize(&shared_writer)?
+            }
+            TestReportFormat::Junit => test_results.report_junit(&shared_writer)?,
+            TestReportFormat::Json => test_results.report_json(&shared_writer)?,
+        };
 
         let writer = shared_writer.into_inner().unwrap();
         Ok((writer, ok))
@@ -0,0 +1,218 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Generation and shrinking of the arguments of `#[random_test]`s.
+
+use move_compiler::unit_test::TestArgument;
+use move_core_types::{
+    account_address::AccountAddress,
+    u256::U256,
+    value::{MoveStruct, MoveStructLayout, MoveTypeLayout, MoveValue},
+};
+use rand::Rng;
+
+/// The maximum length of a generated vector.
+const MAX_VECTOR_LENGTH: usize = 8;
+
+/// Generate a value for each parameter of a random test. Parameters that were assigned in the test
+/// attribute keep their value.
+pub fn generate_arguments(
+    rng: &mut impl Rng,
+    arguments: &[(String, TestArgument)],
+) -> Vec<MoveValue> {
+    arguments
+        .iter()
+        .map(|(_, argument)| match argument {
+            TestArgument::Fixed(value) => value.clone(),
+            TestArgument::Generated(layout) => generate_value(rng, layout),
+        })
+        .collect()
+}
+
+// Integers are biased towards the boundaries of their domain, where most edge cases are.
+macro_rules! generate_integer {
+    ($rng:expr, $ty:ty) => {
+        match $rng.gen_range(0..8) {
+            0 => 0,
+            1 => <$ty>::MAX,
+            2 => $rng.gen_range(0..=16),
+            _ => $rng.gen::<$ty>(),
+        }
+    };
+}
+
+fn generate_value(rng: &mut impl Rng, layout: &MoveTypeLayout) -> MoveValue {
+    match layout {
+        MoveTypeLayout::Bool => MoveValue::Bool(rng.gen()),
+        MoveTypeLayout::U8 => MoveValue::U8(generate_integer!(rng, u8)),
+        MoveTypeLayout::U16 => MoveValue::U16(generate_integer!(rng, u16)),
+        MoveTypeLayout::U32 => MoveValue::U32(generate_integer!(rng, u32)),
+        MoveTypeLayout::U64 => MoveValue::U64(generate_integer!(rng, u64)),
+        MoveTypeLayout::U128 => MoveValue::U128(generate_integer!(rng, u128)),
+        MoveTypeLayout::U256 => MoveValue::U256(match rng.gen_range(0..8) {
+            0 => U256::zero(),
+            1 => U256::max_value(),
+            2 => U256::from(rng.gen_range(0u8..=16)),
+            _ => U256::from_le_bytes(&rng.gen()),
+        }),
+        MoveTypeLayout::Address => MoveValue::Address(AccountAddress::new(rng.gen())),
+        MoveTypeLayout::Vector(elem_layout) => {
+            let len = rng.gen_range(0..=MAX_VECTOR_LENGTH);
+            MoveValue::Vector((0..len).map(|_| generate_value(rng, elem_layout)).collect())
+        }
+        MoveTypeLayout::Struct(struct_layout) => {
+            MoveValue::Struct(MoveStruct::Runtime(match struct_layout {
+                MoveStructLayout::Runtime(fields) => fields
+                    .iter()
+                    .map(|field| generate_value(rng, field))
+                    .collect(),
+                MoveStructLayout::WithFields(fields)
+                | MoveStructLayout::WithTypes { fields, .. } => fields
+                    .iter()
+                    .map(|field| generate_value(rng, &field.layout))
+                    .collect(),
+            }))
+        }
+        MoveTypeLayout::Signer => {
+            unreachable!("ICE: signer arguments of random tests are never generated")
+        }
+    }
+}
+
+/// Compute the candidates for simplifying the arguments `values` of a failing random test. Every
+/// candidate differs from `values` in exactly one generated argument. The candidates of different
+/// arguments are interleaved, and for each argument the candidates that are more likely to simplify
+/// it by a lot come first.
+pub fn shrink_arguments(
+    arguments: &[(String, TestArgument)],
+    values: &[MoveValue],
+) -> Vec<Vec<MoveValue>> {
+    let shrunk_values = arguments
+        .iter()
+        .zip(values)
+        .enumerate()
+        .filter(|(_, ((_, argument), _))| matches!(argument, TestArgument::Generated(_)))
+        .map(|(idx, (_, value))| (idx, shrink_value(value)))
+        .collect();
+    interleave_candidates(values, shrunk_values)
+}
+
+/// Build the candidates that replace a single element of `values` by one of its shrunk values,
+/// taking the shrunk values of the different elements in turn.
+fn interleave_candidates(
+    values: &[MoveValue],
+    shrunk_values: Vec<(usize, Vec<MoveValue>)>,
+) -> Vec<Vec<MoveValue>> {
+    let mut shrunk_values: Vec<_> = shrunk_values
+        .into_iter()
+        .map(|(idx, shrunk)| (idx, shrunk.into_iter()))
+        .collect();
+    let mut candidates = vec![];
+    while !shrunk_values.is_empty() {
+        shrunk_values.retain_mut(|(idx, shrunk)| match shrunk.next() {
+            Some(shrunk_value) => {
+                let mut candidate = values.to_vec();
+                candidate[*idx] = shrunk_value;
+                candidates.push(candidate);
+                true
+            }
+            None => false,
+        });
+    }
+    candidates
+}
+
+// Integers shrink towards zero: first to zero itself, then by subtracting ever smaller halvings
+// of the value, so that the greedy search converges in a logarithmic number of steps.
+macro_rules! shrink_integer {
+    ($value:expr, $ctor:path) => {{
+        let value = $value;
+        let mut candidates = vec![];
+        if value != 0 {
+            candidates.push(0);
+        }
+        let mut delta = value / 2;
+        while delta != 0 {
+            candidates.push(value - delta);
+            delta /= 2;
+        }
+        if value > 1 {
+            candidates.push(value - 1);
+        }
+        candidates.dedup();
+        candidates.into_iter().map($ctor).collect()
+    }};
+}
+
+fn shrink_value(value: &MoveValue) -> Vec<MoveValue> {
+    match value {
+        MoveValue::Bool(true) => vec![MoveValue::Bool(false)],
+        MoveValue::Bool(false) | MoveValue::Signer(_) => vec![],
+        MoveValue::U8(u) => shrink_integer!(*u, MoveValue::U8),
+        MoveValue::U16(u) => shrink_integer!(*u, MoveValue::U16),
+        MoveValue::U32(u) => shrink_integer!(*u, MoveValue::U32),
+        MoveValue::U64(u) => shrink_integer!(*u, MoveValue::U64),
+        MoveValue::U128(u) => shrink_integer!(*u, MoveValue::U128),
+        MoveValue::U256(u) => {
+            let mut candidates = vec![];
+            if *u != U256::zero() {
+                candidates.push(U256::zero());
+            }
+            let two = U256::from(2u8);
+            let mut delta = u.checked_div(two).unwrap();
+            while delta != U256::zero() {
+                candidates.push(u.checked_sub(delta).unwrap());
+                delta = delta.checked_div(two).unwrap();
+            }
+            if *u > U256::one() {
+                candidates.push(u.checked_sub(U256::one()).unwrap());
+            }
+            candidates.dedup();
+            candidates.into_iter().map(MoveValue::U256).collect()
+        }
+        MoveValue::Address(addr) if *addr != AccountAddress::ZERO => {
+            vec![MoveValue::Address(AccountAddress::ZERO)]
+        }
+        MoveValue::Address(_) => vec![],
+        MoveValue::Vector(elems) => {
+            let mut candidates = vec![];
+            if !elems.is_empty() {
+                candidates.push(MoveValue::Vector(vec![]));
+            }
+            if elems.len() > 1 {
+                candidates.push(MoveValue::Vector(elems[..elems.len() / 2].to_vec()));
+            }
+            for idx in 0..elems.len() {
+                let mut shorter = elems.clone();
+                shorter.remove(idx);
+                candidates.push(MoveValue::Vector(shorter));
+            }
+            let shrunk_elems = elems.iter().map(shrink_value).enumerate().collect();
+            candidates.extend(
+                interleave_candidates(elems, shrunk_elems)
+                    .into_iter()
+                    .map(MoveValue::Vector),
+            );
+            candidates
+        }
+        MoveValue::Struct(MoveStruct::Runtime(fields)) => {
+            let shrunk_fields = fields.iter().map(shrink_value).enumerate().collect();
+            interleave_candidates(fields, shrunk_fields)
+                .into_iter()
+                .map(|fields| MoveValue::Struct(MoveStruct::Runtime(fields)))
+                .collect()
+        }
+        // Generated structs always use the runtime representation
+        MoveValue::Struct(_) => vec![],
+    }
+}
+
+/// Render the arguments of a random test as `name = value` pairs, in parameter order.
+pub fn format_arguments(arguments: &[(String, TestArgument)], values: &[MoveValue]) -> String {
+    arguments
+        .iter()
+        .zip(values)
+        .map(|((name, _), value)| format!("{} = {}", name, value))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -5,6 +5,7 @@
 use crate::format_module_id;
 use codespan_reporting::files::{Files, SimpleFiles};
 use colored::{control, Colorize};
+use itertools::Itertools;
 use move_binary_format::{
     access::ModuleAccess,
     errors::{ExecutionState, Location, VMError, VMResult},
@@ -12,11 +13,12 @@ use move_binary_format::{
 use move_command_line_common::files::FileHash;
 use move_compiler::{
     diagnostics::{self, Diagnostic},
-    unit_test::{ModuleTestPlan, TestName, TestPlan},
+    unit_test::{ExpectedMoveError, ModuleTestPlan, TestName, TestPlan},
 };
-use move_core_types::{effects::ChangeSet, language_storage::ModuleId};
+use move_core_types::{effects::ChangeSet, language_storage::ModuleId, vm_status::StatusCode};
 use move_ir_types::location::Loc;
 use move_symbol_pool::Symbol;
+use serde::Serialize;
 use std::{
     collections::{BTreeMap, BTreeSet, HashMap},
     io::{Result, Write},
@@ -30,6 +32,9 @@ pub enum FailureReason {
     NoAbort(String),
     // Aborted with the wrong code
     WrongAbort(String, u64, u64),
+    // Failed with an error other than the expected one, e.g. in the wrong module. Holds the
+    // descriptions of the expected and the actual error
+    WrongError(String, String, String),
     // Abort wasn't expected, but it did
     Aborted(String, u64),
     // Test timed out
@@ -45,6 +50,15 @@ pub enum FailureReason {
     Property(String),
     // The test failed for some unknown reason. This shouldn't be encountered
     Unknown(String),
+    // The #[before_each] function of the test's module failed
+    SetupFailed(String),
+    // A random test failed for the (shrunk) generated arguments
+    RandomTest {
+        seed: u64,
+        iteration: u64,
+        arguments: String,
+        reason: Box<FailureReason>,
+    },
 
     // Failed to compile Move code into EVM bytecode.
     #[cfg(feature = "evm-backend")]
@@ -79,6 +93,69 @@ pub struct TestResults {
     test_plan: TestPlan,
 }
 
+/// Machine-readable report of a test run, as emitted by `--format json`.
+#[derive(Debug, Serialize)]
+pub struct TestRunReport {
+    pub total: u64,
+    pub passed: u64,
+    pub failed: u64,
+    pub duration_secs: f64,
+    pub tests: Vec<TestReport>,
+}
+
+/// The outcome of a single test in a `TestRunReport`.
+#[derive(Debug, Serialize)]
+pub struct TestReport {
+    /// Fully qualified name of the test, i.e., `<addr>::<module>::<function>`.
+    pub name: String,
+    pub module: String,
+    pub function: String,
+    pub status: TestStatus,
+    pub duration_secs: f64,
+    pub instructions_executed: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure: Option<TestFailureReport>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TestStatus {
+    Passed,
+    Failed,
+    TimedOut,
+}
+
+/// Details on why a test failed.
+#[derive(Debug, Serialize)]
+pub struct TestFailureReport {
+    /// A short, stable identifier of the `FailureReason`, e.g., `aborted` or `wrong_abort`.
+    pub kind: &'static str,
+    /// A one-line description of the failure.
+    pub message: String,
+    /// The full failure report, as printed in the human-readable output.
+    pub details: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub abort_code: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<FailureLocation>,
+}
+
+/// The location at which a test failed, as reported by the VM.
+#[derive(Debug, Serialize)]
+pub struct FailureLocation {
+    pub module: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code_offset: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line_start: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line_end: Option<usize>,
+}
+
 impl TestRunInfo {
     pub fn new(function_ident: String, elapsed_time: Duration, instructions_executed: u64) -> Self {
         Self {
@@ -102,6 +179,22 @@ impl FailureReason {
         )
     }
 
+    pub fn wrong_error(expected: &ExpectedMoveError, received: &VMError) -> Self {
+        let location = match received.location() {
+            Location::Module(module_id) => Some(module_id),
+            Location::Undefined | Location::Script => None,
+        };
+        FailureReason::WrongError(
+            "Test did not fail with the expected error".to_string(),
+            describe_error(
+                expected.status,
+                expected.sub_status,
+                expected.location.as_ref(),
+            ),
+            describe_error(received.major_status(), received.sub_status(), location),
+        )
+    }
+
     pub fn aborted(abort_code: u64) -> Self {
         FailureReason::Aborted("Test was not expected to abort".to_string(), abort_code)
     }
@@ -136,6 +229,94 @@ impl FailureReason {
     pub fn unknown() -> Self {
         FailureReason::Unknown("ITE: An unknown error was reported.".to_string())
     }
+
+    pub fn setup_failed(message: String) -> Self {
+        FailureReason::SetupFailed(message)
+    }
+
+    pub fn random_test(seed: u64, iteration: u64, arguments: String, reason: Self) -> Self {
+        FailureReason::RandomTest {
+            seed,
+            iteration,
+            arguments,
+            reason: Box::new(reason),
+        }
+    }
+
+    pub fn is_timeout(&self) -> bool {
+        match self {
+            FailureReason::Timeout(_) => true,
+            FailureReason::RandomTest { reason, .. } => reason.is_timeout(),
+            _ => false,
+        }
+    }
+
+    /// A short, stable identifier for this kind of failure, used in machine-readable reports.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            FailureReason::NoAbort(_) => "no_abort",
+            FailureReason::WrongAbort(..) => "wrong_abort",
+            FailureReason::WrongError(..) => "wrong_error",
+            FailureReason::Aborted(..) => "aborted",
+            FailureReason::Timeout(_) => "timeout",
+            FailureReason::Mismatch { .. } => "mismatch",
+            FailureReason::Property(_) => "property",
+            FailureReason::Unknown(_) => "unknown",
+            FailureReason::SetupFailed(_) => "setup_failed",
+            FailureReason::RandomTest { reason, .. } => reason.kind(),
+            #[cfg(feature = "evm-backend")]
+            FailureReason::MoveToEVMError(_) => "move_to_evm_error",
+        }
+    }
+
+    /// A one-line description of this failure, without any source locations.
+    pub fn message(&self) -> String {
+        match self {
+            FailureReason::NoAbort(message)
+            | FailureReason::Timeout(message)
+            | FailureReason::Unknown(message)
+            | FailureReason::SetupFailed(message) => message.clone(),
+            FailureReason::WrongAbort(message, expected_code, other_code) => format!(
+                "{}. Expected test to abort with {} but instead it aborted with {}",
+                message, expected_code, other_code
+            ),
+            FailureReason::WrongError(message, expected, received) => format!(
+                "{}. Expected test to fail with {} but instead it failed with {}",
+                message, expected, received
+            ),
+            FailureReason::Aborted(message, code) => {
+                format!("{} but it aborted with {}", message, code)
+            }
+            FailureReason::Mismatch { .. } => {
+                "Executions via Move VM and stackless VM yield different results".to_string()
+            }
+            FailureReason::Property(message) => message.lines().next().unwrap_or("").to_string(),
+            FailureReason::RandomTest {
+                seed,
+                arguments,
+                reason,
+                ..
+            } => format!(
+                "{} (random seed {}, arguments: {})",
+                reason.message(),
+                seed,
+                arguments
+            ),
+            #[cfg(feature = "evm-backend")]
+            FailureReason::MoveToEVMError(_) => {
+                "Failed to compile Move code into EVM bytecode".to_string()
+            }
+        }
+    }
+
+    /// The abort code carried by this failure, if the test aborted.
+    pub fn abort_code(&self) -> Option<u64> {
+        match self {
+            FailureReason::WrongAbort(_, _, code) | FailureReason::Aborted(_, code) => Some(*code),
+            FailureReason::RandomTest { reason, .. } => reason.abort_code(),
+            _ => None,
+        }
+    }
 }
 
 impl TestFailure {
@@ -154,7 +335,35 @@ impl TestFailure {
     }
 
     pub fn render_error(&self, test_plan: &TestPlan) -> String {
-        let error_string = match &self.failure_reason {
+        self.render_error_impl(test_plan, control::SHOULD_COLORIZE.should_colorize())
+    }
+
+    fn render_error_impl(&self, test_plan: &TestPlan, colorize: bool) -> String {
+        let error_string = self.render_failure_reason(&self.failure_reason, test_plan, colorize);
+
+        match &self.storage_state {
+            None => error_string,
+            Some(storage_state) => {
+                format!(
+                    "{}\n────── Storage state at point of failure ──────\n{}",
+                    error_string,
+                    if storage_state.is_empty() {
+                        "<empty>"
+                    } else {
+                        storage_state
+                    }
+                )
+            }
+        }
+    }
+
+    fn render_failure_reason(
+        &self,
+        failure_reason: &FailureReason,
+        test_plan: &TestPlan,
+        colorize: bool,
+    ) -> String {
+        match failure_reason {
             FailureReason::NoAbort(message) => message.to_string(),
             FailureReason::Timeout(message) => message.to_string(),
             FailureReason::WrongAbort(message, expected_code, other_code) => {
@@ -162,11 +371,18 @@ impl TestFailure {
                     "{}. Expected test to abort with {} but instead it aborted with {} here",
                     message, expected_code, other_code,
                 );
-                Self::report_error_with_location(test_plan, base_message, &self.vm_error)
+                Self::report_error_with_location(test_plan, base_message, &self.vm_error, colorize)
+            }
+            FailureReason::WrongError(message, expected, received) => {
+                let base_message = format!(
+                    "{}. Expected test to fail with {} but instead it failed with {} here",
+                    message, expected, received,
+                );
+                Self::report_error_with_location(test_plan, base_message, &self.vm_error, colorize)
             }
             FailureReason::Aborted(message, code) => {
                 let base_message = format!("{} but it aborted with {} here", message, code);
-                Self::report_error_with_location(test_plan, base_message, &self.vm_error)
+                Self::report_error_with_location(test_plan, base_message, &self.vm_error, colorize)
             }
             FailureReason::Mismatch {
                 move_vm_return_values,
@@ -188,6 +404,12 @@ impl TestFailure {
                 )
             }
             FailureReason::Property(message) => message.clone(),
+            FailureReason::SetupFailed(message) => Self::report_error_with_location(
+                test_plan,
+                message.clone(),
+                &self.vm_error,
+                colorize,
+            ),
             FailureReason::Unknown(message) => {
                 format!(
                     "{} Location: {}\nVMError (if there is one): {}",
@@ -195,7 +417,8 @@ impl TestFailure {
                     TestFailure::report_error_with_location(
                         test_plan,
                         "".to_string(),
-                        &self.vm_error
+                        &self.vm_error,
+                        colorize,
                     ),
                     self.vm_error
                         .as_ref()
@@ -204,6 +427,23 @@ impl TestFailure {
                 )
             }
 
+            FailureReason::RandomTest {
+                seed,
+                iteration,
+                arguments,
+                reason,
+            } => {
+                format!(
+                    "{}\nFailed on iteration {} with random seed {}, re-run with `--seed {}` to \
+                     reproduce. Arguments (after shrinking): {}",
+                    self.render_failure_reason(reason, test_plan, colorize),
+                    iteration,
+                    seed,
+                    seed,
+                    arguments,
+                )
+            }
+
             #[cfg(feature = "evm-backend")]
             FailureReason::MoveToEVMError(diagnostics) => {
                 format!(
@@ -211,22 +451,80 @@ impl TestFailure {
                     diagnostics
                 )
             }
-        };
+        }
+    }
 
-        match &self.storage_state {
-            None => error_string,
-            Some(storage_state) => {
-                format!(
-                    "{}\n────── Storage state at point of failure ──────\n{}",
-                    error_string,
-                    if storage_state.is_empty() {
-                        "<empty>"
-                    } else {
-                        storage_state
-                    }
-                )
+    fn source_files(test_plan: &TestPlan) -> (SimpleFiles<Symbol, &str>, HashMap<FileHash, usize>) {
+        let mut files = SimpleFiles::new();
+        let mut file_mapping = HashMap::new();
+        for (fhash, (fname, source)) in &test_plan.files {
+            let id = files.add(*fname, source.as_str());
+            file_mapping.insert(*fhash, id);
+        }
+        (files, file_mapping)
+    }
+
+    /// Build the machine-readable report of this failure.
+    pub fn report(&self, test_plan: &TestPlan) -> TestFailureReport {
+        TestFailureReport {
+            kind: self.failure_reason.kind(),
+            message: self.failure_reason.message(),
+            details: self.render_error_impl(test_plan, false),
+            abort_code: self
+                .failure_reason
+                .abort_code()
+                .or_else(|| self.vm_error.as_ref().and_then(|err| err.sub_status())),
+            location: self.failure_location(test_plan),
+        }
+    }
+
+    /// Resolve the location at which the VM reported the failure, mapping it back to source
+    /// whenever the module is part of the test plan.
+    fn failure_location(&self, test_plan: &TestPlan) -> Option<FailureLocation> {
+        let vm_error = self.vm_error.as_ref()?;
+        let module_id = match vm_error.location() {
+            Location::Module(module_id) => module_id,
+            _ => return None,
+        };
+        let mut location = FailureLocation {
+            module: format_module_id(module_id),
+            function: None,
+            code_offset: None,
+            file: None,
+            line_start: None,
+            line_end: None,
+        };
+        let (fdef_idx, offset) = match vm_error.offsets().first() {
+            Some(offset) => *offset,
+            None => return Some(location),
+        };
+        location.code_offset = Some(offset);
+        let named_module = match test_plan.module_info.get(module_id) {
+            Some(named_module) => named_module,
+            None => return Some(location),
+        };
+        if (fdef_idx.0 as usize) < named_module.module.function_defs().len() {
+            let fn_handle_idx = named_module.module.function_def_at(fdef_idx).function;
+            let fn_id_idx = named_module.module.function_handle_at(fn_handle_idx).name;
+            location.function = Some(named_module.module.identifier_at(fn_id_idx).to_string());
+        }
+        let loc = named_module
+            .source_map
+            .get_function_source_map(fdef_idx)
+            .ok()
+            .and_then(|function_source_map| function_source_map.get_code_location(offset));
+        if let Some(loc) = loc {
+            let (files, file_mapping) = Self::source_files(test_plan);
+            location.file = test_plan
+                .files
+                .get(&loc.file_hash())
+                .map(|(fname, _)| fname.to_string());
+            if let Ok((start, end)) = Self::get_line_range(&loc, &files, &file_mapping) {
+                location.line_start = Some(start);
+                location.line_end = Some(end);
             }
         }
+        Some(location)
     }
 
     fn get_line_number(
@@ -243,6 +541,16 @@ impl TestFailure {
         files: &SimpleFiles<Symbol, &str>,
         file_mapping: &HashMap<FileHash, usize>,
     ) -> std::result::Result<String, codespan_reporting::files::Error> {
+        let (start_line_number, end_line_number) = Self::get_line_range(loc, files, file_mapping)?;
+        Ok(format_line_range(start_line_number, end_line_number))
+    }
+
+    /// The first and last line numbers of `loc`.
+    fn get_line_range(
+        loc: &Loc,
+        files: &SimpleFiles<Symbol, &str>,
+        file_mapping: &HashMap<FileHash, usize>,
+    ) -> std::result::Result<(usize, usize), codespan_reporting::files::Error> {
         let id = file_mapping
             .get(&loc.file_hash())
             .ok_or(codespan_reporting::files::Error::FileMissing)?;
@@ -250,11 +558,7 @@ impl TestFailure {
         let start_line_number = files.line_number(*id, start_line_index)?;
         let end_line_index = files.line_index(*id, loc.end() as usize)?;
         let end_line_number = files.line_number(*id, end_line_index)?;
-        if start_line_number == end_line_number {
-            Ok(start_line_number.to_string())
-        } else {
-            Ok(format!("{}-{}", start_line_number, end_line_number))
-        }
+        Ok((start_line_number, end_line_number))
     }
 
     fn report_exec_state(test_plan: &TestPlan, exec_state: &ExecutionState) -> String {
@@ -262,12 +566,7 @@ impl TestFailure {
         let mut buf = String::new();
         if !stack_trace.is_empty() {
             buf.push_str("stack trace\n");
-            let mut files = SimpleFiles::new();
-            let mut file_mapping = HashMap::new();
-            for (fhash, (fname, source)) in &test_plan.files {
-                let id = files.add(*fname, source.as_str());
-                file_mapping.insert(*fhash, id);
-            }
+            let (files, file_mapping) = Self::source_files(test_plan);
 
             for frame in stack_trace {
                 let module_id = match &frame.0 {
@@ -311,8 +610,9 @@ impl TestFailure {
         test_plan: &TestPlan,
         base_message: String,
         vm_error: &Option<VMError>,
+        colorize: bool,
     ) -> String {
-        let report_diagnostics = if control::SHOULD_COLORIZE.should_colorize() {
+        let report_diagnostics = if colorize {
             diagnostics::report_diagnostics_to_color_buffer
         } else {
             diagnostics::report_diagnostics_to_buffer
@@ -525,6 +825,148 @@ impl TestResults {
         writeln!(writer.lock().unwrap())
     }
 
+    /// Collect the outcome of every test that was run, ordered by module and test name.
+    pub fn to_report(&self) -> TestRunReport {
+        let mut tests = Vec::new();
+        for (module_id, test_results) in self.final_statistics.passed.iter() {
+            for test_result in test_results {
+                tests.push(Self::test_report(
+                    module_id,
+                    test_result,
+                    TestStatus::Passed,
+                    None,
+                ));
+            }
+        }
+        for (module_id, test_failures) in self.final_statistics.failed.iter() {
+            for test_failure in test_failures {
+                let status = if test_failure.failure_reason.is_timeout() {
+                    TestStatus::TimedOut
+                } else {
+                    TestStatus::Failed
+                };
+                tests.push(Self::test_report(
+                    module_id,
+                    &test_failure.test_run_info,
+                    status,
+                    Some(test_failure.report(&self.test_plan)),
+                ));
+            }
+        }
+        tests.sort_by(|t1, t2| (&t1.module, &t1.function).cmp(&(&t2.module, &t2.function)));
+
+        let passed = tests
+            .iter()
+            .filter(|test| test.status == TestStatus::Passed)
+            .count() as u64;
+        TestRunReport {
+            total: tests.len() as u64,
+            passed,
+            failed: tests.len() as u64 - passed,
+            duration_secs: tests.iter().map(|test| test.duration_secs).sum(),
+            tests,
+        }
+    }
+
+    fn test_report(
+        module_id: &ModuleId,
+        test_run_info: &TestRunInfo,
+        status: TestStatus,
+        failure: Option<TestFailureReport>,
+    ) -> TestReport {
+        let module = format_module_id(module_id);
+        TestReport {
+            name: format!("{}::{}", module, test_run_info.function_ident),
+            module,
+            function: test_run_info.function_ident.clone(),
+            status,
+            duration_secs: test_run_info.elapsed_time.as_secs_f64(),
+            instructions_executed: test_run_info.instructions_executed,
+            failure,
+        }
+    }
+
+    /// Write the results as a JSON document.
+    /// Returns `true` if all tests passed, `false` if there was a test failure/timeout
+    pub fn report_json<W: Write>(self, writer: &Mutex<W>) -> Result<bool> {
+        let report = self.to_report();
+        let mut writer = writer.lock().unwrap();
+        serde_json::to_writer_pretty(&mut *writer, &report)?;
+        writeln!(writer)?;
+        Ok(report.failed == 0)
+    }
+
+    /// Write the results as a JUnit XML document, with one `<testsuite>` per module.
+    /// Returns `true` if all tests passed, `false` if there was a test failure/timeout
+    pub fn report_junit<W: Write>(self, writer: &Mutex<W>) -> Result<bool> {
+        let report = self.to_report();
+        let mut writer = writer.lock().unwrap();
+        writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            writer,
+            r#"<testsuites name="move-unit-tests" tests="{}" failures="{}" time="{:.6}">"#,
+            report.total, report.failed, report.duration_secs
+        )?;
+        for (module, tests) in &report.tests.iter().group_by(|test| &test.module) {
+            let tests = tests.collect::<Vec<_>>();
+            writeln!(
+                writer,
+                r#"  <testsuite name="{}" tests="{}" failures="{}" time="{:.6}">"#,
+                xml_escape(module),
+                tests.len(),
+                tests
+                    .iter()
+                    .filter(|test| test.status != TestStatus::Passed)
+                    .count(),
+                tests.iter().map(|test| test.duration_secs).sum::<f64>(),
+            )?;
+            for test in tests {
+                writeln!(
+                    writer,
+                    r#"    <testcase name="{}" classname="{}" time="{:.6}">"#,
+                    xml_escape(&test.function),
+                    xml_escape(&test.module),
+                    test.duration_secs
+                )?;
+                writeln!(writer, "      <properties>")?;
+                let mut properties = vec![(
+                    "instructions_executed",
+                    test.instructions_executed.to_string(),
+                )];
+                if let Some(failure) = &test.failure {
+                    if let Some(abort_code) = failure.abort_code {
+                        properties.push(("abort_code", abort_code.to_string()));
+                    }
+                    if let Some(location) = &failure.location {
+                        properties.push(("abort_location", location.to_string()));
+                    }
+                }
+                for (name, value) in properties {
+                    writeln!(
+                        writer,
+                        r#"        <property name="{}" value="{}"/>"#,
+                        name,
+                        xml_escape(&value)
+                    )?;
+                }
+                writeln!(writer, "      </properties>")?;
+                if let Some(failure) = &test.failure {
+                    writeln!(
+                        writer,
+                        r#"      <failure type="{}" message="{}">{}</failure>"#,
+                        failure.kind,
+                        xml_escape(&failure.message),
+                        xml_escape(&failure.details)
+                    )?;
+                }
+                writeln!(writer, "    </testcase>")?;
+            }
+            writeln!(writer, "  </testsuite>")?;
+        }
+        writeln!(writer, "</testsuites>")?;
+        Ok(report.failed == 0)
+    }
+
     /// Returns `true` if all tests passed, `false` if there was a test failure/timeout
     pub fn summarize<W: Write>(self, writer: &Mutex<W>) -> Result<bool> {
         let num_failed_tests = self
@@ -578,3 +1020,72 @@ impl TestResults {
         Ok(num_failed_tests == 0)
     }
 }
+
+impl std::fmt::Display for FailureLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.module)?;
+        if let Some(function) = &self.function {
+            write!(f, "::{}", function)?;
+        }
+        if let Some(code_offset) = self.code_offset {
+            write!(f, " (code offset {})", code_offset)?;
+        }
+        if let (Some(file), Some(start), Some(end)) = (&self.file, self.line_start, self.line_end) {
+            write!(f, " at {}:{}", file, format_line_range(start, end))?;
+        }
+        Ok(())
+    }
+}
+
+/// Formats a line range as "5" if it spans a single line, or as "12-14" otherwise.
+fn format_line_range(start: usize, end: usize) -> String {
+    if start == end {
+        start.to_string()
+    } else {
+        format!("{}-{}", start, end)
+    }
+}
+
+/// Describe a VM error, e.g. "abort code 1 in module 0x1::M" or "an arithmetic error".
+fn describe_error(
+    status: StatusCode,
+    sub_status: Option<u64>,
+    location: Option<&ModuleId>,
+) -> String {
+    let mut description = match (status, sub_status) {
+        (StatusCode::ABORTED, Some(code)) => format!("abort code {}", code),
+        (StatusCode::ARITHMETIC_ERROR, _) => "an arithmetic error".to_string(),
+        (StatusCode::OUT_OF_GAS, _) => "out of gas".to_string(),
+        (StatusCode::VECTOR_OPERATION_ERROR, None) => "a vector error".to_string(),
+        (StatusCode::VECTOR_OPERATION_ERROR, Some(sub_status)) => {
+            format!("a vector error with minor status {}", sub_status)
+        }
+        (status, None) => format!("status {:?}", status),
+        (status, Some(sub_status)) => {
+            format!("status {:?} with minor status {}", status, sub_status)
+        }
+    };
+    if let Some(module_id) = location {
+        description.push_str(&format!(" in module {}", format_module_id(module_id)));
+    }
+    description
+}
+
+/// Escape `s` for use in XML text and attribute values, dropping characters that cannot appear in
+/// an XML 1.0 document.
+fn xml_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' | '\r' | '\t' => escaped.push(c),
+            c if c.is_control() => (),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
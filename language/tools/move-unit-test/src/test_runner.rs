@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    extensions, format_module_id,
+    extensions, fixtures, format_module_id, random_test,
     test_reporter::{FailureReason, TestFailure, TestResults, TestRunInfo, TestStatistics},
 };
 use anyhow::Result;
@@ -13,17 +13,22 @@ use codespan_reporting::{
 };
 use colored::*;
 
-use move_binary_format::{errors::VMResult, file_format::CompiledModule};
+use move_binary_format::{
+    errors::{Location, VMError, VMResult},
+    file_format::CompiledModule,
+};
 use move_bytecode_utils::Modules;
 use move_compiler::{
     shared::{Flags, NumericalAddress, PackagePaths},
-    unit_test::{ExpectedFailure, ModuleTestPlan, TestCase, TestPlan},
+    unit_test::{
+        ExpectedFailure, ExpectedMoveError, ModuleTestPlan, TestArgument, TestCase, TestPlan,
+    },
 };
 use move_core_types::{
     account_address::AccountAddress,
     effects::{ChangeSet, Op},
     identifier::IdentStr,
-    value::serialize_values,
+    value::{serialize_values, MoveValue},
     vm_status::StatusCode,
 };
 use move_model::{
@@ -41,8 +46,17 @@ use move_vm_test_utils::{
     gas_schedule::{zero_cost_schedule, CostTable, Gas, GasCost, GasStatus},
     InMemoryStorage,
 };
+use rand::{rngs::StdRng, SeedableRng};
 use rayon::prelude::*;
-use std::{collections::BTreeMap, io::Write, marker::Send, sync::Mutex, time::Instant};
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    io::Write,
+    marker::Send,
+    path::Path,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
 
 use move_vm_runtime::native_extensions::NativeContextExtensions;
 #[cfg(feature = "evm-backend")]
@@ -52,7 +66,6 @@ use {
     move_to_yul,
     primitive_types::{H160, U256},
     std::convert::TryInto,
-    std::time::Duration,
 };
 
 /// Test state common to all tests
@@ -68,6 +81,9 @@ pub struct SharedTestingConfig {
     check_stackless_vm: bool,
     verbose: bool,
     record_writeset: bool,
+    random_test_iterations: u64,
+    seed: u64,
+    has_fixtures: bool,
 
     #[cfg(feature = "evm-backend")]
     evm: bool,
@@ -79,6 +95,15 @@ pub struct TestRunner {
     tests: TestPlan,
 }
 
+/// The hashes identifying the sessions that run tests and setup functions to native extensions.
+/// They must differ so that, e.g., tables created by a test do not collide with those created
+/// during setup.
+const TEST_SESSION_HASH: [u8; 32] = [0; 32];
+const SETUP_SESSION_HASH: [u8; 32] = [1; 32];
+
+/// The maximum number of runs spent on shrinking the arguments of a failing random test.
+const MAX_SHRINK_RUNS: usize = 1000;
+
 /// A gas schedule where every instruction has a cost of "1". This is used to bound execution of a
 /// test to a certain number of ticks.
 fn unit_cost_table() -> CostTable {
@@ -89,6 +114,18 @@ fn unit_cost_table() -> CostTable {
     cost_schedule
 }
 
+/// Whether `err` is the error that a test annotated with `expected` is expected to fail with. The
+/// sub-status and location are only checked if they are part of the expectation.
+fn expected_error_matches(expected: &ExpectedMoveError, err: &VMError) -> bool {
+    expected.status == err.major_status()
+        && expected
+            .sub_status
+            .map_or(true, |sub_status| err.sub_status() == Some(sub_status))
+        && expected.location.as_ref().map_or(true, |module_id| {
+            matches!(err.location(), Location::Module(location) if location == module_id)
+        })
+}
+
 /// Setup storage state with the set of modules that will be needed for all tests
 fn setup_test_storage<'a>(
     modules: impl Iterator<Item = &'a CompiledModule>,
@@ -150,6 +187,11 @@ impl TestRunner {
         native_function_table: Option<NativeFunctionTable>,
         named_address_values: BTreeMap<String, NumericalAddress>,
         record_writeset: bool,
+        random_test_iterations: u64,
+        // The seed for generating arguments of random tests, picked at random if not set.
+        seed: Option<u64>,
+        // A file of resources to publish before running any test
+        fixtures: Option<&Path>,
         #[cfg(feature = "evm-backend")] evm: bool,
     ) -> Result<Self> {
         let source_files = tests
@@ -158,7 +200,14 @@ impl TestRunner {
             .map(|(filepath, _)| filepath.to_string())
             .collect();
         let modules = tests.module_info.values().map(|info| &info.module);
-        let starting_storage_state = setup_test_storage(modules)?;
+        #[cfg(feature = "evm-backend")]
+        if evm && fixtures.is_some() {
+            anyhow::bail!("Fixtures are not supported by the EVM backend");
+        }
+        let mut starting_storage_state = setup_test_storage(modules)?;
+        if let Some(fixtures) = fixtures {
+            fixtures::load_fixtures(&mut starting_storage_state, fixtures)?;
+        }
         let native_function_table = native_function_table.unwrap_or_else(|| {
             move_stdlib::natives::all_natives(
                 AccountAddress::from_hex_literal("0x1").unwrap(),
@@ -183,6 +232,9 @@ impl TestRunner {
                 verbose,
                 named_address_values,
                 record_writeset,
+                random_test_iterations,
+                seed: seed.unwrap_or_else(rand::random),
+                has_fixtures: fixtures.is_some(),
                 #[cfg(feature = "evm-backend")]
                 evm,
             },
@@ -269,21 +321,22 @@ impl<'a, 'b, W: Write> TestOutput<'a, 'b, W> {
 }
 
 impl SharedTestingConfig {
-    fn execute_via_move_vm(
+    fn execute_via_move_vm<'a>(
         &self,
+        storage: &'a InMemoryStorage,
         test_plan: &ModuleTestPlan,
         function_name: &str,
-        test_info: &TestCase,
+        arguments: &[MoveValue],
+        session_hash: [u8; 32],
     ) -> (
         VMResult<ChangeSet>,
-        VMResult<NativeContextExtensions>,
+        VMResult<NativeContextExtensions<'a>>,
         VMResult<Vec<Vec<u8>>>,
         TestRunInfo,
     ) {
         let move_vm = MoveVM::new(self.native_function_table.clone()).unwrap();
-        let extensions = extensions::new_extensions();
-        let mut session =
-            move_vm.new_session_with_extensions(&self.starting_storage_state, extensions);
+        let extensions = extensions::new_extensions(storage, session_hash);
+        let mut session = move_vm.new_session_with_extensions(storage, extensions);
         let mut gas_meter = GasStatus::new(&self.cost_table, Gas::new(self.execution_bound));
         // TODO: collect VM logs if the verbose flag (i.e, `self.verbose`) is set
 
@@ -292,7 +345,7 @@ impl SharedTestingConfig {
             &test_plan.module_id,
             IdentStr::new(function_name).unwrap(),
             vec![], // no ty args, at least for now
-            serialize_values(test_info.arguments.iter()),
+            serialize_values(arguments),
             &mut gas_meter,
         );
         let mut return_result = serialized_return_values_result.map(|res| {
@@ -412,9 +465,50 @@ impl SharedTestingConfig {
 
         let mut stats = TestStatistics::new();
 
+        // The setup function of the module is run once, and all tests start from the state it
+        // leaves behind
+        let storage = match self.setup_storage_state(test_plan) {
+            Ok(storage) => storage,
+            Err(setup_failure) => {
+                for function_name in test_plan.tests.keys() {
+                    let mut test_failure = setup_failure.clone();
+                    test_failure.test_run_info.function_ident = function_name.to_string();
+                    Self::record_result(
+                        test_plan,
+                        function_name,
+                        Err(test_failure),
+                        output,
+                        &mut stats,
+                    );
+                }
+                return stats;
+            }
+        };
+        // The stackless VM always starts from a state without resources
+        let check_stackless_vm =
+            self.check_stackless_vm && !self.has_fixtures && test_plan.before_each.is_none();
+
         for (function_name, test_info) in &test_plan.tests {
-            let (cs_result, ext_result, exec_result, test_run_info) =
-                self.execute_via_move_vm(test_plan, function_name, test_info);
+            // Random tests are only run on the Move VM, once for each set of generated arguments
+            if let Some(random_arguments) = &test_info.random_arguments {
+                let result = self.exec_random_test(
+                    &storage,
+                    test_plan,
+                    function_name,
+                    test_info,
+                    random_arguments,
+                );
+                Self::record_result(test_plan, function_name, result, output, &mut stats);
+                continue;
+            }
+
+            let (cs_result, ext_result, exec_result, test_run_info) = self.execute_via_move_vm(
+                &storage,
+                test_plan,
+                function_name,
+                &test_info.arguments,
+                TEST_SESSION_HASH,
+            );
 
             if self.record_writeset {
                 stats.test_output(
@@ -424,7 +518,7 @@ impl SharedTestingConfig {
                 );
             }
 
-            if self.check_stackless_vm {
+            if check_stackless_vm {
                 let (stackless_vm_change_set, stackless_vm_result, _, prop_check_result) = self
                     .execute_via_stackless_vm(
                         stackless_model.as_ref().unwrap(),
@@ -433,8 +527,7 @@ impl SharedTestingConfig {
                         test_info,
                     );
                 let move_vm_result = adapt_move_vm_result(exec_result.clone());
-                let move_vm_change_set =
-                    adapt_move_vm_change_set(cs_result.clone(), &self.starting_storage_state);
+                let move_vm_change_set = adapt_move_vm_change_set(cs_result.clone(), &*storage);
                 if stackless_vm_result != move_vm_result
                     || stackless_vm_change_set != move_vm_change_set
                 {
@@ -470,124 +563,312 @@ impl SharedTestingConfig {
                 }
             }
 
-            let save_session_state = || {
-                if self.save_storage_state_on_failure {
-                    cs_result.ok().and_then(|changeset| {
-                        ext_result.ok().and_then(|extensions| {
-                            print_resources_and_extensions(
-                                &changeset,
-                                extensions,
-                                &self.starting_storage_state,
-                            )
-                            .ok()
-                        })
-                    })
+            let result = self.check_move_vm_result(test_info, exec_result, test_run_info, || {
+                self.storage_state(&storage, cs_result, ext_result)
+            });
+            Self::record_result(test_plan, function_name, result, output, &mut stats);
+        }
+
+        stats
+    }
+
+    /// Compute the storage state that the tests of a module start from, i.e., the starting storage
+    /// state updated with the effects of the module's `#[before_each]` function, if any.
+    fn setup_storage_state(
+        &self,
+        test_plan: &ModuleTestPlan,
+    ) -> std::result::Result<Cow<'_, InMemoryStorage>, TestFailure> {
+        let setup = match &test_plan.before_each {
+            None => return Ok(Cow::Borrowed(&self.starting_storage_state)),
+            Some(setup) => setup,
+        };
+        let (cs_result, ext_result, exec_result, test_run_info) = self.execute_via_move_vm(
+            &self.starting_storage_state,
+            test_plan,
+            &setup.function_name,
+            &setup.arguments,
+            SETUP_SESSION_HASH,
+        );
+        let setup_failure = |message: String, vm_error| {
+            Err(TestFailure::new(
+                FailureReason::setup_failed(format!(
+                    "Setup function {}::{} {}",
+                    format_module_id(&test_plan.module_id),
+                    setup.function_name,
+                    message
+                )),
+                test_run_info.clone(),
+                vm_error,
+                None,
+            ))
+        };
+        if let Err(err) = exec_result {
+            let message = match (err.major_status(), err.sub_status()) {
+                (StatusCode::OUT_OF_GAS, _) => "timed out".to_string(),
+                (StatusCode::ABORTED, Some(code)) => format!("aborted with {}", code),
+                (status, _) => format!("failed with {:?}", status),
+            };
+            return setup_failure(message, Some(err));
+        }
+        let mut storage = self.starting_storage_state.clone();
+        let applied = match (cs_result, ext_result) {
+            (Ok(change_set), Ok(extensions)) => {
+                extensions::apply_change_sets(&mut storage, change_set, extensions)
+                    .map_err(|err| err.to_string())
+            }
+            (Err(err), _) | (_, Err(err)) => Err(format!("{:?}", err.major_status())),
+        };
+        match applied {
+            Ok(()) => Ok(Cow::Owned(storage)),
+            Err(err) => setup_failure(format!("produced invalid changes: {}", err), None),
+        }
+    }
+
+    fn record_result(
+        test_plan: &ModuleTestPlan,
+        function_name: &str,
+        result: std::result::Result<TestRunInfo, TestFailure>,
+        output: &TestOutput<impl Write>,
+        stats: &mut TestStatistics,
+    ) {
+        match result {
+            Ok(test_run_info) => {
+                output.pass(function_name);
+                stats.test_success(test_run_info, test_plan);
+            }
+            Err(test_failure) => {
+                if test_failure.failure_reason.is_timeout() {
+                    output.timeout(function_name);
                 } else {
-                    None
+                    output.fail(function_name);
                 }
-            };
-            match exec_result {
-                Err(err) => match (test_info.expected_failure.as_ref(), err.sub_status()) {
-                    // Ran out of ticks, report a test timeout and log a test failure
-                    _ if err.major_status() == StatusCode::OUT_OF_GAS => {
-                        output.timeout(function_name);
-                        stats.test_failure(
-                            TestFailure::new(
-                                FailureReason::timeout(),
-                                test_run_info,
-                                Some(err),
-                                save_session_state(),
-                            ),
-                            test_plan,
-                        )
-                    }
-                    // Expected the test to not abort, but it aborted with `code`
-                    (None, Some(code)) => {
-                        output.fail(function_name);
-                        stats.test_failure(
-                            TestFailure::new(
-                                FailureReason::aborted(code),
-                                test_run_info,
-                                Some(err),
-                                save_session_state(),
-                            ),
-                            test_plan,
-                        )
-                    }
-                    // Expected the test the abort with a specific `code`, and it did abort with
-                    // that abort code
-                    (Some(ExpectedFailure::ExpectedWithCode(code)), Some(other_code))
-                        if matches!(
-                            err.major_status(),
-                            StatusCode::ABORTED | StatusCode::VECTOR_OPERATION_ERROR
-                        ) && *code == other_code =>
-                    {
-                        output.pass(function_name);
-                        stats.test_success(test_run_info, test_plan);
-                    }
-                    // Expected the test to abort with a specific `code` but it aborted with a
-                    // different `other_code`
-                    (Some(ExpectedFailure::ExpectedWithCode(code)), Some(other_code)) => {
-                        output.fail(function_name);
-                        stats.test_failure(
-                            TestFailure::new(
-                                FailureReason::wrong_abort(*code, other_code),
-                                test_run_info,
-                                Some(err),
-                                save_session_state(),
-                            ),
-                            test_plan,
-                        )
-                    }
-                    // Expected the test to abort and it aborted, but we don't need to check the code
-                    (Some(ExpectedFailure::Expected), Some(_)) => {
-                        output.pass(function_name);
-                        stats.test_success(test_run_info, test_plan);
-                    }
-                    // Expected the test to abort and it aborted with internal error
-                    (Some(ExpectedFailure::Expected), None)
-                        if err.major_status() != StatusCode::EXECUTED =>
+                stats.test_failure(test_failure, test_plan);
+            }
+        }
+    }
+
+    /// Render the storage state at the end of a test, if requested by the configuration.
+    fn storage_state(
+        &self,
+        storage: &InMemoryStorage,
+        cs_result: VMResult<ChangeSet>,
+        ext_result: VMResult<NativeContextExtensions>,
+    ) -> Option<String> {
+        if self.save_storage_state_on_failure {
+            cs_result.ok().and_then(|changeset| {
+                ext_result.ok().and_then(|extensions| {
+                    print_resources_and_extensions(&changeset, extensions, storage).ok()
+                })
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Check the result of executing a test on the Move VM against the expectations of the test.
+    fn check_move_vm_result(
+        &self,
+        test_info: &TestCase,
+        exec_result: VMResult<Vec<Vec<u8>>>,
+        test_run_info: TestRunInfo,
+        save_session_state: impl FnOnce() -> Option<String>,
+    ) -> std::result::Result<TestRunInfo, TestFailure> {
+        match exec_result {
+            Err(err) => match (test_info.expected_failure.as_ref(), err.sub_status()) {
+                // Expected the test to fail with a specific error, and it did
+                (Some(ExpectedFailure::ExpectedWithError(expected)), _)
+                    if expected_error_matches(expected, &err) =>
+                {
+                    Ok(test_run_info)
+                }
+                // Ran out of ticks, report a test timeout and log a test failure
+                _ if err.major_status() == StatusCode::OUT_OF_GAS => Err(TestFailure::new(
+                    FailureReason::timeout(),
+                    test_run_info,
+                    Some(err),
+                    save_session_state(),
+                )),
+                // Expected the test to fail with a specific error, but it failed differently
+                (Some(ExpectedFailure::ExpectedWithError(expected)), _) => Err(TestFailure::new(
+                    FailureReason::wrong_error(expected, &err),
+                    test_run_info,
+                    Some(err),
+                    save_session_state(),
+                )),
+                // Expected the test to not abort, but it aborted with `code`
+                (None, Some(code)) => Err(TestFailure::new(
+                    FailureReason::aborted(code),
+                    test_run_info,
+                    Some(err),
+                    save_session_state(),
+                )),
+                // Expected the test the abort with a specific `code`, and it did abort with
+                // that abort code
+                (Some(ExpectedFailure::ExpectedWithCode(code)), Some(other_code))
+                    if matches!(
+                        err.major_status(),
+                        StatusCode::ABORTED | StatusCode::VECTOR_OPERATION_ERROR
+                    ) && *code == other_code =>
+                {
+                    Ok(test_run_info)
+                }
+                // Expected the test to abort with a specific `code` but it aborted with a
+                // different `other_code`
+                (Some(ExpectedFailure::ExpectedWithCode(code)), Some(other_code)) => {
+                    Err(TestFailure::new(
+                        FailureReason::wrong_abort(*code, other_code),
+                        test_run_info,
+                        Some(err),
+                        save_session_state(),
+                    ))
+                }
+                // Expected the test to abort and it aborted, but we don't need to check the code
+                (Some(ExpectedFailure::Expected), Some(_)) => Ok(test_run_info),
+                // Expected the test to abort and it aborted with internal error
+                (Some(ExpectedFailure::Expected), None)
+                    if err.major_status() != StatusCode::EXECUTED =>
+                {
+                    Ok(test_run_info)
+                }
+                // Unexpected return status from the VM, signal that we hit an unknown error.
+                (_, None) => Err(TestFailure::new(
+                    FailureReason::unknown(),
+                    test_run_info,
+                    Some(err),
+                    save_session_state(),
+                )),
+            },
+            Ok(_) => {
+                // Expected the test to fail, but it executed
+                if test_info.expected_failure.is_some() {
+                    Err(TestFailure::new(
+                        FailureReason::no_abort(),
+                        test_run_info,
+                        None,
+                        save_session_state(),
+                    ))
+                } else {
+                    // Expected the test to execute fully and it did
+                    Ok(test_run_info)
+                }
+            }
+        }
+    }
+
+    fn exec_with_arguments(
+        &self,
+        storage: &InMemoryStorage,
+        test_plan: &ModuleTestPlan,
+        function_name: &str,
+        test_info: &TestCase,
+        arguments: &[MoveValue],
+    ) -> std::result::Result<TestRunInfo, TestFailure> {
+        let (cs_result, ext_result, exec_result, test_run_info) = self.execute_via_move_vm(
+            storage,
+            test_plan,
+            function_name,
+            arguments,
+            TEST_SESSION_HASH,
+        );
+        self.check_move_vm_result(test_info, exec_result, test_run_info, || {
+            self.storage_state(storage, cs_result, ext_result)
+        })
+    }
+
+    /// Run a random test with freshly generated arguments until it fails or the configured number
+    /// of iterations is reached. The arguments of a failing run are shrunk before reporting them.
+    fn exec_random_test(
+        &self,
+        storage: &InMemoryStorage,
+        test_plan: &ModuleTestPlan,
+        function_name: &str,
+        test_info: &TestCase,
+        random_arguments: &[(String, TestArgument)],
+    ) -> std::result::Result<TestRunInfo, TestFailure> {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut elapsed_time = Duration::ZERO;
+        let mut instructions_executed = 0;
+        for iteration in 0..self.random_test_iterations {
+            let arguments = random_test::generate_arguments(&mut rng, random_arguments);
+            match self.exec_with_arguments(storage, test_plan, function_name, test_info, &arguments)
+            {
+                Ok(test_run_info) => {
+                    elapsed_time += test_run_info.elapsed_time;
+                    instructions_executed += test_run_info.instructions_executed;
+                }
+                Err(test_failure) => {
+                    let (arguments, test_failure) = self.shrink_failing_arguments(
+                        storage,
+                        test_plan,
+                        function_name,
+                        test_info,
+                        random_arguments,
+                        arguments,
+                        test_failure,
+                    );
+                    return Err(TestFailure::new(
+                        FailureReason::random_test(
+                            self.seed,
+                            iteration,
+                            random_test::format_arguments(random_arguments, &arguments),
+                            test_failure.failure_reason,
+                        ),
+                        test_failure.test_run_info,
+                        test_failure.vm_error,
+                        test_failure.storage_state,
+                    ));
+                }
+            }
+        }
+        Ok(TestRunInfo::new(
+            function_name.to_string(),
+            elapsed_time,
+            instructions_executed,
+        ))
+    }
+
+    /// Greedily simplify the arguments of a failing random test for as long as the test keeps
+    /// failing for the same reason.
+    fn shrink_failing_arguments(
+        &self,
+        storage: &InMemoryStorage,
+        test_plan: &ModuleTestPlan,
+        function_name: &str,
+        test_info: &TestCase,
+        random_arguments: &[(String, TestArgument)],
+        mut arguments: Vec<MoveValue>,
+        mut test_failure: TestFailure,
+    ) -> (Vec<MoveValue>, TestFailure) {
+        let mut remaining_runs = MAX_SHRINK_RUNS;
+        'shrink: loop {
+            for candidate in random_test::shrink_arguments(random_arguments, &arguments) {
+                if remaining_runs == 0 {
+                    break 'shrink;
+                }
+                remaining_runs -= 1;
+                if let Err(candidate_failure) = self.exec_with_arguments(
+                    storage,
+                    test_plan,
+                    function_name,
+                    test_info,
+                    &candidate,
+                ) {
+                    let (candidate_reason, reason) = (
+                        &candidate_failure.failure_reason,
+                        &test_failure.failure_reason,
+                    );
+                    if std::mem::discriminant(candidate_reason) == std::mem::discriminant(reason)
+                        && candidate_reason.abort_code() == reason.abort_code()
                     {
-                        output.pass(function_name);
-                        stats.test_success(test_run_info, test_plan);
-                    }
-                    // Unexpected return status from the VM, signal that we hit an unknown error.
-                    (_, None) => {
-                        output.fail(function_name);
-                        stats.test_failure(
-                            TestFailure::new(
-                                FailureReason::unknown(),
-                                test_run_info,
-                                Some(err),
-                                save_session_state(),
-                            ),
-                            test_plan,
-                        )
-                    }
-                },
-                Ok(_) => {
-                    // Expected the test to fail, but it executed
-                    if test_info.expected_failure.is_some() {
-                        output.fail(function_name);
-                        stats.test_failure(
-                            TestFailure::new(
-                                FailureReason::no_abort(),
-                                test_run_info,
-                                None,
-                                save_session_state(),
-                            ),
-                            test_plan,
-                        )
-                    } else {
-                        // Expected the test to execute fully and it did
-                        output.pass(function_name);
-                        stats.test_success(test_run_info, test_plan);
+                        arguments = candidate;
+                        test_failure = candidate_failure;
+                        continue 'shrink;
                     }
                 }
             }
+            break;
         }
-
-        stats
+        (arguments, test_failure)
     }
 
     #[cfg(feature = "evm-backend")]
@@ -654,6 +935,32 @@ impl SharedTestingConfig {
 
         let gen_options = move_to_yul::options::Options::default();
         for (function_name, test_info) in &test_plan.tests {
+            let unsupported = if test_info.random_arguments.is_some() {
+                Some("Random tests are not supported by the EVM backend")
+            } else if test_plan.before_each.is_some() {
+                Some("Setup functions are not supported by the EVM backend")
+            } else if matches!(
+                test_info.expected_failure,
+                Some(ExpectedFailure::ExpectedWithError(_))
+            ) {
+                Some("Expected failures with a specific error are not supported by the EVM backend")
+            } else {
+                None
+            };
+            if let Some(unsupported) = unsupported {
+                output.fail(function_name);
+                stats.test_failure(
+                    TestFailure::new(
+                        FailureReason::move_to_evm_error(unsupported.to_string()),
+                        TestRunInfo::new(function_name.to_string(), Duration::ZERO, 0),
+                        None,
+                        None,
+                    ),
+                    test_plan,
+                );
+                continue;
+            }
+
             let yul_code = match move_to_yul::generator::Generator::run_for_unit_test(
                 &gen_options,
                 &model,
@@ -5,7 +5,7 @@
 use move_command_line_common::testing::{
     add_update_baseline_fix, format_diff, read_env_update_baseline, EXP_EXT,
 };
-use move_unit_test::{self, UnitTestingConfig};
+use move_unit_test::{self, TestReportFormat, UnitTestingConfig};
 use regex::RegexBuilder;
 use std::{
     fs,
@@ -16,6 +16,8 @@ use std::{
 // tests flaky.
 const TEST_MODIFIER_STRS: &[&str] = &[
     "storage",
+    "json",
+    "junit",
     #[cfg(feature = "evm-backend")]
     "evm",
 ];
@@ -24,6 +26,8 @@ pub fn modify(mut base_config: UnitTestingConfig, modifier_str: &str) -> Option<
     // Add future test modifiers here
     match modifier_str {
         "storage" => base_config.report_storage_on_error = true,
+        "json" => base_config.report_format = TestReportFormat::Json,
+        "junit" => base_config.report_format = TestReportFormat::Junit,
         #[cfg(feature = "evm-backend")]
         "evm" => base_config.evm = true,
         _ => return None,
@@ -89,6 +93,12 @@ fn run_test_impl(path: &Path) -> anyhow::Result<()> {
             .into_iter()
             .collect(),
         report_writeset: true,
+        // Random tests report their seed, so pin it to keep the output stable.
+        seed: Some(0),
+        // Resources to publish before running the tests in `<name>.move`, if any
+        fixtures: Some(path.with_extension("fixtures.json"))
+            .filter(|fixtures| fixtures.exists())
+            .map(|fixtures| fixtures.to_str().unwrap().to_owned()),
 
         ..UnitTestingConfig::default_with_bound(None)
     };
@@ -97,10 +107,15 @@ fn run_test_impl(path: &Path) -> anyhow::Result<()> {
         .multi_line(true)
         .build()
         .unwrap();
+    // Test durations vary between runs, so they are elided from machine-readable reports.
+    let duration_regex = RegexBuilder::new(r#"("duration_secs": |time=")[0-9.]+"#)
+        .build()
+        .unwrap();
 
     for ((buffer, _), exp_path) in run_test_with_modifiers(unit_test_config, path)? {
         let base_output = String::from_utf8(buffer)?;
         let cleaned_output = regex.replacen(&base_output, 0, r"$1$2");
+        let cleaned_output = duration_regex.replacen(&cleaned_output, 0, "${1}0");
         if update_baseline {
             fs::write(&exp_path, &*cleaned_output)?
         }
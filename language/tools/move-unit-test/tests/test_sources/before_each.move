@@ -0,0 +1,66 @@
+address 0x1 {
+module Counter {
+    struct Counter has key { value: u64 }
+
+    #[before_each(admin = @0x1, user = @0x2)]
+    fun setup(admin: signer, user: signer) {
+        move_to(&admin, Counter { value: 10 });
+        move_to(&user, Counter { value: 20 });
+    }
+
+    fun increment(addr: address) acquires Counter {
+        let counter = borrow_global_mut<Counter>(addr);
+        counter.value = counter.value + 1;
+    }
+
+    #[test]
+    fun starts_from_setup_state() acquires Counter {
+        assert!(borrow_global<Counter>(@0x1).value == 10, 0);
+        assert!(borrow_global<Counter>(@0x2).value == 20, 1);
+    }
+
+    #[test]
+    fun changes_are_not_shared_a() acquires Counter {
+        increment(@0x1);
+        assert!(borrow_global<Counter>(@0x1).value == 11, 0);
+    }
+
+    #[test]
+    fun changes_are_not_shared_b() acquires Counter {
+        increment(@0x1);
+        assert!(borrow_global<Counter>(@0x1).value == 11, 0);
+    }
+
+    #[test(new = @0x3)]
+    fun setup_and_test_signers(new: signer) acquires Counter {
+        move_to(&new, Counter { value: 30 });
+        increment(@0x3);
+        assert!(borrow_global<Counter>(@0x3).value == 31, 0);
+    }
+
+    #[test(admin = @0x1)]
+    #[expected_failure]
+    fun cannot_publish_twice(admin: signer) {
+        move_to(&admin, Counter { value: 0 });
+    }
+
+    #[test]
+    fun sees_setup_state_on_failure() acquires Counter {
+        increment(@0x2);
+        abort 7
+    }
+}
+
+module FailingSetup {
+    #[before_each]
+    fun setup() {
+        abort 42
+    }
+
+    #[test]
+    fun never_runs_a() { }
+
+    #[test]
+    fun never_runs_b() { }
+}
+}
@@ -0,0 +1,89 @@
+module 0x1::A {
+    const E_FAILED: u64 = 1;
+
+    public fun fail() {
+        abort E_FAILED
+    }
+}
+
+module 0x1::M {
+    #[test_only]
+    use 0x1::A;
+    #[test_only]
+    use std::vector;
+
+    const E_FAILED: u64 = 1;
+    const E_OTHER: u64 = 2;
+
+    #[test]
+    #[expected_failure(abort_code = E_FAILED, location = Self)]
+    fun abort_with_constant() {
+        abort E_FAILED
+    }
+
+    #[test]
+    #[expected_failure(abort_code = A::E_FAILED, location = A)]
+    fun abort_in_other_module() {
+        A::fail()
+    }
+
+    #[test]
+    #[expected_failure(arithmetic_error, location = Self)]
+    fun arithmetic_error() {
+        1 / 0;
+    }
+
+    #[test]
+    #[expected_failure(vector_error, minor_status = 1, location = Self)]
+    fun vector_out_of_bounds() {
+        let v = vector::empty<u64>();
+        vector::borrow(&v, 1);
+    }
+
+    #[test]
+    #[expected_failure(out_of_gas, location = Self)]
+    fun loops_forever() {
+        loop {}
+    }
+
+    #[test]
+    #[expected_failure(major_status = 4016, location = Self)]
+    fun abort_with_major_status() {
+        abort 0
+    }
+
+    #[test]
+    #[expected_failure(abort_code = E_FAILED, location = Self)]
+    fun fail_abort_in_wrong_module() {
+        A::fail()
+    }
+
+    #[test]
+    #[expected_failure(abort_code = E_OTHER, location = Self)]
+    fun fail_wrong_abort_code() {
+        abort E_FAILED
+    }
+
+    #[test]
+    #[expected_failure(arithmetic_error, location = Self)]
+    fun fail_abort_instead_of_arithmetic_error() {
+        abort E_FAILED
+    }
+
+    #[test]
+    #[expected_failure(vector_error, minor_status = 1, location = Self)]
+    fun fail_pop_empty_vector() {
+        let v = vector::empty<u64>();
+        vector::pop_back(&mut v);
+    }
+
+    #[test]
+    #[expected_failure(arithmetic_error, location = Self)]
+    fun fail_out_of_gas() {
+        loop {}
+    }
+
+    #[test]
+    #[expected_failure(out_of_gas, location = Self)]
+    fun fail_no_error() { }
+}
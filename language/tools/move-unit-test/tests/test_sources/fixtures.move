@@ -0,0 +1,40 @@
+address 0x1 {
+module Bank {
+    use std::string::{Self, String};
+
+    struct Account has key {
+        owner: String,
+        balance: u128,
+        history: vector<u64>,
+        frozen: bool,
+        backup: address,
+    }
+
+    struct Registry has key { accounts: vector<address> }
+
+    #[test]
+    fun fixtures_are_published() acquires Account, Registry {
+        let accounts = &borrow_global<Registry>(@0x1).accounts;
+        assert!(std::vector::length(accounts) == 2, 0);
+        let account = borrow_global<Account>(@0x42);
+        assert!(account.owner == string::utf8(b"alice"), 1);
+        assert!(account.balance == 1000000000000000000000, 2);
+        assert!(account.history == vector[1, 2, 3], 3);
+        assert!(!account.frozen, 4);
+        assert!(account.backup == @0x43, 5);
+    }
+
+    #[test]
+    fun fixtures_can_be_modified() acquires Account {
+        let account = borrow_global_mut<Account>(@0x43);
+        account.balance = account.balance + 1;
+        assert!(account.frozen, 0);
+        abort 0
+    }
+
+    #[test]
+    fun modifications_are_not_shared() acquires Account {
+        assert!(borrow_global<Account>(@0x43).balance == 5, 0);
+    }
+}
+}
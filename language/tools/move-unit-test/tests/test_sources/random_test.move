@@ -0,0 +1,48 @@
+module 0x1::M {
+    struct Range has drop { lo: u64, hi: u64 }
+
+    fun sum(v: &vector<u8>): u64 {
+        let i = 0;
+        let s = 0;
+        while (i < std::vector::length(v)) {
+            s = s + (*std::vector::borrow(v, i) as u64);
+            i = i + 1;
+        };
+        s
+    }
+
+    #[random_test]
+    fun addition_commutes(a: u32, b: u32) {
+        let (a, b) = ((a as u64), (b as u64));
+        assert!(a + b == b + a, 0);
+    }
+
+    #[random_test]
+    fun overflows_are_found(a: u64, b: u64) {
+        // fails for any pair of arguments whose sum does not fit in a u64
+        let _ = a + b;
+    }
+
+    #[random_test]
+    fun small_elements(v: vector<u8>) {
+        // shrinks to a single element just large enough to trigger the abort
+        assert!(sum(&v) < 100, 1);
+    }
+
+    #[random_test]
+    fun ranges_are_ordered(r: Range) {
+        assert!(r.lo <= r.hi, 2);
+    }
+
+    #[random_test(s = @0x1)]
+    fun with_signer(s: signer, x: u8) {
+        assert!(std::signer::address_of(&s) == @0x1, 3);
+        assert!((x as u64) < 256, 4);
+    }
+
+    #[random_test]
+    #[expected_failure(abort_code = 5)]
+    fun expected_to_abort(x: u64) {
+        assert!(x > x, 5)
+    }
+}